@@ -0,0 +1,69 @@
+//! Times the MIDI list and netsync flows in both directions, for every event type
+//! the master flow sends.
+//!
+//! Run with `cargo bench --bench flows`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rtp_midi_netsync::midi::{
+    build_midi_list, parse_midi_list, MidiEvent, MmcCommand, RealtimeMessage,
+};
+use rtp_midi_netsync::netsync::{master_netsync_flow, slave_netsync_flow};
+
+fn events() -> Vec<(&'static str, MidiEvent)> {
+    vec![
+        (
+            "quarter_frame",
+            MidiEvent::MtcQuarter {
+                msg_type: 3,
+                value: 7,
+            },
+        ),
+        (
+            "full_frame",
+            MidiEvent::MtcFull {
+                hour: 1,
+                minute: 2,
+                second: 3,
+                frame: 4,
+            },
+        ),
+        ("play", MidiEvent::Mmc(MmcCommand::Play)),
+        (
+            "locate",
+            MidiEvent::Mmc(MmcCommand::Locate {
+                hour: 1,
+                minute: 2,
+                second: 3,
+                frame: 4,
+                subframe: 0,
+            }),
+        ),
+        ("clock", MidiEvent::Realtime(RealtimeMessage::Clock)),
+        ("song_position", MidiEvent::SongPosition(96)),
+    ]
+}
+
+fn bench_flows(c: &mut Criterion) {
+    for (name, event) in events() {
+        let midi = build_midi_list(&event);
+        let payload = master_netsync_flow(&event).unwrap();
+
+        let mut group = c.benchmark_group(name);
+        group.bench_function("build_midi_list", |b| {
+            b.iter(|| build_midi_list(black_box(&event)))
+        });
+        group.bench_function("parse_midi_list", |b| {
+            b.iter(|| parse_midi_list(black_box(&midi), midi.len()))
+        });
+        group.bench_function("master_netsync_flow", |b| {
+            b.iter(|| master_netsync_flow(black_box(&event)))
+        });
+        group.bench_function("slave_netsync_flow", |b| {
+            b.iter(|| slave_netsync_flow(black_box(&payload)))
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_flows);
+criterion_main!(benches);
@@ -0,0 +1,99 @@
+//! Compares `slave_netsync_flow` with the scratch-buffer `slave_netsync_flow_buf`,
+//! both in time and in heap allocations per decoded payload.
+//!
+//! Run with `cargo bench --bench slave_flow_alloc`. Allocation counts are printed
+//! before the timing results.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rtp_midi_netsync::midi::MidiEvent;
+use rtp_midi_netsync::netsync::{master_netsync_flow, slave_netsync_flow, slave_netsync_flow_buf};
+
+/// System allocator counting every allocation it serves
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Payloads typical of a pass-through heavy session
+fn payloads() -> Vec<(&'static str, Vec<u8>)> {
+    vec![
+        ("note_on", vec![0x03, 0x90, 0x3C, 0x7F]),
+        ("control_change", vec![0x03, 0xB0, 0x07, 0x64]),
+        (
+            "quarter_frame",
+            master_netsync_flow(&MidiEvent::MtcQuarter {
+                msg_type: 3,
+                value: 7,
+            })
+            .unwrap(),
+        ),
+    ]
+}
+
+/// Average number of allocations per call of `f` over `iterations` calls
+fn allocations_per_call(iterations: usize, mut f: impl FnMut()) -> f64 {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    for _ in 0..iterations {
+        f();
+    }
+    (ALLOCATIONS.load(Ordering::Relaxed) - before) as f64 / iterations as f64
+}
+
+fn report_allocations() {
+    const ITERATIONS: usize = 10_000;
+    for (name, payload) in payloads() {
+        let owned = allocations_per_call(ITERATIONS, || {
+            black_box(slave_netsync_flow(black_box(&payload)).unwrap());
+        });
+        let mut scratch = Vec::new();
+        let borrowed = allocations_per_call(ITERATIONS, || {
+            black_box(slave_netsync_flow_buf(black_box(&payload), &mut scratch).unwrap());
+        });
+        println!(
+            "{}: slave_netsync_flow {:.2} allocs/call, slave_netsync_flow_buf {:.4} allocs/call",
+            name, owned, borrowed
+        );
+    }
+}
+
+fn bench_slave_flow(c: &mut Criterion) {
+    report_allocations();
+
+    for (name, payload) in payloads() {
+        let mut group = c.benchmark_group(name);
+        group.bench_function("slave_netsync_flow", |b| {
+            b.iter(|| slave_netsync_flow(black_box(&payload)))
+        });
+        let mut scratch = Vec::new();
+        group.bench_function("slave_netsync_flow_buf", |b| {
+            b.iter(|| {
+                slave_netsync_flow_buf(black_box(&payload), &mut scratch).map(|event| event.kind())
+            })
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_slave_flow);
+criterion_main!(benches);
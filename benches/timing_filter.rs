@@ -0,0 +1,56 @@
+//! Compares the `is_timing_event` filter with a full slave flow parse.
+//!
+//! Run with `cargo bench --bench timing_filter`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rtp_midi_netsync::midi::{MidiEvent, MmcCommand};
+use rtp_midi_netsync::netsync::{is_timing_event, master_netsync_flow, slave_netsync_flow};
+
+fn payloads() -> Vec<(&'static str, Vec<u8>)> {
+    let sync = |event: MidiEvent| master_netsync_flow(&event).unwrap();
+    vec![
+        (
+            "quarter_frame",
+            sync(MidiEvent::MtcQuarter {
+                msg_type: 3,
+                value: 7,
+            }),
+        ),
+        (
+            "full_frame",
+            sync(MidiEvent::MtcFull {
+                hour: 1,
+                minute: 2,
+                second: 3,
+                frame: 4,
+            }),
+        ),
+        (
+            "locate",
+            sync(MidiEvent::Mmc(MmcCommand::Locate {
+                hour: 1,
+                minute: 2,
+                second: 3,
+                frame: 4,
+                subframe: 0,
+            })),
+        ),
+        ("note_on", vec![0x03, 0x90, 0x3C, 0x7F]),
+    ]
+}
+
+fn bench_timing_filter(c: &mut Criterion) {
+    for (name, payload) in payloads() {
+        let mut group = c.benchmark_group(name);
+        group.bench_function("is_timing_event", |b| {
+            b.iter(|| is_timing_event(black_box(&payload)))
+        });
+        group.bench_function("slave_netsync_flow", |b| {
+            b.iter(|| slave_netsync_flow(black_box(&payload)))
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_timing_filter);
+criterion_main!(benches);
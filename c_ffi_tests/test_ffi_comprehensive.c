@@ -67,9 +67,22 @@ int test_vlc_rtpmidi_get_max_payload_size()
     TEST_START("vlc_rtpmidi_get_max_payload_size");
 
     size_t max_size = vlc_rtpmidi_get_max_payload_size();
-    ASSERT_TRUE(max_size >= 16, "Max payload size should be at least 16 bytes");
+    ASSERT_TRUE(max_size >= 14, "Max payload size should fit MMC Locate (14 bytes)");
     ASSERT_TRUE(max_size <= 64, "Max payload size should be reasonable (<=64 bytes)");
 
+    // Exact sizes per event, the largest being the maximum
+    VlcRtpmidiEvent locate = vlc_rtpmidi_create_mmc_locate_event(1, 2, 3, 4);
+    VlcRtpmidiEvent quarter = vlc_rtpmidi_create_mtc_quarter_event(0, 5);
+    size_t size;
+    ASSERT_TRUE(vlc_rtpmidi_get_payload_size_for_event(&locate, &size) == VLC_RTPMIDI_ERROR_SUCCESS,
+                "Locate size query should succeed");
+    ASSERT_TRUE(size == max_size, "MMC Locate should have the largest payload");
+    ASSERT_TRUE(vlc_rtpmidi_get_payload_size_for_event(&quarter, &size) == VLC_RTPMIDI_ERROR_SUCCESS,
+                "Quarter frame size query should succeed");
+    ASSERT_TRUE(size == 3, "Quarter frame payload should be 3 bytes");
+    ASSERT_TRUE(vlc_rtpmidi_get_payload_size_for_event(NULL, &size) == VLC_RTPMIDI_ERROR_NULL_POINTER,
+                "Null event should fail");
+
     TEST_PASS();
     return 1;
 }
@@ -94,6 +107,84 @@ int test_vlc_rtpmidi_get_error_message()
     return 1;
 }
 
+// Test: Error and event type names for every value the library produces
+int test_vlc_rtpmidi_name_strings()
+{
+    TEST_START("vlc_rtpmidi_name_strings");
+
+    for (int code = VLC_RTPMIDI_ERROR_SUCCESS; code <= VLC_RTPMIDI_ERROR_SESSION_FAILED; code++)
+    {
+        ASSERT_TRUE(strcmp(vlc_rtpmidi_get_error_message(code), "Unknown error") != 0,
+                    "Every error code should have a message");
+    }
+
+    for (int type = VLC_RTPMIDI_EVENT_MTC_QUARTER; type <= VLC_RTPMIDI_EVENT_MMC_VARIABLE_PLAY; type++)
+    {
+        ASSERT_TRUE(strcmp(vlc_rtpmidi_get_event_type_name(type), "UNKNOWN") != 0,
+                    "Every event type should have a name");
+    }
+    ASSERT_TRUE(strcmp(vlc_rtpmidi_get_event_type_name(VLC_RTPMIDI_EVENT_MMC_LOCATE), "MMC_LOCATE") == 0,
+                "Locate name");
+    ASSERT_TRUE(strcmp(vlc_rtpmidi_get_event_type_name(-1), "UNKNOWN") == 0, "Negative type");
+    ASSERT_TRUE(strcmp(vlc_rtpmidi_get_event_type_name(VLC_RTPMIDI_EVENT_MMC_VARIABLE_PLAY + 1), "UNKNOWN") == 0,
+                "Type past the last one");
+
+    TEST_PASS();
+    return 1;
+}
+
+// Test: Payload description into a caller buffer
+int test_vlc_rtpmidi_describe_payload()
+{
+    TEST_START("vlc_rtpmidi_describe_payload");
+
+    uint8_t payload[] = {0x02, 0xF1, 0x25};
+    char text[128];
+    size_t text_len = 0;
+    ASSERT_EQ(vlc_rtpmidi_describe_payload_ffi(payload, sizeof(payload), text, sizeof(text), &text_len),
+              VLC_RTPMIDI_ERROR_SUCCESS, "Description should fit");
+    ASSERT_TRUE(strncmp(text, "Header 0x02: LEN=2\nMTC Quarter Frame", 36) == 0, "Header and command lines");
+    ASSERT_TRUE(strlen(text) == text_len, "Length should match the text");
+
+    char small[12];
+    ASSERT_EQ(vlc_rtpmidi_describe_payload_ffi(payload, sizeof(payload), small, sizeof(small), &text_len),
+              VLC_RTPMIDI_ERROR_BUFFER_TOO_SMALL, "Small buffer should be reported");
+    ASSERT_TRUE(strcmp(small, "Header 0...") == 0, "Truncated text should end with an ellipsis");
+    ASSERT_TRUE(text_len == strlen(text), "Full length should still be reported");
+
+    TEST_PASS();
+    return 1;
+}
+
+// Test: SMPTE text formatting and parsing
+int test_vlc_rtpmidi_smpte_strings()
+{
+    TEST_START("vlc_rtpmidi_smpte_strings");
+
+    VlcRtpmidiSmpteTime tc = {.hours = 1, .minutes = 2, .seconds = 3, .frames = 4};
+    char text[VLC_RTPMIDI_SMPTE_STRING_SIZE];
+    ASSERT_EQ(vlc_rtpmidi_smpte_to_string_ffi(&tc, VLC_RTPMIDI_FRAME_RATE_30_NDF, text, sizeof(text)),
+              VLC_RTPMIDI_ERROR_SUCCESS, "Timecode should format");
+    ASSERT_TRUE(strcmp(text, "01:02:03:04") == 0, "Non-drop timecode uses colons");
+    ASSERT_EQ(vlc_rtpmidi_smpte_to_string_ffi(&tc, VLC_RTPMIDI_FRAME_RATE_2997_DF, text, sizeof(text)),
+              VLC_RTPMIDI_ERROR_SUCCESS, "Drop-frame timecode should format");
+    ASSERT_TRUE(strcmp(text, "01:02:03;04") == 0, "Drop-frame timecode uses a semicolon");
+    ASSERT_EQ(vlc_rtpmidi_smpte_to_string_ffi(&tc, VLC_RTPMIDI_FRAME_RATE_30_NDF, text, sizeof(text) - 1),
+              VLC_RTPMIDI_ERROR_BUFFER_TOO_SMALL, "Terminator needs room");
+
+    VlcRtpmidiSmpteTime parsed;
+    ASSERT_EQ(vlc_rtpmidi_smpte_from_string_ffi("23:59:59;24", VLC_RTPMIDI_FRAME_RATE_25, &parsed),
+              VLC_RTPMIDI_ERROR_SUCCESS, "Last frame at 25fps should parse");
+    ASSERT_TRUE(parsed.hours == 23 && parsed.frames == 24, "Parsed fields");
+    ASSERT_EQ(vlc_rtpmidi_smpte_from_string_ffi("23:59:59:25", VLC_RTPMIDI_FRAME_RATE_25, &parsed),
+              VLC_RTPMIDI_ERROR_INVALID_TIMECODE, "Frame past the rate");
+    ASSERT_EQ(vlc_rtpmidi_smpte_from_string_ffi("1h2m", VLC_RTPMIDI_FRAME_RATE_25, &parsed),
+              VLC_RTPMIDI_ERROR_MALFORMED_TIMECODE, "Text not in either form");
+
+    TEST_PASS();
+    return 1;
+}
+
 // Test: Helper function for creating MTC Quarter events
 int test_vlc_rtpmidi_create_mtc_quarter_event()
 {
@@ -152,6 +243,14 @@ int test_create_mmc_events()
     ASSERT_EQ(locate_event.data[2], 30, "Locate second should be 30");
     ASSERT_EQ(locate_event.data[3], 10, "Locate frame should be 10");
 
+    // Test MMC Locate with subframe, clamped to 99
+    VlcRtpmidiEvent locate_sf_event = vlc_rtpmidi_create_mmc_locate_event_sf(2, 15, 30, 10, 42);
+    ASSERT_EQ(locate_sf_event.event_type, VLC_RTPMIDI_EVENT_MMC_LOCATE, "Locate subframe event type");
+    ASSERT_EQ(locate_sf_event.data_len, 5, "Locate subframe event data length should be 5");
+    ASSERT_EQ(locate_sf_event.data[4], 42, "Locate subframe should be 42");
+    locate_sf_event = vlc_rtpmidi_create_mmc_locate_event_sf(2, 15, 30, 10, 200);
+    ASSERT_EQ(locate_sf_event.data[4], 99, "Locate subframe should be clamped to 99");
+
     TEST_PASS();
     return 1;
 }
@@ -299,6 +398,13 @@ int test_slave_flow_empty_buffer()
 
     int result = vlc_rtpmidi_slave_netsync_flow_ffi(empty_buffer, 0, &event); // Zero length
     ASSERT_EQ(result, VLC_RTPMIDI_ERROR_INVALID_SLAVE_EVENT, "Should return INVALID_SLAVE_EVENT for empty buffer");
+    ASSERT_TRUE(strncmp(vlc_rtpmidi_get_last_error_detail(), "slave flow: payload rejected: ", 30) == 0,
+                "Detail should say why the buffer was rejected");
+
+    uint8_t valid[] = {0x02, 0xF1, 0x25};
+    ASSERT_EQ(vlc_rtpmidi_slave_netsync_flow_ffi(valid, sizeof(valid), &event), VLC_RTPMIDI_ERROR_SUCCESS,
+              "Valid payload should parse");
+    ASSERT_TRUE(vlc_rtpmidi_get_last_error_detail()[0] == '\0', "Success should clear the detail");
 
     TEST_PASS();
     return 1;
@@ -433,6 +539,30 @@ int test_invalid_event_data()
     return 1;
 }
 
+// Test: Validation of hand-built events
+int test_validate_event()
+{
+    TEST_START("validate_event");
+
+    VlcRtpmidiEvent full = {.event_type = VLC_RTPMIDI_EVENT_MTC_FULL, .data = {1, 2, 3, 4}, .data_len = 4};
+    ASSERT_EQ(vlc_rtpmidi_validate_event_ffi(&full), VLC_RTPMIDI_ERROR_SUCCESS, "Valid Full Frame");
+
+    full.data[0] = 24;
+    ASSERT_EQ(vlc_rtpmidi_validate_event_ffi(&full), VLC_RTPMIDI_ERROR_INVALID_TIMECODE, "Hours past 23");
+
+    VlcRtpmidiEvent quarter = {.event_type = VLC_RTPMIDI_EVENT_MTC_QUARTER, .data = {8, 0}, .data_len = 2};
+    ASSERT_EQ(vlc_rtpmidi_validate_event_ffi(&quarter), VLC_RTPMIDI_ERROR_INVALID_TIMECODE, "Piece past 7");
+
+    quarter.data_len = 1;
+    ASSERT_EQ(vlc_rtpmidi_validate_event_ffi(&quarter), VLC_RTPMIDI_ERROR_INVALID_EVENT_TYPE, "Wrong data_len");
+
+    VlcRtpmidiEvent unknown = {.event_type = 99, .data_len = 0};
+    ASSERT_EQ(vlc_rtpmidi_validate_event_ffi(&unknown), VLC_RTPMIDI_ERROR_INVALID_EVENT_TYPE, "Unknown type");
+
+    TEST_PASS();
+    return 1;
+}
+
 // Test: Realistic usage scenario
 int test_realistic_scenario()
 {
@@ -510,6 +640,9 @@ int main()
     int success = 1;
     success &= test_vlc_rtpmidi_get_max_payload_size();
     success &= test_vlc_rtpmidi_get_error_message();
+    success &= test_vlc_rtpmidi_name_strings();
+    success &= test_vlc_rtpmidi_describe_payload();
+    success &= test_vlc_rtpmidi_smpte_strings();
     success &= test_vlc_rtpmidi_create_mtc_quarter_event();
     success &= test_vlc_rtpmidi_create_mtc_full_event();
     success &= test_create_mmc_events();
@@ -524,6 +657,7 @@ int main()
     success &= test_roundtrip_mtc_full();
     success &= test_roundtrip_mmc_commands();
     success &= test_invalid_event_data();
+    success &= test_validate_event();
     success &= test_realistic_scenario();
 
     // Print results
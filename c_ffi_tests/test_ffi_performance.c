@@ -87,6 +87,75 @@ static void test_encoding_performance(void)
     printf("\n");
 }
 
+/**
+ * Performance test for encoding a quarter-frame cycle, one call per payload
+ * against a single batch call
+ */
+static void test_batch_encoding_performance(void)
+{
+    printf("Batch Encoding Performance Tests\n");
+    printf("================================\n");
+
+    const int iterations = 100000;
+    VlcRtpmidiEvent cycle[8];
+    uint8_t buffer[8 * 3];
+    size_t offsets[8];
+    size_t actual_size;
+    struct timespec start, end;
+
+    for (uint8_t piece = 0; piece < 8; piece++)
+    {
+        cycle[piece] = vlc_rtpmidi_create_mtc_quarter_event(piece, piece);
+    }
+
+    // One call per quarter frame, packing the payloads by hand
+    clock_gettime(CLOCK_MONOTONIC, &start);
+
+    for (int i = 0; i < iterations; i++)
+    {
+        size_t offset = 0;
+        for (int piece = 0; piece < 8; piece++)
+        {
+            int result = vlc_rtpmidi_master_netsync_flow_ffi(&cycle[piece], buffer + offset,
+                                                             sizeof(buffer) - offset, &actual_size);
+            if (result != VLC_RTPMIDI_ERROR_SUCCESS)
+            {
+                printf("  ERROR: Quarter frame encoding failed at iteration %d\n", i);
+                return;
+            }
+            offsets[piece] = offset;
+            offset += actual_size;
+        }
+    }
+
+    clock_gettime(CLOCK_MONOTONIC, &end);
+    double single_time = get_time_diff(start, end);
+
+    // One batch call per cycle
+    clock_gettime(CLOCK_MONOTONIC, &start);
+
+    for (int i = 0; i < iterations; i++)
+    {
+        int result = vlc_rtpmidi_master_netsync_flow_batch_ffi(cycle, 8, buffer, sizeof(buffer),
+                                                               offsets, &actual_size);
+        if (result != VLC_RTPMIDI_ERROR_SUCCESS)
+        {
+            printf("  ERROR: Batch encoding failed at iteration %d\n", i);
+            return;
+        }
+    }
+
+    clock_gettime(CLOCK_MONOTONIC, &end);
+    double batch_time = get_time_diff(start, end);
+
+    // Report results
+    printf("  8 single calls: %d cycles in %.3f seconds (%.0f ns/cycle)\n",
+           iterations, single_time, single_time * 1e9 / iterations);
+    printf("  1 batch call:   %d cycles in %.3f seconds (%.0f ns/cycle)\n",
+           iterations, batch_time, batch_time * 1e9 / iterations);
+    printf("\n");
+}
+
 /**
  * Performance test for decoding operations
  */
@@ -236,6 +305,7 @@ int main(void)
     printf("==========================================\n\n");
 
     test_encoding_performance();
+    test_batch_encoding_performance();
     test_decoding_performance();
     test_mixed_operations();
     test_memory_usage();
@@ -262,14 +262,25 @@ int test_malformed_payloads()
 
     // Test single byte payloads
     uint8_t single_byte_payloads[][1] = {
-        {0x00}, {0xFF}, {0x80}, {0x7F}, {0x01}, {0xFE}};
+        {0xFF}, {0x80}, {0x7F}, {0x01}, {0xFE}};
 
     for (size_t i = 0; i < sizeof(single_byte_payloads) / sizeof(single_byte_payloads[0]); i++)
     {
         int result = vlc_rtpmidi_slave_netsync_flow_ffi(single_byte_payloads[i], 1, &decoded_event);
-        EXPECT_ERROR(result, VLC_RTPMIDI_ERROR_INVALID_SLAVE_EVENT, "Single byte payload should fail");
+        EXPECT_ERROR(result, VLC_RTPMIDI_ERROR_TRUNCATED_PAYLOAD, "Single byte payload should fail");
     }
 
+    // Test payloads without MIDI commands (LEN=0): valid, but no event
+    uint8_t keepalive[] = {0x00};
+    EXPECT_ERROR(vlc_rtpmidi_slave_netsync_flow_ffi(keepalive, sizeof(keepalive), &decoded_event),
+                 VLC_RTPMIDI_ERROR_NO_EVENT, "Keepalive payload");
+    uint8_t journal_only[] = {0x40, 0x01, 0x00, 0x2A};
+    EXPECT_ERROR(vlc_rtpmidi_slave_netsync_flow_ffi(journal_only, sizeof(journal_only), &decoded_event),
+                 VLC_RTPMIDI_ERROR_NO_EVENT, "Journal-only payload");
+    uint8_t truncated_journal[] = {0x40, 0x01};
+    EXPECT_ERROR(vlc_rtpmidi_slave_netsync_flow_ffi(truncated_journal, sizeof(truncated_journal), &decoded_event),
+                 VLC_RTPMIDI_ERROR_HEADER_MISMATCH, "Truncated journal");
+
     // Test payload with invalid header
     uint8_t invalid_headers[] = {
         0x00, 0x01, // Length 0 or 1 (too short)
@@ -277,22 +288,26 @@ int test_malformed_payloads()
         0xF0, 0x80, // Invalid high nibble
         0x80, 0x90, // High bit set (invalid MIDI)
     };
+    // LEN=0 followed by bytes, then LEN beyond the bytes present
+    int invalid_header_errors[] = {
+        VLC_RTPMIDI_ERROR_HEADER_MISMATCH, VLC_RTPMIDI_ERROR_TRUNCATED_PAYLOAD,
+        VLC_RTPMIDI_ERROR_TRUNCATED_PAYLOAD, VLC_RTPMIDI_ERROR_TRUNCATED_PAYLOAD};
 
     for (size_t i = 0; i < sizeof(invalid_headers) / 2; i++)
     {
         int result = vlc_rtpmidi_slave_netsync_flow_ffi(&invalid_headers[i * 2], 2, &decoded_event);
-        EXPECT_ERROR(result, VLC_RTPMIDI_ERROR_INVALID_SLAVE_EVENT, "Invalid header should fail");
+        EXPECT_ERROR(result, invalid_header_errors[i], "Invalid header should fail");
     }
 
     // Test payload with correct length field but insufficient data
     uint8_t short_payload[] = {0x08, 0xF0}; // Claims 8 bytes but only has 2
     EXPECT_ERROR(vlc_rtpmidi_slave_netsync_flow_ffi(short_payload, sizeof(short_payload), &decoded_event),
-                 VLC_RTPMIDI_ERROR_INVALID_SLAVE_EVENT, "Short payload should fail");
+                 VLC_RTPMIDI_ERROR_TRUNCATED_PAYLOAD, "Short payload should fail");
 
     // Test payload with random garbage
     uint8_t garbage[] = {0xDE, 0xAD, 0xBE, 0xEF, 0xCA, 0xFE, 0xBA, 0xBE};
     EXPECT_ERROR(vlc_rtpmidi_slave_netsync_flow_ffi(garbage, sizeof(garbage), &decoded_event),
-                 VLC_RTPMIDI_ERROR_INVALID_SLAVE_EVENT, "Garbage payload should fail");
+                 VLC_RTPMIDI_ERROR_TRUNCATED_PAYLOAD, "Garbage payload should fail");
 
     TEST_PASS();
     return 1;
@@ -340,7 +355,7 @@ int test_memory_boundaries()
     if (actual_size > 0)
     {
         result = vlc_rtpmidi_slave_netsync_flow_ffi(temp_buffer, actual_size - 1, &decoded);
-        EXPECT_ERROR(result, VLC_RTPMIDI_ERROR_INVALID_SLAVE_EVENT, "One byte short payload should fail");
+        EXPECT_ERROR(result, VLC_RTPMIDI_ERROR_TRUNCATED_PAYLOAD, "One byte short payload should fail");
     }
 
     TEST_PASS();
@@ -1386,6 +1401,781 @@ int test_performance_regression()
     return 1;
 }
 
+// Test: Parsing datagrams batching several payloads
+int test_multi_event_payloads()
+{
+    TEST_START("multi_event_payloads");
+
+    // Play, Full Frame, Quarter Frame, built by the master flow back to back
+    VlcRtpmidiEvent sent[3] = {
+        vlc_rtpmidi_create_mmc_play_event(),
+        vlc_rtpmidi_create_mtc_full_event(1, 2, 3, 4),
+        vlc_rtpmidi_create_mtc_quarter_event(5, 6)};
+    uint8_t datagram[64];
+    size_t datagram_len = 0;
+    size_t first_len = 0;
+    for (size_t i = 0; i < 3; i++)
+    {
+        size_t actual_size;
+        EXPECT_SUCCESS(vlc_rtpmidi_master_netsync_flow_ffi(&sent[i], datagram + datagram_len,
+                                                           sizeof(datagram) - datagram_len, &actual_size),
+                       "Encode payload");
+        if (i == 0)
+            first_len = actual_size;
+        datagram_len += actual_size;
+    }
+
+    // Enough room: every event in order
+    VlcRtpmidiEvent events[4];
+    size_t count = 0;
+    EXPECT_SUCCESS(vlc_rtpmidi_slave_netsync_flow_multi_ffi(datagram, datagram_len, events, 4, &count),
+                   "Parse all payloads");
+    if (count != 3)
+        TEST_FAIL("Expected three events");
+    for (size_t i = 0; i < 3; i++)
+    {
+        if (events[i].event_type != sent[i].event_type || events[i].data_len != sent[i].data_len ||
+            memcmp(events[i].data, sent[i].data, sent[i].data_len) != 0)
+            TEST_FAIL("Event mismatch");
+    }
+
+    // Too little room: required count reported, the first events still written
+    memset(events, 0, sizeof(events));
+    count = 0;
+    EXPECT_ERROR(vlc_rtpmidi_slave_netsync_flow_multi_ffi(datagram, datagram_len, events, 2, &count),
+                 VLC_RTPMIDI_ERROR_BUFFER_TOO_SMALL, "Array too small");
+    if (count != 3)
+        TEST_FAIL("Required count not reported");
+    if (events[0].event_type != VLC_RTPMIDI_EVENT_MMC_PLAY || events[1].event_type != VLC_RTPMIDI_EVENT_MTC_FULL)
+        TEST_FAIL("Leading events not written");
+
+    // Count query without an array
+    count = 0;
+    EXPECT_ERROR(vlc_rtpmidi_slave_netsync_flow_multi_ffi(datagram, datagram_len, NULL, 0, &count),
+                 VLC_RTPMIDI_ERROR_BUFFER_TOO_SMALL, "Count query");
+    if (count != 3)
+        TEST_FAIL("Count query returned wrong count");
+
+    // A single payload behaves like the single-event flow
+    EXPECT_SUCCESS(vlc_rtpmidi_slave_netsync_flow_multi_ffi(datagram, first_len, events, 4, &count),
+                   "Single payload");
+    if (count != 1 || events[0].event_type != VLC_RTPMIDI_EVENT_MMC_PLAY)
+        TEST_FAIL("Single payload mismatch");
+
+    // Truncated final payload and empty buffer fail
+    EXPECT_ERROR(vlc_rtpmidi_slave_netsync_flow_multi_ffi(datagram, datagram_len - 1, events, 4, &count),
+                 VLC_RTPMIDI_ERROR_TRUNCATED_PAYLOAD, "Truncated final payload");
+    EXPECT_ERROR(vlc_rtpmidi_slave_netsync_flow_multi_ffi(datagram, 0, events, 4, &count),
+                 VLC_RTPMIDI_ERROR_INVALID_SLAVE_EVENT, "Empty buffer");
+
+    // Null pointers
+    EXPECT_ERROR(vlc_rtpmidi_slave_netsync_flow_multi_ffi(NULL, datagram_len, events, 4, &count),
+                 VLC_RTPMIDI_ERROR_NULL_POINTER, "Null buffer");
+    EXPECT_ERROR(vlc_rtpmidi_slave_netsync_flow_multi_ffi(datagram, datagram_len, NULL, 4, &count),
+                 VLC_RTPMIDI_ERROR_NULL_POINTER, "Null array with capacity");
+    EXPECT_ERROR(vlc_rtpmidi_slave_netsync_flow_multi_ffi(datagram, datagram_len, events, 4, NULL),
+                 VLC_RTPMIDI_ERROR_NULL_POINTER, "Null count");
+
+    TEST_PASS();
+    return 1;
+}
+
+// Test: Master session handle
+int test_master_session_handle()
+{
+    TEST_START("master_session_handle");
+
+    if (vlc_rtpmidi_master_session_new(NULL) != NULL)
+        TEST_FAIL("Null config should fail");
+
+    VlcRtpmidiNetsyncConfig config = vlc_rtpmidi_default_netsync_config();
+    config.ssrc = 0xCAFEBABE;
+    VlcRtpmidiMasterSession *master = vlc_rtpmidi_master_session_new(&config);
+    if (master == NULL)
+        TEST_FAIL("Session creation failed");
+
+    // Sequence numbers increase per packet, and a rejected event does not use one
+    uint8_t packet[64];
+    size_t packet_size;
+    VlcRtpmidiEvent locate = vlc_rtpmidi_create_mmc_locate_event(0, 1, 0, 0);
+    VlcRtpmidiEvent play = vlc_rtpmidi_create_mmc_play_event();
+    EXPECT_ERROR(vlc_rtpmidi_master_session_packet_for_event(master, &locate, 0, packet, 4, &packet_size),
+                 VLC_RTPMIDI_ERROR_BUFFER_TOO_SMALL, "Small packet buffer");
+    EXPECT_SUCCESS(vlc_rtpmidi_master_session_packet_for_event(master, &locate, 0, packet, sizeof(packet), &packet_size),
+                   "Locate packet");
+    if (packet[2] != 0 || packet[3] != 0)
+        TEST_FAIL("First packet should have sequence number 0");
+    if (packet[8] != 0xCA || packet[9] != 0xFE || packet[10] != 0xBA || packet[11] != 0xBE)
+        TEST_FAIL("SSRC not stamped");
+    EXPECT_SUCCESS(vlc_rtpmidi_master_session_packet_for_event(master, &play, 1000, packet, sizeof(packet), &packet_size),
+                   "Play packet");
+    if (packet[3] != 1)
+        TEST_FAIL("Second packet should have sequence number 1");
+
+    // The payload after the RTP header decodes with the slave flow
+    VlcRtpmidiEvent decoded;
+    EXPECT_SUCCESS(vlc_rtpmidi_slave_netsync_flow_ffi(packet + 12, packet_size - 12, &decoded), "Decode payload");
+    if (decoded.event_type != VLC_RTPMIDI_EVENT_MMC_PLAY)
+        TEST_FAIL("Payload should be MMC Play");
+
+    // The Locate makes a Full Frame and Play due
+    uint8_t packets[128];
+    size_t sizes[VLC_RTPMIDI_MAX_TICK_PACKETS];
+    size_t count;
+    EXPECT_ERROR(vlc_rtpmidi_master_session_tick(master, 2000, packets, 16, sizes, VLC_RTPMIDI_MAX_TICK_PACKETS, &count),
+                 VLC_RTPMIDI_ERROR_BUFFER_TOO_SMALL, "Small tick buffer");
+    EXPECT_SUCCESS(vlc_rtpmidi_master_session_tick(master, 2000, packets, sizeof(packets), sizes,
+                                                   VLC_RTPMIDI_MAX_TICK_PACKETS, &count),
+                   "Tick");
+    if (count != 2)
+        TEST_FAIL("Expected Full Frame and Play");
+    EXPECT_SUCCESS(vlc_rtpmidi_slave_netsync_flow_ffi(packets + 12, sizes[0] - 12, &decoded), "Decode Full Frame");
+    if (decoded.event_type != VLC_RTPMIDI_EVENT_MTC_FULL)
+        TEST_FAIL("First tick packet should be a Full Frame");
+    if (packets[sizes[0] + 3] != 3)
+        TEST_FAIL("Tick packets should continue the sequence");
+
+    // Invalid handles and pointers
+    EXPECT_ERROR(vlc_rtpmidi_master_session_packet_for_event(NULL, &play, 0, packet, sizeof(packet), &packet_size),
+                 VLC_RTPMIDI_ERROR_NULL_POINTER, "Null handle");
+    EXPECT_ERROR(vlc_rtpmidi_master_session_packet_for_event(master, NULL, 0, packet, sizeof(packet), &packet_size),
+                 VLC_RTPMIDI_ERROR_NULL_POINTER, "Null event");
+    EXPECT_ERROR(vlc_rtpmidi_master_session_tick(NULL, 0, packets, sizeof(packets), sizes, 2, &count),
+                 VLC_RTPMIDI_ERROR_NULL_POINTER, "Null handle tick");
+
+    // Rapid create/destroy cycles
+    for (int i = 0; i < 1000; i++)
+    {
+        VlcRtpmidiMasterSession *temp = vlc_rtpmidi_master_session_new(&config);
+        if (temp == NULL)
+            TEST_FAIL("Session creation failed in loop");
+        vlc_rtpmidi_master_session_free(temp);
+    }
+    vlc_rtpmidi_master_session_free(NULL);
+    vlc_rtpmidi_master_session_free(master);
+
+    TEST_PASS();
+    return 1;
+}
+
+int test_slave_session_handle()
+{
+    TEST_START("slave_session_handle");
+
+    if (vlc_rtpmidi_slave_session_new(NULL) != NULL)
+        TEST_FAIL("Null config should fail");
+
+    VlcRtpmidiNetsyncConfig config = vlc_rtpmidi_default_netsync_config();
+    VlcRtpmidiMasterSession *master = vlc_rtpmidi_master_session_new(&config);
+    VlcRtpmidiSlaveSession *slave = vlc_rtpmidi_slave_session_new(&config);
+    if (master == NULL || slave == NULL)
+        TEST_FAIL("Session creation failed");
+
+    uint8_t locate_packet[64];
+    uint8_t play_packet[64];
+    size_t locate_size, play_size;
+    VlcRtpmidiEvent locate = vlc_rtpmidi_create_mmc_locate_event(0, 1, 0, 0);
+    VlcRtpmidiEvent play = vlc_rtpmidi_create_mmc_play_event();
+    EXPECT_SUCCESS(vlc_rtpmidi_master_session_packet_for_event(master, &locate, 0, locate_packet,
+                                                               sizeof(locate_packet), &locate_size),
+                   "Locate packet");
+    EXPECT_SUCCESS(vlc_rtpmidi_master_session_packet_for_event(master, &play, 0, play_packet,
+                                                               sizeof(play_packet), &play_size),
+                   "Play packet");
+
+    // Accepted, repeated and late packets are told apart by the return code
+    EXPECT_SUCCESS(vlc_rtpmidi_slave_session_feed(slave, locate_packet, locate_size, 0), "Feed Locate");
+    EXPECT_SUCCESS(vlc_rtpmidi_slave_session_feed(slave, play_packet, play_size, 0), "Feed Play");
+    EXPECT_ERROR(vlc_rtpmidi_slave_session_feed(slave, play_packet, play_size, 0),
+                 VLC_RTPMIDI_ERROR_DUPLICATE, "Duplicate packet");
+    EXPECT_ERROR(vlc_rtpmidi_slave_session_feed(slave, locate_packet, locate_size, 0),
+                 VLC_RTPMIDI_ERROR_OUT_OF_ORDER, "Late packet");
+    play_packet[11] ^= 0xFF;
+    EXPECT_ERROR(vlc_rtpmidi_slave_session_feed(slave, play_packet, play_size, 0),
+                 VLC_RTPMIDI_ERROR_FOREIGN_SSRC, "Other sender");
+
+    // Events come out in arrival order
+    VlcRtpmidiEvent event;
+    EXPECT_SUCCESS(vlc_rtpmidi_slave_session_poll_event(slave, &event), "Poll Locate");
+    if (event.event_type != VLC_RTPMIDI_EVENT_MMC_LOCATE)
+        TEST_FAIL("First event should be MMC Locate");
+    EXPECT_SUCCESS(vlc_rtpmidi_slave_session_poll_event(slave, &event), "Poll Play");
+    if (event.event_type != VLC_RTPMIDI_EVENT_MMC_PLAY)
+        TEST_FAIL("Second event should be MMC Play");
+    EXPECT_ERROR(vlc_rtpmidi_slave_session_poll_event(slave, &event), VLC_RTPMIDI_ERROR_NO_EVENT, "Empty queue");
+
+    VlcRtpmidiNetsyncMetrics metrics;
+    EXPECT_SUCCESS(vlc_rtpmidi_slave_session_metrics(slave, &metrics), "Metrics");
+    if (metrics.packets_parsed != 2 || metrics.duplicates_dropped != 1 || metrics.out_of_order_dropped != 1 ||
+        metrics.foreign_ssrc_dropped != 1)
+        TEST_FAIL("Unexpected counters");
+    EXPECT_SUCCESS(vlc_rtpmidi_slave_session_reset_metrics(slave), "Reset slave metrics");
+    EXPECT_SUCCESS(vlc_rtpmidi_slave_session_metrics(slave, &metrics), "Metrics after reset");
+    if (metrics.packets_parsed != 0 || metrics.duplicates_dropped != 0)
+        TEST_FAIL("Counters should be cleared");
+    EXPECT_SUCCESS(vlc_rtpmidi_master_session_metrics(master, &metrics), "Master metrics");
+    if (metrics.packets_sent == 0)
+        TEST_FAIL("Master should count sent packets");
+
+    // Invalid handles and pointers
+    EXPECT_ERROR(vlc_rtpmidi_slave_session_feed(NULL, play_packet, play_size, 0),
+                 VLC_RTPMIDI_ERROR_NULL_POINTER, "Null handle");
+    EXPECT_ERROR(vlc_rtpmidi_slave_session_poll_event(slave, NULL), VLC_RTPMIDI_ERROR_NULL_POINTER, "Null event");
+    EXPECT_ERROR(vlc_rtpmidi_slave_session_metrics(slave, NULL), VLC_RTPMIDI_ERROR_NULL_POINTER, "Null metrics");
+
+    vlc_rtpmidi_slave_session_free(NULL);
+    vlc_rtpmidi_slave_session_free(slave);
+    vlc_rtpmidi_master_session_free(master);
+
+    TEST_PASS();
+    return 1;
+}
+
+int test_raw_passthrough()
+{
+    TEST_START("raw_passthrough");
+
+    // SysEx has no event structure and is passed through as raw bytes
+    uint8_t sysex[] = {0x05, 0xF0, 0x7D, 0x01, 0x02, 0xF7};
+    uint8_t midi[16];
+    size_t midi_len;
+    VlcRtpmidiEventType type;
+    EXPECT_SUCCESS(vlc_rtpmidi_slave_netsync_flow_raw_ffi(sysex, sizeof(sysex), midi, sizeof(midi), &midi_len, &type),
+                   "Raw SysEx");
+    if (type != VLC_RTPMIDI_EVENT_RAW || midi_len != 5 || memcmp(midi, sysex + 1, 5) != 0)
+        TEST_FAIL("SysEx should be copied as raw bytes");
+
+    // Too small a buffer reports the required size
+    EXPECT_ERROR(vlc_rtpmidi_slave_netsync_flow_raw_ffi(sysex, sizeof(sysex), midi, 2, &midi_len, &type),
+                 VLC_RTPMIDI_ERROR_BUFFER_TOO_SMALL, "Small raw buffer");
+    if (midi_len != 5)
+        TEST_FAIL("Required size not reported");
+    EXPECT_ERROR(vlc_rtpmidi_slave_netsync_flow_raw_ffi(sysex, sizeof(sysex), NULL, 0, &midi_len, &type),
+                 VLC_RTPMIDI_ERROR_BUFFER_TOO_SMALL, "Size query");
+
+    // Supported events keep their type
+    VlcRtpmidiEvent play = vlc_rtpmidi_create_mmc_play_event();
+    uint8_t payload[16];
+    size_t payload_size;
+    EXPECT_SUCCESS(vlc_rtpmidi_master_netsync_flow_ffi(&play, payload, sizeof(payload), &payload_size), "Play payload");
+    EXPECT_SUCCESS(vlc_rtpmidi_slave_netsync_flow_raw_ffi(payload, payload_size, midi, sizeof(midi), &midi_len, &type),
+                   "Raw Play");
+    if (type != VLC_RTPMIDI_EVENT_MMC_PLAY || midi_len != payload_size - 1)
+        TEST_FAIL("MMC Play should keep its type");
+
+    EXPECT_ERROR(vlc_rtpmidi_slave_netsync_flow_raw_ffi(NULL, 0, midi, sizeof(midi), &midi_len, &type),
+                 VLC_RTPMIDI_ERROR_NULL_POINTER, "Null payload");
+
+    TEST_PASS();
+    return 1;
+}
+
+int test_extended_mmc_roundtrip()
+{
+    TEST_START("extended_mmc_roundtrip");
+
+    struct
+    {
+        VlcRtpmidiEvent event;
+        VlcRtpmidiEventType type;
+        uint8_t command;
+    } commands[] = {
+        {vlc_rtpmidi_create_mmc_deferred_play_event(), VLC_RTPMIDI_EVENT_MMC_DEFERRED_PLAY, 0x03},
+        {vlc_rtpmidi_create_mmc_fast_forward_event(), VLC_RTPMIDI_EVENT_MMC_FAST_FORWARD, 0x04},
+        {vlc_rtpmidi_create_mmc_rewind_event(), VLC_RTPMIDI_EVENT_MMC_REWIND, 0x05},
+        {vlc_rtpmidi_create_mmc_record_strobe_event(), VLC_RTPMIDI_EVENT_MMC_RECORD_STROBE, 0x06},
+        {vlc_rtpmidi_create_mmc_record_exit_event(), VLC_RTPMIDI_EVENT_MMC_RECORD_EXIT, 0x07},
+        {vlc_rtpmidi_create_mmc_pause_event(), VLC_RTPMIDI_EVENT_MMC_PAUSE, 0x09},
+        {vlc_rtpmidi_create_mmc_eject_event(), VLC_RTPMIDI_EVENT_MMC_EJECT, 0x0A},
+        {vlc_rtpmidi_create_mmc_reset_event(), VLC_RTPMIDI_EVENT_MMC_RESET, 0x0D},
+    };
+
+    for (size_t i = 0; i < sizeof(commands) / sizeof(commands[0]); i++)
+    {
+        if (commands[i].event.event_type != commands[i].type || commands[i].event.data_len != 0)
+            TEST_FAIL("Creator built the wrong event");
+
+        // Master flow: F0 7F 7F 06 cmd F7 behind a one-byte header
+        uint8_t payload[16];
+        size_t payload_size;
+        EXPECT_SUCCESS(vlc_rtpmidi_master_netsync_flow_ffi(&commands[i].event, payload, sizeof(payload), &payload_size),
+                       "Extended MMC master flow");
+        if (payload_size != 7 || payload[0] != 0x06 || payload[5] != commands[i].command)
+            TEST_FAIL("Unexpected MMC payload");
+
+        // Slave flow back to the same event
+        VlcRtpmidiEvent decoded;
+        EXPECT_SUCCESS(vlc_rtpmidi_slave_netsync_flow_ffi(payload, payload_size, &decoded),
+                       "Extended MMC slave flow");
+        if (decoded.event_type != commands[i].type || decoded.data_len != 0)
+            TEST_FAIL("Extended MMC did not round-trip");
+
+        // Commands without data reject any
+        VlcRtpmidiEvent with_data = commands[i].event;
+        with_data.data_len = 1;
+        EXPECT_ERROR(vlc_rtpmidi_master_netsync_flow_ffi(&with_data, payload, sizeof(payload), &payload_size),
+                     VLC_RTPMIDI_ERROR_INVALID_EVENT_TYPE, "Extended MMC with data");
+    }
+
+    TEST_PASS();
+    return 1;
+}
+
+int test_beat_clock_roundtrip()
+{
+    TEST_START("beat_clock_roundtrip");
+
+    struct
+    {
+        VlcRtpmidiEvent event;
+        VlcRtpmidiEventType type;
+        uint8_t status;
+    } messages[] = {
+        {vlc_rtpmidi_create_song_position_event(0x3FFF), VLC_RTPMIDI_EVENT_SONG_POSITION, 0xF2},
+        {vlc_rtpmidi_create_clock_event(), VLC_RTPMIDI_EVENT_CLOCK, 0xF8},
+        {vlc_rtpmidi_create_start_event(), VLC_RTPMIDI_EVENT_START, 0xFA},
+        {vlc_rtpmidi_create_continue_event(), VLC_RTPMIDI_EVENT_CONTINUE, 0xFB},
+        {vlc_rtpmidi_create_stop_rt_event(), VLC_RTPMIDI_EVENT_REALTIME_STOP, 0xFC},
+    };
+
+    for (size_t i = 0; i < sizeof(messages) / sizeof(messages[0]); i++)
+    {
+        if (messages[i].event.event_type != messages[i].type)
+            TEST_FAIL("Creator built the wrong event");
+
+        uint8_t payload[16];
+        size_t payload_size;
+        EXPECT_SUCCESS(vlc_rtpmidi_master_netsync_flow_ffi(&messages[i].event, payload, sizeof(payload), &payload_size),
+                       "Beat clock master flow");
+        if (payload[1] != messages[i].status)
+            TEST_FAIL("Unexpected beat clock payload");
+
+        VlcRtpmidiEvent decoded;
+        EXPECT_SUCCESS(vlc_rtpmidi_slave_netsync_flow_ffi(payload, payload_size, &decoded),
+                       "Beat clock slave flow");
+        if (decoded.event_type != messages[i].type || decoded.data_len != messages[i].event.data_len ||
+            memcmp(decoded.data, messages[i].event.data, decoded.data_len) != 0)
+            TEST_FAIL("Beat clock did not round-trip");
+    }
+
+    // Song position is 14 bits: F2 7F 7F
+    VlcRtpmidiEvent spp = vlc_rtpmidi_create_song_position_event(0x3FFF);
+    uint8_t payload[16];
+    size_t payload_size;
+    EXPECT_SUCCESS(vlc_rtpmidi_master_netsync_flow_ffi(&spp, payload, sizeof(payload), &payload_size),
+                   "Song position master flow");
+    if (payload_size != 4 || payload[2] != 0x7F || payload[3] != 0x7F)
+        TEST_FAIL("Unexpected song position payload");
+
+    VlcRtpmidiEvent too_far = vlc_rtpmidi_create_song_position_event(0x4000);
+    EXPECT_ERROR(vlc_rtpmidi_master_netsync_flow_ffi(&too_far, payload, sizeof(payload), &payload_size),
+                 VLC_RTPMIDI_ERROR_INVALID_EVENT_TYPE, "Song position above 0x3FFF");
+
+    TEST_PASS();
+    return 1;
+}
+
+int test_variable_play_roundtrip()
+{
+    TEST_START("variable_play_roundtrip");
+
+    // Half speed forward: F0 7F 7F 06 45 03 00 40 00 F7
+    const uint8_t expected[] = {0x0A, 0xF0, 0x7F, 0x7F, 0x06, 0x45, 0x03, 0x00, 0x40, 0x00, 0xF7};
+    VlcRtpmidiEvent half = vlc_rtpmidi_create_mmc_variable_play_event(0.5f);
+    if (half.event_type != VLC_RTPMIDI_EVENT_MMC_VARIABLE_PLAY || half.data_len != 3)
+        TEST_FAIL("Creator built the wrong event");
+
+    uint8_t payload[16];
+    size_t payload_size;
+    EXPECT_SUCCESS(vlc_rtpmidi_master_netsync_flow_ffi(&half, payload, sizeof(payload), &payload_size),
+                   "Variable Play master flow");
+    if (payload_size != sizeof(expected) || memcmp(payload, expected, sizeof(expected)) != 0)
+        TEST_FAIL("Unexpected Variable Play payload");
+
+    VlcRtpmidiEvent decoded;
+    EXPECT_SUCCESS(vlc_rtpmidi_slave_netsync_flow_ffi(payload, payload_size, &decoded), "Variable Play slave flow");
+    if (decoded.event_type != VLC_RTPMIDI_EVENT_MMC_VARIABLE_PLAY || decoded.data_len != 3 ||
+        memcmp(decoded.data, half.data, 3) != 0)
+        TEST_FAIL("Variable Play did not round-trip");
+
+    half.data_len = 2;
+    EXPECT_ERROR(vlc_rtpmidi_master_netsync_flow_ffi(&half, payload, sizeof(payload), &payload_size),
+                 VLC_RTPMIDI_ERROR_INVALID_EVENT_TYPE, "Variable Play without its three speed bytes");
+
+    TEST_PASS();
+    return 1;
+}
+
+int test_frame_rate_conversions()
+{
+    TEST_START("frame_rate_conversions");
+
+    // 00:01:00:02 is the first frame of minute 1 in drop-frame timecode
+    VlcRtpmidiSmpteTime tc;
+    EXPECT_SUCCESS(vlc_rtpmidi_us_to_smpte_rate_ffi(60060000, VLC_RTPMIDI_FRAME_RATE_2997_DF, &tc),
+                   "Drop-frame conversion");
+    if (tc.hours != 0 || tc.minutes != 1 || tc.seconds != 0 || tc.frames != 2)
+        TEST_FAIL("Drop-frame label not skipped");
+
+    // 25fps round trip through quarter frames, rate bits included
+    VlcRtpmidiSmpteTime pal = {.hours = 1, .minutes = 2, .seconds = 3, .frames = 24};
+    VlcRtpmidiEvent pieces[8];
+    EXPECT_SUCCESS(vlc_rtpmidi_smpte_to_quarter_frames_rate_ffi(&pal, VLC_RTPMIDI_FRAME_RATE_25, pieces),
+                   "25fps quarter frames");
+    VlcRtpmidiSmpteTime decoded;
+    VlcRtpmidiFrameRate rate;
+    EXPECT_SUCCESS(vlc_rtpmidi_quarter_frames_to_smpte_rate_ffi(pieces, &decoded, &rate), "25fps reassembly");
+    if (rate != VLC_RTPMIDI_FRAME_RATE_25 || decoded.frames != 24 || decoded.seconds != 3)
+        TEST_FAIL("25fps cycle did not round-trip");
+    EXPECT_ERROR(vlc_rtpmidi_quarter_frames_to_smpte_ffi(pieces, &decoded), VLC_RTPMIDI_ERROR_INVALID_TIMECODE,
+                 "25fps cycle through the 30fps decoder");
+
+    // Out-of-range rates are rejected, not undefined behavior
+    uint64_t us;
+    EXPECT_ERROR(vlc_rtpmidi_us_to_smpte_rate_ffi(0, (VlcRtpmidiFrameRate)4, &tc),
+                 VLC_RTPMIDI_ERROR_INVALID_FRAME_RATE, "Rate 4");
+    EXPECT_ERROR(vlc_rtpmidi_smpte_to_us_rate_ffi(&pal, (VlcRtpmidiFrameRate)-1, &us),
+                 VLC_RTPMIDI_ERROR_INVALID_FRAME_RATE, "Rate -1");
+
+    VlcRtpmidiNetsyncConfig config = vlc_rtpmidi_default_netsync_config();
+    if (config.frame_rate != VLC_RTPMIDI_FRAME_RATE_30_NDF)
+        TEST_FAIL("Default rate is not 30fps");
+    config.frame_rate = (VlcRtpmidiFrameRate)42;
+    if (vlc_rtpmidi_master_session_new(&config) != NULL || vlc_rtpmidi_slave_session_new(&config) != NULL)
+        TEST_FAIL("Session accepted an invalid rate");
+
+    TEST_PASS();
+    return 1;
+}
+
+int test_device_id_addressing()
+{
+    TEST_START("device_id_addressing");
+
+    // A Locate addressed to device 5 survives master -> payload -> slave
+    VlcRtpmidiEvent locate = vlc_rtpmidi_create_mmc_locate_event(1, 2, 3, 4);
+    uint8_t buffer[32];
+    size_t size;
+    EXPECT_SUCCESS(vlc_rtpmidi_master_netsync_flow_dev_ffi(&locate, 0x05, buffer, sizeof(buffer), &size),
+                   "Addressed Locate");
+    VlcRtpmidiEvent parsed;
+    uint8_t device_id = 0;
+    EXPECT_SUCCESS(vlc_rtpmidi_slave_netsync_flow_dev_ffi(buffer, size, &parsed, &device_id),
+                   "Parse addressed Locate");
+    if (device_id != 0x05 || parsed.event_type != VLC_RTPMIDI_EVENT_MMC_LOCATE ||
+        memcmp(parsed.data, locate.data, 4) != 0)
+        TEST_FAIL("Addressed Locate did not round-trip");
+
+    EXPECT_ERROR(vlc_rtpmidi_master_netsync_flow_dev_ffi(&locate, 0x80, buffer, sizeof(buffer), &size),
+                 VLC_RTPMIDI_ERROR_INVALID_DEVICE_ID, "Device ID 0x80");
+    EXPECT_ERROR(vlc_rtpmidi_slave_netsync_flow_dev_ffi(buffer, size, &parsed, NULL),
+                 VLC_RTPMIDI_ERROR_NULL_POINTER, "NULL device ID output");
+
+    VlcRtpmidiNetsyncConfig config;
+    EXPECT_SUCCESS(vlc_rtpmidi_netsync_config_default(&config, sizeof(config)), "Default config");
+    if (config.device_id != 0x7F || config.struct_size != sizeof(config))
+        TEST_FAIL("Default device ID is not broadcast");
+    config.device_id = 0xFF;
+    if (vlc_rtpmidi_master_session_new(&config) != NULL || vlc_rtpmidi_slave_session_new(&config) != NULL)
+        TEST_FAIL("Session accepted an invalid device ID");
+    EXPECT_ERROR(vlc_rtpmidi_netsync_config_validate(&config), VLC_RTPMIDI_ERROR_INVALID_DEVICE_ID,
+                 "Validation names the device ID");
+
+    TEST_PASS();
+    return 1;
+}
+
+int test_version_and_abi()
+{
+    TEST_START("version_and_abi");
+
+    if (vlc_rtpmidi_abi_version() != VLC_RTPMIDI_ABI_VERSION)
+        TEST_FAIL("Header and library ABI versions differ");
+
+    // Structure layouts as compiled from the header must match the Rust side
+    if (sizeof(VlcRtpmidiEvent) != 16 || sizeof(VlcRtpmidiSmpteTime) != 4 ||
+        sizeof(VlcRtpmidiNetsyncConfig) != 32 || sizeof(VlcRtpmidiNetsyncMetrics) != 144)
+        TEST_FAIL("Structure layout differs from the library");
+    EXPECT_SUCCESS(vlc_rtpmidi_event_layout_check(sizeof(VlcRtpmidiEvent), offsetof(VlcRtpmidiEvent, data),
+                                                  offsetof(VlcRtpmidiEvent, data_len)),
+                   "Event layout check");
+    EXPECT_SUCCESS(vlc_rtpmidi_config_layout_check(sizeof(VlcRtpmidiNetsyncConfig),
+                                                   offsetof(VlcRtpmidiNetsyncConfig, full_frame_interval_us),
+                                                   offsetof(VlcRtpmidiNetsyncConfig, frame_rate),
+                                                   offsetof(VlcRtpmidiNetsyncConfig, device_id)),
+                   "Config layout check");
+    EXPECT_SUCCESS(vlc_rtpmidi_metrics_layout_check(sizeof(VlcRtpmidiNetsyncMetrics),
+                                                    offsetof(VlcRtpmidiNetsyncMetrics, last_drift_us),
+                                                    offsetof(VlcRtpmidiNetsyncMetrics, has_last_drift)),
+                   "Metrics layout check");
+    EXPECT_ERROR(vlc_rtpmidi_event_layout_check(sizeof(VlcRtpmidiEvent) - 3, offsetof(VlcRtpmidiEvent, data),
+                                                offsetof(VlcRtpmidiEvent, data_len)),
+                 VLC_RTPMIDI_ERROR_LAYOUT_MISMATCH, "Packed event layout");
+
+    char expected[32];
+    snprintf(expected, sizeof(expected), "%u.%u.%u", vlc_rtpmidi_version_major(), vlc_rtpmidi_version_minor(),
+             vlc_rtpmidi_version_patch());
+    const char* version = vlc_rtpmidi_version_string();
+    if (version == NULL || strcmp(version, expected) != 0)
+        TEST_FAIL("Version string does not match the numeric version");
+
+    TEST_PASS();
+    return 1;
+}
+
+int test_validate_payload()
+{
+    TEST_START("validate_payload");
+
+    VlcRtpmidiEvent play = vlc_rtpmidi_create_mmc_play_event();
+    uint8_t buffer[32];
+    size_t size;
+    EXPECT_SUCCESS(vlc_rtpmidi_master_netsync_flow_ffi(&play, buffer, sizeof(buffer), &size), "Build Play");
+    int kind = -1;
+    EXPECT_SUCCESS(vlc_rtpmidi_validate_payload_ffi(buffer, size, &kind), "Validate Play");
+    if (kind != VLC_RTPMIDI_EVENT_MMC_PLAY)
+        TEST_FAIL("Play classified as another kind");
+
+    // Every truncation is rejected the way the slave flow rejects it
+    VlcRtpmidiEvent event;
+    for (size_t len = 0; len < size; len++)
+    {
+        int validated = vlc_rtpmidi_validate_payload_ffi(buffer, len, &kind);
+        if (validated != vlc_rtpmidi_slave_netsync_flow_ffi(buffer, len, &event))
+            TEST_FAIL("Validation disagrees with the slave flow");
+    }
+
+    uint8_t note_on[] = {0x03, 0x90, 0x40, 0x7F};
+    EXPECT_ERROR(vlc_rtpmidi_validate_payload_ffi(note_on, sizeof(note_on), &kind),
+                 VLC_RTPMIDI_ERROR_INVALID_EVENT_TYPE, "Note On");
+    if (kind != VLC_RTPMIDI_EVENT_RAW)
+        TEST_FAIL("Note On not classified as raw");
+
+    uint8_t clock[] = {0x01, 0xF8};
+    EXPECT_SUCCESS(vlc_rtpmidi_validate_payload_ffi(clock, sizeof(clock), &kind), "Beat clock");
+    if (kind != VLC_RTPMIDI_EVENT_CLOCK)
+        TEST_FAIL("Beat clock classified as another kind");
+
+    TEST_PASS();
+    return 1;
+}
+
+int test_midi_codec_stress()
+{
+    TEST_START("midi_codec_stress");
+
+    VlcRtpmidiEvent event;
+    size_t consumed, written;
+    uint8_t midi[16];
+
+    // NULL pointers and empty input
+    EXPECT_ERROR(vlc_rtpmidi_parse_midi_ffi(NULL, 2, &event, &consumed), VLC_RTPMIDI_ERROR_NULL_POINTER,
+                 "NULL parse buffer");
+    EXPECT_ERROR(vlc_rtpmidi_parse_midi_ffi(midi, 0, &event, &consumed), VLC_RTPMIDI_ERROR_INVALID_MIDI,
+                 "Empty parse buffer");
+    EXPECT_ERROR(vlc_rtpmidi_build_midi_ffi(NULL, midi, sizeof(midi), &written), VLC_RTPMIDI_ERROR_NULL_POINTER,
+                 "NULL build event");
+    VlcRtpmidiEvent full = vlc_rtpmidi_create_mtc_full_event(1, 2, 3, 4);
+    EXPECT_ERROR(vlc_rtpmidi_build_midi_ffi(&full, NULL, 4, &written), VLC_RTPMIDI_ERROR_NULL_POINTER,
+                 "NULL build buffer with capacity");
+
+    // Every capacity below the message size reports the required size
+    for (size_t cap = 0; cap < 10; cap++)
+    {
+        EXPECT_ERROR(vlc_rtpmidi_build_midi_ffi(&full, midi, cap, &written), VLC_RTPMIDI_ERROR_BUFFER_TOO_SMALL,
+                     "Short build buffer");
+        if (written != 10)
+            TEST_FAIL("Required size not reported");
+    }
+    EXPECT_SUCCESS(vlc_rtpmidi_build_midi_ffi(&full, midi, sizeof(midi), &written), "Build Full Frame");
+
+    // Every prefix parses without reading past its length
+    for (size_t len = 1; len <= written; len++)
+    {
+        uint8_t* copy = malloc(len);
+        memcpy(copy, midi, len);
+        int result = vlc_rtpmidi_parse_midi_ffi(copy, len, &event, &consumed);
+        if (result != 0 && result != VLC_RTPMIDI_ERROR_INVALID_EVENT_TYPE)
+            TEST_FAIL("Unexpected error on a truncated Full Frame");
+        if (consumed == 0 || consumed > len)
+            TEST_FAIL("Consumed length out of range");
+        free(copy);
+    }
+    EXPECT_SUCCESS(vlc_rtpmidi_parse_midi_ffi(midi, written, &event, &consumed), "Parse Full Frame");
+    if (event.event_type != VLC_RTPMIDI_EVENT_MTC_FULL || consumed != 10)
+        TEST_FAIL("Full Frame did not round-trip");
+
+    // Channel messages are not supported by the C event structure
+    uint8_t note_on[] = {0x90, 0x40, 0x7F};
+    EXPECT_ERROR(vlc_rtpmidi_parse_midi_ffi(note_on, sizeof(note_on), &event, &consumed),
+                 VLC_RTPMIDI_ERROR_INVALID_EVENT_TYPE, "Note On");
+
+    TEST_PASS();
+    return 1;
+}
+
+int test_event_accessors()
+{
+    TEST_START("event_accessors");
+
+    VlcRtpmidiEvent event;
+    memset(&event, 0xAB, sizeof(event));
+    EXPECT_SUCCESS(vlc_rtpmidi_event_set_mmc_locate(&event, 1, 2, 3, 4, 5), "Set Locate");
+
+    VlcRtpmidiEventType type;
+    uint8_t data[8];
+    size_t len;
+    EXPECT_SUCCESS(vlc_rtpmidi_event_get_type(&event, &type), "Get type");
+    EXPECT_SUCCESS(vlc_rtpmidi_event_get_data(&event, data, sizeof(data), &len), "Get data");
+    if (type != VLC_RTPMIDI_EVENT_MMC_LOCATE || len != 5 || data[0] != 1 || data[4] != 5)
+        TEST_FAIL("Locate fields not read back");
+
+    // Garbage written by a foreign binding is rejected, not trusted
+    memset(&event, 0xFF, sizeof(event));
+    EXPECT_ERROR(vlc_rtpmidi_event_get_type(&event, &type), VLC_RTPMIDI_ERROR_INVALID_EVENT_TYPE, "Garbage type");
+    EXPECT_ERROR(vlc_rtpmidi_event_get_data(&event, data, sizeof(data), &len), VLC_RTPMIDI_ERROR_INVALID_EVENT_TYPE,
+                 "Garbage length");
+    EXPECT_ERROR(vlc_rtpmidi_event_set_mtc_full(&event, 24, 0, 0, 0), VLC_RTPMIDI_ERROR_INVALID_TIMECODE,
+                 "Hour 24");
+    EXPECT_ERROR(vlc_rtpmidi_event_set_mmc_command(&event, VLC_RTPMIDI_EVENT_MTC_FULL),
+                 VLC_RTPMIDI_ERROR_INVALID_EVENT_TYPE, "Full Frame as MMC command");
+    EXPECT_ERROR(vlc_rtpmidi_event_set_mtc_quarter(NULL, 0, 0), VLC_RTPMIDI_ERROR_NULL_POINTER, "NULL event");
+
+    TEST_PASS();
+    return 1;
+}
+
+typedef struct
+{
+    int calls;
+    int max_level;
+    int too_long;
+} LogCapture;
+
+static void capture_log(int level, const char* msg, void* user)
+{
+    LogCapture* capture = (LogCapture*)user;
+    capture->calls++;
+    if (level > capture->max_level)
+        capture->max_level = level;
+    if (strlen(msg) >= 160)
+        capture->too_long++;
+}
+
+int test_log_callback()
+{
+    TEST_START("log_callback");
+
+    LogCapture capture = {0, -1, 0};
+    uint8_t truncated[] = {0x05, 0xF1};
+    VlcRtpmidiEvent event;
+    vlc_rtpmidi_set_log_callback(capture_log, &capture);
+
+    for (int i = 0; i < 1000; i++)
+    {
+        EXPECT_ERROR(vlc_rtpmidi_slave_netsync_flow_ffi(truncated, sizeof(truncated), &event),
+                     VLC_RTPMIDI_ERROR_TRUNCATED_PAYLOAD, "Truncated payload");
+    }
+    if (capture.calls != 1000 || capture.max_level != VLC_RTPMIDI_LOG_LEVEL_WARNING)
+        TEST_FAIL("Rejected payloads not logged as warnings");
+
+    VlcRtpmidiNetsyncConfig config = vlc_rtpmidi_default_netsync_config();
+    config.device_id = 0xFF;
+    if (vlc_rtpmidi_master_session_new(&config) != NULL)
+        TEST_FAIL("Invalid device ID accepted");
+    if (capture.calls != 1001 || capture.max_level != VLC_RTPMIDI_LOG_LEVEL_ERROR)
+        TEST_FAIL("Rejected configuration not logged as an error");
+    if (capture.too_long != 0)
+        TEST_FAIL("Message longer than the library's buffer");
+
+    // Nothing is logged once the callback is cleared
+    vlc_rtpmidi_set_log_callback(NULL, NULL);
+    vlc_rtpmidi_slave_netsync_flow_ffi(truncated, sizeof(truncated), &event);
+    if (capture.calls != 1001)
+        TEST_FAIL("Callback invoked after being cleared");
+
+    TEST_PASS();
+    return 1;
+}
+
+int test_stream_parser()
+{
+    TEST_START("stream_parser");
+
+    // A quarter frame, a Locate and a Play, back to back as a UART delivers them
+    VlcRtpmidiEvent sent[3] = {
+        vlc_rtpmidi_create_mtc_quarter_event(1, 5),
+        vlc_rtpmidi_create_mmc_locate_event(1, 2, 3, 4),
+        vlc_rtpmidi_create_mmc_play_event(),
+    };
+    uint8_t stream[64];
+    size_t stream_len = 0, len;
+    for (int i = 0; i < 3; i++)
+    {
+        EXPECT_SUCCESS(vlc_rtpmidi_master_netsync_flow_ffi(&sent[i], stream + stream_len,
+                                                           sizeof(stream) - stream_len, &len),
+                       "Encode stream");
+        stream_len += len;
+    }
+
+    // Every chunk size, each chunk polled until the parser would block
+    for (size_t chunk = 1; chunk <= stream_len; chunk++)
+    {
+        VlcRtpmidiStreamParser *parser = vlc_rtpmidi_stream_parser_new(32);
+        if (parser == NULL)
+            TEST_FAIL("Parser not created");
+
+        int received = 0;
+        for (size_t offset = 0; offset < stream_len; offset += chunk)
+        {
+            size_t n = stream_len - offset < chunk ? stream_len - offset : chunk;
+            EXPECT_SUCCESS(vlc_rtpmidi_stream_parser_feed(parser, stream + offset, n), "Feed chunk");
+
+            VlcRtpmidiEvent event;
+            int result;
+            while ((result = vlc_rtpmidi_stream_parser_poll(parser, &event)) == VLC_RTPMIDI_ERROR_SUCCESS)
+            {
+                if (received >= 3 || event.event_type != sent[received].event_type ||
+                    event.data_len != sent[received].data_len ||
+                    memcmp(event.data, sent[received].data, event.data_len) != 0)
+                {
+                    vlc_rtpmidi_stream_parser_free(parser);
+                    TEST_FAIL("Unexpected event");
+                }
+                received++;
+            }
+            EXPECT_ERROR(result, VLC_RTPMIDI_ERROR_WOULD_BLOCK, "Poll after chunk");
+        }
+        vlc_rtpmidi_stream_parser_free(parser);
+        if (received != 3)
+            TEST_FAIL("Events lost");
+    }
+
+    // Line noise before the stream is skipped
+    VlcRtpmidiStreamParser *parser = vlc_rtpmidi_stream_parser_new(32);
+    uint8_t noise[] = {0x00, 0x35, 0x7F, 0x22, 0xF0};
+    VlcRtpmidiEvent event;
+    EXPECT_SUCCESS(vlc_rtpmidi_stream_parser_feed(parser, noise, sizeof(noise)), "Feed noise");
+    EXPECT_SUCCESS(vlc_rtpmidi_stream_parser_feed(parser, stream, 3), "Feed quarter frame");
+    EXPECT_SUCCESS(vlc_rtpmidi_stream_parser_poll(parser, &event), "Poll after noise");
+    if (event.event_type != VLC_RTPMIDI_EVENT_MTC_QUARTER)
+        TEST_FAIL("Wrong event after noise");
+
+    // The buffer is bounded
+    uint8_t large[64] = {0};
+    EXPECT_ERROR(vlc_rtpmidi_stream_parser_feed(parser, large, sizeof(large)),
+                 VLC_RTPMIDI_ERROR_BUFFER_TOO_SMALL, "Feed beyond capacity");
+    EXPECT_ERROR(vlc_rtpmidi_stream_parser_poll(parser, &event), VLC_RTPMIDI_ERROR_WOULD_BLOCK,
+                 "Rejected chunk appended");
+    EXPECT_ERROR(vlc_rtpmidi_stream_parser_poll(NULL, &event), VLC_RTPMIDI_ERROR_NULL_POINTER,
+                 "NULL handle");
+    vlc_rtpmidi_stream_parser_free(parser);
+    vlc_rtpmidi_stream_parser_free(NULL);
+
+    TEST_PASS();
+    return 1;
+}
+
 // Main test runner
 int main()
 {
@@ -1419,6 +2209,21 @@ int main()
     test_defensive_programming();
     test_resource_cleanup();
     test_performance_regression();
+    test_multi_event_payloads();
+    test_master_session_handle();
+    test_slave_session_handle();
+    test_raw_passthrough();
+    test_extended_mmc_roundtrip();
+    test_beat_clock_roundtrip();
+    test_variable_play_roundtrip();
+    test_frame_rate_conversions();
+    test_device_id_addressing();
+    test_version_and_abi();
+    test_validate_payload();
+    test_midi_codec_stress();
+    test_event_accessors();
+    test_log_callback();
+    test_stream_parser();
 
     // Print results
     printf("\n==================================\n");
@@ -1,62 +1,599 @@
+// Generated by cbindgen from src/ffi.rs, do not edit.
+// Regenerate with: UPDATE_HEADER=1 cargo test --test header_tests
+
 #ifndef RTP_MIDI_NETSYNC_H
 #define RTP_MIDI_NETSYNC_H
 
 #include <stddef.h>
 #include <stdint.h>
 
-#ifdef __cplusplus
-extern "C" {
-#endif
+// Version of the C ABI, see `vlc_rtpmidi_abi_version()`
+#define VLC_RTPMIDI_ABI_VERSION 3
+
+// Largest number of packets `vlc_rtpmidi_master_session_tick()` writes
+#define VLC_RTPMIDI_MAX_TICK_PACKETS 2
 
-// MIDI event types
+// Buffer size `vlc_rtpmidi_smpte_to_string_ffi()` needs, terminator included
+#define VLC_RTPMIDI_SMPTE_STRING_SIZE 12
+
+// C-compatible MIDI event types supported by the netsync system
 typedef enum {
-    VLC_RTPMIDI_EVENT_MTC_QUARTER = 0,
-    VLC_RTPMIDI_EVENT_MTC_FULL = 1,
-    VLC_RTPMIDI_EVENT_MMC_STOP = 2,
-    VLC_RTPMIDI_EVENT_MMC_PLAY = 3,
-    VLC_RTPMIDI_EVENT_MMC_LOCATE = 4
+  // MTC Quarter Frame message (incremental time updates)
+  VLC_RTPMIDI_EVENT_MTC_QUARTER = 0,
+  // MTC Full Frame message (absolute time position)
+  VLC_RTPMIDI_EVENT_MTC_FULL = 1,
+  // MMC Stop command
+  VLC_RTPMIDI_EVENT_MMC_STOP = 2,
+  // MMC Play command
+  VLC_RTPMIDI_EVENT_MMC_PLAY = 3,
+  // MMC Locate command (jump to specific time)
+  VLC_RTPMIDI_EVENT_MMC_LOCATE = 4,
+  // Any other MIDI message, only reported by `vlc_rtpmidi_slave_netsync_flow_raw_ffi()`
+  VLC_RTPMIDI_EVENT_RAW = 5,
+  // MMC Deferred Play command (play once a pending Locate completes)
+  VLC_RTPMIDI_EVENT_MMC_DEFERRED_PLAY = 6,
+  // MMC Fast Forward command
+  VLC_RTPMIDI_EVENT_MMC_FAST_FORWARD = 7,
+  // MMC Rewind command
+  VLC_RTPMIDI_EVENT_MMC_REWIND = 8,
+  // MMC Record Strobe command
+  VLC_RTPMIDI_EVENT_MMC_RECORD_STROBE = 9,
+  // MMC Record Exit command
+  VLC_RTPMIDI_EVENT_MMC_RECORD_EXIT = 10,
+  // MMC Pause command
+  VLC_RTPMIDI_EVENT_MMC_PAUSE = 11,
+  // MMC Eject command
+  VLC_RTPMIDI_EVENT_MMC_EJECT = 12,
+  // MMC Reset command
+  VLC_RTPMIDI_EVENT_MMC_RESET = 13,
+  // Song Position Pointer, in MIDI beats (sixteenth notes) from the song start
+  VLC_RTPMIDI_EVENT_SONG_POSITION = 14,
+  // Timing Clock, sent 24 times per quarter note
+  VLC_RTPMIDI_EVENT_CLOCK = 15,
+  // Start playback from the beginning of the song
+  VLC_RTPMIDI_EVENT_START = 16,
+  // Continue playback from the current song position
+  VLC_RTPMIDI_EVENT_CONTINUE = 17,
+  // Stop playback, keeping the song position
+  VLC_RTPMIDI_EVENT_REALTIME_STOP = 18,
+  // MMC Variable Play command (play at a signed speed)
+  VLC_RTPMIDI_EVENT_MMC_VARIABLE_PLAY = 19,
 } VlcRtpmidiEventType;
 
-// MIDI event structure
-typedef struct {
-    VlcRtpmidiEventType event_type;
-    uint8_t data[8];    // Event-specific data, max data is 5 bytes + 3 padding
-    uint8_t data_len;   // Number of valid bytes in data
-} VlcRtpmidiEvent;
+// C-compatible SMPTE frame rate
+typedef enum {
+  // 24 frames per second (film)
+  VLC_RTPMIDI_FRAME_RATE_24 = 0,
+  // 25 frames per second (PAL/EBU)
+  VLC_RTPMIDI_FRAME_RATE_25 = 1,
+  // 30000/1001 frames per second, drop-frame (NTSC)
+  VLC_RTPMIDI_FRAME_RATE_2997_DF = 2,
+  // 30 frames per second, non-drop frame
+  VLC_RTPMIDI_FRAME_RATE_30_NDF = 3,
+} VlcRtpmidiFrameRate;
 
-// Error codes
+// Error codes returned by FFI functions
 typedef enum {
-    VLC_RTPMIDI_ERROR_SUCCESS = 0,
-    VLC_RTPMIDI_ERROR_INVALID_MASTER_EVENT = 1,
-    VLC_RTPMIDI_ERROR_INVALID_SLAVE_EVENT = 2,
-    VLC_RTPMIDI_ERROR_BUFFER_TOO_SMALL = 3,
-    VLC_RTPMIDI_ERROR_NULL_POINTER = 4,
-    VLC_RTPMIDI_ERROR_INVALID_EVENT_TYPE = 5
+  // Operation completed successfully
+  VLC_RTPMIDI_ERROR_SUCCESS = 0,
+  // Invalid MIDI event provided to master flow
+  VLC_RTPMIDI_ERROR_INVALID_MASTER_EVENT = 1,
+  // Invalid network payload provided to slave flow
+  VLC_RTPMIDI_ERROR_INVALID_SLAVE_EVENT = 2,
+  // Provided buffer is too small for the operation
+  VLC_RTPMIDI_ERROR_BUFFER_TOO_SMALL = 3,
+  // Null pointer passed where valid pointer expected
+  VLC_RTPMIDI_ERROR_NULL_POINTER = 4,
+  // Unsupported or malformed event type
+  VLC_RTPMIDI_ERROR_INVALID_EVENT_TYPE = 5,
+  // Not an error: valid payload without MIDI command (LEN=0), output event untouched
+  VLC_RTPMIDI_ERROR_NO_EVENT = 6,
+  // Timecode field out of range (hours 0-23, minutes and seconds 0-59, frames 0-29)
+  VLC_RTPMIDI_ERROR_INVALID_TIMECODE = 7,
+  // Quarter frames not in piece order 0-7
+  VLC_RTPMIDI_ERROR_QUARTER_FRAME_ORDER = 8,
+  // Not an error: the packet repeats the last sequence number and was dropped
+  VLC_RTPMIDI_ERROR_DUPLICATE = 9,
+  // Not an error: the packet is older than the last one received and was dropped
+  VLC_RTPMIDI_ERROR_OUT_OF_ORDER = 10,
+  // Not an error: packet from another SSRC than the session's sender, dropped
+  VLC_RTPMIDI_ERROR_FOREIGN_SSRC = 11,
+  // Frame rate value outside `VlcRtpmidiFrameRate`
+  VLC_RTPMIDI_ERROR_INVALID_FRAME_RATE = 12,
+  // Device ID above 0x7F
+  VLC_RTPMIDI_ERROR_INVALID_DEVICE_ID = 13,
+  // Structure layout compiled by the caller differs from the library's
+  VLC_RTPMIDI_ERROR_LAYOUT_MISMATCH = 14,
+  // MIDI bytes that cannot be read as a message
+  VLC_RTPMIDI_ERROR_INVALID_MIDI = 15,
+  // Not an error: no complete payload is buffered yet, feed more bytes
+  VLC_RTPMIDI_ERROR_WOULD_BLOCK = 16,
+  // Payload shorter than its header announces: it may complete with more bytes
+  VLC_RTPMIDI_ERROR_TRUNCATED_PAYLOAD = 17,
+  // Header flags or LEN inconsistent with the bytes following the header
+  VLC_RTPMIDI_ERROR_HEADER_MISMATCH = 18,
+  // SysEx message without its terminator or addressed to an unexpected device
+  VLC_RTPMIDI_ERROR_MALFORMED_SYSEX = 19,
+  // Timecode field of a received message out of range
+  VLC_RTPMIDI_ERROR_VALUE_OUT_OF_RANGE = 20,
+  // Timecode text not in `HH:MM:SS:FF` or `HH:MM:SS;FF` form
+  VLC_RTPMIDI_ERROR_MALFORMED_TIMECODE = 21,
+  // Session stopped after a fatal error, such as a sender using another RTP version
+  VLC_RTPMIDI_ERROR_SESSION_FAILED = 22,
 } VlcRtpmidiErrorCode;
 
-// Core netsync functions (C provides buffers)
-int vlc_rtpmidi_master_netsync_flow_ffi(const VlcRtpmidiEvent* event,
-                                       uint8_t* buffer,
-                                       size_t buffer_size,
-                                       size_t* actual_size);
+// Severity of a message passed to the log callback
+typedef enum {
+  // Expected conditions worth tracing, such as dropped duplicate packets
+  VLC_RTPMIDI_LOG_LEVEL_DEBUG = 0,
+  // Noteworthy but harmless conditions
+  VLC_RTPMIDI_LOG_LEVEL_INFO = 1,
+  // Rejected input: a malformed payload or an invalid event
+  VLC_RTPMIDI_LOG_LEVEL_WARNING = 2,
+  // Rejected configuration: the requested object was not created
+  VLC_RTPMIDI_LOG_LEVEL_ERROR = 3,
+} VlcRtpmidiLogLevel;
 
-int vlc_rtpmidi_slave_netsync_flow_ffi(const uint8_t* buffer,
-                                     size_t buffer_len,
-                                     VlcRtpmidiEvent* event);
+// Opaque handle to a master session
+typedef struct VlcRtpmidiMasterSession VlcRtpmidiMasterSession;
+
+// Opaque handle to a stateful MTC quarter-frame assembler
+typedef struct VlcRtpmidiMtcAssembler VlcRtpmidiMtcAssembler;
+
+// Opaque handle to a slave session
+typedef struct VlcRtpmidiSlaveSession VlcRtpmidiSlaveSession;
+
+// Opaque handle to a byte-stream payload parser
+typedef struct VlcRtpmidiStreamParser VlcRtpmidiStreamParser;
+
+// C-compatible MIDI event structure
+typedef struct {
+  // `VlcRtpmidiEventType` value of the event
+  uint32_t event_type;
+  // Raw event data (interpretation depends on event_type)
+  uint8_t data[8];
+  // Number of valid bytes in the data array
+  uint8_t data_len;
+} VlcRtpmidiEvent;
+
+// C-compatible SMPTE timecode (30fps non-drop frame unless a rate is given)
+typedef struct {
+  // Hours (0-23)
+  uint8_t hours;
+  // Minutes (0-59)
+  uint8_t minutes;
+  // Seconds (0-59)
+  uint8_t seconds;
+  // Frames (0-29, below the frame rate)
+  uint8_t frames;
+} VlcRtpmidiSmpteTime;
+
+// Log callback installed with `vlc_rtpmidi_set_log_callback()`
+typedef void (*VlcRtpmidiLogCallback)(int level, const char *msg, void *user);
+
+// C-compatible session configuration
+typedef struct {
+  // `sizeof(VlcRtpmidiNetsyncConfig)` as compiled by the application
+  uint32_t struct_size;
+  // RTP synchronization source identifier used for outgoing packets
+  uint32_t ssrc;
+  // Maximum time between Full Frames sent by a master, 0 to disable
+  uint64_t full_frame_interval_us;
+  // Full Frame after this many quarter-frame cycles without one, 0 to disable
+  uint32_t quarter_frame_cycles;
+  // Non-zero to reject every payload deviating from the expected format
+  uint8_t strict_parsing;
+  // `VlcRtpmidiFrameRate` of the Full Frames a master sends and a slave reads
+  uint32_t frame_rate;
+  // Device ID of the Full Frames and MMC commands a master sends, 0x7F for all
+  uint8_t device_id;
+} VlcRtpmidiNetsyncConfig;
+
+// C-compatible session counters
+typedef struct {
+  uint64_t packets_sent;
+  uint64_t packets_parsed;
+  uint64_t empty_payloads;
+  uint64_t rtp_header_errors;
+  uint64_t too_short_errors;
+  uint64_t length_errors;
+  uint64_t midi_errors;
+  uint64_t timecode_errors;
+  uint64_t duplicates_dropped;
+  uint64_t out_of_order_dropped;
+  uint64_t foreign_ssrc_dropped;
+  uint64_t sequence_gaps;
+  uint64_t packets_lost;
+  uint64_t quarter_frame_cycles;
+  uint64_t full_frames_refreshed;
+  uint64_t resync_requests;
+  // Last measured drift in microseconds, valid only if `has_last_drift` is non-zero
+  int64_t last_drift_us;
+  // Non-zero once a drift measurement is available
+  uint8_t has_last_drift;
+} VlcRtpmidiNetsyncMetrics;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Master netsync flow: Convert MIDI event to RTP-MIDI network payload
+int vlc_rtpmidi_master_netsync_flow_ffi(const VlcRtpmidiEvent *event,
+                                        uint8_t *buffer,
+                                        size_t buffer_size,
+                                        size_t *actual_size);
+
+// Master netsync flow: Convert MIDI event to a payload addressed to one device
+int vlc_rtpmidi_master_netsync_flow_dev_ffi(const VlcRtpmidiEvent *event,
+                                            uint8_t device_id,
+                                            uint8_t *buffer,
+                                            size_t buffer_size,
+                                            size_t *actual_size);
+
+// Master netsync flow: Convert several MIDI events to one RTP-MIDI network payload
+int vlc_rtpmidi_master_netsync_flow_multi_ffi(const VlcRtpmidiEvent *events,
+                                              size_t event_count,
+                                              uint8_t *buffer,
+                                              size_t buffer_size,
+                                              size_t *actual_size,
+                                              size_t *failed_index);
 
-// Utility functions
+// Master netsync flow: Convert an array of MIDI events to back-to-back payloads
+int vlc_rtpmidi_master_netsync_flow_batch_ffi(const VlcRtpmidiEvent *events,
+                                              size_t count,
+                                              uint8_t *buffer,
+                                              size_t buffer_size,
+                                              size_t *offsets_out,
+                                              size_t *actual_total);
+
+// Master netsync flow: Convert SMPTE timecode to the payloads of its eight quarter frames
+int vlc_rtpmidi_quarter_frame_payloads_ffi(const VlcRtpmidiSmpteTime *input,
+                                           uint8_t *buffer,
+                                           size_t buffer_size,
+                                           size_t *offsets_out,
+                                           size_t *actual_total);
+
+// Slave netsync flow: Parse RTP-MIDI network payload to MIDI event
+int vlc_rtpmidi_slave_netsync_flow_ffi(const uint8_t *buffer,
+                                       size_t buffer_len,
+                                       VlcRtpmidiEvent *event);
+
+// Slave netsync flow: Parse RTP-MIDI network payload to MIDI event and device ID
+int vlc_rtpmidi_slave_netsync_flow_dev_ffi(const uint8_t *buffer,
+                                           size_t buffer_len,
+                                           VlcRtpmidiEvent *event,
+                                           uint8_t *device_id_out);
+
+// Slave netsync flow: Parse every RTP-MIDI payload of a buffer to MIDI events
+int vlc_rtpmidi_slave_netsync_flow_multi_ffi(const uint8_t *buffer,
+                                             size_t buffer_len,
+                                             VlcRtpmidiEvent *events_out,
+                                             size_t max_events,
+                                             size_t *actual_events);
+
+// Slave netsync flow: Copy the raw MIDI bytes of an RTP-MIDI network payload
+int vlc_rtpmidi_slave_netsync_flow_raw_ffi(const uint8_t *buffer,
+                                           size_t buffer_len,
+                                           uint8_t *out_buf,
+                                           size_t out_cap,
+                                           size_t *out_len,
+                                           VlcRtpmidiEventType *event_type_out);
+
+// Validate and classify an RTP-MIDI network payload without parsing it to an event
+int vlc_rtpmidi_validate_payload_ffi(const uint8_t *buffer, size_t buffer_len, int *event_kind_out);
+
+// Check an event before handing it to a master flow
+int vlc_rtpmidi_validate_event_ffi(const VlcRtpmidiEvent *event);
+
+// Parse the first MIDI message of a byte stream, without a netsync header
+int vlc_rtpmidi_parse_midi_ffi(const uint8_t *buf,
+                               size_t len,
+                               VlcRtpmidiEvent *out,
+                               size_t *consumed);
+
+// Serialize a MIDI event to MIDI bytes, without a netsync header
+int vlc_rtpmidi_build_midi_ffi(const VlcRtpmidiEvent *event,
+                               uint8_t *buf,
+                               size_t cap,
+                               size_t *written);
+
+// Get the maximum buffer size needed for network payloads
 size_t vlc_rtpmidi_get_max_payload_size(void);
-const char* vlc_rtpmidi_get_error_message(int error_code);
 
-// Helper functions for creating MIDI events
+// Get the exact payload size `vlc_rtpmidi_master_netsync_flow_ffi()` produces for an event
+int vlc_rtpmidi_get_payload_size_for_event(const VlcRtpmidiEvent *event, size_t *size_out);
+
+// Get human-readable error message for an error code
+const char *vlc_rtpmidi_get_error_message(int error_code);
+
+// Get the name of an event type
+const char *vlc_rtpmidi_get_event_type_name(int event_type);
+
+// Describe a payload as human-readable text
+int vlc_rtpmidi_describe_payload_ffi(const uint8_t *buffer,
+                                     size_t buffer_len,
+                                     char *out,
+                                     size_t out_cap,
+                                     size_t *out_len);
+
+// Get the major version of the library
+uint32_t vlc_rtpmidi_version_major(void);
+
+// Get the minor version of the library
+uint32_t vlc_rtpmidi_version_minor(void);
+
+// Get the patch version of the library
+uint32_t vlc_rtpmidi_version_patch(void);
+
+// Get the full version of the library as a string
+const char *vlc_rtpmidi_version_string(void);
+
+// Get the ABI version of the library
+uint32_t vlc_rtpmidi_abi_version(void);
+
+// Check that the caller's `VlcRtpmidiEvent` layout matches the library's
+int vlc_rtpmidi_event_layout_check(size_t sizeof_event,
+                                   size_t offsetof_data,
+                                   size_t offsetof_data_len);
+
+// Check that the caller's `VlcRtpmidiNetsyncConfig` layout matches the library's
+int vlc_rtpmidi_config_layout_check(size_t sizeof_config,
+                                    size_t offsetof_full_frame_interval_us,
+                                    size_t offsetof_frame_rate,
+                                    size_t offsetof_device_id);
+
+// Check that the caller's `VlcRtpmidiNetsyncMetrics` layout matches the library's
+int vlc_rtpmidi_metrics_layout_check(size_t sizeof_metrics,
+                                     size_t offsetof_last_drift_us,
+                                     size_t offsetof_has_last_drift);
+
+// Install or remove the log callback
+void vlc_rtpmidi_set_log_callback(VlcRtpmidiLogCallback callback, void *user);
+
+// Get a description of why the last failing call on this thread failed
+const char *vlc_rtpmidi_get_last_error_detail(void);
+
+// Convert microseconds to SMPTE timecode (30fps non-drop frame)
+int vlc_rtpmidi_us_to_smpte_ffi(uint64_t us, VlcRtpmidiSmpteTime *out);
+
+// Convert SMPTE timecode (30fps non-drop frame) to microseconds
+int vlc_rtpmidi_smpte_to_us_ffi(const VlcRtpmidiSmpteTime *input, uint64_t *out);
+
+// Split SMPTE timecode into the eight MTC Quarter Frame events of one cycle
+int vlc_rtpmidi_smpte_to_quarter_frames_ffi(const VlcRtpmidiSmpteTime *input,
+                                            VlcRtpmidiEvent *out_events);
+
+// Reassemble SMPTE timecode from the eight MTC Quarter Frame events of one cycle
+int vlc_rtpmidi_quarter_frames_to_smpte_ffi(const VlcRtpmidiEvent *events,
+                                            VlcRtpmidiSmpteTime *out);
+
+// Convert microseconds to SMPTE timecode at the given frame rate
+int vlc_rtpmidi_us_to_smpte_rate_ffi(uint64_t us, uint32_t rate, VlcRtpmidiSmpteTime *out);
+
+// Convert SMPTE timecode at the given frame rate to microseconds
+int vlc_rtpmidi_smpte_to_us_rate_ffi(const VlcRtpmidiSmpteTime *input,
+                                     uint32_t rate,
+                                     uint64_t *out);
+
+// Split SMPTE timecode into the eight MTC Quarter Frame events of one cycle at a frame rate
+int vlc_rtpmidi_smpte_to_quarter_frames_rate_ffi(const VlcRtpmidiSmpteTime *input,
+                                                 uint32_t rate,
+                                                 VlcRtpmidiEvent *out_events);
+
+// Reassemble SMPTE timecode and its frame rate from the eight Quarter Frames of one cycle
+int vlc_rtpmidi_quarter_frames_to_smpte_rate_ffi(const VlcRtpmidiEvent *events,
+                                                 VlcRtpmidiSmpteTime *out,
+                                                 VlcRtpmidiFrameRate *rate_out);
+
+// Format SMPTE timecode as text
+int vlc_rtpmidi_smpte_to_string_ffi(const VlcRtpmidiSmpteTime *input,
+                                    uint32_t rate,
+                                    char *out,
+                                    size_t cap);
+
+// Parse SMPTE timecode text
+int vlc_rtpmidi_smpte_from_string_ffi(const char *text, uint32_t rate, VlcRtpmidiSmpteTime *out);
+
+// Create a quarter-frame assembler waiting for piece 0
+VlcRtpmidiMtcAssembler *vlc_rtpmidi_mtc_assembler_new(void);
+
+// Feed one MTC Quarter Frame event into an assembler
+int vlc_rtpmidi_mtc_assembler_feed(VlcRtpmidiMtcAssembler *handle,
+                                   const VlcRtpmidiEvent *event,
+                                   VlcRtpmidiSmpteTime *out,
+                                   int *complete);
+
+// Discard the partially assembled cycle of an assembler
+int vlc_rtpmidi_mtc_assembler_reset(VlcRtpmidiMtcAssembler *handle);
+
+// Get the frame rate signalled by the last cycle an assembler completed
+int vlc_rtpmidi_mtc_assembler_frame_rate(const VlcRtpmidiMtcAssembler *handle,
+                                         VlcRtpmidiFrameRate *rate_out);
+
+// Release an assembler
+void vlc_rtpmidi_mtc_assembler_free(VlcRtpmidiMtcAssembler *handle);
+
+// Create a stream parser buffering at most `capacity` unread bytes
+VlcRtpmidiStreamParser *vlc_rtpmidi_stream_parser_new(size_t capacity);
+
+// Append received bytes to a stream parser
+int vlc_rtpmidi_stream_parser_feed(VlcRtpmidiStreamParser *handle,
+                                   const uint8_t *bytes,
+                                   size_t len);
+
+// Take the event of the next complete payload
+int vlc_rtpmidi_stream_parser_poll(VlcRtpmidiStreamParser *handle, VlcRtpmidiEvent *event);
+
+// Release a stream parser
+void vlc_rtpmidi_stream_parser_free(VlcRtpmidiStreamParser *handle);
+
+// Get the default session configuration
+VlcRtpmidiNetsyncConfig vlc_rtpmidi_default_netsync_config(void);
+
+// Initialize a session configuration with the default values
+int vlc_rtpmidi_netsync_config_default(VlcRtpmidiNetsyncConfig *out, size_t struct_size);
+
+// Check a session configuration
+int vlc_rtpmidi_netsync_config_validate(const VlcRtpmidiNetsyncConfig *config);
+
+// Get the maximum size of one RTP packet built by a session
+size_t vlc_rtpmidi_get_max_packet_size(void);
+
+// Create a master session
+VlcRtpmidiMasterSession *vlc_rtpmidi_master_session_new(const VlcRtpmidiNetsyncConfig *config);
+
+// Build the RTP packet for an application event
+int vlc_rtpmidi_master_session_packet_for_event(VlcRtpmidiMasterSession *handle,
+                                                const VlcRtpmidiEvent *event,
+                                                uint64_t now_us,
+                                                uint8_t *buffer,
+                                                size_t buffer_size,
+                                                size_t *actual_size);
+
+// Build the maintenance packets due at `now_us`
+int vlc_rtpmidi_master_session_tick(VlcRtpmidiMasterSession *handle,
+                                    uint64_t now_us,
+                                    uint8_t *buffer,
+                                    size_t buffer_size,
+                                    size_t *packet_sizes,
+                                    size_t max_packets,
+                                    size_t *packet_count);
+
+// Read the session counters
+int vlc_rtpmidi_master_session_metrics(const VlcRtpmidiMasterSession *handle,
+                                       VlcRtpmidiNetsyncMetrics *out);
+
+// Set every session counter back to zero
+int vlc_rtpmidi_master_session_reset_metrics(VlcRtpmidiMasterSession *handle);
+
+// Release a master session
+void vlc_rtpmidi_master_session_free(VlcRtpmidiMasterSession *handle);
+
+// Create a slave session
+VlcRtpmidiSlaveSession *vlc_rtpmidi_slave_session_new(const VlcRtpmidiNetsyncConfig *config);
+
+// Process a complete RTP packet
+int vlc_rtpmidi_slave_session_feed(VlcRtpmidiSlaveSession *handle,
+                                   const uint8_t *buffer,
+                                   size_t buffer_len,
+                                   uint64_t arrival_us);
+
+// Take the oldest queued event
+int vlc_rtpmidi_slave_session_poll_event(VlcRtpmidiSlaveSession *handle, VlcRtpmidiEvent *event);
+
+// Read the session counters
+int vlc_rtpmidi_slave_session_metrics(const VlcRtpmidiSlaveSession *handle,
+                                      VlcRtpmidiNetsyncMetrics *out);
+
+// Set every session counter back to zero
+int vlc_rtpmidi_slave_session_reset_metrics(VlcRtpmidiSlaveSession *handle);
+
+// Release a slave session, discarding any queued events
+void vlc_rtpmidi_slave_session_free(VlcRtpmidiSlaveSession *handle);
+
+// Create MTC Quarter Frame event
 VlcRtpmidiEvent vlc_rtpmidi_create_mtc_quarter_event(uint8_t msg_type, uint8_t value);
-VlcRtpmidiEvent vlc_rtpmidi_create_mtc_full_event(uint8_t hour, uint8_t minute, uint8_t second, uint8_t frame);
+
+// Create the eight MTC Quarter Frame events of one cycle from a timecode
+int vlc_rtpmidi_create_mtc_quarter_sequence(const VlcRtpmidiSmpteTime *time,
+                                            VlcRtpmidiEvent *out_events);
+
+// Create MTC Full Frame event
+VlcRtpmidiEvent vlc_rtpmidi_create_mtc_full_event(uint8_t hour,
+                                                  uint8_t minute,
+                                                  uint8_t second,
+                                                  uint8_t frame);
+
+// Create MMC Stop event
 VlcRtpmidiEvent vlc_rtpmidi_create_mmc_stop_event(void);
+
+// Create MMC Play event
 VlcRtpmidiEvent vlc_rtpmidi_create_mmc_play_event(void);
-VlcRtpmidiEvent vlc_rtpmidi_create_mmc_locate_event(uint8_t hour, uint8_t minute, uint8_t second, uint8_t frame);
+
+// Create MMC Locate event
+VlcRtpmidiEvent vlc_rtpmidi_create_mmc_locate_event(uint8_t hour,
+                                                    uint8_t minute,
+                                                    uint8_t second,
+                                                    uint8_t frame);
+
+// Create MMC Locate event with a subframe
+VlcRtpmidiEvent vlc_rtpmidi_create_mmc_locate_event_sf(uint8_t hour,
+                                                       uint8_t minute,
+                                                       uint8_t second,
+                                                       uint8_t frame,
+                                                       uint8_t subframe);
+
+// Create MMC Deferred Play event
+VlcRtpmidiEvent vlc_rtpmidi_create_mmc_deferred_play_event(void);
+
+// Create MMC Fast Forward event
+VlcRtpmidiEvent vlc_rtpmidi_create_mmc_fast_forward_event(void);
+
+// Create MMC Rewind event
+VlcRtpmidiEvent vlc_rtpmidi_create_mmc_rewind_event(void);
+
+// Create MMC Record Strobe event
+VlcRtpmidiEvent vlc_rtpmidi_create_mmc_record_strobe_event(void);
+
+// Create MMC Record Exit event
+VlcRtpmidiEvent vlc_rtpmidi_create_mmc_record_exit_event(void);
+
+// Create MMC Pause event
+VlcRtpmidiEvent vlc_rtpmidi_create_mmc_pause_event(void);
+
+// Create MMC Eject event
+VlcRtpmidiEvent vlc_rtpmidi_create_mmc_eject_event(void);
+
+// Create MMC Reset event
+VlcRtpmidiEvent vlc_rtpmidi_create_mmc_reset_event(void);
+
+// Create MMC Variable Play event
+VlcRtpmidiEvent vlc_rtpmidi_create_mmc_variable_play_event(float speed);
+
+// Create Song Position Pointer event
+VlcRtpmidiEvent vlc_rtpmidi_create_song_position_event(uint16_t beats);
+
+// Create Timing Clock event
+VlcRtpmidiEvent vlc_rtpmidi_create_clock_event(void);
+
+// Create Start event
+VlcRtpmidiEvent vlc_rtpmidi_create_start_event(void);
+
+// Create Continue event
+VlcRtpmidiEvent vlc_rtpmidi_create_continue_event(void);
+
+// Create real-time Stop event
+VlcRtpmidiEvent vlc_rtpmidi_create_stop_rt_event(void);
+
+// Get the type of an event
+int vlc_rtpmidi_event_get_type(const VlcRtpmidiEvent *event, VlcRtpmidiEventType *type_out);
+
+// Copy the valid data bytes of an event
+int vlc_rtpmidi_event_get_data(const VlcRtpmidiEvent *event, uint8_t *out, size_t cap, size_t *len);
+
+// Overwrite an event with an MTC Quarter Frame
+int vlc_rtpmidi_event_set_mtc_quarter(VlcRtpmidiEvent *event, uint8_t msg_type, uint8_t value);
+
+// Overwrite an event with an MTC Full Frame
+int vlc_rtpmidi_event_set_mtc_full(VlcRtpmidiEvent *event,
+                                   uint8_t hour,
+                                   uint8_t minute,
+                                   uint8_t second,
+                                   uint8_t frame);
+
+// Overwrite an event with an MMC Locate
+int vlc_rtpmidi_event_set_mmc_locate(VlcRtpmidiEvent *event,
+                                     uint8_t hour,
+                                     uint8_t minute,
+                                     uint8_t second,
+                                     uint8_t frame,
+                                     uint8_t subframe);
+
+// Overwrite an event with an MMC command without data
+int vlc_rtpmidi_event_set_mmc_command(VlcRtpmidiEvent *event, uint32_t event_type);
 
 #ifdef __cplusplus
-}
-#endif
+}  // extern "C"
+#endif  // __cplusplus
 
-#endif // RTP_MIDI_NETSYNC_H
+#endif  /* RTP_MIDI_NETSYNC_H */
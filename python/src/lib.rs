@@ -0,0 +1,457 @@
+//! # Python bindings
+//!
+//! PyO3 wrappers exposing the netsync flows and timecode conversions as the
+//! `rtp_midi_netsync` Python module, for scripting protocol tests without the C
+//! API. Build it into the active virtualenv with `maturin develop`.
+//!
+//! Errors are raised as subclasses of `rtp_midi_netsync.Error`, itself a
+//! `ValueError`, one per Rust error enum, with the variant's stable number in a
+//! `code` attribute. A `NetsyncError` raised by the slave flow has the
+//! `ParseError` behind the rejection as its `__cause__`, when there is one.
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict};
+use pyo3::PyTypeInfo;
+
+use rtp_midi_netsync::error::Error as RustError;
+use rtp_midi_netsync::midi::{ManufacturerId, MidiEvent, MmcCommand, MmcSpeed, RealtimeMessage};
+use rtp_midi_netsync::mtc::{self, FrameRate, MtcFullFrame};
+use rtp_midi_netsync::netsync;
+
+create_exception!(
+    rtp_midi_netsync,
+    Error,
+    PyValueError,
+    "Base class of the errors raised by this module."
+);
+create_exception!(
+    rtp_midi_netsync,
+    ParseError,
+    Error,
+    "A MIDI list or payload could not be parsed."
+);
+create_exception!(
+    rtp_midi_netsync,
+    MtcError,
+    Error,
+    "A timecode or quarter frame is invalid."
+);
+create_exception!(
+    rtp_midi_netsync,
+    NetsyncError,
+    Error,
+    "A netsync flow rejected an event or payload."
+);
+
+/// Builds an exception of type `E` with the Rust error's message and code.
+fn error<E: PyTypeInfo>(py: Python<'_>, message: String, code: u16) -> PyErr {
+    let err = PyErr::new::<E, _>(message);
+    let _ = err.value(py).setattr("code", code);
+    err
+}
+
+/// Maps an error from the detailed slave flow, chaining its parse error.
+fn flow_error(py: Python<'_>, err: RustError) -> PyErr {
+    match err {
+        RustError::NetsyncError(flow) => {
+            let err = error::<NetsyncError>(py, flow.kind.to_string(), flow.kind.code());
+            if let Some(cause) = flow.cause {
+                err.set_cause(
+                    py,
+                    Some(error::<ParseError>(py, cause.to_string(), cause.code())),
+                );
+            }
+            err
+        }
+        other => Error::new_err(other.to_string()),
+    }
+}
+
+/// Returns the frame rate for an optional MTC rate code, 30fps by default.
+fn frame_rate(code: Option<u8>) -> PyResult<FrameRate> {
+    match code {
+        None => Ok(FrameRate::Fps30),
+        Some(code) => FrameRate::from_mtc_code(code)
+            .ok_or_else(|| PyValueError::new_err(format!("invalid MTC rate code {}", code))),
+    }
+}
+
+/// SMPTE timecode, formatted as `HH:MM:SS:FF`.
+#[pyclass(name = "MtcFullFrame", eq)]
+#[derive(Clone, PartialEq)]
+struct PyMtcFullFrame {
+    #[pyo3(get, set)]
+    hours: u8,
+    #[pyo3(get, set)]
+    minutes: u8,
+    #[pyo3(get, set)]
+    seconds: u8,
+    #[pyo3(get, set)]
+    frames: u8,
+}
+
+impl From<MtcFullFrame> for PyMtcFullFrame {
+    fn from(smpte: MtcFullFrame) -> Self {
+        Self {
+            hours: smpte.hours,
+            minutes: smpte.minutes,
+            seconds: smpte.seconds,
+            frames: smpte.frames,
+        }
+    }
+}
+
+impl From<&PyMtcFullFrame> for MtcFullFrame {
+    fn from(smpte: &PyMtcFullFrame) -> Self {
+        Self {
+            hours: smpte.hours,
+            minutes: smpte.minutes,
+            seconds: smpte.seconds,
+            frames: smpte.frames,
+        }
+    }
+}
+
+#[pymethods]
+impl PyMtcFullFrame {
+    #[new]
+    #[pyo3(signature = (hours=0, minutes=0, seconds=0, frames=0))]
+    fn new(hours: u8, minutes: u8, seconds: u8, frames: u8) -> Self {
+        Self {
+            hours,
+            minutes,
+            seconds,
+            frames,
+        }
+    }
+
+    /// Parses `HH:MM:SS:FF` or `HH:MM:SS;FF`, raising `MtcError` if it is invalid.
+    #[staticmethod]
+    fn parse(py: Python<'_>, text: &str) -> PyResult<Self> {
+        text.parse::<MtcFullFrame>()
+            .map(Self::from)
+            .map_err(|err| error::<MtcError>(py, err.to_string(), err.code()))
+    }
+
+    fn __str__(&self) -> String {
+        MtcFullFrame::from(self).to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "MtcFullFrame(hours={}, minutes={}, seconds={}, frames={})",
+            self.hours, self.minutes, self.seconds, self.frames
+        )
+    }
+}
+
+/// A MIDI synchronization event, built with the static constructors.
+#[pyclass(name = "MidiEvent", eq, frozen)]
+#[derive(Clone, PartialEq)]
+struct PyMidiEvent(MidiEvent);
+
+#[pymethods]
+impl PyMidiEvent {
+    /// MTC Quarter Frame piece `msg_type` (0-7) carrying `value` (0-15).
+    #[staticmethod]
+    fn mtc_quarter(msg_type: u8, value: u8) -> Self {
+        Self(MidiEvent::MtcQuarter { msg_type, value })
+    }
+
+    /// MTC Full Frame; `hour` carries the rate code in bits 5-6.
+    #[staticmethod]
+    fn mtc_full(hour: u8, minute: u8, second: u8, frame: u8) -> Self {
+        Self(MidiEvent::MtcFull {
+            hour,
+            minute,
+            second,
+            frame,
+        })
+    }
+
+    /// MMC command without data, by name: `"Play"`, `"Stop"`, `"Pause"`...
+    #[staticmethod]
+    fn mmc(command: &str) -> PyResult<Self> {
+        let command = match command {
+            "Stop" => MmcCommand::Stop,
+            "Play" => MmcCommand::Play,
+            "DeferredPlay" => MmcCommand::DeferredPlay,
+            "FastForward" => MmcCommand::FastForward,
+            "Rewind" => MmcCommand::Rewind,
+            "RecordStrobe" => MmcCommand::RecordStrobe,
+            "RecordExit" => MmcCommand::RecordExit,
+            "Pause" => MmcCommand::Pause,
+            "Eject" => MmcCommand::Eject,
+            "Reset" => MmcCommand::Reset,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown MMC command {:?}",
+                    command
+                )))
+            }
+        };
+        Ok(Self(MidiEvent::Mmc(command)))
+    }
+
+    /// MMC Locate to a timecode.
+    #[staticmethod]
+    #[pyo3(signature = (hour, minute, second, frame, subframe=0))]
+    fn locate(hour: u8, minute: u8, second: u8, frame: u8, subframe: u8) -> Self {
+        Self(MidiEvent::Mmc(MmcCommand::Locate {
+            hour,
+            minute,
+            second,
+            frame,
+            subframe,
+        }))
+    }
+
+    /// MMC Shuttle at a speed in multiples of play speed, negative in reverse.
+    #[staticmethod]
+    fn shuttle(speed: f32) -> Self {
+        Self(MidiEvent::Mmc(MmcCommand::Shuttle {
+            speed: MmcSpeed::from_f32(speed),
+        }))
+    }
+
+    /// MMC Masked Write of the bits of byte `start` of `field` that `mask` selects.
+    #[staticmethod]
+    fn masked_write(field: u8, start: u8, mask: u8, data: u8) -> Self {
+        Self(MidiEvent::Mmc(MmcCommand::MaskedWrite {
+            field,
+            start,
+            mask,
+            data,
+        }))
+    }
+
+    /// MMC Masked Write arming or disarming a track, numbered from 1.
+    #[staticmethod]
+    #[pyo3(signature = (track, armed=true))]
+    fn arm_track(track: u16, armed: bool) -> PyResult<Self> {
+        MmcCommand::arm_track(track, armed)
+            .map(|command| Self(MidiEvent::Mmc(command)))
+            .ok_or_else(|| PyValueError::new_err(format!("no track {} in a track bitmap", track)))
+    }
+
+    /// MMC Search at a speed in multiples of play speed, negative in reverse.
+    #[staticmethod]
+    fn search(speed: f32) -> Self {
+        Self(MidiEvent::Mmc(MmcCommand::Search {
+            speed: MmcSpeed::from_f32(speed),
+        }))
+    }
+
+    /// MMC Variable Play at a speed in multiples of play speed, negative in reverse.
+    #[staticmethod]
+    fn variable_play(speed: f32) -> Self {
+        Self(MidiEvent::Mmc(MmcCommand::VariablePlay {
+            speed: MmcSpeed::from_f32(speed),
+        }))
+    }
+
+    /// MIDI Beat Clock message, by name: `"Clock"`, `"Start"`, `"Continue"` or `"Stop"`.
+    #[staticmethod]
+    fn realtime(message: &str) -> PyResult<Self> {
+        let message = match message {
+            "Clock" => RealtimeMessage::Clock,
+            "Start" => RealtimeMessage::Start,
+            "Continue" => RealtimeMessage::Continue,
+            "Stop" => RealtimeMessage::Stop,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown realtime message {:?}",
+                    message
+                )))
+            }
+        };
+        Ok(Self(MidiEvent::Realtime(message)))
+    }
+
+    /// Song Position Pointer, in sixteenth notes.
+    #[staticmethod]
+    fn song_position(position: u16) -> Self {
+        Self(MidiEvent::SongPosition(position))
+    }
+
+    /// Identity Request asking `device_id`, by default every device.
+    #[staticmethod]
+    #[pyo3(signature = (device_id=0x7F))]
+    fn identity_request(device_id: u8) -> Self {
+        Self(MidiEvent::IdentityRequest { device_id })
+    }
+
+    /// Manufacturer SysEx: `id` is the one- or three-byte ID as sent, `data` the
+    /// bytes between it and the terminator.
+    #[staticmethod]
+    fn manufacturer_sysex(id: Vec<u8>, data: Vec<u8>) -> PyResult<Self> {
+        let parsed = ManufacturerId::parse(&id).filter(|parsed| parsed.wire_len() == id.len());
+        let Some(id) = parsed else {
+            return Err(PyValueError::new_err(format!(
+                "invalid manufacturer ID {:02X?}",
+                id
+            )));
+        };
+        Ok(Self(MidiEvent::ManufacturerSysex { id, data }))
+    }
+
+    /// Any other MIDI message, as raw bytes.
+    #[staticmethod]
+    fn other(bytes: Vec<u8>) -> Self {
+        Self(MidiEvent::Other(bytes))
+    }
+
+    /// Name of the event's kind, such as `"MtcQuarter"` or `"Play"`.
+    #[getter]
+    fn kind(&self) -> String {
+        format!("{:?}", self.0.kind())
+    }
+
+    /// Returns the event as a dict: `type` names the variant, and the other keys
+    /// are its fields under the Rust names.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        match &self.0 {
+            MidiEvent::MtcQuarter { msg_type, value } => {
+                dict.set_item("type", "MtcQuarter")?;
+                dict.set_item("msg_type", msg_type)?;
+                dict.set_item("value", value)?;
+            }
+            MidiEvent::MtcFull {
+                hour,
+                minute,
+                second,
+                frame,
+            } => {
+                dict.set_item("type", "MtcFull")?;
+                dict.set_item("hour", hour)?;
+                dict.set_item("minute", minute)?;
+                dict.set_item("second", second)?;
+                dict.set_item("frame", frame)?;
+            }
+            MidiEvent::Mmc(command) => {
+                dict.set_item("type", "Mmc")?;
+                if let MmcCommand::Locate {
+                    hour,
+                    minute,
+                    second,
+                    frame,
+                    subframe,
+                } = command
+                {
+                    dict.set_item("command", "Locate")?;
+                    dict.set_item("hour", hour)?;
+                    dict.set_item("minute", minute)?;
+                    dict.set_item("second", second)?;
+                    dict.set_item("frame", frame)?;
+                    dict.set_item("subframe", subframe)?;
+                } else if let MmcCommand::Shuttle { speed }
+                | MmcCommand::VariablePlay { speed }
+                | MmcCommand::Search { speed } = command
+                {
+                    dict.set_item("command", format!("{:?}", self.0.kind()))?;
+                    dict.set_item("speed", speed.to_f32())?;
+                } else if let MmcCommand::MaskedWrite {
+                    field,
+                    start,
+                    mask,
+                    data,
+                } = command
+                {
+                    dict.set_item("command", "MaskedWrite")?;
+                    dict.set_item("field", field)?;
+                    dict.set_item("start", start)?;
+                    dict.set_item("mask", mask)?;
+                    dict.set_item("data", data)?;
+                } else {
+                    dict.set_item("command", format!("{:?}", command))?;
+                }
+            }
+            MidiEvent::Realtime(message) => {
+                dict.set_item("type", "Realtime")?;
+                dict.set_item("message", format!("{:?}", message))?;
+            }
+            MidiEvent::SongPosition(position) => {
+                dict.set_item("type", "SongPosition")?;
+                dict.set_item("position", position)?;
+            }
+            MidiEvent::IdentityRequest { device_id } => {
+                dict.set_item("type", "IdentityRequest")?;
+                dict.set_item("device_id", device_id)?;
+            }
+            MidiEvent::IdentityReply(identity) => {
+                dict.set_item("type", "IdentityReply")?;
+                dict.set_item("device_id", identity.device_id)?;
+                let manufacturer: Vec<u8> = identity.manufacturer.bytes().collect();
+                dict.set_item("manufacturer", PyBytes::new(py, &manufacturer))?;
+                dict.set_item("family", identity.family)?;
+                dict.set_item("model", identity.model)?;
+                dict.set_item("version", PyBytes::new(py, &identity.version))?;
+            }
+            MidiEvent::ManufacturerSysex { id, data } => {
+                dict.set_item("type", "ManufacturerSysex")?;
+                dict.set_item("id", PyBytes::new(py, &id.bytes().collect::<Vec<_>>()))?;
+                dict.set_item("data", PyBytes::new(py, data))?;
+            }
+            MidiEvent::Other(bytes) => {
+                dict.set_item("type", "Other")?;
+                dict.set_item("bytes", PyBytes::new(py, bytes))?;
+            }
+        }
+        Ok(dict)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("MidiEvent({:?})", self.0)
+    }
+}
+
+/// Builds the network payload for a synchronization event, raising
+/// `NetsyncError` for events the master flow does not send.
+#[pyfunction]
+fn master_netsync_flow<'py>(py: Python<'py>, event: &PyMidiEvent) -> PyResult<Bound<'py, PyBytes>> {
+    netsync::master_netsync_flow(&event.0)
+        .map(|payload| PyBytes::new(py, &payload))
+        .map_err(|err| error::<NetsyncError>(py, err.to_string(), err.code()))
+}
+
+/// Decodes a network payload, raising `NetsyncError` if it is rejected.
+#[pyfunction]
+fn slave_netsync_flow(py: Python<'_>, payload: &[u8]) -> PyResult<PyMidiEvent> {
+    netsync::slave_netsync_flow_detailed(payload)
+        .map(PyMidiEvent)
+        .map_err(|err| flow_error(py, err))
+}
+
+/// Converts microseconds to a timecode at the MTC rate code `rate`, 30fps by default.
+#[pyfunction]
+#[pyo3(signature = (us, rate=None))]
+fn us_to_smpte(us: u64, rate: Option<u8>) -> PyResult<PyMtcFullFrame> {
+    Ok(mtc::us_to_smpte_rate(us, frame_rate(rate)?).into())
+}
+
+/// Converts a timecode at the MTC rate code `rate`, 30fps by default, to microseconds.
+#[pyfunction]
+#[pyo3(signature = (smpte, rate=None))]
+fn smpte_to_us(smpte: &PyMtcFullFrame, rate: Option<u8>) -> PyResult<u64> {
+    Ok(mtc::smpte_to_us_rate(&smpte.into(), frame_rate(rate)?))
+}
+
+#[pymodule]
+#[pyo3(name = "rtp_midi_netsync")]
+fn rtp_midi_netsync_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    m.add("Error", py.get_type::<Error>())?;
+    m.add("ParseError", py.get_type::<ParseError>())?;
+    m.add("MtcError", py.get_type::<MtcError>())?;
+    m.add("NetsyncError", py.get_type::<NetsyncError>())?;
+    m.add_class::<PyMtcFullFrame>()?;
+    m.add_class::<PyMidiEvent>()?;
+    m.add_function(wrap_pyfunction!(master_netsync_flow, m)?)?;
+    m.add_function(wrap_pyfunction!(slave_netsync_flow, m)?)?;
+    m.add_function(wrap_pyfunction!(us_to_smpte, m)?)?;
+    m.add_function(wrap_pyfunction!(smpte_to_us, m)?)?;
+    Ok(())
+}
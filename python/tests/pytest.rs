@@ -0,0 +1,44 @@
+//! Runs `test_bindings.py` with pytest against the extension module cargo built
+//! for this crate. The interpreter is `python3`, or the one named by `PYTHON`;
+//! the test is skipped if it has no pytest.
+
+use std::env;
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+#[test]
+fn pytest() {
+    let python = env::var("PYTHON").unwrap_or_else(|_| "python3".to_string());
+    let has_pytest = Command::new(&python)
+        .args(["-m", "pytest", "--version"])
+        .output()
+        .is_ok_and(|output| output.status.success());
+    if !has_pytest {
+        eprintln!("skipping: pytest is not installed for {}", python);
+        return;
+    }
+
+    // The cdylib sits next to the `deps` directory holding this test
+    let exe = env::current_exe().unwrap();
+    let profile_dir = exe.parent().unwrap().parent().unwrap();
+    let library = profile_dir.join(format!("{}rtp_midi_netsync_py{}", DLL_PREFIX, DLL_SUFFIX));
+    let module_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("python");
+    fs::create_dir_all(&module_dir).unwrap();
+    let extension = if cfg!(windows) { "pyd" } else { "so" };
+    fs::copy(
+        &library,
+        module_dir.join(format!("rtp_midi_netsync.{}", extension)),
+    )
+    .unwrap_or_else(|err| panic!("{}: {}", library.display(), err));
+
+    let status = Command::new(&python)
+        .args(["-m", "pytest", "-q", "tests/test_bindings.py"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .env("PYTHONPATH", &module_dir)
+        .env("PYTHONDONTWRITEBYTECODE", "1")
+        .status()
+        .unwrap();
+    assert!(status.success(), "pytest failed");
+}
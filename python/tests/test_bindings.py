@@ -0,0 +1,166 @@
+import pytest
+
+import rtp_midi_netsync as rmn
+from rtp_midi_netsync import MidiEvent, MtcFullFrame
+
+EVENTS = [
+    MidiEvent.mtc_quarter(3, 7),
+    MidiEvent.mtc_full(0x61, 2, 3, 4),
+    MidiEvent.mmc("Play"),
+    MidiEvent.mmc("Stop"),
+    MidiEvent.locate(1, 2, 3, 4),
+    MidiEvent.shuttle(-0.5),
+    MidiEvent.variable_play(0.5),
+    MidiEvent.search(10.0),
+    MidiEvent.arm_track(3),
+    MidiEvent.realtime("Clock"),
+    MidiEvent.song_position(96),
+]
+
+
+@pytest.mark.parametrize("event", EVENTS, ids=repr)
+def test_round_trip(event):
+    payload = rmn.master_netsync_flow(event)
+    assert isinstance(payload, bytes)
+    assert rmn.slave_netsync_flow(payload) == event
+
+
+def test_known_payload():
+    payload = bytes([0x06, 0xF0, 0x7F, 0x7F, 0x06, 0x02, 0xF7])
+    assert rmn.master_netsync_flow(MidiEvent.mmc("Play")) == payload
+    assert rmn.slave_netsync_flow(payload).kind == "Play"
+
+
+def test_to_dict():
+    event = rmn.slave_netsync_flow(rmn.master_netsync_flow(MidiEvent.locate(1, 2, 3, 4)))
+    assert event.to_dict() == {
+        "type": "Mmc",
+        "command": "Locate",
+        "hour": 1,
+        "minute": 2,
+        "second": 3,
+        "frame": 4,
+        "subframe": 0,
+    }
+
+
+def test_shuttle():
+    payload = bytes([0x0A, 0xF0, 0x7F, 0x7F, 0x06, 0x47, 0x03, 0x41, 0x00, 0x00, 0xF7])
+    assert rmn.master_netsync_flow(MidiEvent.shuttle(-1.0)) == payload
+    event = rmn.slave_netsync_flow(payload)
+    assert event.kind == "Shuttle"
+    assert event.to_dict() == {"type": "Mmc", "command": "Shuttle", "speed": -1.0}
+
+
+def test_variable_play():
+    payload = bytes([0x0A, 0xF0, 0x7F, 0x7F, 0x06, 0x45, 0x03, 0x00, 0x40, 0x00, 0xF7])
+    assert rmn.master_netsync_flow(MidiEvent.variable_play(0.5)) == payload
+    event = rmn.slave_netsync_flow(payload)
+    assert event.kind == "VariablePlay"
+    assert event.to_dict() == {"type": "Mmc", "command": "VariablePlay", "speed": 0.5}
+
+
+def test_arm_track():
+    # Track 3 is bit 0 of byte 1 of the Track Record Ready bitmap
+    payload = bytes([0x0B, 0xF0, 0x7F, 0x7F, 0x06, 0x41, 0x04, 0x4F, 0x01, 0x01, 0x01, 0xF7])
+    assert rmn.master_netsync_flow(MidiEvent.arm_track(3)) == payload
+    assert rmn.slave_netsync_flow(payload).to_dict() == {
+        "type": "Mmc",
+        "command": "MaskedWrite",
+        "field": 0x4F,
+        "start": 1,
+        "mask": 0x01,
+        "data": 0x01,
+    }
+    assert MidiEvent.arm_track(3, armed=False) == MidiEvent.masked_write(0x4F, 1, 0x01, 0x00)
+    with pytest.raises(ValueError):
+        MidiEvent.arm_track(0)
+
+
+def test_errors_are_value_errors():
+    for error in (rmn.ParseError, rmn.MtcError, rmn.NetsyncError):
+        assert issubclass(error, rmn.Error)
+    assert issubclass(rmn.Error, ValueError)
+
+
+def test_master_rejects_other():
+    with pytest.raises(rmn.NetsyncError) as info:
+        rmn.master_netsync_flow(MidiEvent.other(bytes([0x90, 0x40, 0x7F])))
+    assert info.value.code == 300
+
+
+def test_manufacturer_sysex():
+    payload = bytes([0x06, 0xF0, 0x00, 0x20, 0x33, 0x01, 0xF7])
+    event = rmn.slave_netsync_flow(payload)
+    assert event == MidiEvent.manufacturer_sysex(bytes([0x00, 0x20, 0x33]), bytes([0x01]))
+    assert event.kind == "ManufacturerSysex"
+    assert event.to_dict() == {
+        "type": "ManufacturerSysex",
+        "id": bytes([0x00, 0x20, 0x33]),
+        "data": bytes([0x01]),
+    }
+    with pytest.raises(rmn.NetsyncError):
+        rmn.master_netsync_flow(event)
+    with pytest.raises(ValueError):
+        MidiEvent.manufacturer_sysex(bytes([0x41, 0x10]), bytes())
+
+
+def test_identity_exchange():
+    request = MidiEvent.identity_request()
+    assert rmn.master_netsync_flow(request) == bytes([0x06, 0xF0, 0x7E, 0x7F, 0x06, 0x01, 0xF7])
+    payload = bytes(
+        [0x0F, 0xF0, 0x7E, 0x10, 0x06, 0x02, 0x41, 0x02, 0x01, 0x03, 0x00, 1, 2, 3, 4, 0xF7]
+    )
+    assert rmn.slave_netsync_flow(payload).to_dict() == {
+        "type": "IdentityReply",
+        "device_id": 0x10,
+        "manufacturer": bytes([0x41]),
+        "family": 0x0082,
+        "model": 0x0003,
+        "version": bytes([1, 2, 3, 4]),
+    }
+
+
+def test_slave_rejects_short_payload():
+    with pytest.raises(rmn.NetsyncError) as info:
+        rmn.slave_netsync_flow(bytes([0x01]))
+    assert info.value.code == 303
+
+
+def test_slave_error_chains_parse_error():
+    # LEN=5 with a single MIDI byte present
+    with pytest.raises(rmn.NetsyncError) as info:
+        rmn.slave_netsync_flow(bytes([0x05, 0xF1]))
+    assert info.value.code == 304
+    assert isinstance(info.value.__cause__, rmn.ParseError)
+    assert 100 <= info.value.__cause__.code < 200
+
+
+def test_unknown_names():
+    with pytest.raises(ValueError):
+        MidiEvent.mmc("Loop")
+    with pytest.raises(ValueError):
+        MidiEvent.realtime("Tick")
+
+
+def test_timecode_round_trip():
+    smpte = MtcFullFrame(1, 2, 3, 4)
+    assert str(smpte) == "01:02:03:04"
+    assert MtcFullFrame.parse("01:02:03:04") == smpte
+    assert rmn.us_to_smpte(rmn.smpte_to_us(smpte)) == smpte
+
+
+def test_timecode_rates():
+    assert rmn.smpte_to_us(MtcFullFrame(seconds=1), rate=1) == 1_000_000
+    assert rmn.us_to_smpte(60_060_000, rate=2) == MtcFullFrame(0, 1, 0, 2)
+    with pytest.raises(ValueError):
+        rmn.us_to_smpte(0, rate=4)
+
+
+def test_timecode_parse_error():
+    with pytest.raises(rmn.MtcError) as info:
+        MtcFullFrame.parse("24:00:00:00")
+    assert info.value.code == 208
+    with pytest.raises(rmn.MtcError) as info:
+        MtcFullFrame.parse("1:2:3")
+    assert info.value.code == 203
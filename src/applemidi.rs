@@ -0,0 +1,747 @@
+//! AppleMIDI Session Control
+//!
+//! This module implements the session establishment exchange used by macOS Network
+//! MIDI and rtpMIDI on Windows (the "AppleMIDI" protocol) to open RTP-MIDI sessions.
+//!
+//! ## Protocol Structure
+//!
+//! Every session message starts with the `0xFFFF` signature and a two-letter command.
+//! Invitation (`IN`), InvitationAccepted (`OK`), InvitationRejected (`NO`) and
+//! EndSession (`BY`) share the same layout:
+//!
+//! ```text
+//! 0                   1                   2                   3
+//! 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+//! +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+//! |           0xFFFF              |           command             |
+//! +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+//! |                    protocol version (2)                       |
+//! +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+//! |                       initiator token                         |
+//! +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+//! |                     sender SSRC                               |
+//! +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+//! |          name (NUL-terminated UTF-8, IN and OK only) ...      |
+//! +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+//! ```
+//!
+//! A session uses two UDP ports: the control port and the data port right above it.
+//! The initiator invites the listener on the control port first, then on the data
+//! port. The session is established once both invitations have been accepted.
+//!
+//! Once established, peers periodically run a three-way clock synchronization
+//! (`CK`) on the data port, which [`ClockSync`] answers and uses to estimate the
+//! clock offset and one-way latency between them:
+//!
+//! ```text
+//! +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+//! |           0xFFFF              |              CK               |
+//! +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+//! |                         sender SSRC                           |
+//! +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+//! |     count     |                 unused                        |
+//! +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+//! |                  timestamp 1 (64 bits) ...                    |
+//! |                  timestamp 2 (64 bits) ...                    |
+//! |                  timestamp 3 (64 bits) ...                    |
+//! +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+//! ```
+//!
+//! Timestamps count 100 µs ticks of the sender's monotonic clock.
+//!
+//! ## Implementation Note
+//!
+//! [`SessionInitiator`] and [`SessionListener`] only track the exchange and report
+//! which message to send on which [`Channel`]; they do not own sockets or timers.
+//! Retransmitting unanswered invitations is left to the caller.
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::error::AppleMidiError;
+
+/// Signature that starts every AppleMIDI session message.
+pub const APPLEMIDI_SIGNATURE: u16 = 0xFFFF;
+
+/// AppleMIDI protocol version carried by session messages.
+pub const APPLEMIDI_VERSION: u32 = 2;
+
+/// Length of a session message without its name.
+pub const SESSION_MESSAGE_LENGTH: usize = 16;
+
+const COMMAND_INVITATION: [u8; 2] = *b"IN";
+const COMMAND_INVITATION_ACCEPTED: [u8; 2] = *b"OK";
+const COMMAND_INVITATION_REJECTED: [u8; 2] = *b"NO";
+const COMMAND_END_SESSION: [u8; 2] = *b"BY";
+const COMMAND_CLOCK_SYNC: [u8; 2] = *b"CK";
+
+/// Length of a clock synchronization message.
+pub const CLOCK_SYNC_LENGTH: usize = 36;
+
+/// Duration of one AppleMIDI timestamp tick, in microseconds.
+pub const TIMESTAMP_TICK_US: u64 = 100;
+
+/// Fields shared by all session establishment messages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionExchange {
+    /// Random token chosen by the initiator and echoed in every reply.
+    pub initiator_token: u32,
+    /// SSRC of the sender of this message.
+    pub ssrc: u32,
+    /// Human-readable session or device name, if present.
+    pub name: Option<String>,
+}
+
+/// # AppleMIDI session control messages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    /// `IN`: asks the peer to join a session
+    Invitation(SessionExchange),
+    /// `OK`: accepts an invitation
+    InvitationAccepted(SessionExchange),
+    /// `NO`: rejects an invitation
+    InvitationRejected(SessionExchange),
+    /// `BY`: leaves the session
+    EndSession(SessionExchange),
+    /// `CK`: one step of a clock synchronization exchange
+    ClockSync(ClockSyncMessage),
+}
+
+/// Fields of a `CK` clock synchronization message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockSyncMessage {
+    /// SSRC of the sender of this message.
+    pub ssrc: u32,
+    /// Step of the exchange: 0 from the initiator, 1 the reply, 2 the final message.
+    pub count: u8,
+    /// Timestamps in 100 µs ticks; only the first `count + 1` are meaningful.
+    pub timestamps: [u64; 3],
+}
+
+impl Message {
+    /// Returns `true` if `buf` starts with the AppleMIDI signature, which tells
+    /// session messages apart from RTP packets arriving on the same port.
+    pub fn is_applemidi(buf: &[u8]) -> bool {
+        buf.len() >= 2 && u16::from_be_bytes([buf[0], buf[1]]) == APPLEMIDI_SIGNATURE
+    }
+
+    /// Parses an AppleMIDI session message.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - A complete UDP datagram
+    ///
+    /// # Returns
+    ///
+    /// Returns the parsed message. A name that is missing its NUL terminator is
+    /// taken up to the end of the datagram.
+    ///
+    /// # Errors
+    ///
+    /// * `AppleMidiError::TooShort` - If the datagram is shorter than the fixed fields.
+    /// * `AppleMidiError::InvalidSignature` - If it does not start with `0xFFFF`.
+    /// * `AppleMidiError::UnknownCommand` - If the command is not a session or clock
+    ///   synchronization command.
+    /// * `AppleMidiError::UnsupportedVersion` - If the protocol version is not 2.
+    /// * `AppleMidiError::InvalidName` - If the name is not valid UTF-8.
+    pub fn parse(buf: &[u8]) -> Result<Self, AppleMidiError> {
+        if buf.len() < 4 {
+            return Err(AppleMidiError::TooShort {
+                expected: SESSION_MESSAGE_LENGTH,
+                available: buf.len(),
+            });
+        }
+        if !Self::is_applemidi(buf) {
+            return Err(AppleMidiError::InvalidSignature);
+        }
+
+        let command = [buf[2], buf[3]];
+        if command == COMMAND_CLOCK_SYNC {
+            return ClockSyncMessage::parse(buf).map(Message::ClockSync);
+        }
+        let build: fn(SessionExchange) -> Self = match command {
+            COMMAND_INVITATION => Message::Invitation,
+            COMMAND_INVITATION_ACCEPTED => Message::InvitationAccepted,
+            COMMAND_INVITATION_REJECTED => Message::InvitationRejected,
+            COMMAND_END_SESSION => Message::EndSession,
+            _ => return Err(AppleMidiError::UnknownCommand(command)),
+        };
+
+        if buf.len() < SESSION_MESSAGE_LENGTH {
+            return Err(AppleMidiError::TooShort {
+                expected: SESSION_MESSAGE_LENGTH,
+                available: buf.len(),
+            });
+        }
+        let version = u32::from_be_bytes([buf[4], buf[5], buf[6], buf[7]]);
+        if version != APPLEMIDI_VERSION {
+            return Err(AppleMidiError::UnsupportedVersion(version));
+        }
+
+        let name_bytes = &buf[SESSION_MESSAGE_LENGTH..];
+        let name_bytes = match name_bytes.iter().position(|&b| b == 0) {
+            Some(end) => &name_bytes[..end],
+            None => name_bytes,
+        };
+        let name = if name_bytes.is_empty() {
+            None
+        } else {
+            let name = core::str::from_utf8(name_bytes).map_err(|_| AppleMidiError::InvalidName)?;
+            Some(name.to_owned())
+        };
+
+        Ok(build(SessionExchange {
+            initiator_token: u32::from_be_bytes([buf[8], buf[9], buf[10], buf[11]]),
+            ssrc: u32::from_be_bytes([buf[12], buf[13], buf[14], buf[15]]),
+            name,
+        }))
+    }
+
+    /// Serializes the message into its wire form.
+    ///
+    /// The name, when present, is written with its NUL terminator.
+    pub fn serialize(&self) -> Vec<u8> {
+        let (command, exchange) = match self {
+            Message::Invitation(exchange) => (COMMAND_INVITATION, exchange),
+            Message::InvitationAccepted(exchange) => (COMMAND_INVITATION_ACCEPTED, exchange),
+            Message::InvitationRejected(exchange) => (COMMAND_INVITATION_REJECTED, exchange),
+            Message::EndSession(exchange) => (COMMAND_END_SESSION, exchange),
+            Message::ClockSync(ck) => return ck.serialize().to_vec(),
+        };
+
+        let name_len = exchange.name.as_ref().map_or(0, |name| name.len() + 1);
+        let mut out = Vec::with_capacity(SESSION_MESSAGE_LENGTH + name_len);
+        out.extend_from_slice(&APPLEMIDI_SIGNATURE.to_be_bytes());
+        out.extend_from_slice(&command);
+        out.extend_from_slice(&APPLEMIDI_VERSION.to_be_bytes());
+        out.extend_from_slice(&exchange.initiator_token.to_be_bytes());
+        out.extend_from_slice(&exchange.ssrc.to_be_bytes());
+        if let Some(name) = &exchange.name {
+            out.extend_from_slice(name.as_bytes());
+            out.push(0);
+        }
+        out
+    }
+
+    /// Returns the fields shared by session establishment messages, or `None` for
+    /// clock synchronization.
+    pub fn exchange(&self) -> Option<&SessionExchange> {
+        match self {
+            Message::Invitation(exchange)
+            | Message::InvitationAccepted(exchange)
+            | Message::InvitationRejected(exchange)
+            | Message::EndSession(exchange) => Some(exchange),
+            Message::ClockSync(_) => None,
+        }
+    }
+}
+
+impl ClockSyncMessage {
+    /// Parses a `CK` message.
+    ///
+    /// # Errors
+    ///
+    /// * `AppleMidiError::TooShort` - If the datagram is shorter than 36 bytes.
+    /// * `AppleMidiError::InvalidSignature` - If it does not start with `0xFFFF`.
+    /// * `AppleMidiError::UnknownCommand` - If the command is not `CK`.
+    pub fn parse(buf: &[u8]) -> Result<Self, AppleMidiError> {
+        if buf.len() < CLOCK_SYNC_LENGTH {
+            return Err(AppleMidiError::TooShort {
+                expected: CLOCK_SYNC_LENGTH,
+                available: buf.len(),
+            });
+        }
+        if !Message::is_applemidi(buf) {
+            return Err(AppleMidiError::InvalidSignature);
+        }
+        let command = [buf[2], buf[3]];
+        if command != COMMAND_CLOCK_SYNC {
+            return Err(AppleMidiError::UnknownCommand(command));
+        }
+
+        let timestamp = |i: usize| {
+            let start = 12 + 8 * i;
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(&buf[start..start + 8]);
+            u64::from_be_bytes(bytes)
+        };
+        Ok(Self {
+            ssrc: u32::from_be_bytes([buf[4], buf[5], buf[6], buf[7]]),
+            count: buf[8],
+            timestamps: [timestamp(0), timestamp(1), timestamp(2)],
+        })
+    }
+
+    /// Serializes the message into its 36-byte wire form.
+    pub fn serialize(&self) -> [u8; CLOCK_SYNC_LENGTH] {
+        let mut out = [0u8; CLOCK_SYNC_LENGTH];
+        out[0..2].copy_from_slice(&APPLEMIDI_SIGNATURE.to_be_bytes());
+        out[2..4].copy_from_slice(&COMMAND_CLOCK_SYNC);
+        out[4..8].copy_from_slice(&self.ssrc.to_be_bytes());
+        out[8] = self.count;
+        for (i, timestamp) in self.timestamps.iter().enumerate() {
+            let start = 12 + 8 * i;
+            out[start..start + 8].copy_from_slice(&timestamp.to_be_bytes());
+        }
+        out
+    }
+}
+
+/// # Clock synchronization with an AppleMIDI peer.
+///
+/// Answers `CK` messages and estimates, from each completed exchange, the offset
+/// between the peer's clock and the local one and the one-way network latency:
+///
+/// - latency = (t3 - t1) / 2
+/// - offset = t2 - (t1 + t3) / 2 as seen from the initiator, the opposite from the
+///   responder
+///
+/// where t1 and t3 are read on the initiator's clock and t2 on the responder's.
+/// Timestamp arithmetic wraps around at 2^64 ticks. The first exchange sets the
+/// estimates; later ones are smoothed with a gain of 1/8 so a single delayed packet
+/// does not move them much.
+#[derive(Debug, Clone)]
+pub struct ClockSync {
+    ssrc: u32,
+    /// t1 of the exchange this side started, awaiting its count 1 reply
+    pending: Option<u64>,
+    /// Peer clock minus local clock, in ticks
+    offset: Option<i64>,
+    /// One-way latency, in ticks
+    latency: Option<u64>,
+}
+
+impl ClockSync {
+    /// Creates a synchronizer sending messages with the local `ssrc`.
+    pub fn new(ssrc: u32) -> Self {
+        Self {
+            ssrc,
+            pending: None,
+            offset: None,
+            latency: None,
+        }
+    }
+
+    /// Converts a local monotonic time in microseconds to an AppleMIDI timestamp.
+    pub fn timestamp_from_us(us: u64) -> u64 {
+        us / TIMESTAMP_TICK_US
+    }
+
+    /// Starts an exchange and returns the count 0 message to send.
+    ///
+    /// # Arguments
+    ///
+    /// * `now_us` - Local monotonic time in microseconds
+    pub fn start(&mut self, now_us: u64) -> ClockSyncMessage {
+        let t1 = Self::timestamp_from_us(now_us);
+        self.pending = Some(t1);
+        ClockSyncMessage {
+            ssrc: self.ssrc,
+            count: 0,
+            timestamps: [t1, 0, 0],
+        }
+    }
+
+    /// # Processes a received `CK` message.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The received message
+    /// * `now_us` - Local monotonic time in microseconds at which it arrived
+    ///
+    /// # Returns
+    ///
+    /// The reply to send, if the exchange continues. Count 1 replies to an exchange
+    /// this side did not start, and any count above 2, are ignored.
+    pub fn handle(&mut self, message: &ClockSyncMessage, now_us: u64) -> Option<ClockSyncMessage> {
+        let now = Self::timestamp_from_us(now_us);
+        let [t1, t2, _] = message.timestamps;
+        match message.count {
+            0 => Some(ClockSyncMessage {
+                ssrc: self.ssrc,
+                count: 1,
+                timestamps: [t1, now, 0],
+            }),
+            1 if self.pending == Some(t1) => {
+                self.pending = None;
+                let (offset, latency) = Self::estimate(t1, t2, now);
+                self.update(offset, latency);
+                Some(ClockSyncMessage {
+                    ssrc: self.ssrc,
+                    count: 2,
+                    timestamps: [t1, t2, now],
+                })
+            }
+            2 => {
+                let (offset, latency) = Self::estimate(t1, t2, message.timestamps[2]);
+                // The initiator's clock is the remote one here
+                self.update(offset.wrapping_neg(), latency);
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the estimated peer clock minus local clock, in microseconds, once an
+    /// exchange has completed.
+    pub fn offset_us(&self) -> Option<i64> {
+        self.offset
+            .map(|offset| offset.saturating_mul(TIMESTAMP_TICK_US as i64))
+    }
+
+    /// Returns the estimated one-way latency in microseconds, once an exchange has
+    /// completed.
+    pub fn latency_us(&self) -> Option<u64> {
+        self.latency
+            .map(|latency| latency.saturating_mul(TIMESTAMP_TICK_US))
+    }
+
+    /// Translates a time on the peer's clock into local time, both in microseconds.
+    ///
+    /// Returns `None` until an exchange has completed.
+    pub fn remote_to_local_us(&self, remote_us: u64) -> Option<u64> {
+        let offset_us = self.offset_us()?;
+        Some(remote_us.saturating_add_signed(offset_us.saturating_neg()))
+    }
+
+    /// Returns the offset (initiator view, in ticks) and latency of one exchange.
+    fn estimate(t1: u64, t2: u64, t3: u64) -> (i64, u64) {
+        let round_trip = t3.wrapping_sub(t1);
+        let latency = round_trip / 2;
+        let offset = t2.wrapping_sub(t1.wrapping_add(latency)) as i64;
+        (offset, latency)
+    }
+
+    fn update(&mut self, offset: i64, latency: u64) {
+        const GAIN_SHIFT: u32 = 3;
+        self.offset = Some(match self.offset {
+            Some(current) => current.wrapping_add(offset.wrapping_sub(current) >> GAIN_SHIFT),
+            None => offset,
+        });
+        self.latency = Some(match self.latency {
+            Some(current) => {
+                let delta = latency as i64 - current as i64;
+                (current as i64 + (delta >> GAIN_SHIFT)) as u64
+            }
+            None => latency,
+        });
+    }
+}
+
+/// # UDP port a session message travels on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    /// The session control port
+    Control,
+    /// The data port (control port + 1), which also carries RTP-MIDI packets
+    Data,
+}
+
+/// # Progress of a session handshake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionState {
+    /// No session in progress
+    Idle,
+    /// Waiting for the control-channel invitation to be answered
+    ControlPending,
+    /// Control channel accepted, waiting for the data-channel invitation or its answer
+    DataPending,
+    /// Both channels accepted, RTP-MIDI packets can flow
+    Established,
+    /// The peer rejected the invitation
+    Rejected,
+    /// One side ended the session
+    Ended,
+}
+
+/// # A message the caller should send.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outgoing {
+    /// Port to send the message on
+    pub channel: Channel,
+    /// The message to send
+    pub message: Message,
+}
+
+/// # Initiating side of an AppleMIDI session.
+///
+/// Invites a listener on the control channel, then on the data channel, and reports
+/// each message to send. Replies carrying another initiator token are ignored.
+#[derive(Debug, Clone)]
+pub struct SessionInitiator {
+    initiator_token: u32,
+    ssrc: u32,
+    name: String,
+    state: SessionState,
+    remote: Option<SessionExchange>,
+}
+
+impl SessionInitiator {
+    /// Creates an idle initiator.
+    ///
+    /// # Arguments
+    ///
+    /// * `initiator_token` - Random token identifying this invitation attempt
+    /// * `ssrc` - Local SSRC, also used for the RTP-MIDI stream
+    /// * `name` - Session name announced to the listener
+    pub fn new(initiator_token: u32, ssrc: u32, name: impl Into<String>) -> Self {
+        Self {
+            initiator_token,
+            ssrc,
+            name: name.into(),
+            state: SessionState::Idle,
+            remote: None,
+        }
+    }
+
+    /// Starts the handshake and returns the control-channel invitation to send.
+    pub fn start(&mut self) -> Outgoing {
+        self.state = SessionState::ControlPending;
+        self.remote = None;
+        self.invitation(Channel::Control)
+    }
+
+    /// Returns the invitation to resend if the current one went unanswered, or
+    /// `None` if no invitation is pending.
+    pub fn retransmit(&self) -> Option<Outgoing> {
+        match self.state {
+            SessionState::ControlPending => Some(self.invitation(Channel::Control)),
+            SessionState::DataPending => Some(self.invitation(Channel::Data)),
+            _ => None,
+        }
+    }
+
+    /// # Processes a message received from the listener.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - Port the message arrived on
+    /// * `message` - The parsed message
+    ///
+    /// # Returns
+    ///
+    /// The message to send next, if any.
+    pub fn handle(&mut self, channel: Channel, message: &Message) -> Option<Outgoing> {
+        if message.exchange()?.initiator_token != self.initiator_token {
+            return None;
+        }
+
+        match (self.state, channel, message) {
+            (SessionState::ControlPending, Channel::Control, Message::InvitationAccepted(ok)) => {
+                self.remote = Some(ok.clone());
+                self.state = SessionState::DataPending;
+                Some(self.invitation(Channel::Data))
+            }
+            (SessionState::DataPending, Channel::Data, Message::InvitationAccepted(_)) => {
+                self.state = SessionState::Established;
+                None
+            }
+            (
+                SessionState::ControlPending | SessionState::DataPending,
+                _,
+                Message::InvitationRejected(_),
+            ) => {
+                self.state = SessionState::Rejected;
+                None
+            }
+            (_, _, Message::EndSession(by)) if self.is_remote(by.ssrc) => {
+                self.state = SessionState::Ended;
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// Ends the session, returning the `BY` message to send if one was in progress.
+    pub fn end(&mut self) -> Option<Outgoing> {
+        match self.state {
+            SessionState::ControlPending
+            | SessionState::DataPending
+            | SessionState::Established => {
+                self.state = SessionState::Ended;
+                Some(Outgoing {
+                    channel: Channel::Control,
+                    message: Message::EndSession(SessionExchange {
+                        initiator_token: self.initiator_token,
+                        ssrc: self.ssrc,
+                        name: None,
+                    }),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the current handshake state.
+    pub fn state(&self) -> SessionState {
+        self.state
+    }
+
+    /// Returns the listener's SSRC once it has accepted the control invitation.
+    pub fn remote_ssrc(&self) -> Option<u32> {
+        self.remote.as_ref().map(|remote| remote.ssrc)
+    }
+
+    /// Returns the listener's name once it has accepted the control invitation.
+    pub fn remote_name(&self) -> Option<&str> {
+        self.remote
+            .as_ref()
+            .and_then(|remote| remote.name.as_deref())
+    }
+
+    fn is_remote(&self, ssrc: u32) -> bool {
+        self.remote_ssrc() == Some(ssrc)
+    }
+
+    fn invitation(&self, channel: Channel) -> Outgoing {
+        Outgoing {
+            channel,
+            message: Message::Invitation(SessionExchange {
+                initiator_token: self.initiator_token,
+                ssrc: self.ssrc,
+                name: Some(self.name.clone()),
+            }),
+        }
+    }
+}
+
+/// # Listening side of an AppleMIDI session.
+///
+/// Accepts one initiator at a time: invitations from other peers are rejected while
+/// a session is in progress, as are data-channel invitations that were not preceded
+/// by a control-channel one.
+#[derive(Debug, Clone)]
+pub struct SessionListener {
+    ssrc: u32,
+    name: String,
+    accepting: bool,
+    state: SessionState,
+    remote: Option<SessionExchange>,
+}
+
+impl SessionListener {
+    /// Creates an idle listener that accepts invitations.
+    ///
+    /// # Arguments
+    ///
+    /// * `ssrc` - Local SSRC, also used for the RTP-MIDI stream
+    /// * `name` - Name announced to initiators
+    pub fn new(ssrc: u32, name: impl Into<String>) -> Self {
+        Self {
+            ssrc,
+            name: name.into(),
+            accepting: true,
+            state: SessionState::Idle,
+            remote: None,
+        }
+    }
+
+    /// Sets whether new invitations are accepted. An established session is kept.
+    pub fn set_accepting(&mut self, accepting: bool) {
+        self.accepting = accepting;
+    }
+
+    /// # Processes a message received from an initiator.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - Port the message arrived on
+    /// * `message` - The parsed message
+    ///
+    /// # Returns
+    ///
+    /// The reply to send, if any.
+    pub fn handle(&mut self, channel: Channel, message: &Message) -> Option<Outgoing> {
+        match message {
+            Message::Invitation(invitation) => Some(self.answer(channel, invitation)),
+            Message::EndSession(by) if self.is_remote(by) => {
+                self.state = SessionState::Ended;
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// Ends the session, returning the `BY` message to send if one was in progress.
+    pub fn end(&mut self) -> Option<Outgoing> {
+        let remote = self.remote.as_ref()?;
+        match self.state {
+            SessionState::DataPending | SessionState::Established => {
+                let message = Message::EndSession(SessionExchange {
+                    initiator_token: remote.initiator_token,
+                    ssrc: self.ssrc,
+                    name: None,
+                });
+                self.state = SessionState::Ended;
+                Some(Outgoing {
+                    channel: Channel::Control,
+                    message,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the current handshake state.
+    pub fn state(&self) -> SessionState {
+        self.state
+    }
+
+    /// Returns the initiator's SSRC once its control invitation has been accepted.
+    pub fn remote_ssrc(&self) -> Option<u32> {
+        self.remote.as_ref().map(|remote| remote.ssrc)
+    }
+
+    /// Returns the initiator's name once its control invitation has been accepted.
+    pub fn remote_name(&self) -> Option<&str> {
+        self.remote
+            .as_ref()
+            .and_then(|remote| remote.name.as_deref())
+    }
+
+    fn is_remote(&self, exchange: &SessionExchange) -> bool {
+        self.remote.as_ref().is_some_and(|remote| {
+            remote.ssrc == exchange.ssrc && remote.initiator_token == exchange.initiator_token
+        })
+    }
+
+    fn answer(&mut self, channel: Channel, invitation: &SessionExchange) -> Outgoing {
+        let in_session = matches!(
+            self.state,
+            SessionState::DataPending | SessionState::Established
+        );
+        let accepted = match channel {
+            // A repeated control invitation from the current peer is answered again
+            Channel::Control if in_session => self.is_remote(invitation),
+            Channel::Control => {
+                if self.accepting {
+                    self.remote = Some(invitation.clone());
+                    self.state = SessionState::DataPending;
+                }
+                self.accepting
+            }
+            Channel::Data => {
+                let accepted = in_session && self.is_remote(invitation);
+                if accepted {
+                    self.state = SessionState::Established;
+                }
+                accepted
+            }
+        };
+
+        let reply = SessionExchange {
+            initiator_token: invitation.initiator_token,
+            ssrc: self.ssrc,
+            name: accepted.then(|| self.name.clone()),
+        };
+        Outgoing {
+            channel,
+            message: if accepted {
+                Message::InvitationAccepted(reply)
+            } else {
+                Message::InvitationRejected(reply)
+            },
+        }
+    }
+}
@@ -0,0 +1,350 @@
+//! # netsync-inspect
+//!
+//! Decodes netsync payloads for debugging a link: each payload is printed with
+//! its [`describe_payload`] decode, whether the slave flow accepts it under the
+//! chosen policy, and what [`verify_payload`] finds in it.
+//!
+//! ```text
+//! netsync-inspect [--strict | --lenient] [--json] [HEX ...]
+//! netsync-inspect [--strict | --lenient] [--json] --file PATH
+//! netsync-inspect [--strict | --lenient] [--json] --listen PORT [--count N]
+//! ```
+//!
+//! Payloads come from the arguments as hex, one payload per argument, or from
+//! standard input, one per line, when no argument is given. `--file` reads a
+//! binary file of concatenated payloads, split by their LEN headers. `--listen`
+//! decodes live traffic sent to a UDP port through a netsync receiver instead.
+//!
+//! Exits with 0 when every payload is accepted, 1 when any is rejected and 2 on
+//! invalid arguments or input.
+
+use std::fs;
+use std::io::{self, BufRead};
+use std::net::UdpSocket;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use rtp_midi_netsync::netsync::net::blocking::Receiver;
+use rtp_midi_netsync::netsync::{
+    describe_payload, payload_len, slave_netsync_flow_with_policy, verify_payload, NetsyncConfig,
+    ParsePolicy,
+};
+use rtp_midi_netsync::util::hexdump;
+use serde_json::json;
+
+const USAGE: &str = "\
+usage: netsync-inspect [--strict | --lenient] [--json] [HEX ...]
+       netsync-inspect [--strict | --lenient] [--json] --file PATH
+       netsync-inspect [--strict | --lenient] [--json] --listen PORT [--count N]";
+
+/// Where the payloads come from.
+#[derive(Debug, PartialEq, Eq)]
+enum Input {
+    /// Hex payloads given as arguments
+    Hex(Vec<String>),
+    /// Hex payloads read from standard input, one per line
+    Stdin,
+    /// A binary file of concatenated payloads
+    File(PathBuf),
+    /// Live traffic on a UDP port, until `count` events if given
+    Listen { port: u16, count: Option<u64> },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Options {
+    input: Input,
+    policy: ParsePolicy,
+    json: bool,
+}
+
+/// Parses the command line, without the program name.
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Options, String> {
+    let mut policy = ParsePolicy::default();
+    let mut json = false;
+    let mut file = None;
+    let mut port = None;
+    let mut count = None;
+    let mut hex = Vec::new();
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("{} needs a value", name));
+        match arg.as_str() {
+            "--strict" => policy = ParsePolicy::Strict,
+            "--lenient" => policy = ParsePolicy::Lenient,
+            "--json" => json = true,
+            "--file" => file = Some(PathBuf::from(value("--file")?)),
+            "--listen" => {
+                let value = value("--listen")?;
+                port = Some(
+                    value
+                        .parse::<u16>()
+                        .map_err(|_| format!("invalid port: {}", value))?,
+                );
+            }
+            "--count" => {
+                let value = value("--count")?;
+                count = Some(
+                    value
+                        .parse::<u64>()
+                        .map_err(|_| format!("invalid count: {}", value))?,
+                );
+            }
+            "-h" | "--help" => return Err(String::new()),
+            _ if arg.starts_with("--") => return Err(format!("unknown option: {}", arg)),
+            _ => hex.push(arg),
+        }
+    }
+
+    let input = match (file, port) {
+        (Some(_), Some(_)) => return Err("--file and --listen are exclusive".into()),
+        (Some(_), None) | (None, Some(_)) if !hex.is_empty() => {
+            return Err("hex payloads cannot be combined with --file or --listen".into())
+        }
+        (Some(path), None) => Input::File(path),
+        (None, Some(port)) => Input::Listen { port, count },
+        (None, None) if hex.is_empty() => Input::Stdin,
+        (None, None) => Input::Hex(hex),
+    };
+    if count.is_some() && !matches!(input, Input::Listen { .. }) {
+        return Err("--count only applies to --listen".into());
+    }
+    Ok(Options {
+        input,
+        policy,
+        json,
+    })
+}
+
+/// Parses hex bytes, ignoring whitespace, commas and `0x` prefixes.
+fn parse_hex(text: &str) -> Result<Vec<u8>, String> {
+    let digits: String = text
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .map(|token| token.trim_start_matches("0x").trim_start_matches("0X"))
+        .collect();
+    if !digits.len().is_multiple_of(2) {
+        return Err(format!("odd number of hex digits: {}", text));
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&digits[i..i + 2], 16).map_err(|_| format!("invalid hex: {}", text))
+        })
+        .collect()
+}
+
+/// Splits concatenated payloads by their LEN headers.
+fn split_payloads(mut bytes: &[u8]) -> Result<Vec<&[u8]>, String> {
+    let total = bytes.len();
+    let mut payloads = Vec::new();
+    while !bytes.is_empty() {
+        let offset = total - bytes.len();
+        let len = payload_len(bytes)
+            .filter(|&len| len <= bytes.len())
+            .ok_or(format!("truncated payload at byte {}", offset))?;
+        let (payload, rest) = bytes.split_at(len);
+        payloads.push(payload);
+        bytes = rest;
+    }
+    Ok(payloads)
+}
+
+/// Prints the decode of one payload and returns whether the policy accepts it.
+fn inspect(index: usize, payload: &[u8], options: &Options) -> bool {
+    let description = describe_payload(payload);
+    let result = slave_netsync_flow_with_policy(payload, options.policy);
+    let report = verify_payload(payload);
+
+    if options.json {
+        let findings: Vec<_> = report
+            .findings
+            .iter()
+            .map(|finding| {
+                json!({
+                    "offset": finding.offset,
+                    "severity": format!("{:?}", finding.severity).to_lowercase(),
+                    "message": finding.kind.to_string(),
+                })
+            })
+            .collect();
+        let hex: String = payload.iter().map(|byte| format!("{:02x}", byte)).collect();
+        let line = json!({
+            "index": index,
+            "payload": hex,
+            "description": description,
+            "policy": format!("{:?}", options.policy).to_lowercase(),
+            "accepted": result.is_ok(),
+            "event": result.as_ref().ok().map(|event| format!("{:?}", event)),
+            "error": result.as_ref().err().map(ToString::to_string),
+            "findings": findings,
+        });
+        println!("{}", line);
+    } else {
+        println!("#{}: {}", index, hexdump(payload, usize::MAX));
+        for line in description.lines() {
+            println!("  {}", line);
+        }
+        match &result {
+            Ok(event) => println!("  accepted: {:?}", event),
+            Err(violation) => println!("  rejected: {}", violation),
+        }
+        for finding in &report.findings {
+            println!("  {}", finding);
+        }
+    }
+    result.is_ok()
+}
+
+/// Decodes the traffic sent to `port` until `count` events, or forever.
+fn listen(port: u16, count: Option<u64>, options: &Options) -> io::Result<()> {
+    let socket = UdpSocket::bind(("0.0.0.0", port))?;
+    eprintln!("listening on {}", socket.local_addr()?);
+    let config = NetsyncConfig {
+        policy: options.policy,
+        ..NetsyncConfig::default()
+    };
+    let mut receiver = Receiver::new(socket, config)?;
+
+    let mut received = 0;
+    while count.is_none_or(|count| received < count) {
+        let event = match receiver.recv() {
+            Ok(event) => event,
+            Err(err) if err.kind() == io::ErrorKind::TimedOut => continue,
+            Err(err) => return Err(err),
+        };
+        received += 1;
+        let position = receiver.position();
+        if options.json {
+            let line = json!({
+                "index": received - 1,
+                "event": format!("{:?}", event),
+                "position_us": position,
+                "metrics": receiver.session().metrics(),
+            });
+            println!("{}", line);
+        } else {
+            match position {
+                Some(us) => println!("#{}: {:?} at {} us", received - 1, event, us),
+                None => println!("#{}: {:?}", received - 1, event),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            if !message.is_empty() {
+                eprintln!("netsync-inspect: {}", message);
+            }
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let payloads = match &options.input {
+        Input::Hex(args) => args.iter().map(|arg| parse_hex(arg)).collect(),
+        Input::Stdin => io::stdin()
+            .lock()
+            .lines()
+            .filter(|line| {
+                line.as_ref().map_or(true, |line| {
+                    !line.trim().is_empty() && !line.starts_with('#')
+                })
+            })
+            .map(|line| parse_hex(&line.map_err(|err| err.to_string())?))
+            .collect(),
+        Input::File(path) => fs::read(path)
+            .map_err(|err| format!("{}: {}", path.display(), err))
+            .and_then(|bytes| {
+                split_payloads(&bytes).map(|payloads| payloads.iter().map(|p| p.to_vec()).collect())
+            }),
+        Input::Listen { port, count } => {
+            return match listen(*port, *count, &options) {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(err) => {
+                    eprintln!("netsync-inspect: {}", err);
+                    ExitCode::FAILURE
+                }
+            };
+        }
+    };
+    let payloads: Vec<Vec<u8>> = match payloads {
+        Ok(payloads) => payloads,
+        Err(message) => {
+            eprintln!("netsync-inspect: {}", message);
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut all_accepted = true;
+    for (index, payload) in payloads.iter().enumerate() {
+        all_accepted &= inspect(index, payload, &options);
+    }
+    if all_accepted {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<Options, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            args(&[]).unwrap(),
+            Options {
+                input: Input::Stdin,
+                policy: ParsePolicy::Lenient,
+                json: false,
+            }
+        );
+        let options = args(&["--strict", "--json", "02F8", "02 FC"]).unwrap();
+        assert_eq!(options.policy, ParsePolicy::Strict);
+        assert!(options.json);
+        assert_eq!(
+            options.input,
+            Input::Hex(vec!["02F8".into(), "02 FC".into()])
+        );
+        assert_eq!(
+            args(&["--listen", "5004", "--count", "3"]).unwrap().input,
+            Input::Listen {
+                port: 5004,
+                count: Some(3)
+            }
+        );
+        assert!(args(&["--listen"]).is_err());
+        assert!(args(&["--listen", "70000"]).is_err());
+        assert!(args(&["--file", "a.bin", "02F8"]).is_err());
+        assert!(args(&["--count", "3"]).is_err());
+        assert!(args(&["--verbose"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(parse_hex("02F8").unwrap(), [0x02, 0xF8]);
+        assert_eq!(parse_hex("02 f8").unwrap(), [0x02, 0xF8]);
+        assert_eq!(parse_hex("0x02, 0xF8").unwrap(), [0x02, 0xF8]);
+        assert!(parse_hex("02F").is_err());
+        assert!(parse_hex("02G8").is_err());
+    }
+
+    #[test]
+    fn test_split_payloads() {
+        let bytes = [0x01, 0xF8, 0x02, 0xF2, 0x00];
+        assert_eq!(
+            split_payloads(&bytes).unwrap(),
+            [&[0x01, 0xF8][..], &[0x02, 0xF2, 0x00][..]]
+        );
+        assert!(split_payloads(&[0x01, 0xF8, 0x03, 0xF2]).is_err());
+    }
+}
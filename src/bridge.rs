@@ -0,0 +1,16 @@
+//! # Bridges to local MIDI
+//!
+//! Glue between netsync sessions and the MIDI APIs of the host:
+//!
+//! * With the `midir` feature, `HardwareBridge` and the byte conversions for
+//!   portable MIDI ports.
+//! * With the `alsa` feature, on Linux, `alsa::SeqBridge` for the ALSA
+//!   sequencer.
+
+#[cfg(all(feature = "alsa", target_os = "linux"))]
+pub mod alsa;
+#[cfg(feature = "midir")]
+mod midir;
+
+#[cfg(feature = "midir")]
+pub use self::midir::{from_midir_bytes, to_midir_bytes, HardwareBridge};
@@ -0,0 +1,339 @@
+//! # ALSA sequencer bridge
+//!
+//! [`SeqBridge`] is an ALSA sequencer client with one output port, to which other
+//! clients subscribe to receive what a [`SlaveSession`] decodes. Events go out as
+//! the sequencer's own event types, encoded by ALSA from the MIDI bytes:
+//!
+//! * Time updates as a cycle of MTC quarter frames (`SND_SEQ_EVENT_QFRAME`)
+//! * MMC commands as SysEx (`SND_SEQ_EVENT_SYSEX`)
+//! * Real-time messages and Song Position Pointers as their control events
+//!
+//! Optionally, the bridge also owns a sequencer queue and keeps it in step with
+//! the session, for clients scheduling on it: see [`SeqBridge::sync_queue`].
+//!
+//! ```no_run
+//! use rtp_midi_netsync::bridge::alsa::SeqBridge;
+//! use rtp_midi_netsync::netsync::SlaveSession;
+//!
+//! # fn main() -> Result<(), alsa::Error> {
+//! let mut bridge = SeqBridge::new(c"netsync", c"sync out")?;
+//! bridge.drive_queue(c"netsync")?;
+//! let mut session = SlaveSession::new();
+//! # let (packet, arrival_us) = (&[][..], 0);
+//! let _ = bridge.feed_packet(&mut session, packet, arrival_us);
+//! bridge.sync_queue(&session, arrival_us)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::ffi::CStr;
+use std::fmt;
+use std::time::Duration;
+
+use ::alsa::seq::{self, Addr, EvQueueControl, Event, EventType, PortCap, PortType, Seq};
+use ::alsa::{Direction, Result};
+
+use crate::error::NetsyncError;
+use crate::midi::{build_midi_list, MidiEvent, MmcCommand, RealtimeMessage};
+use crate::mtc::{
+    smpte_to_quarter_frames_rate, us_to_smpte_rate, FrameRate, MtcFullFrame, VlcTickT,
+};
+use crate::netsync::net::NetsyncEvent;
+use crate::netsync::{NetsyncHandler, PacketStatus, SlaveSession};
+
+/// Size of the MIDI encoder buffer; longer SysEx messages go out in pieces.
+const ENCODER_BUFFER_LENGTH: u32 = 256;
+
+/// Drift between the queue and the session's position beyond which
+/// [`SeqBridge::sync_queue`] moves the queue.
+pub const MAX_QUEUE_DRIFT: Duration = Duration::from_millis(20);
+
+/// # ALSA sequencer client forwarding a netsync link.
+///
+/// As a [`NetsyncHandler`], sends every event it is handed to the subscribers of
+/// its port. Events the sequencer fails to take are counted in
+/// [`SeqBridge::send_errors`]; the next event is sent regardless.
+pub struct SeqBridge {
+    seq: Seq,
+    port: i32,
+    encoder: seq::MidiEvent,
+    queue: Option<i32>,
+    queue_running: bool,
+    frame_rate: FrameRate,
+    send_errors: u64,
+}
+
+impl SeqBridge {
+    /// # Opens a sequencer client named `client_name` with an output port named `port_name`.
+    ///
+    /// Time updates are converted to timecodes at 30fps until told otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Any error from the sequencer, such as when no sequencer device is available.
+    pub fn new(client_name: &CStr, port_name: &CStr) -> Result<Self> {
+        let seq = Seq::open(None, Some(Direction::Playback), false)?;
+        seq.set_client_name(client_name)?;
+        let port = seq.create_simple_port(
+            port_name,
+            PortCap::READ | PortCap::SUBS_READ,
+            PortType::MIDI_GENERIC | PortType::APPLICATION,
+        )?;
+        Ok(Self {
+            seq,
+            port,
+            encoder: seq::MidiEvent::new(ENCODER_BUFFER_LENGTH)?,
+            queue: None,
+            queue_running: false,
+            frame_rate: FrameRate::Fps30,
+            send_errors: 0,
+        })
+    }
+
+    /// # Returns the address of the bridge's port, for subscribing to it.
+    ///
+    /// # Errors
+    ///
+    /// Any error from the sequencer.
+    pub fn addr(&self) -> Result<Addr> {
+        Ok(Addr {
+            client: self.seq.client_id()?,
+            port: self.port,
+        })
+    }
+
+    /// Returns the sequencer handle, such as for subscribing other clients.
+    pub fn seq(&self) -> &Seq {
+        &self.seq
+    }
+
+    /// Sets the frame rate of the quarter frames sent for time updates.
+    pub fn set_frame_rate(&mut self, frame_rate: FrameRate) {
+        self.frame_rate = frame_rate;
+    }
+
+    /// Returns the frame rate of the quarter frames sent for time updates.
+    pub fn frame_rate(&self) -> FrameRate {
+        self.frame_rate
+    }
+
+    /// Returns the number of events the sequencer failed to take.
+    pub fn send_errors(&self) -> u64 {
+        self.send_errors
+    }
+
+    /// # Feeds a packet to `session`, sending the events it carries to the port.
+    ///
+    /// Time updates are converted at the frame rate of the session's tracker.
+    ///
+    /// # Errors
+    ///
+    /// As [`SlaveSession::feed_packet`].
+    pub fn feed_packet(
+        &mut self,
+        session: &mut SlaveSession,
+        packet: &[u8],
+        arrival_us: VlcTickT,
+    ) -> core::result::Result<PacketStatus, NetsyncError> {
+        self.frame_rate = session.tracker().frame_rate();
+        session.feed_packet(packet, arrival_us, self)
+    }
+
+    /// # Sends an event to the port's subscribers.
+    ///
+    /// # Errors
+    ///
+    /// Any error from the sequencer.
+    pub fn send(&mut self, event: &MidiEvent) -> Result<()> {
+        self.send_bytes(&build_midi_list(event))
+    }
+
+    /// # Sends an event decoded by a netsync receiver to the port's subscribers.
+    ///
+    /// A time update goes out as the eight quarter frames encoding it, at the
+    /// bridge's frame rate.
+    ///
+    /// # Errors
+    ///
+    /// Any error from the sequencer.
+    pub fn forward(&mut self, event: &NetsyncEvent) -> Result<()> {
+        let event = match *event {
+            NetsyncEvent::Play => MidiEvent::Mmc(MmcCommand::Play),
+            NetsyncEvent::Stop => MidiEvent::Mmc(MmcCommand::Stop),
+            NetsyncEvent::Locate(position) => MidiEvent::Mmc(MmcCommand::Locate {
+                hour: position.hours,
+                minute: position.minutes,
+                second: position.seconds,
+                frame: position.frames,
+                subframe: 0,
+            }),
+            NetsyncEvent::Mmc(ref command) => MidiEvent::Mmc(command.clone()),
+            NetsyncEvent::TimeUpdate(us) => {
+                let smpte = us_to_smpte_rate(us, self.frame_rate);
+                for qf in smpte_to_quarter_frames_rate(&smpte, self.frame_rate) {
+                    self.send(&MidiEvent::MtcQuarter {
+                        msg_type: qf.frame_type,
+                        value: qf.value,
+                    })?;
+                }
+                return Ok(());
+            }
+            NetsyncEvent::Realtime(message) => MidiEvent::Realtime(message),
+            NetsyncEvent::SongPosition(position) => MidiEvent::SongPosition(position),
+            NetsyncEvent::Other(ref bytes) => return self.send_bytes(bytes),
+        };
+        self.send(&event)
+    }
+
+    /// # Allocates a queue named `name` for [`SeqBridge::sync_queue`] to drive.
+    ///
+    /// Returns the queue, the same one if already allocated.
+    ///
+    /// # Errors
+    ///
+    /// Any error from the sequencer.
+    pub fn drive_queue(&mut self, name: &CStr) -> Result<i32> {
+        if let Some(queue) = self.queue {
+            return Ok(queue);
+        }
+        let queue = self.seq.alloc_named_queue(name)?;
+        self.queue = Some(queue);
+        Ok(queue)
+    }
+
+    /// Returns the queue allocated by [`SeqBridge::drive_queue`].
+    pub fn queue(&self) -> Option<i32> {
+        self.queue
+    }
+
+    /// # Brings the driven queue in step with `session` at session time `now_us`.
+    ///
+    /// Starts or stops the queue with the session's tracker, moves its real time
+    /// to the tracker's position when they drift apart by more than
+    /// [`MAX_QUEUE_DRIFT`], and sets its tempo to the session's beat clock, once
+    /// measured. Does nothing without a queue. Call it after feeding packets, or
+    /// periodically.
+    ///
+    /// # Errors
+    ///
+    /// Any error from the sequencer.
+    pub fn sync_queue(&mut self, session: &SlaveSession, now_us: VlcTickT) -> Result<()> {
+        let Some(queue) = self.queue else {
+            return Ok(());
+        };
+
+        if let Some(bpm) = session.beat_clock().tempo_bpm() {
+            let tempo = self.seq.get_queue_tempo(queue)?;
+            // Microseconds per quarter note
+            tempo.set_tempo((60_000_000.0 / bpm).round() as u32);
+            self.seq.set_queue_tempo(queue, &tempo)?;
+        }
+
+        let tracker = session.tracker();
+        let playing = tracker.is_playing();
+        if playing != self.queue_running {
+            let control = if playing {
+                EventType::Continue
+            } else {
+                EventType::Stop
+            };
+            self.seq.control_queue(queue, control, 0, None)?;
+            self.queue_running = playing;
+        }
+
+        if let Some(position_us) = tracker.position_at(now_us) {
+            let position = Duration::from_micros(position_us);
+            let queue_time = self.seq.get_queue_status(queue)?.get_real_time();
+            let drift = position.abs_diff(queue_time);
+            if drift > MAX_QUEUE_DRIFT {
+                let mut event = Event::new(
+                    EventType::SetposTime,
+                    &EvQueueControl {
+                        queue,
+                        value: position,
+                    },
+                );
+                event.set_source(self.port);
+                event.set_dest(Addr::system_timer());
+                event.set_direct();
+                self.seq.event_output(&mut event)?;
+            }
+        }
+        self.seq.drain_output()?;
+        Ok(())
+    }
+
+    /// Encodes raw MIDI bytes into sequencer events and sends them directly.
+    fn send_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        self.encoder.reset_encode();
+        let mut rest = bytes;
+        while !rest.is_empty() {
+            let (consumed, event) = self.encoder.encode(rest)?;
+            if let Some(mut event) = event {
+                event.set_source(self.port);
+                event.set_subs();
+                event.set_direct();
+                self.seq.event_output_direct(&mut event)?;
+            }
+            if consumed == 0 {
+                break;
+            }
+            rest = &rest[consumed..];
+        }
+        Ok(())
+    }
+
+    fn forward_counted(&mut self, event: NetsyncEvent) {
+        if self.forward(&event).is_err() {
+            self.send_errors += 1;
+        }
+    }
+}
+
+impl fmt::Debug for SeqBridge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The sequencer handles have nothing to show
+        f.debug_struct("SeqBridge")
+            .field("port", &self.port)
+            .field("queue", &self.queue)
+            .field("frame_rate", &self.frame_rate)
+            .field("send_errors", &self.send_errors)
+            .finish_non_exhaustive()
+    }
+}
+
+impl NetsyncHandler for SeqBridge {
+    fn on_play(&mut self) {
+        self.forward_counted(NetsyncEvent::Play);
+    }
+
+    fn on_stop(&mut self) {
+        self.forward_counted(NetsyncEvent::Stop);
+    }
+
+    fn on_locate(&mut self, position: MtcFullFrame) {
+        self.forward_counted(NetsyncEvent::Locate(position));
+    }
+
+    fn on_mmc(&mut self, command: MmcCommand) {
+        self.forward_counted(NetsyncEvent::Mmc(command));
+    }
+
+    fn on_time_update(&mut self, us: VlcTickT) {
+        self.forward_counted(NetsyncEvent::TimeUpdate(us));
+    }
+
+    fn on_realtime(&mut self, message: RealtimeMessage) {
+        self.forward_counted(NetsyncEvent::Realtime(message));
+    }
+
+    fn on_song_position(&mut self, position: u16) {
+        self.forward_counted(NetsyncEvent::SongPosition(position));
+    }
+
+    fn on_other(&mut self, bytes: &[u8]) {
+        if self.send_bytes(bytes).is_err() {
+            self.send_errors += 1;
+        }
+    }
+}
@@ -0,0 +1,251 @@
+//! # midir bridge
+//!
+//! Conversions between [`MidiEvent`] and the raw messages `midir` sends and
+//! receives, and a [`HardwareBridge`] tying a netsync link to local MIDI ports:
+//!
+//! * Events decoded by a [`SlaveSession`] go out on a `MidiOutputConnection`.
+//!   The session assembles quarter frames into timecodes, so each completed cycle
+//!   reaches the port as one MTC Full Frame.
+//! * Messages arriving on a `MidiInput` are sent by a
+//!   [`blocking::Sender`](crate::netsync::net::blocking::Sender), which keeps the
+//!   master session's clock and can run its maintenance on its own thread.
+//!
+//! ```no_run
+//! use std::net::UdpSocket;
+//! use std::sync::Arc;
+//!
+//! use midir::MidiInput;
+//! use rtp_midi_netsync::bridge::HardwareBridge;
+//! use rtp_midi_netsync::netsync::net::blocking::Sender;
+//! use rtp_midi_netsync::netsync::NetsyncConfig;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let socket = UdpSocket::bind("0.0.0.0:0")?;
+//! socket.connect("192.168.1.20:5004")?;
+//! let mut sender = Sender::new(socket, NetsyncConfig::default())?;
+//! sender.start_maintenance()?;
+//!
+//! let input = MidiInput::new("netsync")?;
+//! let port = &input.ports()[0];
+//! let _connection = HardwareBridge::connect_input(input, port, "netsync-in", Arc::new(sender))?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+
+use midir::{
+    ConnectError, MidiInput, MidiInputConnection, MidiInputPort, MidiOutputConnection, SendError,
+};
+
+use crate::error::{NetsyncError, ParseError};
+use crate::midi::{build_midi_list, parse_midi_list, MidiEvent, MmcCommand, RealtimeMessage};
+use crate::mtc::{us_to_smpte_rate, FrameRate, MtcFullFrame, VlcTickT};
+use crate::netsync::net::blocking::Sender;
+use crate::netsync::net::NetsyncEvent;
+use crate::netsync::{NetsyncHandler, PacketStatus, SlaveSession};
+
+/// # Encodes an event as one MIDI message for `MidiOutputConnection::send`.
+///
+/// The same bytes as [`build_midi_list`].
+pub fn to_midir_bytes(event: &MidiEvent) -> Vec<u8> {
+    build_midi_list(event)
+}
+
+/// # Decodes one MIDI message received by a `MidiInput` callback.
+///
+/// Recognises the same messages as [`parse_midi_list`]; any other message becomes
+/// `MidiEvent::Other`.
+///
+/// # Errors
+///
+/// - `ParseError::EmptyBuffer` if `bytes` is empty
+pub fn from_midir_bytes(bytes: &[u8]) -> Result<MidiEvent, ParseError> {
+    parse_midi_list(bytes, bytes.len())
+}
+
+/// # Netsync link bridged to local MIDI ports.
+///
+/// As a [`NetsyncHandler`], sends every event it is handed to its output port.
+/// Messages the port fails to send are counted in
+/// [`HardwareBridge::send_errors`]; the next event is sent regardless.
+pub struct HardwareBridge {
+    output: MidiOutputConnection,
+    frame_rate: FrameRate,
+    send_errors: u64,
+}
+
+impl HardwareBridge {
+    /// Creates a bridge sending to `output`, converting time updates to
+    /// timecodes at 30fps until told otherwise.
+    pub fn new(output: MidiOutputConnection) -> Self {
+        Self {
+            output,
+            frame_rate: FrameRate::Fps30,
+            send_errors: 0,
+        }
+    }
+
+    /// Sets the frame rate of the Full Frames sent for time updates.
+    pub fn set_frame_rate(&mut self, frame_rate: FrameRate) {
+        self.frame_rate = frame_rate;
+    }
+
+    /// Returns the frame rate of the Full Frames sent for time updates.
+    pub fn frame_rate(&self) -> FrameRate {
+        self.frame_rate
+    }
+
+    /// Returns the number of messages the output port failed to send.
+    pub fn send_errors(&self) -> u64 {
+        self.send_errors
+    }
+
+    /// # Feeds a packet to `session`, sending the events it carries to the output port.
+    ///
+    /// Time updates are converted at the frame rate of the session's tracker.
+    ///
+    /// # Errors
+    ///
+    /// As [`SlaveSession::feed_packet`].
+    pub fn feed_packet(
+        &mut self,
+        session: &mut SlaveSession,
+        packet: &[u8],
+        arrival_us: VlcTickT,
+    ) -> Result<PacketStatus, NetsyncError> {
+        self.frame_rate = session.tracker().frame_rate();
+        session.feed_packet(packet, arrival_us, self)
+    }
+
+    /// # Sends an event to the output port.
+    ///
+    /// # Errors
+    ///
+    /// Any error from the port.
+    pub fn send(&mut self, event: &MidiEvent) -> Result<(), SendError> {
+        self.output.send(&to_midir_bytes(event))
+    }
+
+    /// # Sends an event decoded by a netsync receiver to the output port.
+    ///
+    /// A time update goes out as an MTC Full Frame at the bridge's frame rate.
+    ///
+    /// # Errors
+    ///
+    /// Any error from the port.
+    pub fn forward(&mut self, event: &NetsyncEvent) -> Result<(), SendError> {
+        let event = match *event {
+            NetsyncEvent::Play => MidiEvent::Mmc(MmcCommand::Play),
+            NetsyncEvent::Stop => MidiEvent::Mmc(MmcCommand::Stop),
+            NetsyncEvent::Locate(position) => MidiEvent::Mmc(MmcCommand::Locate {
+                hour: position.hours,
+                minute: position.minutes,
+                second: position.seconds,
+                frame: position.frames,
+                subframe: 0,
+            }),
+            NetsyncEvent::Mmc(ref command) => MidiEvent::Mmc(command.clone()),
+            NetsyncEvent::TimeUpdate(us) => {
+                let smpte = us_to_smpte_rate(us, self.frame_rate);
+                MidiEvent::MtcFull {
+                    hour: smpte.hours,
+                    minute: smpte.minutes,
+                    second: smpte.seconds,
+                    frame: smpte.frames,
+                }
+            }
+            NetsyncEvent::Realtime(message) => MidiEvent::Realtime(message),
+            NetsyncEvent::SongPosition(position) => MidiEvent::SongPosition(position),
+            NetsyncEvent::Other(ref bytes) => return self.output.send(bytes),
+        };
+        self.send(&event)
+    }
+
+    /// # Connects `input` to `port`, sending what arrives through `sender`.
+    ///
+    /// Uses [`HardwareBridge::input_callback`]. Closing the connection returns
+    /// the sender.
+    ///
+    /// # Errors
+    ///
+    /// Any error from connecting to the port.
+    pub fn connect_input(
+        input: MidiInput,
+        port: &MidiInputPort,
+        port_name: &str,
+        sender: Arc<Sender>,
+    ) -> Result<MidiInputConnection<Arc<Sender>>, ConnectError<MidiInput>> {
+        input.connect(port, port_name, Self::input_callback, sender)
+    }
+
+    /// # `MidiInput` callback sending each message through `sender`.
+    ///
+    /// For inputs connected otherwise than by [`HardwareBridge::connect_input`],
+    /// such as virtual ports. Messages the sender does not send, such as notes,
+    /// and socket errors are dropped: the input thread has nowhere to report them.
+    /// Quarter frames are sent one by one, as the slave session reassembles them.
+    pub fn input_callback(_stamp_us: u64, bytes: &[u8], sender: &mut Arc<Sender>) {
+        if let Ok(event) = from_midir_bytes(bytes) {
+            let _ = sender.send_event(&event);
+        }
+    }
+
+    /// Returns the output connection.
+    pub fn into_output(self) -> MidiOutputConnection {
+        self.output
+    }
+
+    fn forward_counted(&mut self, event: NetsyncEvent) {
+        if self.forward(&event).is_err() {
+            self.send_errors += 1;
+        }
+    }
+}
+
+impl fmt::Debug for HardwareBridge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The connection has nothing to show
+        f.debug_struct("HardwareBridge")
+            .field("frame_rate", &self.frame_rate)
+            .field("send_errors", &self.send_errors)
+            .finish_non_exhaustive()
+    }
+}
+
+impl NetsyncHandler for HardwareBridge {
+    fn on_play(&mut self) {
+        self.forward_counted(NetsyncEvent::Play);
+    }
+
+    fn on_stop(&mut self) {
+        self.forward_counted(NetsyncEvent::Stop);
+    }
+
+    fn on_locate(&mut self, position: MtcFullFrame) {
+        self.forward_counted(NetsyncEvent::Locate(position));
+    }
+
+    fn on_mmc(&mut self, command: MmcCommand) {
+        self.forward_counted(NetsyncEvent::Mmc(command));
+    }
+
+    fn on_time_update(&mut self, us: VlcTickT) {
+        self.forward_counted(NetsyncEvent::TimeUpdate(us));
+    }
+
+    fn on_realtime(&mut self, message: RealtimeMessage) {
+        self.forward_counted(NetsyncEvent::Realtime(message));
+    }
+
+    fn on_song_position(&mut self, position: u16) {
+        self.forward_counted(NetsyncEvent::SongPosition(position));
+    }
+
+    fn on_other(&mut self, bytes: &[u8]) {
+        if self.output.send(bytes).is_err() {
+            self.send_errors += 1;
+        }
+    }
+}
@@ -1,33 +1,72 @@
-use std::fmt;
+use core::fmt;
 
+#[cfg(feature = "alloc")]
+use crate::netsync::{Finding, ParsePolicy};
+use crate::util::hexdump;
+
+#[cfg(feature = "alloc")]
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
 pub enum Error {
     Parse(ParseError),
     Mtc(MtcError),
-    NetsyncError(NetsyncError),
+    NetsyncError(FlowError),
+    AppleMidi(AppleMidiError),
+    Policy(PolicyViolation),
 }
 
+#[cfg(feature = "alloc")]
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Error::Parse(e) => write!(f, "Parse error: {}", e),
             Error::Mtc(e) => write!(f, "MTC error: {}", e),
             Error::NetsyncError(e) => write!(f, "Netsync flow error: {}", e),
+            Error::AppleMidi(e) => write!(f, "AppleMIDI error: {}", e),
+            Error::Policy(e) => write!(f, "Payload rejected: {}", e),
         }
     }
 }
 
+#[cfg(feature = "alloc")]
+impl Error {
+    /// Returns `true` if the caller can carry on after the error, `false` if it
+    /// should raise an alarm and stop.
+    ///
+    /// Recoverable errors, such as a truncated or malformed packet, only affect
+    /// the input they were returned for. Fatal errors, such as a peer using an
+    /// unsupported protocol version or a session that has failed, affect all
+    /// further input. See `is_recoverable` of each error type for its variants;
+    /// policy violations are always recoverable.
+    pub fn is_recoverable(&self) -> bool {
+        match self {
+            Error::Parse(e) => e.is_recoverable(),
+            Error::Mtc(e) => e.is_recoverable(),
+            Error::NetsyncError(e) => e.is_recoverable(),
+            Error::AppleMidi(e) => e.is_recoverable(),
+            Error::Policy(_) => true,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Error::Parse(e) => Some(e),
             Error::Mtc(e) => Some(e),
             Error::NetsyncError(e) => Some(e),
+            Error::AppleMidi(e) => Some(e),
+            Error::Policy(e) => Some(e),
         }
     }
 }
 
+/// Errors of the MIDI and payload parsers.
+///
+/// Each variant has a stable numeric [code](ParseError::code) in the range 100-199.
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
 pub enum ParseError {
     /// Buffer is empty or length is zero
     EmptyBuffer,
@@ -35,8 +74,10 @@ pub enum ParseError {
     BufferTooSmall { requested: usize, available: usize },
     /// Insufficient data to parse payload header (need at least 1 byte)
     InsufficientHeaderData,
-    /// Invalid or corrupted MIDI data at specified position
+    /// Invalid or corrupted MIDI data, `position` counting from the start of the MIDI list
     InvalidMidiData { position: usize, byte: u8 },
+    /// RTP header carries a version other than 2
+    UnsupportedRtpVersion(u8),
 }
 
 impl fmt::Display for ParseError {
@@ -65,70 +106,403 @@ impl fmt::Display for ParseError {
                     position, byte
                 )
             }
+            ParseError::UnsupportedRtpVersion(version) => {
+                write!(f, "Unsupported RTP version {}, expected 2", version)
+            }
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for ParseError {}
 
+/// Number of bytes [`ParseError::with_context`] keeps from the parsed buffer.
+pub const PARSE_CONTEXT_LENGTH: usize = 16;
+
+impl ParseError {
+    /// Attaches the bytes around the error, for logging a rejected buffer.
+    ///
+    /// Keeps up to [`PARSE_CONTEXT_LENGTH`] bytes of `buf`, centred on the position
+    /// of `InvalidMidiData` and from the start of `buf` for other errors. `buf`
+    /// must be the buffer the error's positions count from: the MIDI list for
+    /// errors of the MIDI parsers.
+    ///
+    /// Errors are returned without context by default, so that parsing never pays
+    /// for the copy.
+    pub fn with_context(self, buf: &[u8]) -> ParseErrorContext {
+        let center = match self {
+            ParseError::InvalidMidiData { position, .. } => position,
+            _ => 0,
+        };
+        let start = center
+            .saturating_sub(PARSE_CONTEXT_LENGTH / 2)
+            .min(buf.len().saturating_sub(PARSE_CONTEXT_LENGTH));
+        let end = buf.len().min(start + PARSE_CONTEXT_LENGTH);
+
+        let mut snippet = [0; PARSE_CONTEXT_LENGTH];
+        snippet[..end - start].copy_from_slice(&buf[start..end]);
+        ParseErrorContext {
+            error: self,
+            offset: start,
+            buffer_len: buf.len(),
+            snippet,
+            snippet_len: end - start,
+        }
+    }
+
+    /// Returns the stable numeric code of the variant, for logs and tooling.
+    ///
+    /// | Code | Variant                  |
+    /// |------|--------------------------|
+    /// | 100  | `EmptyBuffer`            |
+    /// | 101  | `BufferTooSmall`         |
+    /// | 102  | `InsufficientHeaderData` |
+    /// | 103  | `InvalidMidiData`        |
+    /// | 104  | `UnsupportedRtpVersion`  |
+    ///
+    /// Codes are unique across `ParseError`, `MtcError` and `NetsyncError`. New
+    /// variants take the next free code and codes of removed variants are never reused.
+    pub fn code(&self) -> u16 {
+        match self {
+            ParseError::EmptyBuffer => 100,
+            ParseError::BufferTooSmall { .. } => 101,
+            ParseError::InsufficientHeaderData => 102,
+            ParseError::InvalidMidiData { .. } => 103,
+            ParseError::UnsupportedRtpVersion(_) => 104,
+        }
+    }
+
+    /// Returns the variant with the given [code](ParseError::code).
+    ///
+    /// The code does not record the fields of a variant, which are returned as 0.
+    pub fn from_code(code: u16) -> Option<Self> {
+        Some(match code {
+            100 => ParseError::EmptyBuffer,
+            101 => ParseError::BufferTooSmall {
+                requested: 0,
+                available: 0,
+            },
+            102 => ParseError::InsufficientHeaderData,
+            103 => ParseError::InvalidMidiData {
+                position: 0,
+                byte: 0,
+            },
+            104 => ParseError::UnsupportedRtpVersion(0),
+            _ => return None,
+        })
+    }
+
+    /// Returns `true` if the caller can carry on after the error.
+    ///
+    /// Malformed and truncated input only affects the packet it arrived in, so
+    /// every variant is recoverable except `UnsupportedRtpVersion`: a sender using
+    /// another RTP version is misconfigured and none of its packets can be read.
+    pub fn is_recoverable(&self) -> bool {
+        !matches!(self, ParseError::UnsupportedRtpVersion(_))
+    }
+}
+
+/// A parse error together with the bytes around it.
+///
+/// Returned by [`ParseError::with_context`]. The bytes are held inline, so
+/// capturing them does not allocate. `Display` renders them as hex after the
+/// error, such as `Invalid MIDI data at position 2: 0x90 (bytes 0..5 of 5: F0 7D
+/// 90 40 F7)`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseErrorContext {
+    /// The error
+    pub error: ParseError,
+    /// Position of the first captured byte in the buffer
+    pub offset: usize,
+    /// Length of the whole buffer, of which only the snippet was kept
+    pub buffer_len: usize,
+    snippet: [u8; PARSE_CONTEXT_LENGTH],
+    snippet_len: usize,
+}
+
+impl ParseErrorContext {
+    /// Returns the captured bytes, starting at `offset` in the buffer.
+    pub fn snippet(&self) -> &[u8] {
+        &self.snippet[..self.snippet_len]
+    }
+}
+
+impl fmt::Display for ParseErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (bytes {}..{} of {}: {})",
+            self.error,
+            self.offset,
+            self.offset + self.snippet_len,
+            self.buffer_len,
+            hexdump(self.snippet(), PARSE_CONTEXT_LENGTH)
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseErrorContext {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
 /// Error types for MTC quarter frame processing.
+///
+/// Each variant has a stable numeric [code](MtcError::code) in the range 200-299.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
 pub enum MtcError {
     /// Fewer than 8 quarter frames provided
     IncompleteFrame,
+    /// More than 8 quarter frames provided, not counting repeated pieces
+    TooManyFrames,
     /// Frame type outside valid range (0-7)
     InvalidFrameType,
     /// Value outside valid range or resulting timecode invalid
+    #[deprecated(note = "use the field-specific variants, such as `InvalidFrames`")]
     InvalidValue,
+    /// Timecode text not in `HH:MM:SS:FF` or `HH:MM:SS;FF` form
+    MalformedTimecode,
+    /// Frames field at or above the frame rate, or a frames piece above 15
+    InvalidFrames(u8),
+    /// Seconds field above 59, or a seconds piece above 15
+    InvalidSeconds(u8),
+    /// Minutes field above 59, or a minutes piece above 15
+    InvalidMinutes(u8),
+    /// Hours field above 23, or an hours piece above 15
+    InvalidHours(u8),
+    /// Frame rate other than 30fps non-drop, holding its 2-bit MTC rate code
+    UnsupportedFrameRate(u8),
 }
 
-impl std::fmt::Display for MtcError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for MtcError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             MtcError::IncompleteFrame => {
                 write!(f, "Incomplete MTC frame: need exactly 8 quarter frames")
             }
+            MtcError::TooManyFrames => write!(
+                f,
+                "Too many MTC quarter frames: more than 8 after dropping repeated pieces"
+            ),
             MtcError::InvalidFrameType => write!(f, "Invalid frame type: must be 0-7"),
+            #[allow(deprecated)]
             MtcError::InvalidValue => write!(
                 f,
                 "Invalid value: exceeds valid range or creates invalid timecode"
             ),
+            MtcError::MalformedTimecode => {
+                write!(f, "Malformed timecode: expected HH:MM:SS:FF or HH:MM:SS;FF")
+            }
+            MtcError::InvalidFrames(value) => write!(f, "Invalid frames: {}", value),
+            MtcError::InvalidSeconds(value) => write!(f, "Invalid seconds: {}", value),
+            MtcError::InvalidMinutes(value) => write!(f, "Invalid minutes: {}", value),
+            MtcError::InvalidHours(value) => write!(f, "Invalid hours: {}", value),
+            MtcError::UnsupportedFrameRate(code) => write!(
+                f,
+                "Unsupported frame rate code {}: only 30fps non-drop (3) is supported",
+                code
+            ),
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for MtcError {}
+#[cfg(feature = "std")]
 impl std::error::Error for NetsyncError {}
 
+impl MtcError {
+    /// Returns the stable numeric code of the variant, for logs and tooling.
+    ///
+    /// | Code | Variant                |
+    /// |------|------------------------|
+    /// | 200  | `IncompleteFrame`      |
+    /// | 201  | `InvalidFrameType`     |
+    /// | 202  | `InvalidValue`         |
+    /// | 203  | `MalformedTimecode`    |
+    /// | 204  | `TooManyFrames`        |
+    /// | 205  | `InvalidFrames`        |
+    /// | 206  | `InvalidSeconds`       |
+    /// | 207  | `InvalidMinutes`       |
+    /// | 208  | `InvalidHours`         |
+    /// | 209  | `UnsupportedFrameRate` |
+    ///
+    /// Codes follow the rules of [`ParseError::code`].
+    pub fn code(&self) -> u16 {
+        match self {
+            MtcError::IncompleteFrame => 200,
+            MtcError::InvalidFrameType => 201,
+            #[allow(deprecated)]
+            MtcError::InvalidValue => 202,
+            MtcError::MalformedTimecode => 203,
+            MtcError::TooManyFrames => 204,
+            MtcError::InvalidFrames(_) => 205,
+            MtcError::InvalidSeconds(_) => 206,
+            MtcError::InvalidMinutes(_) => 207,
+            MtcError::InvalidHours(_) => 208,
+            MtcError::UnsupportedFrameRate(_) => 209,
+        }
+    }
+
+    /// Returns the variant with the given [code](MtcError::code).
+    pub fn from_code(code: u16) -> Option<Self> {
+        Some(match code {
+            200 => MtcError::IncompleteFrame,
+            201 => MtcError::InvalidFrameType,
+            #[allow(deprecated)]
+            202 => MtcError::InvalidValue,
+            203 => MtcError::MalformedTimecode,
+            204 => MtcError::TooManyFrames,
+            205 => MtcError::InvalidFrames(0),
+            206 => MtcError::InvalidSeconds(0),
+            207 => MtcError::InvalidMinutes(0),
+            208 => MtcError::InvalidHours(0),
+            209 => MtcError::UnsupportedFrameRate(0),
+            _ => return None,
+        })
+    }
+
+    /// Returns `true` if the caller can carry on after the error.
+    ///
+    /// Always `true`: invalid quarter frames or timecodes only affect the cycle or
+    /// value they are part of.
+    pub fn is_recoverable(&self) -> bool {
+        true
+    }
+}
+
+impl NetsyncError {
+    /// Returns the stable numeric code of the variant, for logs and tooling.
+    ///
+    /// | Code | Variant                       |
+    /// |------|-------------------------------|
+    /// | 300  | `InvalidMasterEvent`          |
+    /// | 301  | `InvalidSlaveEvent`           |
+    /// | 302  | `SessionFailed`               |
+    /// | 303  | `TooShort`                    |
+    /// | 304  | `DeclaredLengthExceedsBuffer` |
+    /// | 305  | `TrailingData`                |
+    /// | 306  | `UnsupportedFlags`            |
+    ///
+    /// Codes follow the rules of [`ParseError::code`].
+    pub fn code(&self) -> u16 {
+        match self {
+            NetsyncError::InvalidMasterEvent => 300,
+            NetsyncError::InvalidSlaveEvent => 301,
+            NetsyncError::SessionFailed => 302,
+            NetsyncError::TooShort { .. } => 303,
+            NetsyncError::DeclaredLengthExceedsBuffer { .. } => 304,
+            NetsyncError::TrailingData { .. } => 305,
+            NetsyncError::UnsupportedFlags { .. } => 306,
+        }
+    }
+
+    /// Returns `true` if the caller can carry on after the error.
+    ///
+    /// Only `SessionFailed` is fatal: a rejected event or payload does not affect
+    /// the next one.
+    pub fn is_recoverable(&self) -> bool {
+        !matches!(self, NetsyncError::SessionFailed)
+    }
+
+    /// Returns the variant with the given [code](NetsyncError::code).
+    pub fn from_code(code: u16) -> Option<Self> {
+        Some(match code {
+            300 => NetsyncError::InvalidMasterEvent,
+            301 => NetsyncError::InvalidSlaveEvent,
+            302 => NetsyncError::SessionFailed,
+            303 => NetsyncError::TooShort { got: 0, min: 0 },
+            304 => NetsyncError::DeclaredLengthExceedsBuffer {
+                declared: 0,
+                available: 0,
+            },
+            305 => NetsyncError::TrailingData { extra: 0 },
+            306 => NetsyncError::UnsupportedFlags { flags: 0 },
+            _ => return None,
+        })
+    }
+}
+
 // Convenience From implementations
+#[cfg(feature = "alloc")]
 impl From<ParseError> for Error {
     fn from(err: ParseError) -> Self {
         Error::Parse(err)
     }
 }
 
+#[cfg(feature = "alloc")]
 impl From<MtcError> for Error {
     fn from(err: MtcError) -> Self {
         Error::Mtc(err)
     }
 }
 
+#[cfg(feature = "alloc")]
 impl From<NetsyncError> for Error {
     fn from(err: NetsyncError) -> Self {
+        Error::NetsyncError(err.into())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<FlowError> for Error {
+    fn from(err: FlowError) -> Self {
         Error::NetsyncError(err)
     }
 }
 
+#[cfg(feature = "alloc")]
+impl From<PolicyViolation> for Error {
+    fn from(err: PolicyViolation) -> Self {
+        Error::Policy(err)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<AppleMidiError> for Error {
+    fn from(err: AppleMidiError) -> Self {
+        Error::AppleMidi(err)
+    }
+}
+
 /// Error types for MTC quarter frame processing.
+///
+/// Each variant has a stable numeric [code](NetsyncError::code) in the range 300-399.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
 pub enum NetsyncError {
     /// Invalid Master Event Entered
     InvalidMasterEvent,
     InvalidSlaveEvent,
+    /// The session stopped after a fatal error and drops every packet
+    SessionFailed,
+    /// The payload is shorter than its header requires
+    TooShort {
+        got: usize,
+        min: usize,
+    },
+    /// The header LEN exceeds the bytes following the header
+    DeclaredLengthExceedsBuffer {
+        declared: usize,
+        available: usize,
+    },
+    /// Bytes follow the MIDI list of LEN bytes without a journal
+    TrailingData {
+        extra: usize,
+    },
+    /// The header sets the Z or P flag, which the slave flow does not support, or
+    /// the J flag in a stream of concatenated payloads
+    UnsupportedFlags {
+        flags: u8,
+    },
 }
 
-impl std::fmt::Display for NetsyncError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for NetsyncError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             NetsyncError::InvalidMasterEvent => {
                 write!(f, "InvalidMasterEvent")
@@ -136,6 +510,170 @@ impl std::fmt::Display for NetsyncError {
             NetsyncError::InvalidSlaveEvent => {
                 write!(f, "InvalidSlaveEvent")
             }
+            NetsyncError::SessionFailed => write!(f, "SessionFailed"),
+            NetsyncError::TooShort { got, min } => {
+                write!(
+                    f,
+                    "Payload too short: {} bytes, expected at least {}",
+                    got, min
+                )
+            }
+            NetsyncError::DeclaredLengthExceedsBuffer {
+                declared,
+                available,
+            } => write!(
+                f,
+                "Declared length {} exceeds the {} bytes available",
+                declared, available
+            ),
+            NetsyncError::TrailingData { extra } => {
+                write!(
+                    f,
+                    "{} trailing bytes after the MIDI list of LEN bytes",
+                    extra
+                )
+            }
+            NetsyncError::UnsupportedFlags { flags } => {
+                write!(f, "Unsupported header flags 0x{:X}", flags)
+            }
+        }
+    }
+}
+
+/// A netsync flow failure together with the parse error that caused it.
+///
+/// Held by [`Error::NetsyncError`], so that `source()` leads from the top-level
+/// error down to the [`ParseError`] that made a payload invalid.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FlowError {
+    /// The error the flow returns
+    pub kind: NetsyncError,
+    /// Why the payload was rejected, when a parse error tells
+    pub cause: Option<ParseError>,
+}
+
+impl core::fmt::Display for FlowError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl FlowError {
+    /// Returns `true` if the caller can carry on after the error.
+    ///
+    /// A flow failure is fatal if its kind or its cause is.
+    pub fn is_recoverable(&self) -> bool {
+        self.kind.is_recoverable() && self.cause.as_ref().is_none_or(ParseError::is_recoverable)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FlowError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.cause.as_ref().map(|e| e as _)
+    }
+}
+
+impl From<NetsyncError> for FlowError {
+    fn from(kind: NetsyncError) -> Self {
+        FlowError { kind, cause: None }
+    }
+}
+
+/// Error types for AppleMIDI session control messages.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum AppleMidiError {
+    /// Message shorter than the fixed fields of its command
+    TooShort { expected: usize, available: usize },
+    /// Message does not start with the 0xFFFF AppleMIDI signature
+    InvalidSignature,
+    /// Two-letter command not recognised
+    UnknownCommand([u8; 2]),
+    /// Protocol version other than 2
+    UnsupportedVersion(u32),
+    /// Session name is not valid UTF-8
+    InvalidName,
+}
+
+impl core::fmt::Display for AppleMidiError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            AppleMidiError::TooShort {
+                expected,
+                available,
+            } => {
+                write!(
+                    f,
+                    "AppleMIDI message needs {} bytes but only {} available",
+                    expected, available
+                )
+            }
+            AppleMidiError::InvalidSignature => write!(f, "Missing AppleMIDI signature 0xFFFF"),
+            AppleMidiError::UnknownCommand(command) => write!(
+                f,
+                "Unknown AppleMIDI command 0x{:02X}{:02X}",
+                command[0], command[1]
+            ),
+            AppleMidiError::UnsupportedVersion(version) => {
+                write!(
+                    f,
+                    "Unsupported AppleMIDI protocol version {}, expected 2",
+                    version
+                )
+            }
+            AppleMidiError::InvalidName => write!(f, "AppleMIDI session name is not valid UTF-8"),
         }
     }
 }
+
+#[cfg(feature = "std")]
+impl std::error::Error for AppleMidiError {}
+
+impl AppleMidiError {
+    /// Returns `true` if the caller can carry on after the error.
+    ///
+    /// Every variant is recoverable except `UnsupportedVersion`: a peer speaking
+    /// another protocol version cannot hold a session.
+    pub fn is_recoverable(&self) -> bool {
+        !matches!(self, AppleMidiError::UnsupportedVersion(_))
+    }
+}
+
+/// Error returned when a payload breaks a rule of the active parse policy.
+#[cfg(feature = "alloc")]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PolicyViolation {
+    /// The policy the payload was checked against
+    pub policy: ParsePolicy,
+    /// The first deviation the policy rejects, with its byte offset
+    pub finding: Finding,
+    /// The strict parser's error for the MIDI list, if it explains the deviation
+    ///
+    /// Positions in a `ParseError::InvalidMidiData` count from the start of the
+    /// MIDI list, whereas the finding's offset counts from the start of the payload.
+    pub cause: Option<ParseError>,
+}
+
+#[cfg(feature = "alloc")]
+impl core::fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:?} policy: {}", self.policy, self.finding)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PolicyViolation {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.cause
+            .as_ref()
+            .map(|cause| cause as &(dyn std::error::Error + 'static))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<PolicyViolation> for NetsyncError {
+    fn from(_: PolicyViolation) -> Self {
+        NetsyncError::InvalidSlaveEvent
+    }
+}
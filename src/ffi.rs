@@ -9,30 +9,92 @@
 //! The FFI layer enables C/C++ applications to:
 //! - Convert MIDI timing events to RTP-MIDI network payloads (master mode)
 //! - Parse RTP-MIDI network payloads back to MIDI events (slave mode)
-//! - Handle MTC (MIDI Time Code) and MMC (MIDI Machine Control) messages
+//! - Handle MTC (MIDI Time Code), MMC (MIDI Machine Control) and beat clock messages
+//! - Convert between microseconds and SMPTE timecode with the same math as Rust,
+//!   at 30fps or at any rate given as a `VlcRtpmidiFrameRate`, and format or parse it
+//!   as text
 //!
 //! ## Usage Pattern
 //!
 //! For master (sender) applications:
 //! 1. Create a `VlcRtpmidiEvent` using helper functions
-//! 2. Call `vlc_rtpmidi_master_netsync_flow_ffi()` to generate network payload
+//! 2. Call `vlc_rtpmidi_master_netsync_flow_ffi()` to generate network payload,
+//!    or `vlc_rtpmidi_master_netsync_flow_multi_ffi()` for several events at once
+//!    and `vlc_rtpmidi_master_netsync_flow_batch_ffi()` for several payloads at once,
+//!    such as the quarter frames `vlc_rtpmidi_quarter_frame_payloads_ffi()` builds
 //! 3. Send the payload over the network
 //!
 //! For slave (receiver) applications:
 //! 1. Receive network payload
-//! 2. Call `vlc_rtpmidi_slave_netsync_flow_ffi()` to parse into `VlcRtpmidiEvent`
+//! 2. Call `vlc_rtpmidi_slave_netsync_flow_ffi()` to parse into `VlcRtpmidiEvent`,
+//!    or `vlc_rtpmidi_slave_netsync_flow_multi_ffi()` for datagrams batching several
+//!    payloads; byte streams such as serial links go through a
+//!    `VlcRtpmidiStreamParser` instead
 //! 3. Process the MIDI event as needed
 //!
 //! ## Memory Safety
 //!
-//! All functions use C-provided buffers and avoid dynamic allocation.
-//! Callers must ensure proper buffer sizing using `vlc_rtpmidi_get_max_payload_size()`.
+//! All functions use C-provided buffers. The single-event master and slave flows,
+//! the MIDI codec, payload validation, timecode conversions and event helpers never
+//! allocate, whatever the event type. Callers must ensure proper buffer sizing using
+//! `vlc_rtpmidi_get_max_payload_size()`.
+//!
+//! The remaining functions allocate:
+//! - `vlc_rtpmidi_master_netsync_flow_multi_ffi()` and
+//!   `vlc_rtpmidi_slave_netsync_flow_multi_ffi()`, for their intermediate event lists
+//! - `vlc_rtpmidi_slave_netsync_flow_dev_ffi()`, for payloads addressed to a single
+//!   device
+//! - The `_new()` functions, for the handle; sessions may also grow their internal
+//!   buffers while processing packets
+//! - Any function rejecting a payload while a log callback is installed
+//! - `vlc_rtpmidi_describe_payload_ffi()`, for the text, bounded by the 4097 bytes
+//!   of the largest payload it decodes
+//!
+//! ## Diagnostics
+//!
+//! Error codes say what failed; a callback installed with
+//! `vlc_rtpmidi_set_log_callback()` also receives a short message saying why, such
+//! as the offending byte of a rejected payload. The reason for the last failure on
+//! each thread can also be read with `vlc_rtpmidi_get_last_error_detail()`, and
+//! `vlc_rtpmidi_describe_payload_ffi()` decodes a captured payload as text.
+//!
+//! ## Symbol Names
+//!
+//! Every exported function starts with `vlc_rtpmidi_`, every type with `VlcRtpmidi`
+//! and every constant with `VLC_RTPMIDI_`, so the library links next to other
+//! RTP-MIDI code in the same process without clashes.
 
-use std::ffi::{c_char, c_int};
-use std::slice;
+use core::cell::RefCell;
+use core::ffi::{c_char, c_int, c_void, CStr};
+use core::fmt::{self, Write};
+use core::mem::{align_of, offset_of, size_of};
+use core::slice;
+use std::collections::VecDeque;
+use std::sync::{PoisonError, RwLock};
 
-use crate::midi::{MidiEvent, MmcCommand};
-use crate::netsync::{master_netsync_flow, slave_netsync_flow};
+use crate::error::{
+    AppleMidiError, Error, FlowError, MtcError, NetsyncError, ParseError, PolicyViolation,
+};
+use crate::midi::{
+    encode_midi, parse_midi_message, set_realtime_sysex_device_id, MidiEvent, MidiEventRef,
+    MmcCommand, MmcSpeed, RealtimeMessage, MMC_LOCATE_LENGTH, MTC_QUARTER_FRAME_LENGTH,
+    SYSEX_DEVICE_ID_BROADCAST,
+};
+use crate::mtc::{
+    quarter_frames_to_smpte, quarter_frames_to_smpte_rate, smpte_to_quarter_frames,
+    smpte_to_quarter_frames_rate, smpte_to_us, smpte_to_us_rate, us_to_smpte, us_to_smpte_rate,
+    FrameRate, MtcFullFrame, MtcQuarterFrame, QuarterFrameAssembler, VlcTickT,
+};
+use crate::netsync::{
+    describe_payload, master_netsync_flow_multi, master_netsync_flow_ref,
+    master_netsync_flow_slice, master_payload_len, peek_event_kind, read_header, rejection_cause,
+    slave_netsync_flow_buf, slave_netsync_flow_opt, slave_netsync_flow_ref,
+    slave_netsync_flow_stream, slave_netsync_flow_with_device, slave_netsync_flow_with_policy,
+    verify_payload, BuildOptions, FindingKind, FullFrameRefreshPolicy, MasterSession, Metrics,
+    NetsyncConfig, NetsyncHandler, PacketStatus, ParsePolicy, Severity, SlaveSession, StreamParser,
+    TrackerConfig, MAX_MIDI_LIST_LENGTH, MAX_PACKET_LENGTH, MAX_PAYLOAD_LENGTH, MAX_TICK_PACKETS,
+};
+use crate::rtp::{RtpHeader, RTP_HEADER_LENGTH};
 
 // ============================================================================
 // FFI TYPE DEFINITIONS
@@ -56,8 +118,103 @@ pub enum VlcRtpmidiEventType {
     MmcPlay = 3,
     /// MMC Locate command (jump to specific time)
     MmcLocate = 4,
+    /// Any other MIDI message, only reported by `vlc_rtpmidi_slave_netsync_flow_raw_ffi()`
+    Raw = 5,
+    /// MMC Deferred Play command (play once a pending Locate completes)
+    MmcDeferredPlay = 6,
+    /// MMC Fast Forward command
+    MmcFastForward = 7,
+    /// MMC Rewind command
+    MmcRewind = 8,
+    /// MMC Record Strobe command
+    MmcRecordStrobe = 9,
+    /// MMC Record Exit command
+    MmcRecordExit = 10,
+    /// MMC Pause command
+    MmcPause = 11,
+    /// MMC Eject command
+    MmcEject = 12,
+    /// MMC Reset command
+    MmcReset = 13,
+    /// Song Position Pointer, in MIDI beats (sixteenth notes) from the song start
+    SongPosition = 14,
+    /// Timing Clock, sent 24 times per quarter note
+    Clock = 15,
+    /// Start playback from the beginning of the song
+    Start = 16,
+    /// Continue playback from the current song position
+    Continue = 17,
+    /// Stop playback, keeping the song position
+    RealtimeStop = 18,
+    /// MMC Variable Play command (play at a signed speed)
+    MmcVariablePlay = 19,
+}
+
+impl TryFrom<u32> for VlcRtpmidiEventType {
+    type Error = VlcRtpmidiErrorCode;
+
+    /// Check a raw `VlcRtpmidiEvent::event_type` value, which C code may set to anything
+    fn try_from(raw: u32) -> Result<Self, Self::Error> {
+        Ok(match raw {
+            0 => VlcRtpmidiEventType::MtcQuarter,
+            1 => VlcRtpmidiEventType::MtcFull,
+            2 => VlcRtpmidiEventType::MmcStop,
+            3 => VlcRtpmidiEventType::MmcPlay,
+            4 => VlcRtpmidiEventType::MmcLocate,
+            5 => VlcRtpmidiEventType::Raw,
+            6 => VlcRtpmidiEventType::MmcDeferredPlay,
+            7 => VlcRtpmidiEventType::MmcFastForward,
+            8 => VlcRtpmidiEventType::MmcRewind,
+            9 => VlcRtpmidiEventType::MmcRecordStrobe,
+            10 => VlcRtpmidiEventType::MmcRecordExit,
+            11 => VlcRtpmidiEventType::MmcPause,
+            12 => VlcRtpmidiEventType::MmcEject,
+            13 => VlcRtpmidiEventType::MmcReset,
+            14 => VlcRtpmidiEventType::SongPosition,
+            15 => VlcRtpmidiEventType::Clock,
+            16 => VlcRtpmidiEventType::Start,
+            17 => VlcRtpmidiEventType::Continue,
+            18 => VlcRtpmidiEventType::RealtimeStop,
+            19 => VlcRtpmidiEventType::MmcVariablePlay,
+            _ => return Err(VlcRtpmidiErrorCode::InvalidEventType),
+        })
+    }
 }
 
+/// MMC commands without data, by event type.
+const MMC_EVENT_COMMANDS: [(VlcRtpmidiEventType, MmcCommand); 10] = [
+    (VlcRtpmidiEventType::MmcStop, MmcCommand::Stop),
+    (VlcRtpmidiEventType::MmcPlay, MmcCommand::Play),
+    (
+        VlcRtpmidiEventType::MmcDeferredPlay,
+        MmcCommand::DeferredPlay,
+    ),
+    (VlcRtpmidiEventType::MmcFastForward, MmcCommand::FastForward),
+    (VlcRtpmidiEventType::MmcRewind, MmcCommand::Rewind),
+    (
+        VlcRtpmidiEventType::MmcRecordStrobe,
+        MmcCommand::RecordStrobe,
+    ),
+    (VlcRtpmidiEventType::MmcRecordExit, MmcCommand::RecordExit),
+    (VlcRtpmidiEventType::MmcPause, MmcCommand::Pause),
+    (VlcRtpmidiEventType::MmcEject, MmcCommand::Eject),
+    (VlcRtpmidiEventType::MmcReset, MmcCommand::Reset),
+];
+
+/// Beat clock real-time messages, by event type.
+const REALTIME_EVENT_MESSAGES: [(VlcRtpmidiEventType, RealtimeMessage); 4] = [
+    (VlcRtpmidiEventType::Clock, RealtimeMessage::Clock),
+    (VlcRtpmidiEventType::Start, RealtimeMessage::Start),
+    (VlcRtpmidiEventType::Continue, RealtimeMessage::Continue),
+    (VlcRtpmidiEventType::RealtimeStop, RealtimeMessage::Stop),
+];
+
+/// Largest Song Position Pointer, the 14 bits the message carries.
+const MAX_SONG_POSITION: u16 = 0x3FFF;
+
+/// Largest MMC Locate subframe, in hundredths of a frame.
+const MAX_SUBFRAME: u8 = 99;
+
 /// C-compatible MIDI event structure
 ///
 /// This structure can hold any supported MIDI event type with a fixed-size
@@ -65,8 +222,11 @@ pub enum VlcRtpmidiEventType {
 #[repr(C)]
 #[derive(Debug, Clone)]
 pub struct VlcRtpmidiEvent {
-    /// Type of MIDI event
-    pub event_type: VlcRtpmidiEventType,
+    /// `VlcRtpmidiEventType` value of the event
+    ///
+    /// A plain integer so that C code storing an out-of-range value gets
+    /// `InvalidEventType` back rather than undefined behavior.
+    pub event_type: u32,
     /// Raw event data (interpretation depends on event_type)
     pub data: [u8; 8], // Maximum size needed for any supported event
     /// Number of valid bytes in the data array
@@ -92,12 +252,543 @@ pub enum VlcRtpmidiErrorCode {
     NullPointer = 4,
     /// Unsupported or malformed event type
     InvalidEventType = 5,
+    /// Not an error: valid payload without MIDI command (LEN=0), output event untouched
+    NoEvent = 6,
+    /// Timecode field out of range (hours 0-23, minutes and seconds 0-59, frames 0-29)
+    InvalidTimecode = 7,
+    /// Quarter frames not in piece order 0-7
+    QuarterFrameOrder = 8,
+    /// Not an error: the packet repeats the last sequence number and was dropped
+    Duplicate = 9,
+    /// Not an error: the packet is older than the last one received and was dropped
+    OutOfOrder = 10,
+    /// Not an error: packet from another SSRC than the session's sender, dropped
+    ForeignSsrc = 11,
+    /// Frame rate value outside `VlcRtpmidiFrameRate`
+    InvalidFrameRate = 12,
+    /// Device ID above 0x7F
+    InvalidDeviceId = 13,
+    /// Structure layout compiled by the caller differs from the library's
+    LayoutMismatch = 14,
+    /// MIDI bytes that cannot be read as a message
+    InvalidMidi = 15,
+    /// Not an error: no complete payload is buffered yet, feed more bytes
+    WouldBlock = 16,
+    /// Payload shorter than its header announces: it may complete with more bytes
+    TruncatedPayload = 17,
+    /// Header flags or LEN inconsistent with the bytes following the header
+    HeaderMismatch = 18,
+    /// SysEx message without its terminator or addressed to an unexpected device
+    MalformedSysex = 19,
+    /// Timecode field of a received message out of range
+    ValueOutOfRange = 20,
+    /// Timecode text not in `HH:MM:SS:FF` or `HH:MM:SS;FF` form
+    MalformedTimecode = 21,
+    /// Session stopped after a fatal error, such as a sender using another RTP version
+    SessionFailed = 22,
+}
+
+impl From<&NetsyncError> for VlcRtpmidiErrorCode {
+    fn from(error: &NetsyncError) -> Self {
+        match error {
+            NetsyncError::InvalidMasterEvent => VlcRtpmidiErrorCode::InvalidMasterEvent,
+            NetsyncError::InvalidSlaveEvent => VlcRtpmidiErrorCode::InvalidSlaveEvent,
+            NetsyncError::SessionFailed => VlcRtpmidiErrorCode::SessionFailed,
+            NetsyncError::TooShort { .. } | NetsyncError::DeclaredLengthExceedsBuffer { .. } => {
+                VlcRtpmidiErrorCode::TruncatedPayload
+            }
+            NetsyncError::TrailingData { .. } | NetsyncError::UnsupportedFlags { .. } => {
+                VlcRtpmidiErrorCode::HeaderMismatch
+            }
+        }
+    }
+}
+
+impl From<&ParseError> for VlcRtpmidiErrorCode {
+    fn from(error: &ParseError) -> Self {
+        match error {
+            ParseError::EmptyBuffer => VlcRtpmidiErrorCode::InvalidSlaveEvent,
+            ParseError::BufferTooSmall { .. } | ParseError::InsufficientHeaderData => {
+                VlcRtpmidiErrorCode::TruncatedPayload
+            }
+            ParseError::InvalidMidiData { .. } => VlcRtpmidiErrorCode::InvalidMidi,
+            ParseError::UnsupportedRtpVersion(_) => VlcRtpmidiErrorCode::HeaderMismatch,
+        }
+    }
+}
+
+impl From<&MtcError> for VlcRtpmidiErrorCode {
+    fn from(error: &MtcError) -> Self {
+        match error {
+            // Quarter frames missing, repeated, out of sequence or past piece 7
+            MtcError::IncompleteFrame | MtcError::InvalidFrameType | MtcError::TooManyFrames => {
+                VlcRtpmidiErrorCode::QuarterFrameOrder
+            }
+            #[allow(deprecated)]
+            MtcError::InvalidValue => VlcRtpmidiErrorCode::InvalidTimecode,
+            MtcError::InvalidFrames(_)
+            | MtcError::InvalidSeconds(_)
+            | MtcError::InvalidMinutes(_)
+            | MtcError::InvalidHours(_)
+            | MtcError::UnsupportedFrameRate(_) => VlcRtpmidiErrorCode::InvalidTimecode,
+            MtcError::MalformedTimecode => VlcRtpmidiErrorCode::MalformedTimecode,
+        }
+    }
+}
+
+impl From<&AppleMidiError> for VlcRtpmidiErrorCode {
+    fn from(error: &AppleMidiError) -> Self {
+        match error {
+            AppleMidiError::TooShort { .. } => VlcRtpmidiErrorCode::TruncatedPayload,
+            AppleMidiError::InvalidSignature | AppleMidiError::UnsupportedVersion(_) => {
+                VlcRtpmidiErrorCode::HeaderMismatch
+            }
+            AppleMidiError::UnknownCommand(_) => VlcRtpmidiErrorCode::InvalidEventType,
+            AppleMidiError::InvalidName => VlcRtpmidiErrorCode::InvalidSlaveEvent,
+        }
+    }
+}
+
+/// Maps a flow failure to the code of its cause, the finer of the two
+impl From<&FlowError> for VlcRtpmidiErrorCode {
+    fn from(error: &FlowError) -> Self {
+        match &error.cause {
+            Some(cause) => VlcRtpmidiErrorCode::from(cause),
+            None => VlcRtpmidiErrorCode::from(&error.kind),
+        }
+    }
+}
+
+impl From<&PolicyViolation> for VlcRtpmidiErrorCode {
+    fn from(violation: &PolicyViolation) -> Self {
+        VlcRtpmidiErrorCode::from(&violation.finding.kind)
+    }
+}
+
+/// The single mapping from the crate's errors to the codes returned over the FFI
+impl From<&Error> for VlcRtpmidiErrorCode {
+    fn from(error: &Error) -> Self {
+        match error {
+            Error::Parse(error) => VlcRtpmidiErrorCode::from(error),
+            Error::Mtc(error) => VlcRtpmidiErrorCode::from(error),
+            Error::NetsyncError(error) => VlcRtpmidiErrorCode::from(error),
+            Error::AppleMidi(error) => VlcRtpmidiErrorCode::from(error),
+            Error::Policy(violation) => VlcRtpmidiErrorCode::from(violation),
+        }
+    }
+}
+
+/// Maps a finding a session's parse policy rejects
+impl From<&FindingKind> for VlcRtpmidiErrorCode {
+    fn from(kind: &FindingKind) -> Self {
+        match kind {
+            FindingKind::EmptyPayload => VlcRtpmidiErrorCode::InvalidSlaveEvent,
+            FindingKind::TruncatedLongHeader | FindingKind::TruncatedMidiList { .. } => {
+                VlcRtpmidiErrorCode::TruncatedPayload
+            }
+            FindingKind::LongHeader { .. }
+            | FindingKind::JournalPresent
+            | FindingKind::DeltaTimePresent
+            | FindingKind::PhantomStatus
+            | FindingKind::EmptyMidiList
+            | FindingKind::TrailingBytes { .. } => VlcRtpmidiErrorCode::HeaderMismatch,
+            FindingKind::MissingStatusByte { .. }
+            | FindingKind::DataByteHighBit { .. }
+            | FindingKind::TruncatedMessage { .. }
+            | FindingKind::ExtraBytes { .. }
+            | FindingKind::PaddedFullFrame { .. }
+            | FindingKind::InvalidLocateLength { .. }
+            | FindingKind::InvalidMmcLength { .. }
+            | FindingKind::UnreadableMessage => VlcRtpmidiErrorCode::InvalidMidi,
+            FindingKind::MissingSysexEnd | FindingKind::NonBroadcastDeviceId { .. } => {
+                VlcRtpmidiErrorCode::MalformedSysex
+            }
+            FindingKind::ValueOutOfRange { .. } | FindingKind::RateBitsInHour { .. } => {
+                VlcRtpmidiErrorCode::ValueOutOfRange
+            }
+            FindingKind::UnusualFrameRate { .. } => VlcRtpmidiErrorCode::InvalidFrameRate,
+            FindingKind::Unrecognized => VlcRtpmidiErrorCode::InvalidEventType,
+        }
+    }
+}
+
+/// C-compatible SMPTE frame rate
+///
+/// The values are the MTC rate codes of `mtc::FrameRate`. Functions and structures
+/// taking a rate read it as a plain integer, so that an out-of-range value is
+/// reported as `InvalidFrameRate` instead of being undefined behavior.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VlcRtpmidiFrameRate {
+    /// 24 frames per second (film)
+    Fps24 = 0,
+    /// 25 frames per second (PAL/EBU)
+    Fps25 = 1,
+    /// 30000/1001 frames per second, drop-frame (NTSC)
+    Fps2997Df = 2,
+    /// 30 frames per second, non-drop frame
+    Fps30Ndf = 3,
+}
+
+/// C-compatible SMPTE timecode (30fps non-drop frame unless a rate is given)
+///
+/// Mirrors `MtcFullFrame` field for field.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct VlcRtpmidiSmpteTime {
+    /// Hours (0-23)
+    pub hours: u8,
+    /// Minutes (0-59)
+    pub minutes: u8,
+    /// Seconds (0-59)
+    pub seconds: u8,
+    /// Frames (0-29, below the frame rate)
+    pub frames: u8,
+}
+
+/// C-compatible session configuration
+///
+/// Mirrors the parts of `NetsyncConfig` a C application needs. Start from
+/// `vlc_rtpmidi_netsync_config_default()` and override fields as needed.
+///
+/// New fields are only ever appended. The library reads `struct_size` bytes, so an
+/// application built against an older header keeps working: the fields it does not
+/// know take their default values.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VlcRtpmidiNetsyncConfig {
+    /// `sizeof(VlcRtpmidiNetsyncConfig)` as compiled by the application
+    pub struct_size: u32,
+    /// RTP synchronization source identifier used for outgoing packets
+    pub ssrc: u32,
+    /// Maximum time between Full Frames sent by a master, 0 to disable
+    pub full_frame_interval_us: u64,
+    /// Full Frame after this many quarter-frame cycles without one, 0 to disable
+    pub quarter_frame_cycles: u32,
+    /// Non-zero to reject every payload deviating from the expected format
+    pub strict_parsing: u8,
+    /// `VlcRtpmidiFrameRate` of the Full Frames a master sends and a slave reads
+    pub frame_rate: u32,
+    /// Device ID of the Full Frames and MMC commands a master sends, 0x7F for all
+    pub device_id: u8,
+}
+
+/// C-compatible session counters
+///
+/// Mirrors `netsync::Metrics`; see its documentation for each counter. New
+/// counters are only ever appended at the end, so the offsets of existing ones
+/// never change; `vlc_rtpmidi_metrics_layout_check()` catches a header that is out
+/// of date.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct VlcRtpmidiNetsyncMetrics {
+    pub packets_sent: u64,
+    pub packets_parsed: u64,
+    pub empty_payloads: u64,
+    pub rtp_header_errors: u64,
+    pub too_short_errors: u64,
+    pub length_errors: u64,
+    pub midi_errors: u64,
+    pub timecode_errors: u64,
+    pub duplicates_dropped: u64,
+    pub out_of_order_dropped: u64,
+    pub foreign_ssrc_dropped: u64,
+    pub sequence_gaps: u64,
+    pub packets_lost: u64,
+    pub quarter_frame_cycles: u64,
+    pub full_frames_refreshed: u64,
+    pub resync_requests: u64,
+    /// Last measured drift in microseconds, valid only if `has_last_drift` is non-zero
+    pub last_drift_us: i64,
+    /// Non-zero once a drift measurement is available
+    pub has_last_drift: u8,
+}
+
+/// Severity of a message passed to the log callback
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum VlcRtpmidiLogLevel {
+    /// Expected conditions worth tracing, such as dropped duplicate packets
+    Debug = 0,
+    /// Noteworthy but harmless conditions
+    Info = 1,
+    /// Rejected input: a malformed payload or an invalid event
+    Warning = 2,
+    /// Rejected configuration: the requested object was not created
+    Error = 3,
 }
 
+/// Log callback installed with `vlc_rtpmidi_set_log_callback()`
+///
+/// `level` is a `VlcRtpmidiLogLevel` value and `msg` a null-terminated message,
+/// valid only for the duration of the call. `user` is the pointer given when the
+/// callback was installed.
+pub type VlcRtpmidiLogCallback =
+    Option<unsafe extern "C" fn(level: c_int, msg: *const c_char, user: *mut c_void)>;
+
+/// Version of the C ABI, see `vlc_rtpmidi_abi_version()`
+///
+/// Covers the layout of every `#[repr(C)]` type above and the numbering of every
+/// enum. Bump it with any such change and record the new layout below.
+///
+/// Version 2 declares `VlcRtpmidiEvent::event_type` as `uint32_t` instead of the enum.
+/// Version 3 starts `VlcRtpmidiNetsyncConfig` with its `struct_size`.
+pub const VLC_RTPMIDI_ABI_VERSION: u32 = 3;
+
+/// Largest number of packets `vlc_rtpmidi_master_session_tick()` writes
+pub const VLC_RTPMIDI_MAX_TICK_PACKETS: usize = 2;
+
+/// Buffer size `vlc_rtpmidi_smpte_to_string_ffi()` needs, terminator included
+pub const VLC_RTPMIDI_SMPTE_STRING_SIZE: usize = 12;
+
+// Fails to compile when a layout changes without an ABI version bump
+const _: () = assert!(
+    VLC_RTPMIDI_ABI_VERSION == 3
+        && size_of::<VlcRtpmidiEvent>() == 16
+        && align_of::<VlcRtpmidiEvent>() == 4
+        && size_of::<VlcRtpmidiSmpteTime>() == 4
+        && size_of::<VlcRtpmidiNetsyncConfig>() == 32
+        && align_of::<VlcRtpmidiNetsyncConfig>() == 8
+        && offset_of!(VlcRtpmidiNetsyncConfig, ssrc) == 4
+        && size_of::<VlcRtpmidiNetsyncMetrics>() == 144
+        && size_of::<VlcRtpmidiEventType>() == 4
+        && VlcRtpmidiEventType::MmcReset as u32 == 13
+        && VlcRtpmidiErrorCode::InvalidDeviceId as u32 == 13
+        && VlcRtpmidiFrameRate::Fps30Ndf as u32 == 3,
+    "C ABI changed: bump VLC_RTPMIDI_ABI_VERSION and record the new layout"
+);
+const _: () = assert!(VLC_RTPMIDI_MAX_TICK_PACKETS == MAX_TICK_PACKETS);
+
 // ============================================================================
 // INTERNAL CONVERSION FUNCTIONS
 // ============================================================================
 
+/// Convert internal Metrics to C-compatible VlcRtpmidiNetsyncMetrics
+fn metrics_to_c(metrics: &Metrics) -> VlcRtpmidiNetsyncMetrics {
+    VlcRtpmidiNetsyncMetrics {
+        packets_sent: metrics.packets_sent,
+        packets_parsed: metrics.packets_parsed,
+        empty_payloads: metrics.empty_payloads,
+        rtp_header_errors: metrics.rtp_header_errors,
+        too_short_errors: metrics.too_short_errors,
+        length_errors: metrics.length_errors,
+        midi_errors: metrics.midi_errors,
+        timecode_errors: metrics.timecode_errors,
+        duplicates_dropped: metrics.duplicates_dropped,
+        out_of_order_dropped: metrics.out_of_order_dropped,
+        foreign_ssrc_dropped: metrics.foreign_ssrc_dropped,
+        sequence_gaps: metrics.sequence_gaps,
+        packets_lost: metrics.packets_lost,
+        quarter_frame_cycles: metrics.quarter_frame_cycles,
+        full_frames_refreshed: metrics.full_frames_refreshed,
+        resync_requests: metrics.resync_requests,
+        last_drift_us: metrics.last_drift_us.unwrap_or(0),
+        has_last_drift: metrics.last_drift_us.is_some() as u8,
+    }
+}
+
+/// Convert C-compatible VlcRtpmidiNetsyncConfig to internal NetsyncConfig
+///
+/// The device ID is checked but not part of `NetsyncConfig`: master session
+/// handles apply it to the packets they build.
+///
+/// # Returns
+/// * `Ok(NetsyncConfig)` - Successfully converted configuration
+/// * `Err(VlcRtpmidiErrorCode::InvalidFrameRate)` - `frame_rate` is out of range
+/// * `Err(VlcRtpmidiErrorCode::InvalidDeviceId)` - `device_id` is above 0x7F
+fn c_to_config(c_config: &VlcRtpmidiNetsyncConfig) -> Result<NetsyncConfig, VlcRtpmidiErrorCode> {
+    if c_config.device_id > SYSEX_DEVICE_ID_BROADCAST {
+        log(
+            VlcRtpmidiLogLevel::Error,
+            format_args!(
+                "config rejected: device_id 0x{:02X} is above 0x7F",
+                c_config.device_id
+            ),
+        );
+        return Err(VlcRtpmidiErrorCode::InvalidDeviceId);
+    }
+    let frame_rate = c_to_frame_rate(c_config.frame_rate).inspect_err(|_| {
+        log(
+            VlcRtpmidiLogLevel::Error,
+            format_args!(
+                "config rejected: frame_rate {} is out of range",
+                c_config.frame_rate
+            ),
+        )
+    })?;
+    Ok(NetsyncConfig {
+        ssrc: c_config.ssrc,
+        refresh: FullFrameRefreshPolicy {
+            full_frame_interval_us: Some(c_config.full_frame_interval_us).filter(|&us| us > 0),
+            quarter_frame_cycles: Some(c_config.quarter_frame_cycles).filter(|&n| n > 0),
+        },
+        tracker: TrackerConfig {
+            frame_rate,
+            ..TrackerConfig::default()
+        },
+        policy: if c_config.strict_parsing != 0 {
+            ParsePolicy::Strict
+        } else {
+            ParsePolicy::Lenient
+        },
+        build: BuildOptions::default(),
+    })
+}
+
+/// Size of `VlcRtpmidiNetsyncConfig` in ABI version 3, the first to start with `struct_size`
+const MIN_CONFIG_SIZE: usize = 32;
+
+/// Copy a configuration of `struct_size` bytes, defaulting the fields it lacks
+///
+/// # Returns
+/// * `Ok(VlcRtpmidiNetsyncConfig)` - The configuration, with the library's layout
+/// * `Err(VlcRtpmidiErrorCode::LayoutMismatch)` - `struct_size` is smaller than the
+///   first layout carrying it, or larger with fields this library does not know set
+///
+/// # Safety
+/// `c_config` must point to a readable configuration of `struct_size` bytes.
+unsafe fn read_c_config(
+    c_config: *const VlcRtpmidiNetsyncConfig,
+) -> Result<VlcRtpmidiNetsyncConfig, VlcRtpmidiErrorCode> {
+    const CONFIG_SIZE: usize = size_of::<VlcRtpmidiNetsyncConfig>();
+
+    let struct_size = (*c_config).struct_size as usize;
+    if struct_size < MIN_CONFIG_SIZE {
+        log(
+            VlcRtpmidiLogLevel::Error,
+            format_args!(
+                "config rejected: struct_size {} is below {}",
+                struct_size, MIN_CONFIG_SIZE
+            ),
+        );
+        return Err(VlcRtpmidiErrorCode::LayoutMismatch);
+    }
+    let unknown = slice::from_raw_parts(
+        c_config.cast::<u8>().add(CONFIG_SIZE),
+        struct_size.saturating_sub(CONFIG_SIZE),
+    );
+    if let Some(offset) = unknown.iter().position(|&byte| byte != 0) {
+        log(
+            VlcRtpmidiLogLevel::Error,
+            format_args!(
+                "config rejected: unknown field set at byte {}",
+                CONFIG_SIZE + offset
+            ),
+        );
+        return Err(VlcRtpmidiErrorCode::LayoutMismatch);
+    }
+
+    // Fields beyond `struct_size` keep their defaults
+    let mut config = vlc_rtpmidi_default_netsync_config();
+    std::ptr::copy_nonoverlapping(
+        c_config.cast::<u8>(),
+        (&mut config as *mut VlcRtpmidiNetsyncConfig).cast::<u8>(),
+        CONFIG_SIZE.min(struct_size),
+    );
+    config.struct_size = CONFIG_SIZE as u32;
+    Ok(config)
+}
+
+/// Convert a raw `VlcRtpmidiFrameRate` value to internal FrameRate
+///
+/// # Returns
+/// * `Ok(FrameRate)` - Successfully converted rate
+/// * `Err(VlcRtpmidiErrorCode::InvalidFrameRate)` - The value is out of range
+fn c_to_frame_rate(rate: u32) -> Result<FrameRate, VlcRtpmidiErrorCode> {
+    u8::try_from(rate)
+        .ok()
+        .and_then(FrameRate::from_mtc_code)
+        .ok_or_else(|| {
+            set_error_detail(format_args!("frame rate {} is out of range", rate));
+            VlcRtpmidiErrorCode::InvalidFrameRate
+        })
+}
+
+/// Convert internal FrameRate to C-compatible VlcRtpmidiFrameRate
+fn frame_rate_to_c(rate: FrameRate) -> VlcRtpmidiFrameRate {
+    match rate {
+        FrameRate::Fps24 => VlcRtpmidiFrameRate::Fps24,
+        FrameRate::Fps25 => VlcRtpmidiFrameRate::Fps25,
+        FrameRate::Fps2997Df => VlcRtpmidiFrameRate::Fps2997Df,
+        FrameRate::Fps30 => VlcRtpmidiFrameRate::Fps30Ndf,
+    }
+}
+
+/// Convert C-compatible VlcRtpmidiSmpteTime to internal MtcFullFrame (30fps)
+///
+/// # Arguments
+/// * `c_time` - C timecode structure to convert
+///
+/// # Returns
+/// * `Ok(MtcFullFrame)` - Successfully converted timecode
+/// * `Err(VlcRtpmidiErrorCode::InvalidTimecode)` - A field is out of range
+fn c_to_smpte(c_time: &VlcRtpmidiSmpteTime) -> Result<MtcFullFrame, VlcRtpmidiErrorCode> {
+    c_to_smpte_rate(c_time, FrameRate::Fps30)
+}
+
+/// Convert C-compatible VlcRtpmidiSmpteTime to internal MtcFullFrame, checking
+/// the frames field against `rate`
+fn c_to_smpte_rate(
+    c_time: &VlcRtpmidiSmpteTime,
+    rate: FrameRate,
+) -> Result<MtcFullFrame, VlcRtpmidiErrorCode> {
+    if c_time.hours > 23
+        || c_time.minutes > 59
+        || c_time.seconds > 59
+        || c_time.frames as u32 >= rate.nominal_fps()
+    {
+        set_error_detail(format_args!(
+            "timecode {:02}:{:02}:{:02}:{:02} is out of range at {} fps",
+            c_time.hours,
+            c_time.minutes,
+            c_time.seconds,
+            c_time.frames,
+            rate.nominal_fps()
+        ));
+        return Err(VlcRtpmidiErrorCode::InvalidTimecode);
+    }
+    Ok(MtcFullFrame {
+        hours: c_time.hours,
+        minutes: c_time.minutes,
+        seconds: c_time.seconds,
+        frames: c_time.frames,
+    })
+}
+
+/// Convert the eight C events of a quarter-frame cycle to internal quarter frames
+///
+/// # Returns
+/// * `Ok([MtcQuarterFrame; 8])` - Successfully converted pieces
+/// * `Err(VlcRtpmidiErrorCode::InvalidEventType)` - An event is not a well-formed
+///   MTC Quarter Frame
+fn c_to_quarter_frames(
+    c_events: &[VlcRtpmidiEvent],
+) -> Result<[MtcQuarterFrame; 8], VlcRtpmidiErrorCode> {
+    let mut frames = [MtcQuarterFrame::default(); 8];
+    for (qf, c_event) in frames.iter_mut().zip(c_events) {
+        match c_to_midi_event(c_event)? {
+            MidiEvent::MtcQuarter { msg_type, value } => {
+                *qf = MtcQuarterFrame {
+                    frame_type: msg_type,
+                    value,
+                }
+            }
+            _ => return Err(VlcRtpmidiErrorCode::InvalidEventType),
+        }
+    }
+    Ok(frames)
+}
+
+/// Convert internal MtcFullFrame to C-compatible VlcRtpmidiSmpteTime
+fn smpte_to_c(smpte: &MtcFullFrame) -> VlcRtpmidiSmpteTime {
+    VlcRtpmidiSmpteTime {
+        hours: smpte.hours,
+        minutes: smpte.minutes,
+        seconds: smpte.seconds,
+        frames: smpte.frames,
+    }
+}
+
 /// Convert C-compatible VlcRtpmidiEvent to internal Rust MidiEvent
 ///
 /// Validates the event data and ensures proper field mapping.
@@ -109,30 +800,38 @@ pub enum VlcRtpmidiErrorCode {
 /// * `Ok(MidiEvent)` - Successfully converted event
 /// * `Err(VlcRtpmidiErrorCode)` - Conversion failed due to invalid data
 fn c_to_midi_event(c_event: &VlcRtpmidiEvent) -> Result<MidiEvent, VlcRtpmidiErrorCode> {
-    // Safely extract the raw event type value without triggering UB
-    // We read the memory as a raw u32 to avoid enum validation
-    let event_type_raw: u32 =
-        unsafe { std::ptr::read(&c_event.event_type as *const VlcRtpmidiEventType as *const u32) };
-
-    match event_type_raw {
-        0 => {
-            // VlcRtpmidiEventType::MtcQuarter
+    let event_type_raw = c_event.event_type;
+    let event_type = match VlcRtpmidiEventType::try_from(event_type_raw) {
+        // Raw messages have no event structure to convert
+        Ok(VlcRtpmidiEventType::Raw) | Err(_) => {
+            // Invalid event type, passed in as an unsupported int value
+            //  (error-case exists in the testfile "test_ffi_stress.c")
+            log(
+                VlcRtpmidiLogLevel::Warning,
+                format_args!("event rejected: unknown event type {}", event_type_raw),
+            );
+            return Err(VlcRtpmidiErrorCode::InvalidEventType);
+        }
+        Ok(event_type) => event_type,
+    };
+
+    match event_type {
+        VlcRtpmidiEventType::MtcQuarter => {
             // MTC Quarter Frame: msg_type (0-7) + value (0-15)
             // Must have exactly 2 bytes
             if c_event.data_len != 2 {
-                return Err(VlcRtpmidiErrorCode::InvalidEventType);
+                return Err(invalid_event_data(event_type_raw, c_event.data_len));
             }
             Ok(MidiEvent::MtcQuarter {
                 msg_type: c_event.data[0],
                 value: c_event.data[1],
             })
         }
-        1 => {
-            // VlcRtpmidiEventType::MtcFull
+        VlcRtpmidiEventType::MtcFull => {
             // MTC Full Frame: hour + minute + second + frame
             // Must have exactly 4 bytes
             if c_event.data_len != 4 {
-                return Err(VlcRtpmidiErrorCode::InvalidEventType);
+                return Err(invalid_event_data(event_type_raw, c_event.data_len));
             }
             Ok(MidiEvent::MtcFull {
                 hour: c_event.data[0],
@@ -141,44 +840,132 @@ fn c_to_midi_event(c_event: &VlcRtpmidiEvent) -> Result<MidiEvent, VlcRtpmidiErr
                 frame: c_event.data[3],
             })
         }
-        2 => {
-            // VlcRtpmidiEventType::MmcStop
-            // MMC Stop: no additional data needed
-            // Must have exactly 0 bytes
-            if c_event.data_len != 0 {
+        VlcRtpmidiEventType::MmcLocate => {
+            // MMC Locate: hour + minute + second + frame, optionally + subframe (0-99)
+            // Must have 4 bytes (subframe 0) or 5 bytes
+            let subframe = match c_event.data_len {
+                4 => 0,
+                5 if c_event.data[4] <= MAX_SUBFRAME => c_event.data[4],
+                _ => return Err(invalid_event_data(event_type_raw, c_event.data_len)),
+            };
+            Ok(MidiEvent::Mmc(MmcCommand::Locate {
+                hour: c_event.data[0],
+                minute: c_event.data[1],
+                second: c_event.data[2],
+                frame: c_event.data[3],
+                subframe,
+            }))
+        }
+        VlcRtpmidiEventType::SongPosition => {
+            // Song Position Pointer: beats as a little-endian 16-bit value (0-0x3FFF)
+            // Must have exactly 2 bytes
+            if c_event.data_len != 2 {
+                return Err(invalid_event_data(event_type_raw, c_event.data_len));
+            }
+            let beats = u16::from_le_bytes([c_event.data[0], c_event.data[1]]);
+            if beats > MAX_SONG_POSITION {
+                log(
+                    VlcRtpmidiLogLevel::Warning,
+                    format_args!(
+                        "event rejected: song position {} above {}",
+                        beats, MAX_SONG_POSITION
+                    ),
+                );
                 return Err(VlcRtpmidiErrorCode::InvalidEventType);
             }
-            Ok(MidiEvent::Mmc(MmcCommand::Stop))
+            Ok(MidiEvent::SongPosition(beats))
+        }
+        VlcRtpmidiEventType::MmcVariablePlay => {
+            // MMC Variable Play: the sh + sm + sl bytes of the speed
+            // Must have exactly 3 bytes
+            if c_event.data_len != 3 {
+                return Err(invalid_event_data(event_type_raw, c_event.data_len));
+            }
+            Ok(MidiEvent::Mmc(MmcCommand::VariablePlay {
+                speed: MmcSpeed::from_bytes([c_event.data[0], c_event.data[1], c_event.data[2]]),
+            }))
         }
-        3 => {
-            // VlcRtpmidiEventType::MmcPlay
-            // MMC Play: no additional data needed
+        event_type => {
+            // MmcStop, MmcPlay and the other MMC commands, and beat clock messages
+            // Commands without data: no additional data needed
             // Must have exactly 0 bytes
             if c_event.data_len != 0 {
-                return Err(VlcRtpmidiErrorCode::InvalidEventType);
+                return Err(invalid_event_data(event_type_raw, c_event.data_len));
             }
-            Ok(MidiEvent::Mmc(MmcCommand::Play))
-        }
-        4 => {
-            // VlcRtpmidiEventType::MmcLocate
-            // MMC Locate: hour + minute + second + frame (subframe set to 0)
-            // Must have exactly 4 bytes
-            if c_event.data_len != 4 {
-                return Err(VlcRtpmidiErrorCode::InvalidEventType);
+            if let Some((_, message)) = REALTIME_EVENT_MESSAGES
+                .iter()
+                .find(|(known, _)| *known == event_type)
+            {
+                return Ok(MidiEvent::Realtime(*message));
             }
-            Ok(MidiEvent::Mmc(MmcCommand::Locate {
-                hour: c_event.data[0],
-                minute: c_event.data[1],
-                second: c_event.data[2],
-                frame: c_event.data[3],
-                subframe: 0, // Always 0 for FFI compatibility
-            }))
+            let (_, command) = MMC_EVENT_COMMANDS
+                .iter()
+                .find(|(known, _)| *known == event_type)
+                .expect("MMC event type");
+            Ok(MidiEvent::Mmc(command.clone()))
         }
-        _ => {
-            // Invalid event type, accessed when enum value is passed in as an unsuppprted int value
-            //  (error-case exists in the testfile "test_ffi_stress.c")
-            Err(VlcRtpmidiErrorCode::InvalidEventType)
+    }
+}
+
+/// Log an event whose data length does not match its type
+fn invalid_event_data(event_type_raw: u32, data_len: u8) -> VlcRtpmidiErrorCode {
+    log(
+        VlcRtpmidiLogLevel::Warning,
+        format_args!(
+            "event rejected: data_len {} is invalid for event type {}",
+            data_len, event_type_raw
+        ),
+    );
+    VlcRtpmidiErrorCode::InvalidEventType
+}
+
+/// Check the fields of an event against their ranges
+///
+/// The master flows encode whatever values an event holds, masked to the bits
+/// the message carries; this is the stricter check of
+/// `vlc_rtpmidi_validate_event_ffi()` and of the event setters.
+///
+/// # Returns
+/// * `Ok(())` - Every field is in range
+/// * `Err(VlcRtpmidiErrorCode::InvalidTimecode)` - A timecode field, quarter-frame
+///   piece or subframe is out of range
+fn check_event_fields(event: &MidiEvent) -> Result<(), VlcRtpmidiErrorCode> {
+    match event {
+        MidiEvent::MtcQuarter { msg_type, value } if *msg_type > 7 || *value > 0x0F => {
+            set_error_detail(format_args!(
+                "quarter frame piece {} with value {} is out of range",
+                msg_type, value
+            ));
+            Err(VlcRtpmidiErrorCode::InvalidTimecode)
+        }
+        MidiEvent::MtcFull {
+            hour,
+            minute,
+            second,
+            frame,
+        }
+        | MidiEvent::Mmc(MmcCommand::Locate {
+            hour,
+            minute,
+            second,
+            frame,
+            ..
+        }) => {
+            c_to_smpte(&VlcRtpmidiSmpteTime {
+                hours: *hour,
+                minutes: *minute,
+                seconds: *second,
+                frames: *frame,
+            })?;
+            match event {
+                MidiEvent::Mmc(MmcCommand::Locate { subframe, .. }) if *subframe > MAX_SUBFRAME => {
+                    set_error_detail(format_args!("subframe {} is out of range", subframe));
+                    Err(VlcRtpmidiErrorCode::InvalidTimecode)
+                }
+                _ => Ok(()),
+            }
         }
+        _ => Ok(()),
     }
 }
 
@@ -196,7 +983,7 @@ fn c_to_midi_event(c_event: &VlcRtpmidiEvent) -> Result<MidiEvent, VlcRtpmidiErr
 fn midi_event_to_c(event: &MidiEvent) -> Result<VlcRtpmidiEvent, VlcRtpmidiErrorCode> {
     match event {
         MidiEvent::MtcQuarter { msg_type, value } => Ok(VlcRtpmidiEvent {
-            event_type: VlcRtpmidiEventType::MtcQuarter,
+            event_type: VlcRtpmidiEventType::MtcQuarter as u32,
             data: {
                 let mut data = [0u8; 8];
                 data[0] = *msg_type;
@@ -211,7 +998,7 @@ fn midi_event_to_c(event: &MidiEvent) -> Result<VlcRtpmidiEvent, VlcRtpmidiError
             second,
             frame,
         } => Ok(VlcRtpmidiEvent {
-            event_type: VlcRtpmidiEventType::MtcFull,
+            event_type: VlcRtpmidiEventType::MtcFull as u32,
             data: {
                 let mut data = [0u8; 8];
                 data[0] = *hour;
@@ -222,36 +1009,64 @@ fn midi_event_to_c(event: &MidiEvent) -> Result<VlcRtpmidiEvent, VlcRtpmidiError
             },
             data_len: 4,
         }),
-        MidiEvent::Mmc(MmcCommand::Stop) => Ok(VlcRtpmidiEvent {
-            event_type: VlcRtpmidiEventType::MmcStop,
-            data: [0u8; 8],
-            data_len: 0,
-        }),
-        MidiEvent::Mmc(MmcCommand::Play) => Ok(VlcRtpmidiEvent {
-            event_type: VlcRtpmidiEventType::MmcPlay,
-            data: [0u8; 8],
-            data_len: 0,
-        }),
         MidiEvent::Mmc(MmcCommand::Locate {
             hour,
             minute,
             second,
             frame,
-            subframe: _, // Ignored in C interface
+            subframe,
         }) => Ok(VlcRtpmidiEvent {
-            event_type: VlcRtpmidiEventType::MmcLocate,
+            event_type: VlcRtpmidiEventType::MmcLocate as u32,
             data: {
                 let mut data = [0u8; 8];
                 data[0] = *hour;
                 data[1] = *minute;
                 data[2] = *second;
                 data[3] = *frame;
+                data[4] = *subframe;
                 data
             },
-            data_len: 4,
+            // Same layout as the 4-argument creator unless a subframe is set
+            data_len: if *subframe == 0 { 4 } else { 5 },
         }),
-        // Other event types are not supported in the C interface
-        MidiEvent::Other(_) => Err(VlcRtpmidiErrorCode::InvalidEventType),
+        MidiEvent::Mmc(MmcCommand::VariablePlay { speed }) => Ok(variable_play_event(*speed)),
+        // Shuttle, Search and Masked Write have no event type in the C interface
+        MidiEvent::Mmc(
+            MmcCommand::Shuttle { .. } | MmcCommand::Search { .. } | MmcCommand::MaskedWrite { .. },
+        ) => Err(VlcRtpmidiErrorCode::InvalidEventType),
+        MidiEvent::Mmc(command) => {
+            let (event_type, _) = MMC_EVENT_COMMANDS
+                .iter()
+                .find(|(_, known)| known == command)
+                .expect("MMC command without data");
+            Ok(event_without_data(*event_type))
+        }
+        MidiEvent::Realtime(message) => {
+            let (event_type, _) = REALTIME_EVENT_MESSAGES
+                .iter()
+                .find(|(_, known)| known == message)
+                .expect("beat clock message");
+            Ok(event_without_data(*event_type))
+        }
+        MidiEvent::SongPosition(beats) => Ok(song_position_event(*beats)),
+        // Device discovery and pass-through messages are not supported in the C interface
+        MidiEvent::IdentityRequest { .. }
+        | MidiEvent::IdentityReply(_)
+        | MidiEvent::ManufacturerSysex { .. }
+        | MidiEvent::Other(_) => Err(VlcRtpmidiErrorCode::InvalidEventType),
+    }
+}
+
+/// Convert a borrowed MidiEventRef to C-compatible VlcRtpmidiEvent
+///
+/// Same as `midi_event_to_c()`, for events parsed without allocating.
+fn midi_event_ref_to_c(event: MidiEventRef<'_>) -> Result<VlcRtpmidiEvent, VlcRtpmidiErrorCode> {
+    match event {
+        // Only pass-through messages would be copied to an owned event
+        MidiEventRef::ManufacturerSysex { .. } | MidiEventRef::Other(_) => {
+            Err(VlcRtpmidiErrorCode::InvalidEventType)
+        }
+        event => midi_event_to_c(&MidiEvent::from(event)),
     }
 }
 
@@ -281,6 +1096,8 @@ fn midi_event_to_c(event: &MidiEvent) -> Result<VlcRtpmidiEvent, VlcRtpmidiError
 /// * `0` (Success) - Payload generated successfully
 /// * Non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
 ///
+/// Field ranges are not checked; `vlc_rtpmidi_validate_event_ffi()` does.
+///
 /// # Example Usage (C)
 /// ```c
 /// VlcRtpmidiEvent event = vlc_rtpmidi_create_mtc_quarter_event(0, 5);
@@ -298,166 +1115,3505 @@ pub unsafe extern "C" fn vlc_rtpmidi_master_netsync_flow_ffi(
     buffer_size: usize,
     actual_size: *mut usize,
 ) -> c_int {
-    // Validate all pointers before use
-    if event.is_null() || buffer.is_null() || actual_size.is_null() {
-        return VlcRtpmidiErrorCode::NullPointer as c_int;
-    }
-
-    // Initialize output parameter to safe default
-    *actual_size = 0;
+    with_error_detail(|| {
+        // Validate all pointers before use
+        if event.is_null() || buffer.is_null() || actual_size.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
 
-    // Safely dereference the event pointer
-    let c_event = &*event;
+        // Initialize output parameter to safe default
+        *actual_size = 0;
 
-    // Convert C event structure to internal Rust representation
-    let rust_event = match c_to_midi_event(c_event) {
-        Ok(event) => event,
-        Err(error_code) => return error_code as c_int,
-    };
+        // Safely dereference the event pointer
+        let c_event = &*event;
 
-    // Generate the network payload using core netsync logic
-    let payload = match master_netsync_flow(&rust_event) {
-        Ok(payload) => payload,
-        Err(_) => return VlcRtpmidiErrorCode::InvalidMasterEvent as c_int,
-    };
+        // Convert C event structure to internal Rust representation
+        let rust_event = match c_to_midi_event(c_event) {
+            Ok(event) => event,
+            Err(error_code) => return error_code as c_int,
+        };
 
-    // Ensure the provided buffer is large enough
-    if payload.len() > buffer_size {
-        return VlcRtpmidiErrorCode::BufferTooSmall as c_int;
-    }
+        // Generate the network payload straight into the C-provided buffer
+        let buffer_slice = slice::from_raw_parts_mut(buffer, buffer_size);
+        let payload_len = match write_master_payload(&rust_event, buffer_slice) {
+            Ok(len) => len,
+            Err(error_code) => return error_code as c_int,
+        };
 
-    // Copy payload data to the C-provided buffer
-    let buffer_slice = slice::from_raw_parts_mut(buffer, buffer_size);
-    buffer_slice[..payload.len()].copy_from_slice(&payload);
+        // Report the actual number of bytes written
+        *actual_size = payload_len;
 
-    // Report the actual number of bytes written
-    *actual_size = payload.len();
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
 
-    VlcRtpmidiErrorCode::Success as c_int
+/// Write the payload for `event` to the start of `buffer` without allocating
+///
+/// # Returns
+/// * `Ok(usize)` - Length of the payload
+/// * `Err(VlcRtpmidiErrorCode::InvalidMasterEvent)` - The master flow rejects the event
+/// * `Err(VlcRtpmidiErrorCode::BufferTooSmall)` - `buffer` cannot hold the payload
+fn write_master_payload(
+    event: &MidiEvent,
+    buffer: &mut [u8],
+) -> Result<usize, VlcRtpmidiErrorCode> {
+    let payload_len = master_payload_len(event).map_err(|error| {
+        log_rejected_event("master flow", event);
+        VlcRtpmidiErrorCode::from(&error)
+    })?;
+    if payload_len > buffer.len() {
+        set_error_detail(format_args!(
+            "payload of {} bytes does not fit in a buffer of {}",
+            payload_len,
+            buffer.len()
+        ));
+        return Err(VlcRtpmidiErrorCode::BufferTooSmall);
+    }
+    Ok(master_netsync_flow_slice(event, buffer).expect("event checked"))
 }
 
-/// Slave netsync flow: Parse RTP-MIDI network payload to MIDI event
+/// Master netsync flow: Convert MIDI event to a payload addressed to one device
 ///
-/// This function is used by slave (receiver) applications to parse incoming
-/// network payloads back into MIDI timing events.
+/// Same as `vlc_rtpmidi_master_netsync_flow_ffi()`, except that MTC Full Frames
+/// and MMC commands are addressed to `device_id` rather than to all devices.
+/// Quarter frames carry no device ID and are unaffected.
 ///
 /// # Safety
 /// This function is unsafe because it dereferences raw pointers. Callers must ensure:
-/// - `buffer` points to a readable buffer of at least `buffer_len` bytes
-/// - `event` points to a writable `VlcRtpmidiEvent` structure
-/// - Both pointers remain valid for the duration of the call
+/// - `event` points to a valid, properly initialized `VlcRtpmidiEvent`
+/// - `buffer` points to a writable buffer of at least `buffer_size` bytes
+/// - `actual_size` points to a writable `usize` location
 ///
 /// # Arguments
-/// * `buffer` - Source buffer containing the network payload
-/// * `buffer_len` - Length of the source buffer in bytes
-/// * `event` - Output: parsed MIDI event structure
+/// * `event` - Pointer to the MIDI event to convert
+/// * `device_id` - Target device (0x00-0x7E), or 0x7F for all devices
+/// * `buffer` - Destination buffer for the network payload
+/// * `buffer_size` - Size of the destination buffer in bytes
+/// * `actual_size` - Output: actual number of bytes written to buffer
 ///
 /// # Returns
-/// * `0` (Success) - Event parsed successfully
-/// * Non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+/// * `0` (Success) - Payload generated successfully
+/// * `13` (InvalidDeviceId) - `device_id` is above 0x7F
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
 ///
 /// # Example Usage (C)
 /// ```c
-/// uint8_t payload[] = { /* received network data */ };
-/// VlcRtpmidiEvent event;
-/// int result = vlc_rtpmidi_slave_netsync_flow_ffi(payload, sizeof(payload), &event);
-/// if (result == 0) {
-///     // Process the parsed event
-/// }
+/// VlcRtpmidiEvent play = vlc_rtpmidi_create_mmc_play_event();
+/// uint8_t buffer[16];
+/// size_t actual_size;
+/// int result = vlc_rtpmidi_master_netsync_flow_dev_ffi(&play, 0x05, buffer, sizeof(buffer),
+///                                                       &actual_size);
 /// ```
 #[no_mangle]
-pub unsafe extern "C" fn vlc_rtpmidi_slave_netsync_flow_ffi(
-    buffer: *const u8,
-    buffer_len: usize,
-    event: *mut VlcRtpmidiEvent,
+pub unsafe extern "C" fn vlc_rtpmidi_master_netsync_flow_dev_ffi(
+    event: *const VlcRtpmidiEvent,
+    device_id: u8,
+    buffer: *mut u8,
+    buffer_size: usize,
+    actual_size: *mut usize,
 ) -> c_int {
-    // Validate all pointers before use
-    if buffer.is_null() || event.is_null() {
-        return VlcRtpmidiErrorCode::NullPointer as c_int;
-    }
+    with_error_detail(|| {
+        if event.is_null() || buffer.is_null() || actual_size.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
 
-    // Create a safe slice from the raw buffer pointer
-    let buf = slice::from_raw_parts(buffer, buffer_len);
+        *actual_size = 0;
 
-    // Parse the network payload using core netsync logic
-    let midi_event = match slave_netsync_flow(buf) {
-        Ok(event) => event,
-        Err(_) => return VlcRtpmidiErrorCode::InvalidSlaveEvent as c_int,
-    };
+        if device_id > SYSEX_DEVICE_ID_BROADCAST {
+            return VlcRtpmidiErrorCode::InvalidDeviceId as c_int;
+        }
 
-    // Convert the parsed event to C-compatible format
-    let c_event = match midi_event_to_c(&midi_event) {
-        Ok(event) => event,
-        Err(error_code) => return error_code as c_int,
-    };
+        let rust_event = match c_to_midi_event(&*event) {
+            Ok(event) => event,
+            Err(error_code) => return error_code as c_int,
+        };
 
-    // Write the result to the output parameter
-    *event = c_event;
+        let buffer_slice = slice::from_raw_parts_mut(buffer, buffer_size);
+        let payload_len = match write_master_payload(&rust_event, buffer_slice) {
+            Ok(len) => len,
+            Err(error_code) => return error_code as c_int,
+        };
+        // The master flow always uses the one-octet header
+        set_realtime_sysex_device_id(&mut buffer_slice[1..payload_len], device_id);
+        *actual_size = payload_len;
 
-    VlcRtpmidiErrorCode::Success as c_int
+        VlcRtpmidiErrorCode::Success as c_int
+    })
 }
 
-// ============================================================================
-// UTILITY FUNCTIONS
-// ============================================================================
-
-/// Get the maximum buffer size needed for network payloads
+/// Master netsync flow: Convert several MIDI events to one RTP-MIDI network payload
+///
+/// The events are serialized in order into a single MIDI list. The two-octet
+/// header form is used when the list is longer than 15 bytes.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `events` points to a readable array of at least `event_count` structures
+/// - `buffer` points to a writable buffer of at least `buffer_size` bytes
+/// - `actual_size` and `failed_index` point to writable `size_t` values
+/// - All pointers remain valid for the duration of the call
+///
+/// # Arguments
+/// * `events` - Array of MIDI events to convert
+/// * `event_count` - Number of events in the array
+/// * `buffer` - Destination buffer for the network payload
+/// * `buffer_size` - Size of the destination buffer in bytes
+/// * `actual_size` - Output: actual number of bytes written to buffer
+/// * `failed_index` - Output: index of the event that caused an `InvalidEventType`
+///   or `InvalidMasterEvent` error, or `event_count` when no single event is at fault
+///
+/// # Returns
+/// * `0` (Success) - Payload generated successfully
+/// * `1` (InvalidMasterEvent) - `event_count` is 0, or the event at `failed_index`
+///   makes the MIDI list exceed the 4095 bytes a header can describe
+/// * `5` (InvalidEventType) - The event at `failed_index` is malformed
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+///
+/// # Example Usage (C)
+/// ```c
+/// VlcRtpmidiEvent events[2] = {
+///     vlc_rtpmidi_create_mmc_locate_event(0, 1, 0, 0),
+///     vlc_rtpmidi_create_mmc_play_event(),
+/// };
+/// uint8_t buffer[64];
+/// size_t actual_size, failed_index;
+/// int result = vlc_rtpmidi_master_netsync_flow_multi_ffi(events, 2, buffer, sizeof(buffer),
+///                                                        &actual_size, &failed_index);
+/// if (result == 0) {
+///     // Send buffer[0..actual_size] over network
+/// }
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_master_netsync_flow_multi_ffi(
+    events: *const VlcRtpmidiEvent,
+    event_count: usize,
+    buffer: *mut u8,
+    buffer_size: usize,
+    actual_size: *mut usize,
+    failed_index: *mut usize,
+) -> c_int {
+    with_error_detail(|| {
+        // Validate all pointers before use
+        if events.is_null() || buffer.is_null() || actual_size.is_null() || failed_index.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        // Initialize output parameters to safe defaults
+        *actual_size = 0;
+        *failed_index = event_count;
+
+        if event_count == 0 {
+            return VlcRtpmidiErrorCode::InvalidMasterEvent as c_int;
+        }
+
+        // Convert every event, locating the first one that is invalid or overflows LEN
+        let c_events = slice::from_raw_parts(events, event_count);
+        let mut rust_events = Vec::with_capacity(event_count);
+        let mut midi_len = 0;
+        for (index, c_event) in c_events.iter().enumerate() {
+            let rust_event = match c_to_midi_event(c_event) {
+                Ok(event) => event,
+                Err(error_code) => {
+                    *failed_index = index;
+                    return error_code as c_int;
+                }
+            };
+            midi_len += encode_midi(&rust_event.as_event_ref(), &mut [0; MMC_LOCATE_LENGTH]).len();
+            if midi_len > MAX_MIDI_LIST_LENGTH {
+                log(
+                    VlcRtpmidiLogLevel::Warning,
+                    format_args!(
+                        "master flow: event {} overflows the {}-byte MIDI list",
+                        index, MAX_MIDI_LIST_LENGTH
+                    ),
+                );
+                *failed_index = index;
+                return VlcRtpmidiErrorCode::InvalidMasterEvent as c_int;
+            }
+            rust_events.push(rust_event);
+        }
+
+        // Generate the network payload using core netsync logic
+        let payload = match master_netsync_flow_multi(&rust_events) {
+            Ok(payload) => payload,
+            Err(error) => return VlcRtpmidiErrorCode::from(&error) as c_int,
+        };
+
+        // Ensure the provided buffer is large enough
+        if payload.len() > buffer_size {
+            return VlcRtpmidiErrorCode::BufferTooSmall as c_int;
+        }
+
+        // Copy payload data to the C-provided buffer
+        let buffer_slice = slice::from_raw_parts_mut(buffer, buffer_size);
+        buffer_slice[..payload.len()].copy_from_slice(&payload);
+
+        // Report the actual number of bytes written
+        *actual_size = payload.len();
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Events `vlc_rtpmidi_master_netsync_flow_batch_ffi()` keeps between its checking
+/// and writing passes: a full quarter-frame cycle with room to spare.
+const BATCH_CACHED_EVENTS: usize = 16;
+
+/// Master netsync flow: Convert an array of MIDI events to back-to-back payloads
+///
+/// Each event becomes its own payload, exactly as `vlc_rtpmidi_master_netsync_flow_ffi()`
+/// would produce it, and the payloads are packed one after the other into `buffer`.
+/// Payload `i` starts at `offsets_out[i]` and ends where the next one starts, or at
+/// `*actual_total` for the last one. This saves a boundary crossing per event when
+/// sending bursts such as the eight quarter frames of a cycle.
+///
+/// The call is atomic: every event is checked and the total size computed before
+/// anything is written, so on error `buffer` and `offsets_out` are left untouched.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `events` points to a readable array of at least `count` structures
+/// - `buffer` points to a writable buffer of at least `buffer_size` bytes
+/// - `offsets_out` points to a writable array of at least `count` `size_t` values
+/// - `actual_total` points to a writable `size_t` location
+/// - All pointers remain valid for the duration of the call
+///
+/// # Arguments
+/// * `events` - Array of MIDI events to convert (may be NULL when `count` is 0)
+/// * `count` - Number of events in the array
+/// * `buffer` - Destination buffer for the payloads (may be NULL when `buffer_size` is 0)
+/// * `buffer_size` - Size of the destination buffer in bytes
+/// * `offsets_out` - Output: start offset of each payload in `buffer`
+///   (may be NULL when `count` is 0)
+/// * `actual_total` - Output: total number of bytes written to buffer
+///
+/// # Returns
+/// * `0` (Success) - All payloads generated successfully; an empty batch writes nothing
+/// * `3` (BufferTooSmall) - The payloads together do not fit in `buffer_size` bytes
+/// * Other non-zero - Error code of the first invalid event (see `VlcRtpmidiErrorCode` enum)
+///
+/// # Example Usage (C)
+/// ```c
+/// VlcRtpmidiEvent events[8];
+/// for (uint8_t piece = 0; piece < 8; piece++) {
+///     events[piece] = vlc_rtpmidi_create_mtc_quarter_event(piece, values[piece]);
+/// }
+/// uint8_t buffer[64];
+/// size_t offsets[8], total;
+/// int result = vlc_rtpmidi_master_netsync_flow_batch_ffi(events, 8, buffer, sizeof(buffer),
+///                                                        offsets, &total);
+/// if (result == 0) {
+///     for (size_t i = 0; i < 8; i++) {
+///         size_t end = i + 1 < 8 ? offsets[i + 1] : total;
+///         // Send buffer[offsets[i]..end] over network
+///     }
+/// }
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_master_netsync_flow_batch_ffi(
+    events: *const VlcRtpmidiEvent,
+    count: usize,
+    buffer: *mut u8,
+    buffer_size: usize,
+    offsets_out: *mut usize,
+    actual_total: *mut usize,
+) -> c_int {
+    with_error_detail(|| {
+        // Validate all pointers before use; the arrays may be omitted for an empty batch
+        if actual_total.is_null()
+            || (events.is_null() && count > 0)
+            || (offsets_out.is_null() && count > 0)
+            || (buffer.is_null() && buffer_size > 0)
+        {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        *actual_total = 0;
+
+        if count == 0 {
+            return VlcRtpmidiErrorCode::Success as c_int;
+        }
+
+        // Check every event and size the batch before writing anything, keeping the
+        // first converted events so that a typical burst is only converted once
+        let c_events = slice::from_raw_parts(events, count);
+        let mut converted: [Option<MidiEvent>; BATCH_CACHED_EVENTS] =
+            [const { None }; BATCH_CACHED_EVENTS];
+        let mut total = 0;
+        for (index, c_event) in c_events.iter().enumerate() {
+            let rust_event = match c_to_midi_event(c_event) {
+                Ok(event) => event,
+                Err(error_code) => return error_code as c_int,
+            };
+            total += match master_payload_len(&rust_event) {
+                Ok(len) => len,
+                Err(error) => {
+                    log_rejected_event("master flow", &rust_event);
+                    return VlcRtpmidiErrorCode::from(&error) as c_int;
+                }
+            };
+            if let Some(slot) = converted.get_mut(index) {
+                *slot = Some(rust_event);
+            }
+        }
+        if total > buffer_size {
+            return VlcRtpmidiErrorCode::BufferTooSmall as c_int;
+        }
+
+        // Every event is known to be valid and to fit: write them, converting the
+        // events past the cached ones again
+        let buffer_slice = slice::from_raw_parts_mut(buffer, buffer_size);
+        let offsets = slice::from_raw_parts_mut(offsets_out, count);
+        let mut offset = 0;
+        for (index, (c_event, slot)) in c_events.iter().zip(offsets).enumerate() {
+            let rust_event = match converted.get_mut(index).and_then(Option::take) {
+                Some(event) => event,
+                None => c_to_midi_event(c_event).expect("event checked"),
+            };
+            *slot = offset;
+            offset += master_netsync_flow_slice(&rust_event, &mut buffer_slice[offset..])
+                .expect("event checked");
+        }
+
+        *actual_total = total;
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Master netsync flow: Convert SMPTE timecode to the payloads of its eight quarter frames
+///
+/// Same as `vlc_rtpmidi_smpte_to_quarter_frames_ffi()` followed by
+/// `vlc_rtpmidi_master_netsync_flow_batch_ffi()` on the eight events, and the same
+/// as `netsync::quarter_frame_payloads()` in Rust. The payloads are packed in
+/// transmission order, piece 0 first, with the layout of the batch function.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `input` points to a readable `VlcRtpmidiSmpteTime` structure
+/// - `buffer` points to a writable buffer of at least `buffer_size` bytes
+/// - `offsets_out` points to a writable array of 8 `size_t` values
+/// - `actual_total` points to a writable `size_t` location
+/// - All pointers remain valid for the duration of the call
+///
+/// # Arguments
+/// * `input` - Timecode to send
+/// * `buffer` - Destination buffer for the payloads
+/// * `buffer_size` - Size of the destination buffer in bytes
+/// * `offsets_out` - Output: start offset of each payload in `buffer`
+/// * `actual_total` - Output: total number of bytes written to buffer
+///
+/// # Returns
+/// * `0` (Success) - All eight payloads generated successfully
+/// * `3` (BufferTooSmall) - The payloads together do not fit in `buffer_size` bytes
+/// * `7` (InvalidTimecode) - A field of `input` is out of range
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+///
+/// On error `buffer` and `offsets_out` are left untouched.
+///
+/// # Example Usage (C)
+/// ```c
+/// VlcRtpmidiSmpteTime tc = { .hours = 0, .minutes = 1, .seconds = 0, .frames = 0 };
+/// uint8_t buffer[64];
+/// size_t offsets[8], total;
+/// if (vlc_rtpmidi_quarter_frame_payloads_ffi(&tc, buffer, sizeof(buffer), offsets,
+///                                            &total) == 0) {
+///     // Send the payloads one per quarter frame period, as with the batch function
+/// }
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_quarter_frame_payloads_ffi(
+    input: *const VlcRtpmidiSmpteTime,
+    buffer: *mut u8,
+    buffer_size: usize,
+    offsets_out: *mut usize,
+    actual_total: *mut usize,
+) -> c_int {
+    with_error_detail(|| {
+        // Validate all pointers before use
+        if input.is_null()
+            || offsets_out.is_null()
+            || actual_total.is_null()
+            || (buffer.is_null() && buffer_size > 0)
+        {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        *actual_total = 0;
+
+        let smpte = match c_to_smpte(&*input) {
+            Ok(smpte) => smpte,
+            Err(error_code) => return error_code as c_int,
+        };
+
+        // Every piece has the same length, so the batch is sized up front
+        let events = smpte_to_quarter_frames(&smpte).map(|qf| MidiEventRef::MtcQuarter {
+            msg_type: qf.frame_type,
+            value: qf.value,
+        });
+        let total = events.len() * (1 + MTC_QUARTER_FRAME_LENGTH);
+        if total > buffer_size {
+            return VlcRtpmidiErrorCode::BufferTooSmall as c_int;
+        }
+
+        let buffer_slice = slice::from_raw_parts_mut(buffer, buffer_size);
+        let offsets = slice::from_raw_parts_mut(offsets_out, events.len());
+        let mut offset = 0;
+        for (event, slot) in events.iter().zip(offsets) {
+            *slot = offset;
+            offset += master_netsync_flow_ref(event, &mut buffer_slice[offset..])
+                .expect("quarter frame checked");
+        }
+
+        *actual_total = total;
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Slave netsync flow: Parse RTP-MIDI network payload to MIDI event
+///
+/// This function is used by slave (receiver) applications to parse incoming
+/// network payloads back into MIDI timing events.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `buffer` points to a readable buffer of at least `buffer_len` bytes
+/// - `event` points to a writable `VlcRtpmidiEvent` structure
+/// - Both pointers remain valid for the duration of the call
+///
+/// # Arguments
+/// * `buffer` - Source buffer containing the network payload
+/// * `buffer_len` - Length of the source buffer in bytes
+/// * `event` - Output: parsed MIDI event structure
+///
+/// # Returns
+/// * `0` (Success) - Event parsed successfully
+/// * `6` (NoEvent) - Valid payload without MIDI commands, such as a keepalive or a
+///   journal-only packet; `event` is not written
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+///
+/// # Example Usage (C)
+/// ```c
+/// uint8_t payload[] = { /* received network data */ };
+/// VlcRtpmidiEvent event;
+/// int result = vlc_rtpmidi_slave_netsync_flow_ffi(payload, sizeof(payload), &event);
+/// if (result == 0) {
+///     // Process the parsed event
+/// }
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_slave_netsync_flow_ffi(
+    buffer: *const u8,
+    buffer_len: usize,
+    event: *mut VlcRtpmidiEvent,
+) -> c_int {
+    with_error_detail(|| {
+        // Validate all pointers before use
+        if buffer.is_null() || event.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        // Create a safe slice from the raw buffer pointer
+        let buf = slice::from_raw_parts(buffer, buffer_len);
+
+        // Payloads without commands are validated, never parsed to an event
+        if let Some((_, 0)) = read_header(buf) {
+            return match slave_netsync_flow_opt(buf) {
+                Ok(_) => VlcRtpmidiErrorCode::NoEvent as c_int,
+                Err(_) => reject_payload("slave flow", buf) as c_int,
+            };
+        }
+
+        // Parse the network payload using core netsync logic, borrowing from it
+        let midi_event = match slave_netsync_flow_ref(buf) {
+            Ok(event) => event,
+            Err(_) => return reject_payload("slave flow", buf) as c_int,
+        };
+
+        // Convert the parsed event to C-compatible format
+        let c_event = match midi_event_ref_to_c(midi_event) {
+            Ok(event) => event,
+            Err(error_code) => return error_code as c_int,
+        };
+
+        // Write the result to the output parameter
+        *event = c_event;
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Slave netsync flow: Parse RTP-MIDI network payload to MIDI event and device ID
+///
+/// Same as `vlc_rtpmidi_slave_netsync_flow_ffi()`, except that MTC Full Frames and
+/// MMC commands addressed to a single device are recognised too, and the device
+/// ID is reported. Slaves typically act on messages addressed to their own ID or
+/// to 0x7F (all devices) and ignore the others.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `buffer` points to a readable buffer of at least `buffer_len` bytes
+/// - `event` points to a writable `VlcRtpmidiEvent` structure
+/// - `device_id_out` points to a writable `uint8_t`
+///
+/// # Arguments
+/// * `buffer` - Source buffer containing the network payload
+/// * `buffer_len` - Length of the source buffer in bytes
+/// * `event` - Output: parsed MIDI event structure
+/// * `device_id_out` - Output: the device ID the event is addressed to, 0x7F for
+///   broadcast messages and for quarter frames
+///
+/// # Returns
+/// * `0` (Success) - Event and device ID written
+/// * `6` (NoEvent) - Valid payload without MIDI commands; nothing is written
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+///
+/// # Example Usage (C)
+/// ```c
+/// VlcRtpmidiEvent event;
+/// uint8_t device_id;
+/// if (vlc_rtpmidi_slave_netsync_flow_dev_ffi(payload, len, &event, &device_id) == 0 &&
+///     (device_id == MY_DEVICE_ID || device_id == 0x7F)) {
+///     // Process the parsed event
+/// }
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_slave_netsync_flow_dev_ffi(
+    buffer: *const u8,
+    buffer_len: usize,
+    event: *mut VlcRtpmidiEvent,
+    device_id_out: *mut u8,
+) -> c_int {
+    with_error_detail(|| {
+        if buffer.is_null() || event.is_null() || device_id_out.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        let buf = slice::from_raw_parts(buffer, buffer_len);
+
+        // Payloads without commands are validated like the plain slave flow does
+        if let Some((_, 0)) = read_header(buf) {
+            return match slave_netsync_flow_opt(buf) {
+                Ok(_) => VlcRtpmidiErrorCode::NoEvent as c_int,
+                Err(_) => reject_payload("slave flow", buf) as c_int,
+            };
+        }
+
+        let (midi_event, device_id) = match slave_netsync_flow_with_device(buf) {
+            Ok(parsed) => parsed,
+            Err(_) => return reject_payload("slave flow", buf) as c_int,
+        };
+
+        let c_event = match midi_event_to_c(&midi_event) {
+            Ok(event) => event,
+            Err(error_code) => return error_code as c_int,
+        };
+
+        *event = c_event;
+        *device_id_out = device_id;
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Slave netsync flow: Parse every RTP-MIDI payload of a buffer to MIDI events
+///
+/// Bridges may batch several consecutive payloads into one datagram, each a
+/// header followed by exactly LEN bytes of MIDI list. This function parses all of
+/// them, where `vlc_rtpmidi_slave_netsync_flow_ffi()` only returns the first.
+/// Payloads without commands, such as keepalives, are skipped.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `buffer` points to a readable buffer of at least `buffer_len` bytes
+/// - `events_out` points to a writable array of at least `max_events` structures,
+///   or is null when `max_events` is 0
+/// - `actual_events` points to a writable `size_t`
+/// - All pointers remain valid for the duration of the call
+///
+/// # Arguments
+/// * `buffer` - Source buffer containing one or more consecutive payloads
+/// * `buffer_len` - Length of the source buffer in bytes
+/// * `events_out` - Output: array receiving the parsed events in order
+/// * `max_events` - Number of structures `events_out` can hold
+/// * `actual_events` - Output: number of events in the buffer
+///
+/// # Returns
+/// * `0` (Success) - All events parsed; `actual_events` of them were written
+/// * `3` (BufferTooSmall) - `max_events` is insufficient; `actual_events` is set to
+///   the required count and the first `max_events` events were written
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum); the contents of
+///   `events_out` and `actual_events` are unspecified
+///
+/// # Example Usage (C)
+/// ```c
+/// VlcRtpmidiEvent events[8];
+/// size_t count;
+/// int result = vlc_rtpmidi_slave_netsync_flow_multi_ffi(datagram, datagram_len,
+///                                                       events, 8, &count);
+/// if (result == 0) {
+///     for (size_t i = 0; i < count; i++) {
+///         // Process events[i]
+///     }
+/// }
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_slave_netsync_flow_multi_ffi(
+    buffer: *const u8,
+    buffer_len: usize,
+    events_out: *mut VlcRtpmidiEvent,
+    max_events: usize,
+    actual_events: *mut usize,
+) -> c_int {
+    with_error_detail(|| {
+        // Validate all pointers before use; the array may be omitted to query the count
+        if buffer.is_null() || actual_events.is_null() || (events_out.is_null() && max_events > 0) {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        let mut rest = slice::from_raw_parts(buffer, buffer_len);
+        if rest.is_empty() {
+            log(
+                VlcRtpmidiLogLevel::Warning,
+                format_args!("slave flow: empty buffer rejected"),
+            );
+            return VlcRtpmidiErrorCode::InvalidSlaveEvent as c_int;
+        }
+
+        // Parse every payload, writing those that fit and counting the rest
+        let mut count = 0;
+        while !rest.is_empty() {
+            let (midi_event, consumed) = match slave_netsync_flow_stream(rest) {
+                Ok(parsed) => parsed,
+                Err(_) => return reject_payload("slave flow", rest) as c_int,
+            };
+            rest = &rest[consumed..];
+            // Payloads without commands carry no event
+            let Some(midi_event) = midi_event else {
+                continue;
+            };
+            let c_event = match midi_event_to_c(&midi_event) {
+                Ok(event) => event,
+                Err(error_code) => return error_code as c_int,
+            };
+            if count < max_events {
+                *events_out.add(count) = c_event;
+            }
+            count += 1;
+        }
+
+        *actual_events = count;
+        if count > max_events {
+            return VlcRtpmidiErrorCode::BufferTooSmall as c_int;
+        }
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Slave netsync flow: Copy the raw MIDI bytes of an RTP-MIDI network payload
+///
+/// Unlike `vlc_rtpmidi_slave_netsync_flow_ffi()`, this function also accepts
+/// messages the C interface has no event structure for, such as SysEx or channel
+/// messages, so they can be passed through. The payload's MIDI list is copied as
+/// received, and its type reported: the event type when the message is a supported
+/// event, `Raw` otherwise.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `buffer` points to a readable buffer of at least `buffer_len` bytes
+/// - `out_buf` points to a writable buffer of at least `out_cap` bytes, or is null
+///   when `out_cap` is 0
+/// - `out_len` points to a writable `size_t`
+/// - `event_type_out` points to a writable `VlcRtpmidiEventType`
+/// - All pointers remain valid for the duration of the call
+///
+/// # Arguments
+/// * `buffer` - Source buffer containing the network payload
+/// * `buffer_len` - Length of the source buffer in bytes
+/// * `out_buf` - Destination buffer for the MIDI bytes
+/// * `out_cap` - Size of the destination buffer in bytes
+/// * `out_len` - Output: number of MIDI bytes, also set when the buffer is too small
+/// * `event_type_out` - Output: type of the message
+///
+/// # Returns
+/// * `0` (Success) - MIDI bytes copied to `out_buf`
+/// * `3` (BufferTooSmall) - `out_len` holds the required size; nothing is copied
+/// * `6` (NoEvent) - Valid payload without MIDI commands; `out_len` is set to 0
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+///
+/// # Example Usage (C)
+/// ```c
+/// uint8_t midi[64];
+/// size_t midi_len;
+/// VlcRtpmidiEventType type;
+/// int result = vlc_rtpmidi_slave_netsync_flow_raw_ffi(payload, payload_len, midi, sizeof(midi),
+///                                                     &midi_len, &type);
+/// if (result == 0 && type == VLC_RTPMIDI_EVENT_RAW) {
+///     // Forward midi[0..midi_len] to the MIDI output
+/// }
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_slave_netsync_flow_raw_ffi(
+    buffer: *const u8,
+    buffer_len: usize,
+    out_buf: *mut u8,
+    out_cap: usize,
+    out_len: *mut usize,
+    event_type_out: *mut VlcRtpmidiEventType,
+) -> c_int {
+    with_error_detail(|| {
+        if buffer.is_null() || out_len.is_null() || event_type_out.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+        if out_buf.is_null() && out_cap > 0 {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        *out_len = 0;
+
+        let buf = slice::from_raw_parts(buffer, buffer_len);
+        if let Some((_, 0)) = read_header(buf) {
+            return match slave_netsync_flow_opt(buf) {
+                Ok(_) => VlcRtpmidiErrorCode::NoEvent as c_int,
+                Err(_) => reject_payload("slave flow", buf) as c_int,
+            };
+        }
+        let midi_event = match slave_netsync_flow_ref(buf) {
+            Ok(event) => event,
+            Err(_) => return reject_payload("slave flow", buf) as c_int,
+        };
+
+        // The slave flow accepted the payload, so it holds exactly one MIDI message
+        let (start, len) = read_header(buf).expect("validated header");
+        let midi = &buf[start..start + len];
+
+        *event_type_out = match midi_event_ref_to_c(midi_event) {
+            Ok(c_event) => {
+                VlcRtpmidiEventType::try_from(c_event.event_type).expect("converted event")
+            }
+            Err(_) => VlcRtpmidiEventType::Raw,
+        };
+        *out_len = midi.len();
+        if midi.len() > out_cap {
+            return VlcRtpmidiErrorCode::BufferTooSmall as c_int;
+        }
+
+        slice::from_raw_parts_mut(out_buf, out_cap)[..midi.len()].copy_from_slice(midi);
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Validate and classify an RTP-MIDI network payload without parsing it to an event
+///
+/// Performs the same checks as `vlc_rtpmidi_slave_netsync_flow_ffi()` and returns
+/// the same code for every payload, but builds no event and never allocates. Meant
+/// for receive threads that filter packets before handing them on.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `buffer` points to a readable buffer of at least `buffer_len` bytes
+/// - `event_kind_out` points to a writable `int`
+///
+/// # Arguments
+/// * `buffer` - Source buffer containing the network payload
+/// * `buffer_len` - Length of the source buffer in bytes
+/// * `event_kind_out` - Output: the `VlcRtpmidiEventType` value of the event,
+///   `Raw` for messages without an event structure, -1 when there is no event
+///
+/// # Returns
+/// * `0` (Success) - `vlc_rtpmidi_slave_netsync_flow_ffi()` would return the event
+/// * `5` (InvalidEventType) - Valid payload whose message has no event structure
+/// * `6` (NoEvent) - Valid payload without MIDI commands
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+///
+/// # Example Usage (C)
+/// ```c
+/// int kind;
+/// if (vlc_rtpmidi_validate_payload_ffi(payload, len, &kind) == 0 &&
+///     kind != VLC_RTPMIDI_EVENT_MTC_QUARTER) {
+///     queue_for_main_thread(payload, len);
+/// }
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_validate_payload_ffi(
+    buffer: *const u8,
+    buffer_len: usize,
+    event_kind_out: *mut c_int,
+) -> c_int {
+    with_error_detail(|| {
+        if buffer.is_null() || event_kind_out.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        *event_kind_out = -1;
+
+        let buf = slice::from_raw_parts(buffer, buffer_len);
+        if let Some((_, 0)) = read_header(buf) {
+            return match slave_netsync_flow_opt(buf) {
+                Ok(_) => VlcRtpmidiErrorCode::NoEvent as c_int,
+                Err(_) => RejectedPayload(buf).code() as c_int,
+            };
+        }
+
+        // Pass-through messages are told apart first, so the scratch buffer stays unused
+        match peek_event_kind(buf) {
+            Ok(kind) if !kind.is_sync() => {
+                *event_kind_out = VlcRtpmidiEventType::Raw as c_int;
+                return VlcRtpmidiErrorCode::InvalidEventType as c_int;
+            }
+            Ok(_) => {}
+            Err(_) => return RejectedPayload(buf).code() as c_int,
+        }
+
+        let event_type = match slave_netsync_flow_buf(buf, &mut Vec::new()) {
+            Ok(MidiEventRef::MtcQuarter { .. }) => VlcRtpmidiEventType::MtcQuarter,
+            Ok(MidiEventRef::MtcFull { .. }) => VlcRtpmidiEventType::MtcFull,
+            Ok(MidiEventRef::Mmc(MmcCommand::Locate { .. })) => VlcRtpmidiEventType::MmcLocate,
+            Ok(MidiEventRef::Mmc(MmcCommand::VariablePlay { .. })) => {
+                VlcRtpmidiEventType::MmcVariablePlay
+            }
+            Ok(MidiEventRef::Mmc(
+                MmcCommand::Shuttle { .. }
+                | MmcCommand::Search { .. }
+                | MmcCommand::MaskedWrite { .. },
+            )) => {
+                *event_kind_out = VlcRtpmidiEventType::Raw as c_int;
+                return VlcRtpmidiErrorCode::InvalidEventType as c_int;
+            }
+            Ok(MidiEventRef::Mmc(command)) => {
+                let (event_type, _) = MMC_EVENT_COMMANDS
+                    .iter()
+                    .find(|(_, known)| *known == command)
+                    .expect("MMC command without data");
+                *event_type
+            }
+            Ok(MidiEventRef::Realtime(message)) => {
+                let (event_type, _) = REALTIME_EVENT_MESSAGES
+                    .iter()
+                    .find(|(_, known)| *known == message)
+                    .expect("beat clock message");
+                *event_type
+            }
+            Ok(MidiEventRef::SongPosition(_)) => VlcRtpmidiEventType::SongPosition,
+            Ok(_) => VlcRtpmidiEventType::Raw,
+            Err(_) => return RejectedPayload(buf).code() as c_int,
+        };
+        *event_kind_out = event_type as c_int;
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Check an event before handing it to a master flow
+///
+/// For events built by hand rather than with the creators. Checks the event type
+/// and `data_len` exactly as every master flow does, with the same routine, then
+/// the range of each field: timecode fields (hours 0-23, minutes and seconds 0-59,
+/// frames 0-29), quarter-frame pieces (0-7) and values (0-15), and subframes (0-99).
+/// The flows themselves do not check ranges and send values masked to the bits
+/// the message carries.
+///
+/// # Safety
+/// This function is unsafe because it dereferences a raw pointer. Callers must ensure
+/// `event` points to a readable `VlcRtpmidiEvent`.
+///
+/// # Arguments
+/// * `event` - Pointer to the event to check
+///
+/// # Returns
+/// * `0` (Success) - Every master flow accepts the event, and its fields are in range
+/// * `5` (InvalidEventType) - Unknown event type, `data_len` not matching the type,
+///   or a Song Position above 0x3FFF; the master flows reject the event too
+/// * `7` (InvalidTimecode) - A field is out of range
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+///
+/// # Example Usage (C)
+/// ```c
+/// VlcRtpmidiEvent event = {.event_type = VLC_RTPMIDI_EVENT_MTC_FULL, .data = {1, 2, 3, 4}, .data_len = 4};
+/// if (vlc_rtpmidi_validate_event_ffi(&event) != 0)
+///     log_error(vlc_rtpmidi_get_last_error_detail());
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_validate_event_ffi(event: *const VlcRtpmidiEvent) -> c_int {
+    with_error_detail(|| {
+        if event.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        match c_to_midi_event(&*event).and_then(|midi_event| check_event_fields(&midi_event)) {
+            Ok(()) => VlcRtpmidiErrorCode::Success as c_int,
+            Err(error_code) => error_code as c_int,
+        }
+    })
+}
+
+// ============================================================================
+// MIDI CODEC FUNCTIONS
+// ============================================================================
+
+/// Parse the first MIDI message of a byte stream, without a netsync header
+///
+/// For MIDI received from another source than the network, such as a serial
+/// port. Recognises the same messages as `vlc_rtpmidi_slave_netsync_flow_ffi()`;
+/// `consumed` tells how far to advance to the next message.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `buf` points to a readable buffer of at least `len` bytes
+/// - `out` points to a writable `VlcRtpmidiEvent` structure
+/// - `consumed` points to a writable `size_t`
+///
+/// # Arguments
+/// * `buf` - MIDI bytes, starting with a status byte
+/// * `len` - Number of bytes in `buf`
+/// * `out` - Output: parsed MIDI event
+/// * `consumed` - Output: length of the message, also set for unsupported messages
+///
+/// # Returns
+/// * `0` (Success) - Event written to `out`
+/// * `5` (InvalidEventType) - Well-formed message without an event structure, such
+///   as SysEx or a channel message; `out` is not written
+/// * `15` (InvalidMidi) - No message could be read; `consumed` is set to 0
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+///
+/// # Example Usage (C)
+/// ```c
+/// size_t offset = 0, consumed;
+/// VlcRtpmidiEvent event;
+/// while (offset < len) {
+///     int result = vlc_rtpmidi_parse_midi_ffi(serial + offset, len - offset, &event, &consumed);
+///     if (result == VLC_RTPMIDI_ERROR_INVALID_MIDI)
+///         break;
+///     if (result == 0)
+///         handle_event(&event);
+///     offset += consumed;
+/// }
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_parse_midi_ffi(
+    buf: *const u8,
+    len: usize,
+    out: *mut VlcRtpmidiEvent,
+    consumed: *mut usize,
+) -> c_int {
+    with_error_detail(|| {
+        if buf.is_null() || out.is_null() || consumed.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        *consumed = 0;
+
+        let (event, len) = match parse_midi_message(slice::from_raw_parts(buf, len)) {
+            Ok(parsed) => parsed,
+            // Only an empty buffer, which is no slave payload: report it as unreadable MIDI
+            Err(_) => return VlcRtpmidiErrorCode::InvalidMidi as c_int,
+        };
+        *consumed = len;
+
+        let c_event = match midi_event_ref_to_c(event) {
+            Ok(c_event) => c_event,
+            Err(error_code) => return error_code as c_int,
+        };
+        *out = c_event;
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Serialize a MIDI event to MIDI bytes, without a netsync header
+///
+/// The counterpart of `vlc_rtpmidi_parse_midi_ffi()`, producing the MIDI list a
+/// master payload would carry.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `event` points to a valid, properly initialized `VlcRtpmidiEvent`
+/// - `buf` points to a writable buffer of at least `cap` bytes, or is null when
+///   `cap` is 0
+/// - `written` points to a writable `size_t`
+///
+/// # Arguments
+/// * `event` - Pointer to the MIDI event to serialize
+/// * `buf` - Destination buffer for the MIDI bytes
+/// * `cap` - Size of the destination buffer in bytes
+/// * `written` - Output: number of bytes written, or required when the buffer is
+///   too small
+///
+/// # Returns
+/// * `0` (Success) - MIDI bytes written to `buf`
+/// * `3` (BufferTooSmall) - `written` holds the required size; nothing is written
+/// * `5` (InvalidEventType) - Unsupported or malformed event
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+///
+/// # Example Usage (C)
+/// ```c
+/// VlcRtpmidiEvent play = vlc_rtpmidi_create_mmc_play_event();
+/// uint8_t midi[16];
+/// size_t written;
+/// if (vlc_rtpmidi_build_midi_ffi(&play, midi, sizeof(midi), &written) == 0) {
+///     serial_write(port, midi, written);
+/// }
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_build_midi_ffi(
+    event: *const VlcRtpmidiEvent,
+    buf: *mut u8,
+    cap: usize,
+    written: *mut usize,
+) -> c_int {
+    with_error_detail(|| {
+        if event.is_null() || written.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+        if buf.is_null() && cap > 0 {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        *written = 0;
+
+        let rust_event = match c_to_midi_event(&*event) {
+            Ok(event) => event,
+            Err(error_code) => return error_code as c_int,
+        };
+
+        let mut scratch = [0; MMC_LOCATE_LENGTH];
+        let midi = encode_midi(&rust_event.as_event_ref(), &mut scratch);
+        *written = midi.len();
+        if midi.len() > cap {
+            return VlcRtpmidiErrorCode::BufferTooSmall as c_int;
+        }
+
+        slice::from_raw_parts_mut(buf, cap)[..midi.len()].copy_from_slice(midi);
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+// ============================================================================
+// UTILITY FUNCTIONS
+// ============================================================================
+
+/// Get the maximum buffer size needed for network payloads
 ///
 /// Use this function to allocate appropriately sized buffers for
 /// `vlc_rtpmidi_master_netsync_flow_ffi()`. The returned size is guaranteed to
 /// accommodate any event type supported by this library.
 ///
 /// # Returns
-/// Maximum buffer size in bytes needed for any payload
+/// Maximum buffer size in bytes needed for any payload
+///
+/// # Example Usage (C)
+/// ```c
+/// size_t max_size = vlc_rtpmidi_get_max_payload_size();
+/// uint8_t* buffer = malloc(max_size);
+/// ```
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_get_max_payload_size() -> usize {
+    // MMC Locate has the largest payload: 1 byte header + 13 bytes MMC data
+    MAX_PAYLOAD_LENGTH
+}
+
+/// Get the exact payload size `vlc_rtpmidi_master_netsync_flow_ffi()` produces for an event
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `event` points to a valid, properly initialized `VlcRtpmidiEvent`
+/// - `size_out` points to a writable `size_t` location
+///
+/// # Arguments
+/// * `event` - Pointer to the MIDI event
+/// * `size_out` - Output: payload size in bytes, header included
+///
+/// # Returns
+/// * `0` (Success) - Size written to `size_out`
+/// * Non-zero - Error code (see `VlcRtpmidiErrorCode` enum); `size_out` is set to 0
+///
+/// # Example Usage (C)
+/// ```c
+/// VlcRtpmidiEvent event = vlc_rtpmidi_create_mmc_play_event();
+/// size_t size;
+/// if (vlc_rtpmidi_get_payload_size_for_event(&event, &size) == 0) {
+///     uint8_t* slot = ring_buffer_reserve(ring, size);
+///     vlc_rtpmidi_master_netsync_flow_ffi(&event, slot, size, &size);
+/// }
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_get_payload_size_for_event(
+    event: *const VlcRtpmidiEvent,
+    size_out: *mut usize,
+) -> c_int {
+    with_error_detail(|| {
+        if event.is_null() || size_out.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        *size_out = 0;
+
+        let rust_event = match c_to_midi_event(&*event) {
+            Ok(event) => event,
+            Err(error_code) => return error_code as c_int,
+        };
+
+        match master_payload_len(&rust_event) {
+            Ok(len) => {
+                *size_out = len;
+                VlcRtpmidiErrorCode::Success as c_int
+            }
+            Err(error) => VlcRtpmidiErrorCode::from(&error) as c_int,
+        }
+    })
+}
+
+/// Get human-readable error message for an error code
+///
+/// Converts numeric error codes returned by FFI functions into
+/// descriptive English text for debugging and user feedback.
+///
+/// # Safety
+/// The returned pointer is valid for the lifetime of the program
+/// and points to a null-terminated C string. Do not free the pointer.
+///
+/// # Arguments
+/// * `error_code` - Error code from any FFI function
+///
+/// # Returns
+/// Pointer to null-terminated error message string
+///
+/// # Example Usage (C)
+/// ```c
+/// int result = vlc_rtpmidi_master_netsync_flow_ffi(...);
+/// if (result != 0) {
+///     const char* message = vlc_rtpmidi_get_error_message(result);
+///     printf("Error: %s\n", message);
+/// }
+/// ```
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_get_error_message(error_code: c_int) -> *const c_char {
+    error_message(error_code).as_ptr() as *const c_char
+}
+
+/// Null-terminated message of an error code
+fn error_message(error_code: c_int) -> &'static str {
+    match error_code {
+        x if x == VlcRtpmidiErrorCode::Success as c_int => "Success\0",
+        x if x == VlcRtpmidiErrorCode::InvalidMasterEvent as c_int => "Invalid master event\0",
+        x if x == VlcRtpmidiErrorCode::InvalidSlaveEvent as c_int => "Invalid slave event\0",
+        x if x == VlcRtpmidiErrorCode::BufferTooSmall as c_int => "Buffer too small\0",
+        x if x == VlcRtpmidiErrorCode::NullPointer as c_int => "Null pointer passed\0",
+        x if x == VlcRtpmidiErrorCode::InvalidEventType as c_int => {
+            "Invalid event type or data length\0"
+        }
+        x if x == VlcRtpmidiErrorCode::NoEvent as c_int => "No event in payload\0",
+        x if x == VlcRtpmidiErrorCode::InvalidTimecode as c_int => "Invalid timecode\0",
+        x if x == VlcRtpmidiErrorCode::QuarterFrameOrder as c_int => {
+            "Quarter frames out of order\0"
+        }
+        x if x == VlcRtpmidiErrorCode::Duplicate as c_int => "Duplicate packet\0",
+        x if x == VlcRtpmidiErrorCode::OutOfOrder as c_int => "Out-of-order packet\0",
+        x if x == VlcRtpmidiErrorCode::ForeignSsrc as c_int => "Packet from another SSRC\0",
+        x if x == VlcRtpmidiErrorCode::InvalidFrameRate as c_int => "Invalid frame rate\0",
+        x if x == VlcRtpmidiErrorCode::InvalidDeviceId as c_int => "Invalid device ID\0",
+        x if x == VlcRtpmidiErrorCode::LayoutMismatch as c_int => "Structure layout mismatch\0",
+        x if x == VlcRtpmidiErrorCode::InvalidMidi as c_int => "Malformed MIDI bytes\0",
+        x if x == VlcRtpmidiErrorCode::WouldBlock as c_int => "No complete payload buffered\0",
+        x if x == VlcRtpmidiErrorCode::TruncatedPayload as c_int => "Truncated payload\0",
+        x if x == VlcRtpmidiErrorCode::HeaderMismatch as c_int => {
+            "Header inconsistent with payload\0"
+        }
+        x if x == VlcRtpmidiErrorCode::MalformedSysex as c_int => "Malformed SysEx message\0",
+        x if x == VlcRtpmidiErrorCode::ValueOutOfRange as c_int => "Value out of range\0",
+        x if x == VlcRtpmidiErrorCode::MalformedTimecode as c_int => "Malformed timecode text\0",
+        x if x == VlcRtpmidiErrorCode::SessionFailed as c_int => "Session failed\0",
+        _ => "Unknown error\0",
+    }
+}
+
+/// Get the name of an event type
+///
+/// Meant for logging, where the raw `event_type` integer is hard to read.
+///
+/// # Safety
+/// The returned pointer is valid for the lifetime of the program
+/// and points to a null-terminated C string. Do not free the pointer.
+///
+/// # Arguments
+/// * `event_type` - A `VlcRtpmidiEventType` value
+///
+/// # Returns
+/// Pointer to the null-terminated name, such as "MTC_QUARTER" or "MMC_LOCATE", or
+/// "UNKNOWN" for values outside `VlcRtpmidiEventType`
+///
+/// # Example Usage (C)
+/// ```c
+/// if (vlc_rtpmidi_slave_netsync_flow_ffi(payload, len, &event) == 0) {
+///     printf("Received %s\n", vlc_rtpmidi_get_event_type_name(event.event_type));
+/// }
+/// ```
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_get_event_type_name(event_type: c_int) -> *const c_char {
+    let name = match u32::try_from(event_type)
+        .ok()
+        .and_then(|raw| VlcRtpmidiEventType::try_from(raw).ok())
+    {
+        Some(VlcRtpmidiEventType::MtcQuarter) => "MTC_QUARTER\0",
+        Some(VlcRtpmidiEventType::MtcFull) => "MTC_FULL\0",
+        Some(VlcRtpmidiEventType::MmcStop) => "MMC_STOP\0",
+        Some(VlcRtpmidiEventType::MmcPlay) => "MMC_PLAY\0",
+        Some(VlcRtpmidiEventType::MmcLocate) => "MMC_LOCATE\0",
+        Some(VlcRtpmidiEventType::Raw) => "RAW\0",
+        Some(VlcRtpmidiEventType::MmcDeferredPlay) => "MMC_DEFERRED_PLAY\0",
+        Some(VlcRtpmidiEventType::MmcFastForward) => "MMC_FAST_FORWARD\0",
+        Some(VlcRtpmidiEventType::MmcRewind) => "MMC_REWIND\0",
+        Some(VlcRtpmidiEventType::MmcRecordStrobe) => "MMC_RECORD_STROBE\0",
+        Some(VlcRtpmidiEventType::MmcRecordExit) => "MMC_RECORD_EXIT\0",
+        Some(VlcRtpmidiEventType::MmcPause) => "MMC_PAUSE\0",
+        Some(VlcRtpmidiEventType::MmcEject) => "MMC_EJECT\0",
+        Some(VlcRtpmidiEventType::MmcReset) => "MMC_RESET\0",
+        Some(VlcRtpmidiEventType::SongPosition) => "SONG_POSITION\0",
+        Some(VlcRtpmidiEventType::Clock) => "CLOCK\0",
+        Some(VlcRtpmidiEventType::Start) => "START\0",
+        Some(VlcRtpmidiEventType::Continue) => "CONTINUE\0",
+        Some(VlcRtpmidiEventType::RealtimeStop) => "REALTIME_STOP\0",
+        Some(VlcRtpmidiEventType::MmcVariablePlay) => "MMC_VARIABLE_PLAY\0",
+        None => "UNKNOWN\0",
+    };
+    name.as_ptr() as *const c_char
+}
+
+/// Describe a payload as human-readable text
+///
+/// Writes the output of `netsync::describe_payload()`: the header, the decoded
+/// command or a hex dump, and any findings, one per line. Meant for logs and
+/// support tooling inspecting captured packets.
+///
+/// When `out_cap` is too small, the text is cut at a character boundary and ends
+/// with "..." so it still reads as a best-effort description. Only the first 4097
+/// bytes of `buffer`, the largest payload a header can describe, are decoded; the
+/// description notes how many bytes were left out. Never panics, whatever the
+/// input.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `buffer` points to a readable buffer of at least `buffer_len` bytes, or is null
+///   when `buffer_len` is 0
+/// - `out` points to a writable buffer of at least `out_cap` bytes, or is null when
+///   `out_cap` is 0
+/// - `out_len` points to a writable `size_t`
+///
+/// # Arguments
+/// * `buffer` - The network payload, starting with the header
+/// * `buffer_len` - Length of the payload in bytes
+/// * `out` - Destination for the null-terminated description
+/// * `out_cap` - Size of the destination buffer in bytes
+/// * `out_len` - Output: length of the complete description, without the null
+///   terminator, also set when the buffer is too small
+///
+/// # Returns
+/// * `0` (Success) - The complete description was written to `out`
+/// * `3` (BufferTooSmall) - `out` holds the truncated description; `out_len + 1`
+///   bytes are needed for all of it
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+///
+/// # Example Usage (C)
+/// ```c
+/// char text[256];
+/// size_t text_len;
+/// vlc_rtpmidi_describe_payload_ffi(packet, packet_len, text, sizeof(text), &text_len);
+/// fprintf(stderr, "%s\n", text);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_describe_payload_ffi(
+    buffer: *const u8,
+    buffer_len: usize,
+    out: *mut c_char,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> c_int {
+    /// Two-octet header followed by the longest MIDI list it can announce
+    const DESCRIBED_BYTES: usize = 2 + MAX_MIDI_LIST_LENGTH;
+    const ELLIPSIS: &str = "...";
+
+    with_error_detail(|| {
+        if (buffer.is_null() && buffer_len > 0)
+            || (out.is_null() && out_cap > 0)
+            || out_len.is_null()
+        {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        let buf = match buffer_len {
+            0 => &[][..],
+            _ => slice::from_raw_parts(buffer, buffer_len),
+        };
+        let mut text = describe_payload(&buf[..buf.len().min(DESCRIBED_BYTES)]);
+        if buf.len() > DESCRIBED_BYTES {
+            let _ = write!(
+                text,
+                "\n({} more bytes not shown)",
+                buf.len() - DESCRIBED_BYTES
+            );
+        }
+        *out_len = text.len();
+        if out_cap == 0 {
+            return VlcRtpmidiErrorCode::BufferTooSmall as c_int;
+        }
+
+        let out = slice::from_raw_parts_mut(out.cast::<u8>(), out_cap);
+        let room = out_cap - 1;
+        let written = if text.len() <= room {
+            out[..text.len()].copy_from_slice(text.as_bytes());
+            text.len()
+        } else {
+            // Keep as much text as fits before the ellipsis, on a character boundary
+            let ellipsis = &ELLIPSIS[..ELLIPSIS.len().min(room)];
+            let mut kept = room - ellipsis.len();
+            while !text.is_char_boundary(kept) {
+                kept -= 1;
+            }
+            out[..kept].copy_from_slice(&text.as_bytes()[..kept]);
+            out[kept..kept + ellipsis.len()].copy_from_slice(ellipsis.as_bytes());
+            kept + ellipsis.len()
+        };
+        out[written] = 0;
+
+        if written == text.len() {
+            VlcRtpmidiErrorCode::Success as c_int
+        } else {
+            set_error_detail(format_args!(
+                "description of {} bytes does not fit in a buffer of {}",
+                text.len(),
+                out_cap
+            ));
+            VlcRtpmidiErrorCode::BufferTooSmall as c_int
+        }
+    })
+}
+
+// ============================================================================
+// VERSION FUNCTIONS
+// ============================================================================
+
+/// Parses one numeric component of the crate version at compile time.
+const fn parse_version_component(component: &str) -> u32 {
+    let bytes = component.as_bytes();
+    let mut value = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        value = value * 10 + (bytes[i] - b'0') as u32;
+        i += 1;
+    }
+    value
+}
+
+/// Get the major version of the library
+///
+/// # Returns
+/// The major component of the crate version
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_version_major() -> u32 {
+    const MAJOR: u32 = parse_version_component(env!("CARGO_PKG_VERSION_MAJOR"));
+    MAJOR
+}
+
+/// Get the minor version of the library
+///
+/// # Returns
+/// The minor component of the crate version
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_version_minor() -> u32 {
+    const MINOR: u32 = parse_version_component(env!("CARGO_PKG_VERSION_MINOR"));
+    MINOR
+}
+
+/// Get the patch version of the library
+///
+/// # Returns
+/// The patch component of the crate version
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_version_patch() -> u32 {
+    const PATCH: u32 = parse_version_component(env!("CARGO_PKG_VERSION_PATCH"));
+    PATCH
+}
+
+/// Get the full version of the library as a string
+///
+/// # Safety
+/// The returned pointer is valid for the lifetime of the program
+/// and points to a null-terminated C string. Do not free the pointer.
+///
+/// # Returns
+/// Pointer to the null-terminated crate version, such as `"0.1.0"`
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_version_string() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr() as *const c_char
+}
+
+/// Get the ABI version of the library
+///
+/// The ABI version changes whenever the layout of a structure or the numbering
+/// of an enum in the header changes. A plugin loading the library dynamically
+/// should refuse any library whose ABI version differs from the
+/// `VLC_RTPMIDI_ABI_VERSION` of the header it was built against.
+///
+/// # Returns
+/// The ABI version the library was built with
+///
+/// # Example Usage (C)
+/// ```c
+/// if (vlc_rtpmidi_abi_version() != VLC_RTPMIDI_ABI_VERSION) {
+///     fprintf(stderr, "incompatible rtp_midi_netsync %s\n", vlc_rtpmidi_version_string());
+///     return -1;
+/// }
+/// ```
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_abi_version() -> u32 {
+    VLC_RTPMIDI_ABI_VERSION
+}
+
+/// Check that the caller's `VlcRtpmidiEvent` layout matches the library's
+///
+/// Pass the `sizeof` and `offsetof` values of the header as compiled by the
+/// application, to catch packing or header mismatches at startup rather than as
+/// silently corrupted events.
+///
+/// # Arguments
+/// * `sizeof_event` - `sizeof(VlcRtpmidiEvent)`
+/// * `offsetof_data` - `offsetof(VlcRtpmidiEvent, data)`
+/// * `offsetof_data_len` - `offsetof(VlcRtpmidiEvent, data_len)`
+///
+/// # Returns
+/// * `0` (Success) - Layouts match
+/// * `14` (LayoutMismatch) - At least one value differs
+///
+/// # Example Usage (C)
+/// ```c
+/// if (vlc_rtpmidi_event_layout_check(sizeof(VlcRtpmidiEvent),
+///                                    offsetof(VlcRtpmidiEvent, data),
+///                                    offsetof(VlcRtpmidiEvent, data_len)) != 0) {
+///     return -1;
+/// }
+/// ```
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_event_layout_check(
+    sizeof_event: usize,
+    offsetof_data: usize,
+    offsetof_data_len: usize,
+) -> c_int {
+    with_error_detail(|| {
+        if sizeof_event == size_of::<VlcRtpmidiEvent>()
+            && offsetof_data == offset_of!(VlcRtpmidiEvent, data)
+            && offsetof_data_len == offset_of!(VlcRtpmidiEvent, data_len)
+        {
+            VlcRtpmidiErrorCode::Success as c_int
+        } else {
+            VlcRtpmidiErrorCode::LayoutMismatch as c_int
+        }
+    })
+}
+
+/// Check that the caller's `VlcRtpmidiNetsyncConfig` layout matches the library's
+///
+/// Same as `vlc_rtpmidi_event_layout_check()`, for the session configuration.
+///
+/// # Arguments
+/// * `sizeof_config` - `sizeof(VlcRtpmidiNetsyncConfig)`
+/// * `offsetof_full_frame_interval_us` - `offsetof(VlcRtpmidiNetsyncConfig, full_frame_interval_us)`
+/// * `offsetof_frame_rate` - `offsetof(VlcRtpmidiNetsyncConfig, frame_rate)`
+/// * `offsetof_device_id` - `offsetof(VlcRtpmidiNetsyncConfig, device_id)`
+///
+/// # Returns
+/// * `0` (Success) - Layouts match
+/// * `14` (LayoutMismatch) - At least one value differs
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_config_layout_check(
+    sizeof_config: usize,
+    offsetof_full_frame_interval_us: usize,
+    offsetof_frame_rate: usize,
+    offsetof_device_id: usize,
+) -> c_int {
+    with_error_detail(|| {
+        if sizeof_config == size_of::<VlcRtpmidiNetsyncConfig>()
+            && offsetof_full_frame_interval_us
+                == offset_of!(VlcRtpmidiNetsyncConfig, full_frame_interval_us)
+            && offsetof_frame_rate == offset_of!(VlcRtpmidiNetsyncConfig, frame_rate)
+            && offsetof_device_id == offset_of!(VlcRtpmidiNetsyncConfig, device_id)
+        {
+            VlcRtpmidiErrorCode::Success as c_int
+        } else {
+            VlcRtpmidiErrorCode::LayoutMismatch as c_int
+        }
+    })
+}
+
+/// Check that the caller's `VlcRtpmidiNetsyncMetrics` layout matches the library's
+///
+/// Same as `vlc_rtpmidi_event_layout_check()`, for the session counters. Since
+/// counters are only appended, a mismatch means the header predates the library.
+///
+/// # Arguments
+/// * `sizeof_metrics` - `sizeof(VlcRtpmidiNetsyncMetrics)`
+/// * `offsetof_last_drift_us` - `offsetof(VlcRtpmidiNetsyncMetrics, last_drift_us)`
+/// * `offsetof_has_last_drift` - `offsetof(VlcRtpmidiNetsyncMetrics, has_last_drift)`
+///
+/// # Returns
+/// * `0` (Success) - Layouts match
+/// * `14` (LayoutMismatch) - At least one value differs
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_metrics_layout_check(
+    sizeof_metrics: usize,
+    offsetof_last_drift_us: usize,
+    offsetof_has_last_drift: usize,
+) -> c_int {
+    with_error_detail(|| {
+        if sizeof_metrics == size_of::<VlcRtpmidiNetsyncMetrics>()
+            && offsetof_last_drift_us == offset_of!(VlcRtpmidiNetsyncMetrics, last_drift_us)
+            && offsetof_has_last_drift == offset_of!(VlcRtpmidiNetsyncMetrics, has_last_drift)
+        {
+            VlcRtpmidiErrorCode::Success as c_int
+        } else {
+            VlcRtpmidiErrorCode::LayoutMismatch as c_int
+        }
+    })
+}
+
+// ============================================================================
+// LOGGING
+// ============================================================================
+
+/// Longest message passed to the log callback, terminator included
+const LOG_MESSAGE_CAPACITY: usize = 160;
+
+/// Installed log callback with its user pointer
+struct LogSink {
+    callback: unsafe extern "C" fn(c_int, *const c_char, *mut c_void),
+    user: *mut c_void,
+}
+
+// The user pointer is only ever handed back to the callback, which the
+// application installs knowing it may be called from any thread
+unsafe impl Send for LogSink {}
+unsafe impl Sync for LogSink {}
+
+/// Held for reading while the callback runs, so replacing it waits for calls in flight
+static LOG_SINK: RwLock<Option<LogSink>> = RwLock::new(None);
+
+/// Stack buffer formatting a message, truncated to leave room for the terminator
+struct LogMessage {
+    bytes: [u8; LOG_MESSAGE_CAPACITY],
+    len: usize,
+}
+
+impl Write for LogMessage {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let available = LOG_MESSAGE_CAPACITY - 1 - self.len;
+        let mut take = s.len().min(available);
+        while !s.is_char_boundary(take) {
+            take -= 1;
+        }
+        self.bytes[self.len..self.len + take].copy_from_slice(&s.as_bytes()[..take]);
+        self.len += take;
+        Ok(())
+    }
+}
+
+/// Returns `true` if a log callback is installed
+fn log_enabled() -> bool {
+    LOG_SINK
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .is_some()
+}
+
+/// Format a message on the stack and pass it to the log callback, if any
+///
+/// Warnings and errors describe why the current call fails and also become the
+/// thread's last error detail.
+fn log(level: VlcRtpmidiLogLevel, args: fmt::Arguments<'_>) {
+    let failure = level >= VlcRtpmidiLogLevel::Warning;
+    let sink = LOG_SINK.read().unwrap_or_else(PoisonError::into_inner);
+    if sink.is_none() && !failure {
+        return;
+    }
+
+    let mut message = LogMessage {
+        bytes: [0; LOG_MESSAGE_CAPACITY],
+        len: 0,
+    };
+    let _ = message.write_fmt(args);
+    message.bytes[message.len] = 0;
+
+    if failure {
+        ERROR_DETAIL.with_borrow_mut(|detail| {
+            detail.bytes = message.bytes;
+            detail.len = message.len;
+        });
+    }
+    let Some(sink) = sink.as_ref() else {
+        return;
+    };
+    unsafe {
+        (sink.callback)(
+            level as c_int,
+            message.bytes.as_ptr() as *const c_char,
+            sink.user,
+        )
+    };
+}
+
+/// Log why a received payload was rejected, returning the code to report
+///
+/// Names the first error `verify_payload()` finds. The verification allocates,
+/// so it only runs while a callback is installed: otherwise, the error of the
+/// MIDI parser is recorded as the last error detail.
+fn reject_payload(function: &str, buf: &[u8]) -> VlcRtpmidiErrorCode {
+    let rejected = RejectedPayload(buf);
+    if !log_enabled() {
+        set_error_detail(format_args!("{}: payload rejected: {}", function, rejected));
+        return rejected.code();
+    }
+    let report = verify_payload(buf);
+    match report
+        .findings
+        .iter()
+        .find(|finding| finding.severity == Severity::Error)
+    {
+        Some(finding) => log(
+            VlcRtpmidiLogLevel::Warning,
+            format_args!("{}: payload rejected: {}", function, finding),
+        ),
+        None => log(
+            VlcRtpmidiLogLevel::Warning,
+            format_args!("{}: payload of {} bytes rejected", function, buf.len()),
+        ),
+    }
+    rejected.code()
+}
+
+/// Log why an event was rejected by the master flow
+fn log_rejected_event(function: &str, event: &MidiEvent) {
+    log(
+        VlcRtpmidiLogLevel::Warning,
+        format_args!("{}: event rejected: {:?}", function, event),
+    );
+}
+
+/// Install or remove the log callback
+///
+/// The library reports why it rejects input through this callback: malformed
+/// payloads and invalid events at `Warning`, invalid configurations at `Error`,
+/// dropped packets at `Debug`. Messages are short, null-terminated and formed on
+/// the stack. No callback is installed by default.
+///
+/// The callback may be called from any thread calling into the library, and from
+/// several at once. Once this function returns, the previous callback is no
+/// longer running and will not be called again, so its `user` data can be freed.
+///
+/// # Safety
+/// This function is unsafe because the callback is later called with `user`.
+/// Callers must ensure:
+/// - `callback` is safe to call from any thread with `user`, until replaced
+/// - `callback` does not call `vlc_rtpmidi_set_log_callback()` itself, which
+///   would deadlock
+///
+/// # Arguments
+/// * `callback` - The function receiving messages, or null to disable logging
+/// * `user` - Pointer passed back to every call of `callback`
+///
+/// # Example Usage (C)
+/// ```c
+/// static void on_log(int level, const char* msg, void* user) {
+///     if (level >= VLC_RTPMIDI_LOG_LEVEL_WARNING) {
+///         fprintf((FILE*)user, "rtpmidi: %s\n", msg);
+///     }
+/// }
+///
+/// vlc_rtpmidi_set_log_callback(on_log, stderr);
+/// // ...
+/// vlc_rtpmidi_set_log_callback(NULL, NULL);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_set_log_callback(
+    callback: VlcRtpmidiLogCallback,
+    user: *mut c_void,
+) {
+    let mut sink = LOG_SINK.write().unwrap_or_else(PoisonError::into_inner);
+    *sink = callback.map(|callback| LogSink { callback, user });
+}
+
+// ============================================================================
+// LAST ERROR DETAIL
+// ============================================================================
+
+thread_local! {
+    /// Detail of the last failing call on this thread, empty after a successful one
+    static ERROR_DETAIL: RefCell<LogMessage> = const {
+        RefCell::new(LogMessage {
+            bytes: [0; LOG_MESSAGE_CAPACITY],
+            len: 0,
+        })
+    };
+}
+
+/// Replace the calling thread's last error detail
+fn set_error_detail(args: fmt::Arguments<'_>) {
+    ERROR_DETAIL.with_borrow_mut(|detail| {
+        detail.len = 0;
+        let _ = detail.write_fmt(args);
+        detail.bytes[detail.len] = 0;
+    });
+}
+
+fn clear_error_detail() {
+    set_error_detail(format_args!(""));
+}
+
+/// Run the body of an entry point returning an error code, recording its outcome
+///
+/// The detail is cleared on success. Failures that did not record a more precise
+/// reason fall back to the message of their error code.
+fn with_error_detail(body: impl FnOnce() -> c_int) -> c_int {
+    clear_error_detail();
+    let code = body();
+    if code != VlcRtpmidiErrorCode::Success as c_int
+        && ERROR_DETAIL.with_borrow(|detail| detail.len == 0)
+    {
+        set_error_detail(format_args!(
+            "{}",
+            error_message(code).trim_end_matches('\0')
+        ));
+    }
+    code
+}
+
+/// Why the slave flow cannot read a payload, from the MIDI parser's error
+struct RejectedPayload<'a>(&'a [u8]);
+
+impl RejectedPayload<'_> {
+    fn error(&self) -> Option<ParseError> {
+        rejection_cause(self.0)
+    }
+
+    /// The code a slave flow reports for the payload, without allocating
+    fn code(&self) -> VlcRtpmidiErrorCode {
+        match (read_header(self.0), self.error()) {
+            // LEN=0 payloads are only rejected for what follows their header
+            (Some((_, 0)), _) => VlcRtpmidiErrorCode::HeaderMismatch,
+            (_, Some(error)) => VlcRtpmidiErrorCode::from(&error),
+            (_, None) => VlcRtpmidiErrorCode::InvalidSlaveEvent,
+        }
+    }
+}
+
+impl fmt::Display for RejectedPayload<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (read_header(self.0), self.error()) {
+            (Some((start, len)), Some(error)) => {
+                write!(f, "LEN={}: {}", len, error.with_context(&self.0[start..]))
+            }
+            (None, Some(error)) => write!(f, "{}", error.with_context(self.0)),
+            (_, None) => write!(f, "payload of {} bytes", self.0.len()),
+        }
+    }
+}
+
+/// Get a description of why the last failing call on this thread failed
+///
+/// Error codes say what failed; the detail says why, such as "LEN=13: Requested
+/// 13 bytes but only 8 available in buffer (bytes 0..8 of 8: F0 7F 7F 06 44 06 01
+/// 21)" for a truncated payload. Rejected payloads are quoted in hex, up to 16
+/// bytes of the MIDI list around the error. Every
+/// function returning an error code sets it when returning non-zero and clears it
+/// when returning 0 (Success). The session `_new()` functions clear it as well, and
+/// set it when they return null. Other functions leave it untouched, so it can be
+/// read after `vlc_rtpmidi_get_error_message()`.
+///
+/// Each thread has its own detail, formed in a fixed buffer without allocating:
+/// messages longer than 159 bytes are truncated.
+///
+/// # Safety
+/// The returned pointer points to a null-terminated C string owned by the calling
+/// thread. It stays valid until the thread exits, but its contents change with the
+/// next call into the library from that thread. Do not free the pointer.
+///
+/// # Returns
+/// Pointer to the null-terminated detail, empty if the last call succeeded
+///
+/// # Example Usage (C)
+/// ```c
+/// int result = vlc_rtpmidi_slave_netsync_flow_ffi(buffer, len, &event);
+/// if (result != 0) {
+///     fprintf(stderr, "%s: %s\n", vlc_rtpmidi_get_error_message(result),
+///             vlc_rtpmidi_get_last_error_detail());
+/// }
+/// ```
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_get_last_error_detail() -> *const c_char {
+    ERROR_DETAIL.with_borrow(|detail| detail.bytes.as_ptr() as *const c_char)
+}
+
+// ============================================================================
+// TIMECODE CONVERSION FUNCTIONS
+// ============================================================================
+
+/// Convert microseconds to SMPTE timecode (30fps non-drop frame)
+///
+/// Performs exactly the same computation as `mtc::us_to_smpte()`, so C and Rust
+/// code agree on every frame boundary. Hours wrap around after 23.
+///
+/// # Safety
+/// This function is unsafe because it dereferences a raw pointer. Callers must ensure
+/// `out` points to a writable `VlcRtpmidiSmpteTime` structure.
+///
+/// # Arguments
+/// * `us` - Timestamp in microseconds
+/// * `out` - Output: the corresponding timecode
+///
+/// # Returns
+/// * `0` (Success) - Timecode written to `out`
+/// * Non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+///
+/// # Example Usage (C)
+/// ```c
+/// VlcRtpmidiSmpteTime tc;
+/// if (vlc_rtpmidi_us_to_smpte_ffi(position_us, &tc) == 0) {
+///     printf("%02u:%02u:%02u:%02u\n", tc.hours, tc.minutes, tc.seconds, tc.frames);
+/// }
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_us_to_smpte_ffi(
+    us: u64,
+    out: *mut VlcRtpmidiSmpteTime,
+) -> c_int {
+    with_error_detail(|| {
+        if out.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        *out = smpte_to_c(&us_to_smpte(us));
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Convert SMPTE timecode (30fps non-drop frame) to microseconds
+///
+/// Performs exactly the same computation as `mtc::smpte_to_us()`, after checking
+/// that every field of the timecode is in range.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `input` points to a readable `VlcRtpmidiSmpteTime` structure
+/// - `out` points to a writable `uint64_t`
+///
+/// # Arguments
+/// * `input` - Timecode to convert
+/// * `out` - Output: the corresponding timestamp in microseconds
+///
+/// # Returns
+/// * `0` (Success) - Timestamp written to `out`
+/// * `7` (InvalidTimecode) - A field of `input` is out of range; `out` is untouched
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+///
+/// # Example Usage (C)
+/// ```c
+/// VlcRtpmidiSmpteTime tc = { .hours = 1, .minutes = 0, .seconds = 0, .frames = 15 };
+/// uint64_t us;
+/// int result = vlc_rtpmidi_smpte_to_us_ffi(&tc, &us);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_smpte_to_us_ffi(
+    input: *const VlcRtpmidiSmpteTime,
+    out: *mut u64,
+) -> c_int {
+    with_error_detail(|| {
+        if input.is_null() || out.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        let smpte = match c_to_smpte(&*input) {
+            Ok(smpte) => smpte,
+            Err(error_code) => return error_code as c_int,
+        };
+        *out = smpte_to_us(&smpte);
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Split SMPTE timecode into the eight MTC Quarter Frame events of one cycle
+///
+/// Performs exactly the same computation as `mtc::smpte_to_quarter_frames()`. The
+/// events are ready to be passed to `vlc_rtpmidi_master_netsync_flow_ffi()` in
+/// order, piece 0 first.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `input` points to a readable `VlcRtpmidiSmpteTime` structure
+/// - `out_events` points to a writable array of 8 `VlcRtpmidiEvent` structures
+///
+/// # Arguments
+/// * `input` - Timecode to split
+/// * `out_events` - Output: the eight quarter-frame events
+///
+/// # Returns
+/// * `0` (Success) - Events written to `out_events`
+/// * `7` (InvalidTimecode) - A field of `input` is out of range; nothing is written
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+///
+/// # Example Usage (C)
+/// ```c
+/// VlcRtpmidiSmpteTime tc = { .hours = 0, .minutes = 1, .seconds = 0, .frames = 0 };
+/// VlcRtpmidiEvent pieces[8];
+/// if (vlc_rtpmidi_smpte_to_quarter_frames_ffi(&tc, pieces) == 0) {
+///     // Send pieces[0] to pieces[7], one per quarter frame period
+/// }
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_smpte_to_quarter_frames_ffi(
+    input: *const VlcRtpmidiSmpteTime,
+    out_events: *mut VlcRtpmidiEvent,
+) -> c_int {
+    with_error_detail(|| {
+        if input.is_null() || out_events.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        let smpte = match c_to_smpte(&*input) {
+            Ok(smpte) => smpte,
+            Err(error_code) => return error_code as c_int,
+        };
+
+        let out = slice::from_raw_parts_mut(out_events, 8);
+        for (c_event, qf) in out.iter_mut().zip(smpte_to_quarter_frames(&smpte)) {
+            *c_event = vlc_rtpmidi_create_mtc_quarter_event(qf.frame_type, qf.value);
+        }
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Reassemble SMPTE timecode from the eight MTC Quarter Frame events of one cycle
+///
+/// Performs exactly the same validation as `mtc::quarter_frames_to_smpte()`.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `events` points to a readable array of 8 `VlcRtpmidiEvent` structures
+/// - `out` points to a writable `VlcRtpmidiSmpteTime` structure
+///
+/// # Arguments
+/// * `events` - The quarter-frame events, piece 0 first
+/// * `out` - Output: the reassembled timecode
+///
+/// # Returns
+/// * `0` (Success) - Timecode written to `out`
+/// * `5` (InvalidEventType) - An event is not a well-formed MTC Quarter Frame
+/// * `7` (InvalidTimecode) - A piece value or the resulting timecode is out of
+///   range, or the rate bits are not 30fps
+/// * `8` (QuarterFrameOrder) - The events are not pieces 0 to 7 in order
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+///
+/// On error, `out` is untouched.
+///
+/// # Example Usage (C)
+/// ```c
+/// VlcRtpmidiSmpteTime tc;
+/// int result = vlc_rtpmidi_quarter_frames_to_smpte_ffi(received_pieces, &tc);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_quarter_frames_to_smpte_ffi(
+    events: *const VlcRtpmidiEvent,
+    out: *mut VlcRtpmidiSmpteTime,
+) -> c_int {
+    with_error_detail(|| {
+        if events.is_null() || out.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        let frames = match c_to_quarter_frames(slice::from_raw_parts(events, 8)) {
+            Ok(frames) => frames,
+            Err(error_code) => return error_code as c_int,
+        };
+
+        let smpte = match quarter_frames_to_smpte(&frames) {
+            Ok(smpte) => smpte,
+            Err(error) => return VlcRtpmidiErrorCode::from(&error) as c_int,
+        };
+        *out = smpte_to_c(&smpte);
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Convert microseconds to SMPTE timecode at the given frame rate
+///
+/// Performs exactly the same computation as `mtc::us_to_smpte_rate()`. With
+/// `VLC_RTPMIDI_FRAME_RATE_30_NDF` the result is that of
+/// `vlc_rtpmidi_us_to_smpte_ffi()`.
+///
+/// # Safety
+/// This function is unsafe because it dereferences a raw pointer. Callers must ensure
+/// `out` points to a writable `VlcRtpmidiSmpteTime` structure.
+///
+/// # Arguments
+/// * `us` - Timestamp in microseconds
+/// * `rate` - Frame rate, a `VlcRtpmidiFrameRate` value
+/// * `out` - Output: the corresponding timecode
+///
+/// # Returns
+/// * `0` (Success) - Timecode written to `out`
+/// * `12` (InvalidFrameRate) - `rate` is out of range; `out` is untouched
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+///
+/// # Example Usage (C)
+/// ```c
+/// VlcRtpmidiSmpteTime tc;
+/// vlc_rtpmidi_us_to_smpte_rate_ffi(position_us, VLC_RTPMIDI_FRAME_RATE_25, &tc);
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_us_to_smpte_rate_ffi(
+    us: u64,
+    rate: u32,
+    out: *mut VlcRtpmidiSmpteTime,
+) -> c_int {
+    with_error_detail(|| {
+        if out.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        let rate = match c_to_frame_rate(rate) {
+            Ok(rate) => rate,
+            Err(error_code) => return error_code as c_int,
+        };
+        *out = smpte_to_c(&us_to_smpte_rate(us, rate));
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Convert SMPTE timecode at the given frame rate to microseconds
+///
+/// Performs exactly the same computation as `mtc::smpte_to_us_rate()`, after
+/// checking that every field of the timecode is in range for `rate`.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `input` points to a readable `VlcRtpmidiSmpteTime` structure
+/// - `out` points to a writable `uint64_t`
+///
+/// # Arguments
+/// * `input` - Timecode to convert
+/// * `rate` - Frame rate of `input`, a `VlcRtpmidiFrameRate` value
+/// * `out` - Output: the corresponding timestamp in microseconds
+///
+/// # Returns
+/// * `0` (Success) - Timestamp written to `out`
+/// * `7` (InvalidTimecode) - A field of `input` is out of range; `out` is untouched
+/// * `12` (InvalidFrameRate) - `rate` is out of range; `out` is untouched
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_smpte_to_us_rate_ffi(
+    input: *const VlcRtpmidiSmpteTime,
+    rate: u32,
+    out: *mut u64,
+) -> c_int {
+    with_error_detail(|| {
+        if input.is_null() || out.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        let rate = match c_to_frame_rate(rate) {
+            Ok(rate) => rate,
+            Err(error_code) => return error_code as c_int,
+        };
+        let smpte = match c_to_smpte_rate(&*input, rate) {
+            Ok(smpte) => smpte,
+            Err(error_code) => return error_code as c_int,
+        };
+        *out = smpte_to_us_rate(&smpte, rate);
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Split SMPTE timecode into the eight MTC Quarter Frame events of one cycle at a frame rate
+///
+/// The rate is signalled in the last piece.
+///
+/// Performs exactly the same computation as `mtc::smpte_to_quarter_frames_rate()`.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `input` points to a readable `VlcRtpmidiSmpteTime` structure
+/// - `out_events` points to a writable array of 8 `VlcRtpmidiEvent` structures
+///
+/// # Arguments
+/// * `input` - Timecode to split
+/// * `rate` - Frame rate to signal in piece 7, a `VlcRtpmidiFrameRate` value
+/// * `out_events` - Output: the eight quarter-frame events
+///
+/// # Returns
+/// * `0` (Success) - Events written to `out_events`
+/// * `7` (InvalidTimecode) - A field of `input` is out of range; nothing is written
+/// * `12` (InvalidFrameRate) - `rate` is out of range; nothing is written
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_smpte_to_quarter_frames_rate_ffi(
+    input: *const VlcRtpmidiSmpteTime,
+    rate: u32,
+    out_events: *mut VlcRtpmidiEvent,
+) -> c_int {
+    with_error_detail(|| {
+        if input.is_null() || out_events.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        let rate = match c_to_frame_rate(rate) {
+            Ok(rate) => rate,
+            Err(error_code) => return error_code as c_int,
+        };
+        let smpte = match c_to_smpte_rate(&*input, rate) {
+            Ok(smpte) => smpte,
+            Err(error_code) => return error_code as c_int,
+        };
+
+        let out = slice::from_raw_parts_mut(out_events, 8);
+        for (c_event, qf) in out
+            .iter_mut()
+            .zip(smpte_to_quarter_frames_rate(&smpte, rate))
+        {
+            *c_event = vlc_rtpmidi_create_mtc_quarter_event(qf.frame_type, qf.value);
+        }
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Reassemble SMPTE timecode and its frame rate from the eight Quarter Frames of one cycle
+///
+/// Performs exactly the same validation as `mtc::quarter_frames_to_smpte_rate()`:
+/// unlike `vlc_rtpmidi_quarter_frames_to_smpte_ffi()`, every frame rate is accepted.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `events` points to a readable array of 8 `VlcRtpmidiEvent` structures
+/// - `out` points to a writable `VlcRtpmidiSmpteTime` structure
+/// - `rate_out` points to a writable `VlcRtpmidiFrameRate`
+///
+/// # Arguments
+/// * `events` - The quarter-frame events, piece 0 first
+/// * `out` - Output: the reassembled timecode
+/// * `rate_out` - Output: the frame rate signalled in piece 7
+///
+/// # Returns
+/// * `0` (Success) - Timecode and rate written
+/// * `5` (InvalidEventType) - An event is not a well-formed MTC Quarter Frame
+/// * `7` (InvalidTimecode) - A piece value or the resulting timecode is out of range
+/// * `8` (QuarterFrameOrder) - The events are not pieces 0 to 7 in order
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+///
+/// On error, `out` and `rate_out` are untouched.
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_quarter_frames_to_smpte_rate_ffi(
+    events: *const VlcRtpmidiEvent,
+    out: *mut VlcRtpmidiSmpteTime,
+    rate_out: *mut VlcRtpmidiFrameRate,
+) -> c_int {
+    with_error_detail(|| {
+        if events.is_null() || out.is_null() || rate_out.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        let frames = match c_to_quarter_frames(slice::from_raw_parts(events, 8)) {
+            Ok(frames) => frames,
+            Err(error_code) => return error_code as c_int,
+        };
+
+        let (smpte, rate) = match quarter_frames_to_smpte_rate(&frames) {
+            Ok(decoded) => decoded,
+            Err(error) => return VlcRtpmidiErrorCode::from(&error) as c_int,
+        };
+        *out = smpte_to_c(&smpte);
+        *rate_out = frame_rate_to_c(rate);
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Format SMPTE timecode as text
+///
+/// Writes `HH:MM:SS:FF`, or `HH:MM:SS;FF` at 29.97fps drop-frame, exactly as the
+/// `Display` implementation of `mtc::MtcFullFrame` does, so C and Rust show the
+/// same text for the same timecode.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `input` points to a readable `VlcRtpmidiSmpteTime` structure
+/// - `out` points to a writable buffer of at least `cap` bytes
+///
+/// # Arguments
+/// * `input` - Timecode to format
+/// * `rate` - Frame rate, a `VlcRtpmidiFrameRate` value
+/// * `out` - Output: null-terminated text
+/// * `cap` - Size of `out` in bytes, at least `VLC_RTPMIDI_SMPTE_STRING_SIZE`
+///
+/// # Returns
+/// * `0` (Success) - Text written to `out`
+/// * `3` (BufferTooSmall) - `cap` is below `VLC_RTPMIDI_SMPTE_STRING_SIZE`
+/// * `7` (InvalidTimecode) - A field is out of range for `rate`
+/// * `12` (InvalidFrameRate) - `rate` is out of range
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+///
+/// On error, `out` is untouched.
+///
+/// # Example Usage (C)
+/// ```c
+/// char text[VLC_RTPMIDI_SMPTE_STRING_SIZE];
+/// if (vlc_rtpmidi_smpte_to_string_ffi(&tc, VLC_RTPMIDI_FRAME_RATE_2997_DF, text, sizeof(text)) == 0)
+///     set_label(text); // "01:02:03;04"
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_smpte_to_string_ffi(
+    input: *const VlcRtpmidiSmpteTime,
+    rate: u32,
+    out: *mut c_char,
+    cap: usize,
+) -> c_int {
+    with_error_detail(|| {
+        if input.is_null() || out.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        let rate = match c_to_frame_rate(rate) {
+            Ok(rate) => rate,
+            Err(error_code) => return error_code as c_int,
+        };
+        let smpte = match c_to_smpte_rate(&*input, rate) {
+            Ok(smpte) => smpte,
+            Err(error_code) => return error_code as c_int,
+        };
+        if cap < VLC_RTPMIDI_SMPTE_STRING_SIZE {
+            return VlcRtpmidiErrorCode::BufferTooSmall as c_int;
+        }
+
+        let mut text = LogMessage {
+            bytes: [0; LOG_MESSAGE_CAPACITY],
+            len: 0,
+        };
+        let _ = match rate {
+            FrameRate::Fps2997Df => write!(text, "{:#}", smpte),
+            _ => write!(text, "{}", smpte),
+        };
+        let out = slice::from_raw_parts_mut(out.cast::<u8>(), cap);
+        out[..text.len].copy_from_slice(&text.bytes[..text.len]);
+        out[text.len] = 0;
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Parse SMPTE timecode text
+///
+/// Accepts `HH:MM:SS:FF` and `HH:MM:SS;FF` whatever the rate, with one or two
+/// digits per field, as the `FromStr` implementation of `mtc::MtcFullFrame` does.
+/// The frames field is then checked against `rate`.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `text` points to a null-terminated string
+/// - `out` points to a writable `VlcRtpmidiSmpteTime` structure
+///
+/// # Arguments
+/// * `text` - Timecode text, such as user input
+/// * `rate` - Frame rate, a `VlcRtpmidiFrameRate` value
+/// * `out` - Output: the parsed timecode
+///
+/// # Returns
+/// * `0` (Success) - Timecode written to `out`
+/// * `7` (InvalidTimecode) - A field is out of range for `rate`
+/// * `12` (InvalidFrameRate) - `rate` is out of range
+/// * `21` (MalformedTimecode) - `text` is not in either form
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+///
+/// On error, `out` is untouched.
+///
+/// # Example Usage (C)
+/// ```c
+/// VlcRtpmidiSmpteTime tc;
+/// if (vlc_rtpmidi_smpte_from_string_ffi(input, VLC_RTPMIDI_FRAME_RATE_25, &tc) != 0)
+///     show_error(vlc_rtpmidi_get_last_error_detail());
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_smpte_from_string_ffi(
+    text: *const c_char,
+    rate: u32,
+    out: *mut VlcRtpmidiSmpteTime,
+) -> c_int {
+    with_error_detail(|| {
+        if text.is_null() || out.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        let rate = match c_to_frame_rate(rate) {
+            Ok(rate) => rate,
+            Err(error_code) => return error_code as c_int,
+        };
+        let text = CStr::from_ptr(text).to_bytes();
+        let parsed = std::str::from_utf8(text)
+            .map_err(|_| MtcError::MalformedTimecode)
+            .and_then(str::parse::<MtcFullFrame>);
+        let smpte = match parsed {
+            Ok(smpte) => smpte_to_c(&smpte),
+            Err(error) => {
+                let problem = match error {
+                    MtcError::InvalidFrames(_)
+                    | MtcError::InvalidSeconds(_)
+                    | MtcError::InvalidMinutes(_)
+                    | MtcError::InvalidHours(_) => "is out of range",
+                    _ => "is not HH:MM:SS:FF or HH:MM:SS;FF",
+                };
+                set_error_detail(format_args!(
+                    "timecode {:?} {}",
+                    String::from_utf8_lossy(text),
+                    problem
+                ));
+                return VlcRtpmidiErrorCode::from(&error) as c_int;
+            }
+        };
+        if let Err(error_code) = c_to_smpte_rate(&smpte, rate) {
+            return error_code as c_int;
+        }
+        *out = smpte;
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+// ============================================================================
+// MTC QUARTER-FRAME ASSEMBLER
+// ============================================================================
+
+/// Opaque handle to a stateful MTC quarter-frame assembler
+///
+/// Collects quarter frames one at a time and reports the timecode once a full
+/// cycle of pieces 0-7 has been received, with the rules of
+/// `mtc::QuarterFrameAssembler`.
+///
+/// # Ownership
+/// Handles are created by `vlc_rtpmidi_mtc_assembler_new()` and owned by the
+/// caller, who must release each of them exactly once with
+/// `vlc_rtpmidi_mtc_assembler_free()`. A handle must not be used from several
+/// threads at the same time.
+///
+/// # Example Usage (C)
+/// ```c
+/// VlcRtpmidiMtcAssembler* assembler = vlc_rtpmidi_mtc_assembler_new();
+/// if (assembler == NULL) {
+///     return;
+/// }
+///
+/// VlcRtpmidiEvent event;
+/// while (receive_quarter_frame(&event)) {
+///     VlcRtpmidiSmpteTime tc;
+///     int complete;
+///     if (vlc_rtpmidi_mtc_assembler_feed(assembler, &event, &tc, &complete) == 0 && complete) {
+///         // A full cycle was received: tc holds the master position
+///     }
+/// }
+///
+/// vlc_rtpmidi_mtc_assembler_free(assembler);
+/// ```
+pub struct VlcRtpmidiMtcAssembler {
+    inner: QuarterFrameAssembler,
+}
+
+/// Create a quarter-frame assembler waiting for piece 0
+///
+/// This is the only function of the assembler interface that allocates.
+///
+/// # Returns
+/// A new handle, to be released with `vlc_rtpmidi_mtc_assembler_free()`
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_mtc_assembler_new() -> *mut VlcRtpmidiMtcAssembler {
+    Box::into_raw(Box::new(VlcRtpmidiMtcAssembler {
+        inner: QuarterFrameAssembler::new(),
+    }))
+}
+
+/// Feed one MTC Quarter Frame event into an assembler
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `handle` was returned by `vlc_rtpmidi_mtc_assembler_new()` and not yet freed
+/// - `event` points to a readable `VlcRtpmidiEvent` structure
+/// - `out` points to a writable `VlcRtpmidiSmpteTime` structure
+/// - `complete` points to a writable `int`
+///
+/// # Arguments
+/// * `handle` - The assembler
+/// * `event` - The received quarter frame
+/// * `out` - Output: the decoded timecode, written only when a cycle completes
+/// * `complete` - Output: 1 if this event completed a cycle and `out` was written,
+///   0 if more pieces are needed
+///
+/// # Returns
+/// * `0` (Success) - The event was accepted; check `complete`
+/// * `5` (InvalidEventType) - The event is not a well-formed MTC Quarter Frame or
+///   its piece number exceeds 7
+/// * `7` (InvalidTimecode) - The piece value or the completed timecode is invalid
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+///
+/// Invalid quarter frames discard the partially assembled cycle.
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_mtc_assembler_feed(
+    handle: *mut VlcRtpmidiMtcAssembler,
+    event: *const VlcRtpmidiEvent,
+    out: *mut VlcRtpmidiSmpteTime,
+    complete: *mut c_int,
+) -> c_int {
+    with_error_detail(|| {
+        if handle.is_null() || event.is_null() || out.is_null() || complete.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        *complete = 0;
+
+        let frame = match c_to_midi_event(&*event) {
+            Ok(MidiEvent::MtcQuarter { msg_type, value }) => MtcQuarterFrame {
+                frame_type: msg_type,
+                value,
+            },
+            Ok(_) => return VlcRtpmidiErrorCode::InvalidEventType as c_int,
+            Err(error_code) => return error_code as c_int,
+        };
+
+        // Pieces past 7 are not well-formed quarter frames
+        if frame.frame_type > 7 {
+            (*handle).inner.reset();
+            return VlcRtpmidiErrorCode::InvalidEventType as c_int;
+        }
+
+        match (*handle).inner.feed(frame) {
+            Ok(Some(smpte)) => {
+                *out = smpte_to_c(&smpte);
+                *complete = 1;
+            }
+            Ok(None) => {}
+            Err(error) => return VlcRtpmidiErrorCode::from(&error) as c_int,
+        }
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Discard the partially assembled cycle of an assembler
+///
+/// # Safety
+/// `handle` must have been returned by `vlc_rtpmidi_mtc_assembler_new()` and not
+/// yet freed.
+///
+/// # Returns
+/// * `0` (Success) - The assembler waits for piece 0 again
+/// * `4` (NullPointer) - `handle` is null
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_mtc_assembler_reset(
+    handle: *mut VlcRtpmidiMtcAssembler,
+) -> c_int {
+    with_error_detail(|| {
+        if handle.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        (*handle).inner.reset();
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Get the frame rate signalled by the last cycle an assembler completed
+///
+/// Cycles are accepted at any frame rate; use this to interpret the timecode
+/// returned by `vlc_rtpmidi_mtc_assembler_feed()`.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `handle` was returned by `vlc_rtpmidi_mtc_assembler_new()` and not yet freed
+/// - `rate_out` points to a writable `VlcRtpmidiFrameRate`
+///
+/// # Returns
+/// * `0` (Success) - Rate written to `rate_out`, 30fps until a cycle completes
+/// * `4` (NullPointer) - `handle` or `rate_out` is null
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_mtc_assembler_frame_rate(
+    handle: *const VlcRtpmidiMtcAssembler,
+    rate_out: *mut VlcRtpmidiFrameRate,
+) -> c_int {
+    with_error_detail(|| {
+        if handle.is_null() || rate_out.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        *rate_out = frame_rate_to_c((*handle).inner.frame_rate());
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Release an assembler
+///
+/// Passing null is allowed and does nothing, like `free()`.
+///
+/// # Safety
+/// `handle` must be null or have been returned by `vlc_rtpmidi_mtc_assembler_new()`
+/// and not yet freed. It must not be used after this call.
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_mtc_assembler_free(handle: *mut VlcRtpmidiMtcAssembler) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+// ============================================================================
+// STREAM PARSER
+// ============================================================================
+
+/// Opaque handle to a byte-stream payload parser
+///
+/// Buffers bytes received in arbitrary chunks, such as from a UART, and returns
+/// one event per complete payload, like `netsync::StreamParser`. Bytes that cannot
+/// start a payload are skipped until a plausible header is found, so the parser
+/// recovers from line noise and from joining a stream mid-payload.
+///
+/// # Ownership
+/// Handles are created by `vlc_rtpmidi_stream_parser_new()` and owned by the
+/// caller, who must release each of them exactly once with
+/// `vlc_rtpmidi_stream_parser_free()`. A handle must not be used from several
+/// threads at the same time.
+///
+/// # Allocation
+/// The buffer is reserved at construction: feeding and polling never allocate.
+///
+/// # Example Usage (C)
+/// ```c
+/// VlcRtpmidiStreamParser* parser = vlc_rtpmidi_stream_parser_new(256);
+/// if (parser == NULL) {
+///     return;
+/// }
+///
+/// uint8_t chunk[32];
+/// size_t len;
+/// while ((len = uart_read(chunk, sizeof(chunk))) > 0) {
+///     vlc_rtpmidi_stream_parser_feed(parser, chunk, len);
+///     VlcRtpmidiEvent event;
+///     int result;
+///     while ((result = vlc_rtpmidi_stream_parser_poll(parser, &event)) !=
+///            VLC_RTPMIDI_ERROR_WOULD_BLOCK) {
+///         if (result == 0) {
+///             // Process event
+///         }
+///     }
+/// }
+///
+/// vlc_rtpmidi_stream_parser_free(parser);
+/// ```
+pub struct VlcRtpmidiStreamParser {
+    inner: StreamParser,
+}
+
+/// Create a stream parser buffering at most `capacity` unread bytes
+///
+/// Capacities below `vlc_rtpmidi_get_max_payload_size()` are raised to it.
+///
+/// # Arguments
+/// * `capacity` - Size of the internal buffer in bytes
+///
+/// # Returns
+/// A new handle, to be released with `vlc_rtpmidi_stream_parser_free()`
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_stream_parser_new(capacity: usize) -> *mut VlcRtpmidiStreamParser {
+    Box::into_raw(Box::new(VlcRtpmidiStreamParser {
+        inner: StreamParser::new(capacity),
+    }))
+}
+
+/// Append received bytes to a stream parser
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `handle` was returned by `vlc_rtpmidi_stream_parser_new()` and not yet freed
+/// - `bytes` points to a readable buffer of at least `len` bytes
+///
+/// # Arguments
+/// * `handle` - The parser
+/// * `bytes` - The received bytes (may be NULL when `len` is 0)
+/// * `len` - Number of bytes to append
+///
+/// # Returns
+/// * `0` (Success) - All bytes were appended
+/// * `3` (BufferTooSmall) - The bytes do not fit next to the unread ones; nothing
+///   is appended. Poll events to make room, then feed them again
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_stream_parser_feed(
+    handle: *mut VlcRtpmidiStreamParser,
+    bytes: *const u8,
+    len: usize,
+) -> c_int {
+    with_error_detail(|| {
+        if handle.is_null() || (bytes.is_null() && len > 0) {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+        if len == 0 {
+            return VlcRtpmidiErrorCode::Success as c_int;
+        }
+
+        match (*handle).inner.feed(slice::from_raw_parts(bytes, len)) {
+            Ok(()) => VlcRtpmidiErrorCode::Success as c_int,
+            // The parser's own buffer is full, not a payload truncated
+            Err(_) => VlcRtpmidiErrorCode::BufferTooSmall as c_int,
+        }
+    })
+}
+
+/// Take the event of the next complete payload
+///
+/// Call repeatedly after each `vlc_rtpmidi_stream_parser_feed()` until it returns
+/// `WouldBlock`.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `handle` was returned by `vlc_rtpmidi_stream_parser_new()` and not yet freed
+/// - `event` points to a writable `VlcRtpmidiEvent` structure
+///
+/// # Returns
+/// * `0` (Success) - An event was written to `event`
+/// * `5` (InvalidEventType) - The payload holds a message without an event
+///   structure; it is consumed and `event` is untouched
+/// * `16` (WouldBlock) - No complete payload is buffered yet; `event` is untouched
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_stream_parser_poll(
+    handle: *mut VlcRtpmidiStreamParser,
+    event: *mut VlcRtpmidiEvent,
+) -> c_int {
+    with_error_detail(|| {
+        if handle.is_null() || event.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        let Some(midi_event) = (*handle).inner.poll_ref() else {
+            return VlcRtpmidiErrorCode::WouldBlock as c_int;
+        };
+        match midi_event_ref_to_c(midi_event) {
+            Ok(c_event) => {
+                *event = c_event;
+                VlcRtpmidiErrorCode::Success as c_int
+            }
+            Err(error_code) => error_code as c_int,
+        }
+    })
+}
+
+/// Release a stream parser
+///
+/// Passing null is allowed and does nothing, like `free()`.
+///
+/// # Safety
+/// `handle` must be null or have been returned by `vlc_rtpmidi_stream_parser_new()`
+/// and not yet freed. It must not be used after this call.
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_stream_parser_free(handle: *mut VlcRtpmidiStreamParser) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+// ============================================================================
+// MASTER SESSION
+// ============================================================================
+
+/// Opaque handle to a master session
+///
+/// Numbers outgoing packets, stamps them with the configured SSRC and schedules
+/// the maintenance packets slaves need to resync, like `netsync::MasterSession`.
+/// Functions taking this handle produce complete RTP packets, not bare payloads.
+///
+/// # Ownership
+/// Handles are created by `vlc_rtpmidi_master_session_new()` and owned by the
+/// caller, who must release each of them exactly once with
+/// `vlc_rtpmidi_master_session_free()`. A handle must not be used from several
+/// threads at the same time.
+///
+/// # Allocation
+/// All memory is reserved at construction: building packets never allocates.
+///
+/// # Example Usage (C)
+/// ```c
+/// VlcRtpmidiNetsyncConfig config = vlc_rtpmidi_default_netsync_config();
+/// config.ssrc = 0x12345678;
+/// VlcRtpmidiMasterSession* master = vlc_rtpmidi_master_session_new(&config);
+///
+/// uint8_t packet[64];
+/// size_t packet_size;
+/// VlcRtpmidiEvent play = vlc_rtpmidi_create_mmc_play_event();
+/// if (vlc_rtpmidi_master_session_packet_for_event(master, &play, now_us, packet,
+///                                                 sizeof(packet), &packet_size) == 0) {
+///     send(sock, packet, packet_size, 0);
+/// }
+///
+/// // Once per quarter-frame period
+/// uint8_t packets[128];
+/// size_t sizes[VLC_RTPMIDI_MAX_TICK_PACKETS], count;
+/// if (vlc_rtpmidi_master_session_tick(master, now_us, packets, sizeof(packets), sizes,
+///                                     VLC_RTPMIDI_MAX_TICK_PACKETS, &count) == 0) {
+///     size_t offset = 0;
+///     for (size_t i = 0; i < count; i++) {
+///         send(sock, packets + offset, sizes[i], 0);
+///         offset += sizes[i];
+///     }
+/// }
+///
+/// vlc_rtpmidi_master_session_free(master);
+/// ```
+pub struct VlcRtpmidiMasterSession {
+    session: MasterSession,
+    /// Packet staging area, with room for the largest tick
+    scratch: Vec<u8>,
+    /// Device ID of the MTC Full Frames and MMC commands sent
+    device_id: u8,
+}
+
+impl VlcRtpmidiMasterSession {
+    /// Addresses one packet built by the session to the configured device.
+    fn address_packet(&self, packet: &mut [u8]) {
+        if self.device_id != SYSEX_DEVICE_ID_BROADCAST {
+            // Sessions always use the one-octet payload header
+            set_realtime_sysex_device_id(&mut packet[RTP_HEADER_LENGTH + 1..], self.device_id);
+        }
+    }
+}
+
+/// Get the default session configuration
+///
+/// SSRC 0, one Full Frame per second, no cycle-count trigger, lenient parsing,
+/// 30fps non-drop frame, messages addressed to all devices. `struct_size` is the
+/// library's; prefer `vlc_rtpmidi_netsync_config_default()`, which records the
+/// size the application was compiled with.
+///
+/// # Returns
+/// Initialized `VlcRtpmidiNetsyncConfig` structure
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_default_netsync_config() -> VlcRtpmidiNetsyncConfig {
+    let config = NetsyncConfig::default();
+    VlcRtpmidiNetsyncConfig {
+        struct_size: size_of::<VlcRtpmidiNetsyncConfig>() as u32,
+        ssrc: config.ssrc,
+        full_frame_interval_us: config.refresh.full_frame_interval_us.unwrap_or(0),
+        quarter_frame_cycles: config.refresh.quarter_frame_cycles.unwrap_or(0),
+        strict_parsing: (config.policy == ParsePolicy::Strict) as u8,
+        frame_rate: frame_rate_to_c(config.tracker.frame_rate) as u32,
+        device_id: SYSEX_DEVICE_ID_BROADCAST,
+    }
+}
+
+/// Initialize a session configuration with the default values
+///
+/// Writes the values of `vlc_rtpmidi_default_netsync_config()` to the first
+/// `struct_size` bytes of `out`, then sets `out->struct_size`. Fields the library
+/// does not know are zeroed.
+///
+/// # Safety
+/// `out` must point to a writable structure of `struct_size` bytes.
+///
+/// # Arguments
+/// * `out` - Output: the configuration to initialize
+/// * `struct_size` - `sizeof(VlcRtpmidiNetsyncConfig)`
+///
+/// # Returns
+/// * `0` (Success) - `out` initialized
+/// * `14` (LayoutMismatch) - `struct_size` is too small for any known layout
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
 ///
 /// # Example Usage (C)
 /// ```c
-/// size_t max_size = vlc_rtpmidi_get_max_payload_size();
-/// uint8_t* buffer = malloc(max_size);
+/// VlcRtpmidiNetsyncConfig config;
+/// vlc_rtpmidi_netsync_config_default(&config, sizeof(config));
+/// config.device_id = 0x10;
+/// VlcRtpmidiMasterSession *session = vlc_rtpmidi_master_session_new(&config);
 /// ```
 #[no_mangle]
-pub extern "C" fn vlc_rtpmidi_get_max_payload_size() -> usize {
-    // MMC Locate has the largest payload:
-    // - 1 byte header + 13 bytes MMC data = 14 bytes
-    // - Adding padding for safety and future expansion
-    16
+pub unsafe extern "C" fn vlc_rtpmidi_netsync_config_default(
+    out: *mut VlcRtpmidiNetsyncConfig,
+    struct_size: usize,
+) -> c_int {
+    with_error_detail(|| {
+        if out.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+        if struct_size < MIN_CONFIG_SIZE || u32::try_from(struct_size).is_err() {
+            set_error_detail(format_args!(
+                "struct_size {} is below {}",
+                struct_size, MIN_CONFIG_SIZE
+            ));
+            return VlcRtpmidiErrorCode::LayoutMismatch as c_int;
+        }
+
+        let defaults = vlc_rtpmidi_default_netsync_config();
+        std::ptr::write_bytes(out.cast::<u8>(), 0, struct_size);
+        std::ptr::copy_nonoverlapping(
+            (&defaults as *const VlcRtpmidiNetsyncConfig).cast::<u8>(),
+            out.cast::<u8>(),
+            struct_size.min(size_of::<VlcRtpmidiNetsyncConfig>()),
+        );
+        (*out).struct_size = struct_size as u32;
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
 }
 
-/// Get human-readable error message for an error code
+/// Check a session configuration
 ///
-/// Converts numeric error codes returned by FFI functions into
-/// descriptive English text for debugging and user feedback.
+/// Performs the checks of the session `_new()` functions, which only return null,
+/// and reports the first failing one.
 ///
 /// # Safety
-/// The returned pointer is valid for the lifetime of the program
-/// and points to a null-terminated C string. Do not free the pointer.
+/// `config` must point to a readable configuration of `config->struct_size` bytes.
+///
+/// # Returns
+/// * `0` (Success) - The session `_new()` functions accept `config`
+/// * `14` (LayoutMismatch) - `struct_size` is too small, or fields unknown to this
+///   library are set
+/// * `12` (InvalidFrameRate) - `frame_rate` is not a `VlcRtpmidiFrameRate` value
+/// * `13` (InvalidDeviceId) - `device_id` is above 0x7F
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_netsync_config_validate(
+    config: *const VlcRtpmidiNetsyncConfig,
+) -> c_int {
+    with_error_detail(|| {
+        if config.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        match read_c_config(config).and_then(|config| c_to_config(&config)) {
+            Ok(_) => VlcRtpmidiErrorCode::Success as c_int,
+            Err(error_code) => error_code as c_int,
+        }
+    })
+}
+
+/// Get the maximum size of one RTP packet built by a session
+///
+/// # Returns
+/// Maximum packet size in bytes; `vlc_rtpmidi_master_session_tick()` needs
+/// `VLC_RTPMIDI_MAX_TICK_PACKETS` times this much
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_get_max_packet_size() -> usize {
+    MAX_PACKET_LENGTH
+}
+
+/// Create a master session
+///
+/// # Safety
+/// `config` must point to a readable configuration of `config->struct_size` bytes.
 ///
 /// # Arguments
-/// * `error_code` - Error code from any FFI function
+/// * `config` - Session configuration, copied into the session
 ///
 /// # Returns
-/// Pointer to null-terminated error message string
+/// A new handle, to be released with `vlc_rtpmidi_master_session_free()`, or null
+/// if `config` is null or invalid; `vlc_rtpmidi_netsync_config_validate()` tells why
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_master_session_new(
+    config: *const VlcRtpmidiNetsyncConfig,
+) -> *mut VlcRtpmidiMasterSession {
+    clear_error_detail();
+    if config.is_null() {
+        set_error_detail(format_args!("config is null"));
+        return std::ptr::null_mut();
+    }
+    let Ok(c_config) = read_c_config(config) else {
+        return std::ptr::null_mut();
+    };
+    let Ok(config) = c_to_config(&c_config) else {
+        return std::ptr::null_mut();
+    };
+
+    Box::into_raw(Box::new(VlcRtpmidiMasterSession {
+        session: MasterSession::with_config(config),
+        scratch: Vec::with_capacity(MAX_TICK_PACKETS * MAX_PACKET_LENGTH),
+        device_id: c_config.device_id,
+    }))
+}
+
+/// Build the RTP packet for an application event
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `handle` was returned by `vlc_rtpmidi_master_session_new()` and not yet freed
+/// - `event` points to a readable `VlcRtpmidiEvent` structure
+/// - `buffer` points to a writable buffer of at least `buffer_size` bytes
+/// - `actual_size` points to a writable `size_t`
+///
+/// # Arguments
+/// * `handle` - The master session
+/// * `event` - The sync event to send
+/// * `now_us` - Local monotonic time at which the packet is sent
+/// * `buffer` - Destination buffer for the RTP packet
+/// * `buffer_size` - Size of the destination buffer in bytes
+/// * `actual_size` - Output: actual number of bytes written to buffer
+///
+/// # Returns
+/// * `0` (Success) - Packet generated; the session recorded the event
+/// * `1` (InvalidMasterEvent) - A quarter frame completed an invalid timecode
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+///
+/// On error no sequence number is used, so a `BufferTooSmall` event can be retried
+/// with a larger buffer.
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_master_session_packet_for_event(
+    handle: *mut VlcRtpmidiMasterSession,
+    event: *const VlcRtpmidiEvent,
+    now_us: u64,
+    buffer: *mut u8,
+    buffer_size: usize,
+    actual_size: *mut usize,
+) -> c_int {
+    with_error_detail(|| {
+        if handle.is_null() || event.is_null() || buffer.is_null() || actual_size.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        *actual_size = 0;
+
+        let rust_event = match c_to_midi_event(&*event) {
+            Ok(event) => event,
+            Err(error_code) => return error_code as c_int,
+        };
+
+        // Check the size before the session records the event and uses a sequence number
+        let packet_len = match master_payload_len(&rust_event) {
+            Ok(len) => RTP_HEADER_LENGTH + len,
+            Err(error) => {
+                log_rejected_event("master session", &rust_event);
+                return VlcRtpmidiErrorCode::from(&error) as c_int;
+            }
+        };
+        if packet_len > buffer_size {
+            return VlcRtpmidiErrorCode::BufferTooSmall as c_int;
+        }
+
+        let handle = &mut *handle;
+        handle.scratch.clear();
+        if let Err(error) =
+            handle
+                .session
+                .packet_for_event_into(&rust_event, now_us, &mut handle.scratch)
+        {
+            log_rejected_event("master session", &rust_event);
+            return VlcRtpmidiErrorCode::from(&error) as c_int;
+        }
+
+        let buffer_slice = slice::from_raw_parts_mut(buffer, buffer_size);
+        let packet = &mut buffer_slice[..handle.scratch.len()];
+        packet.copy_from_slice(&handle.scratch);
+        handle.address_packet(packet);
+        *actual_size = handle.scratch.len();
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Build the maintenance packets due at `now_us`
+///
+/// Call periodically, for example once per quarter-frame period. When a Full
+/// Frame is due, it is returned followed by an MMC Play if the transport is
+/// running. The packets are written back to back to `buffer`, and the size of
+/// each to `packet_sizes`.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `handle` was returned by `vlc_rtpmidi_master_session_new()` and not yet freed
+/// - `buffer` points to a writable buffer of at least `buffer_size` bytes
+/// - `packet_sizes` points to a writable array of at least `max_packets` values
+/// - `packet_count` points to a writable `size_t`
+///
+/// # Arguments
+/// * `handle` - The master session
+/// * `now_us` - Local monotonic time, on the same clock as for events
+/// * `buffer` - Destination buffer, at least `VLC_RTPMIDI_MAX_TICK_PACKETS` times
+///   `vlc_rtpmidi_get_max_packet_size()` bytes
+/// * `buffer_size` - Size of the destination buffer in bytes
+/// * `packet_sizes` - Output: size of each packet, in order
+/// * `max_packets` - Capacity of `packet_sizes`, at least `VLC_RTPMIDI_MAX_TICK_PACKETS`
+/// * `packet_count` - Output: number of packets written, 0 if nothing is due
+///
+/// # Returns
+/// * `0` (Success) - `packet_count` packets written
+/// * `3` (BufferTooSmall) - `buffer` or `packet_sizes` is smaller than required;
+///   the session is unchanged
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_master_session_tick(
+    handle: *mut VlcRtpmidiMasterSession,
+    now_us: u64,
+    buffer: *mut u8,
+    buffer_size: usize,
+    packet_sizes: *mut usize,
+    max_packets: usize,
+    packet_count: *mut usize,
+) -> c_int {
+    with_error_detail(|| {
+        if handle.is_null() || buffer.is_null() || packet_sizes.is_null() || packet_count.is_null()
+        {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        *packet_count = 0;
+
+        // Require room for the worst case before the session decides what is due
+        if buffer_size < MAX_TICK_PACKETS * MAX_PACKET_LENGTH || max_packets < MAX_TICK_PACKETS {
+            return VlcRtpmidiErrorCode::BufferTooSmall as c_int;
+        }
+
+        let handle = &mut *handle;
+        handle.scratch.clear();
+        let mut lengths = [0; MAX_TICK_PACKETS];
+        let count = handle
+            .session
+            .tick_into(now_us, &mut handle.scratch, &mut lengths);
+
+        let buffer_slice = slice::from_raw_parts_mut(buffer, buffer_size);
+        buffer_slice[..handle.scratch.len()].copy_from_slice(&handle.scratch);
+        let mut offset = 0;
+        for &len in &lengths[..count] {
+            handle.address_packet(&mut buffer_slice[offset..offset + len]);
+            offset += len;
+        }
+        slice::from_raw_parts_mut(packet_sizes, max_packets)[..count]
+            .copy_from_slice(&lengths[..count]);
+        *packet_count = count;
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Read the session counters
+///
+/// Copies the counters without allocating, so it is cheap enough to poll
+/// periodically from a monitoring thread, as long as calls on the same handle are
+/// not concurrent.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `handle` was returned by `vlc_rtpmidi_master_session_new()` and not yet freed
+/// - `out` points to a writable `VlcRtpmidiNetsyncMetrics` structure
+///
+/// # Returns
+/// * `0` (Success) - Counters written to `out`
+/// * Non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
 ///
 /// # Example Usage (C)
 /// ```c
-/// int result = vlc_rtpmidi_master_netsync_flow_ffi(...);
-/// if (result != 0) {
-///     const char* message = vlc_rtpmidi_get_error_message(result);
-///     printf("Error: %s\n", message);
+/// VlcRtpmidiNetsyncMetrics metrics;
+/// if (vlc_rtpmidi_master_session_metrics(session, &metrics) == 0) {
+///     report_counter("rtpmidi.packets_sent", metrics.packets_sent);
 /// }
 /// ```
 #[no_mangle]
-pub extern "C" fn vlc_rtpmidi_get_error_message(error_code: c_int) -> *const c_char {
-    let message = match error_code {
-        x if x == VlcRtpmidiErrorCode::Success as c_int => "Success\0",
-        x if x == VlcRtpmidiErrorCode::InvalidMasterEvent as c_int => "Invalid master event\0",
-        x if x == VlcRtpmidiErrorCode::InvalidSlaveEvent as c_int => "Invalid slave event\0",
-        x if x == VlcRtpmidiErrorCode::BufferTooSmall as c_int => "Buffer too small\0",
-        x if x == VlcRtpmidiErrorCode::NullPointer as c_int => "Null pointer passed\0",
-        x if x == VlcRtpmidiErrorCode::InvalidEventType as c_int => "Invalid event type\0",
-        _ => "Unknown error\0",
+pub unsafe extern "C" fn vlc_rtpmidi_master_session_metrics(
+    handle: *const VlcRtpmidiMasterSession,
+    out: *mut VlcRtpmidiNetsyncMetrics,
+) -> c_int {
+    with_error_detail(|| {
+        if handle.is_null() || out.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        *out = metrics_to_c((*handle).session.metrics());
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Set every session counter back to zero
+///
+/// # Safety
+/// `handle` must have been returned by `vlc_rtpmidi_master_session_new()` and not
+/// yet freed.
+///
+/// # Returns
+/// * `0` (Success) - Counters cleared
+/// * Non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_master_session_reset_metrics(
+    handle: *mut VlcRtpmidiMasterSession,
+) -> c_int {
+    with_error_detail(|| {
+        if handle.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        (*handle).session.reset_metrics();
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Release a master session
+///
+/// Passing null is allowed and does nothing, like `free()`.
+///
+/// # Safety
+/// `handle` must be null or have been returned by `vlc_rtpmidi_master_session_new()`
+/// and not yet freed. It must not be used after this call.
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_master_session_free(handle: *mut VlcRtpmidiMasterSession) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+// ============================================================================
+// SLAVE SESSION
+// ============================================================================
+
+/// Number of decoded events a slave session handle keeps until they are polled.
+const SLAVE_EVENT_QUEUE_CAPACITY: usize = 32;
+
+/// Opaque handle to a slave session
+///
+/// Receives complete RTP packets, drops duplicates, late packets and packets
+/// from other senders, and assembles quarter frames, like `netsync::SlaveSession`.
+/// Decoded events are queued and drained one at a time with
+/// `vlc_rtpmidi_slave_session_poll_event()`:
+/// - MMC commands are returned as such
+/// - Every Full Frame and every completed quarter-frame cycle is returned as an
+///   MTC Full Frame event carrying the master position, as timecode at the
+///   configured frame rate
+///
+/// Beat clock and other messages are not queued. The queue holds 32 events; when
+/// it is full, the oldest event is dropped.
+///
+/// # Ownership and threads
+/// Handles are created by `vlc_rtpmidi_slave_session_new()` and owned by the
+/// caller, who must release each of them exactly once with
+/// `vlc_rtpmidi_slave_session_free()`. A handle is not thread-safe: use it from
+/// one thread at a time, typically the network receive thread, or protect every
+/// call with a lock.
+///
+/// # Example Usage (C)
+/// ```c
+/// VlcRtpmidiNetsyncConfig config = vlc_rtpmidi_default_netsync_config();
+/// VlcRtpmidiSlaveSession* slave = vlc_rtpmidi_slave_session_new(&config);
+///
+/// ssize_t len = recv(sock, packet, sizeof(packet), 0);
+/// int status = vlc_rtpmidi_slave_session_feed(slave, packet, len, now_us);
+/// if (status == VLC_RTPMIDI_ERROR_SUCCESS) {
+///     VlcRtpmidiEvent event;
+///     while (vlc_rtpmidi_slave_session_poll_event(slave, &event) == VLC_RTPMIDI_ERROR_SUCCESS) {
+///         // Apply the event
+///     }
+/// }
+///
+/// vlc_rtpmidi_slave_session_free(slave);
+/// ```
+pub struct VlcRtpmidiSlaveSession {
+    session: SlaveSession,
+    events: EventQueue,
+}
+
+/// Bounded queue of C events filled by the session's handler callbacks.
+struct EventQueue {
+    events: VecDeque<VlcRtpmidiEvent>,
+    /// Rate of the MTC Full Frame events reporting time updates
+    frame_rate: FrameRate,
+}
+
+impl EventQueue {
+    fn push(&mut self, event: VlcRtpmidiEvent) {
+        if self.events.len() == SLAVE_EVENT_QUEUE_CAPACITY {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+    }
+}
+
+impl NetsyncHandler for EventQueue {
+    fn on_play(&mut self) {
+        self.push(vlc_rtpmidi_create_mmc_play_event());
+    }
+
+    fn on_stop(&mut self) {
+        self.push(vlc_rtpmidi_create_mmc_stop_event());
+    }
+
+    fn on_mmc(&mut self, command: MmcCommand) {
+        if let Ok(event) = midi_event_to_c(&MidiEvent::Mmc(command)) {
+            self.push(event);
+        }
+    }
+
+    fn on_locate(&mut self, position: MtcFullFrame) {
+        self.push(vlc_rtpmidi_create_mmc_locate_event(
+            position.hours,
+            position.minutes,
+            position.seconds,
+            position.frames,
+        ));
+    }
+
+    fn on_time_update(&mut self, us: VlcTickT) {
+        let smpte = us_to_smpte_rate(us, self.frame_rate);
+        self.push(vlc_rtpmidi_create_mtc_full_event(
+            smpte.hours,
+            smpte.minutes,
+            smpte.seconds,
+            smpte.frames,
+        ));
+    }
+}
+
+/// Create a slave session
+///
+/// # Safety
+/// `config` must point to a readable configuration of `config->struct_size` bytes.
+///
+/// # Arguments
+/// * `config` - Session configuration, copied into the session
+///
+/// # Returns
+/// A new handle, to be released with `vlc_rtpmidi_slave_session_free()`, or null
+/// if `config` is null or invalid; `vlc_rtpmidi_netsync_config_validate()` tells why
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_slave_session_new(
+    config: *const VlcRtpmidiNetsyncConfig,
+) -> *mut VlcRtpmidiSlaveSession {
+    clear_error_detail();
+    if config.is_null() {
+        set_error_detail(format_args!("config is null"));
+        return std::ptr::null_mut();
+    }
+    let Ok(config) = read_c_config(config).and_then(|config| c_to_config(&config)) else {
+        return std::ptr::null_mut();
     };
-    message.as_ptr() as *const c_char
+
+    Box::into_raw(Box::new(VlcRtpmidiSlaveSession {
+        events: EventQueue {
+            events: VecDeque::with_capacity(SLAVE_EVENT_QUEUE_CAPACITY),
+            frame_rate: config.tracker.frame_rate,
+        },
+        session: SlaveSession::with_config(config),
+    }))
+}
+
+/// Process a complete RTP packet
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `handle` was returned by `vlc_rtpmidi_slave_session_new()` and not yet freed
+/// - `buffer` points to a readable buffer of at least `buffer_len` bytes
+///
+/// # Arguments
+/// * `handle` - The slave session
+/// * `buffer` - The received RTP packet
+/// * `buffer_len` - Length of the packet in bytes
+/// * `arrival_us` - Local monotonic time at which the packet was received
+///
+/// # Returns
+/// * `0` (Success) - The packet was accepted; poll for the resulting event
+/// * `6` (NoEvent) - Valid packet without MIDI commands
+/// * `9` (Duplicate) - Repeated sequence number, dropped
+/// * `10` (OutOfOrder) - Older than the last packet received, dropped
+/// * `11` (ForeignSsrc) - Sent by another SSRC than the session's sender, dropped
+/// * `17` (TruncatedPayload) - The RTP header or payload is cut short
+/// * `18` (HeaderMismatch) - Payload flags or LEN that the session's parse policy
+///   rejects
+/// * `15` (InvalidMidi), `19` (MalformedSysex), `20` (ValueOutOfRange) - The MIDI
+///   list is rejected by the session's parse policy
+/// * `2` (InvalidSlaveEvent) - Any other invalid packet
+/// * `22` (SessionFailed) - The packet uses an RTP version other than 2, or such a
+///   packet was fed before: the session drops every packet and must be recreated.
+///   The cause is logged at `Error` and kept as the last error detail
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_slave_session_feed(
+    handle: *mut VlcRtpmidiSlaveSession,
+    buffer: *const u8,
+    buffer_len: usize,
+    arrival_us: u64,
+) -> c_int {
+    with_error_detail(|| {
+        if handle.is_null() || buffer.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        let handle = &mut *handle;
+        let packet = slice::from_raw_parts(buffer, buffer_len);
+        let code = match handle
+            .session
+            .feed_packet(packet, arrival_us, &mut handle.events)
+        {
+            Ok(PacketStatus::Accepted) => VlcRtpmidiErrorCode::Success,
+            Ok(PacketStatus::NoCommands) => VlcRtpmidiErrorCode::NoEvent,
+            Ok(PacketStatus::Duplicate) => VlcRtpmidiErrorCode::Duplicate,
+            Ok(PacketStatus::OutOfOrder) => VlcRtpmidiErrorCode::OutOfOrder,
+            Ok(PacketStatus::ForeignSsrc | PacketStatus::Looped) => {
+                VlcRtpmidiErrorCode::ForeignSsrc
+            }
+            // Only returned by the bidirectional endpoint
+            Ok(PacketStatus::Feedback) => VlcRtpmidiErrorCode::Success,
+            Err(NetsyncError::SessionFailed) => {
+                if let Some(failure) = handle.session.failure() {
+                    log(
+                        VlcRtpmidiLogLevel::Error,
+                        format_args!("slave session: failed: {}", failure),
+                    );
+                }
+                VlcRtpmidiErrorCode::SessionFailed
+            }
+            Err(_) => match RtpHeader::parse(packet) {
+                Ok((_, payload)) => {
+                    let code = reject_payload("slave session", payload);
+                    // A stricter policy may be what rejected it
+                    match slave_netsync_flow_with_policy(payload, handle.session.parse_policy()) {
+                        Err(violation) => {
+                            if let Some(cause) = violation.cause.clone() {
+                                let midi = read_header(payload)
+                                    .map_or(payload, |(start, _)| &payload[start..]);
+                                set_error_detail(format_args!(
+                                    "slave session: payload rejected: {}",
+                                    cause.with_context(midi)
+                                ));
+                            }
+                            VlcRtpmidiErrorCode::from(&violation.finding.kind)
+                        }
+                        Ok(_) => code,
+                    }
+                }
+                Err(error) => {
+                    log(
+                        VlcRtpmidiLogLevel::Warning,
+                        format_args!("slave session: RTP header rejected: {}", error),
+                    );
+                    VlcRtpmidiErrorCode::from(&error)
+                }
+            },
+        };
+        let dropped = match code {
+            VlcRtpmidiErrorCode::Duplicate => Some("repeated sequence number"),
+            VlcRtpmidiErrorCode::OutOfOrder => Some("older than the last packet"),
+            VlcRtpmidiErrorCode::ForeignSsrc => Some("sent by another SSRC"),
+            _ => None,
+        };
+        if let Some(reason) = dropped {
+            log(
+                VlcRtpmidiLogLevel::Debug,
+                format_args!("slave session: packet dropped: {}", reason),
+            );
+        }
+        code as c_int
+    })
+}
+
+/// Take the oldest queued event
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `handle` was returned by `vlc_rtpmidi_slave_session_new()` and not yet freed
+/// - `event` points to a writable `VlcRtpmidiEvent` structure
+///
+/// # Returns
+/// * `0` (Success) - An event was written to `event`
+/// * `6` (NoEvent) - The queue is empty; `event` is untouched
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_slave_session_poll_event(
+    handle: *mut VlcRtpmidiSlaveSession,
+    event: *mut VlcRtpmidiEvent,
+) -> c_int {
+    with_error_detail(|| {
+        if handle.is_null() || event.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        match (*handle).events.events.pop_front() {
+            Some(next) => {
+                *event = next;
+                VlcRtpmidiErrorCode::Success as c_int
+            }
+            None => VlcRtpmidiErrorCode::NoEvent as c_int,
+        }
+    })
+}
+
+/// Read the session counters
+///
+/// Same as `vlc_rtpmidi_master_session_metrics()`, for a slave session.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `handle` was returned by `vlc_rtpmidi_slave_session_new()` and not yet freed
+/// - `out` points to a writable `VlcRtpmidiNetsyncMetrics` structure
+///
+/// # Returns
+/// * `0` (Success) - Counters written to `out`
+/// * Non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_slave_session_metrics(
+    handle: *const VlcRtpmidiSlaveSession,
+    out: *mut VlcRtpmidiNetsyncMetrics,
+) -> c_int {
+    with_error_detail(|| {
+        if handle.is_null() || out.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        *out = metrics_to_c((*handle).session.metrics());
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Set every session counter back to zero
+///
+/// # Safety
+/// `handle` must have been returned by `vlc_rtpmidi_slave_session_new()` and not
+/// yet freed.
+///
+/// # Returns
+/// * `0` (Success) - Counters cleared
+/// * Non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_slave_session_reset_metrics(
+    handle: *mut VlcRtpmidiSlaveSession,
+) -> c_int {
+    with_error_detail(|| {
+        if handle.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        (*handle).session.reset_metrics();
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Release a slave session, discarding any queued events
+///
+/// Passing null is allowed and does nothing, like `free()`.
+///
+/// # Safety
+/// `handle` must be null or have been returned by `vlc_rtpmidi_slave_session_new()`
+/// and not yet freed. It must not be used after this call.
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_slave_session_free(handle: *mut VlcRtpmidiSlaveSession) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
 }
 
 // ============================================================================
@@ -488,7 +4644,7 @@ pub extern "C" fn vlc_rtpmidi_get_error_message(error_code: c_int) -> *const c_c
 #[no_mangle]
 pub extern "C" fn vlc_rtpmidi_create_mtc_quarter_event(msg_type: u8, value: u8) -> VlcRtpmidiEvent {
     VlcRtpmidiEvent {
-        event_type: VlcRtpmidiEventType::MtcQuarter,
+        event_type: VlcRtpmidiEventType::MtcQuarter as u32,
         data: {
             let mut data = [0u8; 8];
             data[0] = msg_type;
@@ -499,6 +4655,59 @@ pub extern "C" fn vlc_rtpmidi_create_mtc_quarter_event(msg_type: u8, value: u8)
     }
 }
 
+/// Create the eight MTC Quarter Frame events of one cycle from a timecode
+///
+/// The events are written in transmission order, piece 0 first, with the same
+/// values as `mtc::smpte_to_quarter_frames()`. A cycle spans two frames: a master
+/// chasing a local clock sends one event per quarter frame period and creates the
+/// next cycle from the clock once the eight have gone out. Same as
+/// `vlc_rtpmidi_smpte_to_quarter_frames_ffi()`, named after the other creators.
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `time` points to a readable `VlcRtpmidiSmpteTime` structure
+/// - `out_events` points to a writable array of 8 `VlcRtpmidiEvent` structures
+///
+/// # Arguments
+/// * `time` - Timecode of the cycle, at 30fps
+/// * `out_events` - Output: the eight quarter-frame events
+///
+/// # Returns
+/// * `0` (Success) - Events written to `out_events`
+/// * `7` (InvalidTimecode) - A field of `time` is out of range; nothing is written
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+///
+/// # Example Usage (C)
+/// ```c
+/// static VlcRtpmidiEvent cycle[8];
+/// static int next_piece = 8;
+///
+/// // Called every quarter frame period (1/120 s at 30fps)
+/// void on_quarter_frame_timer(void) {
+///     if (next_piece == 8) {
+///         VlcRtpmidiSmpteTime tc = local_clock_timecode();
+///         if (vlc_rtpmidi_create_mtc_quarter_sequence(&tc, cycle) != 0) {
+///             return;
+///         }
+///         next_piece = 0;
+///     }
+///
+///     uint8_t buffer[16];
+///     size_t len;
+///     if (vlc_rtpmidi_master_netsync_flow_ffi(&cycle[next_piece++], buffer, sizeof(buffer),
+///                                             &len) == 0) {
+///         send_payload(buffer, len);
+///     }
+/// }
+/// ```
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_create_mtc_quarter_sequence(
+    time: *const VlcRtpmidiSmpteTime,
+    out_events: *mut VlcRtpmidiEvent,
+) -> c_int {
+    with_error_detail(|| vlc_rtpmidi_smpte_to_quarter_frames_ffi(time, out_events))
+}
+
 /// Create MTC Full Frame event
 ///
 /// MTC Full Frame messages provide complete absolute time position
@@ -513,9 +4722,14 @@ pub extern "C" fn vlc_rtpmidi_create_mtc_quarter_event(msg_type: u8, value: u8)
 /// # Returns
 /// Initialized `VlcRtpmidiEvent` structure for MTC Full Frame
 #[no_mangle]
-pub extern "C" fn vlc_rtpmidi_create_mtc_full_event(hour: u8, minute: u8, second: u8, frame: u8) -> VlcRtpmidiEvent {
+pub extern "C" fn vlc_rtpmidi_create_mtc_full_event(
+    hour: u8,
+    minute: u8,
+    second: u8,
+    frame: u8,
+) -> VlcRtpmidiEvent {
     VlcRtpmidiEvent {
-        event_type: VlcRtpmidiEventType::MtcFull,
+        event_type: VlcRtpmidiEventType::MtcFull as u32,
         data: {
             let mut data = [0u8; 8];
             data[0] = hour;
@@ -537,7 +4751,7 @@ pub extern "C" fn vlc_rtpmidi_create_mtc_full_event(hour: u8, minute: u8, second
 #[no_mangle]
 pub extern "C" fn vlc_rtpmidi_create_mmc_stop_event() -> VlcRtpmidiEvent {
     VlcRtpmidiEvent {
-        event_type: VlcRtpmidiEventType::MmcStop,
+        event_type: VlcRtpmidiEventType::MmcStop as u32,
         data: [0u8; 8],
         data_len: 0,
     }
@@ -552,7 +4766,7 @@ pub extern "C" fn vlc_rtpmidi_create_mmc_stop_event() -> VlcRtpmidiEvent {
 #[no_mangle]
 pub extern "C" fn vlc_rtpmidi_create_mmc_play_event() -> VlcRtpmidiEvent {
     VlcRtpmidiEvent {
-        event_type: VlcRtpmidiEventType::MmcPlay,
+        event_type: VlcRtpmidiEventType::MmcPlay as u32,
         data: [0u8; 8],
         data_len: 0,
     }
@@ -579,7 +4793,7 @@ pub extern "C" fn vlc_rtpmidi_create_mmc_locate_event(
     frame: u8,
 ) -> VlcRtpmidiEvent {
     VlcRtpmidiEvent {
-        event_type: VlcRtpmidiEventType::MmcLocate,
+        event_type: VlcRtpmidiEventType::MmcLocate as u32,
         data: {
             let mut data = [0u8; 8];
             data[0] = hour;
@@ -591,3 +4805,477 @@ pub extern "C" fn vlc_rtpmidi_create_mmc_locate_event(
         data_len: 4,
     }
 }
+
+/// Create MMC Locate event with a subframe
+///
+/// Same as `vlc_rtpmidi_create_mmc_locate_event()`, with the subframe appended as
+/// a fifth data byte. Subframes over 99 are clamped to 99, the largest value MMC
+/// allows, so the returned event is always valid. The subframe is passed on to
+/// `MmcCommand::Locate` unchanged; see its documentation for how it is sent.
+///
+/// # Arguments
+/// * `hour` - Target hours (0-23)
+/// * `minute` - Target minutes (0-59)
+/// * `second` - Target seconds (0-59)
+/// * `frame` - Target frame (0-29, depending on frame rate)
+/// * `subframe` - Target subframe in hundredths of a frame (0-99, clamped)
+///
+/// # Returns
+/// Initialized `VlcRtpmidiEvent` structure for MMC Locate, with a data length of 5
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_create_mmc_locate_event_sf(
+    hour: u8,
+    minute: u8,
+    second: u8,
+    frame: u8,
+    subframe: u8,
+) -> VlcRtpmidiEvent {
+    let mut event = vlc_rtpmidi_create_mmc_locate_event(hour, minute, second, frame);
+    event.data[4] = subframe.min(MAX_SUBFRAME);
+    event.data_len = 5;
+    event
+}
+
+/// Builds an event without data, such as an MMC command or a beat clock message.
+fn event_without_data(event_type: VlcRtpmidiEventType) -> VlcRtpmidiEvent {
+    VlcRtpmidiEvent {
+        event_type: event_type as u32,
+        data: [0u8; 8],
+        data_len: 0,
+    }
+}
+
+/// Create MMC Deferred Play event
+///
+/// Deferred Play starts playback once a pending Locate has completed, so a
+/// Locate followed by Deferred Play starts exactly at the located position.
+///
+/// # Returns
+/// Initialized `VlcRtpmidiEvent` structure for MMC Deferred Play
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_create_mmc_deferred_play_event() -> VlcRtpmidiEvent {
+    event_without_data(VlcRtpmidiEventType::MmcDeferredPlay)
+}
+
+/// Create MMC Fast Forward event
+///
+/// Fast Forward winds synchronized devices forward at high speed.
+///
+/// # Returns
+/// Initialized `VlcRtpmidiEvent` structure for MMC Fast Forward
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_create_mmc_fast_forward_event() -> VlcRtpmidiEvent {
+    event_without_data(VlcRtpmidiEventType::MmcFastForward)
+}
+
+/// Create MMC Rewind event
+///
+/// Rewind winds synchronized devices backward at high speed.
+///
+/// # Returns
+/// Initialized `VlcRtpmidiEvent` structure for MMC Rewind
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_create_mmc_rewind_event() -> VlcRtpmidiEvent {
+    event_without_data(VlcRtpmidiEventType::MmcRewind)
+}
+
+/// Create MMC Record Strobe event
+///
+/// Record Strobe enters record on the armed tracks.
+///
+/// # Returns
+/// Initialized `VlcRtpmidiEvent` structure for MMC Record Strobe
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_create_mmc_record_strobe_event() -> VlcRtpmidiEvent {
+    event_without_data(VlcRtpmidiEventType::MmcRecordStrobe)
+}
+
+/// Create MMC Record Exit event
+///
+/// Record Exit leaves record, leaving the transport running.
+///
+/// # Returns
+/// Initialized `VlcRtpmidiEvent` structure for MMC Record Exit
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_create_mmc_record_exit_event() -> VlcRtpmidiEvent {
+    event_without_data(VlcRtpmidiEventType::MmcRecordExit)
+}
+
+/// Create MMC Pause event
+///
+/// Pause halts playback while keeping the position.
+///
+/// # Returns
+/// Initialized `VlcRtpmidiEvent` structure for MMC Pause
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_create_mmc_pause_event() -> VlcRtpmidiEvent {
+    event_without_data(VlcRtpmidiEventType::MmcPause)
+}
+
+/// Create MMC Eject event
+///
+/// Eject unloads the medium of synchronized devices.
+///
+/// # Returns
+/// Initialized `VlcRtpmidiEvent` structure for MMC Eject
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_create_mmc_eject_event() -> VlcRtpmidiEvent {
+    event_without_data(VlcRtpmidiEventType::MmcEject)
+}
+
+/// Create MMC Reset event
+///
+/// MMC Reset returns synchronized devices to their default MMC state.
+///
+/// # Returns
+/// Initialized `VlcRtpmidiEvent` structure for MMC Reset
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_create_mmc_reset_event() -> VlcRtpmidiEvent {
+    event_without_data(VlcRtpmidiEventType::MmcReset)
+}
+
+/// Builds an MMC Variable Play event.
+fn variable_play_event(speed: MmcSpeed) -> VlcRtpmidiEvent {
+    let mut data = [0u8; 8];
+    data[..3].copy_from_slice(&speed.to_bytes());
+    VlcRtpmidiEvent {
+        event_type: VlcRtpmidiEventType::MmcVariablePlay as u32,
+        data,
+        data_len: 3,
+    }
+}
+
+/// Create MMC Variable Play event
+///
+/// Variable Play makes synchronized devices play at a given speed, for
+/// varispeed playback. The speed is stored as the three `sh sm sl` bytes of the
+/// MMC standard speed format.
+///
+/// # Arguments
+/// * `speed` - Speed in multiples of play speed, negative in reverse. Rounded to
+///   the nearest speed the format holds, saturating just under 1024; NaN is 0
+///
+/// # Returns
+/// Initialized `VlcRtpmidiEvent` structure for MMC Variable Play
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_create_mmc_variable_play_event(speed: f32) -> VlcRtpmidiEvent {
+    variable_play_event(MmcSpeed::from_f32(speed))
+}
+
+/// Builds a Song Position Pointer event, without range check.
+fn song_position_event(beats: u16) -> VlcRtpmidiEvent {
+    let mut data = [0u8; 8];
+    data[..2].copy_from_slice(&beats.to_le_bytes());
+    VlcRtpmidiEvent {
+        event_type: VlcRtpmidiEventType::SongPosition as u32,
+        data,
+        data_len: 2,
+    }
+}
+
+/// Create Song Position Pointer event
+///
+/// Song Position Pointer tells beat-clock slaves where to resume on the next
+/// Continue. The position is stored as a little-endian 16-bit value in the first
+/// two data bytes.
+///
+/// # Arguments
+/// * `beats` - MIDI beats (sixteenth notes) since the song start (0-0x3FFF)
+///
+/// # Returns
+/// Initialized `VlcRtpmidiEvent` structure for Song Position Pointer. Positions
+/// above 0x3FFF are kept as given and rejected by the master flow with
+/// `InvalidEventType`.
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_create_song_position_event(beats: u16) -> VlcRtpmidiEvent {
+    song_position_event(beats)
+}
+
+/// Create Timing Clock event
+///
+/// Sent 24 times per quarter note while the transport runs.
+///
+/// # Returns
+/// Initialized `VlcRtpmidiEvent` structure for Timing Clock
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_create_clock_event() -> VlcRtpmidiEvent {
+    event_without_data(VlcRtpmidiEventType::Clock)
+}
+
+/// Create Start event
+///
+/// Start makes beat-clock slaves play from the beginning of the song.
+///
+/// # Returns
+/// Initialized `VlcRtpmidiEvent` structure for Start
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_create_start_event() -> VlcRtpmidiEvent {
+    event_without_data(VlcRtpmidiEventType::Start)
+}
+
+/// Create Continue event
+///
+/// Continue makes beat-clock slaves play from the current song position.
+///
+/// # Returns
+/// Initialized `VlcRtpmidiEvent` structure for Continue
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_create_continue_event() -> VlcRtpmidiEvent {
+    event_without_data(VlcRtpmidiEventType::Continue)
+}
+
+/// Create real-time Stop event
+///
+/// Stop halts beat-clock slaves, keeping the song position. Unlike
+/// `vlc_rtpmidi_create_mmc_stop_event()`, this is the single-byte `FC` message.
+///
+/// # Returns
+/// Initialized `VlcRtpmidiEvent` structure for the real-time Stop
+#[no_mangle]
+pub extern "C" fn vlc_rtpmidi_create_stop_rt_event() -> VlcRtpmidiEvent {
+    event_without_data(VlcRtpmidiEventType::RealtimeStop)
+}
+
+// ============================================================================
+// EVENT ACCESSOR FUNCTIONS
+// ============================================================================
+//
+// For bindings that cannot handle by-value structures or fixed arrays, such as
+// C# or Python through dlopen: every field of `VlcRtpmidiEvent` can be read and
+// written through these functions, which keep `data_len` consistent with the
+// event type.
+
+/// Get the type of an event
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `event` points to a readable `VlcRtpmidiEvent`
+/// - `type_out` points to a writable `VlcRtpmidiEventType`
+///
+/// # Arguments
+/// * `event` - Pointer to the event
+/// * `type_out` - Output: the event type
+///
+/// # Returns
+/// * `0` (Success) - Type written to `type_out`
+/// * `5` (InvalidEventType) - The event holds no valid type; `type_out` is not written
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_event_get_type(
+    event: *const VlcRtpmidiEvent,
+    type_out: *mut VlcRtpmidiEventType,
+) -> c_int {
+    with_error_detail(|| {
+        if event.is_null() || type_out.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        *type_out = match VlcRtpmidiEventType::try_from((*event).event_type) {
+            Ok(event_type) => event_type,
+            Err(error_code) => return error_code as c_int,
+        };
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Copy the valid data bytes of an event
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `event` points to a readable `VlcRtpmidiEvent`
+/// - `out` points to a writable buffer of at least `cap` bytes, or is null when
+///   `cap` is 0
+/// - `len` points to a writable `size_t`
+///
+/// # Arguments
+/// * `event` - Pointer to the event
+/// * `out` - Destination buffer for the data bytes
+/// * `cap` - Size of the destination buffer in bytes
+/// * `len` - Output: number of data bytes, also set when the buffer is too small
+///
+/// # Returns
+/// * `0` (Success) - `len` bytes copied to `out`
+/// * `3` (BufferTooSmall) - `len` holds the required size; nothing is copied
+/// * `5` (InvalidEventType) - `data_len` exceeds the data array; `len` is set to 0
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_event_get_data(
+    event: *const VlcRtpmidiEvent,
+    out: *mut u8,
+    cap: usize,
+    len: *mut usize,
+) -> c_int {
+    with_error_detail(|| {
+        if event.is_null() || len.is_null() {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+        if out.is_null() && cap > 0 {
+            return VlcRtpmidiErrorCode::NullPointer as c_int;
+        }
+
+        *len = 0;
+
+        let event = &*event;
+        let Some(data) = event.data.get(..event.data_len as usize) else {
+            return VlcRtpmidiErrorCode::InvalidEventType as c_int;
+        };
+        *len = data.len();
+        if data.len() > cap {
+            return VlcRtpmidiErrorCode::BufferTooSmall as c_int;
+        }
+
+        slice::from_raw_parts_mut(out, cap)[..data.len()].copy_from_slice(data);
+
+        VlcRtpmidiErrorCode::Success as c_int
+    })
+}
+
+/// Overwrite an event with an MTC Quarter Frame
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `event` points to a writable `VlcRtpmidiEvent`
+///
+/// # Arguments
+/// * `event` - Pointer to the event to overwrite
+/// * `msg_type` - Message type/piece number (0-7)
+/// * `value` - Data nibble value (0-15)
+///
+/// # Returns
+/// * `0` (Success) - Event overwritten
+/// * `7` (InvalidTimecode) - `msg_type` or `value` out of range; `event` is not written
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_event_set_mtc_quarter(
+    event: *mut VlcRtpmidiEvent,
+    msg_type: u8,
+    value: u8,
+) -> c_int {
+    with_error_detail(|| set_event(event, MidiEvent::MtcQuarter { msg_type, value }))
+}
+
+/// Overwrite an event with an MTC Full Frame
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `event` points to a writable `VlcRtpmidiEvent`
+///
+/// # Arguments
+/// * `event` - Pointer to the event to overwrite
+/// * `hour` - Hours (0-23)
+/// * `minute` - Minutes (0-59)
+/// * `second` - Seconds (0-59)
+/// * `frame` - Frame number (0-29)
+///
+/// # Returns
+/// * `0` (Success) - Event overwritten
+/// * `7` (InvalidTimecode) - A field is out of range; `event` is not written
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_event_set_mtc_full(
+    event: *mut VlcRtpmidiEvent,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    frame: u8,
+) -> c_int {
+    with_error_detail(|| {
+        set_event(
+            event,
+            MidiEvent::MtcFull {
+                hour,
+                minute,
+                second,
+                frame,
+            },
+        )
+    })
+}
+
+/// Overwrite an event with an MMC Locate
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `event` points to a writable `VlcRtpmidiEvent`
+///
+/// # Arguments
+/// * `event` - Pointer to the event to overwrite
+/// * `hour` - Hours (0-23)
+/// * `minute` - Minutes (0-59)
+/// * `second` - Seconds (0-59)
+/// * `frame` - Frame number (0-29)
+/// * `subframe` - Subframe in hundredths of a frame (0-99)
+///
+/// # Returns
+/// * `0` (Success) - Event overwritten, with `data_len` 4 when `subframe` is 0
+/// * `7` (InvalidTimecode) - A field is out of range; `event` is not written
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_event_set_mmc_locate(
+    event: *mut VlcRtpmidiEvent,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    frame: u8,
+    subframe: u8,
+) -> c_int {
+    with_error_detail(|| {
+        set_event(
+            event,
+            MidiEvent::Mmc(MmcCommand::Locate {
+                hour,
+                minute,
+                second,
+                frame,
+                subframe,
+            }),
+        )
+    })
+}
+
+/// Overwrite an event with an MMC command without data
+///
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers. Callers must ensure:
+/// - `event` points to a writable `VlcRtpmidiEvent`
+///
+/// # Arguments
+/// * `event` - Pointer to the event to overwrite
+/// * `event_type` - A `VlcRtpmidiEventType` value of an MMC command without data,
+///   such as `MmcPlay` or `MmcPause`
+///
+/// # Returns
+/// * `0` (Success) - Event overwritten, with `data_len` 0
+/// * `5` (InvalidEventType) - Not an MMC command without data; `event` is not written
+/// * Other non-zero - Error code (see `VlcRtpmidiErrorCode` enum)
+#[no_mangle]
+pub unsafe extern "C" fn vlc_rtpmidi_event_set_mmc_command(
+    event: *mut VlcRtpmidiEvent,
+    event_type: u32,
+) -> c_int {
+    with_error_detail(|| {
+        match MMC_EVENT_COMMANDS
+            .iter()
+            .find(|(known, _)| *known as u32 == event_type)
+        {
+            Some((_, command)) => set_event(event, MidiEvent::Mmc(command.clone())),
+            None => VlcRtpmidiErrorCode::InvalidEventType as c_int,
+        }
+    })
+}
+
+/// Writes an event through a C pointer once its fields are checked, leaving it
+/// untouched on error.
+unsafe fn set_event(event: *mut VlcRtpmidiEvent, midi_event: MidiEvent) -> c_int {
+    if event.is_null() {
+        return VlcRtpmidiErrorCode::NullPointer as c_int;
+    }
+    match check_event_fields(&midi_event).and_then(|()| midi_event_to_c(&midi_event)) {
+        Ok(c_event) => {
+            *event = c_event;
+            VlcRtpmidiErrorCode::Success as c_int
+        }
+        Err(error_code) => error_code as c_int,
+    }
+}
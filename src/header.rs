@@ -34,6 +34,7 @@
 /// - The lower 4 bits contain the length field
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PayloadHeader {
     /// The 4-bit flags field containing B, J, Z, P flags as defined in RFC 6295.
     /// Currently, all flags are expected to be set to 0.
@@ -63,7 +64,7 @@ impl PayloadHeader {
     /// Returns `ParseError::InsufficientHeaderData` if the buffer contains
     /// fewer than 1 byte.
     pub fn parse(buf: &[u8]) -> Result<Self, super::error::ParseError> {
-        if buf.len() < 1 {
+        if buf.is_empty() {
             return Err(super::error::ParseError::InsufficientHeaderData);
         }
         let byte = buf[0];
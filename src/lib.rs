@@ -1,10 +1,27 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+pub mod applemidi;
+#[cfg(any(feature = "midir", all(feature = "alsa", target_os = "linux")))]
+pub mod bridge;
 pub mod error;
+// The browser has no use for the C API, and `wasm` builds avoid its `std::ffi` types
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
 pub mod ffi;
 pub mod header;
 pub mod midi;
 pub mod mtc;
 pub mod netsync;
+pub mod rtp;
+#[cfg(feature = "test-utils")]
+pub mod testing;
 pub mod util;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
+pub use core::result::Result;
+#[cfg(feature = "alloc")]
 pub use error::Error;
-pub use std::result::Result;
@@ -4,8 +4,19 @@
 //!
 //! - **Quarter-Frame MTC (MIDI Time Code)**: `0xF1 nn` where `nn` encodes message type and value
 //! - **Full-Frame MTC (SysEx)**: `F0 7F devID 01 01 hr mn sc fr F7` for complete timecode
-//! - **MMC (MIDI Machine Control)**: `F0 7F devID 06 cmd(01/02) F7` for transport control (Stop/Play/Locate)
+//! - **MMC (MIDI Machine Control)**: `F0 7F devID 06 cmd F7` for transport control (Stop/Play, Deferred
+//!   Play, Fast Forward, Rewind, Record Strobe/Exit, Pause, Eject and Reset)
 //! - **MMC (MIDI Machine Control)**: `F0 7F devID 06 cmd(44) bytes(06) 01 hr mn sc fr sf F7` for transport control (Locate)
+//! - **MMC (MIDI Machine Control)**: `F0 7F devID 06 cmd(45|46|47) bytes(03) sh sm sl F7` for varispeed,
+//!   search and jog/shuttle control (Variable Play, Search, Shuttle)
+//! - **MMC (MIDI Machine Control)**: `F0 7F devID 06 cmd(41) bytes(04) name byte# mask data F7` for
+//!   changing some bits of a field, such as arming single tracks (Masked Write)
+//! - **MIDI Beat Clock**: `F8` (Clock), `FA` (Start), `FB` (Continue), `FC` (Stop) real-time messages
+//! - **Song Position Pointer**: `F2 lsb msb` giving the position in sixteenth notes
+//! - **Identity Request/Reply**: `F0 7E devID 06 01 F7` and `F0 7E devID 06 02 id ff ff mm mm vv vv vv vv F7`
+//!   for device discovery (Universal Non-Real-Time General Information)
+//! - **Manufacturer SysEx**: `F0 id data F7` with a one-byte ID or a three-byte `00 xx yy` ID,
+//!   passed through with the ID told apart
 //! - **Other MIDI messages**: Treated as raw data for pass-through
 //!
 //! # Message Format Assumptions
@@ -13,8 +24,14 @@
 //! - Messages are sent without delta-time (FLAGS=0 in RTP-MIDI)
 //! - One message per packet (no multiple message handling required)
 //! - Device ID is typically broadcast (0x7F) for universal real-time messages
+//! - MMC Stop, Play and Locate missing only their `F7` terminator are accepted, except
+//!   by the strict parsers
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 use crate::error::ParseError;
+use crate::util::midi_len::expected_len;
 
 /// Local result type alias for cleaner function signatures.
 type Result<T> = core::result::Result<T, ParseError>;
@@ -32,12 +49,46 @@ const SYSEX_END: u8 = 0xF7;
 /// System Common message start byte for Quarter-Frame MTC.
 const SYSCOMMON_START: u8 = 0xF1;
 
+/// System Common status byte for Song Position Pointer.
+const SONG_POSITION_POINTER: u8 = 0xF2;
+
+/// System Real-Time status byte for Timing Clock.
+const REALTIME_CLOCK: u8 = 0xF8;
+
+/// System Real-Time status byte for Start.
+const REALTIME_START: u8 = 0xFA;
+
+/// System Real-Time status byte for Continue.
+const REALTIME_CONTINUE: u8 = 0xFB;
+
+/// System Real-Time status byte for Stop.
+const REALTIME_STOP: u8 = 0xFC;
+
 /// Broadcast device ID used in Universal Real-Time SysEx messages.
-const SYSEX_DEVICE_ID_BROADCAST: u8 = 0x7F;
+pub const SYSEX_DEVICE_ID_BROADCAST: u8 = 0x7F;
 
 /// Universal Real-Time SysEx ID (manufacturer ID for real-time messages).
 const UNIVERSAL_REALTIME_ID: u8 = 0x7F;
 
+/// Universal Non-Real-Time SysEx ID.
+const UNIVERSAL_NON_REALTIME_ID: u8 = 0x7E;
+
+/// Sub-ID for General Information messages.
+const GENERAL_INFO_SUB_ID1: u8 = 0x06;
+
+/// Second sub-ID for Identity Request messages.
+const IDENTITY_REQUEST_SUB_ID2: u8 = 0x01;
+
+/// Second sub-ID for Identity Reply messages.
+const IDENTITY_REPLY_SUB_ID2: u8 = 0x02;
+
+/// First byte of a three-byte manufacturer ID.
+const MANUFACTURER_ID_EXTENDED: u8 = 0x00;
+
+/// Highest one-byte manufacturer ID; `7D` is reserved for non-commercial use and
+/// `7E`/`7F` for universal messages.
+const MANUFACTURER_ID_SHORT_MAX: u8 = 0x7C;
+
 /// Sub-ID for MIDI Machine Control (MMC) messages.
 const MMC_SUB_ID1: u8 = 0x06;
 
@@ -53,12 +104,54 @@ const MMC_STOP_CMD_BYTE: u8 = 0x01;
 /// MMC command byte for Play transport control.
 const MMC_PLAY_CMD_BYTE: u8 = 0x02;
 
+/// MMC command byte for Deferred Play transport control.
+const MMC_DEFERRED_PLAY_CMD_BYTE: u8 = 0x03;
+
+/// MMC command byte for Fast Forward transport control.
+const MMC_FAST_FORWARD_CMD_BYTE: u8 = 0x04;
+
+/// MMC command byte for Rewind transport control.
+const MMC_REWIND_CMD_BYTE: u8 = 0x05;
+
+/// MMC command byte for Record Strobe transport control.
+const MMC_RECORD_STROBE_CMD_BYTE: u8 = 0x06;
+
+/// MMC command byte for Record Exit transport control.
+const MMC_RECORD_EXIT_CMD_BYTE: u8 = 0x07;
+
+/// MMC command byte for Pause transport control.
+const MMC_PAUSE_CMD_BYTE: u8 = 0x09;
+
+/// MMC command byte for Eject transport control.
+const MMC_EJECT_CMD_BYTE: u8 = 0x0A;
+
+/// MMC command byte for MMC Reset.
+const MMC_RESET_CMD_BYTE: u8 = 0x0D;
+
 /// MMC Command byte for Locate transport control.
 const MMC_LOCATE_CMD_BYTE: u8 = 0x44;
 
 /// MMC Expected length of a complete Locate MTC SysEx message.
 const MMC_LOCATE_SIZE_BYTE: u8 = 0x06;
 
+/// MMC Command byte for Masked Write.
+const MMC_MASKED_WRITE_CMD_BYTE: u8 = 0x41;
+
+/// MMC Count of the data bytes of a Masked Write command, always 4.
+const MMC_MASKED_WRITE_SIZE_BYTE: u8 = 0x04;
+
+/// MMC Command byte for Variable Play.
+const MMC_VARIABLE_PLAY_CMD_BYTE: u8 = 0x45;
+
+/// MMC Command byte for Search.
+const MMC_SEARCH_CMD_BYTE: u8 = 0x46;
+
+/// MMC Command byte for Shuttle.
+const MMC_SHUTTLE_CMD_BYTE: u8 = 0x47;
+
+/// MMC Count of the data bytes of a Variable Play, Search or Shuttle command, always 3.
+const MMC_SPEED_SIZE_BYTE: u8 = 0x03;
+
 /// Expected length of a complete Full-Frame MTC SysEx message.
 pub const MTC_FULL_FRAME_LENGTH: usize = 10;
 
@@ -68,8 +161,45 @@ pub const MMC_START_STOP_LENGTH: usize = 6;
 /// Expected length of a complete Locate MTC SysEx message.
 pub const MMC_LOCATE_LENGTH: usize = 13;
 
+/// Expected length of a complete MMC Variable Play, Search or Shuttle SysEx message.
+pub const MMC_SPEED_LENGTH: usize = 10;
+
+/// Expected length of a complete MMC Masked Write SysEx message.
+pub const MMC_MASKED_WRITE_LENGTH: usize = 11;
+
+/// MMC Track Record Ready field, the bitmap of the tracks armed for recording.
+pub const MMC_TRACK_RECORD_READY: u8 = 0x4F;
+
 /// Expected length of a Quarter-Frame MTC message.
-pub const MTC_QUARTER_FRAME_LENGTH: usize = 2;
+pub const MTC_QUARTER_FRAME_LENGTH: usize = message_len(SYSCOMMON_START);
+
+/// Expected length of a Song Position Pointer message.
+pub const SONG_POSITION_LENGTH: usize = message_len(SONG_POSITION_POINTER);
+
+/// Length of a System Real-Time message.
+pub const REALTIME_LENGTH: usize = message_len(REALTIME_CLOCK);
+
+/// Expected length of a complete Identity Request SysEx message.
+pub const IDENTITY_REQUEST_LENGTH: usize = 6;
+
+/// Length of the longest Identity Reply SysEx message, with a three-byte
+/// manufacturer ID.
+pub const IDENTITY_REPLY_MAX_LENGTH: usize = 17;
+
+/// Length of a complete message with a fixed-length status byte, for the constants
+/// above.
+const fn message_len(status: u8) -> usize {
+    match expected_len(status) {
+        Some(data_len) => 1 + data_len,
+        None => panic!("status byte without a fixed length"),
+    }
+}
+
+/// MIDI Beat Clock resolution: timing clocks per quarter note.
+pub const CLOCKS_PER_QUARTER_NOTE: u32 = 24;
+
+/// Timing clocks per Song Position Pointer unit (a sixteenth note).
+pub const CLOCKS_PER_SONG_POSITION: u32 = 6;
 
 // ============================================================================
 // MIDI Event Types
@@ -80,6 +210,7 @@ pub const MTC_QUARTER_FRAME_LENGTH: usize = 2;
 /// This enum represents the subset of MIDI messages that are commonly used
 /// for synchronization between networked MIDI devices. Other MIDI messages
 /// are preserved as raw byte data.
+#[cfg(feature = "alloc")]
 #[derive(Debug, Clone, PartialEq)]
 pub enum MidiEvent {
     /// # Quarter-Frame MIDI Time Code message.
@@ -99,6 +230,10 @@ pub enum MidiEvent {
     ///
     /// Provides complete timecode information in a single message, typically
     /// sent when synchronization starts or when a jump in time occurs.
+    ///
+    /// Bytes some devices add between the frame byte and `F7` are ignored, so a
+    /// padded frame is rebuilt without them. [`parse_midi_message`] counts them as
+    /// consumed, for callers forwarding the original bytes instead.
     MtcFull {
         hour: u8,
         minute: u8,
@@ -111,6 +246,36 @@ pub enum MidiEvent {
     /// Represents MMC commands that affect transport state.
     Mmc(MmcCommand),
 
+    /// # MIDI Beat Clock real-time message.
+    ///
+    /// Format: a single status byte (`F8`, `FA`, `FB` or `FC`).
+    Realtime(RealtimeMessage),
+
+    /// # Song Position Pointer.
+    ///
+    /// Format: `F2 lsb msb`, a 14-bit count of sixteenth notes (6 timing clocks each)
+    /// since the start of the song.
+    SongPosition(u16),
+
+    /// # Identity Request, asking devices to describe themselves.
+    ///
+    /// Format: `F0 7E devID 06 01 F7`, where `devID` is the device asked, `7F` for
+    /// every device on the link.
+    IdentityRequest { device_id: u8 },
+
+    /// # Identity Reply, describing the device answering an Identity Request.
+    ///
+    /// Format: `F0 7E devID 06 02 id ff ff mm mm vv vv vv vv F7`, where `devID` is
+    /// the device answering.
+    IdentityReply(DeviceIdentity),
+
+    /// # Manufacturer-specific System Exclusive message.
+    ///
+    /// Format: `F0 id data F7`, where `id` is one byte or three bytes starting with
+    /// `00`. `data` holds the bytes between the ID and the terminator. Not a sync
+    /// event, so only passed through, but with the ID told apart for routing.
+    ManufacturerSysex { id: ManufacturerId, data: Vec<u8> },
+
     /// # Any other MIDI message not specifically handled.
     ///
     /// Raw bytes are preserved to allow pass-through of other MIDI data
@@ -118,6 +283,170 @@ pub enum MidiEvent {
     Other(Vec<u8>),
 }
 
+/// # Manufacturer ID of a System Exclusive message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ManufacturerId {
+    /// # One-byte ID, from `01` to `7C`.
+    Short(u8),
+
+    /// # Three-byte ID `00 xx yy`, holding `xx yy`.
+    Extended([u8; 2]),
+}
+
+impl ManufacturerId {
+    /// Reads the ID at the start of `bytes`, the message following `F0`.
+    ///
+    /// Returns `None` for the reserved IDs (`00` on its own, `7D`-`7F`), for a
+    /// three-byte ID cut short, and for bytes with the high bit set.
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        match *bytes {
+            [MANUFACTURER_ID_EXTENDED, high, low, ..] if (high | low) & 0x80 == 0 => {
+                Some(ManufacturerId::Extended([high, low]))
+            }
+            [id, ..] if (1..=MANUFACTURER_ID_SHORT_MAX).contains(&id) => {
+                Some(ManufacturerId::Short(id))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the number of bytes of the ID in a message: 1 or 3.
+    pub const fn wire_len(self) -> usize {
+        match self {
+            ManufacturerId::Short(_) => 1,
+            ManufacturerId::Extended(_) => 3,
+        }
+    }
+
+    /// Returns the bytes of the ID as sent in a message.
+    pub fn bytes(self) -> impl Iterator<Item = u8> {
+        let bytes = match self {
+            ManufacturerId::Short(id) => [id, 0, 0],
+            ManufacturerId::Extended([high, low]) => [MANUFACTURER_ID_EXTENDED, high, low],
+        };
+        bytes.into_iter().take(self.wire_len())
+    }
+}
+
+impl core::fmt::Display for ManufacturerId {
+    /// Formats the ID as its bytes in hex, such as `41` or `00 20 33`.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match *self {
+            ManufacturerId::Short(id) => write!(f, "{:02X}", id),
+            ManufacturerId::Extended([high, low]) => write!(f, "00 {:02X} {:02X}", high, low),
+        }
+    }
+}
+
+/// # Identity of a device, as carried by an Identity Reply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DeviceIdentity {
+    /// Device ID the device answers to
+    pub device_id: u8,
+    /// Manufacturer of the device
+    pub manufacturer: ManufacturerId,
+    /// Device family code, 14-bit, sent least significant 7 bits first
+    pub family: u16,
+    /// Model number within the family, 14-bit, sent least significant 7 bits first
+    pub model: u16,
+    /// Software revision level, in a format of the manufacturer's choosing
+    pub version: [u8; 4],
+}
+
+impl DeviceIdentity {
+    /// Returns the length of the Identity Reply carrying this identity: 15 bytes,
+    /// or [`IDENTITY_REPLY_MAX_LENGTH`] with a three-byte manufacturer ID.
+    pub const fn reply_len(&self) -> usize {
+        IDENTITY_REPLY_MAX_LENGTH - 3 + self.manufacturer.wire_len()
+    }
+}
+
+/// # Signed speed of an MMC Variable Play, Search or Shuttle, in the MMC standard
+/// speed format.
+///
+/// Kept as the three data bytes `sh sm sl`, so a parsed command is sent back
+/// byte for byte:
+///
+/// ```text
+/// sh = 0 g s s s i i i    g: reverse, sss: shift, iii: integer part
+/// sm = 0 m m m m m m m
+/// sl = 0 l l l l l l l
+/// ```
+///
+/// The 17 bits `iii mmmmmmm lllllll` are a fixed-point magnitude with
+/// `14 - sss` fraction bits, `1.0` being play speed: `01 00 00` is play speed
+/// forward, `41 00 00` play speed in reverse and `00 40 00` half speed forward.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MmcSpeed([u8; 3]);
+
+impl MmcSpeed {
+    /// Zero speed, stopping the transport.
+    pub const STOP: Self = Self([0; 3]);
+
+    /// Largest 17-bit magnitude.
+    const MAX_MAGNITUDE: u32 = 0x1_FFFF;
+
+    /// Largest shift, leaving 7 fraction bits.
+    const MAX_SHIFT: u8 = 7;
+
+    /// Returns the speed sent as `sh sm sl`, masked to 7 bits each.
+    pub const fn from_bytes(bytes: [u8; 3]) -> Self {
+        Self([bytes[0] & 0x7F, bytes[1] & 0x7F, bytes[2] & 0x7F])
+    }
+
+    /// Returns the `sh sm sl` data bytes.
+    pub const fn to_bytes(self) -> [u8; 3] {
+        self.0
+    }
+
+    /// Returns the speed closest to `speed`, in multiples of play speed, negative
+    /// in reverse.
+    ///
+    /// Uses the smallest shift that holds the integer part, for the finest
+    /// fraction. Speeds beyond the largest magnitude, just under 1024, saturate;
+    /// NaN is [`STOP`](Self::STOP).
+    pub fn from_f32(speed: f32) -> Self {
+        if speed.is_nan() {
+            return Self::STOP;
+        }
+        let reverse = speed < 0.0;
+        let magnitude = if reverse { -speed } else { speed };
+
+        // The cast saturates, so out of range speeds end at the largest shift
+        let fixed = |shift: u8| (magnitude * (1u32 << (14 - shift)) as f32 + 0.5) as u32;
+        let mut shift = 0;
+        while fixed(shift) > Self::MAX_MAGNITUDE && shift < Self::MAX_SHIFT {
+            shift += 1;
+        }
+        let raw = fixed(shift).min(Self::MAX_MAGNITUDE);
+        Self([
+            u8::from(reverse) << 6 | shift << 3 | (raw >> 14) as u8,
+            (raw >> 7) as u8 & 0x7F,
+            raw as u8 & 0x7F,
+        ])
+    }
+
+    /// Returns the speed in multiples of play speed, negative in reverse.
+    ///
+    /// Exact: every speed the format holds is an `f32`.
+    pub fn to_f32(self) -> f32 {
+        let [sh, sm, sl] = self.0;
+        let shift = (sh >> 3) & 0x07;
+        let raw = u32::from(sh & 0x07) << 14 | u32::from(sm) << 7 | u32::from(sl);
+        let magnitude = raw as f32 / (1u32 << (14 - shift)) as f32;
+        if self.is_reverse() {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+
+    /// Returns `true` if the direction bit is set.
+    pub const fn is_reverse(self) -> bool {
+        self.0[0] & 0x40 != 0
+    }
+}
+
 /// # MIDI Machine Control (MMC) command types.
 ///
 /// MMC commands are used to control transport state and positioning
@@ -134,6 +463,46 @@ pub enum MmcCommand {
     /// Format: `F0 7F devID 06 02 F7`
     Play,
 
+    /// # Deferred Play transport command, starting once a pending Locate completes.
+    ///
+    /// Format: `F0 7F devID 06 03 F7`
+    DeferredPlay,
+
+    /// # Fast Forward transport command.
+    ///
+    /// Format: `F0 7F devID 06 04 F7`
+    FastForward,
+
+    /// # Rewind transport command.
+    ///
+    /// Format: `F0 7F devID 06 05 F7`
+    Rewind,
+
+    /// # Record Strobe command, entering record on the armed tracks.
+    ///
+    /// Format: `F0 7F devID 06 06 F7`
+    RecordStrobe,
+
+    /// # Record Exit command, leaving record.
+    ///
+    /// Format: `F0 7F devID 06 07 F7`
+    RecordExit,
+
+    /// # Pause transport command.
+    ///
+    /// Format: `F0 7F devID 06 09 F7`
+    Pause,
+
+    /// # Eject command.
+    ///
+    /// Format: `F0 7F devID 06 0A F7`
+    Eject,
+
+    /// # MMC Reset command, returning the receiver to its default state.
+    ///
+    /// Format: `F0 7F devID 06 0D F7`
+    Reset,
+
     /// # Locate to timecode position command.
     ///
     /// Format: `F0 7F devID 06 44 06 01 hr mn sc fr sf F7`
@@ -147,31 +516,422 @@ pub enum MmcCommand {
         frame: u8,
         subframe: u8,
     },
+
+    /// # Shuttle command, moving the transport at a signed speed.
+    ///
+    /// Format: `F0 7F devID 06 47 03 sh sm sl F7`
+    ///
+    /// Used for jog/shuttle control; [`MmcSpeed::STOP`] halts the transport.
+    Shuttle { speed: MmcSpeed },
+
+    /// # Variable Play command, playing at a signed speed.
+    ///
+    /// Format: `F0 7F devID 06 45 03 sh sm sl F7`
+    ///
+    /// Used for varispeed playback, `1.0` being play speed.
+    VariablePlay { speed: MmcSpeed },
+
+    /// # Search command, moving the transport at a signed speed without playing.
+    ///
+    /// Format: `F0 7F devID 06 46 03 sh sm sl F7`
+    ///
+    /// Sent by some decks while they pre-roll to a located position.
+    Search { speed: MmcSpeed },
+
+    /// # Masked Write command, changing some bits of one byte of a field.
+    ///
+    /// Format: `F0 7F devID 06 41 04 name byte# mask data F7`
+    ///
+    /// Sets the bits of byte `start` of the bitmap `field` that `mask` selects to
+    /// those of `data`, leaving the others, so single tracks can be armed in
+    /// [`MMC_TRACK_RECORD_READY`]. Every byte must be 7-bit clean.
+    MaskedWrite {
+        field: u8,
+        start: u8,
+        mask: u8,
+        data: u8,
+    },
+}
+
+impl MmcCommand {
+    /// Returns the command byte of a command without data, `None` for Locate and
+    /// the commands carrying a speed.
+    pub(crate) fn command_byte(&self) -> Option<u8> {
+        Some(match self {
+            MmcCommand::Stop => MMC_STOP_CMD_BYTE,
+            MmcCommand::Play => MMC_PLAY_CMD_BYTE,
+            MmcCommand::DeferredPlay => MMC_DEFERRED_PLAY_CMD_BYTE,
+            MmcCommand::FastForward => MMC_FAST_FORWARD_CMD_BYTE,
+            MmcCommand::Rewind => MMC_REWIND_CMD_BYTE,
+            MmcCommand::RecordStrobe => MMC_RECORD_STROBE_CMD_BYTE,
+            MmcCommand::RecordExit => MMC_RECORD_EXIT_CMD_BYTE,
+            MmcCommand::Pause => MMC_PAUSE_CMD_BYTE,
+            MmcCommand::Eject => MMC_EJECT_CMD_BYTE,
+            MmcCommand::Reset => MMC_RESET_CMD_BYTE,
+            MmcCommand::Locate { .. }
+            | MmcCommand::Shuttle { .. }
+            | MmcCommand::VariablePlay { .. }
+            | MmcCommand::Search { .. }
+            | MmcCommand::MaskedWrite { .. } => return None,
+        })
+    }
+
+    /// Returns the `(start, mask, data)` of a Masked Write arming or disarming
+    /// `track` in a track bitmap.
+    ///
+    /// Tracks are numbered from 1 and follow the Video, reserved, Time Code, Aux A
+    /// and Aux B bits of the bitmap: track 1 is bit 5 of byte 0, track 3 bit 0 of
+    /// byte 1. Returns `None` for track 0 and past the last track of a bitmap, 891.
+    pub const fn arm_track_bits(track: u16, armed: bool) -> Option<(u8, u8, u8)> {
+        const FIRST_TRACK_BIT: u16 = 5;
+        const LAST_TRACK: u16 = 0x7F * 7 + 6 - FIRST_TRACK_BIT + 1;
+
+        if track == 0 || track > LAST_TRACK {
+            return None;
+        }
+        let bit = track - 1 + FIRST_TRACK_BIT;
+        let mask = 1 << (bit % 7);
+        Some(((bit / 7) as u8, mask, if armed { mask } else { 0 }))
+    }
+
+    /// Returns the Masked Write of [`MMC_TRACK_RECORD_READY`] arming or disarming
+    /// `track`, numbered as by [`arm_track_bits`](Self::arm_track_bits).
+    pub fn arm_track(track: u16, armed: bool) -> Option<Self> {
+        let (start, mask, data) = Self::arm_track_bits(track, armed)?;
+        Some(MmcCommand::MaskedWrite {
+            field: MMC_TRACK_RECORD_READY,
+            start,
+            mask,
+            data,
+        })
+    }
+
+    /// Returns the command byte and speed of a Variable Play, Search or Shuttle.
+    pub(crate) fn speed_command(&self) -> Option<(u8, MmcSpeed)> {
+        match *self {
+            MmcCommand::VariablePlay { speed } => Some((MMC_VARIABLE_PLAY_CMD_BYTE, speed)),
+            MmcCommand::Search { speed } => Some((MMC_SEARCH_CMD_BYTE, speed)),
+            MmcCommand::Shuttle { speed } => Some((MMC_SHUTTLE_CMD_BYTE, speed)),
+            _ => None,
+        }
+    }
+
+    /// Returns the Variable Play, Search or Shuttle sent with `byte` and `speed`.
+    pub(crate) fn from_speed_command(byte: u8, speed: MmcSpeed) -> Option<Self> {
+        match byte {
+            MMC_VARIABLE_PLAY_CMD_BYTE => Some(MmcCommand::VariablePlay { speed }),
+            MMC_SEARCH_CMD_BYTE => Some(MmcCommand::Search { speed }),
+            MMC_SHUTTLE_CMD_BYTE => Some(MmcCommand::Shuttle { speed }),
+            _ => None,
+        }
+    }
+
+    /// Returns the command without data sent with `byte`, if it is one this crate knows.
+    pub(crate) fn from_command_byte(byte: u8) -> Option<Self> {
+        Some(match byte {
+            MMC_STOP_CMD_BYTE => MmcCommand::Stop,
+            MMC_PLAY_CMD_BYTE => MmcCommand::Play,
+            MMC_DEFERRED_PLAY_CMD_BYTE => MmcCommand::DeferredPlay,
+            MMC_FAST_FORWARD_CMD_BYTE => MmcCommand::FastForward,
+            MMC_REWIND_CMD_BYTE => MmcCommand::Rewind,
+            MMC_RECORD_STROBE_CMD_BYTE => MmcCommand::RecordStrobe,
+            MMC_RECORD_EXIT_CMD_BYTE => MmcCommand::RecordExit,
+            MMC_PAUSE_CMD_BYTE => MmcCommand::Pause,
+            MMC_EJECT_CMD_BYTE => MmcCommand::Eject,
+            MMC_RESET_CMD_BYTE => MmcCommand::Reset,
+            _ => return None,
+        })
+    }
+}
+
+/// # Type of a MIDI message, without its contents.
+///
+/// Each variant corresponds to one [`MidiEvent`] shape, as returned by
+/// [`MidiEvent::kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MessageKind {
+    /// Quarter-Frame MTC
+    MtcQuarter,
+    /// Full-Frame MTC
+    MtcFull,
+    /// MMC Play
+    Play,
+    /// MMC Stop
+    Stop,
+    /// MMC Locate
+    Locate,
+    /// MMC Shuttle
+    Shuttle,
+    /// MMC Variable Play
+    VariablePlay,
+    /// MMC Search
+    Search,
+    /// MMC Masked Write
+    MaskedWrite,
+    /// Any other MMC transport command (Deferred Play, Fast Forward, Rewind, Record
+    /// Strobe/Exit, Pause, Eject or Reset)
+    OtherMmc,
+    /// MIDI Beat Clock real-time message
+    Realtime,
+    /// Song Position Pointer
+    SongPosition,
+    /// Identity Request
+    IdentityRequest,
+    /// Identity Reply
+    IdentityReply,
+    /// Manufacturer-specific SysEx
+    ManufacturerSysex,
+    /// Any other message
+    Other,
+}
+
+impl MessageKind {
+    /// Returns `true` for MMC transport commands.
+    pub fn is_transport(self) -> bool {
+        matches!(
+            self,
+            Self::Play
+                | Self::Stop
+                | Self::Locate
+                | Self::Shuttle
+                | Self::VariablePlay
+                | Self::Search
+                | Self::OtherMmc
+        )
+    }
+
+    /// Returns `true` for the synchronization messages the master flow sends,
+    /// `false` for device discovery and for the messages that are only passed
+    /// through.
+    pub fn is_sync(self) -> bool {
+        !matches!(
+            self,
+            Self::IdentityRequest | Self::IdentityReply | Self::ManufacturerSysex | Self::Other
+        )
+    }
+
+    /// Returns the length of a message of this kind as the master flow sends it,
+    /// status byte included, or `None` for Identity Replies and pass-through
+    /// messages, whose length varies.
+    pub const fn wire_len(self) -> Option<usize> {
+        Some(match self {
+            Self::MtcQuarter => MTC_QUARTER_FRAME_LENGTH,
+            Self::MtcFull => MTC_FULL_FRAME_LENGTH,
+            Self::Play | Self::Stop | Self::OtherMmc => MMC_START_STOP_LENGTH,
+            Self::Locate => MMC_LOCATE_LENGTH,
+            Self::Shuttle | Self::VariablePlay | Self::Search => MMC_SPEED_LENGTH,
+            Self::MaskedWrite => MMC_MASKED_WRITE_LENGTH,
+            Self::Realtime => REALTIME_LENGTH,
+            Self::SongPosition => SONG_POSITION_LENGTH,
+            Self::IdentityRequest => IDENTITY_REQUEST_LENGTH,
+            Self::IdentityReply | Self::ManufacturerSysex | Self::Other => return None,
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl MidiEvent {
+    /// Returns the kind of this event.
+    pub fn kind(&self) -> MessageKind {
+        match self {
+            MidiEvent::MtcQuarter { .. } => MessageKind::MtcQuarter,
+            MidiEvent::MtcFull { .. } => MessageKind::MtcFull,
+            MidiEvent::Mmc(MmcCommand::Play) => MessageKind::Play,
+            MidiEvent::Mmc(MmcCommand::Stop) => MessageKind::Stop,
+            MidiEvent::Mmc(MmcCommand::Locate { .. }) => MessageKind::Locate,
+            MidiEvent::Mmc(MmcCommand::Shuttle { .. }) => MessageKind::Shuttle,
+            MidiEvent::Mmc(MmcCommand::VariablePlay { .. }) => MessageKind::VariablePlay,
+            MidiEvent::Mmc(MmcCommand::Search { .. }) => MessageKind::Search,
+            MidiEvent::Mmc(MmcCommand::MaskedWrite { .. }) => MessageKind::MaskedWrite,
+            MidiEvent::Mmc(_) => MessageKind::OtherMmc,
+            MidiEvent::Realtime(_) => MessageKind::Realtime,
+            MidiEvent::SongPosition(_) => MessageKind::SongPosition,
+            MidiEvent::IdentityRequest { .. } => MessageKind::IdentityRequest,
+            MidiEvent::IdentityReply(_) => MessageKind::IdentityReply,
+            MidiEvent::ManufacturerSysex { .. } => MessageKind::ManufacturerSysex,
+            MidiEvent::Other(_) => MessageKind::Other,
+        }
+    }
+}
+
+/// # Borrowed form of [`MidiEvent`].
+///
+/// Identical to `MidiEvent` except that pass-through messages borrow their bytes
+/// instead of owning them, so a receive loop can decode every packet without
+/// allocating.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MidiEventRef<'a> {
+    /// See [`MidiEvent::MtcQuarter`]
+    MtcQuarter { msg_type: u8, value: u8 },
+    /// See [`MidiEvent::MtcFull`]
+    MtcFull {
+        hour: u8,
+        minute: u8,
+        second: u8,
+        frame: u8,
+    },
+    /// See [`MidiEvent::Mmc`]
+    Mmc(MmcCommand),
+    /// See [`MidiEvent::Realtime`]
+    Realtime(RealtimeMessage),
+    /// See [`MidiEvent::SongPosition`]
+    SongPosition(u16),
+    /// See [`MidiEvent::IdentityRequest`]
+    IdentityRequest { device_id: u8 },
+    /// See [`MidiEvent::IdentityReply`]
+    IdentityReply(DeviceIdentity),
+    /// See [`MidiEvent::ManufacturerSysex`]
+    ManufacturerSysex { id: ManufacturerId, data: &'a [u8] },
+    /// See [`MidiEvent::Other`]
+    Other(&'a [u8]),
+}
+
+impl MidiEventRef<'_> {
+    /// Returns the kind of this event.
+    pub fn kind(&self) -> MessageKind {
+        match self {
+            MidiEventRef::MtcQuarter { .. } => MessageKind::MtcQuarter,
+            MidiEventRef::MtcFull { .. } => MessageKind::MtcFull,
+            MidiEventRef::Mmc(MmcCommand::Play) => MessageKind::Play,
+            MidiEventRef::Mmc(MmcCommand::Stop) => MessageKind::Stop,
+            MidiEventRef::Mmc(MmcCommand::Locate { .. }) => MessageKind::Locate,
+            MidiEventRef::Mmc(MmcCommand::Shuttle { .. }) => MessageKind::Shuttle,
+            MidiEventRef::Mmc(MmcCommand::VariablePlay { .. }) => MessageKind::VariablePlay,
+            MidiEventRef::Mmc(MmcCommand::Search { .. }) => MessageKind::Search,
+            MidiEventRef::Mmc(MmcCommand::MaskedWrite { .. }) => MessageKind::MaskedWrite,
+            MidiEventRef::Mmc(_) => MessageKind::OtherMmc,
+            MidiEventRef::Realtime(_) => MessageKind::Realtime,
+            MidiEventRef::SongPosition(_) => MessageKind::SongPosition,
+            MidiEventRef::IdentityRequest { .. } => MessageKind::IdentityRequest,
+            MidiEventRef::IdentityReply(_) => MessageKind::IdentityReply,
+            MidiEventRef::ManufacturerSysex { .. } => MessageKind::ManufacturerSysex,
+            MidiEventRef::Other(_) => MessageKind::Other,
+        }
+    }
+
+    /// Converts into an owned [`MidiEvent`], copying pass-through bytes.
+    #[cfg(feature = "alloc")]
+    pub fn to_owned_event(&self) -> MidiEvent {
+        match self {
+            MidiEventRef::MtcQuarter { msg_type, value } => MidiEvent::MtcQuarter {
+                msg_type: *msg_type,
+                value: *value,
+            },
+            MidiEventRef::MtcFull {
+                hour,
+                minute,
+                second,
+                frame,
+            } => MidiEvent::MtcFull {
+                hour: *hour,
+                minute: *minute,
+                second: *second,
+                frame: *frame,
+            },
+            MidiEventRef::Mmc(command) => MidiEvent::Mmc(command.clone()),
+            MidiEventRef::Realtime(message) => MidiEvent::Realtime(*message),
+            MidiEventRef::SongPosition(position) => MidiEvent::SongPosition(*position),
+            MidiEventRef::IdentityRequest { device_id } => MidiEvent::IdentityRequest {
+                device_id: *device_id,
+            },
+            MidiEventRef::IdentityReply(identity) => MidiEvent::IdentityReply(*identity),
+            MidiEventRef::ManufacturerSysex { id, data } => MidiEvent::ManufacturerSysex {
+                id: *id,
+                data: data.to_vec(),
+            },
+            MidiEventRef::Other(bytes) => MidiEvent::Other(bytes.to_vec()),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<MidiEventRef<'a>> for MidiEvent {
+    fn from(event: MidiEventRef<'a>) -> Self {
+        event.to_owned_event()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl MidiEvent {
+    /// Borrows this event as a [`MidiEventRef`].
+    pub fn as_event_ref(&self) -> MidiEventRef<'_> {
+        match self {
+            MidiEvent::MtcQuarter { msg_type, value } => MidiEventRef::MtcQuarter {
+                msg_type: *msg_type,
+                value: *value,
+            },
+            MidiEvent::MtcFull {
+                hour,
+                minute,
+                second,
+                frame,
+            } => MidiEventRef::MtcFull {
+                hour: *hour,
+                minute: *minute,
+                second: *second,
+                frame: *frame,
+            },
+            MidiEvent::Mmc(command) => MidiEventRef::Mmc(command.clone()),
+            MidiEvent::Realtime(message) => MidiEventRef::Realtime(*message),
+            MidiEvent::SongPosition(position) => MidiEventRef::SongPosition(*position),
+            MidiEvent::IdentityRequest { device_id } => MidiEventRef::IdentityRequest {
+                device_id: *device_id,
+            },
+            MidiEvent::IdentityReply(identity) => MidiEventRef::IdentityReply(*identity),
+            MidiEvent::ManufacturerSysex { id, data } => {
+                MidiEventRef::ManufacturerSysex { id: *id, data }
+            }
+            MidiEvent::Other(bytes) => MidiEventRef::Other(bytes),
+        }
+    }
+}
+
+/// # MIDI Beat Clock real-time messages.
+///
+/// Beat-clock slaves advance by one twenty-fourth of a quarter note on each Clock
+/// while running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RealtimeMessage {
+    /// # Timing Clock, sent 24 times per quarter note.
+    ///
+    /// Format: `F8`
+    Clock,
+
+    /// # Start playback from the beginning of the song.
+    ///
+    /// Format: `FA`
+    Start,
+
+    /// # Resume playback from the current song position.
+    ///
+    /// Format: `FB`
+    Continue,
+
+    /// # Stop playback, keeping the song position.
+    ///
+    /// Format: `FC`
+    Stop,
 }
 
 // ============================================================================
 // Parsing Functions
 // ============================================================================
 
-/// Parse exactly one MIDI message from a buffer.
+/// Classify exactly one MIDI message in a buffer without copying it.
 ///
-/// This internal function handles the core parsing logic for different MIDI message types.
-/// It assumes no delta-time is present (FLAGS=0 in RTP-MIDI context).
-///
-/// # Arguments
-///
-/// * `buf` - Buffer containing MIDI data
-/// * `len` - Number of bytes to parse from the buffer
+/// This internal function holds the recognition rules shared by [`parse_midi`] and
+/// [`peek_midi_kind`], so both always agree on the type of a message.
 ///
 /// # Returns
 ///
-/// Returns a tuple of `MidiEvent` on success.
+/// Returns the message kind and the slice holding the message, which for SysEx
+/// messages ends at the first terminator.
 ///
 /// # Errors
 ///
 /// - `ParseError::EmptyBuffer` if the buffer is empty or length is 0
 /// - `ParseError::BufferTooSmall` if `len` exceeds the buffer size
-fn parse_midi(buf: &[u8], len: usize) -> Result<MidiEvent> {
+fn classify_midi(buf: &[u8], len: usize) -> Result<(MessageKind, &[u8])> {
     if len == 0 || buf.is_empty() {
         return Err(ParseError::EmptyBuffer);
     }
@@ -189,20 +949,32 @@ fn parse_midi(buf: &[u8], len: usize) -> Result<MidiEvent> {
     if b0 == SYSCOMMON_START {
         if len < MTC_QUARTER_FRAME_LENGTH {
             // Not enough data for complete quarter frame, treat as Other
-            return Ok(MidiEvent::Other(buf[..len].to_vec()));
+            return Ok((MessageKind::Other, &buf[..len]));
         }
-        let data_byte = buf[1];
         // In some cases, there might be more than 2 bytes (padding after the command)
-        // The data is 0nnn dddd, where nnn is the message type and dddd is the value
-        return Ok(MidiEvent::MtcQuarter {
-            msg_type: data_byte >> 4,
-            value: data_byte & 0x0F,
-        });
+        return Ok((MessageKind::MtcQuarter, buf));
+    }
+
+    // MIDI Beat Clock (System Real-Time)
+    if matches!(
+        b0,
+        REALTIME_CLOCK | REALTIME_START | REALTIME_CONTINUE | REALTIME_STOP
+    ) {
+        return Ok((MessageKind::Realtime, &buf[..REALTIME_LENGTH]));
+    }
+
+    // Song Position Pointer (System Common)
+    if b0 == SONG_POSITION_POINTER {
+        if len < SONG_POSITION_LENGTH {
+            // Not enough data for complete pointer, treat as Other
+            return Ok((MessageKind::Other, &buf[..len]));
+        }
+        return Ok((MessageKind::SongPosition, &buf[..SONG_POSITION_LENGTH]));
     }
 
     if b0 != SYSEX_START {
         // Not a SysEx or Quarter-Frame message, treat as Other
-        return Ok(MidiEvent::Other(buf[..len].to_vec()));
+        return Ok((MessageKind::Other, &buf[..len]));
     }
 
     // Find SysEx terminator or use all available bytes
@@ -213,6 +985,28 @@ fn parse_midi(buf: &[u8], len: usize) -> Result<MidiEvent> {
         None => (&buf[..len], len),
     };
 
+    // Manufacturer SysEx, complete and with 7-bit data: F0 id data F7
+    if let Some(id) = ManufacturerId::parse(&cmd_slice[1..]) {
+        let terminated =
+            cmd_slice.len() > id.wire_len() + 1 && cmd_slice[cmd_size - 1] == SYSEX_END;
+        if terminated && cmd_slice[1..cmd_size - 1].iter().all(|&b| b & 0x80 == 0) {
+            return Ok((MessageKind::ManufacturerSysex, cmd_slice));
+        }
+        return Ok((MessageKind::Other, &buf[..len]));
+    }
+
+    // Identity Request and Reply, complete and with 7-bit data
+    if let Some(kind) = identity_kind(cmd_slice) {
+        return Ok((kind, cmd_slice));
+    }
+
+    // MMC Stop, Play and Locate whose length covers all but the missing terminator
+    if sysex_end_pos.is_none() {
+        if let Some(kind) = unterminated_mmc_kind(cmd_slice) {
+            return Ok((kind, cmd_slice));
+        }
+    }
+
     // SysEx-based messages
     if !(cmd_slice.len() >= 4
         && cmd_slice[1] == UNIVERSAL_REALTIME_ID
@@ -220,7 +1014,7 @@ fn parse_midi(buf: &[u8], len: usize) -> Result<MidiEvent> {
         && cmd_slice[cmd_size - 1] == SYSEX_END)
     {
         // Not a SysEx message, treat as Other
-        return Ok(MidiEvent::Other(buf[..len].to_vec()));
+        return Ok((MessageKind::Other, &buf[..len]));
     }
 
     // MMC Locate: F0 7F devID 06 44 06 01 hr mn sc fr sf F7
@@ -231,122 +1025,360 @@ fn parse_midi(buf: &[u8], len: usize) -> Result<MidiEvent> {
         && cmd_slice[6] == 0x01
     // Always 0x01 for Locate command
     {
-        return Ok(MidiEvent::Mmc(MmcCommand::Locate {
-            hour: cmd_slice[7],
-            minute: cmd_slice[8],
-            second: cmd_slice[9],
-            frame: cmd_slice[10],
-            subframe: 0, // Subframe is always 0 for this usecase
-        }));
+        return Ok((MessageKind::Locate, cmd_slice));
+    }
+
+    // MMC Variable Play, Search and Shuttle: F0 7F devID 06 45|46|47 03 sh sm sl F7
+    if cmd_slice.len() >= MMC_SPEED_LENGTH
+        && cmd_slice[3] == MMC_SUB_ID1
+        && cmd_slice[5] == MMC_SPEED_SIZE_BYTE
+    {
+        if let Some(command) = MmcCommand::from_speed_command(cmd_slice[4], MmcSpeed::STOP) {
+            return Ok((MidiEventRef::Mmc(command).kind(), cmd_slice));
+        }
+    }
+
+    // MMC Masked Write: F0 7F devID 06 41 04 name byte# mask data F7, terminated
+    // right after the bytes its count announces
+    if cmd_slice.len() >= MMC_START_STOP_LENGTH
+        && cmd_slice[3] == MMC_SUB_ID1
+        && cmd_slice[4] == MMC_MASKED_WRITE_CMD_BYTE
+    {
+        let count = usize::from(cmd_slice[5]);
+        if count == usize::from(MMC_MASKED_WRITE_SIZE_BYTE)
+            && cmd_size == 6 + count + 1
+            && cmd_slice[6..6 + count].iter().all(|&b| b & 0x80 == 0)
+        {
+            return Ok((MessageKind::MaskedWrite, cmd_slice));
+        }
     }
 
-    // Full-Frame MTC: F0 7F devID 01 01 hr mn sc fr F7
+    // Full-Frame MTC: F0 7F devID 01 01 hr mn sc fr F7, the time fields read from
+    // their fixed positions whatever vendor bytes precede the terminator
     if cmd_slice.len() >= MTC_FULL_FRAME_LENGTH
         && cmd_slice[3] == MTC_FULL_FRAME_SUB_ID1
         && cmd_slice[4] == MTC_FULL_FRAME_SUB_ID2
     {
-        return Ok(MidiEvent::MtcFull {
-            hour: cmd_slice[5],
-            minute: cmd_slice[6],
-            second: cmd_slice[7],
-            frame: cmd_slice[8],
-        });
+        return Ok((MessageKind::MtcFull, cmd_slice));
     }
 
-    // MMC Stop/Play: F0 7F devID 06 cmd F7
+    // MMC commands without data: F0 7F devID 06 cmd F7
     if cmd_slice.len() >= MMC_START_STOP_LENGTH && cmd_slice[3] == MMC_SUB_ID1 {
-        let cmd_byte = cmd_slice[4];
-        if cmd_byte == MMC_STOP_CMD_BYTE {
-            return Ok(MidiEvent::Mmc(MmcCommand::Stop));
-        } else if cmd_byte == MMC_PLAY_CMD_BYTE {
-            return Ok(MidiEvent::Mmc(MmcCommand::Play));
+        match MmcCommand::from_command_byte(cmd_slice[4]) {
+            Some(MmcCommand::Stop) => return Ok((MessageKind::Stop, cmd_slice)),
+            Some(MmcCommand::Play) => return Ok((MessageKind::Play, cmd_slice)),
+            Some(_) => return Ok((MessageKind::OtherMmc, cmd_slice)),
+            None => {}
         }
     }
 
     // Fallback for other SysEx
-    return Ok(MidiEvent::Other(cmd_slice.to_vec()));
+    Ok((MessageKind::Other, cmd_slice))
+}
+
+/// Recognises an Identity Request or Reply in a SysEx message ending at its first
+/// terminator, for [`classify_midi`].
+fn identity_kind(msg: &[u8]) -> Option<MessageKind> {
+    let [SYSEX_START, UNIVERSAL_NON_REALTIME_ID, body @ .., SYSEX_END] = msg else {
+        return None;
+    };
+    if body.iter().any(|&b| b & 0x80 != 0) {
+        return None;
+    }
+    match body {
+        // F0 7E devID 06 01 F7
+        [_, GENERAL_INFO_SUB_ID1, IDENTITY_REQUEST_SUB_ID2] => Some(MessageKind::IdentityRequest),
+        // F0 7E devID 06 02 id ff ff mm mm vv vv vv vv F7
+        [_, GENERAL_INFO_SUB_ID1, IDENTITY_REPLY_SUB_ID2, reply @ ..] => {
+            let id = ManufacturerId::parse(reply)?;
+            (reply.len() == id.wire_len() + 8).then_some(MessageKind::IdentityReply)
+        }
+        _ => None,
+    }
 }
 
-/// Serialize a MIDI event into a byte buffer.
+/// Recognises an MMC Stop, Play or Locate sent without its `F7` terminator, for
+/// [`classify_midi`].
 ///
-/// This internal function handles building the binary representation of MIDI events.
+/// Some controllers drop the terminator when their frames are tunneled through a
+/// driver, so such a frame is accepted when it is exactly one byte short of the
+/// complete message and otherwise well formed.
+fn unterminated_mmc_kind(msg: &[u8]) -> Option<MessageKind> {
+    let [SYSEX_START, UNIVERSAL_REALTIME_ID, SYSEX_DEVICE_ID_BROADCAST, MMC_SUB_ID1, body @ ..] =
+        msg
+    else {
+        return None;
+    };
+    if body.iter().any(|&b| b & 0x80 != 0) {
+        return None;
+    }
+    match body {
+        [MMC_STOP_CMD_BYTE] => Some(MessageKind::Stop),
+        [MMC_PLAY_CMD_BYTE] => Some(MessageKind::Play),
+        [MMC_LOCATE_CMD_BYTE, MMC_LOCATE_SIZE_BYTE, 0x01, _, _, _, _, _] => {
+            Some(MessageKind::Locate)
+        }
+        _ => None,
+    }
+}
+
+/// Parse exactly one MIDI message from a buffer.
+///
+/// This internal function handles the core parsing logic for different MIDI message types.
+/// It assumes no delta-time is present (FLAGS=0 in RTP-MIDI context).
 ///
 /// # Arguments
 ///
-/// * `event` - The MIDI event to serialize
-/// * `buf` - Buffer to append the serialized bytes to
-fn build_midi(event: &MidiEvent, buf: &mut Vec<u8>) {
-    match event {
-        MidiEvent::MtcQuarter { msg_type, value } => {
-            buf.push(SYSCOMMON_START);
-            buf.push((msg_type << 4) | (value & 0x0F));
+/// * `buf` - Buffer containing MIDI data
+/// * `len` - Number of bytes to parse from the buffer
+///
+/// # Returns
+///
+/// Returns a `MidiEventRef` borrowing pass-through bytes from `buf` on success.
+///
+/// # Errors
+///
+/// - `ParseError::EmptyBuffer` if the buffer is empty or length is 0
+/// - `ParseError::BufferTooSmall` if `len` exceeds the buffer size
+fn parse_midi(buf: &[u8], len: usize) -> Result<MidiEventRef<'_>> {
+    let (kind, msg) = classify_midi(buf, len)?;
+
+    Ok(match kind {
+        // The data is 0nnn dddd, where nnn is the message type and dddd is the value
+        MessageKind::MtcQuarter => MidiEventRef::MtcQuarter {
+            msg_type: msg[1] >> 4,
+            value: msg[1] & 0x0F,
+        },
+        MessageKind::MtcFull => MidiEventRef::MtcFull {
+            hour: msg[5],
+            minute: msg[6],
+            second: msg[7],
+            frame: msg[8],
+        },
+        MessageKind::Play => MidiEventRef::Mmc(MmcCommand::Play),
+        MessageKind::Stop => MidiEventRef::Mmc(MmcCommand::Stop),
+        MessageKind::OtherMmc => MidiEventRef::Mmc(
+            MmcCommand::from_command_byte(msg[4]).expect("classified MMC command"),
+        ),
+        MessageKind::Locate => MidiEventRef::Mmc(MmcCommand::Locate {
+            hour: msg[7],
+            minute: msg[8],
+            second: msg[9],
+            frame: msg[10],
+            subframe: 0, // Subframe is always 0 for this usecase
+        }),
+        MessageKind::Shuttle | MessageKind::VariablePlay | MessageKind::Search => {
+            MidiEventRef::Mmc(
+                MmcCommand::from_speed_command(
+                    msg[4],
+                    MmcSpeed::from_bytes([msg[6], msg[7], msg[8]]),
+                )
+                .expect("classified MMC speed command"),
+            )
+        }
+        MessageKind::MaskedWrite => MidiEventRef::Mmc(MmcCommand::MaskedWrite {
+            field: msg[6],
+            start: msg[7],
+            mask: msg[8],
+            data: msg[9],
+        }),
+        MessageKind::Realtime => MidiEventRef::Realtime(match msg[0] {
+            REALTIME_CLOCK => RealtimeMessage::Clock,
+            REALTIME_START => RealtimeMessage::Start,
+            REALTIME_CONTINUE => RealtimeMessage::Continue,
+            _ => RealtimeMessage::Stop,
+        }),
+        // The position is 14 bits, least significant 7 bits first
+        MessageKind::SongPosition => {
+            MidiEventRef::SongPosition(u16::from(msg[1] & 0x7F) | u16::from(msg[2] & 0x7F) << 7)
+        }
+        MessageKind::IdentityRequest => MidiEventRef::IdentityRequest { device_id: msg[2] },
+        // The family and model codes are 14 bits, least significant 7 bits first
+        MessageKind::IdentityReply => {
+            let manufacturer =
+                ManufacturerId::parse(&msg[5..]).expect("classified manufacturer ID");
+            let fields = &msg[5 + manufacturer.wire_len()..];
+            MidiEventRef::IdentityReply(DeviceIdentity {
+                device_id: msg[2],
+                manufacturer,
+                family: u16::from(fields[0]) | u16::from(fields[1]) << 7,
+                model: u16::from(fields[2]) | u16::from(fields[3]) << 7,
+                version: [fields[4], fields[5], fields[6], fields[7]],
+            })
+        }
+        MessageKind::ManufacturerSysex => {
+            let id = ManufacturerId::parse(&msg[1..]).expect("classified manufacturer ID");
+            MidiEventRef::ManufacturerSysex {
+                id,
+                data: &msg[1 + id.wire_len()..msg.len() - 1],
+            }
+        }
+        MessageKind::Other => MidiEventRef::Other(msg),
+    })
+}
+
+/// Serialize a MIDI event without allocating.
+///
+/// # Arguments
+///
+/// * `event` - The MIDI event to serialize, borrowed
+/// * `scratch` - Receives the message; [`IDENTITY_REPLY_MAX_LENGTH`] bytes fit any
+///   of them but manufacturer SysEx
+///
+/// # Returns
+///
+/// The message bytes, at the start of `scratch` or, for `MidiEventRef::Other`, in
+/// the bytes `event` borrows.
+///
+/// # Panics
+///
+/// If `scratch` is shorter than the message.
+pub(crate) fn encode_midi<'a>(event: &MidiEventRef<'a>, scratch: &'a mut [u8]) -> &'a [u8] {
+    let mut put = |bytes: &[u8]| {
+        scratch[..bytes.len()].copy_from_slice(bytes);
+        bytes.len()
+    };
+    let len = match event {
+        MidiEventRef::MtcQuarter { msg_type, value } => {
+            put(&[SYSCOMMON_START, (msg_type << 4) | (value & 0x0F)])
         }
-        MidiEvent::MtcFull {
+        MidiEventRef::MtcFull {
             hour,
             minute,
             second,
             frame,
-        } => {
-            buf.extend_from_slice(&[
+        } => put(&[
+            SYSEX_START,
+            UNIVERSAL_REALTIME_ID,
+            SYSEX_DEVICE_ID_BROADCAST,
+            MTC_FULL_FRAME_SUB_ID1,
+            MTC_FULL_FRAME_SUB_ID2,
+            *hour,
+            *minute,
+            *second,
+            *frame,
+            SYSEX_END,
+        ]),
+        MidiEventRef::Mmc(mmc_cmd) => match mmc_cmd {
+            MmcCommand::Locate {
+                hour,
+                minute,
+                second,
+                frame,
+                subframe: _,
+            } => put(&[
                 SYSEX_START,
                 UNIVERSAL_REALTIME_ID,
                 SYSEX_DEVICE_ID_BROADCAST,
-                MTC_FULL_FRAME_SUB_ID1,
-                MTC_FULL_FRAME_SUB_ID2,
+                MMC_SUB_ID1,
+                MMC_LOCATE_CMD_BYTE,
+                MMC_LOCATE_SIZE_BYTE, // Length byte, always 0x06 for Locate
+                0x01,                 // Always 0x01 for Locate command
                 *hour,
                 *minute,
                 *second,
                 *frame,
+                0x00, // Subframe is always 0 for this usecase
                 SYSEX_END,
-            ]);
-        }
-        MidiEvent::Mmc(mmc_cmd) => match mmc_cmd {
-            MmcCommand::Stop => {
-                buf.extend_from_slice(&[
+            ]),
+            MmcCommand::Shuttle { .. }
+            | MmcCommand::VariablePlay { .. }
+            | MmcCommand::Search { .. } => {
+                let (command_byte, speed) = mmc_cmd.speed_command().expect("speed command");
+                let [sh, sm, sl] = speed.to_bytes();
+                put(&[
                     SYSEX_START,
                     UNIVERSAL_REALTIME_ID,
                     SYSEX_DEVICE_ID_BROADCAST,
                     MMC_SUB_ID1,
-                    MMC_STOP_CMD_BYTE,
+                    command_byte,
+                    MMC_SPEED_SIZE_BYTE,
+                    sh,
+                    sm,
+                    sl,
                     SYSEX_END,
-                ]);
+                ])
             }
-            MmcCommand::Play => {
-                buf.extend_from_slice(&[
-                    SYSEX_START,
-                    UNIVERSAL_REALTIME_ID,
-                    SYSEX_DEVICE_ID_BROADCAST,
-                    MMC_SUB_ID1,
-                    MMC_PLAY_CMD_BYTE,
-                    SYSEX_END,
-                ]);
+            MmcCommand::MaskedWrite {
+                field,
+                start,
+                mask,
+                data,
+            } => put(&[
+                SYSEX_START,
+                UNIVERSAL_REALTIME_ID,
+                SYSEX_DEVICE_ID_BROADCAST,
+                MMC_SUB_ID1,
+                MMC_MASKED_WRITE_CMD_BYTE,
+                MMC_MASKED_WRITE_SIZE_BYTE,
+                *field,
+                *start,
+                *mask,
+                *data,
+                SYSEX_END,
+            ]),
+            // Every other command is a single byte without data
+            command => put(&[
+                SYSEX_START,
+                UNIVERSAL_REALTIME_ID,
+                SYSEX_DEVICE_ID_BROADCAST,
+                MMC_SUB_ID1,
+                command.command_byte().expect("command without data"),
+                SYSEX_END,
+            ]),
+        },
+        MidiEventRef::Realtime(message) => put(&[match message {
+            RealtimeMessage::Clock => REALTIME_CLOCK,
+            RealtimeMessage::Start => REALTIME_START,
+            RealtimeMessage::Continue => REALTIME_CONTINUE,
+            RealtimeMessage::Stop => REALTIME_STOP,
+        }]),
+        MidiEventRef::SongPosition(position) => put(&[
+            SONG_POSITION_POINTER,
+            (position & 0x7F) as u8,
+            ((position >> 7) & 0x7F) as u8,
+        ]),
+        MidiEventRef::IdentityRequest { device_id } => put(&[
+            SYSEX_START,
+            UNIVERSAL_NON_REALTIME_ID,
+            *device_id,
+            GENERAL_INFO_SUB_ID1,
+            IDENTITY_REQUEST_SUB_ID2,
+            SYSEX_END,
+        ]),
+        MidiEventRef::IdentityReply(identity) => {
+            let seven_bit = |code: u16| [(code & 0x7F) as u8, ((code >> 7) & 0x7F) as u8];
+            let bytes = [
+                SYSEX_START,
+                UNIVERSAL_NON_REALTIME_ID,
+                identity.device_id,
+                GENERAL_INFO_SUB_ID1,
+                IDENTITY_REPLY_SUB_ID2,
+            ]
+            .into_iter()
+            .chain(identity.manufacturer.bytes())
+            .chain(seven_bit(identity.family))
+            .chain(seven_bit(identity.model))
+            .chain(identity.version)
+            .chain([SYSEX_END]);
+            for (out, byte) in scratch.iter_mut().zip(bytes) {
+                *out = byte;
             }
-            MmcCommand::Locate {
-                hour,
-                minute,
-                second,
-                frame,
-                subframe: _,
-            } => {
-                buf.extend_from_slice(&[
-                    SYSEX_START,
-                    UNIVERSAL_REALTIME_ID,
-                    SYSEX_DEVICE_ID_BROADCAST,
-                    MMC_SUB_ID1,
-                    MMC_LOCATE_CMD_BYTE,
-                    MMC_LOCATE_SIZE_BYTE, // Length byte, always 0x06 for Locate
-                    0x01,                 // Always 0x01 for Locate command
-                    *hour,
-                    *minute,
-                    *second,
-                    *frame,
-                    0x00, // Subframe is always 0 for this usecase
-                    SYSEX_END,
-                ]);
+            identity.reply_len()
+        }
+        MidiEventRef::ManufacturerSysex { id, data } => {
+            let len = 2 + id.wire_len() + data.len();
+            scratch[0] = SYSEX_START;
+            for (out, byte) in scratch[1..].iter_mut().zip(id.bytes()) {
+                *out = byte;
             }
-        },
-        MidiEvent::Other(bytes) => buf.extend_from_slice(bytes),
-    }
+            scratch[1 + id.wire_len()..len - 1].copy_from_slice(data);
+            scratch[len - 1] = SYSEX_END;
+            len
+        }
+        MidiEventRef::Other(bytes) => return bytes,
+    };
+    &scratch[..len]
 }
 
 // ============================================================================
@@ -374,7 +1406,31 @@ fn build_midi(event: &MidiEvent, buf: &mut Vec<u8>) {
 ///
 /// - `ParseError::BufferTooSmall` if `len` exceeds the actual buffer size
 /// - `ParseError::EmptyBuffer` if the buffer is empty or `len` is 0
+#[cfg(feature = "alloc")]
 pub fn parse_midi_list(buf: &[u8], len: usize) -> Result<MidiEvent> {
+    if len > buf.len() {
+        return Err(ParseError::BufferTooSmall {
+            requested: len,
+            available: buf.len(),
+        });
+    }
+    parse_midi(buf, len).map(MidiEvent::from)
+}
+
+/// Parse a single MIDI message from a buffer without copying it.
+///
+/// Same as [`parse_midi_list`], except that pass-through messages borrow their
+/// bytes from `buf` instead of being copied into a new vector.
+///
+/// # Arguments
+///
+/// * `buf` - Buffer containing MIDI message data
+/// * `len` - Number of bytes to parse from the buffer (must not exceed `buf.len()`)
+///
+/// # Errors
+///
+/// Returns the same errors as [`parse_midi_list`].
+pub fn parse_midi_list_ref(buf: &[u8], len: usize) -> Result<MidiEventRef<'_>> {
     if len > buf.len() {
         return Err(ParseError::BufferTooSmall {
             requested: len,
@@ -384,6 +1440,166 @@ pub fn parse_midi_list(buf: &[u8], len: usize) -> Result<MidiEvent> {
     parse_midi(buf, len)
 }
 
+/// Parse a single MIDI message from a buffer, rejecting malformed SysEx messages.
+///
+/// Same as [`parse_midi_list`], except that SysEx messages it would pass through
+/// as `MidiEvent::Other` because they are malformed are rejected instead:
+///
+/// - a byte with the high bit set before the `F7` terminator
+/// - a manufacturer SysEx without a terminator, or terminated within its ID
+/// - an MMC Stop, Play or Locate without its terminator
+/// - an MMC Locate whose length byte is not `06`
+/// - an MMC Variable Play, Search or Shuttle whose length byte is not `03`
+/// - an MMC Masked Write whose length byte is not `04`
+/// - an MTC Full Frame terminated before its frame byte, or holding extra bytes
+///   between its frame byte and the terminator
+///
+/// # Arguments
+///
+/// * `buf` - Buffer containing MIDI message data
+/// * `len` - Number of bytes to parse from the buffer (must not exceed `buf.len()`)
+///
+/// # Errors
+///
+/// Returns the same errors as [`parse_midi_list`], and:
+///
+/// - `ParseError::InvalidMidiData` with the offending byte, its `position` counted
+///   from the start of `buf`, which is the start of the MIDI list in a payload
+/// - `ParseError::BufferTooSmall` if an unterminated MTC Full Frame ends early, or
+///   a manufacturer SysEx or MMC Stop, Play or Locate without its terminator
+#[cfg(feature = "alloc")]
+pub fn parse_midi_list_strict(buf: &[u8], len: usize) -> Result<MidiEvent> {
+    let event = parse_midi_list(buf, len)?;
+    check_sysex(&buf[..len])?;
+    Ok(event)
+}
+
+/// Find the first byte making a SysEx message malformed, for strict parsing.
+#[cfg(feature = "alloc")]
+fn check_sysex(msg: &[u8]) -> Result<()> {
+    if msg.first() != Some(&SYSEX_START) {
+        return Ok(());
+    }
+    let end = msg.iter().position(|&b| b == SYSEX_END);
+    let body = &msg[1..end.unwrap_or(msg.len())];
+    let invalid = |position: usize| ParseError::InvalidMidiData {
+        position,
+        byte: msg[position],
+    };
+
+    if let Some(i) = body.iter().position(|&b| b & 0x80 != 0) {
+        return Err(invalid(1 + i));
+    }
+    match msg.get(1) {
+        Some(&UNIVERSAL_REALTIME_ID) => {}
+        // Three-byte manufacturer ID cut short by the terminator
+        Some(&MANUFACTURER_ID_EXTENDED) if end.is_some_and(|end| end < 4) => {
+            return Err(invalid(end.expect("terminated")));
+        }
+        Some(&id) if id <= MANUFACTURER_ID_SHORT_MAX && end.is_none() => {
+            return Err(ParseError::BufferTooSmall {
+                requested: msg.len() + 1,
+                available: msg.len(),
+            });
+        }
+        _ => return Ok(()),
+    }
+    match (msg.get(3), msg.get(4)) {
+        // Stop, Play and Locate are accepted without a terminator by lenient parsing
+        (
+            Some(&MMC_SUB_ID1),
+            Some(&(MMC_STOP_CMD_BYTE | MMC_PLAY_CMD_BYTE | MMC_LOCATE_CMD_BYTE)),
+        ) if end.is_none() => Err(ParseError::BufferTooSmall {
+            requested: msg.len() + 1,
+            available: msg.len(),
+        }),
+        // Locate: F0 7F devID 06 44 06 01 hr mn sc fr sf F7
+        (Some(&MMC_SUB_ID1), Some(&MMC_LOCATE_CMD_BYTE))
+            if msg.get(5).is_some_and(|&b| b != MMC_LOCATE_SIZE_BYTE) =>
+        {
+            Err(invalid(5))
+        }
+        // Variable Play, Search and Shuttle: F0 7F devID 06 45|46|47 03 sh sm sl F7
+        (
+            Some(&MMC_SUB_ID1),
+            Some(&(MMC_VARIABLE_PLAY_CMD_BYTE | MMC_SEARCH_CMD_BYTE | MMC_SHUTTLE_CMD_BYTE)),
+        ) if msg.get(5).is_some_and(|&b| b != MMC_SPEED_SIZE_BYTE) => Err(invalid(5)),
+        // Masked Write: F0 7F devID 06 41 04 name byte# mask data F7
+        (Some(&MMC_SUB_ID1), Some(&MMC_MASKED_WRITE_CMD_BYTE))
+            if msg.get(5).is_some_and(|&b| b != MMC_MASKED_WRITE_SIZE_BYTE) =>
+        {
+            Err(invalid(5))
+        }
+        // Full Frame: F0 7F devID 01 01 hr mn sc fr F7
+        (Some(&MTC_FULL_FRAME_SUB_ID1), Some(&MTC_FULL_FRAME_SUB_ID2)) => match end {
+            Some(end) if end < MTC_FULL_FRAME_LENGTH - 1 => Err(invalid(end)),
+            Some(end) if end > MTC_FULL_FRAME_LENGTH - 1 => Err(invalid(MTC_FULL_FRAME_LENGTH - 1)),
+            None if msg.len() < MTC_FULL_FRAME_LENGTH => Err(ParseError::BufferTooSmall {
+                requested: MTC_FULL_FRAME_LENGTH,
+                available: msg.len(),
+            }),
+            _ => Ok(()),
+        },
+        _ => Ok(()),
+    }
+}
+
+/// Parse the first MIDI message of a byte stream.
+///
+/// Recognises the same messages as [`parse_midi_list`], for sources such as a
+/// serial port where messages follow one another without a netsync header.
+/// Messages that are not recognised take up the rest of the buffer, SysEx
+/// messages excepted, which end at their terminator.
+///
+/// # Arguments
+///
+/// * `buf` - Buffer starting with a MIDI status byte
+///
+/// # Returns
+///
+/// Returns `Ok((event, consumed))`, where `consumed` is the length of the message
+/// at the start of `buf`. Pass-through messages borrow their bytes from `buf`.
+///
+/// # Errors
+///
+/// - `ParseError::EmptyBuffer` if the buffer is empty
+pub fn parse_midi_message(buf: &[u8]) -> Result<(MidiEventRef<'_>, usize)> {
+    let (kind, msg) = classify_midi(buf, buf.len())?;
+    let consumed = match kind {
+        // Quarter frames are classified together with whatever follows them
+        MessageKind::MtcQuarter => MTC_QUARTER_FRAME_LENGTH,
+        MessageKind::Other if buf[0] == SYSEX_START => buf
+            .iter()
+            .position(|&b| b == SYSEX_END)
+            .map_or(buf.len(), |end| end + 1),
+        _ => msg.len(),
+    };
+    Ok((parse_midi(&buf[..consumed], consumed)?, consumed))
+}
+
+/// Determine the kind of a single MIDI message without parsing or copying it.
+///
+/// Applies the same recognition rules as [`parse_midi_list`], so the result always
+/// matches the kind of the event it would return.
+///
+/// # Arguments
+///
+/// * `buf` - Buffer containing MIDI message data
+/// * `len` - Number of bytes to inspect from the buffer (must not exceed `buf.len()`)
+///
+/// # Errors
+///
+/// Returns the same errors as [`parse_midi_list`].
+pub fn peek_midi_kind(buf: &[u8], len: usize) -> Result<MessageKind> {
+    if len > buf.len() {
+        return Err(ParseError::BufferTooSmall {
+            requested: len,
+            available: buf.len(),
+        });
+    }
+    classify_midi(buf, len).map(|(kind, _)| kind)
+}
+
 /// Build a complete MIDI message packet from an event.
 ///
 /// Serializes a MIDI event into a byte vector suitable for transmission
@@ -397,10 +1613,55 @@ pub fn parse_midi_list(buf: &[u8], len: usize) -> Result<MidiEvent> {
 /// # Returns
 ///
 /// * A `Vec<u8>` containing the complete MIDI message bytes.
+#[cfg(feature = "alloc")]
 pub fn build_midi_list(event: &MidiEvent) -> Vec<u8> {
-    let mut buf = Vec::new();
-    build_midi(event, &mut buf);
-    buf
+    match event {
+        // The only message that may not fit the scratch buffer
+        MidiEvent::ManufacturerSysex { id, data } => {
+            let mut msg = alloc::vec![0; 2 + id.wire_len() + data.len()];
+            encode_midi(&event.as_event_ref(), &mut msg);
+            msg
+        }
+        _ => encode_midi(&event.as_event_ref(), &mut [0; IDENTITY_REPLY_MAX_LENGTH]).to_vec(),
+    }
+}
+
+/// Returns the device ID a Universal Real-Time SysEx message, such as an MTC Full
+/// Frame or an MMC command, is addressed to.
+///
+/// # Arguments
+///
+/// * `midi` - The message bytes, starting with the status byte
+///
+/// # Returns
+///
+/// The device ID byte, or `None` if `midi` is not a Universal Real-Time SysEx.
+pub fn realtime_sysex_device_id(midi: &[u8]) -> Option<u8> {
+    match midi {
+        [SYSEX_START, UNIVERSAL_REALTIME_ID, device_id, ..] => Some(*device_id),
+        _ => None,
+    }
+}
+
+/// Addresses a Universal Real-Time SysEx message to another device.
+///
+/// # Arguments
+///
+/// * `midi` - The message bytes, starting with the status byte
+/// * `device_id` - The new device ID, [`SYSEX_DEVICE_ID_BROADCAST`] for all devices
+///
+/// # Returns
+///
+/// `true` if the device ID was replaced, `false` if `midi` is not a Universal
+/// Real-Time SysEx, in which case it is left unchanged.
+pub fn set_realtime_sysex_device_id(midi: &mut [u8], device_id: u8) -> bool {
+    match midi {
+        [SYSEX_START, UNIVERSAL_REALTIME_ID, id, ..] => {
+            *id = device_id;
+            true
+        }
+        _ => false,
+    }
 }
 
 #[cfg(test)]
@@ -450,6 +1711,28 @@ mod tests {
             },
             MidiEvent::Mmc(MmcCommand::Stop),
             MidiEvent::Mmc(MmcCommand::Play),
+            MidiEvent::Realtime(RealtimeMessage::Clock),
+            MidiEvent::Realtime(RealtimeMessage::Start),
+            MidiEvent::Realtime(RealtimeMessage::Continue),
+            MidiEvent::Realtime(RealtimeMessage::Stop),
+            MidiEvent::SongPosition(0x3FFF),
+            MidiEvent::SongPosition(200),
+            MidiEvent::IdentityRequest { device_id: 0x7F },
+            MidiEvent::IdentityReply(DeviceIdentity {
+                device_id: 0x10,
+                manufacturer: ManufacturerId::Extended([0x20, 0x33]),
+                family: 0x3FFF,
+                model: 0x0102,
+                version: [1, 2, 3, 4],
+            }),
+            MidiEvent::ManufacturerSysex {
+                id: ManufacturerId::Short(0x41),
+                data: vec![0x10, 0x42, 0x12],
+            },
+            MidiEvent::ManufacturerSysex {
+                id: ManufacturerId::Extended([0x20, 0x33]),
+                data: vec![],
+            },
             MidiEvent::Other(vec![0x90, 0x60, 0x7F]),
         ];
 
@@ -457,6 +1740,10 @@ mod tests {
             let built = build_midi_list(&event);
             let parsed = parse_midi_list(&built, built.len()).unwrap();
             assert_eq!(event, parsed);
+            assert_eq!(
+                parse_midi_list_ref(&built, built.len()).unwrap(),
+                event.as_event_ref()
+            );
         }
     }
 }
@@ -9,7 +9,7 @@
 //! - `HH`: Hours (0-23)
 //! - `MM`: Minutes (0-59)
 //! - `SS`: Seconds (0-59)
-//! - `FF`: Frames (0-29 for 30fps non-drop frame)
+//! - `FF`: Frames (0-23 at 24fps, 0-24 at 25fps, 0-29 at 30fps and 29.97fps drop-frame)
 //!
 //! ## MTC Quarter Frame
 //!
@@ -27,15 +27,29 @@
 //! | 7 | Hours high nibble + frame rate | 0-1 (+ rate bits) |
 //!
 //!
-//! ## Limitations
+//! ## Frame Rates
 //!
-//! - Only supports 30fps non-drop frame format
-//! - Drop frame timecode is not implemented
-//! - Frame rates other than 30fps are not supported
+//! The plain conversions ([`us_to_smpte`], [`smpte_to_us`], [`smpte_to_quarter_frames`]
+//! and [`quarter_frames_to_smpte`]) work at 30fps non-drop frame. Their `_rate`
+//! counterparts take a [`FrameRate`] and handle every rate MTC can signal. Drop-frame
+//! timecode skips frame numbers 0 and 1 at the start of every minute not divisible
+//! by ten, so that its labels follow the wall clock at 30000/1001 frames per second.
+//! [`convert_rate`] converts timecode between two rates, for a slave whose video
+//! runs at another rate than its master, and the `_pulled` conversions take the
+//! 0.1% [`Pull`] of film-to-video transfers into account. [`DriftEstimator`]
+//! measures how far the master clock actually runs from the local one.
+//!
+//! ## Text Form
+//!
+//! [`MtcFullFrame`] formats as `HH:MM:SS:FF`, or `HH:MM:SS;FF` with the alternate
+//! flag (`{:#}`), the usual notation of drop-frame timecode. Parsing accepts both.
+use core::fmt;
+use core::str::FromStr;
+
 use crate::error::MtcError;
 
 /// SMPTE timecode: HH:MM:SS:FF (30fps non-drop frame)
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub struct MtcFullFrame {
     pub hours: u8,   // 0-23
     pub minutes: u8, // 0-59
@@ -43,8 +57,136 @@ pub struct MtcFullFrame {
     pub frames: u8,  // 0-29
 }
 
+impl MtcFullFrame {
+    /// Returns `true` if this timecode exists at `rate`: every field is in range
+    /// and, at 29.97fps drop-frame, it is not one of the frame numbers skipped at
+    /// the start of a minute.
+    pub fn is_valid_for(&self, rate: FrameRate) -> bool {
+        self.hours < 24
+            && self.minutes < 60
+            && self.seconds < 60
+            && u32::from(self.frames) < rate.nominal_fps()
+            && !self.is_dropped(rate)
+    }
+
+    /// Returns this timecode, or the first one after it if its frame number is
+    /// dropped at `rate`, such as `00:05:00;02` for `00:05:00;00`.
+    pub fn next_valid(&self, rate: FrameRate) -> MtcFullFrame {
+        if self.is_dropped(rate) {
+            MtcFullFrame { frames: 2, ..*self }
+        } else {
+            *self
+        }
+    }
+
+    /// Returns this timecode, or the last one before it if its frame number is
+    /// dropped at `rate`, such as `00:04:59;29` for `00:05:00;00`.
+    pub fn prev_valid(&self, rate: FrameRate) -> MtcFullFrame {
+        if self.is_dropped(rate) {
+            MtcFullFrame {
+                minutes: self.minutes - 1,
+                seconds: 59,
+                frames: 29,
+                ..*self
+            }
+        } else {
+            *self
+        }
+    }
+
+    /// # Moves the timecode by a number of frames at the given rate.
+    ///
+    /// Counts real frames, so drop-frame timecode never lands on a skipped frame
+    /// number, and wraps around at midnight. A skipped starting label counts from
+    /// [`MtcFullFrame::next_valid`].
+    ///
+    /// # Arguments
+    ///
+    /// * `frames` - Number of frames to move by, negative to move back
+    /// * `rate` - Frame rate of the timecode
+    ///
+    /// # Returns
+    ///
+    /// The timecode `frames` frames away
+    pub fn add_frames(&self, frames: i64, rate: FrameRate) -> MtcFullFrame {
+        let day = frames_per_day(rate) as i64;
+        let framenum = (frame_number(self, rate) as i64 + frames).rem_euclid(day);
+        frame_label(framenum as u64, rate)
+    }
+
+    /// Frame numbers 0 and 1 are skipped at the start of every drop-frame minute
+    /// not divisible by ten.
+    fn is_dropped(&self, rate: FrameRate) -> bool {
+        rate == FrameRate::Fps2997Df
+            && self.seconds == 0
+            && self.frames < 2
+            && !self.minutes.is_multiple_of(10)
+    }
+}
+
+impl fmt::Display for MtcFullFrame {
+    /// Writes `HH:MM:SS:FF`, or `HH:MM:SS;FF` with `{:#}` for drop-frame timecode
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let separator = if f.alternate() { ';' } else { ':' };
+        write!(
+            f,
+            "{:02}:{:02}:{:02}{}{:02}",
+            self.hours, self.minutes, self.seconds, separator, self.frames
+        )
+    }
+}
+
+impl FromStr for MtcFullFrame {
+    type Err = MtcError;
+
+    /// Parses `HH:MM:SS:FF` or `HH:MM:SS;FF`, with one or two digits per field
+    ///
+    /// # Errors
+    ///
+    /// * [`MtcError::MalformedTimecode`] - The text is not in either form
+    /// * [`MtcError::InvalidHours`], [`MtcError::InvalidMinutes`],
+    ///   [`MtcError::InvalidSeconds`] or [`MtcError::InvalidFrames`] - The first field
+    ///   out of range at 30fps
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (time, frames) = s
+            .rsplit_once([':', ';'])
+            .ok_or(MtcError::MalformedTimecode)?;
+        let mut fields = time.split(':');
+        let field = |text: Option<&str>| match text {
+            Some(text)
+                if (1..=2).contains(&text.len()) && text.bytes().all(|b| b.is_ascii_digit()) =>
+            {
+                Ok(text.parse::<u8>().expect("one or two digits"))
+            }
+            _ => Err(MtcError::MalformedTimecode),
+        };
+        let smpte = MtcFullFrame {
+            hours: field(fields.next())?,
+            minutes: field(fields.next())?,
+            seconds: field(fields.next())?,
+            frames: field(Some(frames))?,
+        };
+        if fields.next().is_some() {
+            return Err(MtcError::MalformedTimecode);
+        }
+        if smpte.hours > 23 {
+            return Err(MtcError::InvalidHours(smpte.hours));
+        }
+        if smpte.minutes > 59 {
+            return Err(MtcError::InvalidMinutes(smpte.minutes));
+        }
+        if smpte.seconds > 59 {
+            return Err(MtcError::InvalidSeconds(smpte.seconds));
+        }
+        if smpte.frames > 29 {
+            return Err(MtcError::InvalidFrames(smpte.frames));
+        }
+        Ok(smpte)
+    }
+}
+
 /// MTC quarter frame: frame_type (0-7) + value (0-15)
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub struct MtcQuarterFrame {
     /// Frame type identifier (0-7), determines which part of timecode this frame contains
     pub frame_type: u8,
@@ -55,15 +197,219 @@ pub struct MtcQuarterFrame {
 /// Microseconds timestamp (VLC-compatible)
 pub type VlcTickT = u64;
 
-/// SMPTE frame rate: 30 frames per second (non-drop frame)
-const SMPTE_30_FPS: u32 = 30;
-
-/// SMPTE frame rate base (always 1 for non-drop frame)
-const SMPTE_30_FPS_BASE: u32 = 1;
-
 /// Microseconds per second conversion factor
 const VLC_TICK_FROM_SEC: u64 = 1_000_000;
 
+/// Frames in ten minutes of 29.97fps drop-frame timecode
+const DROP_FRAME_FRAMES_PER_10_MIN: u64 = 17_982;
+
+/// Frames in each minute of 29.97fps drop-frame timecode not divisible by ten
+const DROP_FRAME_FRAMES_PER_MIN: u64 = 1_798;
+
+/// # SMPTE frame rates that can be signalled in MTC.
+///
+/// The discriminants are the 2-bit rate codes carried in bits 1-2 of quarter
+/// frame 7 (and bits 5-6 of the Full Frame hours byte).
+///
+/// Pass it to the `_rate` conversions, such as [`us_to_smpte_rate`], to work with
+/// timecode at rates other than 30fps.
+///
+/// With the `serde` feature, rates are named `"24"`, `"25"`, `"29.97df"` and `"30"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FrameRate {
+    /// 24 frames per second (film)
+    #[cfg_attr(feature = "serde", serde(rename = "24"))]
+    Fps24 = 0,
+    /// 25 frames per second (PAL/EBU)
+    #[cfg_attr(feature = "serde", serde(rename = "25"))]
+    Fps25 = 1,
+    /// 30000/1001 frames per second, drop-frame (NTSC)
+    #[cfg_attr(feature = "serde", serde(rename = "29.97df"))]
+    Fps2997Df = 2,
+    /// 30 frames per second, non-drop frame
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "30"))]
+    Fps30 = 3,
+}
+
+impl FrameRate {
+    /// Decodes a 2-bit MTC rate code, returning `None` for values above 3.
+    pub fn from_mtc_code(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(FrameRate::Fps24),
+            1 => Some(FrameRate::Fps25),
+            2 => Some(FrameRate::Fps2997Df),
+            3 => Some(FrameRate::Fps30),
+            _ => None,
+        }
+    }
+
+    /// Returns the 2-bit MTC rate code.
+    pub fn mtc_code(self) -> u8 {
+        self as u8
+    }
+
+    /// Returns the nominal number of frames per second (30 for 29.97 drop-frame).
+    pub fn nominal_fps(self) -> u32 {
+        match self {
+            FrameRate::Fps24 => 24,
+            FrameRate::Fps25 => 25,
+            FrameRate::Fps2997Df | FrameRate::Fps30 => 30,
+        }
+    }
+
+    /// Returns the duration of one frame in microseconds, rounded down.
+    pub fn frame_duration_us(self) -> VlcTickT {
+        match self {
+            FrameRate::Fps2997Df => 1001 * VLC_TICK_FROM_SEC / 30_000,
+            _ => VLC_TICK_FROM_SEC / self.nominal_fps() as u64,
+        }
+    }
+
+    /// Returns the time between two quarter-frame messages in microseconds, rounded down.
+    ///
+    /// Four quarter frames are sent per frame, so a full 8-piece cycle spans two frames.
+    pub fn quarter_frame_period_us(self) -> VlcTickT {
+        self.quarter_frames_to_us(1)
+    }
+
+    /// Returns the duration of `count` quarter-frame periods in microseconds, rounded
+    /// down once rather than per period.
+    pub fn quarter_frames_to_us(self, count: u64) -> VlcTickT {
+        match self {
+            FrameRate::Fps2997Df => count * 1001 * VLC_TICK_FROM_SEC / 120_000,
+            _ => count * VLC_TICK_FROM_SEC / (4 * self.nominal_fps() as u64),
+        }
+    }
+}
+
+/// # How a time between two frames is rounded to a whole frame.
+///
+/// Used by [`convert_rate`] and [`convert_rate_us`]. The plain conversions, such as
+/// [`us_to_smpte_rate`], round to the nearest frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Rounding {
+    /// The nearest frame, the later one when exactly halfway
+    #[default]
+    Nearest,
+    /// The frame showing at that time, never later than it
+    Down,
+    /// The first frame starting at or after that time
+    Up,
+}
+
+impl Rounding {
+    /// Divides `num` by `den`, rounding the quotient as requested.
+    fn div(self, num: u128, den: u128) -> u64 {
+        let quotient = match self {
+            Rounding::Nearest => (2 * num + den) / (2 * den),
+            Rounding::Down => num / den,
+            Rounding::Up => num.div_ceil(den),
+        };
+        quotient as u64
+    }
+}
+
+/// # Speed offset between the timecode and the clock it is played against.
+///
+/// Film transferred to video runs 0.1% slow, 29.97 instead of 30 or 23.976
+/// instead of 24 frames per second, and audio recorded against one is pulled to
+/// match the other. Timecode chased without the pull drifts by 3.6 seconds an hour.
+///
+/// The factors are exactly 1000/1001 and 1001/1000, applied in integer arithmetic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Pull {
+    /// The timecode runs at the speed of the clock
+    #[default]
+    None,
+    /// The timecode runs 1001/1000 as fast as the clock
+    Up01,
+    /// The timecode runs 1000/1001 as fast as the clock
+    Down01,
+}
+
+impl Pull {
+    /// Returns the timecode time elapsed per clock time, as a fraction.
+    pub fn ratio(self) -> (u64, u64) {
+        match self {
+            Pull::None => (1, 1),
+            Pull::Up01 => (1001, 1000),
+            Pull::Down01 => (1000, 1001),
+        }
+    }
+
+    /// Converts a clock duration to the timecode time elapsed meanwhile, rounded down.
+    pub fn apply(self, us: VlcTickT) -> VlcTickT {
+        let (num, den) = self.ratio();
+        Rounding::Down.div(us as u128 * num as u128, den as u128)
+    }
+
+    /// Converts a timecode duration to the clock time it takes, rounded down.
+    pub fn remove(self, us: VlcTickT) -> VlcTickT {
+        let (num, den) = self.ratio();
+        Rounding::Down.div(us as u128 * den as u128, num as u128)
+    }
+}
+
+/// Returns the exact frame rate as a fraction of frames per second.
+fn frames_per_second(rate: FrameRate) -> (u128, u128) {
+    match rate {
+        FrameRate::Fps2997Df => (30_000, 1001),
+        _ => (rate.nominal_fps() as u128, 1),
+    }
+}
+
+/// Counts the frames from midnight to the frame labelled `smpte`.
+///
+/// Drop-frame labels that do not exist are read as the next valid one.
+fn frame_number(smpte: &MtcFullFrame, rate: FrameRate) -> u64 {
+    let smpte = smpte.next_valid(rate);
+    let fps = rate.nominal_fps() as u64;
+    let total_minutes = smpte.hours as u64 * 60 + smpte.minutes as u64;
+    let label = (total_minutes * 60 + smpte.seconds as u64) * fps + smpte.frames as u64;
+
+    match rate {
+        FrameRate::Fps2997Df => label.saturating_sub(2 * (total_minutes - total_minutes / 10)),
+        _ => label,
+    }
+}
+
+/// Returns the number of frames in 24 hours of timecode.
+fn frames_per_day(rate: FrameRate) -> u64 {
+    match rate {
+        FrameRate::Fps2997Df => 24 * 6 * DROP_FRAME_FRAMES_PER_10_MIN,
+        _ => 24 * 3600 * rate.nominal_fps() as u64,
+    }
+}
+
+/// Labels the frame `framenum` frames after midnight, wrapping after 24 hours.
+fn frame_label(framenum: u64, rate: FrameRate) -> MtcFullFrame {
+    // Drop-frame labels skip two frame numbers every minute except each tenth
+    let label = match rate {
+        FrameRate::Fps2997Df => {
+            let tens = framenum / DROP_FRAME_FRAMES_PER_10_MIN;
+            let rest = framenum % DROP_FRAME_FRAMES_PER_10_MIN;
+            let dropped_minutes = rest.saturating_sub(2) / DROP_FRAME_FRAMES_PER_MIN;
+            framenum + 18 * tens + 2 * dropped_minutes
+        }
+        _ => framenum,
+    };
+
+    let fps = rate.nominal_fps() as u64;
+    MtcFullFrame {
+        hours: ((label / fps / 3600) % 24) as u8, // Limit hours to 0-23 unlike VLC impl
+        minutes: ((label / fps / 60) % 60) as u8,
+        seconds: ((label / fps) % 60) as u8,
+        frames: (label % fps) as u8,
+    }
+}
+
 /// # Converts microseconds to SMPTE timecode (30fps non-drop frame).
 ///
 ///
@@ -83,24 +429,25 @@ const VLC_TICK_FROM_SEC: u64 = 1_000_000;
 ///
 /// SMPTE timecode structure with hours, minutes, seconds, and frames
 pub fn us_to_smpte(us: VlcTickT) -> MtcFullFrame {
-    let frame_rate = SMPTE_30_FPS;
-    let frame_rate_base = SMPTE_30_FPS_BASE;
-
-    let framenum =
-        ((us * frame_rate as u64) as f64 / frame_rate_base as f64 / VLC_TICK_FROM_SEC as f64)
-            .round() as u64;
-
-    let frames = (framenum % (frame_rate / frame_rate_base) as u64) as u8;
-    let seconds = ((framenum * frame_rate_base as u64 / frame_rate as u64) % 60) as u8;
-    let minutes = ((framenum * frame_rate_base as u64 / frame_rate as u64 / 60) % 60) as u8;
-    let hours = ((framenum * frame_rate_base as u64 / frame_rate as u64 / 3600) % 24) as u8; // Limit hours to 0-23 unlike VLC impl
+    us_to_smpte_rate(us, FrameRate::Fps30)
+}
 
-    MtcFullFrame {
-        hours,
-        minutes,
-        seconds,
-        frames,
-    }
+/// # Converts microseconds to SMPTE timecode at the given frame rate.
+///
+/// Same rounding as [`us_to_smpte`], which this is for `FrameRate::Fps30`. At
+/// 29.97fps drop-frame the frame count is taken at 30000/1001 frames per second
+/// and labelled with drop-frame numbering.
+///
+/// # Arguments
+///
+/// * `us` - Timestamp in microseconds since epoch
+/// * `rate` - Frame rate of the timecode
+///
+/// # Returns
+///
+/// SMPTE timecode structure with hours, minutes, seconds, and frames
+pub fn us_to_smpte_rate(us: VlcTickT, rate: FrameRate) -> MtcFullFrame {
+    convert_rate_us(us, rate, Rounding::Nearest)
 }
 
 /// # Converts SMPTE timecode to microseconds (30fps non-drop frame).
@@ -114,15 +461,127 @@ pub fn us_to_smpte(us: VlcTickT) -> MtcFullFrame {
 ///
 /// Timestamp in microseconds
 pub fn smpte_to_us(smpte: &MtcFullFrame) -> VlcTickT {
-    let frame_rate = SMPTE_30_FPS;
-    let frame_rate_base = SMPTE_30_FPS_BASE;
+    smpte_to_us_rate(smpte, FrameRate::Fps30)
+}
 
-    let framenum = (smpte.hours as u64 * 3600 * frame_rate as u64 / frame_rate_base as u64)
-        + (smpte.minutes as u64 * 60 * frame_rate as u64 / frame_rate_base as u64)
-        + (smpte.seconds as u64 * frame_rate as u64 / frame_rate_base as u64)
-        + (smpte.frames as u64);
+/// # Converts SMPTE timecode at the given frame rate to microseconds.
+///
+/// This function performs the inverse operation of [`us_to_smpte_rate`]. Drop-frame
+/// labels that do not exist, such as `00:01:00:00`, are read as the next valid one.
+///
+/// # Arguments
+///
+/// * `smpte` - SMPTE timecode structure
+/// * `rate` - Frame rate of the timecode
+///
+/// # Returns
+///
+/// Timestamp in microseconds
+pub fn smpte_to_us_rate(smpte: &MtcFullFrame, rate: FrameRate) -> VlcTickT {
+    smpte_to_us_pulled(smpte, rate, Pull::None)
+}
 
-    (framenum * frame_rate_base as u64 * VLC_TICK_FROM_SEC) / frame_rate as u64
+/// # Converts clock time to SMPTE timecode running with a pull.
+///
+/// Same as [`us_to_smpte_rate`], which this is for `Pull::None`, for timecode
+/// running faster or slower than the clock `us` is measured on. The pull and the
+/// frame rate are applied together, so the frame is rounded only once.
+///
+/// # Arguments
+///
+/// * `us` - Clock time in microseconds since the timecode was at midnight
+/// * `rate` - Frame rate of the timecode
+/// * `pull` - Speed of the timecode relative to the clock
+///
+/// # Returns
+///
+/// SMPTE timecode structure with hours, minutes, seconds, and frames
+pub fn us_to_smpte_pulled(us: VlcTickT, rate: FrameRate, pull: Pull) -> MtcFullFrame {
+    let (num, den) = frames_per_second(rate);
+    let (pull_num, pull_den) = pull.ratio();
+    let framenum = Rounding::Nearest.div(
+        us as u128 * num * pull_num as u128,
+        den * VLC_TICK_FROM_SEC as u128 * pull_den as u128,
+    );
+    frame_label(framenum, rate)
+}
+
+/// # Converts SMPTE timecode running with a pull to clock time.
+///
+/// This function performs the inverse operation of [`us_to_smpte_pulled`], rounding
+/// down to the microsecond.
+///
+/// # Arguments
+///
+/// * `smpte` - SMPTE timecode structure
+/// * `rate` - Frame rate of the timecode
+/// * `pull` - Speed of the timecode relative to the clock
+///
+/// # Returns
+///
+/// Clock time in microseconds since the timecode was at midnight
+pub fn smpte_to_us_pulled(smpte: &MtcFullFrame, rate: FrameRate, pull: Pull) -> VlcTickT {
+    let (num, den) = frames_per_second(rate);
+    let (pull_num, pull_den) = pull.ratio();
+    Rounding::Down.div(
+        frame_number(smpte, rate) as u128 * den * VLC_TICK_FROM_SEC as u128 * pull_den as u128,
+        num * pull_num as u128,
+    )
+}
+
+/// # Converts SMPTE timecode from one frame rate to another.
+///
+/// Goes through the time at which the frame starts, as [`smpte_to_us_rate`] then
+/// [`convert_rate_us`] would, but keeps that time exact instead of truncating it
+/// to a whole microsecond, so the result is rounded only once. Converting to the
+/// same rate returns the timecode unchanged, a nonexistent drop-frame label
+/// aside.
+///
+/// # Arguments
+///
+/// * `tc` - SMPTE timecode at the `from` rate
+/// * `from` - Frame rate of `tc`
+/// * `to` - Frame rate of the result
+/// * `rounding` - Which target frame a time between two of them gives
+///
+/// # Returns
+///
+/// SMPTE timecode at the `to` rate
+pub fn convert_rate(
+    tc: &MtcFullFrame,
+    from: FrameRate,
+    to: FrameRate,
+    rounding: Rounding,
+) -> MtcFullFrame {
+    let (from_num, from_den) = frames_per_second(from);
+    let (to_num, to_den) = frames_per_second(to);
+    let framenum = rounding.div(
+        frame_number(tc, from) as u128 * from_den * to_num,
+        from_num * to_den,
+    );
+    frame_label(framenum, to)
+}
+
+/// # Converts microseconds to SMPTE timecode at the given frame rate and rounding.
+///
+/// Same as [`us_to_smpte_rate`], which this is for `Rounding::Nearest`. When
+/// converting many timecodes, keeping their times in microseconds and converting
+/// each once with this avoids rounding them to a frame of an intermediate rate.
+///
+/// # Arguments
+///
+/// * `us` - Timestamp in microseconds since epoch
+/// * `to` - Frame rate of the result
+/// * `rounding` - Which frame a time between two of them gives
+///
+/// # Returns
+///
+/// SMPTE timecode at the `to` rate
+pub fn convert_rate_us(us: VlcTickT, to: FrameRate, rounding: Rounding) -> MtcFullFrame {
+    // Integer arithmetic, which `no_std` builds support
+    let (num, den) = frames_per_second(to);
+    let framenum = rounding.div(us as u128 * num, den * VLC_TICK_FROM_SEC as u128);
+    frame_label(framenum, to)
 }
 
 /// # Converts SMPTE timecode to 8 MTC quarter frames.
@@ -140,6 +599,23 @@ pub fn smpte_to_us(smpte: &MtcFullFrame) -> VlcTickT {
 ///
 /// Array of 8 MTC quarter frames containing the timecode data
 pub fn smpte_to_quarter_frames(smpte: &MtcFullFrame) -> [MtcQuarterFrame; 8] {
+    smpte_to_quarter_frames_rate(smpte, FrameRate::Fps30)
+}
+
+/// # Converts SMPTE timecode to 8 MTC quarter frames signalling the given frame rate.
+///
+/// Same as [`smpte_to_quarter_frames`], with the rate code of `rate` in bits 1-2
+/// of piece 7.
+///
+/// # Arguments
+///
+/// * `smpte` - SMPTE timecode structure to convert
+/// * `rate` - Frame rate to signal
+///
+/// # Returns
+///
+/// Array of 8 MTC quarter frames containing the timecode data
+pub fn smpte_to_quarter_frames_rate(smpte: &MtcFullFrame, rate: FrameRate) -> [MtcQuarterFrame; 8] {
     [
         MtcQuarterFrame {
             frame_type: 0,
@@ -171,11 +647,38 @@ pub fn smpte_to_quarter_frames(smpte: &MtcFullFrame) -> [MtcQuarterFrame; 8] {
         },
         MtcQuarterFrame {
             frame_type: 7,
-            value: ((smpte.hours >> 4) & 0x01) | (0x03 << 1), // 30fps non-drop = 11 (0x03)
+            value: ((smpte.hours >> 4) & 0x01) | (rate.mtc_code() << 1),
         },
     ]
 }
 
+/// # Returns the quarter frame a master should be sending at a given time.
+///
+/// For masters driven by an external clock, which cannot keep a scheduler. Piece
+/// 0 of every cycle starts an even number of frames after midnight, and the eight
+/// pieces of a cycle carry the timecode of that frame. The piece returned is the last one due at or
+/// before `us`, its instant being given by [`FrameRate::quarter_frames_to_us`].
+///
+/// # Arguments
+///
+/// * `us` - Timestamp in microseconds since epoch
+/// * `rate` - Frame rate of the timecode, also signalled in piece 7
+///
+/// # Returns
+///
+/// The piece index (0-7) and the quarter frame to send
+pub fn quarter_frame_at(us: VlcTickT, rate: FrameRate) -> (u8, MtcQuarterFrame) {
+    // Quarter frames due so far, less the one at time 0
+    let (num, den) = frames_per_second(rate);
+    let count = Rounding::Up.div((us as u128 + 1) * 4 * num, den * VLC_TICK_FROM_SEC as u128) - 1;
+    let piece = (count % 8) as u8;
+    let smpte = frame_label((count - count % 8) / 4, rate);
+    (
+        piece,
+        smpte_to_quarter_frames_rate(&smpte, rate)[piece as usize],
+    )
+}
+
 /// # Converts 8 MTC quarter frames to SMPTE timecode.
 ///
 /// This function reconstructs SMPTE timecode from 8 MTC quarter frames. It validates that:
@@ -184,8 +687,8 @@ pub fn smpte_to_quarter_frames(smpte: &MtcFullFrame) -> [MtcQuarterFrame; 8] {
 /// - Frame rate type indicates 30fps non-drop frame
 /// - Resulting timecode values are within valid SMPTE ranges
 ///
-/// Limitiations:
-/// Does not allow duplicate frames (some maybe sent twice for redundancy, should be supported later)
+/// Same as [`quarter_frames_to_smpte_slice`] for exactly 8 frames, so a repeated
+/// piece leaves the cycle incomplete.
 ///
 /// # Arguments
 ///
@@ -196,13 +699,77 @@ pub fn smpte_to_quarter_frames(smpte: &MtcFullFrame) -> [MtcQuarterFrame; 8] {
 /// * `Ok(Mtc_full)` - Successfully reconstructed SMPTE timecode
 /// * `Err(MtcError)` - Invalid frame data or unsupported frame rate
 pub fn quarter_frames_to_smpte(frames: &[MtcQuarterFrame; 8]) -> Result<MtcFullFrame, MtcError> {
+    quarter_frames_to_smpte_slice(frames)
+}
+
+/// # Converts the quarter frames a receiver collected to SMPTE timecode.
+///
+/// Follows the duplicate rule of [`QuarterFrameAssembler`]: a piece repeating the previous
+/// one is a redundant retransmission and is dropped. The remaining pieces must
+/// be exactly one cycle of pieces 0-7, validated as by [`quarter_frames_to_smpte`]
+/// and in sequence from piece 0.
+///
+/// # Arguments
+///
+/// * `frames` - The quarter frames in the order they arrived
+///
+/// # Returns
+///
+/// * `Ok(MtcFullFrame)` - Successfully reconstructed SMPTE timecode
+/// * `Err(MtcError::IncompleteFrame)` - Fewer than 8 pieces once repeats are dropped
+/// * `Err(MtcError::TooManyFrames)` - More than 8 pieces once repeats are dropped
+/// * `Err(MtcError)` - Otherwise, invalid frame data or unsupported frame rate
+pub fn quarter_frames_to_smpte_slice(frames: &[MtcQuarterFrame]) -> Result<MtcFullFrame, MtcError> {
+    let mut cycle = [MtcQuarterFrame::default(); 8];
+    let mut count = 0;
+    let mut previous = None;
+    for frame in frames {
+        if previous == Some(frame) {
+            continue;
+        }
+        previous = Some(frame);
+        let slot = cycle.get_mut(count).ok_or(MtcError::TooManyFrames)?;
+        // Report pieces out of sequence even if the cycle is incomplete
+        if frame.frame_type != count as u8 {
+            return Err(MtcError::InvalidFrameType);
+        }
+        *slot = *frame;
+        count += 1;
+    }
+    if count < cycle.len() {
+        return Err(MtcError::IncompleteFrame);
+    }
+
+    match quarter_frames_to_smpte_rate(&cycle)? {
+        (smpte, FrameRate::Fps30) => Ok(smpte),
+        // Only 30fps non-drop (11 binary = 0x03) is supported
+        (_, rate) => Err(MtcError::UnsupportedFrameRate(rate.mtc_code())),
+    }
+}
+
+/// # Converts 8 MTC quarter frames to SMPTE timecode at the rate they signal.
+///
+/// Same validation as [`quarter_frames_to_smpte`], except that every frame rate is
+/// accepted and the frames field is checked against it.
+///
+/// # Arguments
+///
+/// * `frames` - Array of exactly 8 MTC quarter frames
+///
+/// # Returns
+///
+/// * `Ok((MtcFullFrame, FrameRate))` - The timecode and the rate from piece 7
+/// * `Err(MtcError)` - Invalid frame data
+pub fn quarter_frames_to_smpte_rate(
+    frames: &[MtcQuarterFrame; 8],
+) -> Result<(MtcFullFrame, FrameRate), MtcError> {
     // Validate frame types are in correct order (0-7) and values are within range
     for (i, frame) in frames.iter().enumerate() {
         if frame.frame_type != i as u8 {
             return Err(MtcError::InvalidFrameType);
         }
         if frame.value > 15 {
-            return Err(MtcError::InvalidValue);
+            return Err(invalid_piece(frame));
         }
     }
 
@@ -212,32 +779,279 @@ pub fn quarter_frames_to_smpte(frames: &[MtcQuarterFrame; 8]) -> Result<MtcFullF
     let minutes_val = frames[4].value | ((frames[5].value & 0x03) << 4);
     let hours_val = frames[6].value | ((frames[7].value & 0x01) << 4);
 
+    // Extract frame rate from frame 7 (bits 1-2); two bits always decode
+    let rate = FrameRate::from_mtc_code((frames[7].value >> 1) & 0x03).unwrap_or_default();
+
     // Validate SMPTE ranges
-    if frames_val > 29 {
-        // 30fps non-drop frame: frames 0-29
-        return Err(MtcError::InvalidValue);
+    if frames_val as u32 >= rate.nominal_fps() {
+        return Err(MtcError::InvalidFrames(frames_val));
     }
     if seconds_val > 59 {
-        return Err(MtcError::InvalidValue);
+        return Err(MtcError::InvalidSeconds(seconds_val));
     }
     if minutes_val > 59 {
-        return Err(MtcError::InvalidValue);
+        return Err(MtcError::InvalidMinutes(minutes_val));
     }
     if hours_val > 23 {
-        return Err(MtcError::InvalidValue);
+        return Err(MtcError::InvalidHours(hours_val));
     }
 
-    // Extract and validate frame rate from frame 7 (bits 1-2)
-    let frame_rate_type = (frames[7].value >> 1) & 0x03;
-    if frame_rate_type != 0x03 {
-        // Only 30fps non-drop (11 binary = 0x03) is supported
-        return Err(MtcError::InvalidValue);
+    Ok((
+        MtcFullFrame {
+            hours: hours_val,
+            minutes: minutes_val,
+            seconds: seconds_val,
+            frames: frames_val,
+        },
+        rate,
+    ))
+}
+
+/// Returns the error for a quarter frame whose value does not fit in 4 bits, named
+/// after the timecode field the piece carries.
+fn invalid_piece(frame: &MtcQuarterFrame) -> MtcError {
+    match frame.frame_type {
+        0 | 1 => MtcError::InvalidFrames(frame.value),
+        2 | 3 => MtcError::InvalidSeconds(frame.value),
+        4 | 5 => MtcError::InvalidMinutes(frame.value),
+        _ => MtcError::InvalidHours(frame.value),
+    }
+}
+
+/// # Stateful assembler for incoming MTC quarter frames.
+///
+/// Quarter frames arrive one at a time, so a receiver has to collect a full cycle
+/// of pieces 0-7 before the timecode can be reconstructed. The assembler keeps the
+/// pieces received so far and hands back the decoded timecode once piece 7 completes
+/// an uninterrupted forward cycle.
+///
+/// Rules:
+/// - Piece 0 always starts a new cycle, discarding any partial data
+/// - A piece repeating the previous one is ignored (redundant retransmission)
+/// - Any other out-of-sequence piece discards the partial cycle
+///
+/// The returned timecode is the one encoded in the cycle, without the two-frame
+/// transmission latency compensation some receivers apply. Cycles are accepted at
+/// any frame rate; [`QuarterFrameAssembler::frame_rate`] tells which one was signalled.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QuarterFrameAssembler {
+    /// Pieces collected for the cycle in progress, indexed by frame type
+    pieces: [MtcQuarterFrame; 8],
+    /// Number of consecutive pieces collected, starting at piece 0
+    received: u8,
+    /// Frame rate signalled by the last completed cycle
+    frame_rate: FrameRate,
+}
+
+impl QuarterFrameAssembler {
+    /// Creates an empty assembler waiting for piece 0.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// # Feeds one quarter frame into the assembler.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The received quarter frame
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(MtcFullFrame))` - The frame completed a cycle, holding the decoded timecode
+    /// * `Ok(None)` - More pieces are needed
+    /// * `Err(MtcError)` - The frame is out of range or the completed cycle is invalid
+    pub fn feed(&mut self, frame: MtcQuarterFrame) -> Result<Option<MtcFullFrame>, MtcError> {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            frame_type = frame.frame_type,
+            value = frame.value,
+            pending = self.received,
+            "quarter frame"
+        );
+        if frame.frame_type > 7 {
+            self.reset();
+            return Err(MtcError::InvalidFrameType);
+        }
+        if frame.value > 15 {
+            self.reset();
+            return Err(invalid_piece(&frame));
+        }
+
+        if frame.frame_type == 0 {
+            self.received = 0;
+        } else if self.received > 0 && self.pieces[self.received as usize - 1] == frame {
+            // Redundant copy of the previous piece
+            return Ok(None);
+        } else if frame.frame_type != self.received {
+            // Gap or reordering, wait for the next piece 0
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                expected = self.received,
+                frame_type = frame.frame_type,
+                "quarter-frame cycle broken"
+            );
+            self.reset();
+            return Ok(None);
+        }
+
+        self.pieces[frame.frame_type as usize] = frame;
+        self.received = frame.frame_type + 1;
+
+        if self.received < 8 {
+            return Ok(None);
+        }
+
+        self.reset();
+        let (smpte, rate) = quarter_frames_to_smpte_rate(&self.pieces)?;
+        #[cfg(feature = "tracing")]
+        tracing::trace!(timecode = ?smpte, rate = ?rate, "quarter-frame cycle assembled");
+        self.frame_rate = rate;
+        Ok(Some(smpte))
     }
 
-    Ok(MtcFullFrame {
-        hours: hours_val,
-        minutes: minutes_val,
-        seconds: seconds_val,
-        frames: frames_val,
-    })
+    /// Discards any partially assembled cycle.
+    pub fn reset(&mut self) {
+        self.received = 0;
+    }
+
+    /// Returns the number of consecutive pieces collected for the current cycle.
+    pub fn pending(&self) -> u8 {
+        self.received
+    }
+
+    /// Returns the frame rate signalled by the last completed cycle, 30fps until
+    /// one completes.
+    pub fn frame_rate(&self) -> FrameRate {
+        self.frame_rate
+    }
+}
+
+/// Change in master minus local time, in microseconds, between two observations
+/// that [`DriftEstimator::new`] takes as a Locate or another jump of the master.
+pub const DRIFT_DISCONTINUITY_US: VlcTickT = 100_000;
+
+/// # Estimates the rate and offset of the master clock against the local one.
+///
+/// Fits a line through the offset of the decoded master position from the local
+/// arrival time, plotted against the arrival time, so network jitter averages out
+/// and the slope is the master's rate error. Older observations fade out
+/// exponentially: each new one scales the weight of the previous ones by
+/// `1 - 1/window`, which keeps memory constant without a buffer.
+///
+/// An offset jumping by more than the discontinuity threshold, or local time going
+/// backwards, starts the estimate over, since the master has been relocated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DriftEstimator {
+    /// Weight kept by past observations at each new one
+    decay: f64,
+    discontinuity_us: VlcTickT,
+    /// Arrival time and offset of the first observation since the last reset,
+    /// subtracted from later ones to keep the sums small
+    origin: Option<(VlcTickT, i64)>,
+    /// Arrival time and offset of the latest observation
+    last: Option<(VlcTickT, i64)>,
+    samples: u32,
+    weight: f64,
+    mean_x: f64,
+    mean_y: f64,
+    cov_xx: f64,
+    cov_xy: f64,
+}
+
+impl DriftEstimator {
+    /// Creates an estimator averaging over about `window` observations, with the
+    /// [`DRIFT_DISCONTINUITY_US`] threshold.
+    pub fn new(window: u32) -> Self {
+        Self::with_discontinuity(window, DRIFT_DISCONTINUITY_US)
+    }
+
+    /// Creates an estimator averaging over about `window` observations, starting
+    /// over when the offset jumps by more than `discontinuity_us`.
+    pub fn with_discontinuity(window: u32, discontinuity_us: VlcTickT) -> Self {
+        Self {
+            decay: 1.0 - 1.0 / window.max(1) as f64,
+            discontinuity_us,
+            origin: None,
+            last: None,
+            samples: 0,
+            weight: 0.0,
+            mean_x: 0.0,
+            mean_y: 0.0,
+            cov_xx: 0.0,
+            cov_xy: 0.0,
+        }
+    }
+
+    /// # Adds a decoded master position and the local time it arrived at.
+    ///
+    /// # Arguments
+    ///
+    /// * `local_arrival_us` - Local monotonic time at which the position arrived
+    /// * `decoded_master_us` - Master position it carried, such as a completed
+    ///   quarter-frame cycle converted with [`smpte_to_us_rate`]
+    ///
+    /// # Returns
+    ///
+    /// `true` if the observation was a discontinuity and the estimate started over.
+    pub fn observe(&mut self, local_arrival_us: VlcTickT, decoded_master_us: VlcTickT) -> bool {
+        let offset = decoded_master_us as i64 - local_arrival_us as i64;
+        let discontinuity = self.last.is_some_and(|(last_us, last_offset)| {
+            local_arrival_us < last_us || offset.abs_diff(last_offset) > self.discontinuity_us
+        });
+        if discontinuity {
+            self.reset();
+        }
+
+        // Welford's update with exponential forgetting
+        let (origin_us, origin_offset) = *self.origin.get_or_insert((local_arrival_us, offset));
+        let x = (local_arrival_us - origin_us) as f64;
+        let y = (offset - origin_offset) as f64;
+        self.weight = self.decay * self.weight + 1.0;
+        let dx = x - self.mean_x;
+        self.mean_x += dx / self.weight;
+        self.mean_y += (y - self.mean_y) / self.weight;
+        self.cov_xx = self.decay * self.cov_xx + dx * (x - self.mean_x);
+        self.cov_xy = self.decay * self.cov_xy + dx * (y - self.mean_y);
+
+        self.last = Some((local_arrival_us, offset));
+        self.samples = self.samples.saturating_add(1);
+        discontinuity
+    }
+
+    /// Returns how much faster the master clock runs than the local one, in parts
+    /// per million, or `None` until two observations at different times arrived.
+    pub fn rate_ppm(&self) -> Option<f64> {
+        self.slope().map(|slope| slope * 1e6)
+    }
+
+    /// Returns the master position minus the local time at the latest observation,
+    /// in microseconds, as the fitted line puts it, or `None` before the first one.
+    pub fn offset_us(&self) -> Option<i64> {
+        let (origin_us, origin_offset) = self.origin?;
+        let (last_us, _) = self.last?;
+        let x = (last_us - origin_us) as f64;
+        let y = self.mean_y + self.slope().unwrap_or(0.0) * (x - self.mean_x);
+        // Rounded to the nearest without `f64::round`, which `no_std` lacks
+        let rounded = if y < 0.0 { y - 0.5 } else { y + 0.5 };
+        Some(origin_offset + rounded as i64)
+    }
+
+    /// Returns the number of observations since the estimate last started over,
+    /// saturating at `u32::MAX`.
+    pub fn samples(&self) -> u32 {
+        self.samples
+    }
+
+    /// Forgets every observation, keeping the window and threshold.
+    pub fn reset(&mut self) {
+        *self = Self {
+            decay: self.decay,
+            discontinuity_us: self.discontinuity_us,
+            ..Self::with_discontinuity(1, 0)
+        };
+    }
+
+    fn slope(&self) -> Option<f64> {
+        (self.samples >= 2 && self.cov_xx > 0.0).then(|| self.cov_xy / self.cov_xx)
+    }
 }
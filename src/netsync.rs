@@ -11,13 +11,94 @@
 //! - Full-Frame MTC
 //! - MMC (MIDI Machine Control) Start/Stop commands
 //! - MMC Locate commands
+//! - MIDI Beat Clock (Clock/Start/Continue/Stop) and Song Position Pointer
+//! - Identity Request/Reply, for discovering the devices on a link
 
-use crate::error::NetsyncError;
+#[cfg(feature = "alloc")]
+mod beat_clock;
+#[cfg(feature = "alloc")]
+mod config;
+#[cfg(feature = "alloc")]
+mod describe;
+#[cfg(feature = "alloc")]
+mod endpoint;
+#[cfg(feature = "alloc")]
+mod feedback;
+#[cfg(feature = "alloc")]
+mod identity;
+mod metrics;
+#[cfg(feature = "std")]
+pub mod net;
+#[cfg(feature = "heapless")]
+mod payload;
+#[cfg(feature = "alloc")]
+mod policy;
+#[cfg(feature = "alloc")]
+mod session;
+#[cfg(feature = "alloc")]
+mod stream;
+#[cfg(feature = "alloc")]
+mod tracker;
+#[cfg(feature = "alloc")]
+mod verify;
+
+#[cfg(feature = "alloc")]
+pub use beat_clock::BeatClockTracker;
+#[cfg(feature = "alloc")]
+pub use config::{BuildOptions, NetsyncConfig};
+#[cfg(feature = "alloc")]
+pub use describe::describe_payload;
+#[cfg(feature = "alloc")]
+pub use endpoint::Endpoint;
+#[cfg(feature = "alloc")]
+pub use feedback::SlaveFeedback;
+pub use metrics::Metrics;
+#[cfg(feature = "heapless")]
+pub use payload::Payload;
+#[cfg(feature = "alloc")]
+pub use policy::{slave_netsync_flow_with_policy, ParsePolicy};
+#[cfg(feature = "alloc")]
+pub use session::{
+    FullFrameRefreshPolicy, MasterSession, NetsyncHandler, PacketStatus, SlaveSession,
+    MAX_PACKET_LENGTH, MAX_TICK_PACKETS,
+};
+#[cfg(feature = "alloc")]
+pub use stream::StreamParser;
+#[cfg(feature = "alloc")]
+pub use tracker::{PositionTracker, SyncState, TrackerConfig};
+#[cfg(feature = "alloc")]
+pub use verify::{verify_payload, Finding, FindingKind, Report, Severity};
+
+#[cfg(feature = "alloc")]
+pub(crate) use policy::parse_payload_with_policy;
+
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use crate::error::{Error, FlowError};
+use crate::error::{NetsyncError, ParseError};
 use crate::header::PayloadHeader;
-use crate::midi::{build_midi_list, parse_midi_list, MidiEvent, MmcCommand};
+#[cfg(feature = "alloc")]
 use crate::midi::{
-    MMC_LOCATE_LENGTH, MMC_START_STOP_LENGTH, MTC_FULL_FRAME_LENGTH, MTC_QUARTER_FRAME_LENGTH,
+    build_midi_list, parse_midi_list, realtime_sysex_device_id, set_realtime_sysex_device_id,
+    MidiEvent, SYSEX_DEVICE_ID_BROADCAST,
 };
+use crate::midi::{
+    encode_midi, parse_midi_list_ref, peek_midi_kind, MessageKind, MidiEventRef, MmcCommand,
+    MMC_LOCATE_LENGTH, MTC_QUARTER_FRAME_LENGTH,
+};
+#[cfg(feature = "heapless")]
+use crate::mtc::smpte_to_quarter_frames;
+#[cfg(feature = "alloc")]
+use crate::mtc::{
+    quarter_frame_at, smpte_to_quarter_frames_rate, smpte_to_us_rate, us_to_smpte_rate,
+    MtcQuarterFrame, QuarterFrameAssembler, VlcTickT,
+};
+#[cfg(any(feature = "alloc", feature = "heapless"))]
+use crate::mtc::{FrameRate, MtcFullFrame};
 
 /// Converts a MIDI synchronization event into a network payload for master transmission.
 ///
@@ -28,7 +109,7 @@ use crate::midi::{
 /// # Arguments
 ///
 /// * `event` - The MIDI synchronization event to convert. Must be a valid sync event
-///             (MTC or MMC), not `MidiEvent::Other`.
+///   (MTC, MMC or beat clock), not a pass-through message such as `MidiEvent::Other`.
 ///
 /// # Returns
 ///
@@ -37,33 +118,552 @@ use crate::midi::{
 /// # Errors
 ///
 /// * `NetsyncError::InvalidMasterEvent` - If the event is `MidiEvent::Other` or
-///   another unsupported event type for master synchronization.
+///   another unsupported event type for master synchronization, or an MMC Masked
+///   Write with a byte above 0x7F.
+#[cfg(feature = "alloc")]
 pub fn master_netsync_flow(event: &MidiEvent) -> Result<Vec<u8>, NetsyncError> {
-    // Determine header length based on event type
-    let header_len = match event {
-        MidiEvent::Mmc(MmcCommand::Play | MmcCommand::Stop) => MMC_START_STOP_LENGTH,
-        MidiEvent::Mmc(MmcCommand::Locate { .. }) => MMC_LOCATE_LENGTH,
-        MidiEvent::MtcFull { .. } => MTC_FULL_FRAME_LENGTH,
-        MidiEvent::MtcQuarter { .. } => MTC_QUARTER_FRAME_LENGTH,
-        MidiEvent::Other(_) => return Err(NetsyncError::InvalidMasterEvent),
+    #[cfg(feature = "tracing")]
+    let _span = tracing::trace_span!("master_netsync_flow", kind = ?event.kind()).entered();
+
+    // Built on the stack, then copied once into an exactly sized vector
+    let mut buf = [0; MAX_PAYLOAD_LENGTH];
+    let result = master_netsync_flow_ref(&event.as_event_ref(), &mut buf);
+    #[cfg(feature = "tracing")]
+    match result {
+        Ok(len) => tracing::trace!(len, "payload built"),
+        Err(err) => tracing::debug!(error = ?err, "event not sent"),
+    }
+    Ok(buf[..result?].to_vec())
+}
+
+/// Appends the network payload for a MIDI synchronization event to `out`.
+///
+/// Same as [`master_netsync_flow`], without allocating when `out` already has
+/// room for the payload.
+///
+/// # Arguments
+///
+/// * `event` - The MIDI synchronization event to convert.
+/// * `out` - Buffer the payload is appended to. Left unchanged on error.
+///
+/// # Errors
+///
+/// * `NetsyncError::InvalidMasterEvent` - If the event is `MidiEvent::Other` or
+///   another unsupported event type for master synchronization.
+#[cfg(feature = "alloc")]
+pub fn master_netsync_flow_into(event: &MidiEvent, out: &mut Vec<u8>) -> Result<(), NetsyncError> {
+    let mut buf = [0; MAX_PAYLOAD_LENGTH];
+    let len = master_netsync_flow_ref(&event.as_event_ref(), &mut buf)?;
+    out.extend_from_slice(&buf[..len]);
+    Ok(())
+}
+
+/// Converts a MIDI synchronization event into a network payload laid out as
+/// `options` asks.
+///
+/// Same as [`master_netsync_flow`], with the padding of
+/// [`BuildOptions::quarter_frame_padding`] after MTC Quarter Frames.
+///
+/// # Errors
+///
+/// * `NetsyncError::InvalidMasterEvent` - If `master_netsync_flow` rejects the
+///   event, or if it is a quarter frame and the padding is above
+///   [`MAX_QUARTER_FRAME_PADDING`].
+#[cfg(feature = "alloc")]
+pub fn master_netsync_flow_with(
+    event: &MidiEvent,
+    options: &BuildOptions,
+) -> Result<Vec<u8>, NetsyncError> {
+    let mut payload = Vec::new();
+    master_netsync_flow_into_with(event, options, &mut payload)?;
+    Ok(payload)
+}
+
+/// Same as [`master_netsync_flow_into`], laid out as `options` asks.
+#[cfg(feature = "alloc")]
+pub(crate) fn master_netsync_flow_into_with(
+    event: &MidiEvent,
+    options: &BuildOptions,
+    out: &mut Vec<u8>,
+) -> Result<(), NetsyncError> {
+    let mut buf = [0; MAX_PAYLOAD_LENGTH];
+    let len = write_payload(
+        &event.as_event_ref(),
+        options.quarter_frame_padding,
+        &mut buf,
+    )?;
+    out.extend_from_slice(&buf[..len]);
+    Ok(())
+}
+
+/// Returns the length of the MIDI list the master flow sends for `event` laid out
+/// as `options` asks, padding included.
+///
+/// # Errors
+///
+/// * `NetsyncError::InvalidMasterEvent` - As for [`master_netsync_flow_with`].
+#[cfg(feature = "alloc")]
+pub(crate) fn master_midi_list_len_with(
+    event: &MidiEvent,
+    options: &BuildOptions,
+) -> Result<usize, NetsyncError> {
+    let event = event.as_event_ref();
+    Ok(midi_list_len(&event)? + quarter_frame_padding(&event, options.quarter_frame_padding)?)
+}
+
+/// Writes the network payload for a MIDI synchronization event to `out`.
+///
+/// Same as [`master_netsync_flow`], without allocating.
+///
+/// # Arguments
+///
+/// * `event` - The MIDI synchronization event to convert.
+/// * `out` - Buffer the payload is written to, from its start.
+///
+/// # Returns
+///
+/// The length of the payload, as [`master_payload_len`] returns it.
+///
+/// # Errors
+///
+/// * `NetsyncError::InvalidMasterEvent` - If the event is `MidiEvent::Other` or
+///   another unsupported event type for master synchronization.
+///
+/// # Panics
+///
+/// If `out` is shorter than [`master_payload_len`] for `event`.
+#[cfg(feature = "alloc")]
+pub fn master_netsync_flow_slice(event: &MidiEvent, out: &mut [u8]) -> Result<usize, NetsyncError> {
+    master_netsync_flow_ref(&event.as_event_ref(), out)
+}
+
+/// Converts a MIDI synchronization event into a fixed-capacity [`Payload`].
+///
+/// Same as [`master_netsync_flow`], without allocating: the payload is returned
+/// by value, for threads where heap use is forbidden.
+///
+/// # Errors
+///
+/// * `NetsyncError::InvalidMasterEvent` - If the event is `MidiEvent::Other`,
+///   whatever its length. Pass-through messages are never truncated to fit.
+#[cfg(all(feature = "heapless", feature = "alloc"))]
+pub fn master_netsync_flow_fixed(event: &MidiEvent) -> Result<Payload, NetsyncError> {
+    let mut payload = Payload {
+        buf: [0; MAX_PAYLOAD_LENGTH],
+        len: 0,
     };
+    payload.len = master_netsync_flow_ref(&event.as_event_ref(), &mut payload.buf)?;
+    Ok(payload)
+}
+
+/// Writes the network payload for a borrowed MIDI synchronization event to `out`.
+///
+/// Same as [`master_netsync_flow_slice`], for a [`MidiEventRef`]. Needs neither
+/// `std` nor `alloc`.
+///
+/// # Returns
+///
+/// The length of the payload, at most [`MAX_PAYLOAD_LENGTH`].
+///
+/// # Errors
+///
+/// * `NetsyncError::InvalidMasterEvent` - If the event is `MidiEventRef::Other`.
+///
+/// # Panics
+///
+/// If `out` is shorter than the payload.
+pub fn master_netsync_flow_ref(
+    event: &MidiEventRef<'_>,
+    out: &mut [u8],
+) -> Result<usize, NetsyncError> {
+    write_payload(event, 0, out)
+}
+
+/// Writes the payload for `event` to `out`, with `padding` zero bytes after an MTC
+/// Quarter Frame counted in the header LEN. Returns the payload length.
+fn write_payload(
+    event: &MidiEventRef<'_>,
+    padding: u8,
+    out: &mut [u8],
+) -> Result<usize, NetsyncError> {
+    let message_len = midi_list_len(event)?;
+    let len = message_len + quarter_frame_padding(event, padding)?;
+
+    let header = PayloadHeader::new(0x0, len as u8).serialize();
+    out[..header.len()].copy_from_slice(&header);
+    // Pass-through messages were rejected above, so the message is written in place
+    encode_midi(event, &mut out[header.len()..]);
+    out[header.len() + message_len..header.len() + len].fill(0);
+
+    Ok(header.len() + len)
+}
+
+/// Returns the padding bytes that follow `event`: `padding` for an MTC Quarter
+/// Frame, none for any other message.
+///
+/// # Errors
+///
+/// * `NetsyncError::InvalidMasterEvent` - If `event` is a quarter frame and
+///   `padding` is above [`MAX_QUARTER_FRAME_PADDING`].
+fn quarter_frame_padding(event: &MidiEventRef<'_>, padding: u8) -> Result<usize, NetsyncError> {
+    match event {
+        MidiEventRef::MtcQuarter { .. } if padding > MAX_QUARTER_FRAME_PADDING => {
+            Err(NetsyncError::InvalidMasterEvent)
+        }
+        MidiEventRef::MtcQuarter { .. } => Ok(padding.into()),
+        _ => Ok(0),
+    }
+}
+
+/// Converts a timecode into the payloads of its eight MTC Quarter Frames.
+///
+/// Splits `tc` with [`smpte_to_quarter_frames`] and wraps each piece as
+/// [`master_netsync_flow`] would, returning the payloads in transmission order,
+/// piece 0 first. A slave assembling the eight payloads recovers `tc`.
+///
+/// # Errors
+///
+/// * `NetsyncError::InvalidMasterEvent` - If `tc` does not exist at 30fps, as its
+///   out-of-range fields would be truncated by the quarter frames.
+#[cfg(feature = "heapless")]
+pub fn quarter_frame_payloads(tc: &MtcFullFrame) -> Result<[Payload; 8], NetsyncError> {
+    check_timecode(tc)?;
+    let mut payloads = [Payload {
+        buf: [0; MAX_PAYLOAD_LENGTH],
+        len: 0,
+    }; 8];
+    for (payload, qf) in payloads.iter_mut().zip(smpte_to_quarter_frames(tc)) {
+        let event = MidiEventRef::MtcQuarter {
+            msg_type: qf.frame_type,
+            value: qf.value,
+        };
+        payload.len = master_netsync_flow_ref(&event, &mut payload.buf)?;
+    }
+    Ok(payloads)
+}
+
+/// Rejects a timecode with a field out of range at every frame rate, which the
+/// master flow would otherwise send truncated.
+#[cfg(any(feature = "alloc", feature = "heapless"))]
+fn check_timecode(tc: &MtcFullFrame) -> Result<(), NetsyncError> {
+    if tc.is_valid_for(FrameRate::Fps30) {
+        Ok(())
+    } else {
+        Err(NetsyncError::InvalidMasterEvent)
+    }
+}
+
+/// Returns the length of the MIDI list the master flow sends for `event`, which
+/// is also the LEN of its payload header.
+///
+/// # Errors
+///
+/// * `NetsyncError::InvalidMasterEvent` - If the event is `MidiEvent::Other`.
+#[cfg(feature = "alloc")]
+pub fn master_midi_list_len(event: &MidiEvent) -> Result<usize, NetsyncError> {
+    midi_list_len(&event.as_event_ref())
+}
+
+fn midi_list_len(event: &MidiEventRef<'_>) -> Result<usize, NetsyncError> {
+    // Masked Write bytes are sent as given, so one above 0x7F would break the SysEx
+    if let MidiEventRef::Mmc(MmcCommand::MaskedWrite {
+        field,
+        start,
+        mask,
+        data,
+    }) = event
+    {
+        if (field | start | mask | data) & 0x80 != 0 {
+            return Err(NetsyncError::InvalidMasterEvent);
+        }
+    }
+    event
+        .kind()
+        .wire_len()
+        .ok_or(NetsyncError::InvalidMasterEvent)
+}
 
-    // Build MIDI data
-    let midi_data = build_midi_list(event);
+/// Returns the exact length of the payload [`master_netsync_flow`] produces for
+/// `event`, header included.
+///
+/// # Errors
+///
+/// * `NetsyncError::InvalidMasterEvent` - If the event is `MidiEvent::Other`.
+#[cfg(feature = "alloc")]
+pub fn master_payload_len(event: &MidiEvent) -> Result<usize, NetsyncError> {
+    // The single-event flow always uses the one-octet header
+    Ok(1 + master_midi_list_len(event)?)
+}
 
-    // Create payload with proper capacity
-    let mut payload = Vec::with_capacity(1 + midi_data.len());
+/// Longest payload [`master_netsync_flow`] produces: the header and the longest
+/// supported MIDI message (MMC Locate).
+pub const MAX_PAYLOAD_LENGTH: usize = 1 + MMC_LOCATE_LENGTH;
 
-    // Add header (currently always 1 byte)
-    let header = PayloadHeader::new(0x0, header_len as u8);
-    payload.extend_from_slice(&header.serialize());
+/// Most padding bytes [`BuildOptions::quarter_frame_padding`] allows: a padded
+/// quarter frame still fits in [`MAX_PAYLOAD_LENGTH`].
+pub const MAX_QUARTER_FRAME_PADDING: u8 = (MAX_PAYLOAD_LENGTH - 1 - MTC_QUARTER_FRAME_LENGTH) as u8;
 
-    // Add MIDI data
+/// Longest MIDI list a payload can carry, with the two-octet header's 12-bit LEN.
+pub const MAX_MIDI_LIST_LENGTH: usize = 0x0FFF;
+
+/// Converts several MIDI synchronization events into a single network payload.
+///
+/// The events' MIDI messages are concatenated into one MIDI list, in order. The
+/// one-octet header is used while the list fits in its 4-bit LEN; longer lists use
+/// the two-octet form (B flag set, 12-bit LEN).
+///
+/// # Arguments
+///
+/// * `events` - The events to convert, none of them pass-through messages such as
+///   `MidiEvent::Other`.
+///
+/// # Returns
+///
+/// Returns a `Vec<u8>` containing the complete network payload on success.
+///
+/// # Errors
+///
+/// * `NetsyncError::InvalidMasterEvent` - If `events` is empty or contains
+///   a pass-through message or an event [`master_netsync_flow`] rejects, or if
+///   the MIDI list exceeds [`MAX_MIDI_LIST_LENGTH`].
+#[cfg(feature = "alloc")]
+pub fn master_netsync_flow_multi(events: &[MidiEvent]) -> Result<Vec<u8>, NetsyncError> {
+    const B_FLAG: u8 = 0x80;
+    const SHORT_HEADER_MAX_LEN: usize = 0x0F;
+
+    if events.is_empty()
+        || events
+            .iter()
+            .any(|e| !e.kind().is_sync() || midi_list_len(&e.as_event_ref()).is_err())
+    {
+        return Err(NetsyncError::InvalidMasterEvent);
+    }
+
+    let midi_data: Vec<u8> = events.iter().flat_map(build_midi_list).collect();
+    let len = midi_data.len();
+    if len > MAX_MIDI_LIST_LENGTH {
+        return Err(NetsyncError::InvalidMasterEvent);
+    }
+
+    let mut payload = Vec::with_capacity(2 + len);
+    if len <= SHORT_HEADER_MAX_LEN {
+        payload.extend_from_slice(&PayloadHeader::new(0x0, len as u8).serialize());
+    } else {
+        payload.extend_from_slice(&[B_FLAG | (len >> 8) as u8, len as u8]);
+    }
     payload.extend_from_slice(&midi_data);
 
     Ok(payload)
 }
 
+/// Returns the events that start slaves rolling from `start`: a Full Frame
+/// locating them, immediately followed by MMC Play.
+#[cfg(feature = "alloc")]
+pub(crate) fn sync_burst_events(start: &MtcFullFrame) -> Result<[MidiEvent; 2], NetsyncError> {
+    check_timecode(start)?;
+    Ok([
+        MidiEvent::MtcFull {
+            hour: start.hours,
+            minute: start.minutes,
+            second: start.seconds,
+            frame: start.frames,
+        },
+        MidiEvent::Mmc(MmcCommand::Play),
+    ])
+}
+
+/// Converts the start of playback at `start` into network payloads.
+///
+/// Slaves need an absolute position immediately followed by Play to start in sync,
+/// so the payloads are a Full Frame then an MMC Play, to be sent in order and
+/// back to back. [`sync_burst_multi`] carries both in a single payload instead.
+///
+/// # Errors
+///
+/// * `NetsyncError::InvalidMasterEvent` - If a field of `start` is out of range.
+#[cfg(feature = "alloc")]
+pub fn sync_burst(start: &MtcFullFrame) -> Result<Vec<Vec<u8>>, NetsyncError> {
+    sync_burst_events(start)?
+        .iter()
+        .map(master_netsync_flow)
+        .collect()
+}
+
+/// Converts the start of playback at `start` into a single network payload.
+///
+/// Same as [`sync_burst`], with the Full Frame and the MMC Play in one MIDI list,
+/// as [`master_netsync_flow_multi`] builds it, so they cannot be reordered or
+/// separated on the way.
+///
+/// # Errors
+///
+/// * `NetsyncError::InvalidMasterEvent` - If a field of `start` is out of range.
+#[cfg(feature = "alloc")]
+pub fn sync_burst_multi(start: &MtcFullFrame) -> Result<Vec<u8>, NetsyncError> {
+    master_netsync_flow_multi(&sync_burst_events(start)?)
+}
+
+/// # Which messages [`master_time_update`] sends for a position.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeUpdateKind {
+    /// A Full Frame with the timecode at the position
+    FullFrame,
+    /// The quarter frame due at the position, as [`quarter_frame_at`] picks it.
+    /// [`MasterSession::time_update`] sends the next piece of its cycle instead.
+    QuarterFrame,
+    /// The eight quarter frames of a cycle carrying the timecode at the position
+    QuarterFrameBurst,
+}
+
+/// Returns the events [`master_time_update`] sends for `us` at `rate`.
+#[cfg(feature = "alloc")]
+pub(crate) fn time_update_events(
+    us: VlcTickT,
+    kind: TimeUpdateKind,
+    rate: FrameRate,
+) -> Vec<MidiEvent> {
+    let quarter = |qf: MtcQuarterFrame| MidiEvent::MtcQuarter {
+        msg_type: qf.frame_type,
+        value: qf.value,
+    };
+    match kind {
+        TimeUpdateKind::FullFrame => {
+            let smpte = us_to_smpte_rate(us, rate);
+            vec![MidiEvent::MtcFull {
+                hour: smpte.hours,
+                minute: smpte.minutes,
+                second: smpte.seconds,
+                frame: smpte.frames,
+            }]
+        }
+        TimeUpdateKind::QuarterFrame => vec![quarter(quarter_frame_at(us, rate).1)],
+        TimeUpdateKind::QuarterFrameBurst => {
+            smpte_to_quarter_frames_rate(&us_to_smpte_rate(us, rate), rate)
+                .into_iter()
+                .map(quarter)
+                .collect()
+        }
+    }
+}
+
+/// Converts a position in microseconds into the payloads that send it to slaves.
+///
+/// Saves masters driven by a microsecond clock the conversion to a timecode, at
+/// 30fps as [`us_to_smpte`](crate::mtc::us_to_smpte) does it; positions wrap past 24 hours.
+///
+/// # Arguments
+///
+/// * `us` - Position of the master, in microseconds
+/// * `kind` - Full Frame, the single quarter frame due at `us`, or a whole cycle
+///
+/// # Returns
+///
+/// The payloads to transmit in order: one, or eight for a quarter-frame burst.
+///
+/// # Errors
+///
+/// * `NetsyncError::InvalidMasterEvent` - If the master flow rejects a message,
+///   which the timecodes built from `us` never cause.
+#[cfg(feature = "alloc")]
+pub fn master_time_update(
+    us: VlcTickT,
+    kind: TimeUpdateKind,
+) -> Result<Vec<Vec<u8>>, NetsyncError> {
+    time_update_events(us, kind, FrameRate::Fps30)
+        .iter()
+        .map(master_netsync_flow)
+        .collect()
+}
+
+/// # Timing of the sequence [`cue_and_play`] builds.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct CueOptions {
+    /// Time to wait between the Locate and the Deferred Play, for slaves that take
+    /// a while to locate. `0` sends them back to back.
+    pub settle_us: VlcTickT,
+}
+
+/// Returns the events that cue slaves to `target` and start them once they are
+/// there: a Locate, followed by MMC Deferred Play.
+#[cfg(feature = "alloc")]
+pub(crate) fn cue_and_play_events(target: &MtcFullFrame) -> Result<[MidiEvent; 2], NetsyncError> {
+    check_timecode(target)?;
+    Ok([
+        MidiEvent::Mmc(MmcCommand::Locate {
+            hour: target.hours,
+            minute: target.minutes,
+            second: target.seconds,
+            frame: target.frames,
+            subframe: 0,
+        }),
+        MidiEvent::Mmc(MmcCommand::DeferredPlay),
+    ])
+}
+
+/// Converts cueing playback at `target` into network payloads.
+///
+/// The payloads are a Locate then an MMC Deferred Play, so that slaves start
+/// rolling from `target` once they have located rather than from wherever they
+/// are when the command arrives.
+///
+/// # Returns
+///
+/// The payloads to transmit in order, each with the time to send it at in
+/// microseconds after the first: the Deferred Play follows `options.settle_us`
+/// after the Locate.
+///
+/// # Errors
+///
+/// * `NetsyncError::InvalidMasterEvent` - If a field of `target` is out of range.
+#[cfg(feature = "alloc")]
+pub fn cue_and_play(
+    target: &MtcFullFrame,
+    options: CueOptions,
+) -> Result<Vec<(VlcTickT, Vec<u8>)>, NetsyncError> {
+    let [locate, deferred_play] = cue_and_play_events(target)?;
+    Ok(vec![
+        (0, master_netsync_flow(&locate)?),
+        (options.settle_us, master_netsync_flow(&deferred_play)?),
+    ])
+}
+
+/// Converts a MIDI synchronization event to a network payload addressed to one
+/// device.
+///
+/// Same as [`master_netsync_flow`], except that MTC Full Frames and MMC commands
+/// are addressed to `device_id` rather than broadcast. Other events carry no
+/// device ID and are built unchanged.
+///
+/// # Arguments
+///
+/// * `event` - The MIDI synchronization event to convert.
+/// * `device_id` - The target device, `SYSEX_DEVICE_ID_BROADCAST` (0x7F) for all.
+///
+/// # Errors
+///
+/// * `NetsyncError::InvalidMasterEvent` - If `master_netsync_flow` rejects the
+///   event, or if `device_id` is above 0x7F.
+#[cfg(feature = "alloc")]
+pub fn master_netsync_flow_to_device(
+    event: &MidiEvent,
+    device_id: u8,
+) -> Result<Vec<u8>, NetsyncError> {
+    if device_id > SYSEX_DEVICE_ID_BROADCAST {
+        return Err(NetsyncError::InvalidMasterEvent);
+    }
+    let mut payload = master_netsync_flow(event)?;
+    // The master flow always uses the one-octet header
+    set_realtime_sysex_device_id(&mut payload[1..], device_id);
+    Ok(payload)
+}
+
 /// Reconstructs a MIDI synchronization event from a received network payload.
 ///
 /// This function parses a network payload received from a master device and
@@ -73,7 +673,7 @@ pub fn master_netsync_flow(event: &MidiEvent) -> Result<Vec<u8>, NetsyncError> {
 /// # Arguments
 ///
 /// * `buf` - The received network payload bytes. Must contain at least a header
-///           and the corresponding MIDI data.
+///   and the corresponding MIDI data.
 ///
 /// # Returns
 ///
@@ -81,27 +681,564 @@ pub fn master_netsync_flow(event: &MidiEvent) -> Result<Vec<u8>, NetsyncError> {
 ///
 /// # Errors
 ///
-/// * `NetsyncError::InvalidSlaveEvent` - If the buffer is too small to contain
-///   a valid payload (less than 3 bytes for the smallest valid message).
-/// * `ParseError::InsufficientHeaderData` - If the buffer doesn't contain enough
-///   data to parse the header.
-/// * `ParseError::BufferTooSmall` - If the buffer is smaller than the length
-///   specified in the header.
-/// * `ParseError::InvalidMidiData` - If the MIDI data in the payload is malformed.
+/// * `NetsyncError::TooShort` - If the buffer is too small to contain a valid
+///   payload (less than 2 bytes for the smallest valid message).
+/// * `NetsyncError::UnsupportedFlags` - If the header sets the Z (delta time) or
+///   P (phantom running status) flag.
+/// * `NetsyncError::TrailingData` - If bytes follow the MIDI list of LEN bytes and
+///   the header has no J flag announcing a journal.
+/// * `NetsyncError::DeclaredLengthExceedsBuffer` - If the buffer is smaller than
+///   the length specified in the header.
+/// * `NetsyncError::InvalidSlaveEvent` - If LEN is 0 or the MIDI data in the
+///   payload is malformed.
 /// ```
+#[cfg(feature = "alloc")]
 pub fn slave_netsync_flow(buf: &[u8]) -> Result<MidiEvent, NetsyncError> {
-    // Check minimum payload size (1 byte header + 2 bytes MTC Quarter Frame = 3 bytes minimum)
-    const MIN_PAYLOAD_SIZE: usize = 3;
-    if buf.len() < MIN_PAYLOAD_SIZE {
-        return Err(NetsyncError::InvalidSlaveEvent);
+    #[cfg(feature = "tracing")]
+    let _span = tracing::trace_span!("slave_netsync_flow", len = buf.len()).entered();
+
+    let result = parse_payload(buf).map_err(NetsyncError::from);
+    #[cfg(feature = "tracing")]
+    match &result {
+        Ok(event) => tracing::trace!(kind = ?event.kind(), "payload decoded"),
+        Err(err) => tracing::debug!(error = ?err, "payload rejected"),
+    }
+    result
+}
+
+/// Reconstructs a MIDI synchronization event, keeping the reason for a rejection.
+///
+/// Same as [`slave_netsync_flow`], which accepts and rejects exactly the same
+/// payloads, but failures are returned as an [`Error`] whose `source()` chain ends
+/// at the [`ParseError`] behind the rejection, when there is one.
+///
+/// # Arguments
+///
+/// * `buf` - The received network payload bytes.
+///
+/// # Errors
+///
+/// * `Error::NetsyncError` - Whenever `slave_netsync_flow` would fail, holding its
+///   `NetsyncError` and the cause of the rejection.
+#[cfg(feature = "alloc")]
+pub fn slave_netsync_flow_detailed(buf: &[u8]) -> Result<MidiEvent, Error> {
+    slave_netsync_flow(buf).map_err(|kind| {
+        Error::from(FlowError {
+            kind,
+            cause: rejection_cause(buf),
+        })
+    })
+}
+
+/// Returns the parse error explaining why the slave flow rejects `buf`.
+///
+/// `None` when the MIDI list itself parses and only the framing is at fault.
+#[cfg(feature = "alloc")]
+pub(crate) fn rejection_cause(buf: &[u8]) -> Option<ParseError> {
+    match read_header(buf) {
+        None if buf.is_empty() => Some(ParseError::EmptyBuffer),
+        None => Some(ParseError::InsufficientHeaderData),
+        Some((start, len)) => parse_midi_list_ref(&buf[start..], len).err(),
+    }
+}
+
+/// Reconstructs a MIDI synchronization event and the device it is addressed to.
+///
+/// Same as [`slave_netsync_flow`], except that MTC Full Frames and MMC commands
+/// are recognised whatever device they are addressed to, rather than only when
+/// broadcast.
+///
+/// # Arguments
+///
+/// * `buf` - The received network payload bytes.
+///
+/// # Returns
+///
+/// The reconstructed `MidiEvent` and its device ID, `SYSEX_DEVICE_ID_BROADCAST`
+/// (0x7F) for broadcast messages and for messages without a device ID.
+///
+/// # Errors
+///
+/// * The error of `slave_netsync_flow` whenever it would fail for the same payload
+///   addressed to all devices.
+#[cfg(feature = "alloc")]
+pub fn slave_netsync_flow_with_device(buf: &[u8]) -> Result<(MidiEvent, u8), NetsyncError> {
+    let (midi, len) = split_payload(buf)?;
+    let list = midi
+        .get(..len)
+        .ok_or(NetsyncError::DeclaredLengthExceedsBuffer {
+            declared: len,
+            available: midi.len(),
+        })?;
+
+    match realtime_sysex_device_id(list) {
+        Some(device_id) if device_id < SYSEX_DEVICE_ID_BROADCAST => {
+            let mut broadcast = list.to_vec();
+            set_realtime_sysex_device_id(&mut broadcast, SYSEX_DEVICE_ID_BROADCAST);
+            match parse_midi_list(&broadcast, len) {
+                // Pass unrecognised messages through with their original bytes
+                Ok(MidiEvent::Other(_)) => Ok((slave_netsync_flow(buf)?, device_id)),
+                Ok(event) => Ok((event, device_id)),
+                Err(_) => Err(NetsyncError::InvalidSlaveEvent),
+            }
+        }
+        _ => Ok((slave_netsync_flow(buf)?, SYSEX_DEVICE_ID_BROADCAST)),
+    }
+}
+
+/// Reconstructs a MIDI event from a received payload, borrowing from it.
+///
+/// Behaves like [`slave_netsync_flow`], but pass-through messages are returned as
+/// a [`MidiEventRef::Other`] borrowing their bytes from `buf`, so decoding never
+/// allocates.
+///
+/// # Arguments
+///
+/// * `buf` - The received network payload bytes.
+///
+/// # Returns
+///
+/// Returns the reconstructed `MidiEventRef` on successful parsing.
+///
+/// # Errors
+///
+/// * The error of `slave_netsync_flow` whenever it would fail for the same payload.
+pub fn slave_netsync_flow_ref(buf: &[u8]) -> Result<MidiEventRef<'_>, NetsyncError> {
+    split_payload(buf)
+        .and_then(|(midi, len)| parse_midi_list_ref(midi, len).map_err(PayloadError::from))
+        .map_err(NetsyncError::from)
+}
+
+/// Reconstructs a MIDI event from a received payload without allocating.
+///
+/// Behaves like [`slave_netsync_flow`], but pass-through messages are copied into
+/// `scratch` and returned as a borrowed [`MidiEventRef::Other`], or for a
+/// manufacturer SysEx, its data bytes. The scratch buffer
+/// is cleared on every call and keeps its capacity, so once it has grown to the
+/// largest message seen, decoding never allocates. Since the event borrows from
+/// `scratch` rather than `buf`, the receive buffer can be reused straight away.
+///
+/// # Arguments
+///
+/// * `buf` - The received network payload bytes.
+/// * `scratch` - Buffer reused across calls to hold pass-through message bytes.
+///
+/// # Returns
+///
+/// Returns the reconstructed `MidiEventRef` on successful parsing.
+///
+/// # Errors
+///
+/// * The error of `slave_netsync_flow` whenever it would fail for the same payload.
+#[cfg(feature = "alloc")]
+pub fn slave_netsync_flow_buf<'s>(
+    buf: &[u8],
+    scratch: &'s mut Vec<u8>,
+) -> Result<MidiEventRef<'s>, NetsyncError> {
+    let event = slave_netsync_flow_ref(buf)?;
+
+    scratch.clear();
+    Ok(match event {
+        MidiEventRef::Other(bytes) => {
+            scratch.extend_from_slice(bytes);
+            MidiEventRef::Other(scratch)
+        }
+        MidiEventRef::ManufacturerSysex { id, data } => {
+            scratch.extend_from_slice(data);
+            MidiEventRef::ManufacturerSysex { id, data: scratch }
+        }
+        MidiEventRef::MtcQuarter { msg_type, value } => {
+            MidiEventRef::MtcQuarter { msg_type, value }
+        }
+        MidiEventRef::MtcFull {
+            hour,
+            minute,
+            second,
+            frame,
+        } => MidiEventRef::MtcFull {
+            hour,
+            minute,
+            second,
+            frame,
+        },
+        MidiEventRef::Mmc(command) => MidiEventRef::Mmc(command),
+        MidiEventRef::Realtime(message) => MidiEventRef::Realtime(message),
+        MidiEventRef::SongPosition(position) => MidiEventRef::SongPosition(position),
+        MidiEventRef::IdentityRequest { device_id } => MidiEventRef::IdentityRequest { device_id },
+        MidiEventRef::IdentityReply(identity) => MidiEventRef::IdentityReply(identity),
+    })
+}
+
+/// Reconstructs a MIDI event from a payload that may carry no command at all.
+///
+/// RFC 6295 allows a command section with LEN=0 when a packet only carries a
+/// recovery journal or serves as a keepalive. [`slave_netsync_flow`] rejects such
+/// payloads; this function returns `None` for them instead, after checking that
+/// the header is complete and that a journal is present exactly when the J flag
+/// announces one.
+///
+/// # Arguments
+///
+/// * `buf` - The received network payload bytes.
+///
+/// # Returns
+///
+/// Returns `Some(event)` as `slave_netsync_flow` would, or `None` for a valid
+/// payload without commands.
+///
+/// # Errors
+///
+/// * The error of `slave_netsync_flow` whenever it would fail for a payload with
+///   commands.
+/// * `NetsyncError::TooShort` - If a payload without commands has an incomplete
+///   header or a J flag without a journal.
+/// * `NetsyncError::TrailingData` - If a payload without commands has stray bytes
+///   without a J flag.
+#[cfg(feature = "alloc")]
+pub fn slave_netsync_flow_opt(buf: &[u8]) -> Result<Option<MidiEvent>, NetsyncError> {
+    match read_header(buf) {
+        Some((start, 0)) => validate_empty_payload(buf, start).map(|_| None),
+        _ => slave_netsync_flow(buf).map(Some),
     }
+}
+
+/// Returns the position a payload sends to slaves, in microseconds.
+///
+/// Decodes `buf` as [`slave_netsync_flow_opt`] does and converts the timecode of
+/// a Full Frame, or of the quarter-frame cycle it completes, as
+/// [`smpte_to_us`](crate::mtc::smpte_to_us) does, but at the frame rate the last
+/// completed cycle in `state` signalled. Full Frames and MMC Locate discard a
+/// partially assembled cycle, as [`SlaveSession`] does.
+///
+/// # Arguments
+///
+/// * `buf` - The received network payload bytes.
+/// * `state` - Assembler holding the quarter frames received so far, kept across calls
+///
+/// # Returns
+///
+/// * `Ok(Some(us))` - For a Full Frame or a quarter frame completing a cycle
+/// * `Ok(None)` - For a quarter frame leaving its cycle incomplete, and for
+///   payloads without an absolute time: MMC commands such as Play and Stop, other
+///   messages, or no command at all
+///
+/// # Errors
+///
+/// * The error of `slave_netsync_flow_opt` whenever it would fail.
+/// * `NetsyncError::InvalidSlaveEvent` - If a quarter frame is out of range or
+///   completes an invalid timecode.
+#[cfg(feature = "alloc")]
+pub fn slave_time_update(
+    buf: &[u8],
+    state: &mut QuarterFrameAssembler,
+) -> Result<Option<VlcTickT>, NetsyncError> {
+    let smpte = match slave_netsync_flow_opt(buf)? {
+        Some(MidiEvent::MtcQuarter { msg_type, value }) => state
+            .feed(MtcQuarterFrame {
+                frame_type: msg_type,
+                value,
+            })
+            .map_err(|_| NetsyncError::InvalidSlaveEvent)?,
+        Some(MidiEvent::MtcFull {
+            hour,
+            minute,
+            second,
+            frame,
+        }) => {
+            state.reset();
+            Some(MtcFullFrame {
+                hours: hour,
+                minutes: minute,
+                seconds: second,
+                frames: frame,
+            })
+        }
+        Some(MidiEvent::Mmc(MmcCommand::Locate { .. })) => {
+            state.reset();
+            None
+        }
+        _ => None,
+    };
+    Ok(smpte.map(|smpte| smpte_to_us_rate(&smpte, state.frame_rate())))
+}
 
-    // Parse header
-    let header = PayloadHeader::parse(&buf[..1]).map_err(|_| NetsyncError::InvalidSlaveEvent)?;
+/// Checks the part of a LEN=0 payload following its header of `start` bytes.
+#[cfg(feature = "alloc")]
+pub(crate) fn validate_empty_payload(buf: &[u8], start: usize) -> Result<(), NetsyncError> {
+    const J_FLAG: u8 = 0x40;
+    // S/Y/A/H flags with TOTCHAN, then the 16-bit checkpoint sequence number
+    const JOURNAL_HEADER_LENGTH: usize = 3;
+
+    let rest = buf.len() - start;
+    if buf[0] & J_FLAG != 0 {
+        if rest < JOURNAL_HEADER_LENGTH {
+            return Err(NetsyncError::TooShort {
+                got: buf.len(),
+                min: start + JOURNAL_HEADER_LENGTH,
+            });
+        }
+    } else if rest > 0 {
+        return Err(NetsyncError::TrailingData { extra: rest });
+    }
+    Ok(())
+}
+
+/// Reason a payload was rejected by the slave flow, used for per-kind metrics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PayloadError {
+    /// Buffer shorter than the smallest valid payload
+    TooShort { got: usize, min: usize },
+    /// Header LEN exceeds the data following the header
+    LengthMismatch { declared: usize, available: usize },
+    /// Bytes follow the MIDI list of a payload without a journal
+    TrailingData { extra: usize },
+    /// The header sets a flag the slave flow does not support
+    UnsupportedFlags { flags: u8 },
+    /// The MIDI list could not be parsed
+    Midi(ParseError),
+    /// The payload breaks a rule of the session's `ParsePolicy`
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+    Rejected,
+}
+
+/// Parses a payload like [`slave_netsync_flow`], keeping the reason for a rejection.
+#[cfg(feature = "alloc")]
+fn parse_payload(buf: &[u8]) -> Result<MidiEvent, PayloadError> {
+    let (midi, len) = split_payload(buf)?;
 
     // Parse MIDI data with proper error propagation
-    let midi_event = parse_midi_list(&buf[1..], header.len as usize)
-        .map_err(|_| NetsyncError::InvalidSlaveEvent)?;
+    parse_midi_list(midi, len).map_err(PayloadError::from)
+}
+
+/// Determines the kind of event a payload carries without parsing it.
+///
+/// This only inspects the header byte and the first few MIDI bytes, and never
+/// allocates. It is meant for receive loops that need to prioritise transport
+/// commands over quarter frames before doing the full parse.
+///
+/// # Arguments
+///
+/// * `buf` - The received network payload bytes.
+///
+/// # Returns
+///
+/// Returns the `MessageKind` of the event `slave_netsync_flow` would return for
+/// the same payload.
+///
+/// # Errors
+///
+/// * The error of `slave_netsync_flow` whenever it would fail for the same payload.
+pub fn peek_event_kind(buf: &[u8]) -> Result<MessageKind, NetsyncError> {
+    split_payload(buf)
+        .and_then(|(midi, len)| peek_midi_kind(midi, len).map_err(PayloadError::from))
+        .map_err(NetsyncError::from)
+}
 
-    Ok(midi_event)
+/// Returns `true` if a payload carries an MTC, MMC or beat clock timing event.
+///
+/// Intended for bridges that forward non-timing traffic elsewhere: it classifies the
+/// payload in place without building a `MidiEvent`. Both the one-octet header and
+/// the two-octet form (B flag set, 12-bit LEN) are accepted, and quarter frames
+/// followed by padding bytes are still recognised.
+///
+/// # Arguments
+///
+/// * `buf` - The received network payload bytes.
+///
+/// # Returns
+///
+/// `true` for Quarter-Frame and Full-Frame MTC, MMC transport commands, beat
+/// clock real-time messages and Song Position Pointer. `false` for any other
+/// message and for malformed payloads.
+pub fn is_timing_event(buf: &[u8]) -> bool {
+    let Ok((midi, len)) = split_payload(buf) else {
+        return false;
+    };
+
+    matches!(peek_midi_kind(midi, len), Ok(kind) if kind.is_sync())
+}
+
+/// Reconstructs the first MIDI event of a buffer holding consecutive payloads.
+///
+/// Bridges may batch several payloads into one datagram. Each payload is a header
+/// followed by exactly LEN bytes of MIDI list, so the next payload starts right
+/// after it. Both the one-octet header and the two-octet form (B flag set, 12-bit
+/// LEN) are accepted. Unlike [`slave_netsync_flow`], SysEx parsing never looks
+/// past LEN, since the following bytes belong to the next payload. As with
+/// [`slave_netsync_flow_opt`], a payload with LEN=0, such as a keepalive, carries
+/// no event but still consumes its header. A recovery journal has no length of its
+/// own to skip, so payloads announcing one are rejected.
+///
+/// # Arguments
+///
+/// * `buf` - The received bytes, starting with a payload header.
+///
+/// # Returns
+///
+/// Returns the event, `None` for a payload without commands, and the number of
+/// bytes consumed (header plus LEN), which is where the next payload begins.
+///
+/// # Errors
+///
+/// * `NetsyncError::TooShort` - If the header is incomplete.
+/// * `NetsyncError::UnsupportedFlags` - If the header sets the J (journal), Z (delta
+///   time) or P (phantom running status) flag.
+/// * `NetsyncError::DeclaredLengthExceedsBuffer` - If LEN exceeds the remaining
+///   bytes.
+/// * `NetsyncError::InvalidSlaveEvent` - If the MIDI list cannot be parsed.
+#[cfg(feature = "alloc")]
+pub fn slave_netsync_flow_stream(buf: &[u8]) -> Result<(Option<MidiEvent>, usize), NetsyncError> {
+    const J_FLAG: u8 = 0x4;
+    const Z_FLAG: u8 = 0x2;
+    const P_FLAG: u8 = 0x1;
+
+    let (start, len) = read_header(buf).ok_or(NetsyncError::TooShort {
+        got: buf.len(),
+        min: buf.len() + 1,
+    })?;
+    let flags = buf[0] >> 4;
+    if flags & (J_FLAG | Z_FLAG | P_FLAG) != 0 {
+        return Err(NetsyncError::UnsupportedFlags { flags });
+    }
+    let end = start + len;
+    if end > buf.len() {
+        return Err(NetsyncError::DeclaredLengthExceedsBuffer {
+            declared: len,
+            available: buf.len() - start,
+        });
+    }
+
+    if len == 0 {
+        return Ok((None, end));
+    }
+
+    let event =
+        parse_midi_list(&buf[start..end], len).map_err(|_| NetsyncError::InvalidSlaveEvent)?;
+    Ok((Some(event), end))
+}
+
+/// Reconstructs every MIDI event of a buffer holding consecutive payloads.
+///
+/// Calls [`slave_netsync_flow_stream`] until the buffer is consumed.
+///
+/// # Arguments
+///
+/// * `buf` - The received bytes, holding zero or more complete payloads.
+///
+/// # Returns
+///
+/// Returns the events in order. Payloads without commands are skipped.
+///
+/// # Errors
+///
+/// * The error of `slave_netsync_flow_stream` for the first invalid payload,
+///   including a truncated final one. Events before it are discarded.
+#[cfg(feature = "alloc")]
+pub fn parse_all_payloads(buf: &[u8]) -> Result<Vec<MidiEvent>, NetsyncError> {
+    let mut events = Vec::new();
+    let mut rest = buf;
+    while !rest.is_empty() {
+        let (event, consumed) = slave_netsync_flow_stream(rest)?;
+        events.extend(event);
+        rest = &rest[consumed..];
+    }
+    Ok(events)
+}
+
+/// Returns the length of the payload at the start of `buf`: its header plus the
+/// MIDI list length it declares.
+///
+/// For splitting concatenated payloads the way [`slave_netsync_flow_stream`] does,
+/// without decoding them. Journals are not counted, stream mode rejects them.
+///
+/// # Arguments
+///
+/// * `buf` - Bytes starting with a payload header.
+///
+/// # Returns
+///
+/// The length, which may exceed `buf`, or `None` if the header is incomplete.
+pub fn payload_len(buf: &[u8]) -> Option<usize> {
+    read_header(buf).map(|(start, len)| start + len)
+}
+
+/// Decodes a one- or two-octet payload header, returning where the MIDI list
+/// starts and its length, or `None` if the header is incomplete.
+pub(crate) fn read_header(buf: &[u8]) -> Option<(usize, usize)> {
+    const B_FLAG: u8 = 0x80;
+
+    let &first = buf.first()?;
+    if first & B_FLAG == 0 {
+        Some((1, (first & 0x0F) as usize))
+    } else {
+        let &second = buf.get(1)?;
+        Some((2, ((first & 0x0F) as usize) << 8 | second as usize))
+    }
+}
+
+/// Validates the payload framing and returns the MIDI list with its header length.
+fn split_payload(buf: &[u8]) -> Result<(&[u8], usize), PayloadError> {
+    // Check minimum payload size for a command (1 byte header + 1 byte real-time
+    // message). Payloads without commands are handled by `slave_netsync_flow_opt`.
+    const MIN_PAYLOAD_SIZE: usize = 2;
+    const J_FLAG: u8 = 0x4;
+    const Z_FLAG: u8 = 0x2;
+    const P_FLAG: u8 = 0x1;
+    if buf.len() < MIN_PAYLOAD_SIZE {
+        return Err(PayloadError::TooShort {
+            got: buf.len(),
+            min: MIN_PAYLOAD_SIZE,
+        });
+    }
+
+    // The same header reader as every other decoder, so the B flag is honoured
+    let (start, len) = read_header(buf).ok_or(PayloadError::TooShort {
+        got: buf.len(),
+        min: MIN_PAYLOAD_SIZE,
+    })?;
+    let flags = buf[0] >> 4;
+    if flags & (Z_FLAG | P_FLAG) != 0 {
+        return Err(PayloadError::UnsupportedFlags { flags });
+    }
+    // Without a journal, nothing may follow the MIDI list
+    let end = start + len;
+    if flags & J_FLAG == 0 && end < buf.len() {
+        return Err(PayloadError::TrailingData {
+            extra: buf.len() - end,
+        });
+    }
+    Ok((&buf[start..], len))
+}
+
+impl From<ParseError> for PayloadError {
+    fn from(err: ParseError) -> Self {
+        match err {
+            ParseError::BufferTooSmall {
+                requested,
+                available,
+            } => PayloadError::LengthMismatch {
+                declared: requested,
+                available,
+            },
+            err => PayloadError::Midi(err),
+        }
+    }
+}
+
+impl From<PayloadError> for NetsyncError {
+    fn from(err: PayloadError) -> Self {
+        match err {
+            PayloadError::TooShort { got, min } => NetsyncError::TooShort { got, min },
+            PayloadError::LengthMismatch {
+                declared,
+                available,
+            } => NetsyncError::DeclaredLengthExceedsBuffer {
+                declared,
+                available,
+            },
+            PayloadError::TrailingData { extra } => NetsyncError::TrailingData { extra },
+            PayloadError::UnsupportedFlags { flags } => NetsyncError::UnsupportedFlags { flags },
+            PayloadError::Midi(_) | PayloadError::Rejected => NetsyncError::InvalidSlaveEvent,
+        }
+    }
 }
@@ -0,0 +1,139 @@
+//! # Slave-side MIDI Beat Clock following
+//!
+//! Beat-clock masters do not transmit absolute positions while running: the slave
+//! counts Timing Clock messages from the last Start or Song Position Pointer, and
+//! derives the tempo from how often they arrive. [`BeatClockTracker`] does both.
+//!
+//! Individual clock intervals are dominated by network jitter (a few milliseconds
+//! on a 20 ms period at 120 BPM), so the tempo is measured over a sliding window
+//! of clocks rather than from consecutive pairs.
+
+use crate::midi::{MidiEvent, RealtimeMessage, CLOCKS_PER_QUARTER_NOTE, CLOCKS_PER_SONG_POSITION};
+use crate::mtc::VlcTickT;
+
+/// Number of clock intervals the tempo is measured over: two quarter notes.
+const TEMPO_WINDOW: usize = 2 * CLOCKS_PER_QUARTER_NOTE as usize;
+
+/// # Accumulates beat clock messages into a song position and tempo.
+///
+/// - Start rewinds to the beginning of the song and starts counting clocks
+/// - Continue resumes counting from the current position
+/// - Stop freezes the position
+/// - Song Position Pointer moves the position to a multiple of six clocks
+///
+/// Clocks received while stopped are only used for tempo estimation, since masters
+/// commonly keep sending them.
+#[derive(Debug, Clone)]
+pub struct BeatClockTracker {
+    running: bool,
+    /// Clocks since the beginning of the song
+    clocks: u64,
+    /// Arrival times of the most recent clocks, oldest first once full
+    arrivals: [VlcTickT; TEMPO_WINDOW + 1],
+    /// Index the next arrival is written at
+    next: usize,
+    /// Number of valid entries in `arrivals`
+    count: usize,
+}
+
+impl Default for BeatClockTracker {
+    fn default() -> Self {
+        Self {
+            running: false,
+            clocks: 0,
+            arrivals: [0; TEMPO_WINDOW + 1],
+            next: 0,
+            count: 0,
+        }
+    }
+}
+
+impl BeatClockTracker {
+    /// Creates a stopped tracker at the beginning of the song.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// # Updates the tracker with a received event.
+    ///
+    /// Events other than beat clock messages and Song Position Pointer are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The decoded event, typically from `slave_netsync_flow`
+    /// * `arrival_us` - Local monotonic time at which the event was received
+    pub fn apply_event(&mut self, event: &MidiEvent, arrival_us: VlcTickT) {
+        match event {
+            MidiEvent::Realtime(RealtimeMessage::Clock) => {
+                if self.running {
+                    self.clocks += 1;
+                }
+                self.record_arrival(arrival_us);
+            }
+            MidiEvent::Realtime(RealtimeMessage::Start) => {
+                self.clocks = 0;
+                self.running = true;
+            }
+            MidiEvent::Realtime(RealtimeMessage::Continue) => self.running = true,
+            MidiEvent::Realtime(RealtimeMessage::Stop) => self.running = false,
+            MidiEvent::SongPosition(position) => {
+                self.clocks = *position as u64 * CLOCKS_PER_SONG_POSITION as u64;
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns `true` between Start or Continue and Stop.
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Returns the number of clocks since the beginning of the song.
+    pub fn clocks(&self) -> u64 {
+        self.clocks
+    }
+
+    /// Returns the position in quarter notes since the beginning of the song.
+    pub fn beat_position(&self) -> f64 {
+        self.clocks as f64 / CLOCKS_PER_QUARTER_NOTE as f64
+    }
+
+    /// Returns the position in Song Position Pointer units (sixteenth notes),
+    /// rounded down and saturated to the 14-bit range.
+    pub fn song_position(&self) -> u16 {
+        (self.clocks / CLOCKS_PER_SONG_POSITION as u64).min(0x3FFF) as u16
+    }
+
+    /// Returns the average interval between clocks over the measurement window, in
+    /// microseconds, or `None` until two clocks have been received.
+    pub fn clock_interval_us(&self) -> Option<f64> {
+        if self.count < 2 {
+            return None;
+        }
+        let newest = self.arrivals[(self.next + TEMPO_WINDOW) % (TEMPO_WINDOW + 1)];
+        let oldest =
+            self.arrivals[(self.next + TEMPO_WINDOW + 1 - self.count) % (TEMPO_WINDOW + 1)];
+        Some(newest.saturating_sub(oldest) as f64 / (self.count - 1) as f64)
+    }
+
+    /// Returns the estimated tempo in quarter notes per minute, or `None` until two
+    /// clocks have been received.
+    pub fn tempo_bpm(&self) -> Option<f64> {
+        let interval_us = self.clock_interval_us()?;
+        if interval_us <= 0.0 {
+            return None;
+        }
+        Some(60_000_000.0 / (interval_us * CLOCKS_PER_QUARTER_NOTE as f64))
+    }
+
+    /// Forgets the position, transport state and tempo history.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    fn record_arrival(&mut self, arrival_us: VlcTickT) {
+        self.arrivals[self.next] = arrival_us;
+        self.next = (self.next + 1) % (TEMPO_WINDOW + 1);
+        self.count = (self.count + 1).min(TEMPO_WINDOW + 1);
+    }
+}
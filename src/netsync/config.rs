@@ -0,0 +1,75 @@
+//! # Shared configuration for master and slave sessions
+
+use crate::netsync::{FullFrameRefreshPolicy, ParsePolicy, TrackerConfig};
+
+/// # Configuration shared by [`MasterSession`](crate::netsync::MasterSession) and
+/// [`SlaveSession`](crate::netsync::SlaveSession).
+///
+/// With the `serde` feature, the field names are the schema of configuration
+/// files, and unknown fields are rejected. Each field may be left out for its
+/// default. Within `refresh` and `tracker`, the optional settings are disabled
+/// when left out, and the others are required:
+///
+/// ```toml
+/// ssrc = 1
+/// policy = "strict"
+///
+/// [refresh]
+/// full_frame_interval_us = 500000
+///
+/// [build]
+/// quarter_frame_padding = 2
+///
+/// [tracker]
+/// frame_rate = "25"
+/// stale_after_quarter_frames = 24
+/// freewheel_frames = 5
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct NetsyncConfig {
+    /// RTP synchronization source identifier used for outgoing packets.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub ssrc: u32,
+    /// When the master resends Full Frames on its own.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub refresh: FullFrameRefreshPolicy,
+    /// Frame rate and sync-loss detection settings for the slave position tracker.
+    /// The frame rate also sets the timecode of the Full Frames a master sends and
+    /// how a slave reads received ones.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tracker: TrackerConfig,
+    /// How strictly the slave treats payloads that deviate from the expected format.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub policy: ParsePolicy,
+    /// How the master lays out the payloads it sends.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub build: BuildOptions,
+}
+
+/// # How the master flow lays out payloads.
+///
+/// The default is the layout of [`master_netsync_flow`](crate::netsync::master_netsync_flow);
+/// [`master_netsync_flow_with`](crate::netsync::master_netsync_flow_with) and
+/// [`MasterSession`](crate::netsync::MasterSession) apply the others.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct BuildOptions {
+    /// Zero bytes appended after each MTC Quarter Frame, counted in the header LEN,
+    /// at most [`MAX_QUARTER_FRAME_PADDING`](crate::netsync::MAX_QUARTER_FRAME_PADDING).
+    ///
+    /// MIDI-OX sends quarter frames padded to four bytes (`F1 23 00 00`), and some
+    /// receivers written against it only accept commands of that size: set `2` for
+    /// them. Our slave flow skips the padding and decodes the plain quarter frame,
+    /// except under [`ParsePolicy::Strict`], which rejects the extra bytes.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub quarter_frame_padding: u8,
+}
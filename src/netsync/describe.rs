@@ -0,0 +1,236 @@
+//! # Human-readable payload rendering
+//!
+//! [`describe_payload`] prints a payload the way a protocol analyzer would, for
+//! logs and tooling: the header, the decoded command, and a hex dump of anything
+//! that is not understood. Findings from [`verify_payload`] are appended so odd
+//! payloads explain themselves.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::midi::{MidiEvent, MmcCommand, RealtimeMessage};
+use crate::mtc::FrameRate;
+use crate::netsync::{read_header, slave_netsync_flow, verify_payload, Severity};
+use crate::util::{hexdump, hexdump_lines, HEXDUMP_LINE_LENGTH};
+
+/// Names of the eight quarter-frame pieces, indexed by message type.
+const QUARTER_FRAME_PIECES: [&str; 8] = [
+    "frames low",
+    "frames high",
+    "seconds low",
+    "seconds high",
+    "minutes low",
+    "minutes high",
+    "hours low",
+    "hours high + rate",
+];
+
+/// # Renders a payload as text, one line per element.
+///
+/// The first line describes the header, the second the decoded command. Payloads
+/// the slave flow accepts are decoded with it; anything it rejects is shown as a
+/// hex dump followed by the reasons from [`verify_payload`]. Warnings and errors
+/// about accepted payloads are listed as well.
+///
+/// # Arguments
+///
+/// * `buf` - The network payload bytes, starting with the header.
+///
+/// # Returns
+///
+/// The description, without a trailing newline. Never panics, whatever the input.
+///
+/// # Example
+///
+/// ```
+/// use rtp_midi_netsync::midi::MidiEvent;
+/// use rtp_midi_netsync::netsync::{describe_payload, master_netsync_flow};
+///
+/// let payload = master_netsync_flow(&MidiEvent::MtcFull {
+///     hour: 1,
+///     minute: 23,
+///     second: 45,
+///     frame: 10,
+/// })
+/// .unwrap();
+/// assert_eq!(
+///     describe_payload(&payload),
+///     "Header 0x0A: LEN=10\nMTC Full 01:23:45:10 @30fps"
+/// );
+/// ```
+pub fn describe_payload(buf: &[u8]) -> String {
+    let mut out = String::new();
+    let Some(&first) = buf.first() else {
+        return "Empty payload".to_string();
+    };
+
+    let _ = write!(out, "Header 0x{:02X}:", first);
+    let flags: Vec<&str> = [(0x80, "B"), (0x40, "J"), (0x20, "Z"), (0x10, "P")]
+        .into_iter()
+        .filter(|(bit, _)| first & bit != 0)
+        .map(|(_, name)| name)
+        .collect();
+    if !flags.is_empty() {
+        let _ = write!(out, " flags {},", flags.join(" "));
+    }
+    let start = match read_header(buf) {
+        Some((2, len)) => {
+            let _ = write!(out, " LEN={} (two octets)", len);
+            2
+        }
+        Some((start, len)) => {
+            let _ = write!(out, " LEN={}", len);
+            start
+        }
+        // B flag without its second octet
+        None => {
+            let _ = write!(out, " LEN={}", first & 0x0F);
+            1
+        }
+    };
+
+    let report = verify_payload(buf);
+    match slave_netsync_flow(buf) {
+        Ok(event) => {
+            let _ = write!(out, "\n{}", describe_event(&event));
+            for finding in &report.findings {
+                if finding.severity > Severity::Info {
+                    let _ = write!(out, "\n  {}", finding);
+                }
+            }
+        }
+        Err(_) => {
+            let bytes = &buf[start..];
+            if bytes.len() > HEXDUMP_LINE_LENGTH {
+                // Dump the whole payload so offsets match those of the findings
+                let _ = write!(out, "\nMalformed ({} bytes):", buf.len());
+                for line in hexdump_lines(buf).to_string().lines() {
+                    let _ = write!(out, "\n  {}", line);
+                }
+            } else {
+                let _ = write!(out, "\nMalformed: {}", hex_dump(bytes));
+            }
+            for finding in &report.findings {
+                let _ = write!(out, "\n  {}", finding);
+            }
+        }
+    }
+    out
+}
+
+/// Renders a decoded event on one line.
+fn describe_event(event: &MidiEvent) -> String {
+    match event {
+        MidiEvent::MtcQuarter { msg_type, value } => format!(
+            "MTC Quarter Frame piece {} ({}) value 0x{:X}",
+            msg_type,
+            QUARTER_FRAME_PIECES[(*msg_type & 0x07) as usize],
+            value
+        ),
+        MidiEvent::MtcFull {
+            hour,
+            minute,
+            second,
+            frame,
+        } => format!(
+            "MTC Full {:02}:{:02}:{:02}:{:02} @{}",
+            hour & 0x1F,
+            minute,
+            second,
+            frame,
+            rate_label(*hour)
+        ),
+        MidiEvent::Mmc(MmcCommand::Play) => "MMC Play".to_string(),
+        MidiEvent::Mmc(MmcCommand::Stop) => "MMC Stop".to_string(),
+        MidiEvent::Mmc(MmcCommand::DeferredPlay) => "MMC Deferred Play".to_string(),
+        MidiEvent::Mmc(MmcCommand::FastForward) => "MMC Fast Forward".to_string(),
+        MidiEvent::Mmc(MmcCommand::Rewind) => "MMC Rewind".to_string(),
+        MidiEvent::Mmc(MmcCommand::RecordStrobe) => "MMC Record Strobe".to_string(),
+        MidiEvent::Mmc(MmcCommand::RecordExit) => "MMC Record Exit".to_string(),
+        MidiEvent::Mmc(MmcCommand::Pause) => "MMC Pause".to_string(),
+        MidiEvent::Mmc(MmcCommand::Eject) => "MMC Eject".to_string(),
+        MidiEvent::Mmc(MmcCommand::Reset) => "MMC Reset".to_string(),
+        MidiEvent::Mmc(MmcCommand::Locate {
+            hour,
+            minute,
+            second,
+            frame,
+            subframe,
+        }) => format!(
+            "MMC Locate {:02}:{:02}:{:02}:{:02}.{:02} @{}",
+            hour & 0x1F,
+            minute,
+            second,
+            frame,
+            subframe,
+            rate_label(*hour)
+        ),
+        MidiEvent::Mmc(MmcCommand::Shuttle { speed }) => {
+            format!("MMC Shuttle {:+}x", speed.to_f32())
+        }
+        MidiEvent::Mmc(MmcCommand::VariablePlay { speed }) => {
+            format!("MMC Variable Play {:+}x", speed.to_f32())
+        }
+        MidiEvent::Mmc(MmcCommand::Search { speed }) => {
+            format!("MMC Search {:+}x", speed.to_f32())
+        }
+        MidiEvent::Mmc(MmcCommand::MaskedWrite {
+            field,
+            start,
+            mask,
+            data,
+        }) => format!(
+            "MMC Masked Write field 0x{:02X} byte {} mask 0x{:02X} data 0x{:02X}",
+            field, start, mask, data
+        ),
+        MidiEvent::Realtime(message) => match message {
+            RealtimeMessage::Clock => "Timing Clock",
+            RealtimeMessage::Start => "Start",
+            RealtimeMessage::Continue => "Continue",
+            RealtimeMessage::Stop => "Stop",
+        }
+        .to_string(),
+        MidiEvent::SongPosition(position) => format!(
+            "Song Position Pointer {} (beat {})",
+            position,
+            *position as f64 / 4.0
+        ),
+        MidiEvent::IdentityRequest { device_id } => {
+            format!("Identity Request to device {:02X}", device_id)
+        }
+        MidiEvent::IdentityReply(identity) => format!(
+            "Identity Reply from device {:02X}: manufacturer {}, family {:04X}, model {:04X}, version {}",
+            identity.device_id,
+            identity.manufacturer,
+            identity.family,
+            identity.model,
+            hex_dump(&identity.version)
+        ),
+        MidiEvent::ManufacturerSysex { id, data } => format!(
+            "Manufacturer SysEx {} ({} data bytes): {}",
+            id,
+            data.len(),
+            hex_dump(data)
+        ),
+        MidiEvent::Other(bytes) => format!("Other ({} bytes): {}", bytes.len(), hex_dump(bytes)),
+    }
+}
+
+/// Returns the frame rate signalled by the rate bits of an hour byte, 30fps when
+/// no bits are set as this crate's master flow sends it.
+fn rate_label(hour: u8) -> &'static str {
+    match FrameRate::from_mtc_code((hour >> 5) & 0x03) {
+        Some(FrameRate::Fps25) => "25fps",
+        Some(FrameRate::Fps2997Df) => "29.97fps drop-frame",
+        _ => "30fps",
+    }
+}
+
+fn hex_dump(bytes: &[u8]) -> String {
+    if bytes.is_empty() {
+        return "(no bytes)".to_string();
+    }
+    hexdump(bytes, bytes.len()).to_string()
+}
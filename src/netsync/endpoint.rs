@@ -0,0 +1,177 @@
+//! # Combined master and slave roles on one link
+//!
+//! Devices such as mixing consoles send transport commands while chasing timecode
+//! from another machine over the same link. [`Endpoint`] owns a [`MasterSession`]
+//! and a [`SlaveSession`] sharing one [`NetsyncConfig`] and SSRC, and routes
+//! incoming packets to the right one.
+//!
+//! Two endpoints that both forward what they receive would bounce every event back
+//! and forth forever. An endpoint therefore refuses to send an event identical to
+//! one it received within the last 50 ms, and drops packets carrying its own SSRC.
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use crate::error::NetsyncError;
+use crate::midi::{DeviceIdentity, MidiEvent};
+use crate::mtc::VlcTickT;
+use crate::netsync::{
+    MasterSession, NetsyncConfig, NetsyncHandler, PacketStatus, SlaveFeedback, SlaveSession,
+};
+use crate::rtp::RtpHeader;
+
+/// Time during which sending an event identical to a received one is treated as
+/// an echo and suppressed.
+const ECHO_WINDOW_US: VlcTickT = 50_000;
+
+/// # Bidirectional netsync endpoint.
+///
+/// Outgoing events go through the master side, incoming sync packets through the
+/// slave side, and resync feedback from the remote slave is handed to the master
+/// side. Both sides use the SSRC from the endpoint's configuration.
+#[derive(Debug, Clone, Default)]
+pub struct Endpoint {
+    ssrc: u32,
+    master: MasterSession,
+    slave: SlaveSession,
+    /// Sync events received within the echo window, oldest first
+    recent: VecDeque<(MidiEvent, VlcTickT)>,
+}
+
+impl Endpoint {
+    /// Creates an endpoint using the default [`NetsyncConfig`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an endpoint whose master and slave sides both use `config`.
+    pub fn with_config(config: NetsyncConfig) -> Self {
+        Self {
+            ssrc: config.ssrc,
+            master: MasterSession::with_config(config),
+            slave: SlaveSession::with_config(config),
+            recent: VecDeque::new(),
+        }
+    }
+
+    /// # Builds the RTP packet for an application event.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The sync event to send
+    /// * `now_us` - Local monotonic time at which the packet is sent
+    ///
+    /// # Returns
+    ///
+    /// The RTP packet to transmit, or `None` if the same event was received less
+    /// than 50 ms ago and sending it would echo it back to its origin.
+    ///
+    /// # Errors
+    ///
+    /// * `NetsyncError::InvalidMasterEvent` - As for [`MasterSession::packet_for_event`].
+    pub fn send_event(
+        &mut self,
+        event: &MidiEvent,
+        now_us: VlcTickT,
+    ) -> Result<Option<Vec<u8>>, NetsyncError> {
+        self.forget_before(now_us);
+        if let Some(index) = self.recent.iter().position(|(recent, _)| recent == event) {
+            // Each received event suppresses a single echo
+            self.recent.remove(index);
+            return Ok(None);
+        }
+        self.master.packet_for_event(event, now_us).map(Some)
+    }
+
+    /// # Processes a complete RTP packet received at `arrival_us`.
+    ///
+    /// Resync requests and Identity Replies are passed to the master side, packets
+    /// carrying this endpoint's own SSRC are dropped, and everything else is
+    /// handled by the slave side as in [`SlaveSession::feed_packet`].
+    ///
+    /// # Arguments
+    ///
+    /// * `packet` - The received RTP packet
+    /// * `arrival_us` - Local monotonic time at which the packet was received
+    /// * `handler` - Receiver of the decoded events
+    ///
+    /// # Returns
+    ///
+    /// Whether the packet was accepted or why it was dropped.
+    ///
+    /// # Errors
+    ///
+    /// As for [`SlaveSession::feed_packet`].
+    pub fn receive_packet<H: NetsyncHandler + ?Sized>(
+        &mut self,
+        packet: &[u8],
+        arrival_us: VlcTickT,
+        handler: &mut H,
+    ) -> Result<PacketStatus, NetsyncError> {
+        let Ok((rtp, _)) = RtpHeader::parse(packet) else {
+            // Let the slave side count the error
+            return self.slave.feed_packet(packet, arrival_us, handler);
+        };
+        if rtp.ssrc == self.ssrc {
+            return Ok(PacketStatus::Looped);
+        }
+        if let Some(feedback) = SlaveFeedback::from_packet(packet) {
+            self.master.handle_feedback(feedback);
+            return Ok(PacketStatus::Feedback);
+        }
+        if let Some(identity) = DeviceIdentity::from_packet(packet) {
+            self.master.handle_identity_reply(identity);
+            return Ok(PacketStatus::Feedback);
+        }
+
+        let (status, event) = self.slave.feed_packet_event(packet, arrival_us, handler)?;
+        if let Some(event) = event.filter(|event| event.kind().is_sync()) {
+            self.forget_before(arrival_us);
+            self.recent.push_back((event, arrival_us));
+        }
+        Ok(status)
+    }
+
+    /// # Returns the packets due at `now_us`.
+    ///
+    /// Combines the master side's maintenance packets from [`MasterSession::tick`]
+    /// with any resync request from [`SlaveSession::feedback_to_send`] and Identity
+    /// Reply from [`SlaveSession::identity_reply_to_send`].
+    pub fn tick(&mut self, now_us: VlcTickT) -> Vec<Vec<u8>> {
+        let mut packets = self.master.tick(now_us);
+        packets.extend(self.slave.feedback_to_send(now_us));
+        packets.extend(self.slave.identity_reply_to_send(now_us));
+        packets
+    }
+
+    /// Returns the sending side.
+    pub fn master(&self) -> &MasterSession {
+        &self.master
+    }
+
+    /// Returns the sending side for direct use, bypassing echo suppression.
+    pub fn master_mut(&mut self) -> &mut MasterSession {
+        &mut self.master
+    }
+
+    /// Returns the receiving side.
+    pub fn slave(&self) -> &SlaveSession {
+        &self.slave
+    }
+
+    /// Returns the receiving side for direct use.
+    pub fn slave_mut(&mut self) -> &mut SlaveSession {
+        &mut self.slave
+    }
+
+    /// Drops received events older than the echo window.
+    fn forget_before(&mut self, now_us: VlcTickT) {
+        while self
+            .recent
+            .front()
+            .is_some_and(|(_, at)| now_us.saturating_sub(*at) >= ECHO_WINDOW_US)
+        {
+            self.recent.pop_front();
+        }
+    }
+}
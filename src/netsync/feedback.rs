@@ -0,0 +1,102 @@
+//! # Slave-to-master resync feedback
+//!
+//! Quarter frames only carry a complete position every two frames, and a slave
+//! that lost packets cannot tell whether it missed a Locate or a transport change.
+//! Rather than waiting for the next periodic Full Frame, a [`SlaveSession`] can ask
+//! the master to resend the absolute position right away.
+//!
+//! The request travels as a small SysEx message using the non-commercial
+//! manufacturer ID `7D`, so it passes through any RTP-MIDI transport unchanged:
+//!
+//! ```text
+//! F0 7D 4E 53 01 reason F7
+//! ```
+//!
+//! where `4E 53` is ASCII `NS` (netsync), `01` the resync request command and
+//! `reason` one of the [`SlaveFeedback`] codes.
+//!
+//! [`SlaveSession`]: crate::netsync::SlaveSession
+
+use alloc::vec::Vec;
+
+use crate::header::PayloadHeader;
+use crate::midi::MidiEvent;
+use crate::netsync::slave_netsync_flow;
+use crate::rtp::RtpHeader;
+
+/// Non-commercial SysEx manufacturer ID.
+const SYSEX_NON_COMMERCIAL_ID: u8 = 0x7D;
+
+/// Bytes identifying netsync messages after the manufacturer ID.
+const NETSYNC_SYSEX_TAG: [u8; 2] = *b"NS";
+
+/// Netsync SysEx command asking the master to resend its position.
+const RESYNC_REQUEST_COMMAND: u8 = 0x01;
+
+/// Length of a resync request SysEx message.
+pub const RESYNC_REQUEST_LENGTH: usize = 7;
+
+/// # Feedback a slave sends to its master.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlaveFeedback {
+    /// Packets were lost: resend the absolute position
+    PacketLoss,
+    /// The application asked for a resync, for example after a seek on the slave side
+    Requested,
+}
+
+impl SlaveFeedback {
+    fn reason_code(self) -> u8 {
+        match self {
+            SlaveFeedback::PacketLoss => 0x01,
+            SlaveFeedback::Requested => 0x02,
+        }
+    }
+
+    /// Returns the resync request SysEx message carrying this feedback.
+    pub fn to_sysex(self) -> [u8; RESYNC_REQUEST_LENGTH] {
+        [
+            0xF0,
+            SYSEX_NON_COMMERCIAL_ID,
+            NETSYNC_SYSEX_TAG[0],
+            NETSYNC_SYSEX_TAG[1],
+            RESYNC_REQUEST_COMMAND,
+            self.reason_code(),
+            0xF7,
+        ]
+    }
+
+    /// Returns the network payload carrying this feedback.
+    pub fn to_payload(self) -> Vec<u8> {
+        let mut payload = Vec::with_capacity(1 + RESYNC_REQUEST_LENGTH);
+        payload
+            .extend_from_slice(&PayloadHeader::new(0x0, RESYNC_REQUEST_LENGTH as u8).serialize());
+        payload.extend_from_slice(&self.to_sysex());
+        payload
+    }
+
+    /// Recognises feedback in a parsed event, returning `None` for anything else.
+    pub fn from_event(event: &MidiEvent) -> Option<Self> {
+        let MidiEvent::Other(bytes) = event else {
+            return None;
+        };
+        match bytes.as_slice() {
+            [0xF0, SYSEX_NON_COMMERCIAL_ID, tag0, tag1, RESYNC_REQUEST_COMMAND, reason, 0xF7]
+                if [*tag0, *tag1] == NETSYNC_SYSEX_TAG =>
+            {
+                match reason {
+                    0x01 => Some(SlaveFeedback::PacketLoss),
+                    0x02 => Some(SlaveFeedback::Requested),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Recognises feedback in a complete RTP packet, returning `None` for anything else.
+    pub fn from_packet(packet: &[u8]) -> Option<Self> {
+        let (_, payload) = RtpHeader::parse(packet).ok()?;
+        Self::from_event(&slave_netsync_flow(payload).ok()?)
+    }
+}
@@ -0,0 +1,64 @@
+//! # Device discovery over the sync link
+//!
+//! A master enumerates its slaves with the Universal Non-Real-Time Identity
+//! Request. [`MasterSession::discover`] broadcasts it, every [`SlaveSession`] given
+//! a [`DeviceIdentity`] answers with an Identity Reply from
+//! [`SlaveSession::identity_reply_to_send`], and the master collects the replies
+//! with [`MasterSession::handle_identity_reply`]:
+//!
+//! ```text
+//! master -> slaves  F0 7E 7F 06 01 F7
+//! slave -> master   F0 7E devID 06 02 id ff ff mm mm vv vv vv vv F7
+//! ```
+//!
+//! A reply with a three-byte manufacturer ID is 17 bytes long, more than the
+//! one-octet payload header can announce, so replies use the two-octet form when
+//! needed and are recognised with either.
+//!
+//! [`MasterSession::discover`]: crate::netsync::MasterSession::discover
+//! [`MasterSession::handle_identity_reply`]: crate::netsync::MasterSession::handle_identity_reply
+//! [`SlaveSession`]: crate::netsync::SlaveSession
+//! [`SlaveSession::identity_reply_to_send`]: crate::netsync::SlaveSession::identity_reply_to_send
+
+use alloc::vec::Vec;
+
+use crate::header::PayloadHeader;
+use crate::midi::{build_midi_list, parse_midi_list_ref, DeviceIdentity, MidiEvent, MidiEventRef};
+use crate::netsync::read_header;
+use crate::rtp::RtpHeader;
+
+impl DeviceIdentity {
+    /// Returns the network payload carrying the Identity Reply for this identity.
+    pub fn to_payload(&self) -> Vec<u8> {
+        const B_FLAG: u8 = 0x80;
+        const SHORT_HEADER_MAX_LEN: usize = 0x0F;
+
+        let reply = build_midi_list(&MidiEvent::IdentityReply(*self));
+        let len = reply.len();
+        let mut payload = Vec::with_capacity(2 + len);
+        if len <= SHORT_HEADER_MAX_LEN {
+            payload.extend_from_slice(&PayloadHeader::new(0x0, len as u8).serialize());
+        } else {
+            payload.extend_from_slice(&[B_FLAG | (len >> 8) as u8, len as u8]);
+        }
+        payload.extend_from_slice(&reply);
+        payload
+    }
+
+    /// Recognises an Identity Reply in a network payload, returning `None` for
+    /// anything else.
+    pub fn from_payload(payload: &[u8]) -> Option<Self> {
+        let (start, len) = read_header(payload)?;
+        match parse_midi_list_ref(payload.get(start..start + len)?, len).ok()? {
+            MidiEventRef::IdentityReply(identity) => Some(identity),
+            _ => None,
+        }
+    }
+
+    /// Recognises an Identity Reply in a complete RTP packet, returning `None` for
+    /// anything else.
+    pub fn from_packet(packet: &[u8]) -> Option<Self> {
+        let (_, payload) = RtpHeader::parse(packet).ok()?;
+        Self::from_payload(payload)
+    }
+}
@@ -0,0 +1,55 @@
+//! # Session counters for monitoring
+//!
+//! Both session types maintain a [`Metrics`] instance. Counters only ever increase
+//! until [`Metrics::reset`] is called; those that do not apply to a session's role
+//! stay at zero.
+
+/// # Counters maintained by a session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Metrics {
+    /// Packets built by a master session, including maintenance packets
+    pub packets_sent: u64,
+    /// Packets accepted and parsed by a slave session
+    pub packets_parsed: u64,
+    /// Valid packets without MIDI commands (LEN=0), such as keepalives or
+    /// journal-only packets, accepted by a slave session
+    pub empty_payloads: u64,
+    /// Packets rejected because the RTP header was invalid
+    pub rtp_header_errors: u64,
+    /// Payloads shorter than the smallest valid payload
+    pub too_short_errors: u64,
+    /// Payloads whose header LEN exceeds the data that follows it
+    pub length_errors: u64,
+    /// Payloads whose MIDI list could not be parsed
+    pub midi_errors: u64,
+    /// Quarter frames that were out of range or completed an invalid timecode
+    pub timecode_errors: u64,
+    /// Packets dropped because their sequence number was already received
+    pub duplicates_dropped: u64,
+    /// Packets dropped because they arrived after a newer packet
+    pub out_of_order_dropped: u64,
+    /// Packets dropped because they came from another SSRC than the first one seen
+    pub foreign_ssrc_dropped: u64,
+    /// Number of times the sequence number jumped forward by more than one
+    pub sequence_gaps: u64,
+    /// Total number of packets skipped over by sequence gaps
+    pub packets_lost: u64,
+    /// Quarter-frame cycles completed (sent by a master, received by a slave)
+    pub quarter_frame_cycles: u64,
+    /// Full Frames emitted by the master refresh policy
+    pub full_frames_refreshed: u64,
+    /// Resync requests sent by a slave or received by a master
+    pub resync_requests: u64,
+    /// Difference between the last received master position and the slave's
+    /// estimate just before it arrived, in microseconds. Positive when the master
+    /// was ahead. `None` until a time update arrives while playing.
+    pub last_drift_us: Option<i64>,
+}
+
+impl Metrics {
+    /// Sets every counter back to zero.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
@@ -0,0 +1,124 @@
+//! # UDP transports
+//!
+//! Glue between the sessions and a UDP socket. A sender wraps a
+//! [`MasterSession`](crate::netsync::MasterSession): it sends events as they
+//! happen and, on each tick, acts on resync requests, refreshes the Full Frame and
+//! keeps an idle link alive. A receiver wraps a
+//! [`SlaveSession`](crate::netsync::SlaveSession) and hands out the events it
+//! decodes, sending resync requests back to the master as losses are detected.
+//! Identity Replies travel the same way, so a sender's session collects the
+//! identities of the receivers that answer its discovery request.
+//!
+//! `NetsyncSender` and `NetsyncReceiver` run on a Tokio socket, with the `tokio`
+//! feature; [`blocking`] has the same over a [`std::net::UdpSocket`]. Both
+//! share the session handling, so they only differ in how they wait.
+//!
+//! Session time is the time elapsed since each side was created.
+//!
+//! ```no_run
+//! # #[cfg(feature = "tokio")]
+//! # mod example {
+//! use rtp_midi_netsync::mtc::MtcFullFrame;
+//! use rtp_midi_netsync::netsync::net::{NetsyncSender, TICK_INTERVAL};
+//! use rtp_midi_netsync::netsync::NetsyncConfig;
+//! use tokio::net::UdpSocket;
+//!
+//! # async fn example() -> std::io::Result<()> {
+//! let socket = UdpSocket::bind("0.0.0.0:0").await?;
+//! socket.connect("192.168.1.20:5004").await?;
+//! let mut sender = NetsyncSender::new(socket, NetsyncConfig::default());
+//! sender.seek(MtcFullFrame::default()).await?;
+//! sender.play().await?;
+//!
+//! let mut interval = tokio::time::interval(TICK_INTERVAL);
+//! loop {
+//!     interval.tick().await;
+//!     sender.tick().await?;
+//! }
+//! # }
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::midi::{MmcCommand, RealtimeMessage};
+use crate::mtc::{MtcFullFrame, VlcTickT};
+use crate::netsync::NetsyncHandler;
+
+pub mod blocking;
+mod state;
+#[cfg(feature = "tokio")]
+mod udp;
+
+#[cfg(feature = "tokio")]
+pub use udp::{NetsyncReceiver, NetsyncSender};
+
+/// How often a sender's tick should be called, and how often a receiver checks
+/// for feedback to send while no packet arrives.
+pub const TICK_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Idle time after which a sender sends a keepalive.
+pub const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Largest datagram read from the socket, well above
+/// [`MAX_PACKET_LENGTH`](crate::netsync::MAX_PACKET_LENGTH). Anything longer is
+/// not a netsync packet.
+const RECV_BUFFER_LENGTH: usize = 1500;
+
+/// # Event decoded by a receiver.
+///
+/// One variant per [`NetsyncHandler`] callback.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NetsyncEvent {
+    /// See [`NetsyncHandler::on_play`]
+    Play,
+    /// See [`NetsyncHandler::on_stop`]
+    Stop,
+    /// See [`NetsyncHandler::on_locate`]
+    Locate(MtcFullFrame),
+    /// See [`NetsyncHandler::on_mmc`]
+    Mmc(MmcCommand),
+    /// See [`NetsyncHandler::on_time_update`]
+    TimeUpdate(VlcTickT),
+    /// See [`NetsyncHandler::on_realtime`]
+    Realtime(RealtimeMessage),
+    /// See [`NetsyncHandler::on_song_position`]
+    SongPosition(u16),
+    /// See [`NetsyncHandler::on_other`]
+    Other(Vec<u8>),
+}
+
+impl NetsyncHandler for VecDeque<NetsyncEvent> {
+    fn on_play(&mut self) {
+        self.push_back(NetsyncEvent::Play);
+    }
+
+    fn on_stop(&mut self) {
+        self.push_back(NetsyncEvent::Stop);
+    }
+
+    fn on_locate(&mut self, position: MtcFullFrame) {
+        self.push_back(NetsyncEvent::Locate(position));
+    }
+
+    fn on_mmc(&mut self, command: MmcCommand) {
+        self.push_back(NetsyncEvent::Mmc(command));
+    }
+
+    fn on_time_update(&mut self, us: VlcTickT) {
+        self.push_back(NetsyncEvent::TimeUpdate(us));
+    }
+
+    fn on_realtime(&mut self, message: RealtimeMessage) {
+        self.push_back(NetsyncEvent::Realtime(message));
+    }
+
+    fn on_song_position(&mut self, position: u16) {
+        self.push_back(NetsyncEvent::SongPosition(position));
+    }
+
+    fn on_other(&mut self, bytes: &[u8]) {
+        self.push_back(NetsyncEvent::Other(bytes.to_vec()));
+    }
+}
@@ -0,0 +1,312 @@
+//! # Blocking UDP transport
+//!
+//! The same links as the Tokio transport, over a [`std::net::UdpSocket`] for
+//! programs without an async runtime. A [`Sender`] can run its periodic work
+//! on a thread of its own; a [`Receiver`] sends resync requests while blocked in
+//! [`Receiver::recv`].
+//!
+//! ```no_run
+//! use std::net::UdpSocket;
+//!
+//! use rtp_midi_netsync::mtc::MtcFullFrame;
+//! use rtp_midi_netsync::netsync::net::blocking::Sender;
+//! use rtp_midi_netsync::netsync::NetsyncConfig;
+//!
+//! # fn main() -> std::io::Result<()> {
+//! let socket = UdpSocket::bind("0.0.0.0:0")?;
+//! socket.connect("192.168.1.20:5004")?;
+//! let mut sender = Sender::new(socket, NetsyncConfig::default())?;
+//! sender.start_maintenance()?;
+//! sender.seek(MtcFullFrame::default())?;
+//! sender.play()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io;
+use std::net::UdpSocket;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use super::state::{self, ReceiverState, SenderState};
+use super::{NetsyncEvent, RECV_BUFFER_LENGTH, TICK_INTERVAL};
+use crate::midi::{MidiEvent, MmcCommand};
+use crate::mtc::{MtcFullFrame, VlcTickT};
+use crate::netsync::{MasterSession, NetsyncConfig, SlaveSession};
+
+/// What a [`Sender`] shares with its maintenance thread.
+#[derive(Debug)]
+struct Shared {
+    socket: UdpSocket,
+    state: Mutex<SenderState>,
+    stop: AtomicBool,
+}
+
+impl Shared {
+    fn state(&self) -> MutexGuard<'_, SenderState> {
+        // The state stays consistent even if a holder panicked
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn send(&self, state: &mut SenderState, packet: &[u8]) -> io::Result<()> {
+        self.socket.send(packet)?;
+        state.sent();
+        Ok(())
+    }
+
+    fn tick(&self) -> io::Result<()> {
+        let mut state = self.state();
+        let mut buf = [0; RECV_BUFFER_LENGTH];
+        loop {
+            match self.socket.recv(&mut buf) {
+                Ok(len) => state.receive(&buf[..len]),
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                // Such as ICMP port unreachable while the slave is not listening yet
+                Err(err) if err.kind() == io::ErrorKind::ConnectionRefused => break,
+                Err(err) => return Err(err),
+            }
+        }
+
+        for packet in state.due_packets() {
+            self.send(&mut state, &packet)?;
+        }
+        Ok(())
+    }
+}
+
+/// # Sending side of a netsync link over UDP.
+///
+/// The socket must be connected to the slave, and also receives the slave's
+/// resync requests. It is switched to non-blocking mode, so that checking for
+/// them never waits.
+///
+/// Methods take `&self`, so the sender can be shared between threads. Dropping it
+/// stops its maintenance thread.
+#[derive(Debug)]
+pub struct Sender {
+    shared: Arc<Shared>,
+    maintenance: Option<JoinHandle<()>>,
+}
+
+impl Sender {
+    /// # Creates a sender on a socket connected to the slave, with a session using `config`.
+    ///
+    /// # Errors
+    ///
+    /// Any error from switching the socket to non-blocking mode.
+    pub fn new(socket: UdpSocket, config: NetsyncConfig) -> io::Result<Self> {
+        socket.set_nonblocking(true)?;
+        Ok(Self {
+            shared: Arc::new(Shared {
+                socket,
+                state: Mutex::new(SenderState::new(config)),
+                stop: AtomicBool::new(false),
+            }),
+            maintenance: None,
+        })
+    }
+
+    /// # Starts a thread calling [`Sender::tick`] every [`TICK_INTERVAL`].
+    ///
+    /// The thread runs until the sender is dropped. Socket errors it meets are
+    /// not reported; the next tick tries again. Does nothing if the thread is
+    /// already running.
+    ///
+    /// # Errors
+    ///
+    /// Any error from spawning the thread.
+    pub fn start_maintenance(&mut self) -> io::Result<()> {
+        if self.maintenance.is_some() {
+            return Ok(());
+        }
+        let shared = Arc::clone(&self.shared);
+        let handle = thread::Builder::new()
+            .name("netsync-maintenance".into())
+            .spawn(move || {
+                while !shared.stop.load(Ordering::Relaxed) {
+                    let _ = shared.tick();
+                    thread::sleep(TICK_INTERVAL);
+                }
+            })?;
+        self.maintenance = Some(handle);
+        Ok(())
+    }
+
+    /// # Sends a sync event.
+    ///
+    /// # Errors
+    ///
+    /// * `io::ErrorKind::InvalidInput` - If the session does not send the event,
+    ///   wrapping the `NetsyncError` from [`MasterSession::packet_for_event`].
+    /// * Any error from the socket.
+    pub fn send_event(&self, event: &MidiEvent) -> io::Result<()> {
+        let mut state = self.shared.state();
+        let packet = state.event_packet(event)?;
+        self.shared.send(&mut state, &packet)
+    }
+
+    /// Sends an MMC Play.
+    pub fn play(&self) -> io::Result<()> {
+        self.send_event(&MidiEvent::Mmc(MmcCommand::Play))
+    }
+
+    /// Sends an MMC Stop.
+    pub fn stop(&self) -> io::Result<()> {
+        self.send_event(&MidiEvent::Mmc(MmcCommand::Stop))
+    }
+
+    /// Sends an MMC Locate to `position`.
+    pub fn seek(&self, position: MtcFullFrame) -> io::Result<()> {
+        self.send_event(&state::locate(position))
+    }
+
+    /// # Performs the session's periodic work.
+    ///
+    /// Hands any resync request or Identity Reply received from the slave to the
+    /// session, sends the maintenance packets from [`MasterSession::tick`], and
+    /// sends a keepalive if nothing was sent for
+    /// [`KEEPALIVE_INTERVAL`](super::KEEPALIVE_INTERVAL).
+    /// Call it every [`TICK_INTERVAL`], unless [`Sender::start_maintenance`] does.
+    pub fn tick(&self) -> io::Result<()> {
+        self.shared.tick()
+    }
+
+    /// Runs `f` on the session. Packets it builds are not sent.
+    pub fn with_session<R>(&self, f: impl FnOnce(&mut MasterSession) -> R) -> R {
+        f(self.shared.state().session_mut())
+    }
+
+    /// Returns the socket.
+    pub fn socket(&self) -> &UdpSocket {
+        &self.shared.socket
+    }
+}
+
+impl Drop for Sender {
+    fn drop(&mut self) {
+        if let Some(handle) = self.maintenance.take() {
+            self.shared.stop.store(true, Ordering::Relaxed);
+            let _ = handle.join();
+        }
+    }
+}
+
+/// # Receiving side of a netsync link over UDP.
+///
+/// [`Receiver::recv`] reads packets from the socket and feeds them to the
+/// session until one carries an event. Packets the session drops or rejects are
+/// counted in its [`Metrics`](crate::netsync::Metrics). Resync requests go back
+/// to the address of the last packet received.
+///
+/// The socket's own read timeout is set to [`TICK_INTERVAL`], so that resync
+/// requests go out while no packet arrives; use [`Receiver::set_read_timeout`]
+/// to bound how long [`Receiver::recv`] waits.
+#[derive(Debug)]
+pub struct Receiver {
+    socket: UdpSocket,
+    state: ReceiverState,
+    read_timeout: Option<Duration>,
+    buf: Vec<u8>,
+}
+
+impl Receiver {
+    /// # Creates a receiver on a bound socket, with a session using `config`.
+    ///
+    /// [`Receiver::recv`] waits without limit until a read timeout is set.
+    ///
+    /// # Errors
+    ///
+    /// Any error from setting the socket's read timeout.
+    pub fn new(socket: UdpSocket, config: NetsyncConfig) -> io::Result<Self> {
+        socket.set_nonblocking(false)?;
+        socket.set_read_timeout(Some(TICK_INTERVAL))?;
+        Ok(Self {
+            socket,
+            state: ReceiverState::new(config),
+            read_timeout: None,
+            buf: vec![0; RECV_BUFFER_LENGTH],
+        })
+    }
+
+    /// Sets how long [`Receiver::recv`] waits for an event, without limit if
+    /// `None`. The wait is measured in steps of [`TICK_INTERVAL`].
+    pub fn set_read_timeout(&mut self, timeout: Option<Duration>) {
+        self.read_timeout = timeout;
+    }
+
+    /// Returns how long [`Receiver::recv`] waits for an event.
+    pub fn read_timeout(&self) -> Option<Duration> {
+        self.read_timeout
+    }
+
+    /// # Waits for the next event.
+    ///
+    /// # Errors
+    ///
+    /// * `io::ErrorKind::TimedOut` - If no event arrived within the read timeout.
+    /// * `io::ErrorKind::InvalidData` - If the session has failed.
+    /// * Any other error from the socket.
+    pub fn recv(&mut self) -> io::Result<NetsyncEvent> {
+        let deadline = self.read_timeout.map(|timeout| Instant::now() + timeout);
+        loop {
+            if let Some(event) = self.state.next_event() {
+                return Ok(event);
+            }
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return Err(io::ErrorKind::TimedOut.into());
+            }
+
+            match self.socket.recv_from(&mut self.buf) {
+                Ok((len, peer)) => {
+                    self.state.receive(&self.buf[..len], peer)?;
+                    self.send_feedback();
+                    continue;
+                }
+                // The read timeout, reported as either kind depending on the platform
+                Err(err)
+                    if matches!(
+                        err.kind(),
+                        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                    ) => {}
+                // A resync request reached a master that is gone
+                Err(err) if err.kind() == io::ErrorKind::ConnectionRefused => {}
+                Err(err) => return Err(err),
+            }
+            self.send_feedback();
+        }
+    }
+
+    /// Returns the master position now, as estimated by the session's tracker.
+    pub fn position(&self) -> Option<VlcTickT> {
+        self.state.position()
+    }
+
+    /// Returns the session time now, the clock of the session's tracker.
+    pub fn now_us(&self) -> VlcTickT {
+        self.state.now_us()
+    }
+
+    /// Returns the session.
+    pub fn session(&self) -> &SlaveSession {
+        self.state.session()
+    }
+
+    /// Returns the session for direct use.
+    pub fn session_mut(&mut self) -> &mut SlaveSession {
+        self.state.session_mut()
+    }
+
+    /// Returns the socket.
+    pub fn socket(&self) -> &UdpSocket {
+        &self.socket
+    }
+
+    /// Sends the session's resync request, if one is due.
+    fn send_feedback(&mut self) {
+        if let Some((packet, peer)) = self.state.feedback() {
+            let _ = self.socket.send_to(&packet, peer);
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! # Transport-agnostic session handling
+//!
+//! What each side sends and does with what it receives, independent of how the
+//! bytes move. The Tokio and blocking transports only do socket I/O around these.
+
+use std::collections::VecDeque;
+use std::io;
+use std::net::SocketAddr;
+use std::time::Instant;
+
+use super::{NetsyncEvent, KEEPALIVE_INTERVAL};
+use crate::error::NetsyncError;
+use crate::midi::{DeviceIdentity, MidiEvent, MmcCommand};
+use crate::mtc::{MtcFullFrame, VlcTickT};
+use crate::netsync::{MasterSession, NetsyncConfig, SlaveFeedback, SlaveSession};
+
+/// Returns the microseconds elapsed since `epoch`.
+fn elapsed_us(epoch: Instant) -> VlcTickT {
+    epoch.elapsed().as_micros() as VlcTickT
+}
+
+/// Returns the MMC Locate to `position`.
+pub(super) fn locate(position: MtcFullFrame) -> MidiEvent {
+    MidiEvent::Mmc(MmcCommand::Locate {
+        hour: position.hours,
+        minute: position.minutes,
+        second: position.seconds,
+        frame: position.frames,
+        subframe: 0,
+    })
+}
+
+/// Master session and its clock.
+#[derive(Debug)]
+pub(super) struct SenderState {
+    session: MasterSession,
+    epoch: Instant,
+    last_sent: Instant,
+}
+
+impl SenderState {
+    pub(super) fn new(config: NetsyncConfig) -> Self {
+        let now = Instant::now();
+        Self {
+            session: MasterSession::with_config(config),
+            epoch: now,
+            last_sent: now,
+        }
+    }
+
+    /// # Builds the packet for `event`.
+    ///
+    /// # Errors
+    ///
+    /// `io::ErrorKind::InvalidInput`, wrapping the `NetsyncError` from
+    /// [`MasterSession::packet_for_event`].
+    pub(super) fn event_packet(&mut self, event: &MidiEvent) -> io::Result<Vec<u8>> {
+        self.session
+            .packet_for_event(event, elapsed_us(self.epoch))
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))
+    }
+
+    /// Hands a datagram received from the slave to the session, if it is a
+    /// resync request or an Identity Reply.
+    pub(super) fn receive(&mut self, datagram: &[u8]) {
+        if let Some(feedback) = SlaveFeedback::from_packet(datagram) {
+            self.session.handle_feedback(feedback);
+        } else if let Some(identity) = DeviceIdentity::from_packet(datagram) {
+            self.session.handle_identity_reply(identity);
+        }
+    }
+
+    /// Returns the maintenance packets from [`MasterSession::tick`], then a
+    /// keepalive if nothing was sent for [`KEEPALIVE_INTERVAL`].
+    pub(super) fn due_packets(&mut self) -> Vec<Vec<u8>> {
+        let now_us = elapsed_us(self.epoch);
+        let mut packets = self.session.tick(now_us);
+        if packets.is_empty() && self.last_sent.elapsed() >= KEEPALIVE_INTERVAL {
+            packets.push(self.session.keepalive(now_us));
+        }
+        packets
+    }
+
+    /// Records that a packet went out, postponing the next keepalive.
+    pub(super) fn sent(&mut self) {
+        self.last_sent = Instant::now();
+    }
+
+    #[cfg(feature = "tokio")]
+    pub(super) fn session(&self) -> &MasterSession {
+        &self.session
+    }
+
+    pub(super) fn session_mut(&mut self) -> &mut MasterSession {
+        &mut self.session
+    }
+}
+
+/// Slave session, its clock and the events it decoded but not yet handed out.
+#[derive(Debug)]
+pub(super) struct ReceiverState {
+    session: SlaveSession,
+    epoch: Instant,
+    peer: Option<SocketAddr>,
+    events: VecDeque<NetsyncEvent>,
+}
+
+impl ReceiverState {
+    pub(super) fn new(config: NetsyncConfig) -> Self {
+        Self {
+            session: SlaveSession::with_config(config),
+            epoch: Instant::now(),
+            peer: None,
+            events: VecDeque::new(),
+        }
+    }
+
+    /// Returns the next decoded event.
+    pub(super) fn next_event(&mut self) -> Option<NetsyncEvent> {
+        self.events.pop_front()
+    }
+
+    /// # Feeds a datagram received from `peer` to the session.
+    ///
+    /// Resync requests and Identity Replies go back to the last peer. Packets the
+    /// session drops or rejects are counted in its metrics.
+    ///
+    /// # Errors
+    ///
+    /// `io::ErrorKind::InvalidData` if the session has failed.
+    pub(super) fn receive(&mut self, datagram: &[u8], peer: SocketAddr) -> io::Result<()> {
+        self.peer = Some(peer);
+        let arrival_us = self.now_us();
+        let result = self
+            .session
+            .feed_packet(datagram, arrival_us, &mut self.events);
+        if let Err(err @ NetsyncError::SessionFailed) = result {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, err));
+        }
+        Ok(())
+    }
+
+    /// Returns the session's resync request, or else its Identity Reply, and where
+    /// to send it, if one is due. A request that cannot be sent may be dropped; the
+    /// session repeats it.
+    pub(super) fn feedback(&mut self) -> Option<(Vec<u8>, SocketAddr)> {
+        let peer = self.peer?;
+        let now_us = self.now_us();
+        let packet = self
+            .session
+            .feedback_to_send(now_us)
+            .or_else(|| self.session.identity_reply_to_send(now_us))?;
+        Some((packet, peer))
+    }
+
+    pub(super) fn position(&self) -> Option<VlcTickT> {
+        self.session.tracker().position_at(self.now_us())
+    }
+
+    pub(super) fn now_us(&self) -> VlcTickT {
+        elapsed_us(self.epoch)
+    }
+
+    pub(super) fn session(&self) -> &SlaveSession {
+        &self.session
+    }
+
+    pub(super) fn session_mut(&mut self) -> &mut SlaveSession {
+        &mut self.session
+    }
+}
@@ -0,0 +1,204 @@
+//! # Tokio UDP transport
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use tokio::io::ReadBuf;
+use tokio::net::UdpSocket;
+use tokio::time::{Interval, MissedTickBehavior};
+
+use super::state::{self, ReceiverState, SenderState};
+use super::{NetsyncEvent, RECV_BUFFER_LENGTH, TICK_INTERVAL};
+use crate::midi::{MidiEvent, MmcCommand};
+use crate::mtc::{MtcFullFrame, VlcTickT};
+use crate::netsync::{MasterSession, NetsyncConfig, SlaveSession};
+
+/// # Sending side of a netsync link over UDP.
+///
+/// The socket must be connected to the slave, and also receives the slave's
+/// resync requests.
+#[derive(Debug)]
+pub struct NetsyncSender {
+    socket: UdpSocket,
+    state: SenderState,
+}
+
+impl NetsyncSender {
+    /// Creates a sender on a socket connected to the slave, with a session
+    /// using `config`.
+    pub fn new(socket: UdpSocket, config: NetsyncConfig) -> Self {
+        Self {
+            socket,
+            state: SenderState::new(config),
+        }
+    }
+
+    /// # Sends a sync event.
+    ///
+    /// # Errors
+    ///
+    /// * `io::ErrorKind::InvalidInput` - If the session does not send the event,
+    ///   wrapping the `NetsyncError` from [`MasterSession::packet_for_event`].
+    /// * Any error from the socket.
+    pub async fn send_event(&mut self, event: &MidiEvent) -> io::Result<()> {
+        let packet = self.state.event_packet(event)?;
+        self.send(&packet).await
+    }
+
+    /// Sends an MMC Play.
+    pub async fn play(&mut self) -> io::Result<()> {
+        self.send_event(&MidiEvent::Mmc(MmcCommand::Play)).await
+    }
+
+    /// Sends an MMC Stop.
+    pub async fn stop(&mut self) -> io::Result<()> {
+        self.send_event(&MidiEvent::Mmc(MmcCommand::Stop)).await
+    }
+
+    /// Sends an MMC Locate to `position`.
+    pub async fn seek(&mut self, position: MtcFullFrame) -> io::Result<()> {
+        self.send_event(&state::locate(position)).await
+    }
+
+    /// # Performs the session's periodic work.
+    ///
+    /// Hands any resync request or Identity Reply received from the slave to the
+    /// session, sends the maintenance packets from [`MasterSession::tick`], and
+    /// sends a keepalive if nothing was sent for
+    /// [`KEEPALIVE_INTERVAL`](super::KEEPALIVE_INTERVAL).
+    /// Call it every [`TICK_INTERVAL`].
+    pub async fn tick(&mut self) -> io::Result<()> {
+        let mut buf = [0; RECV_BUFFER_LENGTH];
+        loop {
+            match self.socket.try_recv(&mut buf) {
+                Ok(len) => self.state.receive(&buf[..len]),
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                // Such as ICMP port unreachable while the slave is not listening yet
+                Err(err) if err.kind() == io::ErrorKind::ConnectionRefused => break,
+                Err(err) => return Err(err),
+            }
+        }
+
+        for packet in self.state.due_packets() {
+            self.send(&packet).await?;
+        }
+        Ok(())
+    }
+
+    /// Returns the session.
+    pub fn session(&self) -> &MasterSession {
+        self.state.session()
+    }
+
+    /// Returns the session for direct use. Packets it builds are not sent.
+    pub fn session_mut(&mut self) -> &mut MasterSession {
+        self.state.session_mut()
+    }
+
+    /// Returns the socket.
+    pub fn socket(&self) -> &UdpSocket {
+        &self.socket
+    }
+
+    async fn send(&mut self, packet: &[u8]) -> io::Result<()> {
+        self.socket.send(packet).await?;
+        self.state.sent();
+        Ok(())
+    }
+}
+
+/// # Receiving side of a netsync link over UDP.
+///
+/// Polling the stream reads packets from the socket and feeds them to the
+/// session, yielding the events they carry. Packets the session drops or rejects
+/// yield nothing and are counted in its [`Metrics`](crate::netsync::Metrics); an
+/// item is an error only if the socket fails or the session has failed. Resync
+/// requests go back to the address of the last packet received.
+#[derive(Debug)]
+pub struct NetsyncReceiver {
+    socket: UdpSocket,
+    state: ReceiverState,
+    interval: Interval,
+    buf: Vec<u8>,
+}
+
+impl NetsyncReceiver {
+    /// Creates a receiver on a bound socket, with a session using `config`.
+    pub fn new(socket: UdpSocket, config: NetsyncConfig) -> Self {
+        let mut interval = tokio::time::interval(TICK_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        Self {
+            socket,
+            state: ReceiverState::new(config),
+            interval,
+            buf: vec![0; RECV_BUFFER_LENGTH],
+        }
+    }
+
+    /// Returns the master position now, as estimated by the session's tracker.
+    pub fn position(&self) -> Option<VlcTickT> {
+        self.state.position()
+    }
+
+    /// Returns the session time now, the clock of the session's tracker.
+    pub fn now_us(&self) -> VlcTickT {
+        self.state.now_us()
+    }
+
+    /// Returns the session.
+    pub fn session(&self) -> &SlaveSession {
+        self.state.session()
+    }
+
+    /// Returns the session for direct use.
+    pub fn session_mut(&mut self) -> &mut SlaveSession {
+        self.state.session_mut()
+    }
+
+    /// Returns the socket.
+    pub fn socket(&self) -> &UdpSocket {
+        &self.socket
+    }
+
+    /// Sends the session's resync request, if one is due, without waiting.
+    fn send_feedback(&mut self) {
+        if let Some((packet, peer)) = self.state.feedback() {
+            let _ = self.socket.try_send_to(&packet, peer);
+        }
+    }
+}
+
+impl Stream for NetsyncReceiver {
+    type Item = io::Result<NetsyncEvent>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(event) = this.state.next_event() {
+                return Poll::Ready(Some(Ok(event)));
+            }
+            if this.interval.poll_tick(cx).is_ready() {
+                this.send_feedback();
+                continue;
+            }
+
+            let mut buf = ReadBuf::new(&mut this.buf);
+            let peer = match this.socket.poll_recv_from(cx, &mut buf) {
+                Poll::Ready(Ok(peer)) => peer,
+                Poll::Ready(Err(err)) if err.kind() == io::ErrorKind::ConnectionRefused => {
+                    // A resync request reached a master that is gone
+                    continue;
+                }
+                Poll::Ready(Err(err)) => return Poll::Ready(Some(Err(err))),
+                Poll::Pending => return Poll::Pending,
+            };
+            let len = buf.filled().len();
+            if let Err(err) = this.state.receive(&this.buf[..len], peer) {
+                return Poll::Ready(Some(Err(err)));
+            }
+            this.send_feedback();
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! # Fixed-capacity payloads
+//!
+//! [`Payload`] holds one master flow payload inline, so real-time threads can
+//! build and pass payloads around by value without touching the heap. Its
+//! capacity is [`MAX_PAYLOAD_LENGTH`], the longest payload the master flow
+//! produces.
+
+use core::fmt;
+use core::ops::Deref;
+
+use crate::netsync::MAX_PAYLOAD_LENGTH;
+
+/// # Network payload stored inline.
+///
+/// Returned by [`master_netsync_flow_fixed`](crate::netsync::master_netsync_flow_fixed)
+/// and [`quarter_frame_payloads`](crate::netsync::quarter_frame_payloads), and
+/// dereferences to the payload bytes.
+#[derive(Clone, Copy)]
+pub struct Payload {
+    pub(super) buf: [u8; MAX_PAYLOAD_LENGTH],
+    pub(super) len: usize,
+}
+
+impl Payload {
+    /// Largest number of bytes a payload holds.
+    pub const CAPACITY: usize = MAX_PAYLOAD_LENGTH;
+}
+
+impl Deref for Payload {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+}
+
+impl AsRef<[u8]> for Payload {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl PartialEq for Payload {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for Payload {}
+
+impl fmt::Debug for Payload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Payload").field(&&**self).finish()
+    }
+}
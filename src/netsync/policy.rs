@@ -0,0 +1,234 @@
+//! # Strict and lenient slave parsing
+//!
+//! Real-world senders deviate from the payloads this crate produces in a handful of
+//! recurring ways. [`ParsePolicy`] makes the slave's reaction to each of them
+//! explicit, using the findings of [`verify_payload`] as the single list of
+//! deviations:
+//!
+//! | Deviation                                 | Strict   | Lenient                      |
+//! |-------------------------------------------|----------|------------------------------|
+//! | Recovery journal (J flag)                 | rejected | journal ignored              |
+//! | Bytes after LEN                           | rejected | ignored                      |
+//! | Bytes after a complete message within LEN | rejected | ignored                      |
+//! | Device ID other than broadcast            | rejected | recognised                   |
+//! | SysEx without `F7`                        | rejected | recognised                   |
+//! | MMC Locate length byte other than `06`    | rejected | passed through as `Other`    |
+//! | MMC speed command length byte not `03`    | rejected | passed through as `Other`    |
+//! | MMC Masked Write length byte not `04`     | rejected | passed through as `Other`    |
+//! | Rate bits in a Full Frame or Locate hour  | rejected | masked off the hour          |
+//! | Time field out of range                   | rejected | passed on                    |
+//! | Truncated Quarter Frame or Song Position  | rejected | passed through as `Other`    |
+//!
+//! Payloads that cannot be read at all are rejected under both policies: empty
+//! payloads, LEN of 0 or beyond the payload, delta times (Z flag), running status
+//! (P flag or a missing status byte) and data bytes with the high bit set.
+
+use crate::error::{ParseError, PolicyViolation};
+use crate::midi::{parse_midi_list, parse_midi_list_strict, MidiEvent, MmcCommand};
+use crate::netsync::{read_header, verify_payload, Finding, FindingKind, PayloadError};
+
+/// # How strictly the slave flow treats deviating payloads.
+///
+/// See the [module documentation](self) for the behaviour of each policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum ParsePolicy {
+    /// Reject every deviation from the payloads `master_netsync_flow` produces
+    Strict,
+    /// Accept the common real-world quirks, rejecting only unreadable payloads
+    #[default]
+    Lenient,
+}
+
+impl ParsePolicy {
+    /// Returns `true` if a payload with this finding is rejected under the policy.
+    pub fn rejects(self, kind: &FindingKind) -> bool {
+        match kind {
+            // Valid payloads, only reported for information
+            FindingKind::LongHeader { .. }
+            | FindingKind::Unrecognized
+            | FindingKind::UnusualFrameRate { .. } => false,
+            FindingKind::JournalPresent
+            | FindingKind::TrailingBytes { .. }
+            | FindingKind::ExtraBytes { .. }
+            | FindingKind::PaddedFullFrame { .. }
+            | FindingKind::NonBroadcastDeviceId { .. }
+            | FindingKind::MissingSysexEnd
+            | FindingKind::RateBitsInHour { .. }
+            | FindingKind::ValueOutOfRange { .. }
+            | FindingKind::TruncatedMessage { .. }
+            | FindingKind::InvalidLocateLength { .. }
+            | FindingKind::InvalidMmcLength { .. } => self == ParsePolicy::Strict,
+            FindingKind::EmptyPayload
+            | FindingKind::TruncatedLongHeader
+            | FindingKind::EmptyMidiList
+            | FindingKind::TruncatedMidiList { .. }
+            | FindingKind::DeltaTimePresent
+            | FindingKind::PhantomStatus
+            | FindingKind::MissingStatusByte { .. }
+            | FindingKind::DataByteHighBit { .. }
+            | FindingKind::UnreadableMessage => true,
+        }
+    }
+}
+
+/// Reconstructs a MIDI event from a received payload under an explicit policy.
+///
+/// # Arguments
+///
+/// * `buf` - The received network payload bytes.
+/// * `policy` - Which deviations to reject.
+///
+/// # Returns
+///
+/// Returns the reconstructed `MidiEvent` on success.
+///
+/// # Errors
+///
+/// * `PolicyViolation` - With the first finding the policy rejects, its offset, and
+///   the error of [`parse_midi_list_strict`] for the MIDI list as its cause. If the
+///   policy's parser rejects a payload without such a finding, the finding is
+///   `FindingKind::UnreadableMessage` and the cause is the parser's error.
+pub fn slave_netsync_flow_with_policy(
+    buf: &[u8],
+    policy: ParsePolicy,
+) -> Result<MidiEvent, PolicyViolation> {
+    let report = verify_payload(buf);
+    if let Some(finding) = report
+        .findings
+        .into_iter()
+        .find(|finding| policy.rejects(&finding.kind))
+    {
+        return Err(PolicyViolation {
+            policy,
+            finding,
+            cause: strict_cause(buf),
+        });
+    }
+
+    // The report guarantees a complete header and a non-empty MIDI list within buf,
+    // and a parser rejecting what the report let through is a violation, not a panic
+    let (start, parsed) = match read_header(buf) {
+        Some((start, len)) if len > 0 && start + len <= buf.len() => {
+            let midi = &buf[start..start + len];
+            let parsed = match policy {
+                ParsePolicy::Strict => parse_midi_list_strict(midi, len),
+                ParsePolicy::Lenient => parse_lenient(midi),
+            };
+            (start, parsed)
+        }
+        _ => (0, Err(ParseError::InsufficientHeaderData)),
+    };
+    parsed.map_err(|cause| {
+        let position = match cause {
+            ParseError::InvalidMidiData { position, .. } => position,
+            _ => 0,
+        };
+        PolicyViolation {
+            policy,
+            finding: Finding {
+                offset: start + position,
+                severity: FindingKind::UnreadableMessage.severity(),
+                kind: FindingKind::UnreadableMessage,
+            },
+            cause: Some(cause),
+        }
+    })
+}
+
+/// Returns the error of the strict MIDI parser for the MIDI list of `buf`.
+fn strict_cause(buf: &[u8]) -> Option<ParseError> {
+    let (start, len) = read_header(buf)?;
+    parse_midi_list_strict(&buf[start..], len).err()
+}
+
+/// Parses a policy-checked payload, keeping the reason for a rejection for metrics.
+pub(crate) fn parse_payload_with_policy(
+    buf: &[u8],
+    policy: ParsePolicy,
+) -> Result<MidiEvent, PayloadError> {
+    slave_netsync_flow_with_policy(buf, policy).map_err(|violation| {
+        match violation.finding.kind {
+            // Not even a header and one MIDI byte
+            _ if buf.len() < 2 => PayloadError::TooShort {
+                got: buf.len(),
+                min: 2,
+            },
+            FindingKind::TruncatedMidiList {
+                declared,
+                available,
+            } => PayloadError::LengthMismatch {
+                declared,
+                available,
+            },
+            FindingKind::TrailingBytes {
+                declared,
+                available,
+            } => PayloadError::TrailingData {
+                extra: available - declared,
+            },
+            FindingKind::DeltaTimePresent | FindingKind::PhantomStatus => {
+                PayloadError::UnsupportedFlags { flags: buf[0] >> 4 }
+            }
+            _ => PayloadError::Rejected,
+        }
+    })
+}
+
+/// Parses a MIDI list after normalising universal real-time SysEx quirks.
+fn parse_lenient(midi: &[u8]) -> Result<MidiEvent, ParseError> {
+    const SYSEX_START: u8 = 0xF0;
+    const SYSEX_END: u8 = 0xF7;
+    const UNIVERSAL_REALTIME_ID: u8 = 0x7F;
+
+    if midi.len() < 3 || midi[0] != SYSEX_START || midi[1] != UNIVERSAL_REALTIME_ID {
+        return parse_midi_list(midi, midi.len());
+    }
+
+    // Address to broadcast and terminate, dropping anything after the terminator
+    let mut sysex = match midi.iter().position(|&b| b == SYSEX_END) {
+        Some(end) => midi[..=end].to_vec(),
+        None => {
+            let mut sysex = midi.to_vec();
+            sysex.push(SYSEX_END);
+            sysex
+        }
+    };
+    if sysex.len() > 3 {
+        sysex[2] = UNIVERSAL_REALTIME_ID;
+    }
+
+    Ok(match parse_midi_list(&sysex, sysex.len())? {
+        MidiEvent::MtcFull {
+            hour,
+            minute,
+            second,
+            frame,
+        } => MidiEvent::MtcFull {
+            hour: hour & 0x1F,
+            minute,
+            second,
+            frame,
+        },
+        MidiEvent::Mmc(MmcCommand::Locate {
+            hour,
+            minute,
+            second,
+            frame,
+            subframe,
+        }) => MidiEvent::Mmc(MmcCommand::Locate {
+            hour: hour & 0x1F,
+            minute,
+            second,
+            frame,
+            subframe,
+        }),
+        // Pass other messages through unmodified
+        MidiEvent::Other(_) => parse_midi_list(midi, midi.len())?,
+        event => event,
+    })
+}
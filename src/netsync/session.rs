@@ -0,0 +1,1099 @@
+//! # Stateful master and slave sessions
+//!
+//! Consumers of [`slave_netsync_flow`] all end up writing the same `match` over
+//! [`MidiEvent`], and quarter frames are useless until a full cycle has been
+//! assembled. [`SlaveSession`] owns that state and reports decoded events through
+//! the [`NetsyncHandler`] trait, with timecode already converted to microseconds.
+//!
+//! [`MasterSession`] wraps [`master_netsync_flow`] and keeps track of what has been
+//! sent, so it can schedule maintenance payloads such as periodic Full Frames.
+//!
+//! Sessions exchange complete RTP packets: the master numbers them and stamps its
+//! SSRC, and the slave uses both to drop duplicates, late packets and packets from
+//! other senders. Both maintain [`Metrics`].
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::error::{Error, NetsyncError};
+use crate::midi::{
+    build_midi_list, DeviceIdentity, MidiEvent, MmcCommand, RealtimeMessage,
+    SYSEX_DEVICE_ID_BROADCAST,
+};
+use crate::mtc::{
+    smpte_to_quarter_frames_rate, smpte_to_us_rate, us_to_smpte_rate, MtcFullFrame,
+    MtcQuarterFrame, QuarterFrameAssembler, VlcTickT,
+};
+use crate::netsync::{
+    cue_and_play_events, master_midi_list_len_with, master_netsync_flow,
+    master_netsync_flow_into_with, parse_payload_with_policy, read_header, sync_burst_events,
+    time_update_events, validate_empty_payload, BeatClockTracker, CueOptions, Metrics,
+    NetsyncConfig, ParsePolicy, PayloadError, PositionTracker, SlaveFeedback, TimeUpdateKind,
+    MAX_PAYLOAD_LENGTH,
+};
+use crate::rtp::{RtpHeader, RTP_HEADER_LENGTH, RTP_MIDI_CLOCK_RATE};
+
+/// Minimum time between two resync requests from a slave. Losses detected in the
+/// meantime are coalesced into the next request.
+const FEEDBACK_HOLDOFF_US: VlcTickT = 100_000;
+
+/// Largest RTP packet a master session builds: RTP header and the longest payload.
+pub const MAX_PACKET_LENGTH: usize = RTP_HEADER_LENGTH + MAX_PAYLOAD_LENGTH;
+
+/// Largest number of packets returned by one [`MasterSession::tick`].
+pub const MAX_TICK_PACKETS: usize = 2;
+
+/// Sequence number distance beyond which a packet is considered older than the
+/// last one received rather than newer (RFC 3550, Appendix A.1).
+const SEQUENCE_WINDOW: u16 = 0x8000;
+
+/// # Callbacks invoked by [`SlaveSession::dispatch`].
+///
+/// Every method has an empty default implementation so handlers only need to
+/// implement the events they care about.
+pub trait NetsyncHandler {
+    /// Called when an MMC Play command is received.
+    fn on_play(&mut self) {}
+
+    /// Called when an MMC Stop command is received.
+    fn on_stop(&mut self) {}
+
+    /// Called when an MMC Locate command is received, with the target position.
+    fn on_locate(&mut self, _position: MtcFullFrame) {}
+
+    /// Called when any other MMC transport command is received, such as Pause or
+    /// Deferred Play.
+    fn on_mmc(&mut self, _command: MmcCommand) {}
+
+    /// Called with the master position in microseconds whenever a Full Frame
+    /// arrives or a quarter-frame cycle completes.
+    fn on_time_update(&mut self, _us: VlcTickT) {}
+
+    /// Called when a MIDI Beat Clock real-time message is received.
+    fn on_realtime(&mut self, _message: RealtimeMessage) {}
+
+    /// Called when a Song Position Pointer is received, in sixteenth notes.
+    fn on_song_position(&mut self, _position: u16) {}
+
+    /// Called when an Identity Request is received, with the device ID it asks,
+    /// `7F` for every device.
+    fn on_identity_request(&mut self, _device_id: u8) {}
+
+    /// Called when an Identity Reply is received.
+    fn on_identity_reply(&mut self, _identity: DeviceIdentity) {}
+
+    /// Called with the raw bytes of any message that is not a sync event, manufacturer
+    /// SysEx included.
+    fn on_other(&mut self, _bytes: &[u8]) {}
+}
+
+/// # Outcome of feeding an RTP packet to a [`SlaveSession`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PacketStatus {
+    /// The packet was parsed and its event dispatched
+    Accepted,
+    /// The packet repeats the last sequence number received and was dropped
+    Duplicate,
+    /// The packet is older than the last one received and was dropped
+    OutOfOrder,
+    /// The packet comes from another SSRC than the session's sender and was dropped
+    ForeignSsrc,
+    /// The packet carries the receiver's own SSRC and was dropped, returned by
+    /// [`Endpoint`](crate::netsync::Endpoint) only
+    Looped,
+    /// The packet is a resync request or an Identity Reply and was handed to the
+    /// master side, returned by [`Endpoint`](crate::netsync::Endpoint) only
+    Feedback,
+    /// The packet is valid but carries no MIDI command (LEN=0), such as a keepalive
+    /// or a journal-only packet; it still counts for sequence tracking
+    NoCommands,
+}
+
+/// # Receiving side of a netsync link.
+///
+/// Parses incoming payloads, assembles quarter frames into complete timecodes
+/// and forwards the results to a [`NetsyncHandler`].
+///
+/// The first SSRC seen through [`SlaveSession::feed_packet`] becomes the session's
+/// sender; packets from any other SSRC are dropped.
+#[derive(Debug, Clone, Default)]
+pub struct SlaveSession {
+    assembler: QuarterFrameAssembler,
+    tracker: PositionTracker,
+    beat_clock: BeatClockTracker,
+    policy: ParsePolicy,
+    ssrc: u32,
+    next_sequence: u16,
+    remote_ssrc: Option<u32>,
+    last_sequence: Option<u16>,
+    pending_feedback: Option<SlaveFeedback>,
+    last_feedback_us: Option<VlcTickT>,
+    identity: Option<DeviceIdentity>,
+    identity_reply_due: bool,
+    metrics: Metrics,
+    failure: Option<Error>,
+}
+
+impl SlaveSession {
+    /// Creates a new session with no partially received timecode.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a session using the SSRC, tracker settings and parse policy from `config`.
+    pub fn with_config(config: NetsyncConfig) -> Self {
+        Self {
+            tracker: PositionTracker::with_config(config.tracker),
+            policy: config.policy,
+            ssrc: config.ssrc,
+            ..Self::default()
+        }
+    }
+
+    /// # Parses a payload and invokes the matching handler callback.
+    ///
+    /// Quarter frames only produce an `on_time_update` call once a complete cycle
+    /// has been received. Full Frames and Locate commands discard any partially
+    /// assembled cycle since they mark a discontinuity.
+    ///
+    /// # Arguments
+    ///
+    /// * `payload` - The received network payload
+    /// * `handler` - Receiver of the decoded events
+    ///
+    /// # Errors
+    ///
+    /// * The framing errors of [`slave_netsync_flow`](crate::netsync::slave_netsync_flow),
+    ///   such as `NetsyncError::TooShort` or `NetsyncError::UnsupportedFlags`, if the
+    ///   payload cannot be framed.
+    /// * `NetsyncError::InvalidSlaveEvent` - If the payload is otherwise rejected by
+    ///   the session's [`ParsePolicy`] or a completed quarter-frame cycle does not
+    ///   form a valid timecode.
+    pub fn dispatch<H: NetsyncHandler + ?Sized>(
+        &mut self,
+        payload: &[u8],
+        handler: &mut H,
+    ) -> Result<(), NetsyncError> {
+        let event = parse_payload_with_policy(payload, self.policy).map_err(NetsyncError::from)?;
+        self.dispatch_event(event, handler).map(|_| ())
+    }
+
+    /// # Processes a complete RTP packet received at `arrival_us`.
+    ///
+    /// Drops duplicates, late packets and packets from foreign SSRCs, then parses
+    /// the payload under the session's [`ParsePolicy`], updates the session's
+    /// [`PositionTracker`] and invokes the
+    /// matching handler callback as [`SlaveSession::dispatch`] does. Packets without
+    /// MIDI commands are validated and reported as [`PacketStatus::NoCommands`].
+    ///
+    /// # Arguments
+    ///
+    /// * `packet` - The received RTP packet
+    /// * `arrival_us` - Local monotonic time at which the packet was received
+    /// * `handler` - Receiver of the decoded events
+    ///
+    /// # Returns
+    ///
+    /// Whether the packet was accepted or why it was dropped.
+    ///
+    /// # Errors
+    ///
+    /// * `NetsyncError::TooShort`, `NetsyncError::DeclaredLengthExceedsBuffer`,
+    ///   `NetsyncError::TrailingData` or `NetsyncError::UnsupportedFlags` - If the
+    ///   payload header does not describe a MIDI list this session can read.
+    /// * `NetsyncError::InvalidSlaveEvent` - If the RTP header or MIDI list cannot be
+    ///   parsed or a completed quarter-frame cycle does not form a valid timecode.
+    ///
+    /// Each of these is counted in the session's [`Metrics`].
+    /// * `NetsyncError::SessionFailed` - If the packet has a fatal error, such as an
+    ///   RTP version other than 2, or the session already failed. See
+    ///   [`SlaveSession::failure`].
+    pub fn feed_packet<H: NetsyncHandler + ?Sized>(
+        &mut self,
+        packet: &[u8],
+        arrival_us: VlcTickT,
+        handler: &mut H,
+    ) -> Result<PacketStatus, NetsyncError> {
+        self.feed_packet_event(packet, arrival_us, handler)
+            .map(|(status, _)| status)
+    }
+
+    /// Same as [`SlaveSession::feed_packet`], also returning the event dispatched
+    /// for an accepted packet, as decoded under the session's [`ParsePolicy`].
+    pub(crate) fn feed_packet_event<H: NetsyncHandler + ?Sized>(
+        &mut self,
+        packet: &[u8],
+        arrival_us: VlcTickT,
+        handler: &mut H,
+    ) -> Result<(PacketStatus, Option<MidiEvent>), NetsyncError> {
+        #[cfg(feature = "tracing")]
+        let span = tracing::trace_span!(
+            "feed_packet",
+            len = packet.len(),
+            sequence = tracing::field::Empty
+        )
+        .entered();
+
+        if self.failure.is_some() {
+            return Err(NetsyncError::SessionFailed);
+        }
+        let (rtp, payload) = RtpHeader::parse(packet)
+            .map_err(|err| self.reject(err.into(), |metrics| metrics.rtp_header_errors += 1))?;
+        #[cfg(feature = "tracing")]
+        span.record("sequence", rtp.sequence_number);
+
+        match self.remote_ssrc {
+            Some(ssrc) if ssrc != rtp.ssrc => {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(ssrc = rtp.ssrc, "foreign SSRC dropped");
+                self.metrics.foreign_ssrc_dropped += 1;
+                return Ok((PacketStatus::ForeignSsrc, None));
+            }
+            _ => self.remote_ssrc = Some(rtp.ssrc),
+        }
+
+        if let Some(last) = self.last_sequence {
+            let delta = rtp.sequence_number.wrapping_sub(last);
+            if delta == 0 {
+                #[cfg(feature = "tracing")]
+                tracing::debug!("duplicate dropped");
+                self.metrics.duplicates_dropped += 1;
+                return Ok((PacketStatus::Duplicate, None));
+            }
+            if delta >= SEQUENCE_WINDOW {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(last, "out-of-order packet dropped");
+                self.metrics.out_of_order_dropped += 1;
+                return Ok((PacketStatus::OutOfOrder, None));
+            }
+            if delta > 1 {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(lost = delta - 1, "sequence gap");
+                self.metrics.sequence_gaps += 1;
+                self.metrics.packets_lost += (delta - 1) as u64;
+                self.pending_feedback
+                    .get_or_insert(SlaveFeedback::PacketLoss);
+            }
+        }
+        self.last_sequence = Some(rtp.sequence_number);
+
+        if let Some((start, 0)) = read_header(payload) {
+            validate_empty_payload(payload, start)
+                .map_err(|err| self.reject(err.into(), |metrics| metrics.midi_errors += 1))?;
+            self.metrics.empty_payloads += 1;
+            return Ok((PacketStatus::NoCommands, None));
+        }
+
+        let event = parse_payload_with_policy(payload, self.policy).map_err(|err| match err {
+            PayloadError::TooShort { .. } => self
+                .reject(NetsyncError::from(err).into(), |metrics| {
+                    metrics.too_short_errors += 1
+                }),
+            PayloadError::LengthMismatch { .. } | PayloadError::TrailingData { .. } => self
+                .reject(NetsyncError::from(err).into(), |metrics| {
+                    metrics.length_errors += 1
+                }),
+            PayloadError::Midi(err) => self.reject(err.into(), |metrics| metrics.midi_errors += 1),
+            PayloadError::UnsupportedFlags { .. } | PayloadError::Rejected => self
+                .reject(NetsyncError::from(err).into(), |metrics| {
+                    metrics.midi_errors += 1
+                }),
+        })?;
+        self.metrics.packets_parsed += 1;
+        #[cfg(feature = "tracing")]
+        tracing::trace!(kind = ?event.kind(), "packet accepted");
+        if matches!(event, MidiEvent::MtcFull { .. }) {
+            // The absolute position has been re-established
+            self.pending_feedback = None;
+        }
+
+        let predicted_us = self.tracker.position_at(arrival_us);
+        let was_playing = self.tracker.is_playing();
+        // Errors are reported by the session's own assembler below
+        let _ = self.tracker.apply_event(&event, arrival_us);
+        self.beat_clock.apply_event(&event, arrival_us);
+
+        let is_time_update = self.dispatch_event(event.clone(), handler)?;
+        if is_time_update && was_playing {
+            if let (Some(predicted), Some(actual)) =
+                (predicted_us, self.tracker.position_at(arrival_us))
+            {
+                self.metrics.last_drift_us = Some(actual as i64 - predicted as i64);
+            }
+        }
+
+        Ok((PacketStatus::Accepted, Some(event)))
+    }
+
+    /// Asks the master to resend its absolute position with the next
+    /// [`SlaveSession::feedback_to_send`].
+    pub fn request_resync(&mut self) {
+        self.pending_feedback = Some(SlaveFeedback::Requested);
+    }
+
+    /// # Returns the feedback packet to send to the master, if any.
+    ///
+    /// A resync request is pending after a sequence gap was detected by
+    /// [`SlaveSession::feed_packet`] or after [`SlaveSession::request_resync`], until
+    /// a Full Frame arrives. Requests are spaced at least 100 ms apart so a burst of
+    /// losses produces a single one.
+    ///
+    /// # Arguments
+    ///
+    /// * `now_us` - Local monotonic time at which the packet would be sent
+    ///
+    /// # Returns
+    ///
+    /// The RTP packet to send back to the master, or `None` if nothing is due.
+    pub fn feedback_to_send(&mut self, now_us: VlcTickT) -> Option<Vec<u8>> {
+        let feedback = self.pending_feedback?;
+        if self
+            .last_feedback_us
+            .is_some_and(|last| now_us.saturating_sub(last) < FEEDBACK_HOLDOFF_US)
+        {
+            return None;
+        }
+
+        self.pending_feedback = None;
+        self.last_feedback_us = Some(now_us);
+        self.metrics.resync_requests += 1;
+
+        let header = RtpHeader::new(self.next_sequence, rtp_timestamp(now_us), self.ssrc);
+        self.next_sequence = self.next_sequence.wrapping_add(1);
+        let mut packet = header.serialize().to_vec();
+        packet.extend_from_slice(&feedback.to_payload());
+        Some(packet)
+    }
+
+    /// Sets the identity this session answers Identity Requests with.
+    ///
+    /// Requests asking `identity.device_id`, or every device, make the next
+    /// [`SlaveSession::identity_reply_to_send`] return a reply. Without an identity,
+    /// requests are only reported to the handler.
+    pub fn set_identity(&mut self, identity: Option<DeviceIdentity>) {
+        self.identity = identity;
+        self.identity_reply_due = false;
+    }
+
+    /// Returns the identity this session answers Identity Requests with.
+    pub fn identity(&self) -> Option<DeviceIdentity> {
+        self.identity
+    }
+
+    /// # Returns the Identity Reply packet to send to the master, if any.
+    ///
+    /// A reply is due once an Identity Request asking this session's device, or
+    /// every device, has been dispatched.
+    ///
+    /// # Arguments
+    ///
+    /// * `now_us` - Local monotonic time at which the packet would be sent
+    ///
+    /// # Returns
+    ///
+    /// The RTP packet to send back to the master, or `None` if nothing is due.
+    pub fn identity_reply_to_send(&mut self, now_us: VlcTickT) -> Option<Vec<u8>> {
+        let identity = self.identity.filter(|_| self.identity_reply_due)?;
+        self.identity_reply_due = false;
+
+        let header = RtpHeader::new(self.next_sequence, rtp_timestamp(now_us), self.ssrc);
+        self.next_sequence = self.next_sequence.wrapping_add(1);
+        let mut packet = header.serialize().to_vec();
+        packet.extend_from_slice(&identity.to_payload());
+        Some(packet)
+    }
+
+    /// Returns the tracker estimating the master position from fed packets.
+    pub fn tracker(&self) -> &PositionTracker {
+        &self.tracker
+    }
+
+    /// Returns the tracker following beat clock messages from fed packets.
+    pub fn beat_clock(&self) -> &BeatClockTracker {
+        &self.beat_clock
+    }
+
+    /// Returns the policy received payloads are parsed under.
+    pub fn parse_policy(&self) -> ParsePolicy {
+        self.policy
+    }
+
+    /// Returns the session counters.
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    /// Sets every session counter back to zero.
+    pub fn reset_metrics(&mut self) {
+        self.metrics.reset();
+    }
+
+    /// Returns the fatal error that stopped the session, if any.
+    ///
+    /// Once set, [`SlaveSession::feed_packet`] drops every packet with
+    /// `NetsyncError::SessionFailed` until [`SlaveSession::clear_failure`] is called.
+    pub fn failure(&self) -> Option<&Error> {
+        self.failure.as_ref()
+    }
+
+    /// Resumes a failed session, for instance once the sender has been reconfigured.
+    pub fn clear_failure(&mut self) {
+        self.failure = None;
+    }
+
+    /// Counts a recoverable error with `count`, or stops the session on a fatal one.
+    ///
+    /// Returns the error to report for the packet.
+    fn reject(&mut self, error: Error, count: impl FnOnce(&mut Metrics)) -> NetsyncError {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(error = %error, recoverable = error.is_recoverable(), "packet rejected");
+        if error.is_recoverable() {
+            count(&mut self.metrics);
+            match error {
+                Error::NetsyncError(flow) => flow.kind,
+                _ => NetsyncError::InvalidSlaveEvent,
+            }
+        } else {
+            self.failure = Some(error);
+            NetsyncError::SessionFailed
+        }
+    }
+
+    /// Invokes the handler callback for `event`, returning `true` if it carried an
+    /// absolute time update.
+    fn dispatch_event<H: NetsyncHandler + ?Sized>(
+        &mut self,
+        event: MidiEvent,
+        handler: &mut H,
+    ) -> Result<bool, NetsyncError> {
+        match event {
+            MidiEvent::MtcQuarter { msg_type, value } => {
+                let frame = MtcQuarterFrame {
+                    frame_type: msg_type,
+                    value,
+                };
+                let completed = self.assembler.feed(frame).map_err(|err| {
+                    self.reject(err.into(), |metrics| metrics.timecode_errors += 1)
+                })?;
+                if let Some(smpte) = completed {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(timecode = ?smpte, "timecode from quarter frames");
+                    self.metrics.quarter_frame_cycles += 1;
+                    handler.on_time_update(smpte_to_us_rate(&smpte, self.assembler.frame_rate()));
+                    return Ok(true);
+                }
+            }
+            MidiEvent::MtcFull {
+                hour,
+                minute,
+                second,
+                frame,
+            } => {
+                self.assembler.reset();
+                let smpte = MtcFullFrame {
+                    hours: hour,
+                    minutes: minute,
+                    seconds: second,
+                    frames: frame,
+                };
+                #[cfg(feature = "tracing")]
+                tracing::debug!(timecode = ?smpte, "timecode from Full Frame");
+                handler.on_time_update(smpte_to_us_rate(&smpte, self.tracker.frame_rate()));
+                return Ok(true);
+            }
+            MidiEvent::Mmc(MmcCommand::Play) => handler.on_play(),
+            MidiEvent::Mmc(MmcCommand::Stop) => handler.on_stop(),
+            MidiEvent::Mmc(MmcCommand::Locate {
+                hour,
+                minute,
+                second,
+                frame,
+                ..
+            }) => {
+                self.assembler.reset();
+                handler.on_locate(MtcFullFrame {
+                    hours: hour,
+                    minutes: minute,
+                    seconds: second,
+                    frames: frame,
+                });
+            }
+            MidiEvent::Mmc(command) => handler.on_mmc(command),
+            MidiEvent::Realtime(message) => handler.on_realtime(message),
+            MidiEvent::SongPosition(position) => handler.on_song_position(position),
+            MidiEvent::IdentityRequest { device_id } => {
+                if self.identity.is_some_and(|identity| {
+                    device_id == SYSEX_DEVICE_ID_BROADCAST || device_id == identity.device_id
+                }) {
+                    self.identity_reply_due = true;
+                }
+                handler.on_identity_request(device_id);
+            }
+            MidiEvent::IdentityReply(identity) => handler.on_identity_reply(identity),
+            event @ MidiEvent::ManufacturerSysex { .. } => {
+                handler.on_other(&build_midi_list(&event))
+            }
+            MidiEvent::Other(bytes) => handler.on_other(&bytes),
+        }
+
+        Ok(false)
+    }
+}
+
+/// # When a [`MasterSession`] resends a Full Frame on its own.
+///
+/// A Full Frame is always due immediately after a discontinuity (a Locate, or a
+/// quarter-frame cycle that does not follow on from the previous one). Both
+/// periodic triggers below are optional and apply only once the position is known.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct FullFrameRefreshPolicy {
+    /// Maximum time between Full Frames, `None` to disable the periodic refresh.
+    pub full_frame_interval_us: Option<VlcTickT>,
+    /// Resend after this many consecutive quarter-frame cycles without a Full Frame,
+    /// `None` to disable.
+    pub quarter_frame_cycles: Option<u32>,
+}
+
+impl Default for FullFrameRefreshPolicy {
+    /// One Full Frame per second, no cycle-count trigger.
+    fn default() -> Self {
+        Self {
+            full_frame_interval_us: Some(1_000_000),
+            quarter_frame_cycles: None,
+        }
+    }
+}
+
+/// # Sending side of a netsync link.
+///
+/// Builds RTP packets for the events the application sends and tracks the
+/// resulting master position, so that [`MasterSession::tick`] can produce the
+/// maintenance packets late-joining or recovering slaves need to resync.
+///
+/// Sequence numbers start at 0 and increase by one per packet, wrapping at 65535.
+#[derive(Debug, Clone, Default)]
+pub struct MasterSession {
+    config: NetsyncConfig,
+    tracker: PositionTracker,
+    assembler: QuarterFrameAssembler,
+    next_sequence: u16,
+    /// Position encoded by the last completed quarter-frame cycle
+    last_cycle_us: Option<VlcTickT>,
+    last_full_frame_us: Option<VlcTickT>,
+    cycles_since_full_frame: u32,
+    full_frame_due: bool,
+    /// Identities collected since the last discovery, in order of arrival
+    identities: Vec<DeviceIdentity>,
+    /// Cycle [`MasterSession::time_update`] is sending, and the next piece to send
+    time_cycle: Option<([MtcQuarterFrame; 8], usize)>,
+    metrics: Metrics,
+}
+
+impl MasterSession {
+    /// Creates a session using the default [`NetsyncConfig`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a session with the SSRC, refresh policy and frame rate from `config`.
+    pub fn with_config(config: NetsyncConfig) -> Self {
+        Self {
+            tracker: PositionTracker::with_config(config.tracker),
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// Creates a session with a custom Full Frame refresh policy.
+    pub fn with_policy(policy: FullFrameRefreshPolicy) -> Self {
+        Self::with_config(NetsyncConfig {
+            refresh: policy,
+            ..NetsyncConfig::default()
+        })
+    }
+
+    /// Returns the active Full Frame refresh policy.
+    pub fn policy(&self) -> FullFrameRefreshPolicy {
+        self.config.refresh
+    }
+
+    /// # Handles feedback received from a slave.
+    ///
+    /// A resync request makes the next [`MasterSession::tick`] send a Full Frame
+    /// (followed by Play if rolling), so a slave recovers within one round trip.
+    /// Use [`SlaveFeedback::from_packet`] to recognise feedback packets.
+    pub fn handle_feedback(&mut self, feedback: SlaveFeedback) {
+        match feedback {
+            SlaveFeedback::PacketLoss | SlaveFeedback::Requested => {
+                self.metrics.resync_requests += 1;
+                self.full_frame_due = true;
+            }
+        }
+    }
+
+    /// # Builds the packet that asks every device on the link to identify itself.
+    ///
+    /// Broadcasts an Identity Request and forgets the identities discovered so far.
+    /// Pass the replies, recognised with [`DeviceIdentity::from_packet`], to
+    /// [`MasterSession::handle_identity_reply`].
+    ///
+    /// # Arguments
+    ///
+    /// * `now_us` - Local monotonic time at which the packet is sent
+    pub fn discover(&mut self, now_us: VlcTickT) -> Vec<u8> {
+        self.identities.clear();
+        let payload = master_netsync_flow(&MidiEvent::IdentityRequest {
+            device_id: SYSEX_DEVICE_ID_BROADCAST,
+        })
+        .expect("Identity Request is sent by the master flow");
+        self.wrap(&payload, now_us)
+    }
+
+    /// Records an identity received in answer to [`MasterSession::discover`].
+    ///
+    /// A device answering more than once is only recorded once.
+    pub fn handle_identity_reply(&mut self, identity: DeviceIdentity) {
+        if !self.identities.contains(&identity) {
+            self.identities.push(identity);
+        }
+    }
+
+    /// Returns the identities discovered since the last [`MasterSession::discover`],
+    /// in order of arrival.
+    pub fn discovered(&self) -> &[DeviceIdentity] {
+        &self.identities
+    }
+
+    /// # Builds the RTP packet for an application event and records it.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The sync event to send
+    /// * `now_us` - Local monotonic time at which the packet is sent
+    ///
+    /// # Returns
+    ///
+    /// The RTP packet to transmit, wrapping the payload produced by `master_netsync_flow`,
+    /// laid out as the session's [`BuildOptions`](crate::netsync::BuildOptions) ask.
+    ///
+    /// # Errors
+    ///
+    /// * `NetsyncError::InvalidMasterEvent` - If the event is not a supported sync
+    ///   event, or a quarter frame is out of range, completes an invalid timecode or
+    ///   is configured with more padding than
+    ///   [`MAX_QUARTER_FRAME_PADDING`](crate::netsync::MAX_QUARTER_FRAME_PADDING).
+    pub fn packet_for_event(
+        &mut self,
+        event: &MidiEvent,
+        now_us: VlcTickT,
+    ) -> Result<Vec<u8>, NetsyncError> {
+        let mut packet = Vec::new();
+        self.packet_for_event_into(event, now_us, &mut packet)?;
+        Ok(packet)
+    }
+
+    /// # Appends the RTP packet for an application event to `packet` and records it.
+    ///
+    /// Same as [`MasterSession::packet_for_event`], without allocating when `packet`
+    /// already has room for [`MAX_PACKET_LENGTH`] more bytes.
+    ///
+    /// # Errors
+    ///
+    /// * `NetsyncError::InvalidMasterEvent` - As for `packet_for_event`. Neither
+    ///   `packet` nor the session are changed.
+    pub fn packet_for_event_into(
+        &mut self,
+        event: &MidiEvent,
+        now_us: VlcTickT,
+        packet: &mut Vec<u8>,
+    ) -> Result<(), NetsyncError> {
+        master_midi_list_len_with(event, &self.config.build)?;
+        self.tracker
+            .apply_event(event, now_us)
+            .map_err(|_| NetsyncError::InvalidMasterEvent)?;
+
+        match event {
+            MidiEvent::MtcQuarter { msg_type, value } => {
+                let frame = MtcQuarterFrame {
+                    frame_type: *msg_type,
+                    value: *value,
+                };
+                if let Ok(Some(smpte)) = self.assembler.feed(frame) {
+                    self.complete_cycle(smpte_to_us_rate(&smpte, self.assembler.frame_rate()));
+                }
+            }
+            MidiEvent::MtcFull { .. } => {
+                self.assembler.reset();
+                self.last_cycle_us = None;
+                self.mark_full_frame_sent(now_us);
+            }
+            MidiEvent::Mmc(MmcCommand::Locate { .. }) => {
+                self.assembler.reset();
+                self.last_cycle_us = None;
+                self.full_frame_due = true;
+            }
+            MidiEvent::Mmc(_)
+            | MidiEvent::Realtime(_)
+            | MidiEvent::SongPosition(_)
+            | MidiEvent::IdentityRequest { .. }
+            | MidiEvent::IdentityReply(_)
+            | MidiEvent::ManufacturerSysex { .. }
+            | MidiEvent::Other(_) => {}
+        }
+
+        self.wrap_into(event, now_us, packet);
+        Ok(())
+    }
+
+    /// # Builds the packets that send a position to slaves and records them.
+    ///
+    /// Same as [`master_time_update`](crate::netsync::master_time_update), at the
+    /// session's frame rate. [`TimeUpdateKind::QuarterFrame`] sends the next piece
+    /// of a cycle instead of the piece due at `position_us`: piece 0 takes the
+    /// timecode at `position_us`, and the following calls send pieces 1 to 7 of
+    /// the same timecode, so call it once per quarter-frame period. A Full Frame or
+    /// a burst starts the next call on a new cycle.
+    ///
+    /// # Arguments
+    ///
+    /// * `position_us` - Position of the master, in microseconds
+    /// * `kind` - Full Frame, the next quarter frame, or a whole cycle
+    /// * `now_us` - Local monotonic time at which the packets are sent
+    ///
+    /// # Returns
+    ///
+    /// The RTP packets to transmit in order.
+    pub fn time_update(
+        &mut self,
+        position_us: VlcTickT,
+        kind: TimeUpdateKind,
+        now_us: VlcTickT,
+    ) -> Result<Vec<Vec<u8>>, NetsyncError> {
+        let rate = self.tracker.frame_rate();
+        let (events, time_cycle) = match kind {
+            TimeUpdateKind::QuarterFrame => {
+                let (pieces, next) = match self.time_cycle {
+                    Some((pieces, next)) if next < pieces.len() => (pieces, next),
+                    _ => {
+                        let smpte = us_to_smpte_rate(position_us, rate);
+                        (smpte_to_quarter_frames_rate(&smpte, rate), 0)
+                    }
+                };
+                let event = MidiEvent::MtcQuarter {
+                    msg_type: pieces[next].frame_type,
+                    value: pieces[next].value,
+                };
+                (vec![event], Some((pieces, next + 1)))
+            }
+            TimeUpdateKind::FullFrame | TimeUpdateKind::QuarterFrameBurst => {
+                (time_update_events(position_us, kind, rate), None)
+            }
+        };
+        let packets = events
+            .iter()
+            .map(|event| self.packet_for_event(event, now_us))
+            .collect::<Result<_, _>>()?;
+        self.time_cycle = time_cycle;
+        Ok(packets)
+    }
+
+    /// # Builds the packets that start slaves rolling from `start` and records them.
+    ///
+    /// The packets carry the payloads of [`sync_burst`](crate::netsync::sync_burst): a Full Frame then an MMC
+    /// Play, to be sent in order and back to back. The tracked position is `start`
+    /// at `now_us`, and rolling from there.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - Timecode playback starts from
+    /// * `now_us` - Local monotonic time at which the packets are sent
+    ///
+    /// # Errors
+    ///
+    /// * `NetsyncError::InvalidMasterEvent` - If a field of `start` is out of range.
+    ///   The session is not changed.
+    pub fn play(
+        &mut self,
+        start: &MtcFullFrame,
+        now_us: VlcTickT,
+    ) -> Result<Vec<Vec<u8>>, NetsyncError> {
+        sync_burst_events(start)?
+            .iter()
+            .map(|event| self.packet_for_event(event, now_us))
+            .collect()
+    }
+
+    /// # Builds the packets that cue slaves to `target_us` and start them there.
+    ///
+    /// The packets carry the payloads of [`cue_and_play`](crate::netsync::cue_and_play):
+    /// a Locate at `now_us`, then a Deferred Play `options.settle_us` later, each
+    /// recorded at the time it is due. The tracked position rolls from
+    /// `target_us` from the Deferred Play on.
+    ///
+    /// # Arguments
+    ///
+    /// * `target_us` - Position playback starts from, in microseconds
+    /// * `options` - Delay to leave between the two packets
+    /// * `now_us` - Local monotonic time at which the first packet is sent
+    ///
+    /// # Returns
+    ///
+    /// The RTP packets to transmit in order, each with the local time to send it at.
+    pub fn cue_play(
+        &mut self,
+        target_us: VlcTickT,
+        options: CueOptions,
+        now_us: VlcTickT,
+    ) -> Vec<(VlcTickT, Vec<u8>)> {
+        let target = us_to_smpte_rate(target_us, self.tracker.frame_rate());
+        let events = cue_and_play_events(&target).expect("timecodes from us_to_smpte are in range");
+        let send_times = [now_us, now_us + options.settle_us];
+        events
+            .iter()
+            .zip(send_times)
+            .map(|(event, send_us)| {
+                let packet = self
+                    .packet_for_event(event, send_us)
+                    .expect("Locate and Deferred Play are supported sync events");
+                (send_us, packet)
+            })
+            .collect()
+    }
+
+    /// # Returns any maintenance packets due at `now_us`.
+    ///
+    /// Call this periodically (for example once per quarter-frame period). When a
+    /// Full Frame is due it is built from the current position estimate, followed by
+    /// an MMC Play if the transport is running so that slaves which missed the
+    /// original Play start rolling too. Between the Locate and the Deferred Play of
+    /// [`MasterSession::cue_play`], it repeats the Locate target without Play.
+    ///
+    /// # Arguments
+    ///
+    /// * `now_us` - Local monotonic time, on the same clock as `packet_for_event`
+    ///
+    /// # Returns
+    ///
+    /// The RTP packets to transmit in order, empty if nothing is due or the position
+    /// is not known yet.
+    pub fn tick(&mut self, now_us: VlcTickT) -> Vec<Vec<u8>> {
+        let mut buf = Vec::new();
+        let mut lengths = [0; MAX_TICK_PACKETS];
+        let count = self.tick_into(now_us, &mut buf, &mut lengths);
+
+        let mut packets = Vec::with_capacity(count);
+        let mut rest = buf.as_slice();
+        for &len in &lengths[..count] {
+            let (packet, tail) = rest.split_at(len);
+            packets.push(packet.to_vec());
+            rest = tail;
+        }
+        packets
+    }
+
+    /// # Appends any maintenance packets due at `now_us` to `packets`, back to back.
+    ///
+    /// Same as [`MasterSession::tick`], without allocating when `packets` already
+    /// has room for `MAX_TICK_PACKETS * MAX_PACKET_LENGTH` more bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `now_us` - Local monotonic time, on the same clock as `packet_for_event`
+    /// * `packets` - Buffer the packets are appended to
+    /// * `lengths` - Receives the length of each appended packet, in order
+    ///
+    /// # Returns
+    ///
+    /// The number of packets appended, at most [`MAX_TICK_PACKETS`].
+    pub fn tick_into(
+        &mut self,
+        now_us: VlcTickT,
+        packets: &mut Vec<u8>,
+        lengths: &mut [usize; MAX_TICK_PACKETS],
+    ) -> usize {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("tick", now_us).entered();
+
+        // While slaves settle on a cue, the refresh repeats it without Play so that
+        // they still wait for the Deferred Play
+        let cued_us = self.tracker.cued_position();
+        let Some(position_us) = cued_us.or_else(|| self.tracker.position_at(now_us)) else {
+            return 0;
+        };
+        if !self.full_frame_is_due(now_us) {
+            return 0;
+        }
+
+        let smpte = us_to_smpte_rate(position_us, self.tracker.frame_rate());
+        let full_frame = MidiEvent::MtcFull {
+            hour: smpte.hours,
+            minute: smpte.minutes,
+            second: smpte.seconds,
+            frame: smpte.frames,
+        };
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            timecode = ?smpte,
+            sequence = self.next_sequence,
+            playing = self.tracker.is_playing(),
+            "Full Frame refresh"
+        );
+        let mut count = 0;
+        lengths[count] = self.wrap_into(&full_frame, now_us, packets);
+        count += 1;
+        self.metrics.full_frames_refreshed += 1;
+        if self.tracker.rolling_at(now_us) && cued_us.is_none() {
+            lengths[count] = self.wrap_into(&MidiEvent::Mmc(MmcCommand::Play), now_us, packets);
+            count += 1;
+        }
+
+        self.mark_full_frame_sent(now_us);
+        count
+    }
+
+    /// # Builds the packet for one MIDI Beat Clock tick.
+    ///
+    /// Beat-clock slaves expect 24 ticks per quarter note, sent continuously.
+    ///
+    /// # Arguments
+    ///
+    /// * `now_us` - Local monotonic time at which the packet is sent
+    pub fn clock_tick(&mut self, now_us: VlcTickT) -> Vec<u8> {
+        self.realtime_packet(RealtimeMessage::Clock, now_us)
+    }
+
+    /// # Builds the packets that start beat-clock slaves at a song position.
+    ///
+    /// A Song Position Pointer is sent first, followed by Start when starting from
+    /// the beginning of the song, or Continue otherwise since Start would rewind
+    /// the slaves to position 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `song_position` - Position in sixteenth notes, 14-bit
+    /// * `now_us` - Local monotonic time at which the packets are sent
+    ///
+    /// # Returns
+    ///
+    /// The RTP packets to transmit in order.
+    pub fn start_at(&mut self, song_position: u16, now_us: VlcTickT) -> Vec<Vec<u8>> {
+        let pointer = master_netsync_flow(&MidiEvent::SongPosition(song_position & 0x3FFF))
+            .expect("Song Position Pointer is a supported sync event");
+        let start = if song_position == 0 {
+            RealtimeMessage::Start
+        } else {
+            RealtimeMessage::Continue
+        };
+        vec![
+            self.wrap(&pointer, now_us),
+            self.realtime_packet(start, now_us),
+        ]
+    }
+
+    /// Builds the packet that resumes beat-clock slaves from their current position.
+    pub fn continue_(&mut self, now_us: VlcTickT) -> Vec<u8> {
+        self.realtime_packet(RealtimeMessage::Continue, now_us)
+    }
+
+    /// Builds the packet that stops beat-clock slaves, keeping their position.
+    pub fn stop(&mut self, now_us: VlcTickT) -> Vec<u8> {
+        self.realtime_packet(RealtimeMessage::Stop, now_us)
+    }
+
+    /// # Builds a keepalive packet, carrying no MIDI command (LEN=0).
+    ///
+    /// Sent when the link is otherwise idle, it shows slaves the master is still
+    /// there and advances the sequence number like any other packet. Slaves report
+    /// it as [`PacketStatus::NoCommands`].
+    pub fn keepalive(&mut self, now_us: VlcTickT) -> Vec<u8> {
+        self.wrap(&[0x00], now_us)
+    }
+
+    /// Returns the session counters.
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    /// Sets every session counter back to zero.
+    pub fn reset_metrics(&mut self) {
+        self.metrics.reset();
+    }
+
+    fn realtime_packet(&mut self, message: RealtimeMessage, now_us: VlcTickT) -> Vec<u8> {
+        let payload = master_netsync_flow(&MidiEvent::Realtime(message))
+            .expect("real-time messages are supported sync events");
+        self.wrap(&payload, now_us)
+    }
+
+    /// Appends the RTP header for the next sequence number and the payload for
+    /// `event`, a supported sync event, to `out`. Returns the packet length.
+    fn wrap_into(&mut self, event: &MidiEvent, now_us: VlcTickT, out: &mut Vec<u8>) -> usize {
+        let start = out.len();
+        let header = RtpHeader::new(self.next_sequence, rtp_timestamp(now_us), self.config.ssrc);
+        self.next_sequence = self.next_sequence.wrapping_add(1);
+        self.metrics.packets_sent += 1;
+
+        out.extend_from_slice(&header.serialize());
+        master_netsync_flow_into_with(event, &self.config.build, out)
+            .expect("caller checked the event is supported");
+        out.len() - start
+    }
+
+    /// Prepends the RTP header for the next sequence number to `payload`.
+    fn wrap(&mut self, payload: &[u8], now_us: VlcTickT) -> Vec<u8> {
+        let header = RtpHeader::new(self.next_sequence, rtp_timestamp(now_us), self.config.ssrc);
+        self.next_sequence = self.next_sequence.wrapping_add(1);
+        self.metrics.packets_sent += 1;
+
+        let mut packet = Vec::with_capacity(header.serialize().len() + payload.len());
+        packet.extend_from_slice(&header.serialize());
+        packet.extend_from_slice(payload);
+        packet
+    }
+
+    fn full_frame_is_due(&self, now_us: VlcTickT) -> bool {
+        let policy = self.config.refresh;
+        let interval_elapsed = match (policy.full_frame_interval_us, self.last_full_frame_us) {
+            (Some(_), None) => true,
+            (Some(interval), Some(last)) => now_us.saturating_sub(last) >= interval,
+            (None, _) => false,
+        };
+        let cycles_elapsed = policy
+            .quarter_frame_cycles
+            .is_some_and(|cycles| self.cycles_since_full_frame >= cycles);
+
+        self.full_frame_due || interval_elapsed || cycles_elapsed
+    }
+
+    /// Counts a completed cycle and flags a Full Frame if it jumped from the previous one.
+    fn complete_cycle(&mut self, position_us: VlcTickT) {
+        self.cycles_since_full_frame += 1;
+        self.metrics.quarter_frame_cycles += 1;
+        if let Some(previous_us) = self.last_cycle_us {
+            // Consecutive cycles are two frames apart
+            let frame_us = self.assembler.frame_rate().frame_duration_us();
+            let expected_us = previous_us + 2 * frame_us;
+            if position_us.abs_diff(expected_us) > frame_us / 2 {
+                self.full_frame_due = true;
+            }
+        }
+        self.last_cycle_us = Some(position_us);
+    }
+
+    fn mark_full_frame_sent(&mut self, now_us: VlcTickT) {
+        self.last_full_frame_us = Some(now_us);
+        self.cycles_since_full_frame = 0;
+        self.full_frame_due = false;
+    }
+}
+
+/// Converts a local time to an RTP-MIDI timestamp, wrapping at 32 bits.
+fn rtp_timestamp(now_us: VlcTickT) -> u32 {
+    // In 128 bits so that late local times still wrap rather than overflow
+    (u128::from(now_us) * u128::from(RTP_MIDI_CLOCK_RATE) / 1_000_000) as u32
+}
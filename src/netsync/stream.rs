@@ -0,0 +1,125 @@
+//! # Payload framing for byte streams
+//!
+//! Serial links deliver payloads as a plain byte stream, split into chunks that
+//! have nothing to do with payload boundaries. [`StreamParser`] buffers the bytes
+//! and hands out one event per complete payload, using the header's LEN to find
+//! where the next payload starts.
+//!
+//! A stream has no datagram boundaries to fall back on after line noise or a
+//! receiver that started listening mid-payload. Bytes that cannot start a payload
+//! are skipped one at a time until a plausible header is found: no J, Z or P flag,
+//! a LEN of at least one that fits the buffer, and a status byte right after it.
+
+use alloc::vec::Vec;
+
+use crate::error::ParseError;
+use crate::midi::{parse_midi_list_ref, MidiEvent, MidiEventRef};
+use crate::netsync::{read_header, MAX_PAYLOAD_LENGTH};
+
+/// # Reassembles payloads from a byte stream of arbitrary chunks.
+///
+/// The buffer is allocated once, at construction: feeding and polling never
+/// allocate, except when [`poll`](Self::poll) returns a pass-through message.
+#[derive(Debug, Clone)]
+pub struct StreamParser {
+    buf: Vec<u8>,
+    /// Index of the first byte not yet consumed
+    start: usize,
+    capacity: usize,
+    /// Bytes skipped while resynchronizing
+    discarded: u64,
+}
+
+impl StreamParser {
+    /// Creates a parser buffering at most `capacity` unread bytes.
+    ///
+    /// Capacities below [`MAX_PAYLOAD_LENGTH`] are raised to it, so that every
+    /// payload the master flow produces fits.
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(MAX_PAYLOAD_LENGTH);
+        Self {
+            buf: Vec::with_capacity(capacity),
+            start: 0,
+            capacity,
+            discarded: 0,
+        }
+    }
+
+    /// Largest number of unread bytes the parser holds.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of bytes fed but not yet consumed by [`poll`](Self::poll).
+    pub fn buffered(&self) -> usize {
+        self.buf.len() - self.start
+    }
+
+    /// Number of bytes skipped so far because they could not start a payload.
+    pub fn discarded(&self) -> u64 {
+        self.discarded
+    }
+
+    /// # Appends received bytes to the buffer.
+    ///
+    /// # Errors
+    ///
+    /// * `ParseError::BufferTooSmall` - If the unread bytes and `bytes` together
+    ///   exceed the capacity. Nothing is appended; poll events to make room.
+    pub fn feed(&mut self, bytes: &[u8]) -> Result<(), ParseError> {
+        let available = self.capacity - self.buffered();
+        if bytes.len() > available {
+            return Err(ParseError::BufferTooSmall {
+                requested: bytes.len(),
+                available,
+            });
+        }
+
+        // Move the unread bytes to the front rather than growing the buffer
+        if self.buf.len() + bytes.len() > self.capacity {
+            self.buf.drain(..self.start);
+            self.start = 0;
+        }
+        self.buf.extend_from_slice(bytes);
+        Ok(())
+    }
+
+    /// Returns the event of the next complete payload, or `None` until more bytes
+    /// are fed.
+    pub fn poll(&mut self) -> Option<MidiEvent> {
+        self.poll_ref().map(MidiEvent::from)
+    }
+
+    /// Same as [`poll`](Self::poll), borrowing pass-through messages from the
+    /// buffer instead of copying them.
+    pub fn poll_ref(&mut self) -> Option<MidiEventRef<'_>> {
+        const FLAGS_OTHER_THAN_B: u8 = 0x70;
+        const STATUS_BIT: u8 = 0x80;
+
+        loop {
+            let unread = &self.buf[self.start..];
+            let &first = unread.first()?;
+            let (header_len, len) = read_header(unread)?;
+            let plausible = first & FLAGS_OTHER_THAN_B == 0
+                && len > 0
+                && header_len + len <= self.capacity
+                && unread
+                    .get(header_len)
+                    .is_none_or(|&status| status & STATUS_BIT != 0);
+            if plausible {
+                let end = header_len + len;
+                if unread.len() < end {
+                    // Wait for the rest of the payload
+                    return None;
+                }
+                if let Ok(event) = parse_midi_list_ref(&unread[header_len..end], len) {
+                    self.start += end;
+                    return Some(event);
+                }
+            }
+            // Not the start of a payload: try the next byte
+            self.start += 1;
+            self.discarded += 1;
+        }
+    }
+}
@@ -0,0 +1,366 @@
+//! # Slave-side master position tracking
+//!
+//! Time updates only arrive every few milliseconds (or not at all while stopped),
+//! so a slave needs to extrapolate between them to know where the master is "now".
+//! [`PositionTracker`] remembers the last decoded timecode, the local time it
+//! arrived at and the transport state, and answers position queries from that.
+//!
+//! It also reports whether the estimate can still be trusted: if sync messages
+//! stop arriving while the transport is playing, the tracker keeps extrapolating
+//! ("freewheels") for a configurable window, then reports [`SyncState::Lost`]
+//! until updates resume.
+
+use crate::error::MtcError;
+use crate::midi::{MidiEvent, MmcCommand};
+use crate::mtc::{
+    smpte_to_us_rate, FrameRate, MtcFullFrame, MtcQuarterFrame, Pull, QuarterFrameAssembler,
+    VlcTickT,
+};
+
+/// Quarter frames between piece 0 of a cycle (the instant the encoded timecode
+/// refers to) and piece 7, which completes it.
+const QUARTER_FRAME_CYCLE_LATENCY: VlcTickT = 7;
+
+/// # Configuration for a [`PositionTracker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct TrackerConfig {
+    /// Frame rate assumed until quarter frame 7 signals the actual one.
+    pub frame_rate: FrameRate,
+    /// Number of quarter-frame periods without any sync message, while playing,
+    /// after which updates are considered to have stopped. `None` disables detection.
+    ///
+    /// Expressed in quarter frames so the timeout follows the active frame rate.
+    pub stale_after_quarter_frames: Option<u32>,
+    /// Number of frames to keep extrapolating once updates have stopped before
+    /// declaring sync lost. `0` declares it lost immediately.
+    pub freewheel_frames: u32,
+    /// Speed of the master timecode relative to the local clock, used to
+    /// extrapolate the position between updates.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pull: Pull,
+}
+
+impl Default for TrackerConfig {
+    /// 30fps, updates considered stopped after 3 missed quarter-frame cycles
+    /// (24 quarter frames, 200 ms), no freewheel, no pull.
+    fn default() -> Self {
+        Self {
+            frame_rate: FrameRate::Fps30,
+            stale_after_quarter_frames: Some(24),
+            freewheel_frames: 0,
+            pull: Pull::None,
+        }
+    }
+}
+
+/// # Trust level of the position estimate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncState {
+    /// No absolute position has been received yet
+    Unlocked,
+    /// The estimate is backed by recent sync messages, or the transport is stopped
+    Locked,
+    /// Updates stopped while playing, but the freewheel window has not elapsed yet
+    Freewheeling,
+    /// Updates stopped while playing for longer than the freewheel window
+    Lost,
+}
+
+/// Last known master position and the local time it was observed at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Anchor {
+    position_us: VlcTickT,
+    arrival_us: VlcTickT,
+}
+
+/// # Estimates the master position from received sync events.
+///
+/// - While playing, the position advances linearly with local time from the last update,
+///   scaled by the configured [`Pull`]
+/// - Stop freezes the position where the master was estimated to be
+/// - Locate and Full Frame jump directly to the transmitted position
+/// - Deferred Play following a Locate rolls from the Locate target once it arrives,
+///   however long the master waited for slaves to settle in between
+/// - Completed quarter-frame cycles re-anchor the estimate on the decoded timecode,
+///   compensated for the time the cycle took to transmit
+///
+/// Quarter frame 7 also updates the active frame rate from its rate bits.
+#[derive(Debug, Clone, Default)]
+pub struct PositionTracker {
+    config: TrackerConfig,
+    frame_rate: FrameRate,
+    assembler: QuarterFrameAssembler,
+    anchor: Option<Anchor>,
+    playing: bool,
+    /// Locate target a Deferred Play starts from, while no other position or
+    /// transport change has superseded it
+    cue_us: Option<VlcTickT>,
+    /// Arrival time of the most recent message proving the master is alive
+    last_update_us: Option<VlcTickT>,
+}
+
+impl PositionTracker {
+    /// Creates a tracker with no known position and the transport stopped.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a tracker with a custom configuration.
+    pub fn with_config(config: TrackerConfig) -> Self {
+        Self {
+            config,
+            frame_rate: config.frame_rate,
+            ..Self::default()
+        }
+    }
+
+    /// # Updates the tracker with a received event.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The decoded event, typically from `slave_netsync_flow`
+    /// * `arrival_us` - Local monotonic time at which the event was received
+    ///
+    /// # Errors
+    ///
+    /// Returns the `MtcError` from the quarter-frame assembler if a quarter frame is
+    /// out of range or completes an invalid timecode. The position is left unchanged.
+    pub fn apply_event(&mut self, event: &MidiEvent, arrival_us: VlcTickT) -> Result<(), MtcError> {
+        match event {
+            MidiEvent::MtcQuarter { msg_type, value } => {
+                let frame = MtcQuarterFrame {
+                    frame_type: *msg_type,
+                    value: *value,
+                };
+                if let Some(smpte) = self.assembler.feed(frame)? {
+                    self.frame_rate = self.assembler.frame_rate();
+                    let latency_us = self
+                        .frame_rate
+                        .quarter_frames_to_us(QUARTER_FRAME_CYCLE_LATENCY);
+                    self.jump(
+                        smpte_to_us_rate(&smpte, self.frame_rate) + latency_us,
+                        arrival_us,
+                    );
+                }
+                self.last_update_us = Some(arrival_us);
+            }
+            MidiEvent::MtcFull {
+                hour,
+                minute,
+                second,
+                frame,
+            }
+            | MidiEvent::Mmc(MmcCommand::Locate {
+                hour,
+                minute,
+                second,
+                frame,
+                ..
+            }) => {
+                self.assembler.reset();
+                let smpte = MtcFullFrame {
+                    hours: *hour,
+                    minutes: *minute,
+                    seconds: *second,
+                    frames: *frame,
+                };
+                let position_us = smpte_to_us_rate(&smpte, self.frame_rate);
+                // A Full Frame repeating the target, as a master refreshing slaves
+                // while they settle sends, keeps the cue
+                let cue_us = match event {
+                    MidiEvent::Mmc(_) => Some(position_us),
+                    _ => self.cue_us.filter(|&cue_us| cue_us == position_us),
+                };
+                self.jump(position_us, arrival_us);
+                self.cue_us = cue_us;
+                self.last_update_us = Some(arrival_us);
+            }
+            MidiEvent::Mmc(MmcCommand::DeferredPlay) => {
+                match self.cue_us {
+                    Some(position_us) => self.jump(position_us, arrival_us),
+                    None => self.freeze(arrival_us),
+                }
+                self.playing = true;
+                self.last_update_us = Some(arrival_us);
+            }
+            MidiEvent::Mmc(MmcCommand::Play) => {
+                self.freeze(arrival_us);
+                self.playing = true;
+                self.last_update_us = Some(arrival_us);
+            }
+            // Only play speed is extrapolated, so Variable Play at any other speed
+            // waits for time updates as Shuttle does
+            MidiEvent::Mmc(MmcCommand::VariablePlay { speed }) if speed.to_f32() == 1.0 => {
+                self.freeze(arrival_us);
+                self.playing = true;
+                self.last_update_us = Some(arrival_us);
+            }
+            // Winding, shuttling, varispeed and pausing leave the position to the
+            // next time update
+            MidiEvent::Mmc(
+                MmcCommand::Stop
+                | MmcCommand::Pause
+                | MmcCommand::FastForward
+                | MmcCommand::Rewind
+                | MmcCommand::Shuttle { .. }
+                | MmcCommand::VariablePlay { .. }
+                | MmcCommand::Eject
+                | MmcCommand::Reset,
+            ) => {
+                self.freeze(arrival_us);
+                self.playing = false;
+            }
+            // Search means moving but not playing, as during a locate pre-roll: the
+            // transport has not stopped, but only the next time update or Play says
+            // where it is
+            MidiEvent::Mmc(MmcCommand::Search { .. }) => {
+                self.freeze(arrival_us);
+                self.playing = false;
+            }
+            // Recording and arming tracks do not change the transport
+            MidiEvent::Mmc(
+                MmcCommand::RecordStrobe | MmcCommand::RecordExit | MmcCommand::MaskedWrite { .. },
+            ) => {}
+            MidiEvent::Realtime(_)
+            | MidiEvent::SongPosition(_)
+            | MidiEvent::IdentityRequest { .. }
+            | MidiEvent::IdentityReply(_)
+            | MidiEvent::ManufacturerSysex { .. }
+            | MidiEvent::Other(_) => {}
+        }
+        Ok(())
+    }
+
+    /// # Updates the tracker with an event stamped on the master's clock.
+    ///
+    /// Using the send time instead of the arrival time removes network latency and
+    /// jitter from the estimate. The offset is typically
+    /// [`ClockSync::offset_us`](crate::applemidi::ClockSync::offset_us).
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The decoded event, typically from `slave_netsync_flow`
+    /// * `remote_us` - Time at which the master sent the event, on its own clock
+    /// * `clock_offset_us` - Master clock minus local clock, in microseconds
+    ///
+    /// # Errors
+    ///
+    /// Same as [`PositionTracker::apply_event`].
+    pub fn apply_remote_event(
+        &mut self,
+        event: &MidiEvent,
+        remote_us: VlcTickT,
+        clock_offset_us: i64,
+    ) -> Result<(), MtcError> {
+        let local_us = remote_us.saturating_add_signed(clock_offset_us.saturating_neg());
+        self.apply_event(event, local_us)
+    }
+
+    /// # Estimates the master position at a local time.
+    ///
+    /// # Arguments
+    ///
+    /// * `now_us` - Local monotonic time, on the same clock as the arrival times
+    ///
+    /// # Returns
+    ///
+    /// The estimated master position in microseconds, or `None` if no absolute
+    /// position has been received yet.
+    pub fn position_at(&self, now_us: VlcTickT) -> Option<VlcTickT> {
+        let anchor = self.anchor?;
+        if self.playing {
+            let elapsed_us = now_us.saturating_sub(anchor.arrival_us);
+            Some(anchor.position_us + self.config.pull.apply(elapsed_us))
+        } else {
+            Some(anchor.position_us)
+        }
+    }
+
+    /// # Reports how trustworthy the estimate is at a local time.
+    ///
+    /// While playing, the estimate becomes [`SyncState::Freewheeling`] once no sync
+    /// message has arrived for the configured number of quarter-frame periods at
+    /// the active frame rate, then [`SyncState::Lost`] once the freewheel window
+    /// has also elapsed (the window end is inclusive). It returns to
+    /// [`SyncState::Locked`] as soon as a message arrives. A stopped transport is
+    /// not expected to send anything and stays locked.
+    ///
+    /// The position keeps being extrapolated in every state; it is up to the
+    /// application to stop following it once sync is lost.
+    ///
+    /// # Arguments
+    ///
+    /// * `now_us` - Local monotonic time, on the same clock as the arrival times
+    pub fn sync_state_at(&self, now_us: VlcTickT) -> SyncState {
+        if self.anchor.is_none() {
+            return SyncState::Unlocked;
+        }
+        let (Some(last_update_us), Some(quarter_frames)) =
+            (self.last_update_us, self.config.stale_after_quarter_frames)
+        else {
+            return SyncState::Locked;
+        };
+
+        let timeout_us = self.frame_rate.quarter_frames_to_us(quarter_frames as u64);
+        let freewheel_us = self
+            .frame_rate
+            .quarter_frames_to_us(4 * self.config.freewheel_frames as u64);
+        let silence_us = now_us.saturating_sub(last_update_us);
+
+        if !self.playing || silence_us <= timeout_us {
+            SyncState::Locked
+        } else if silence_us <= timeout_us + freewheel_us {
+            SyncState::Freewheeling
+        } else {
+            SyncState::Lost
+        }
+    }
+
+    /// Returns the frame rate currently assumed for timing decisions.
+    pub fn frame_rate(&self) -> FrameRate {
+        self.frame_rate
+    }
+
+    /// Returns `true` if the last transport command was Play.
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Returns `true` if playing and the transport started rolling at or before
+    /// `now_us`, as opposed to a Play recorded ahead of the time it is sent at.
+    pub(crate) fn rolling_at(&self, now_us: VlcTickT) -> bool {
+        self.playing && self.anchor.is_none_or(|anchor| anchor.arrival_us <= now_us)
+    }
+
+    /// Returns the position a Deferred Play would roll from, in microseconds, if
+    /// the last position or transport change received was a Locate.
+    pub fn cued_position(&self) -> Option<VlcTickT> {
+        self.cue_us
+    }
+
+    /// Forgets the known position and transport state, keeping the configuration.
+    pub fn reset(&mut self) {
+        *self = Self::with_config(self.config);
+    }
+
+    fn jump(&mut self, position_us: VlcTickT, arrival_us: VlcTickT) {
+        self.anchor = Some(Anchor {
+            position_us,
+            arrival_us,
+        });
+        self.cue_us = None;
+    }
+
+    /// Re-anchors at the current estimate so a transport change does not retroactively
+    /// alter the elapsed time accounting.
+    fn freeze(&mut self, arrival_us: VlcTickT) {
+        if let Some(position_us) = self.position_at(arrival_us) {
+            self.jump(position_us, arrival_us);
+        }
+    }
+}
@@ -0,0 +1,619 @@
+//! # Payload diagnostics for interop debugging
+//!
+//! [`slave_netsync_flow`](crate::netsync::slave_netsync_flow) stays on the fast
+//! path and only reports the first framing problem or `InvalidSlaveEvent`. When a
+//! third-party sender almost works, [`verify_payload`] walks the same bytes slowly
+//! and lists everything that deviates from what this crate expects, with the byte
+//! offset of each problem.
+//!
+//! Offsets count from the first header octet. Full Frame and Locate time fields are
+//! checked against the frame rate signalled in the hour byte, or 30fps when no rate
+//! bits are set, which is how this crate's own master flow sends them.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::midi::{MmcCommand, MTC_QUARTER_FRAME_LENGTH, REALTIME_LENGTH, SONG_POSITION_LENGTH};
+use crate::mtc::FrameRate;
+use crate::netsync::read_header;
+
+/// J flag: a recovery journal follows the MIDI list.
+const FLAG_J: u8 = 0x4;
+/// Z flag: the first MIDI command is preceded by a delta time.
+const FLAG_Z: u8 = 0x2;
+/// P flag: the first MIDI command relies on running status.
+const FLAG_P: u8 = 0x1;
+
+/// # How serious a [`Finding`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Severity {
+    /// Worth knowing, but the payload is handled as the sender intended
+    Info,
+    /// Accepted, but likely not handled the way the sender intended
+    Warning,
+    /// Rejected by the slave flow, or decoded into an invalid event
+    Error,
+}
+
+/// # A problem found in a payload.
+///
+/// With the `serde` feature, serialized with its name in snake case under `kind`,
+/// next to its fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(tag = "kind", rename_all = "snake_case")
+)]
+pub enum FindingKind {
+    /// The payload has no bytes at all
+    EmptyPayload,
+    /// The B flag announces a two-octet header but only one octet is present
+    TruncatedLongHeader,
+    /// The B flag is set: LEN is read from the 12-bit two-octet header
+    LongHeader { len: usize },
+    /// The J flag is set: the recovery journal is ignored
+    JournalPresent,
+    /// The Z flag is set: delta times are not supported
+    DeltaTimePresent,
+    /// The P flag is set: running status is not supported
+    PhantomStatus,
+    /// LEN is 0, so there is no MIDI command
+    EmptyMidiList,
+    /// LEN announces more bytes than the payload holds
+    TruncatedMidiList { declared: usize, available: usize },
+    /// The payload holds more bytes than LEN announces without a journal
+    TrailingBytes { declared: usize, available: usize },
+    /// The MIDI list starts with a data byte instead of a status byte
+    MissingStatusByte { byte: u8 },
+    /// A data byte has its high bit set
+    DataByteHighBit { byte: u8 },
+    /// A SysEx message is not terminated by `F7` within the MIDI list
+    MissingSysexEnd,
+    /// A message is shorter than its type requires
+    TruncatedMessage {
+        message: &'static str,
+        expected: usize,
+        available: usize,
+    },
+    /// Bytes follow a complete message in the MIDI list
+    ExtraBytes { count: usize },
+    /// The length byte of an MMC Locate is not `06`, so it is passed through
+    InvalidLocateLength { byte: u8 },
+    /// The length byte of another MMC command with data is not the one the
+    /// command requires, so it is passed through
+    InvalidMmcLength {
+        command: &'static str,
+        byte: u8,
+        expected: u8,
+    },
+    /// A Full Frame holds extra bytes between its frame byte and `F7`, which the
+    /// slave flow ignores
+    PaddedFullFrame { count: usize },
+    /// A universal real-time SysEx is not addressed to the broadcast device ID,
+    /// so it is passed through instead of being recognised
+    NonBroadcastDeviceId { device_id: u8 },
+    /// A time field is out of range
+    ValueOutOfRange {
+        field: &'static str,
+        value: u8,
+        max: u8,
+    },
+    /// The frame rate is not 30fps, which the plain timecode conversions assume
+    UnusualFrameRate { rate: FrameRate },
+    /// The hour byte of a Full Frame or Locate carries rate bits, which the slave
+    /// flow reads as part of the hour
+    RateBitsInHour { rate: FrameRate },
+    /// The message is not a sync event: a device discovery message, or one passed
+    /// through as `MidiEvent::Other` or `MidiEvent::ManufacturerSysex`
+    Unrecognized,
+    /// The policy's parser rejects a message none of the other findings explain.
+    /// Never reported by [`verify_payload`], only by
+    /// [`slave_netsync_flow_with_policy`](crate::netsync::slave_netsync_flow_with_policy)
+    UnreadableMessage,
+}
+
+impl FindingKind {
+    /// Returns the severity this kind of finding is reported with.
+    pub fn severity(&self) -> Severity {
+        match self {
+            FindingKind::LongHeader { .. }
+            | FindingKind::JournalPresent
+            | FindingKind::ExtraBytes { .. }
+            | FindingKind::UnusualFrameRate { .. }
+            | FindingKind::Unrecognized => Severity::Info,
+            FindingKind::PaddedFullFrame { .. }
+            | FindingKind::NonBroadcastDeviceId { .. }
+            | FindingKind::RateBitsInHour { .. } => Severity::Warning,
+            FindingKind::EmptyPayload
+            | FindingKind::TruncatedLongHeader
+            | FindingKind::DeltaTimePresent
+            | FindingKind::PhantomStatus
+            | FindingKind::EmptyMidiList
+            | FindingKind::TruncatedMidiList { .. }
+            | FindingKind::TrailingBytes { .. }
+            | FindingKind::MissingStatusByte { .. }
+            | FindingKind::DataByteHighBit { .. }
+            | FindingKind::MissingSysexEnd
+            | FindingKind::TruncatedMessage { .. }
+            | FindingKind::InvalidLocateLength { .. }
+            | FindingKind::InvalidMmcLength { .. }
+            | FindingKind::ValueOutOfRange { .. }
+            | FindingKind::UnreadableMessage => Severity::Error,
+        }
+    }
+}
+
+impl fmt::Display for FindingKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FindingKind::EmptyPayload => write!(f, "empty payload"),
+            FindingKind::TruncatedLongHeader => {
+                write!(f, "B flag set but only one header octet present")
+            }
+            FindingKind::LongHeader { len } => {
+                write!(f, "B flag set, two-octet header with LEN={}", len)
+            }
+            FindingKind::JournalPresent => write!(f, "J flag set, recovery journal ignored"),
+            FindingKind::DeltaTimePresent => {
+                write!(f, "Z flag set, delta times are not supported")
+            }
+            FindingKind::PhantomStatus => {
+                write!(f, "P flag set, running status is not supported")
+            }
+            FindingKind::EmptyMidiList => write!(f, "LEN=0, no MIDI command"),
+            FindingKind::TruncatedMidiList {
+                declared,
+                available,
+            } => write!(
+                f,
+                "LEN={} but MIDI list is only {} bytes",
+                declared, available
+            ),
+            FindingKind::TrailingBytes {
+                declared,
+                available,
+            } => write!(f, "LEN={} but MIDI list is {} bytes", declared, available),
+            FindingKind::MissingStatusByte { byte } => {
+                write!(
+                    f,
+                    "data byte 0x{:02X} where a status byte was expected",
+                    byte
+                )
+            }
+            FindingKind::DataByteHighBit { byte } => {
+                write!(f, "data byte 0x{:02X} has its high bit set", byte)
+            }
+            FindingKind::MissingSysexEnd => write!(f, "SysEx not terminated by F7"),
+            FindingKind::TruncatedMessage {
+                message,
+                expected,
+                available,
+            } => write!(
+                f,
+                "{} needs {} bytes but only {} present",
+                message, expected, available
+            ),
+            FindingKind::ExtraBytes { count } => {
+                write!(f, "{} extra bytes after the message", count)
+            }
+            FindingKind::InvalidLocateLength { byte } => write!(
+                f,
+                "MMC Locate length byte 0x{:02X} instead of 0x06, passed through",
+                byte
+            ),
+            FindingKind::InvalidMmcLength {
+                command,
+                byte,
+                expected,
+            } => write!(
+                f,
+                "MMC {} length byte 0x{:02X} instead of 0x{:02X}, passed through",
+                command, byte, expected
+            ),
+            FindingKind::PaddedFullFrame { count } => {
+                write!(f, "{} extra bytes before the Full Frame terminator", count)
+            }
+            FindingKind::NonBroadcastDeviceId { device_id } => write!(
+                f,
+                "device ID 0x{:02X} instead of broadcast, passed through",
+                device_id
+            ),
+            FindingKind::ValueOutOfRange { field, value, max } => {
+                write!(f, "{} value {} exceeds maximum {}", field, value, max)
+            }
+            FindingKind::UnusualFrameRate { rate } => write!(
+                f,
+                "frame rate {:?} signalled, plain timecode conversions assume 30fps",
+                rate
+            ),
+            FindingKind::RateBitsInHour { rate } => write!(
+                f,
+                "hour byte carries rate bits for {:?}, read as part of the hour",
+                rate
+            ),
+            FindingKind::Unrecognized => write!(f, "not a sync message, passed through"),
+            FindingKind::UnreadableMessage => write!(f, "message rejected by the parser"),
+        }
+    }
+}
+
+/// # A problem found in a payload, with where it was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Finding {
+    /// Offset of the offending byte from the start of the payload
+    pub offset: usize,
+    /// How serious the problem is
+    pub severity: Severity,
+    /// What the problem is
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub kind: FindingKind,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "[{}] byte {}: {}", severity, self.offset, self.kind)
+    }
+}
+
+/// # Result of [`verify_payload`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Report {
+    /// Findings in the order of the bytes they refer to
+    pub findings: Vec<Finding>,
+}
+
+impl Report {
+    /// Returns `true` if nothing at all was found.
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// Returns `true` if any finding has `Severity::Error`.
+    pub fn has_errors(&self) -> bool {
+        self.worst() == Some(Severity::Error)
+    }
+
+    /// Returns the highest severity found, or `None` for a clean report.
+    pub fn worst(&self) -> Option<Severity> {
+        self.findings.iter().map(|finding| finding.severity).max()
+    }
+
+    /// Returns `true` if a finding of the given kind was reported.
+    pub fn contains(&self, kind: &FindingKind) -> bool {
+        self.findings.iter().any(|finding| finding.kind == *kind)
+    }
+
+    fn push(&mut self, offset: usize, kind: FindingKind) {
+        self.findings.push(Finding {
+            offset,
+            severity: kind.severity(),
+            kind,
+        });
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_clean() {
+            return write!(f, "no findings");
+        }
+        for (i, finding) in self.findings.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", finding)?;
+        }
+        Ok(())
+    }
+}
+
+/// # Checks a payload and lists everything that deviates from expectations.
+///
+/// This is a diagnostic entry point separate from the slave flow: it never fails,
+/// never panics, and keeps going after the first problem where it can. Every
+/// payload the slave flow rejects produces at least one `Severity::Error` finding.
+///
+/// # Arguments
+///
+/// * `buf` - The network payload bytes, starting with the header.
+///
+/// # Returns
+///
+/// A [`Report`] that is clean for payloads built by `master_netsync_flow`.
+pub fn verify_payload(buf: &[u8]) -> Report {
+    let mut report = Report::default();
+
+    let Some(&first) = buf.first() else {
+        report.push(0, FindingKind::EmptyPayload);
+        return report;
+    };
+    let Some((start, len)) = read_header(buf) else {
+        report.push(0, FindingKind::TruncatedLongHeader);
+        return report;
+    };
+    if start == 2 {
+        report.push(0, FindingKind::LongHeader { len });
+    }
+    let flags = first >> 4;
+    if flags & FLAG_J != 0 {
+        report.push(0, FindingKind::JournalPresent);
+    }
+    if flags & FLAG_Z != 0 {
+        report.push(0, FindingKind::DeltaTimePresent);
+    }
+    if flags & FLAG_P != 0 {
+        report.push(0, FindingKind::PhantomStatus);
+    }
+
+    if len == 0 {
+        report.push(0, FindingKind::EmptyMidiList);
+        return report;
+    }
+    let available = buf.len() - start;
+    if len > available {
+        report.push(
+            0,
+            FindingKind::TruncatedMidiList {
+                declared: len,
+                available,
+            },
+        );
+    }
+    let midi = &buf[start..start + len.min(available)];
+    verify_message(midi, start, &mut report);
+    // With the J flag, the bytes after LEN are the journal
+    if len < available && flags & FLAG_J == 0 {
+        report.push(
+            start + len,
+            FindingKind::TrailingBytes {
+                declared: len,
+                available,
+            },
+        );
+    }
+
+    report
+}
+
+/// Checks the MIDI command at the start of `midi`, located at `base` in the payload.
+fn verify_message(midi: &[u8], base: usize, report: &mut Report) {
+    let Some(&status) = midi.first() else {
+        return;
+    };
+    let complete_len = match status {
+        0x00..=0x7F => {
+            report.push(base, FindingKind::MissingStatusByte { byte: status });
+            return;
+        }
+        // Quarter Frame: F1 0nnn dddd
+        0xF1 => {
+            if !verify_length(
+                midi,
+                base,
+                "Quarter Frame",
+                MTC_QUARTER_FRAME_LENGTH,
+                report,
+            ) {
+                return;
+            }
+            verify_data_bytes(&midi[1..MTC_QUARTER_FRAME_LENGTH], base + 1, report);
+            // Piece 7 carries the rate code in bits 1-2 of its value
+            if midi[1] >> 4 == 7 {
+                verify_rate_code((midi[1] >> 1) & 0x03, base + 1, report);
+            }
+            MTC_QUARTER_FRAME_LENGTH
+        }
+        // Song Position Pointer: F2 lsb msb
+        0xF2 => {
+            if !verify_length(
+                midi,
+                base,
+                "Song Position Pointer",
+                SONG_POSITION_LENGTH,
+                report,
+            ) {
+                return;
+            }
+            verify_data_bytes(&midi[1..SONG_POSITION_LENGTH], base + 1, report);
+            SONG_POSITION_LENGTH
+        }
+        0xF8 | 0xFA | 0xFB | 0xFC => REALTIME_LENGTH,
+        0xF0 => {
+            let Some(end) = midi.iter().position(|&b| b == 0xF7) else {
+                report.push(base + midi.len(), FindingKind::MissingSysexEnd);
+                verify_data_bytes(&midi[1..], base + 1, report);
+                return;
+            };
+            verify_data_bytes(&midi[1..end], base + 1, report);
+            verify_sysex(&midi[..=end], base, report);
+            end + 1
+        }
+        _ => {
+            report.push(base, FindingKind::Unrecognized);
+            return;
+        }
+    };
+
+    if midi.len() > complete_len {
+        report.push(
+            base + complete_len,
+            FindingKind::ExtraBytes {
+                count: midi.len() - complete_len,
+            },
+        );
+    }
+}
+
+/// Checks a terminated SysEx message for Full Frame and MMC contents.
+fn verify_sysex(sysex: &[u8], base: usize, report: &mut Report) {
+    // Three-byte manufacturer ID: F0 00 xx yy ... F7
+    if sysex.get(1) == Some(&0x00) && !verify_length(sysex, base, "Manufacturer SysEx", 5, report) {
+        return;
+    }
+    // Universal real-time: F0 7F devID sub1 ...
+    if sysex.get(1) != Some(&0x7F) {
+        report.push(base, FindingKind::Unrecognized);
+        return;
+    }
+    let Some(&device_id) = sysex.get(2) else {
+        report.push(base, FindingKind::Unrecognized);
+        return;
+    };
+    if device_id != 0x7F {
+        report.push(base + 2, FindingKind::NonBroadcastDeviceId { device_id });
+    }
+
+    match (sysex.get(3), sysex.get(4)) {
+        // Full Frame: F0 7F devID 01 01 hr mn sc fr F7
+        (Some(0x01), Some(0x01)) => {
+            if verify_length(sysex, base, "Full Frame", 10, report) {
+                verify_time_fields(&sysex[5..9], base + 5, report);
+                if sysex.len() > 10 {
+                    report.push(
+                        base + 9,
+                        FindingKind::PaddedFullFrame {
+                            count: sysex.len() - 10,
+                        },
+                    );
+                }
+            }
+        }
+        // Commands without data, such as Stop and Play: F0 7F devID 06 cmd F7
+        (Some(0x06), Some(&command)) if MmcCommand::from_command_byte(command).is_some() => {}
+        // Locate: F0 7F devID 06 44 06 01 hr mn sc fr sf F7
+        (Some(0x06), Some(0x44)) => {
+            if !verify_length(sysex, base, "MMC Locate", 13, report) {
+                return;
+            }
+            if sysex[5] != 0x06 {
+                report.push(
+                    base + 5,
+                    FindingKind::InvalidLocateLength { byte: sysex[5] },
+                );
+                return;
+            }
+            if sysex[6] != 0x01 {
+                report.push(base + 6, FindingKind::Unrecognized);
+                return;
+            }
+            verify_time_fields(&sysex[7..11], base + 7, report);
+            if sysex[11] > 99 {
+                report.push(
+                    base + 11,
+                    FindingKind::ValueOutOfRange {
+                        field: "subframes",
+                        value: sysex[11],
+                        max: 99,
+                    },
+                );
+            }
+        }
+        // Masked Write: F0 7F devID 06 41 04 name byte# mask data F7
+        (Some(0x06), Some(0x41)) => verify_mmc_length(sysex, base, "Masked Write", 0x04, report),
+        // Variable Play: F0 7F devID 06 45 03 sh sm sl F7
+        (Some(0x06), Some(0x45)) => verify_mmc_length(sysex, base, "Variable Play", 0x03, report),
+        // Search: F0 7F devID 06 46 03 sh sm sl F7
+        (Some(0x06), Some(0x46)) => verify_mmc_length(sysex, base, "Search", 0x03, report),
+        // Shuttle: F0 7F devID 06 47 03 sh sm sl F7
+        (Some(0x06), Some(0x47)) => verify_mmc_length(sysex, base, "Shuttle", 0x03, report),
+        _ => report.push(base, FindingKind::Unrecognized),
+    }
+}
+
+/// Checks the length byte of an MMC command carrying `size` data bytes, then that
+/// they are all present.
+fn verify_mmc_length(
+    sysex: &[u8],
+    base: usize,
+    command: &'static str,
+    size: u8,
+    report: &mut Report,
+) {
+    match sysex.get(5) {
+        Some(&byte) if byte != size => report.push(
+            base + 5,
+            FindingKind::InvalidMmcLength {
+                command,
+                byte,
+                expected: size,
+            },
+        ),
+        _ => {
+            verify_length(sysex, base, command, 7 + size as usize, report);
+        }
+    }
+}
+
+/// Reports a truncated message and returns `false` if `midi` is shorter than `expected`.
+fn verify_length(
+    midi: &[u8],
+    base: usize,
+    message: &'static str,
+    expected: usize,
+    report: &mut Report,
+) -> bool {
+    if midi.len() >= expected {
+        return true;
+    }
+    report.push(
+        base,
+        FindingKind::TruncatedMessage {
+            message,
+            expected,
+            available: midi.len(),
+        },
+    );
+    false
+}
+
+fn verify_data_bytes(data: &[u8], base: usize, report: &mut Report) {
+    for (i, &byte) in data.iter().enumerate() {
+        if byte & 0x80 != 0 {
+            report.push(base + i, FindingKind::DataByteHighBit { byte });
+        }
+    }
+}
+
+/// Checks `hr mn sc fr` against the frame rate signalled in the hour byte.
+fn verify_time_fields(fields: &[u8], base: usize, report: &mut Report) {
+    let rate_code = (fields[0] >> 5) & 0x03;
+    let fps = match FrameRate::from_mtc_code(rate_code) {
+        Some(rate) if rate_code != 0 => {
+            report.push(base, FindingKind::RateBitsInHour { rate });
+            rate.nominal_fps() as u8
+        }
+        _ => 30,
+    };
+
+    let limits = [
+        ("hours", fields[0] & 0x1F, 23),
+        ("minutes", fields[1], 59),
+        ("seconds", fields[2], 59),
+        ("frames", fields[3], fps - 1),
+    ];
+    for (i, (field, value, max)) in limits.into_iter().enumerate() {
+        if value > max {
+            report.push(base + i, FindingKind::ValueOutOfRange { field, value, max });
+        }
+    }
+}
+
+fn verify_rate_code(code: u8, offset: usize, report: &mut Report) {
+    match FrameRate::from_mtc_code(code) {
+        Some(FrameRate::Fps30) | None => {}
+        Some(rate) => report.push(offset, FindingKind::UnusualFrameRate { rate }),
+    }
+}
@@ -0,0 +1,212 @@
+//! RTP Fixed Header Parsing and Serialization
+//!
+//! This module implements the RTP fixed header as defined in RFC 3550, Section 5.1,
+//! which precedes the MIDI command section in every RTP-MIDI packet (RFC 6295, Section 2).
+//!
+//! ## Protocol Structure
+//!
+//! ```text
+//! 0                   1                   2                   3
+//! 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+//! +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+//! |V=2|P|X|  CC   |M|     PT      |       sequence number         |
+//! +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+//! |                           timestamp                           |
+//! +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+//! |           synchronization source (SSRC) identifier            |
+//! +=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+
+//! |            contributing source (CSRC) identifiers             |
+//! |                             ....                              |
+//! +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+//! ```
+//!
+//! ## Implementation Note
+//!
+//! Serialization always produces a 12-byte header without padding, extension or
+//! CSRCs. Parsing skips any CSRC list and header extension and strips padding.
+
+use crate::error::ParseError;
+
+/// Length of the RTP fixed header without CSRCs.
+pub const RTP_HEADER_LENGTH: usize = 12;
+
+/// RTP version carried in every packet.
+pub const RTP_VERSION: u8 = 2;
+
+/// Dynamic payload type used for RTP-MIDI by AppleMIDI and rtpMIDI.
+pub const RTP_MIDI_PAYLOAD_TYPE: u8 = 97;
+
+/// RTP timestamp clock rate used for RTP-MIDI packets, in Hz (100 µs units).
+pub const RTP_MIDI_CLOCK_RATE: u32 = 10_000;
+
+/// Represents the fields of an RTP fixed header relevant to RTP-MIDI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RtpHeader {
+    /// The marker bit, set by RTP-MIDI senders when the command section is non-empty.
+    pub marker: bool,
+    /// The 7-bit payload type.
+    pub payload_type: u8,
+    /// Sequence number, incremented by one for each packet sent.
+    pub sequence_number: u16,
+    /// Sampling instant of the packet, in units of the RTP clock rate.
+    pub timestamp: u32,
+    /// Synchronization source identifier of the sender.
+    pub ssrc: u32,
+}
+
+impl RtpHeader {
+    /// Creates a new RTP-MIDI header with the marker bit set.
+    ///
+    /// # Arguments
+    ///
+    /// * `sequence_number` - Sequence number of the packet
+    /// * `timestamp` - RTP timestamp of the packet
+    /// * `ssrc` - Synchronization source identifier of the sender
+    pub fn new(sequence_number: u16, timestamp: u32, ssrc: u32) -> Self {
+        Self {
+            marker: true,
+            payload_type: RTP_MIDI_PAYLOAD_TYPE,
+            sequence_number,
+            timestamp,
+            ssrc,
+        }
+    }
+
+    /// Parses an RTP header and returns it along with the packet payload.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - A complete RTP packet
+    ///
+    /// # Returns
+    ///
+    /// Returns the parsed header and the payload slice following it, with any CSRC
+    /// list, header extension and padding removed.
+    ///
+    /// # Errors
+    ///
+    /// * `ParseError::InsufficientHeaderData` - If the buffer is shorter than the
+    ///   header, its CSRC list, extension or declared padding.
+    /// * `ParseError::UnsupportedRtpVersion` - If the version field is not 2.
+    pub fn parse(buf: &[u8]) -> Result<(Self, &[u8]), ParseError> {
+        if buf.len() < RTP_HEADER_LENGTH {
+            return Err(ParseError::InsufficientHeaderData);
+        }
+
+        let version = buf[0] >> 6;
+        if version != RTP_VERSION {
+            return Err(ParseError::UnsupportedRtpVersion(version));
+        }
+        let has_padding = buf[0] & 0x20 != 0;
+        let has_extension = buf[0] & 0x10 != 0;
+        let csrc_count = (buf[0] & 0x0F) as usize;
+
+        let header = RtpHeader {
+            marker: buf[1] & 0x80 != 0,
+            payload_type: buf[1] & 0x7F,
+            sequence_number: u16::from_be_bytes([buf[2], buf[3]]),
+            timestamp: u32::from_be_bytes([buf[4], buf[5], buf[6], buf[7]]),
+            ssrc: u32::from_be_bytes([buf[8], buf[9], buf[10], buf[11]]),
+        };
+
+        let mut start = RTP_HEADER_LENGTH + 4 * csrc_count;
+        if has_extension {
+            if buf.len() < start + 4 {
+                return Err(ParseError::InsufficientHeaderData);
+            }
+            let words = u16::from_be_bytes([buf[start + 2], buf[start + 3]]) as usize;
+            start += 4 + 4 * words;
+        }
+        let mut end = buf.len();
+        if has_padding {
+            let padding = buf[buf.len() - 1] as usize;
+            end = end.saturating_sub(padding);
+        }
+        if start > end {
+            return Err(ParseError::InsufficientHeaderData);
+        }
+
+        Ok((header, &buf[start..end]))
+    }
+
+    /// Serializes the header into its 12-byte wire form.
+    pub fn serialize(&self) -> [u8; RTP_HEADER_LENGTH] {
+        let mut out = [0u8; RTP_HEADER_LENGTH];
+        out[0] = RTP_VERSION << 6;
+        out[1] = ((self.marker as u8) << 7) | (self.payload_type & 0x7F);
+        out[2..4].copy_from_slice(&self.sequence_number.to_be_bytes());
+        out[4..8].copy_from_slice(&self.timestamp.to_be_bytes());
+        out[8..12].copy_from_slice(&self.ssrc.to_be_bytes());
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_roundtrip() {
+        let header = RtpHeader::new(0xBEEF, 0x0102_0304, 0xCAFE_F00D);
+        let mut packet = header.serialize().to_vec();
+        packet.extend_from_slice(&[0x02, 0xF1, 0x23]);
+
+        let (parsed, payload) = RtpHeader::parse(&packet).unwrap();
+        assert_eq!(parsed, header);
+        assert_eq!(payload, &[0x02, 0xF1, 0x23]);
+    }
+
+    #[test]
+    fn serialize_layout() {
+        let bytes = RtpHeader::new(1, 2, 3).serialize();
+        assert_eq!(
+            bytes,
+            [0x80, 0xE1, 0x00, 0x01, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x03]
+        );
+    }
+
+    #[test]
+    fn parse_skips_csrc_extension_and_padding() {
+        let mut packet = vec![
+            0x80 | 0x20 | 0x10 | 0x01,
+            0x61,
+            0,
+            7,
+            0,
+            0,
+            0,
+            1,
+            0,
+            0,
+            0,
+            2,
+        ];
+        packet.extend_from_slice(&[0xAA; 4]); // One CSRC
+        packet.extend_from_slice(&[0xBE, 0xDE, 0x00, 0x01, 0x11, 0x22, 0x33, 0x44]); // Extension
+        packet.extend_from_slice(&[0x02, 0xF1, 0x23]);
+        packet.extend_from_slice(&[0x00, 0x02]); // Two bytes of padding
+
+        let (parsed, payload) = RtpHeader::parse(&packet).unwrap();
+        assert_eq!(parsed.sequence_number, 7);
+        assert!(!parsed.marker);
+        assert_eq!(payload, &[0x02, 0xF1, 0x23]);
+    }
+
+    #[test]
+    fn parse_invalid_headers() {
+        assert_eq!(
+            RtpHeader::parse(&[0x80; 11]),
+            Err(ParseError::InsufficientHeaderData)
+        );
+        assert_eq!(
+            RtpHeader::parse(&[0x40; 12]),
+            Err(ParseError::UnsupportedRtpVersion(1))
+        );
+        // Two CSRCs declared but absent
+        assert_eq!(
+            RtpHeader::parse(&[0x82, 0x61, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+            Err(ParseError::InsufficientHeaderData)
+        );
+    }
+}
@@ -0,0 +1,8 @@
+//! Test support shared with downstream crates, enabled by the `test-utils` feature.
+
+#[cfg(feature = "alloc")]
+mod lossy;
+pub mod vectors;
+
+#[cfg(feature = "alloc")]
+pub use lossy::{LossyChannel, LossyConfig};
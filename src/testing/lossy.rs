@@ -0,0 +1,161 @@
+//! # In-process lossy link between sessions
+//!
+//! [`LossyChannel`] stands in for the network between a
+//! [`MasterSession`](crate::netsync::MasterSession) and a
+//! [`SlaveSession`](crate::netsync::SlaveSession): packets sent into it are
+//! dropped, duplicated and reordered according to a [`LossyConfig`], and come out
+//! in their delivery order. Every decision is drawn from a generator seeded by the
+//! configuration, so a scenario replays identically on every run.
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+/// # Impairments applied by a [`LossyChannel`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LossyConfig {
+    /// Probability that a packet is dropped, from 0.0 to 1.0
+    pub loss: f64,
+    /// Probability that a delivered packet is delivered twice, from 0.0 to 1.0
+    pub duplication: f64,
+    /// Largest number of later packets a packet can be delivered after; 0 keeps
+    /// the sending order
+    pub max_reorder: usize,
+    /// Seed of the generator behind every decision
+    pub seed: u64,
+}
+
+impl Default for LossyConfig {
+    /// A perfect link: no loss, duplication or reordering.
+    fn default() -> Self {
+        Self {
+            loss: 0.0,
+            duplication: 0.0,
+            max_reorder: 0,
+            seed: 0,
+        }
+    }
+}
+
+/// # Simulated packet link with loss, duplication and reordering.
+///
+/// Each packet is held back for a random number of sends, up to
+/// [`max_reorder`](LossyConfig::max_reorder), and is overtaken by the later
+/// packets released before it. A packet is therefore delivered at most
+/// `max_reorder` places away from where it was sent. Call
+/// [`LossyChannel::flush`] to deliver the packets still held back.
+///
+/// # Example
+///
+/// ```
+/// use rtp_midi_netsync::testing::{LossyChannel, LossyConfig};
+///
+/// let mut channel = LossyChannel::new(LossyConfig::default());
+/// channel.send(&[1]);
+/// assert_eq!(channel.recv(), Some(vec![1]));
+///
+/// let mut channel = LossyChannel::new(LossyConfig {
+///     loss: 1.0,
+///     ..LossyConfig::default()
+/// });
+/// channel.send(&[1]);
+/// assert_eq!(channel.recv(), None);
+/// assert_eq!(channel.dropped(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LossyChannel {
+    config: LossyConfig,
+    rng: u64,
+    /// Packets with the send count at which they are released, in delivery order
+    in_flight: VecDeque<(u64, Vec<u8>)>,
+    sent: u64,
+    dropped: u64,
+    duplicated: u64,
+}
+
+impl LossyChannel {
+    /// Creates an empty channel applying `config`.
+    pub fn new(config: LossyConfig) -> Self {
+        Self {
+            config,
+            rng: config.seed,
+            in_flight: VecDeque::new(),
+            sent: 0,
+            dropped: 0,
+            duplicated: 0,
+        }
+    }
+
+    /// Sends a packet into the channel, which may drop or duplicate it.
+    pub fn send(&mut self, packet: &[u8]) {
+        let index = self.sent;
+        self.sent += 1;
+        if self.chance(self.config.loss) {
+            self.dropped += 1;
+            return;
+        }
+        self.enqueue(index, packet);
+        if self.chance(self.config.duplication) {
+            self.duplicated += 1;
+            self.enqueue(index, packet);
+        }
+    }
+
+    /// Returns the next packet leaving the channel, or `None` if the next one is
+    /// still held back.
+    pub fn recv(&mut self) -> Option<Vec<u8>> {
+        match self.in_flight.front() {
+            Some(&(release, _)) if release < self.sent => {
+                self.in_flight.pop_front().map(|(_, packet)| packet)
+            }
+            _ => None,
+        }
+    }
+
+    /// Delivers every packet still in flight, in delivery order.
+    pub fn flush(&mut self) -> Vec<Vec<u8>> {
+        self.in_flight.drain(..).map(|(_, packet)| packet).collect()
+    }
+
+    /// Number of packets sent into the channel.
+    pub fn sent(&self) -> u64 {
+        self.sent
+    }
+
+    /// Number of packets dropped.
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Number of packets delivered twice.
+    pub fn duplicated(&self) -> u64 {
+        self.duplicated
+    }
+
+    /// Queues the packet sent as number `index`, after every packet released no
+    /// later than it.
+    fn enqueue(&mut self, index: u64, packet: &[u8]) {
+        let delay = self.next_u64() % (self.config.max_reorder as u64 + 1);
+        let release = index + delay;
+        let position = self
+            .in_flight
+            .iter()
+            .position(|&(other, _)| other > release)
+            .unwrap_or(self.in_flight.len());
+        self.in_flight.insert(position, (release, packet.to_vec()));
+    }
+
+    fn chance(&mut self, probability: f64) -> bool {
+        // 53 random bits, the precision of an f64 in [0, 1)
+        let sample = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        sample < probability
+    }
+
+    /// SplitMix64, which gives well-mixed output for any seed, including 0.
+    fn next_u64(&mut self) -> u64 {
+        self.rng = self.rng.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.rng;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
@@ -0,0 +1,228 @@
+//! # Golden payloads for interoperability tests
+//!
+//! Byte-exact payloads in the shapes the senders we interoperate with put on the
+//! wire, each with the event [`slave_netsync_flow`](crate::netsync::slave_netsync_flow)
+//! must decode from it. Unlike round trips through this crate's own master flow,
+//! they catch changes that break compatibility with other implementations.
+//!
+//! The payloads follow the RTP-MIDI command section (RFC 6295) and the MIDI 1.0
+//! MTC and MMC specifications, and are written out by hand rather than built
+//! with this crate:
+//!
+//! * [`Source::Vlc`] - VLC's netsync master: broadcast MTC Full Frame and MMC
+//!   messages with a one-octet header
+//! * [`Source::RtpMidi`] - rtpMIDI sessions: beat clock, Song Position Pointer,
+//!   recovery journals after the MIDI list and MMC addressed to one device
+//! * [`Source::MtcGenerator`] - a hardware MTC generator: quarter frames at 25fps
+//!   and Full Frames at 24fps, 25fps and 29.97fps drop-frame
+//!
+//! [`VECTORS`] lists the single payloads; [`MTC_GENERATOR_CYCLE`] adds a full
+//! quarter frame cycle with the timecode it assembles into.
+
+use crate::midi::{MidiEventRef, MmcCommand, RealtimeMessage};
+use crate::mtc::{FrameRate, MtcFullFrame};
+
+/// Sender a vector imitates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    /// VLC's netsync master
+    Vlc,
+    /// rtpMIDI sessions, such as the Windows driver
+    RtpMidi,
+    /// Hardware MTC generator
+    MtcGenerator,
+}
+
+/// # A payload with its expected decoding.
+#[derive(Debug, Clone)]
+pub struct Vector {
+    /// Unique name, for test failure messages
+    pub name: &'static str,
+    /// Sender the payload imitates
+    pub source: Source,
+    /// The RTP-MIDI payload: header, MIDI list and any journal
+    pub payload: &'static [u8],
+    /// Event the slave flow decodes from `payload`
+    pub event: MidiEventRef<'static>,
+    /// Timecode carried by MTC Full Frame and MMC Locate events, with the rate
+    /// bits of the hours byte cleared
+    pub timecode: Option<MtcFullFrame>,
+}
+
+/// # One cycle of eight quarter frames.
+#[derive(Debug, Clone)]
+pub struct QuarterFrameCycle {
+    /// One payload per quarter frame, pieces 0 to 7
+    pub payloads: [&'static [u8]; 8],
+    /// Timecode the cycle assembles into
+    pub timecode: MtcFullFrame,
+    /// Frame rate signalled by quarter frame 7
+    pub rate: FrameRate,
+}
+
+const fn timecode(hours: u8, minutes: u8, seconds: u8, frames: u8) -> Option<MtcFullFrame> {
+    Some(MtcFullFrame {
+        hours,
+        minutes,
+        seconds,
+        frames,
+    })
+}
+
+/// Single payloads, grouped by source.
+pub const VECTORS: &[Vector] = &[
+    Vector {
+        name: "vlc_mtc_full_frame",
+        source: Source::Vlc,
+        payload: &[
+            0x0A, 0xF0, 0x7F, 0x7F, 0x01, 0x01, 0x61, 0x02, 0x03, 0x04, 0xF7,
+        ],
+        event: MidiEventRef::MtcFull {
+            hour: 0x61,
+            minute: 2,
+            second: 3,
+            frame: 4,
+        },
+        timecode: timecode(1, 2, 3, 4),
+    },
+    Vector {
+        name: "vlc_mmc_play",
+        source: Source::Vlc,
+        payload: &[0x06, 0xF0, 0x7F, 0x7F, 0x06, 0x02, 0xF7],
+        event: MidiEventRef::Mmc(MmcCommand::Play),
+        timecode: None,
+    },
+    Vector {
+        name: "vlc_mmc_stop",
+        source: Source::Vlc,
+        payload: &[0x06, 0xF0, 0x7F, 0x7F, 0x06, 0x01, 0xF7],
+        event: MidiEventRef::Mmc(MmcCommand::Stop),
+        timecode: None,
+    },
+    Vector {
+        name: "vlc_mmc_locate",
+        source: Source::Vlc,
+        payload: &[
+            0x0D, 0xF0, 0x7F, 0x7F, 0x06, 0x44, 0x06, 0x01, 0x61, 0x3B, 0x3B, 0x1D, 0x00, 0xF7,
+        ],
+        event: MidiEventRef::Mmc(MmcCommand::Locate {
+            hour: 0x61,
+            minute: 59,
+            second: 59,
+            frame: 29,
+            subframe: 0,
+        }),
+        timecode: timecode(1, 59, 59, 29),
+    },
+    Vector {
+        name: "rtpmidi_clock",
+        source: Source::RtpMidi,
+        payload: &[0x01, 0xF8],
+        event: MidiEventRef::Realtime(RealtimeMessage::Clock),
+        timecode: None,
+    },
+    Vector {
+        name: "rtpmidi_start",
+        source: Source::RtpMidi,
+        payload: &[0x01, 0xFA],
+        event: MidiEventRef::Realtime(RealtimeMessage::Start),
+        timecode: None,
+    },
+    Vector {
+        name: "rtpmidi_song_position",
+        source: Source::RtpMidi,
+        payload: &[0x03, 0xF2, 0x00, 0x02],
+        event: MidiEventRef::SongPosition(256),
+        timecode: None,
+    },
+    Vector {
+        // J flag: a journal header with no chapters follows the MIDI list
+        name: "rtpmidi_clock_with_journal",
+        source: Source::RtpMidi,
+        payload: &[0x41, 0xF8, 0x00, 0x01, 0x00],
+        event: MidiEventRef::Realtime(RealtimeMessage::Clock),
+        timecode: None,
+    },
+    Vector {
+        // Only the broadcast device ID is decoded as MMC by the plain slave flow
+        name: "rtpmidi_mmc_play_device_16",
+        source: Source::RtpMidi,
+        payload: &[0x06, 0xF0, 0x7F, 0x10, 0x06, 0x02, 0xF7],
+        event: MidiEventRef::Other(&[0xF0, 0x7F, 0x10, 0x06, 0x02, 0xF7]),
+        timecode: None,
+    },
+    Vector {
+        name: "mtc_generator_full_frame_24fps",
+        source: Source::MtcGenerator,
+        payload: &[
+            0x0A, 0xF0, 0x7F, 0x7F, 0x01, 0x01, 0x0A, 0x00, 0x00, 0x17, 0xF7,
+        ],
+        event: MidiEventRef::MtcFull {
+            hour: 0x0A,
+            minute: 0,
+            second: 0,
+            frame: 23,
+        },
+        timecode: timecode(10, 0, 0, 23),
+    },
+    Vector {
+        name: "mtc_generator_full_frame_25fps",
+        source: Source::MtcGenerator,
+        payload: &[
+            0x0A, 0xF0, 0x7F, 0x7F, 0x01, 0x01, 0x21, 0x02, 0x03, 0x04, 0xF7,
+        ],
+        event: MidiEventRef::MtcFull {
+            hour: 0x21,
+            minute: 2,
+            second: 3,
+            frame: 4,
+        },
+        timecode: timecode(1, 2, 3, 4),
+    },
+    Vector {
+        // First label of minute 1 in drop-frame timecode: frames 0 and 1 are skipped
+        name: "mtc_generator_full_frame_2997df",
+        source: Source::MtcGenerator,
+        payload: &[
+            0x0A, 0xF0, 0x7F, 0x7F, 0x01, 0x01, 0x40, 0x01, 0x00, 0x02, 0xF7,
+        ],
+        event: MidiEventRef::MtcFull {
+            hour: 0x40,
+            minute: 1,
+            second: 0,
+            frame: 2,
+        },
+        timecode: timecode(0, 1, 0, 2),
+    },
+    Vector {
+        name: "mtc_generator_quarter_frame_7",
+        source: Source::MtcGenerator,
+        payload: &[0x02, 0xF1, 0x72],
+        event: MidiEventRef::MtcQuarter {
+            msg_type: 7,
+            value: 0x2,
+        },
+        timecode: None,
+    },
+];
+
+/// Quarter frames of 00:00:10:00 at 25fps from a hardware MTC generator.
+pub const MTC_GENERATOR_CYCLE: QuarterFrameCycle = QuarterFrameCycle {
+    payloads: [
+        &[0x02, 0xF1, 0x00],
+        &[0x02, 0xF1, 0x10],
+        &[0x02, 0xF1, 0x2A],
+        &[0x02, 0xF1, 0x30],
+        &[0x02, 0xF1, 0x40],
+        &[0x02, 0xF1, 0x50],
+        &[0x02, 0xF1, 0x60],
+        &[0x02, 0xF1, 0x72],
+    ],
+    timecode: MtcFullFrame {
+        hours: 0,
+        minutes: 0,
+        seconds: 10,
+        frames: 0,
+    },
+    rate: FrameRate::Fps25,
+};
@@ -1 +1,8 @@
+//! Encoding and formatting helpers shared by the parsers and diagnostics.
 
+mod hexdump;
+pub mod midi_len;
+pub mod sevenbit;
+pub mod vlq;
+
+pub use hexdump::{hexdump, hexdump_lines, HexDump, HexDumpLines, HEXDUMP_LINE_LENGTH};
@@ -0,0 +1,98 @@
+//! # Hex rendering of byte slices
+//!
+//! Errors, diagnostics and event descriptions all quote bytes the same way: two
+//! upper-case hex digits per byte, separated by spaces. The adapters here borrow
+//! the bytes and write them straight to the formatter, so rendering never
+//! allocates.
+
+use core::fmt;
+
+/// Bytes per line of [`hexdump_lines`] output.
+pub const HEXDUMP_LINE_LENGTH: usize = 16;
+
+/// Single-line hex rendering of a byte slice, returned by [`hexdump`].
+#[derive(Debug, Clone, Copy)]
+pub struct HexDump<'a> {
+    bytes: &'a [u8],
+    max_len: usize,
+}
+
+/// Renders `bytes` as space-separated hex, such as `F0 7F 7F 06 44`.
+///
+/// At most `max_len` bytes are shown; longer slices end with the number of bytes
+/// left out, such as `F0 7F 7F 06 44 … (+7 bytes)`. An empty slice renders as an
+/// empty string.
+///
+/// # Example
+///
+/// ```
+/// use rtp_midi_netsync::util::hexdump;
+///
+/// let bytes = [0xF0, 0x7F, 0x7F, 0x06, 0x44, 0x06, 0x01, 0x21, 0x00, 0x00, 0x00, 0xF7];
+/// assert_eq!(hexdump(&bytes, 5).to_string(), "F0 7F 7F 06 44 … (+7 bytes)");
+/// ```
+pub fn hexdump(bytes: &[u8], max_len: usize) -> HexDump<'_> {
+    HexDump { bytes, max_len }
+}
+
+impl fmt::Display for HexDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let shown = &self.bytes[..self.bytes.len().min(self.max_len)];
+        for (i, byte) in shown.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{:02X}", byte)?;
+        }
+        let hidden = self.bytes.len() - shown.len();
+        if hidden > 0 {
+            let separator = if shown.is_empty() { "" } else { " " };
+            write!(f, "{}… (+{} bytes)", separator, hidden)?;
+        }
+        Ok(())
+    }
+}
+
+/// Multi-line hex rendering with offsets, returned by [`hexdump_lines`].
+#[derive(Debug, Clone, Copy)]
+pub struct HexDumpLines<'a> {
+    bytes: &'a [u8],
+}
+
+/// Renders `bytes` as lines of [`HEXDUMP_LINE_LENGTH`] bytes, each starting with
+/// the hex offset of its first byte, such as `0010  F7 00`.
+///
+/// Lines are separated by `\n`, without a trailing newline. An empty slice renders
+/// as an empty string.
+///
+/// # Example
+///
+/// ```
+/// use rtp_midi_netsync::util::hexdump_lines;
+///
+/// let bytes: Vec<u8> = (0..18).collect();
+/// assert_eq!(
+///     hexdump_lines(&bytes).to_string(),
+///     "0000  00 01 02 03 04 05 06 07 08 09 0A 0B 0C 0D 0E 0F\n0010  10 11"
+/// );
+/// ```
+pub fn hexdump_lines(bytes: &[u8]) -> HexDumpLines<'_> {
+    HexDumpLines { bytes }
+}
+
+impl fmt::Display for HexDumpLines<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, line) in self.bytes.chunks(HEXDUMP_LINE_LENGTH).enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(
+                f,
+                "{:04X}  {}",
+                i * HEXDUMP_LINE_LENGTH,
+                hexdump(line, HEXDUMP_LINE_LENGTH)
+            )?;
+        }
+        Ok(())
+    }
+}
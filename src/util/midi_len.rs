@@ -0,0 +1,37 @@
+//! # Data byte counts of MIDI messages
+//!
+//! Every MIDI 1.0 message except SysEx has a fixed number of data bytes
+//! following its status byte. [`expected_len`] returns that number, so parsers
+//! and diagnostics look it up in one place:
+//!
+//! | Status      | Message                                   | Data bytes |
+//! |-------------|-------------------------------------------|------------|
+//! | `80`-`BF`   | Note Off, Note On, Poly Pressure, Control | 2          |
+//! | `C0`-`DF`   | Program Change, Channel Pressure          | 1          |
+//! | `E0`-`EF`   | Pitch Bend                                | 2          |
+//! | `F0`, `F7`  | SysEx start and end                       | delimited  |
+//! | `F1`, `F3`  | Quarter Frame, Song Select                | 1          |
+//! | `F2`        | Song Position Pointer                     | 2          |
+//! | `F4`, `F5`  | Undefined System Common                   | unknown    |
+//! | `F6`        | Tune Request                              | 0          |
+//! | `F8`-`FF`   | System Real-Time                          | 0          |
+
+/// Returns the number of data bytes following `status` in a complete message.
+///
+/// # Arguments
+///
+/// * `status` - The status byte of the message
+///
+/// # Returns
+///
+/// The data byte count, or `None` for data bytes (`00`-`7F`), SysEx, whose length
+/// is delimited by `F7`, and the undefined System Common bytes `F4` and `F5`.
+pub const fn expected_len(status: u8) -> Option<usize> {
+    match status {
+        0x00..=0x7F => None,
+        0x80..=0xBF | 0xE0..=0xEF | 0xF2 => Some(2),
+        0xC0..=0xDF | 0xF1 | 0xF3 => Some(1),
+        0xF0 | 0xF7 | 0xF4 | 0xF5 => None,
+        0xF6 | 0xF8..=0xFF => Some(0),
+    }
+}
@@ -0,0 +1,144 @@
+//! # 7-bit packing of binary data for SysEx messages
+//!
+//! SysEx data bytes must have their high bit clear, so binary payloads are sent
+//! in groups of up to 7 bytes, each preceded by a byte collecting their high bits.
+//! Bit `n` of that byte holds the high bit of data byte `n` of the group, which
+//! follows with its high bit cleared. This is the layout Korg documents for its
+//! SysEx data dumps:
+//!
+//! ```text
+//! data:   D0 D1 D2 D3 D4 D5 D6 | D7 ...
+//! packed: H  d0 d1 d2 d3 d4 d5 d6 | H' d7 ...
+//!         H = (D6 >> 7) << 6 | ... | (D1 >> 7) << 1 | D0 >> 7, dn = Dn & 0x7F
+//! ```
+//!
+//! A final group holds the remaining 1-6 bytes, so `n` bytes pack into
+//! `n + ceil(n / 7)` bytes.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::error::ParseError;
+
+/// Data bytes per group
+const GROUP: usize = 7;
+
+/// Returns the number of bytes `len` data bytes pack into.
+pub fn packed_len(len: usize) -> usize {
+    len + len.div_ceil(GROUP)
+}
+
+/// Returns the number of data bytes `len` packed bytes unpack into.
+///
+/// A final group made only of its high-bit byte counts as empty.
+pub fn unpacked_len(len: usize) -> usize {
+    len / (GROUP + 1) * GROUP + (len % (GROUP + 1)).saturating_sub(1)
+}
+
+/// Packs `data` into 7-bit bytes appended to `out`.
+///
+/// # Arguments
+///
+/// * `data` - Binary data, of any length
+/// * `out` - Vector receiving the [`packed_len`] packed bytes
+#[cfg(feature = "alloc")]
+pub fn pack(data: &[u8], out: &mut Vec<u8>) {
+    let start = out.len();
+    out.resize(start + packed_len(data.len()), 0);
+    pack_into(data, &mut out[start..]);
+}
+
+/// Packs `data` into 7-bit bytes at the start of `out`, without allocating.
+///
+/// # Returns
+///
+/// The number of bytes written, [`packed_len`] of `data.len()`.
+///
+/// # Panics
+///
+/// Panics if `out` is shorter than [`packed_len`] of `data.len()`.
+pub fn pack_into(data: &[u8], out: &mut [u8]) -> usize {
+    let len = packed_len(data.len());
+    for (group, packed) in data.chunks(GROUP).zip(out[..len].chunks_mut(GROUP + 1)) {
+        packed[0] = 0;
+        for (i, &byte) in group.iter().enumerate() {
+            packed[0] |= (byte >> 7) << i;
+            packed[i + 1] = byte & 0x7F;
+        }
+    }
+    len
+}
+
+/// Unpacks 7-bit bytes produced by [`pack`], appending the data to `out`.
+///
+/// # Arguments
+///
+/// * `packed` - Packed bytes, such as the body of a SysEx message
+/// * `out` - Vector receiving the data; left unchanged on error
+///
+/// # Errors
+///
+/// Returns the same errors as [`unpack_into`].
+#[cfg(feature = "alloc")]
+pub fn unpack(packed: &[u8], out: &mut Vec<u8>) -> Result<(), ParseError> {
+    let start = out.len();
+    out.resize(start + unpacked_len(packed.len()), 0);
+    match unpack_into(packed, &mut out[start..]) {
+        Ok(_) => Ok(()),
+        Err(err) => {
+            out.truncate(start);
+            Err(err)
+        }
+    }
+}
+
+/// Unpacks 7-bit bytes at the start of `out`, without allocating.
+///
+/// # Returns
+///
+/// The number of bytes written, [`unpacked_len`] of `packed.len()`.
+///
+/// # Errors
+///
+/// * `ParseError::InvalidMidiData` - If a byte has its high bit set, or a high-bit
+///   byte has a bit set for a data byte missing from the final group, with the
+///   byte and its position in `packed`
+/// * `ParseError::BufferTooSmall` - If `packed` ends with a high-bit byte
+///   not followed by any data byte
+///
+/// # Panics
+///
+/// Panics if `out` is shorter than [`unpacked_len`] of `packed.len()`.
+pub fn unpack_into(packed: &[u8], out: &mut [u8]) -> Result<usize, ParseError> {
+    if let Some(position) = packed.iter().position(|&byte| byte & 0x80 != 0) {
+        return Err(ParseError::InvalidMidiData {
+            position,
+            byte: packed[position],
+        });
+    }
+
+    let len = unpacked_len(packed.len());
+    for (index, (group, data)) in packed
+        .chunks(GROUP + 1)
+        .zip(out[..len].chunks_mut(GROUP))
+        .enumerate()
+    {
+        let high_bits = group[0];
+        if high_bits >> (group.len() - 1) != 0 {
+            return Err(ParseError::InvalidMidiData {
+                position: index * (GROUP + 1),
+                byte: high_bits,
+            });
+        }
+        for (i, byte) in data.iter_mut().enumerate() {
+            *byte = group[i + 1] | ((high_bits >> i) & 0x01) << 7;
+        }
+    }
+    if packed.len() % (GROUP + 1) == 1 {
+        return Err(ParseError::BufferTooSmall {
+            requested: packed.len() + 1,
+            available: packed.len(),
+        });
+    }
+    Ok(len)
+}
@@ -0,0 +1,135 @@
+//! # MIDI variable-length quantities
+//!
+//! Delta times (Z flag) and parts of the recovery journal encode integers as
+//! variable-length quantities: 7 bits per byte, most significant group first, with
+//! the high bit set on every byte except the last. At most 4 bytes are used, so
+//! values range up to [`MAX_VALUE`] (28 bits).
+//!
+//! | Value        | Encoding      |
+//! |--------------|---------------|
+//! | `0x00`       | `00`          |
+//! | `0x7F`       | `7F`          |
+//! | `0x80`       | `81 00`       |
+//! | `0x3FFF`     | `FF 7F`       |
+//! | `0x4000`     | `81 80 00`    |
+//! | `0x0FFFFFFF` | `FF FF FF 7F` |
+
+use crate::error::ParseError;
+
+/// Largest value a variable-length quantity can hold.
+pub const MAX_VALUE: u32 = 0x0FFF_FFFF;
+
+/// Largest number of bytes of a variable-length quantity.
+pub const MAX_LENGTH: usize = 4;
+
+const CONTINUATION: u8 = 0x80;
+
+/// Returns the number of bytes [`encode`] writes for `value`.
+///
+/// # Panics
+///
+/// Panics if `value` exceeds [`MAX_VALUE`].
+pub fn encoded_len(value: u32) -> usize {
+    assert!(
+        value <= MAX_VALUE,
+        "VLQ value 0x{:X} exceeds 28 bits",
+        value
+    );
+    match value {
+        0..=0x7F => 1,
+        0x80..=0x3FFF => 2,
+        0x4000..=0x1F_FFFF => 3,
+        _ => 4,
+    }
+}
+
+/// Encodes `value` at the start of `buf` in its shortest form.
+///
+/// # Arguments
+///
+/// * `value` - The value to encode, at most [`MAX_VALUE`]
+/// * `buf` - Destination, at least [`encoded_len`] bytes long
+///
+/// # Returns
+///
+/// The number of bytes written.
+///
+/// # Panics
+///
+/// Panics if `value` exceeds [`MAX_VALUE`] or `buf` is too short.
+pub fn encode(value: u32, buf: &mut [u8]) -> usize {
+    let len = encoded_len(value);
+    for (i, byte) in buf[..len].iter_mut().enumerate() {
+        let shift = 7 * (len - 1 - i);
+        let continuation = if i + 1 < len { CONTINUATION } else { 0 };
+        *byte = ((value >> shift) & 0x7F) as u8 | continuation;
+    }
+    len
+}
+
+/// Decodes the variable-length quantity at the start of `buf`.
+///
+/// Leading `80` bytes, which only add zero bits, are accepted as long as the
+/// quantity fits in [`MAX_LENGTH`] bytes. Use [`decode_strict`] to reject them.
+///
+/// # Arguments
+///
+/// * `buf` - Bytes starting with the quantity; any bytes after it are ignored
+///
+/// # Returns
+///
+/// The value and the number of bytes it occupies.
+///
+/// # Errors
+///
+/// * `ParseError::EmptyBuffer` - If `buf` is empty
+/// * `ParseError::BufferTooSmall` - If `buf` ends on a byte with the continuation
+///   bit set
+/// * `ParseError::InvalidMidiData` - If the quantity is longer than 4 bytes, with
+///   the fourth byte and its position
+pub fn decode(buf: &[u8]) -> Result<(u32, usize), ParseError> {
+    if buf.is_empty() {
+        return Err(ParseError::EmptyBuffer);
+    }
+
+    let mut value = 0u32;
+    for (i, &byte) in buf.iter().take(MAX_LENGTH).enumerate() {
+        value = value << 7 | (byte & 0x7F) as u32;
+        if byte & CONTINUATION == 0 {
+            return Ok((value, i + 1));
+        }
+    }
+    if buf.len() < MAX_LENGTH {
+        Err(ParseError::BufferTooSmall {
+            requested: buf.len() + 1,
+            available: buf.len(),
+        })
+    } else {
+        Err(ParseError::InvalidMidiData {
+            position: MAX_LENGTH - 1,
+            byte: buf[MAX_LENGTH - 1],
+        })
+    }
+}
+
+/// Decodes a variable-length quantity, rejecting non-canonical encodings.
+///
+/// Same as [`decode`], except that the quantity must be in the shortest form
+/// [`encode`] produces.
+///
+/// # Errors
+///
+/// Returns the same errors as [`decode`], and:
+///
+/// * `ParseError::InvalidMidiData` - If the quantity starts with a redundant `80`
+///   byte, with that byte at position 0
+pub fn decode_strict(buf: &[u8]) -> Result<(u32, usize), ParseError> {
+    let (value, len) = decode(buf)?;
+    if len > 1 && buf[0] == CONTINUATION {
+        return Err(ParseError::InvalidMidiData {
+            position: 0,
+            byte: CONTINUATION,
+        });
+    }
+    Ok((value, len))
+}
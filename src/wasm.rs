@@ -0,0 +1,250 @@
+//! # Browser bindings
+//!
+//! `wasm-bindgen` wrappers for decoding mirrored netsync payloads in a web page,
+//! without the C API. Build with the `wasm` feature for `wasm32-unknown-unknown`:
+//!
+//! ```bash
+//! cargo build --target wasm32-unknown-unknown --features wasm
+//! ```
+//!
+//! Decoded events are plain objects mirroring [`MidiEvent`]: a `type` property
+//! naming the variant and one property per field, under the Rust names. Byte
+//! fields are `Uint8Array`s, including a manufacturer ID, as its bytes on the wire.
+//! Timestamps are microseconds as `BigInt`, and timecodes are `HH:MM:SS:FF`
+//! strings, as formatted by [`MtcFullFrame`]. Failures are thrown as `Error`s.
+
+use js_sys::{Object, Reflect, Uint8Array};
+use wasm_bindgen::prelude::*;
+
+use crate::error::MtcError;
+use crate::midi::{MidiEvent, MmcCommand, RealtimeMessage};
+use crate::mtc::{smpte_to_us_rate, us_to_smpte_rate, FrameRate, MtcFullFrame, VlcTickT};
+use crate::netsync;
+
+/// # Decodes a netsync payload into an event object.
+///
+/// The payload is decoded by [`slave_netsync_flow`](netsync::slave_netsync_flow),
+/// so it starts with the RTP-MIDI header, without the RTP header. For example, an
+/// MMC Locate decodes to:
+///
+/// ```js
+/// { type: "Mmc", command: { type: "Locate", hour: 1, minute: 2, second: 3, frame: 4, subframe: 0 } }
+/// ```
+///
+/// # Errors
+///
+/// Throws the `NetsyncError` message when the slave flow rejects the payload.
+#[wasm_bindgen]
+pub fn decode_payload(payload: &[u8]) -> Result<JsValue, JsError> {
+    let event =
+        netsync::slave_netsync_flow(payload).map_err(|err| JsError::new(&err.to_string()))?;
+    Ok(event_object(&event))
+}
+
+/// Renders a payload as text, as [`netsync::describe_payload`] does.
+#[wasm_bindgen]
+pub fn describe_payload(payload: &[u8]) -> String {
+    netsync::describe_payload(payload)
+}
+
+/// # Converts microseconds to a timecode string.
+///
+/// # Arguments
+///
+/// * `us` - Timestamp in microseconds
+/// * `rate` - MTC rate code: 0 for 24fps, 1 for 25fps, 2 for 29.97fps drop-frame,
+///   3 or `undefined` for 30fps
+///
+/// # Returns
+///
+/// `HH:MM:SS:FF`, or `HH:MM:SS;FF` at 29.97fps drop-frame.
+///
+/// # Errors
+///
+/// Throws if `rate` is not a rate code.
+#[wasm_bindgen]
+pub fn us_to_smpte(us: VlcTickT, rate: Option<u8>) -> Result<String, JsError> {
+    let rate = frame_rate(rate)?;
+    let smpte = us_to_smpte_rate(us, rate);
+    Ok(if rate == FrameRate::Fps2997Df {
+        format!("{:#}", smpte)
+    } else {
+        smpte.to_string()
+    })
+}
+
+/// # Converts a timecode string to microseconds.
+///
+/// # Arguments
+///
+/// * `timecode` - `HH:MM:SS:FF` or `HH:MM:SS;FF`
+/// * `rate` - MTC rate code, as for [`us_to_smpte`]
+///
+/// # Errors
+///
+/// Throws if the timecode does not parse or `rate` is not a rate code.
+#[wasm_bindgen]
+pub fn smpte_to_us(timecode: &str, rate: Option<u8>) -> Result<VlcTickT, JsError> {
+    let rate = frame_rate(rate)?;
+    let smpte: MtcFullFrame = timecode
+        .parse()
+        .map_err(|err: MtcError| JsError::new(&err.to_string()))?;
+    Ok(smpte_to_us_rate(&smpte, rate))
+}
+
+/// Returns the frame rate for an optional MTC rate code, 30fps by default.
+fn frame_rate(code: Option<u8>) -> Result<FrameRate, JsError> {
+    match code {
+        None => Ok(FrameRate::Fps30),
+        Some(code) => FrameRate::from_mtc_code(code)
+            .ok_or_else(|| JsError::new(&format!("invalid MTC rate code {}", code))),
+    }
+}
+
+/// Builds a plain object from `type` and the given properties.
+fn object(kind: &str, properties: &[(&str, JsValue)]) -> JsValue {
+    let object = Object::new();
+    let _ = Reflect::set(&object, &"type".into(), &kind.into());
+    for (name, value) in properties {
+        let _ = Reflect::set(&object, &(*name).into(), value);
+    }
+    object.into()
+}
+
+fn event_object(event: &MidiEvent) -> JsValue {
+    match *event {
+        MidiEvent::MtcQuarter { msg_type, value } => object(
+            "MtcQuarter",
+            &[("msg_type", msg_type.into()), ("value", value.into())],
+        ),
+        MidiEvent::MtcFull {
+            hour,
+            minute,
+            second,
+            frame,
+        } => object(
+            "MtcFull",
+            &[
+                ("hour", hour.into()),
+                ("minute", minute.into()),
+                ("second", second.into()),
+                ("frame", frame.into()),
+            ],
+        ),
+        MidiEvent::Mmc(ref command) => object("Mmc", &[("command", mmc_object(command))]),
+        MidiEvent::Realtime(message) => {
+            object("Realtime", &[("message", realtime_object(message))])
+        }
+        MidiEvent::SongPosition(position) => {
+            object("SongPosition", &[("position", position.into())])
+        }
+        MidiEvent::IdentityRequest { device_id } => {
+            object("IdentityRequest", &[("device_id", device_id.into())])
+        }
+        MidiEvent::IdentityReply(identity) => object(
+            "IdentityReply",
+            &[(
+                "identity",
+                object(
+                    "DeviceIdentity",
+                    &[
+                        ("device_id", identity.device_id.into()),
+                        (
+                            "manufacturer",
+                            Uint8Array::from(
+                                &identity.manufacturer.bytes().collect::<Vec<_>>()[..],
+                            )
+                            .into(),
+                        ),
+                        ("family", identity.family.into()),
+                        ("model", identity.model.into()),
+                        ("version", Uint8Array::from(&identity.version[..]).into()),
+                    ],
+                ),
+            )],
+        ),
+        MidiEvent::ManufacturerSysex { id, ref data } => object(
+            "ManufacturerSysex",
+            &[
+                (
+                    "id",
+                    Uint8Array::from(&id.bytes().collect::<Vec<_>>()[..]).into(),
+                ),
+                ("data", Uint8Array::from(data.as_slice()).into()),
+            ],
+        ),
+        MidiEvent::Other(ref bytes) => object(
+            "Other",
+            &[("bytes", Uint8Array::from(bytes.as_slice()).into())],
+        ),
+    }
+}
+
+fn mmc_object(command: &MmcCommand) -> JsValue {
+    let kind = match *command {
+        MmcCommand::Stop => "Stop",
+        MmcCommand::Play => "Play",
+        MmcCommand::DeferredPlay => "DeferredPlay",
+        MmcCommand::FastForward => "FastForward",
+        MmcCommand::Rewind => "Rewind",
+        MmcCommand::RecordStrobe => "RecordStrobe",
+        MmcCommand::RecordExit => "RecordExit",
+        MmcCommand::Pause => "Pause",
+        MmcCommand::Eject => "Eject",
+        MmcCommand::Reset => "Reset",
+        MmcCommand::Locate {
+            hour,
+            minute,
+            second,
+            frame,
+            subframe,
+        } => {
+            return object(
+                "Locate",
+                &[
+                    ("hour", hour.into()),
+                    ("minute", minute.into()),
+                    ("second", second.into()),
+                    ("frame", frame.into()),
+                    ("subframe", subframe.into()),
+                ],
+            )
+        }
+        MmcCommand::Shuttle { speed } => {
+            return object("Shuttle", &[("speed", speed.to_f32().into())])
+        }
+        MmcCommand::VariablePlay { speed } => {
+            return object("VariablePlay", &[("speed", speed.to_f32().into())])
+        }
+        MmcCommand::Search { speed } => {
+            return object("Search", &[("speed", speed.to_f32().into())])
+        }
+        MmcCommand::MaskedWrite {
+            field,
+            start,
+            mask,
+            data,
+        } => {
+            return object(
+                "MaskedWrite",
+                &[
+                    ("field", field.into()),
+                    ("start", start.into()),
+                    ("mask", mask.into()),
+                    ("data", data.into()),
+                ],
+            )
+        }
+    };
+    object(kind, &[])
+}
+
+fn realtime_object(message: RealtimeMessage) -> JsValue {
+    let kind = match message {
+        RealtimeMessage::Clock => "Clock",
+        RealtimeMessage::Start => "Start",
+        RealtimeMessage::Continue => "Continue",
+        RealtimeMessage::Stop => "Stop",
+    };
+    object(kind, &[])
+}
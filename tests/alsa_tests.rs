@@ -0,0 +1,125 @@
+#![cfg(all(feature = "alsa", target_os = "linux"))]
+
+use std::path::Path;
+use std::time::Duration;
+
+use alsa::seq::{Addr, EventType, PortCap, PortSubscribe, PortType, Seq};
+use alsa::Direction;
+
+use rtp_midi_netsync::bridge::alsa::{SeqBridge, MAX_QUEUE_DRIFT};
+use rtp_midi_netsync::midi::{MidiEvent, MmcCommand};
+use rtp_midi_netsync::mtc::{smpte_to_us, MtcFullFrame};
+use rtp_midi_netsync::netsync::{MasterSession, SlaveSession};
+
+const TIMECODE: MtcFullFrame = MtcFullFrame {
+    hours: 1,
+    minutes: 2,
+    seconds: 3,
+    frames: 4,
+};
+
+/// Whether the kernel exposes a sequencer, which containers mostly do not
+fn seq_available() -> bool {
+    let available = Path::new("/dev/snd/seq").exists();
+    if !available {
+        eprintln!("skipping: no ALSA sequencer device");
+    }
+    available
+}
+
+/// A client subscribed to the bridge's port
+fn subscriber(bridge: &SeqBridge) -> Seq {
+    let seq = Seq::open(None, Some(Direction::Capture), false).unwrap();
+    let port = seq
+        .create_simple_port(
+            c"netsync-test-in",
+            PortCap::WRITE | PortCap::SUBS_WRITE,
+            PortType::MIDI_GENERIC | PortType::APPLICATION,
+        )
+        .unwrap();
+    let subscription = PortSubscribe::empty().unwrap();
+    subscription.set_sender(bridge.addr().unwrap());
+    subscription.set_dest(Addr {
+        client: seq.client_id().unwrap(),
+        port,
+    });
+    seq.subscribe_port(&subscription).unwrap();
+    seq
+}
+
+fn event_types(seq: &Seq, count: usize) -> Vec<EventType> {
+    let mut input = seq.input();
+    (0..count)
+        .map(|_| input.event_input().unwrap().get_type())
+        .collect()
+}
+
+fn locate() -> MidiEvent {
+    MidiEvent::Mmc(MmcCommand::Locate {
+        hour: TIMECODE.hours,
+        minute: TIMECODE.minutes,
+        second: TIMECODE.seconds,
+        frame: TIMECODE.frames,
+        subframe: 0,
+    })
+}
+
+#[test]
+fn test_events_reach_subscribers() {
+    if !seq_available() {
+        return;
+    }
+    let mut bridge = SeqBridge::new(c"netsync-test", c"sync out").unwrap();
+    let subscriber = subscriber(&bridge);
+
+    let mut master = MasterSession::new();
+    let mut slave = SlaveSession::new();
+    let full_frame = MidiEvent::MtcFull {
+        hour: TIMECODE.hours,
+        minute: TIMECODE.minutes,
+        second: TIMECODE.seconds,
+        frame: TIMECODE.frames,
+    };
+    for event in [MidiEvent::Mmc(MmcCommand::Play), full_frame] {
+        let packet = master.packet_for_event(&event, 0).unwrap();
+        bridge.feed_packet(&mut slave, &packet, 0).unwrap();
+    }
+
+    // MMC as SysEx, then the time update as a quarter-frame cycle
+    let mut expected = vec![EventType::Sysex];
+    expected.extend([EventType::Qframe; 8]);
+    assert_eq!(event_types(&subscriber, 9), expected);
+    assert_eq!(bridge.send_errors(), 0);
+}
+
+#[test]
+fn test_queue_follows_tracker() {
+    if !seq_available() {
+        return;
+    }
+    let mut bridge = SeqBridge::new(c"netsync-test", c"sync out").unwrap();
+    let queue = bridge.drive_queue(c"netsync-test").unwrap();
+    assert_eq!(bridge.drive_queue(c"netsync-test").unwrap(), queue);
+
+    let mut master = MasterSession::new();
+    let mut slave = SlaveSession::new();
+    for event in [locate(), MidiEvent::Mmc(MmcCommand::Play)] {
+        let packet = master.packet_for_event(&event, 0).unwrap();
+        bridge.feed_packet(&mut slave, &packet, 0).unwrap();
+    }
+    bridge.sync_queue(&slave, 0).unwrap();
+
+    let status = bridge.seq().get_queue_status(queue).unwrap();
+    let position = Duration::from_micros(smpte_to_us(&TIMECODE));
+    assert!(status.get_real_time().abs_diff(position) <= MAX_QUEUE_DRIFT);
+}
+
+#[test]
+fn test_sync_without_queue_does_nothing() {
+    if !seq_available() {
+        return;
+    }
+    let mut bridge = SeqBridge::new(c"netsync-test", c"sync out").unwrap();
+    bridge.sync_queue(&SlaveSession::new(), 0).unwrap();
+    assert_eq!(bridge.queue(), None);
+}
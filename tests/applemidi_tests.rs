@@ -0,0 +1,414 @@
+use rtp_midi_netsync::applemidi::{
+    Channel, ClockSync, ClockSyncMessage, Message, Outgoing, SessionExchange, SessionInitiator,
+    SessionListener, SessionState,
+};
+use rtp_midi_netsync::error::AppleMidiError;
+
+// Reference datagrams in the layout macOS Network MIDI uses for a session with a
+// Mac named "Studio Mac" (initiator) and a listener named "VLC".
+
+const TOKEN: u32 = 0x5E3A_91C7;
+const MAC_SSRC: u32 = 0xA82B_0D41;
+const VLC_SSRC: u32 = 0x1F2E_3D4C;
+
+const MAC_INVITATION: [u8; 27] = [
+    0xFF, 0xFF, 0x49, 0x4E, 0x00, 0x00, 0x00, 0x02, 0x5E, 0x3A, 0x91, 0xC7, 0xA8, 0x2B, 0x0D, 0x41,
+    0x53, 0x74, 0x75, 0x64, 0x69, 0x6F, 0x20, 0x4D, 0x61, 0x63, 0x00,
+];
+
+const VLC_ACCEPTED: [u8; 20] = [
+    0xFF, 0xFF, 0x4F, 0x4B, 0x00, 0x00, 0x00, 0x02, 0x5E, 0x3A, 0x91, 0xC7, 0x1F, 0x2E, 0x3D, 0x4C,
+    0x56, 0x4C, 0x43, 0x00,
+];
+
+const VLC_REJECTED: [u8; 16] = [
+    0xFF, 0xFF, 0x4E, 0x4F, 0x00, 0x00, 0x00, 0x02, 0x5E, 0x3A, 0x91, 0xC7, 0x1F, 0x2E, 0x3D, 0x4C,
+];
+
+const MAC_END_SESSION: [u8; 16] = [
+    0xFF, 0xFF, 0x42, 0x59, 0x00, 0x00, 0x00, 0x02, 0x5E, 0x3A, 0x91, 0xC7, 0xA8, 0x2B, 0x0D, 0x41,
+];
+
+fn exchange(ssrc: u32, name: Option<&str>) -> SessionExchange {
+    SessionExchange {
+        initiator_token: TOKEN,
+        ssrc,
+        name: name.map(str::to_owned),
+    }
+}
+
+// === Message Tests ===
+
+#[test]
+fn test_reference_vectors_roundtrip() {
+    let vectors: [(&[u8], Message); 4] = [
+        (
+            &MAC_INVITATION,
+            Message::Invitation(exchange(MAC_SSRC, Some("Studio Mac"))),
+        ),
+        (
+            &VLC_ACCEPTED,
+            Message::InvitationAccepted(exchange(VLC_SSRC, Some("VLC"))),
+        ),
+        (
+            &VLC_REJECTED,
+            Message::InvitationRejected(exchange(VLC_SSRC, None)),
+        ),
+        (
+            &MAC_END_SESSION,
+            Message::EndSession(exchange(MAC_SSRC, None)),
+        ),
+    ];
+
+    for (bytes, message) in vectors {
+        assert_eq!(Message::parse(bytes), Ok(message.clone()));
+        assert_eq!(message.serialize(), bytes);
+    }
+}
+
+#[test]
+fn test_parse_name_without_terminator() {
+    let unterminated = &MAC_INVITATION[..MAC_INVITATION.len() - 1];
+    assert_eq!(
+        Message::parse(unterminated)
+            .unwrap()
+            .exchange()
+            .unwrap()
+            .name
+            .as_deref(),
+        Some("Studio Mac")
+    );
+}
+
+#[test]
+fn test_parse_invalid_messages() {
+    assert_eq!(
+        Message::parse(&[0xFF, 0xFF]),
+        Err(AppleMidiError::TooShort {
+            expected: 16,
+            available: 2
+        })
+    );
+    assert_eq!(
+        Message::parse(&MAC_END_SESSION[..12]),
+        Err(AppleMidiError::TooShort {
+            expected: 16,
+            available: 12
+        })
+    );
+
+    let mut rtp = MAC_END_SESSION;
+    rtp[0] = 0x80;
+    assert_eq!(Message::parse(&rtp), Err(AppleMidiError::InvalidSignature));
+    assert!(!Message::is_applemidi(&rtp));
+
+    let mut unknown = MAC_END_SESSION;
+    unknown[2..4].copy_from_slice(b"XX");
+    assert_eq!(
+        Message::parse(&unknown),
+        Err(AppleMidiError::UnknownCommand(*b"XX"))
+    );
+
+    let mut version = MAC_END_SESSION;
+    version[7] = 0x01;
+    assert_eq!(
+        Message::parse(&version),
+        Err(AppleMidiError::UnsupportedVersion(1))
+    );
+
+    let mut name = MAC_INVITATION;
+    name[16] = 0xFF;
+    assert_eq!(Message::parse(&name), Err(AppleMidiError::InvalidName));
+}
+
+// === Session State Machine Tests ===
+
+/// Delivers a message through its wire form, as a socket would
+fn deliver(outgoing: &Outgoing) -> (Channel, Message) {
+    (
+        outgoing.channel,
+        Message::parse(&outgoing.message.serialize()).unwrap(),
+    )
+}
+
+#[test]
+fn test_handshake_establishes_session() {
+    let mut initiator = SessionInitiator::new(TOKEN, MAC_SSRC, "Studio Mac");
+    let mut listener = SessionListener::new(VLC_SSRC, "VLC");
+
+    let control_in = initiator.start();
+    assert_eq!(control_in.channel, Channel::Control);
+    assert_eq!(control_in.message.serialize(), MAC_INVITATION);
+    assert_eq!(initiator.state(), SessionState::ControlPending);
+
+    let (channel, message) = deliver(&control_in);
+    let control_ok = listener.handle(channel, &message).unwrap();
+    assert_eq!(control_ok.message.serialize(), VLC_ACCEPTED);
+    assert_eq!(listener.state(), SessionState::DataPending);
+
+    let (channel, message) = deliver(&control_ok);
+    let data_in = initiator.handle(channel, &message).unwrap();
+    assert_eq!(data_in.channel, Channel::Data);
+    assert_eq!(initiator.state(), SessionState::DataPending);
+
+    let (channel, message) = deliver(&data_in);
+    let data_ok = listener.handle(channel, &message).unwrap();
+    assert_eq!(data_ok.channel, Channel::Data);
+    assert_eq!(listener.state(), SessionState::Established);
+
+    let (channel, message) = deliver(&data_ok);
+    assert_eq!(initiator.handle(channel, &message), None);
+    assert_eq!(initiator.state(), SessionState::Established);
+
+    assert_eq!(initiator.remote_ssrc(), Some(VLC_SSRC));
+    assert_eq!(initiator.remote_name(), Some("VLC"));
+    assert_eq!(listener.remote_ssrc(), Some(MAC_SSRC));
+    assert_eq!(listener.remote_name(), Some("Studio Mac"));
+}
+
+#[test]
+fn test_initiator_end_session() {
+    let mut initiator = SessionInitiator::new(TOKEN, MAC_SSRC, "Studio Mac");
+    let mut listener = SessionListener::new(VLC_SSRC, "VLC");
+    let (channel, message) = deliver(&initiator.start());
+    listener.handle(channel, &message);
+
+    let by = initiator.end().unwrap();
+    assert_eq!(by.channel, Channel::Control);
+    assert_eq!(by.message.serialize(), MAC_END_SESSION);
+    assert_eq!(initiator.state(), SessionState::Ended);
+    assert_eq!(initiator.end(), None);
+
+    let (channel, message) = deliver(&by);
+    assert_eq!(listener.handle(channel, &message), None);
+    assert_eq!(listener.state(), SessionState::Ended);
+}
+
+#[test]
+fn test_listener_end_session() {
+    let mut initiator = SessionInitiator::new(TOKEN, MAC_SSRC, "Studio Mac");
+    let mut listener = SessionListener::new(VLC_SSRC, "VLC");
+    assert_eq!(listener.end(), None);
+
+    let (channel, message) = deliver(&initiator.start());
+    let (channel, message) = deliver(&listener.handle(channel, &message).unwrap());
+    initiator.handle(channel, &message);
+
+    let (channel, message) = deliver(&listener.end().unwrap());
+    initiator.handle(channel, &message);
+    assert_eq!(initiator.state(), SessionState::Ended);
+}
+
+#[test]
+fn test_listener_rejects_when_not_accepting() {
+    let mut initiator = SessionInitiator::new(TOKEN, MAC_SSRC, "Studio Mac");
+    let mut listener = SessionListener::new(VLC_SSRC, "VLC");
+    listener.set_accepting(false);
+
+    let (channel, message) = deliver(&initiator.start());
+    let reply = listener.handle(channel, &message).unwrap();
+    assert_eq!(reply.message.serialize(), VLC_REJECTED);
+    assert_eq!(listener.state(), SessionState::Idle);
+
+    let (channel, message) = deliver(&reply);
+    assert_eq!(initiator.handle(channel, &message), None);
+    assert_eq!(initiator.state(), SessionState::Rejected);
+    assert_eq!(initiator.retransmit(), None);
+}
+
+#[test]
+fn test_listener_rejects_data_invitation_without_control() {
+    let mut listener = SessionListener::new(VLC_SSRC, "VLC");
+    let invitation = Message::parse(&MAC_INVITATION).unwrap();
+
+    let reply = listener.handle(Channel::Data, &invitation).unwrap();
+    assert!(matches!(reply.message, Message::InvitationRejected(_)));
+    assert_eq!(listener.state(), SessionState::Idle);
+}
+
+#[test]
+fn test_listener_rejects_second_initiator() {
+    let mut listener = SessionListener::new(VLC_SSRC, "VLC");
+    let invitation = Message::parse(&MAC_INVITATION).unwrap();
+    listener.handle(Channel::Control, &invitation);
+
+    let intruder = Message::Invitation(SessionExchange {
+        initiator_token: 0x0102_0304,
+        ssrc: 0x0506_0708,
+        name: Some("Other".to_owned()),
+    });
+    for channel in [Channel::Control, Channel::Data] {
+        let reply = listener.handle(channel, &intruder).unwrap();
+        assert!(matches!(reply.message, Message::InvitationRejected(_)));
+    }
+
+    // The original initiator can still complete its handshake
+    let reply = listener.handle(Channel::Data, &invitation).unwrap();
+    assert!(matches!(reply.message, Message::InvitationAccepted(_)));
+    assert_eq!(listener.remote_ssrc(), Some(MAC_SSRC));
+}
+
+#[test]
+fn test_initiator_retransmit_and_foreign_token() {
+    let mut initiator = SessionInitiator::new(TOKEN, MAC_SSRC, "Studio Mac");
+    assert_eq!(initiator.retransmit(), None);
+
+    let first = initiator.start();
+    assert_eq!(initiator.retransmit(), Some(first));
+
+    // Replies to another invitation attempt are ignored
+    let stale = Message::InvitationAccepted(SessionExchange {
+        initiator_token: TOKEN ^ 1,
+        ssrc: VLC_SSRC,
+        name: None,
+    });
+    assert_eq!(initiator.handle(Channel::Control, &stale), None);
+    assert_eq!(initiator.state(), SessionState::ControlPending);
+
+    let ok = Message::parse(&VLC_ACCEPTED).unwrap();
+    initiator.handle(Channel::Control, &ok);
+    assert_eq!(
+        initiator.retransmit().map(|outgoing| outgoing.channel),
+        Some(Channel::Data)
+    );
+}
+
+// === Clock Synchronization Tests ===
+
+const MAC_CLOCK_SYNC: [u8; 36] = [
+    0xFF, 0xFF, 0x43, 0x4B, 0xA8, 0x2B, 0x0D, 0x41, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x27, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00,
+];
+
+#[test]
+fn test_clock_sync_message_roundtrip() {
+    let ck = ClockSyncMessage {
+        ssrc: MAC_SSRC,
+        count: 0,
+        timestamps: [10_000, 0, 0],
+    };
+    assert_eq!(Message::parse(&MAC_CLOCK_SYNC), Ok(Message::ClockSync(ck)));
+    assert_eq!(Message::ClockSync(ck).serialize(), MAC_CLOCK_SYNC);
+    assert_eq!(Message::ClockSync(ck).exchange(), None);
+
+    assert_eq!(
+        ClockSyncMessage::parse(&MAC_CLOCK_SYNC[..35]),
+        Err(AppleMidiError::TooShort {
+            expected: 36,
+            available: 35
+        })
+    );
+    assert_eq!(
+        ClockSyncMessage::parse(&[MAC_INVITATION.as_slice(), &[0; 9]].concat()),
+        Err(AppleMidiError::UnknownCommand(*b"IN"))
+    );
+}
+
+#[test]
+fn test_clock_sync_exchange() {
+    // The listener's clock runs 5 s ahead of the initiator's, one-way latency is 1 ms
+    let mut initiator = ClockSync::new(MAC_SSRC);
+    let mut listener = ClockSync::new(VLC_SSRC);
+
+    let ck0 = initiator.start(1_000_000);
+    assert_eq!(ck0.timestamps, [10_000, 0, 0]);
+
+    let ck1 = listener.handle(&ck0, 6_001_000).unwrap();
+    assert_eq!(ck1.count, 1);
+    assert_eq!(ck1.ssrc, VLC_SSRC);
+    assert_eq!(ck1.timestamps, [10_000, 60_010, 0]);
+    assert_eq!(listener.offset_us(), None);
+
+    let ck2 = initiator.handle(&ck1, 1_002_000).unwrap();
+    assert_eq!(ck2.count, 2);
+    assert_eq!(ck2.timestamps, [10_000, 60_010, 10_020]);
+    assert_eq!(initiator.offset_us(), Some(5_000_000));
+    assert_eq!(initiator.latency_us(), Some(1_000));
+
+    assert_eq!(listener.handle(&ck2, 6_002_000), None);
+    assert_eq!(listener.offset_us(), Some(-5_000_000));
+    assert_eq!(listener.latency_us(), Some(1_000));
+
+    assert_eq!(initiator.remote_to_local_us(6_500_000), Some(1_500_000));
+    assert_eq!(listener.remote_to_local_us(1_500_000), Some(6_500_000));
+}
+
+#[test]
+fn test_clock_sync_smoothing() {
+    fn exchange(initiator: &mut ClockSync, t1_us: u64, t2_us: u64, t3_us: u64) {
+        let ck0 = initiator.start(t1_us);
+        let ck1 = ClockSync::new(VLC_SSRC).handle(&ck0, t2_us).unwrap();
+        initiator.handle(&ck1, t3_us).unwrap();
+    }
+
+    let mut initiator = ClockSync::new(MAC_SSRC);
+    exchange(&mut initiator, 1_000_000, 6_001_000, 1_002_000);
+    assert_eq!(initiator.offset_us(), Some(5_000_000));
+
+    // The next exchange measures 800 us more: the estimate moves by an eighth of it
+    exchange(&mut initiator, 2_000_000, 7_001_800, 2_002_000);
+    assert_eq!(initiator.offset_us(), Some(5_000_100));
+    assert_eq!(initiator.latency_us(), Some(1_000));
+}
+
+#[test]
+fn test_clock_sync_wraparound() {
+    // Initiator timestamps wrap between t1 and t3: round trip of 20 ticks
+    let mut responder = ClockSync::new(VLC_SSRC);
+    let ck2 = ClockSyncMessage {
+        ssrc: MAC_SSRC,
+        count: 2,
+        timestamps: [u64::MAX - 5, 100, 14],
+    };
+    responder.handle(&ck2, 0);
+    assert_eq!(responder.latency_us(), Some(1_000));
+    // Offset in the initiator's view: 100 - (MAX - 5 + 10) = 96 ticks
+    assert_eq!(responder.offset_us(), Some(-9_600));
+
+    // Responder timestamp wrapped relative to the initiator's
+    let mut responder = ClockSync::new(VLC_SSRC);
+    let ck2 = ClockSyncMessage {
+        ssrc: MAC_SSRC,
+        count: 2,
+        timestamps: [1_000, u64::MAX - 9, 1_020],
+    };
+    responder.handle(&ck2, 0);
+    assert_eq!(responder.offset_us(), Some(102_000));
+}
+
+#[test]
+fn test_clock_sync_adversarial_timestamps() {
+    // Offsets at opposite ends of the i64 range, which are neighbours once wrapped
+    let mut responder = ClockSync::new(VLC_SSRC);
+    for t2 in [(1 << 63) + 1, i64::MAX as u64 - 6] {
+        let ck2 = ClockSyncMessage {
+            ssrc: MAC_SSRC,
+            count: 2,
+            timestamps: [0, t2, 0],
+        };
+        assert_eq!(responder.handle(&ck2, 0), None);
+    }
+    // The estimate wraps one tick past i64::MAX, and the conversion saturates
+    assert_eq!(responder.offset_us(), Some(i64::MIN));
+    assert_eq!(responder.latency_us(), Some(0));
+}
+
+#[test]
+fn test_clock_sync_ignores_unexpected_replies() {
+    let mut initiator = ClockSync::new(MAC_SSRC);
+    let stray = ClockSyncMessage {
+        ssrc: VLC_SSRC,
+        count: 1,
+        timestamps: [42, 1_000, 0],
+    };
+    assert_eq!(initiator.handle(&stray, 0), None);
+
+    initiator.start(1_000_000);
+    assert_eq!(initiator.handle(&stray, 0), None);
+    assert_eq!(
+        initiator.handle(&ClockSyncMessage { count: 3, ..stray }, 0),
+        None
+    );
+    assert_eq!(initiator.offset_us(), None);
+}
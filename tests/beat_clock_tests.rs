@@ -0,0 +1,247 @@
+use rtp_midi_netsync::midi::{MidiEvent, RealtimeMessage};
+use rtp_midi_netsync::mtc::VlcTickT;
+use rtp_midi_netsync::netsync::{
+    master_netsync_flow, slave_netsync_flow, BeatClockTracker, MasterSession, NetsyncHandler,
+    SlaveSession,
+};
+use rtp_midi_netsync::rtp::RtpHeader;
+
+const CLOCK: MidiEvent = MidiEvent::Realtime(RealtimeMessage::Clock);
+const START: MidiEvent = MidiEvent::Realtime(RealtimeMessage::Start);
+const CONTINUE: MidiEvent = MidiEvent::Realtime(RealtimeMessage::Continue);
+const STOP: MidiEvent = MidiEvent::Realtime(RealtimeMessage::Stop);
+
+/// Clock interval at 120 BPM: half a second per quarter note, 24 clocks each
+const CLOCK_INTERVAL_120_BPM_US: f64 = 500_000.0 / 24.0;
+
+/// Deterministic jitter in [-max_us, +max_us] from a linear congruential generator
+struct Jitter {
+    state: u64,
+    max_us: i64,
+}
+
+impl Jitter {
+    fn next(&mut self) -> i64 {
+        self.state = self
+            .state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        ((self.state >> 33) % (2 * self.max_us as u64 + 1)) as i64 - self.max_us
+    }
+}
+
+/// Arrival times of `count` clocks at 120 BPM with ±2 ms jitter, starting at 1 s
+fn jittered_clock_arrivals(count: usize) -> Vec<VlcTickT> {
+    let mut jitter = Jitter {
+        state: 0x1234_5678,
+        max_us: 2_000,
+    };
+    (0..count)
+        .map(|i| {
+            let ideal = 1_000_000.0 + i as f64 * CLOCK_INTERVAL_120_BPM_US;
+            (ideal as i64 + jitter.next()) as VlcTickT
+        })
+        .collect()
+}
+
+// === Flow Tests ===
+
+#[test]
+fn test_beat_clock_flow_roundtrip() {
+    let events = [
+        CLOCK,
+        START,
+        CONTINUE,
+        STOP,
+        MidiEvent::SongPosition(0),
+        MidiEvent::SongPosition(1_000),
+        MidiEvent::SongPosition(0x3FFF),
+    ];
+    for event in events {
+        let payload = master_netsync_flow(&event).unwrap();
+        assert_eq!(slave_netsync_flow(&payload), Ok(event));
+    }
+
+    assert_eq!(master_netsync_flow(&CLOCK).unwrap(), vec![0x01, 0xF8]);
+    assert_eq!(
+        master_netsync_flow(&MidiEvent::SongPosition(1_000)).unwrap(),
+        vec![0x03, 0xF2, 0x68, 0x07]
+    );
+}
+
+#[test]
+fn test_truncated_song_position_is_other() {
+    assert_eq!(
+        slave_netsync_flow(&[0x02, 0xF2, 0x10]),
+        Ok(MidiEvent::Other(vec![0xF2, 0x10]))
+    );
+}
+
+// === Tracker Tests ===
+
+#[test]
+fn test_tracker_counts_clocks_while_running() {
+    let mut tracker = BeatClockTracker::new();
+    tracker.apply_event(&CLOCK, 0);
+    assert_eq!(tracker.clocks(), 0);
+
+    tracker.apply_event(&START, 0);
+    for i in 0..36 {
+        tracker.apply_event(&CLOCK, i * 20_833);
+    }
+    assert!(tracker.is_running());
+    assert_eq!(tracker.clocks(), 36);
+    assert_eq!(tracker.beat_position(), 1.5);
+    assert_eq!(tracker.song_position(), 6);
+
+    tracker.apply_event(&STOP, 800_000);
+    tracker.apply_event(&CLOCK, 820_000);
+    assert_eq!(tracker.clocks(), 36);
+
+    tracker.apply_event(&CONTINUE, 830_000);
+    tracker.apply_event(&CLOCK, 840_000);
+    assert_eq!(tracker.clocks(), 37);
+
+    tracker.apply_event(&START, 900_000);
+    assert_eq!(tracker.clocks(), 0);
+}
+
+#[test]
+fn test_tracker_song_position_pointer() {
+    let mut tracker = BeatClockTracker::new();
+    tracker.apply_event(&MidiEvent::SongPosition(16), 0);
+    assert_eq!(tracker.clocks(), 96);
+    assert_eq!(tracker.beat_position(), 4.0);
+
+    tracker.apply_event(&CONTINUE, 0);
+    tracker.apply_event(&CLOCK, 10);
+    assert_eq!(tracker.clocks(), 97);
+}
+
+#[test]
+fn test_tempo_unknown_until_two_clocks() {
+    let mut tracker = BeatClockTracker::new();
+    assert_eq!(tracker.tempo_bpm(), None);
+    tracker.apply_event(&CLOCK, 1_000);
+    assert_eq!(tracker.tempo_bpm(), None);
+    tracker.apply_event(&CLOCK, 21_833);
+    assert!((tracker.tempo_bpm().unwrap() - 120.0).abs() < 0.1);
+}
+
+#[test]
+fn test_tempo_120_bpm_with_jitter() {
+    let arrivals = jittered_clock_arrivals(24 * 16);
+    let mut tracker = BeatClockTracker::new();
+    tracker.apply_event(&START, 0);
+
+    let mut worst_pair_bpm: f64 = 120.0;
+    for (i, &arrival_us) in arrivals.iter().enumerate() {
+        tracker.apply_event(&CLOCK, arrival_us);
+        if i > 0 {
+            let interval_us = (arrival_us - arrivals[i - 1]) as f64;
+            let pair_bpm = 60_000_000.0 / (interval_us * 24.0);
+            if (pair_bpm - 120.0).abs() > (worst_pair_bpm - 120.0).abs() {
+                worst_pair_bpm = pair_bpm;
+            }
+        }
+        // Once the measurement window is full, the estimate stays within 1 BPM
+        if i >= 48 {
+            let bpm = tracker.tempo_bpm().unwrap();
+            assert!((bpm - 120.0).abs() < 1.0, "clock {}: {} BPM", i, bpm);
+        }
+    }
+
+    // Consecutive clocks alone would be off by more than 10 BPM
+    assert!((worst_pair_bpm - 120.0).abs() > 10.0);
+    assert_eq!(tracker.beat_position(), 16.0);
+}
+
+#[test]
+fn test_tempo_follows_change() {
+    let mut tracker = BeatClockTracker::new();
+    let mut now_us = 0;
+    for _ in 0..96 {
+        tracker.apply_event(&CLOCK, now_us);
+        now_us += 20_833;
+    }
+    // Switch to 100 BPM: 25 ms per clock
+    for _ in 0..49 {
+        now_us += 25_000;
+        tracker.apply_event(&CLOCK, now_us);
+    }
+    assert!((tracker.tempo_bpm().unwrap() - 100.0).abs() < 0.01);
+}
+
+// === Session Tests ===
+
+#[derive(Default)]
+struct BeatClockHandler {
+    realtime: Vec<RealtimeMessage>,
+    positions: Vec<u16>,
+}
+
+impl NetsyncHandler for BeatClockHandler {
+    fn on_realtime(&mut self, message: RealtimeMessage) {
+        self.realtime.push(message);
+    }
+
+    fn on_song_position(&mut self, position: u16) {
+        self.positions.push(position);
+    }
+}
+
+fn event_of(packet: &[u8]) -> MidiEvent {
+    slave_netsync_flow(RtpHeader::parse(packet).unwrap().1).unwrap()
+}
+
+#[test]
+fn test_master_beat_clock_packets() {
+    let mut master = MasterSession::new();
+
+    let start = master.start_at(0, 0);
+    assert_eq!(
+        start.iter().map(|packet| event_of(packet)).collect::<Vec<_>>(),
+        vec![MidiEvent::SongPosition(0), START]
+    );
+    let resume = master.start_at(64, 0);
+    assert_eq!(
+        resume.iter().map(|packet| event_of(packet)).collect::<Vec<_>>(),
+        vec![MidiEvent::SongPosition(64), CONTINUE]
+    );
+
+    assert_eq!(event_of(&master.clock_tick(0)), CLOCK);
+    assert_eq!(event_of(&master.continue_(0)), CONTINUE);
+    assert_eq!(event_of(&master.stop(0)), STOP);
+    assert_eq!(master.metrics().packets_sent, 7);
+}
+
+#[test]
+fn test_beat_clock_end_to_end() {
+    let mut master = MasterSession::new();
+    let mut slave = SlaveSession::new();
+    let mut handler = BeatClockHandler::default();
+
+    for packet in master.start_at(32, 0) {
+        slave.feed_packet(&packet, 0, &mut handler).unwrap();
+    }
+    for arrival_us in jittered_clock_arrivals(24 * 4) {
+        let packet = master.clock_tick(arrival_us);
+        slave
+            .feed_packet(&packet, arrival_us, &mut handler)
+            .unwrap();
+    }
+    let stop = master.stop(3_000_000);
+    slave.feed_packet(&stop, 3_000_000, &mut handler).unwrap();
+
+    let beat_clock = slave.beat_clock();
+    assert!(!beat_clock.is_running());
+    // Sixteenth note 32 is beat 8, followed by four beats of clocks
+    assert_eq!(beat_clock.beat_position(), 12.0);
+    assert_eq!(beat_clock.song_position(), 48);
+    assert!((beat_clock.tempo_bpm().unwrap() - 120.0).abs() < 1.0);
+
+    assert_eq!(handler.positions, vec![32]);
+    assert_eq!(handler.realtime.len(), 1 + 24 * 4 + 1);
+    assert_eq!(handler.realtime[0], RealtimeMessage::Continue);
+    assert_eq!(handler.realtime.last(), Some(&RealtimeMessage::Stop));
+}
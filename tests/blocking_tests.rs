@@ -0,0 +1,148 @@
+use std::io;
+use std::net::UdpSocket;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rtp_midi_netsync::midi::{DeviceIdentity, ManufacturerId, MidiEvent};
+use rtp_midi_netsync::mtc::{smpte_to_us, MtcFullFrame};
+use rtp_midi_netsync::netsync::net::blocking::{Receiver, Sender};
+use rtp_midi_netsync::netsync::net::{NetsyncEvent, KEEPALIVE_INTERVAL};
+use rtp_midi_netsync::netsync::NetsyncConfig;
+
+const START: MtcFullFrame = MtcFullFrame {
+    hours: 1,
+    minutes: 0,
+    seconds: 0,
+    frames: 0,
+};
+
+/// A sender connected to a receiver over loopback
+fn link() -> (Sender, Receiver) {
+    let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+    sender.connect(receiver.local_addr().unwrap()).unwrap();
+    let config = NetsyncConfig::default();
+    let mut receiver = Receiver::new(receiver, config).unwrap();
+    receiver.set_read_timeout(Some(Duration::from_secs(1)));
+    (Sender::new(sender, config).unwrap(), receiver)
+}
+
+#[test]
+fn test_transport_sequence() {
+    let (sender, mut receiver) = link();
+
+    sender.seek(START).unwrap();
+    assert_eq!(receiver.recv().unwrap(), NetsyncEvent::Locate(START));
+    sender.play().unwrap();
+    assert_eq!(receiver.recv().unwrap(), NetsyncEvent::Play);
+
+    // The first tick refreshes the Full Frame, followed by Play while rolling
+    thread::sleep(Duration::from_millis(50));
+    sender.tick().unwrap();
+    let NetsyncEvent::TimeUpdate(position_us) = receiver.recv().unwrap() else {
+        panic!("expected a time update");
+    };
+    let start_us = smpte_to_us(&START);
+    assert!(
+        (start_us + 40_000..start_us + 500_000).contains(&position_us),
+        "{} us",
+        position_us
+    );
+    assert_eq!(receiver.recv().unwrap(), NetsyncEvent::Play);
+    assert!(receiver.session().tracker().is_playing());
+
+    sender.stop().unwrap();
+    assert_eq!(receiver.recv().unwrap(), NetsyncEvent::Stop);
+    assert!(!receiver.session().tracker().is_playing());
+
+    assert_eq!(receiver.session().metrics().packets_parsed, 5);
+    assert_eq!(receiver.session().metrics().sequence_gaps, 0);
+}
+
+#[test]
+fn test_lost_packet_triggers_resync() {
+    let (sender, mut receiver) = link();
+    sender.seek(START).unwrap();
+    receiver.recv().unwrap();
+    sender.tick().unwrap();
+    receiver.recv().unwrap();
+
+    // A packet lost on the way opens a sequence gap
+    sender.with_session(|session| session.keepalive(0));
+    sender.send_event(&MidiEvent::SongPosition(16)).unwrap();
+    assert_eq!(receiver.recv().unwrap(), NetsyncEvent::SongPosition(16));
+    assert_eq!(receiver.session().metrics().sequence_gaps, 1);
+
+    // The resync request reaches the sender, which answers with a Full Frame
+    thread::sleep(Duration::from_millis(20));
+    sender.tick().unwrap();
+    sender.with_session(|session| {
+        assert_eq!(session.metrics().resync_requests, 1);
+        assert_eq!(session.metrics().full_frames_refreshed, 2);
+    });
+    assert_eq!(
+        receiver.recv().unwrap(),
+        NetsyncEvent::TimeUpdate(smpte_to_us(&START))
+    );
+}
+
+#[test]
+fn test_receiver_answers_identity_request() {
+    let (sender, mut receiver) = link();
+    let identity = DeviceIdentity {
+        device_id: 0x01,
+        manufacturer: ManufacturerId::Short(0x41),
+        family: 1,
+        model: 2,
+        version: [0, 0, 0, 1],
+    };
+    receiver.session_mut().set_identity(Some(identity));
+
+    let request = sender.with_session(|session| session.discover(0));
+    sender.socket().send(&request).unwrap();
+    sender.send_event(&MidiEvent::SongPosition(16)).unwrap();
+    assert_eq!(receiver.recv().unwrap(), NetsyncEvent::SongPosition(16));
+
+    thread::sleep(Duration::from_millis(20));
+    sender.tick().unwrap();
+    sender.with_session(|session| assert_eq!(session.discovered(), [identity]));
+}
+
+#[test]
+fn test_maintenance_thread_keeps_link_alive() {
+    let (mut sender, mut receiver) = link();
+    sender.start_maintenance().unwrap();
+    sender.seek(START).unwrap();
+    assert_eq!(receiver.recv().unwrap(), NetsyncEvent::Locate(START));
+    // Refreshed without calling tick
+    assert_eq!(
+        receiver.recv().unwrap(),
+        NetsyncEvent::TimeUpdate(smpte_to_us(&START))
+    );
+
+    // Later refreshes and keepalives keep coming while idle
+    thread::sleep(KEEPALIVE_INTERVAL + Duration::from_millis(100));
+    sender.play().unwrap();
+    while receiver.recv().unwrap() != NetsyncEvent::Play {}
+    assert!(sender.with_session(|session| session.metrics().packets_sent) > 3);
+    assert_eq!(receiver.session().metrics().sequence_gaps, 0);
+}
+
+#[test]
+fn test_recv_times_out() {
+    let (_sender, mut receiver) = link();
+    receiver.set_read_timeout(Some(Duration::from_millis(50)));
+    let started = Instant::now();
+    let err = receiver.recv().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+    assert!(started.elapsed() >= Duration::from_millis(50));
+}
+
+#[test]
+fn test_unsupported_event_is_invalid_input() {
+    let (sender, _receiver) = link();
+    let err = sender
+        .send_event(&MidiEvent::Other(vec![0x90, 0x40, 0x7F]))
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
@@ -0,0 +1,175 @@
+#![cfg(feature = "midir")]
+
+use rtp_midi_netsync::bridge::{from_midir_bytes, to_midir_bytes};
+use rtp_midi_netsync::error::ParseError;
+use rtp_midi_netsync::midi::{MidiEvent, MmcCommand, RealtimeMessage};
+
+#[test]
+fn test_midir_bytes_round_trip() {
+    let events = [
+        MidiEvent::MtcQuarter {
+            msg_type: 3,
+            value: 0x0A,
+        },
+        MidiEvent::MtcFull {
+            hour: 1,
+            minute: 2,
+            second: 3,
+            frame: 4,
+        },
+        MidiEvent::Mmc(MmcCommand::Play),
+        MidiEvent::Mmc(MmcCommand::Locate {
+            hour: 1,
+            minute: 2,
+            second: 3,
+            frame: 4,
+            subframe: 0,
+        }),
+        MidiEvent::Realtime(RealtimeMessage::Clock),
+        MidiEvent::SongPosition(300),
+        MidiEvent::Other(vec![0x90, 0x40, 0x7F]),
+    ];
+    for event in events {
+        let bytes = to_midir_bytes(&event);
+        assert_eq!(from_midir_bytes(&bytes).unwrap(), event);
+    }
+    assert_eq!(
+        to_midir_bytes(&MidiEvent::Mmc(MmcCommand::Stop)),
+        [0xF0, 0x7F, 0x7F, 0x06, 0x01, 0xF7]
+    );
+}
+
+#[test]
+fn test_empty_message_is_rejected() {
+    assert!(matches!(
+        from_midir_bytes(&[]),
+        Err(ParseError::EmptyBuffer)
+    ));
+}
+
+/// Loopback through the platform's virtual ports, where it has them
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+mod virtual_ports {
+    use std::net::UdpSocket;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+
+    use midir::os::unix::VirtualInput;
+    use midir::{MidiInput, MidiOutput, MidiOutputConnection};
+
+    use rtp_midi_netsync::bridge::{to_midir_bytes, HardwareBridge};
+    use rtp_midi_netsync::midi::{MidiEvent, MmcCommand};
+    use rtp_midi_netsync::mtc::{smpte_to_quarter_frames, MtcFullFrame};
+    use rtp_midi_netsync::netsync::net::blocking::{Receiver, Sender};
+    use rtp_midi_netsync::netsync::net::NetsyncEvent;
+    use rtp_midi_netsync::netsync::{MasterSession, NetsyncConfig, SlaveSession};
+
+    const TIMECODE: MtcFullFrame = MtcFullFrame {
+        hours: 1,
+        minutes: 2,
+        seconds: 3,
+        frames: 4,
+    };
+
+    /// Connects an output to the virtual port `port_name`, or `None` when the
+    /// platform has no MIDI backend, as in most containers
+    fn connect_output(port_name: &str) -> Option<MidiOutputConnection> {
+        let output = MidiOutput::new("netsync-test").ok()?;
+        let port = output.ports().into_iter().find(|port| {
+            output
+                .port_name(port)
+                .is_ok_and(|name| name.contains(port_name))
+        })?;
+        output.connect(&port, "netsync-test-out").ok()
+    }
+
+    fn midi_input() -> Option<MidiInput> {
+        let input = MidiInput::new("netsync-test");
+        if input.is_err() {
+            eprintln!("skipping: no MIDI backend");
+        }
+        input.ok()
+    }
+
+    #[test]
+    fn test_slave_events_reach_output() {
+        let Some(input) = midi_input() else {
+            return;
+        };
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let _port = input
+            .create_virtual(
+                "netsync-bridge-sink",
+                |_, bytes, received: &mut Arc<Mutex<Vec<Vec<u8>>>>| {
+                    received.lock().unwrap().push(bytes.to_vec())
+                },
+                Arc::clone(&received),
+            )
+            .unwrap();
+        let mut bridge = HardwareBridge::new(connect_output("netsync-bridge-sink").unwrap());
+
+        let mut master = MasterSession::new();
+        let mut slave = SlaveSession::new();
+        let play = MidiEvent::Mmc(MmcCommand::Play);
+        let packet = master.packet_for_event(&play, 0).unwrap();
+        bridge.feed_packet(&mut slave, &packet, 0).unwrap();
+        for (piece, qf) in smpte_to_quarter_frames(&TIMECODE).iter().enumerate() {
+            let event = MidiEvent::MtcQuarter {
+                msg_type: qf.frame_type,
+                value: qf.value,
+            };
+            let packet = master.packet_for_event(&event, piece as u64).unwrap();
+            bridge.feed_packet(&mut slave, &packet, 0).unwrap();
+        }
+
+        thread::sleep(Duration::from_millis(100));
+        // The quarter-frame cycle arrives as one Full Frame
+        let full_frame = MidiEvent::MtcFull {
+            hour: TIMECODE.hours,
+            minute: TIMECODE.minutes,
+            second: TIMECODE.seconds,
+            frame: TIMECODE.frames,
+        };
+        assert_eq!(
+            *received.lock().unwrap(),
+            [to_midir_bytes(&play), to_midir_bytes(&full_frame)]
+        );
+        assert_eq!(bridge.send_errors(), 0);
+    }
+
+    #[test]
+    fn test_input_feeds_sender() {
+        let Some(input) = midi_input() else {
+            return;
+        };
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+        sender.connect(receiver.local_addr().unwrap()).unwrap();
+        let config = NetsyncConfig::default();
+        let mut receiver = Receiver::new(receiver, config).unwrap();
+        receiver.set_read_timeout(Some(Duration::from_secs(1)));
+        let sender = Arc::new(Sender::new(sender, config).unwrap());
+
+        let _port = input
+            .create_virtual(
+                "netsync-bridge-source",
+                HardwareBridge::input_callback,
+                sender,
+            )
+            .unwrap();
+        let mut output = connect_output("netsync-bridge-source").unwrap();
+        output
+            .send(&to_midir_bytes(&MidiEvent::Mmc(MmcCommand::Play)))
+            .unwrap();
+        // Not a sync event, so not sent
+        output.send(&[0x90, 0x40, 0x7F]).unwrap();
+        output
+            .send(&to_midir_bytes(&MidiEvent::Mmc(MmcCommand::Stop)))
+            .unwrap();
+
+        assert_eq!(receiver.recv().unwrap(), NetsyncEvent::Play);
+        assert_eq!(receiver.recv().unwrap(), NetsyncEvent::Stop);
+        assert_eq!(receiver.session().metrics().sequence_gaps, 0);
+    }
+}
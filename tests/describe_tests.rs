@@ -0,0 +1,125 @@
+use rtp_midi_netsync::midi::{MidiEvent, MmcCommand, MmcSpeed, RealtimeMessage};
+use rtp_midi_netsync::netsync::{describe_payload, master_netsync_flow};
+
+fn describe(event: MidiEvent) -> String {
+    describe_payload(&master_netsync_flow(&event).unwrap())
+}
+
+#[test]
+fn test_describe_sync_events() {
+    assert_eq!(
+        describe(MidiEvent::MtcFull {
+            hour: 1,
+            minute: 23,
+            second: 45,
+            frame: 10,
+        }),
+        "Header 0x0A: LEN=10\nMTC Full 01:23:45:10 @30fps"
+    );
+    assert_eq!(
+        describe(MidiEvent::MtcQuarter {
+            msg_type: 3,
+            value: 0x2,
+        }),
+        "Header 0x02: LEN=2\nMTC Quarter Frame piece 3 (seconds high) value 0x2"
+    );
+    assert_eq!(
+        describe(MidiEvent::Mmc(MmcCommand::Locate {
+            hour: 0,
+            minute: 1,
+            second: 2,
+            frame: 3,
+            subframe: 0,
+        })),
+        "Header 0x0D: LEN=13\nMMC Locate 00:01:02:03.00 @30fps"
+    );
+    assert_eq!(
+        describe(MidiEvent::Mmc(MmcCommand::Play)),
+        "Header 0x06: LEN=6\nMMC Play"
+    );
+    assert_eq!(
+        describe(MidiEvent::Mmc(MmcCommand::Shuttle {
+            speed: MmcSpeed::from_f32(-0.5),
+        })),
+        "Header 0x0A: LEN=10\nMMC Shuttle -0.5x"
+    );
+    assert_eq!(
+        describe(MidiEvent::Realtime(RealtimeMessage::Clock)),
+        "Header 0x01: LEN=1\nTiming Clock"
+    );
+    assert_eq!(
+        describe(MidiEvent::SongPosition(10)),
+        "Header 0x03: LEN=3\nSong Position Pointer 10 (beat 2.5)"
+    );
+}
+
+#[test]
+fn test_describe_rate_bits() {
+    // Hour byte 0x21: 25fps, hour 1
+    let payload = [
+        0x0A, 0xF0, 0x7F, 0x7F, 0x01, 0x01, 0x21, 0x02, 0x03, 0x04, 0xF7,
+    ];
+    assert_eq!(
+        describe_payload(&payload),
+        "Header 0x0A: LEN=10\nMTC Full 01:02:03:04 @25fps\n  \
+         [warning] byte 6: hour byte carries rate bits for Fps25, read as part of the hour"
+    );
+}
+
+#[test]
+fn test_describe_pass_through() {
+    assert_eq!(
+        describe_payload(&[0x03, 0x90, 0x3C, 0x7F]),
+        "Header 0x03: LEN=3\nOther (3 bytes): 90 3C 7F"
+    );
+}
+
+#[test]
+fn test_describe_malformed() {
+    assert_eq!(describe_payload(&[]), "Empty payload");
+    assert_eq!(
+        describe_payload(&[0x05, 0xF1]),
+        "Header 0x05: LEN=5\nMalformed: F1\n  \
+         [error] byte 0: LEN=5 but MIDI list is only 1 bytes\n  \
+         [error] byte 1: Quarter Frame needs 2 bytes but only 1 present"
+    );
+    // The 12-bit LEN of a two-octet header runs past the payload
+    assert_eq!(
+        describe_payload(&[0xC1, 0x02, 0xF8]),
+        "Header 0xC1: flags B J, LEN=258 (two octets)\nMalformed: F8\n  \
+         [info] byte 0: B flag set, two-octet header with LEN=258\n  \
+         [info] byte 0: J flag set, recovery journal ignored\n  \
+         [error] byte 0: LEN=258 but MIDI list is only 1 bytes"
+    );
+}
+
+#[test]
+fn test_describe_never_panics() {
+    let bytes = [
+        0x00, 0x02, 0x0F, 0x7F, 0x80, 0xC3, 0xF0, 0xF1, 0xF2, 0xF7, 0xFF,
+    ];
+    for &a in &bytes {
+        for &b in &bytes {
+            for &c in &bytes {
+                let description = describe_payload(&[a, b, c]);
+                assert!(description.starts_with("Header"));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_describe_long_malformed_payload() {
+    // Z flag set: rejected, and too long to quote on one line
+    let mut payload = vec![0x2F];
+    payload.extend((0..17).map(|i| i * 3));
+    assert_eq!(
+        describe_payload(&payload),
+        "Header 0x2F: flags Z, LEN=15\nMalformed (18 bytes):\n  \
+         0000  2F 00 03 06 09 0C 0F 12 15 18 1B 1E 21 24 27 2A\n  \
+         0010  2D 30\n  \
+         [error] byte 0: Z flag set, delta times are not supported\n  \
+         [error] byte 1: data byte 0x00 where a status byte was expected\n  \
+         [error] byte 16: LEN=15 but MIDI list is 17 bytes"
+    );
+}
@@ -0,0 +1,206 @@
+use rtp_midi_netsync::midi::{MidiEvent, MmcCommand, RealtimeMessage};
+use rtp_midi_netsync::mtc::{MtcFullFrame, VlcTickT};
+use rtp_midi_netsync::netsync::{
+    Endpoint, FullFrameRefreshPolicy, NetsyncConfig, NetsyncHandler, PacketStatus,
+};
+use rtp_midi_netsync::rtp::RtpHeader;
+
+/// Handler of a device that forwards every transport command it receives
+#[derive(Default)]
+struct Reflector {
+    received: Vec<MidiEvent>,
+}
+
+impl NetsyncHandler for Reflector {
+    fn on_play(&mut self) {
+        self.received.push(MidiEvent::Mmc(MmcCommand::Play));
+    }
+
+    fn on_stop(&mut self) {
+        self.received.push(MidiEvent::Mmc(MmcCommand::Stop));
+    }
+
+    fn on_locate(&mut self, position: MtcFullFrame) {
+        self.received.push(MidiEvent::Mmc(MmcCommand::Locate {
+            hour: position.hours,
+            minute: position.minutes,
+            second: position.seconds,
+            frame: position.frames,
+            subframe: 0,
+        }));
+    }
+
+    fn on_realtime(&mut self, message: RealtimeMessage) {
+        self.received.push(MidiEvent::Realtime(message));
+    }
+}
+
+fn endpoint(ssrc: u32) -> Endpoint {
+    Endpoint::with_config(NetsyncConfig {
+        ssrc,
+        refresh: FullFrameRefreshPolicy {
+            full_frame_interval_us: None,
+            quarter_frame_cycles: None,
+        },
+        ..NetsyncConfig::default()
+    })
+}
+
+fn locate() -> MidiEvent {
+    MidiEvent::Mmc(MmcCommand::Locate {
+        hour: 0,
+        minute: 2,
+        second: 0,
+        frame: 0,
+        subframe: 0,
+    })
+}
+
+/// Delivers `packets` to `to`, then lets its application forward everything it
+/// received, returning the packets sent in response
+fn deliver(
+    to: &mut Endpoint,
+    handler: &mut Reflector,
+    packets: Vec<Vec<u8>>,
+    now_us: VlcTickT,
+) -> Vec<Vec<u8>> {
+    for packet in packets {
+        to.receive_packet(&packet, now_us, handler).unwrap();
+    }
+    let mut responses = Vec::new();
+    for event in handler.received.drain(..) {
+        responses.extend(to.send_event(&event, now_us).unwrap());
+    }
+    responses.extend(to.tick(now_us));
+    responses
+}
+
+#[test]
+fn test_reflecting_endpoints_do_not_loop() {
+    let mut a = endpoint(0xA);
+    let mut b = endpoint(0xB);
+    let mut handler_a = Reflector::default();
+    let mut handler_b = Reflector::default();
+
+    let mut to_b = vec![
+        a.send_event(&locate(), 0).unwrap().unwrap(),
+        a.send_event(&MidiEvent::Mmc(MmcCommand::Play), 0)
+            .unwrap()
+            .unwrap(),
+    ];
+    let mut to_a = Vec::new();
+
+    // 1 ms one-way latency, one second of exchange
+    for step in 1..=1_000 {
+        let now_us = step * 1_000;
+        let from_b = deliver(&mut b, &mut handler_b, to_b, now_us);
+        let from_a = deliver(&mut a, &mut handler_a, to_a, now_us);
+        to_a = from_b;
+        to_b = from_a;
+    }
+
+    // B followed A without sending anything back, and A only sent its commands
+    // and the Full Frame refresh following the Locate
+    assert!(b.slave().tracker().is_playing());
+    assert_eq!(a.master().metrics().packets_sent, 4);
+    assert_eq!(b.master().metrics().packets_sent, 0);
+    assert_eq!(a.slave().metrics().packets_parsed, 0);
+}
+
+#[test]
+fn test_echo_window_expires() {
+    let mut a = endpoint(0xA);
+    let mut b = endpoint(0xB);
+    let play = MidiEvent::Mmc(MmcCommand::Play);
+
+    let packet = a.send_event(&play, 0).unwrap().unwrap();
+    b.receive_packet(&packet, 1_000, &mut Reflector::default())
+        .unwrap();
+
+    assert_eq!(b.send_event(&play, 10_000), Ok(None));
+    // Only one echo is suppressed per received event
+    assert!(b.send_event(&play, 20_000).unwrap().is_some());
+
+    b.receive_packet(
+        &a.send_event(&MidiEvent::Mmc(MmcCommand::Stop), 100_000)
+            .unwrap()
+            .unwrap(),
+        100_000,
+        &mut Reflector::default(),
+    )
+    .unwrap();
+    // A deliberate Stop well after the received one goes out
+    assert!(b
+        .send_event(&MidiEvent::Mmc(MmcCommand::Stop), 200_000)
+        .unwrap()
+        .is_some());
+}
+
+#[test]
+fn test_own_packets_are_dropped() {
+    let mut a = endpoint(0xA);
+    let packet = a
+        .send_event(&MidiEvent::Mmc(MmcCommand::Play), 0)
+        .unwrap()
+        .unwrap();
+
+    let mut handler = Reflector::default();
+    assert_eq!(
+        a.receive_packet(&packet, 0, &mut handler),
+        Ok(PacketStatus::Looped)
+    );
+    assert!(handler.received.is_empty());
+    assert_eq!(a.slave().metrics().packets_parsed, 0);
+}
+
+#[test]
+fn test_feedback_reaches_master_side() {
+    let mut a = endpoint(0xA);
+    let mut b = endpoint(0xB);
+    let mut handler = Reflector::default();
+
+    let first = a.send_event(&locate(), 0).unwrap().unwrap();
+    let _lost = a
+        .send_event(&MidiEvent::Mmc(MmcCommand::Play), 0)
+        .unwrap()
+        .unwrap();
+    // The Full Frame due after the Locate is lost as well
+    assert_eq!(a.tick(0).len(), 2);
+    let stop = a
+        .send_event(&MidiEvent::Mmc(MmcCommand::Stop), 1_000)
+        .unwrap()
+        .unwrap();
+
+    b.receive_packet(&first, 1_000, &mut handler).unwrap();
+    b.receive_packet(&stop, 2_000, &mut handler).unwrap();
+    let feedback = b.tick(2_000);
+    assert_eq!(feedback.len(), 1);
+
+    assert_eq!(
+        a.receive_packet(&feedback[0], 3_000, &mut handler),
+        Ok(PacketStatus::Feedback)
+    );
+    assert_eq!(a.master().metrics().resync_requests, 1);
+    // Stopped, so only the Full Frame is resent
+    assert_eq!(a.tick(3_000).len(), 1);
+}
+
+#[test]
+fn test_echo_matches_the_event_as_dispatched() {
+    let mut b = endpoint(0xB);
+    let mut handler = Reflector::default();
+
+    // A Locate addressed to device 0x10 is recognised under the lenient policy
+    let mut packet = RtpHeader::new(0, 0, 0xA).serialize().to_vec();
+    packet.extend_from_slice(&[
+        0x0D, 0xF0, 0x7F, 0x10, 0x06, 0x44, 0x06, 0x01, 0x00, 0x02, 0x00, 0x00, 0x00, 0xF7,
+    ]);
+    assert_eq!(
+        b.receive_packet(&packet, 0, &mut handler),
+        Ok(PacketStatus::Accepted)
+    );
+    assert_eq!(handler.received, vec![locate()]);
+
+    // Forwarding it is an echo, though the default slave flow passes it through
+    assert_eq!(b.send_event(&locate(), 1_000), Ok(None));
+}
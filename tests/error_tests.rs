@@ -0,0 +1,223 @@
+use rtp_midi_netsync::error::{
+    AppleMidiError, FlowError, MtcError, NetsyncError, ParseError, PARSE_CONTEXT_LENGTH,
+};
+use rtp_midi_netsync::midi::parse_midi_list_strict;
+use rtp_midi_netsync::Error;
+
+// === Stable Codes ===
+
+/// Every code ever assigned; extend it, never renumber or reuse an entry
+#[test]
+fn test_error_codes_are_pinned() {
+    let parse_errors = [
+        (ParseError::EmptyBuffer, 100),
+        (
+            ParseError::BufferTooSmall {
+                requested: 0,
+                available: 0,
+            },
+            101,
+        ),
+        (ParseError::InsufficientHeaderData, 102),
+        (
+            ParseError::InvalidMidiData {
+                position: 0,
+                byte: 0,
+            },
+            103,
+        ),
+        (ParseError::UnsupportedRtpVersion(0), 104),
+    ];
+    for (error, code) in parse_errors {
+        assert_eq!(error.code(), code, "{:?}", error);
+        assert_eq!(ParseError::from_code(code), Some(error));
+    }
+
+    #[allow(deprecated)]
+    let mtc_errors = [
+        (MtcError::IncompleteFrame, 200),
+        (MtcError::InvalidFrameType, 201),
+        (MtcError::InvalidValue, 202),
+        (MtcError::MalformedTimecode, 203),
+        (MtcError::TooManyFrames, 204),
+        (MtcError::InvalidFrames(0), 205),
+        (MtcError::InvalidSeconds(0), 206),
+        (MtcError::InvalidMinutes(0), 207),
+        (MtcError::InvalidHours(0), 208),
+        (MtcError::UnsupportedFrameRate(0), 209),
+    ];
+    for (error, code) in mtc_errors {
+        assert_eq!(error.code(), code, "{:?}", error);
+        assert_eq!(MtcError::from_code(code), Some(error));
+    }
+
+    let netsync_errors = [
+        (NetsyncError::InvalidMasterEvent, 300),
+        (NetsyncError::InvalidSlaveEvent, 301),
+        (NetsyncError::SessionFailed, 302),
+        (NetsyncError::TooShort { got: 0, min: 0 }, 303),
+        (
+            NetsyncError::DeclaredLengthExceedsBuffer {
+                declared: 0,
+                available: 0,
+            },
+            304,
+        ),
+        (NetsyncError::TrailingData { extra: 0 }, 305),
+        (NetsyncError::UnsupportedFlags { flags: 0 }, 306),
+    ];
+    for (error, code) in netsync_errors {
+        assert_eq!(error.code(), code, "{:?}", error);
+        assert_eq!(NetsyncError::from_code(code), Some(error));
+    }
+}
+
+#[test]
+fn test_error_codes_keep_fields_out() {
+    let error = ParseError::BufferTooSmall {
+        requested: 13,
+        available: 8,
+    };
+    assert_eq!(error.code(), 101);
+    assert_eq!(
+        ParseError::from_code(101),
+        Some(ParseError::BufferTooSmall {
+            requested: 0,
+            available: 0,
+        })
+    );
+}
+
+#[test]
+fn test_unassigned_codes() {
+    for code in [0, 99, 105, 199, 210, 299, 307, u16::MAX] {
+        assert_eq!(ParseError::from_code(code), None, "{}", code);
+        assert_eq!(MtcError::from_code(code), None, "{}", code);
+        assert_eq!(NetsyncError::from_code(code), None, "{}", code);
+    }
+}
+
+// === Recoverable and Fatal Errors ===
+
+#[test]
+fn test_error_classification() {
+    let table: Vec<(Error, bool)> = vec![
+        (ParseError::EmptyBuffer.into(), true),
+        (
+            ParseError::BufferTooSmall {
+                requested: 13,
+                available: 8,
+            }
+            .into(),
+            true,
+        ),
+        (ParseError::InsufficientHeaderData.into(), true),
+        (
+            ParseError::InvalidMidiData {
+                position: 3,
+                byte: 0x90,
+            }
+            .into(),
+            true,
+        ),
+        (ParseError::UnsupportedRtpVersion(1).into(), false),
+        (MtcError::IncompleteFrame.into(), true),
+        (MtcError::InvalidFrameType.into(), true),
+        (MtcError::InvalidFrames(30).into(), true),
+        (MtcError::MalformedTimecode.into(), true),
+        (MtcError::TooManyFrames.into(), true),
+        (NetsyncError::InvalidMasterEvent.into(), true),
+        (NetsyncError::InvalidSlaveEvent.into(), true),
+        (NetsyncError::SessionFailed.into(), false),
+        (NetsyncError::TrailingData { extra: 1 }.into(), true),
+        (
+            AppleMidiError::TooShort {
+                expected: 16,
+                available: 4,
+            }
+            .into(),
+            true,
+        ),
+        (AppleMidiError::InvalidSignature.into(), true),
+        (AppleMidiError::UnknownCommand(*b"XX").into(), true),
+        (AppleMidiError::UnsupportedVersion(1).into(), false),
+        (AppleMidiError::InvalidName.into(), true),
+    ];
+    for (error, recoverable) in table {
+        assert_eq!(error.is_recoverable(), recoverable, "{:?}", error);
+    }
+
+    // A flow failure is fatal if its cause is
+    let flow = |cause| FlowError {
+        kind: NetsyncError::InvalidSlaveEvent,
+        cause: Some(cause),
+    };
+    assert!(flow(ParseError::EmptyBuffer).is_recoverable());
+    assert!(!flow(ParseError::UnsupportedRtpVersion(3)).is_recoverable());
+}
+
+// === Context Snippets ===
+
+#[test]
+fn test_parse_error_context_short_buffer() {
+    let list = [0xF0, 0x7D, 0x90, 0x40, 0xF7];
+    let error = parse_midi_list_strict(&list, list.len()).unwrap_err();
+    let context = error.clone().with_context(&list);
+    assert_eq!(context.error, error);
+    assert_eq!(context.offset, 0);
+    assert_eq!(context.snippet(), &list);
+    assert_eq!(
+        context.to_string(),
+        "Invalid MIDI data at position 2: 0x90 (bytes 0..5 of 5: F0 7D 90 40 F7)"
+    );
+    assert_eq!(
+        std::error::Error::source(&context).unwrap().to_string(),
+        error.to_string()
+    );
+}
+
+#[test]
+fn test_parse_error_context_is_truncated_around_the_error() {
+    // A 40-byte SysEx with a status byte at position 30
+    let mut list = vec![0xF0];
+    list.extend(1..39);
+    list.push(0xF7);
+    list[30] = 0x99;
+    let error = parse_midi_list_strict(&list, list.len()).unwrap_err();
+    assert_eq!(
+        error,
+        ParseError::InvalidMidiData {
+            position: 30,
+            byte: 0x99
+        }
+    );
+
+    let context = error.with_context(&list);
+    assert_eq!(context.snippet().len(), PARSE_CONTEXT_LENGTH);
+    assert_eq!(context.offset, 22);
+    assert_eq!(context.snippet(), &list[22..38]);
+    assert_eq!(
+        context.to_string(),
+        "Invalid MIDI data at position 30: 0x99 \
+         (bytes 22..38 of 40: 16 17 18 19 1A 1B 1C 1D 99 1F 20 21 22 23 24 25)"
+    );
+
+    // Near the end, the snippet keeps its full length
+    let error = ParseError::InvalidMidiData {
+        position: 39,
+        byte: 0xF7,
+    };
+    let context = error.with_context(&list);
+    assert_eq!(context.offset, 24);
+    assert_eq!(context.snippet(), &list[24..]);
+
+    // Other errors show the start of the buffer
+    let context = ParseError::BufferTooSmall {
+        requested: 50,
+        available: 40,
+    }
+    .with_context(&list);
+    assert_eq!(context.offset, 0);
+    assert_eq!(context.snippet(), &list[..PARSE_CONTEXT_LENGTH]);
+    assert_eq!(ParseError::EmptyBuffer.with_context(&[]).snippet(), &[]);
+}
@@ -0,0 +1,267 @@
+use std::collections::VecDeque;
+
+use rtp_midi_netsync::midi::{MidiEvent, MmcCommand};
+use rtp_midi_netsync::mtc::{smpte_to_quarter_frames, smpte_to_us, us_to_smpte, MtcFullFrame};
+use rtp_midi_netsync::netsync::{
+    slave_netsync_flow, FullFrameRefreshPolicy, MasterSession, NetsyncConfig, NetsyncHandler,
+    SlaveFeedback, SlaveSession,
+};
+use rtp_midi_netsync::rtp::RtpHeader;
+
+/// Quarter frame period at 30fps, rounded to whole microseconds
+const QF_PERIOD_US: u64 = 8_333;
+
+/// One-way network latency of the simulated link
+const LATENCY_US: u64 = 5_000;
+
+struct NoopHandler;
+
+impl NetsyncHandler for NoopHandler {}
+
+/// In-memory one-way link delivering packets after a fixed latency, dropping
+/// everything sent during the configured loss windows
+struct LossyChannel {
+    in_flight: VecDeque<(u64, Vec<u8>)>,
+    loss_windows: Vec<(u64, u64)>,
+}
+
+impl LossyChannel {
+    fn new(loss_windows: Vec<(u64, u64)>) -> Self {
+        Self {
+            in_flight: VecDeque::new(),
+            loss_windows,
+        }
+    }
+
+    fn send(&mut self, now_us: u64, packet: Vec<u8>) {
+        let lost = self
+            .loss_windows
+            .iter()
+            .any(|&(start, end)| (start..end).contains(&now_us));
+        if !lost {
+            self.in_flight.push_back((now_us + LATENCY_US, packet));
+        }
+    }
+
+    fn receive(&mut self, now_us: u64) -> Vec<Vec<u8>> {
+        let mut delivered = Vec::new();
+        while self
+            .in_flight
+            .front()
+            .is_some_and(|(arrival, _)| *arrival <= now_us)
+        {
+            delivered.push(self.in_flight.pop_front().unwrap().1);
+        }
+        delivered
+    }
+}
+
+fn is_full_frame(packet: &[u8]) -> bool {
+    let payload = RtpHeader::parse(packet).unwrap().1;
+    matches!(slave_netsync_flow(payload), Ok(MidiEvent::MtcFull { .. }))
+}
+
+/// Outcome of a simulated session
+struct Run {
+    /// Arrival times of Full Frames at the slave
+    full_frames_at_slave: Vec<u64>,
+    /// Arrival time of the first packet after each loss window
+    first_arrival_after_loss: Vec<u64>,
+    master: MasterSession,
+    slave: SlaveSession,
+}
+
+/// Runs a master rolling from one minute for `duration_us`, sending quarter frames
+/// every period, over a link losing the downstream packets sent in `loss_windows`.
+fn run(loss_windows: Vec<(u64, u64)>, duration_us: u64) -> Run {
+    let mut master = MasterSession::with_config(NetsyncConfig {
+        ssrc: 0x0000_AAAA,
+        // No periodic refresh: only feedback can trigger a Full Frame after the start
+        refresh: FullFrameRefreshPolicy {
+            full_frame_interval_us: None,
+            quarter_frame_cycles: None,
+        },
+        ..NetsyncConfig::default()
+    });
+    let mut slave = SlaveSession::with_config(NetsyncConfig {
+        ssrc: 0x0000_BBBB,
+        ..NetsyncConfig::default()
+    });
+    let mut downstream = LossyChannel::new(loss_windows.clone());
+    let mut upstream = LossyChannel::new(Vec::new());
+
+    let start = MtcFullFrame {
+        hours: 0,
+        minutes: 1,
+        seconds: 0,
+        frames: 0,
+    };
+    let start_us = smpte_to_us(&start);
+    for event in [
+        MidiEvent::Mmc(MmcCommand::Locate {
+            hour: 0,
+            minute: 1,
+            second: 0,
+            frame: 0,
+            subframe: 0,
+        }),
+        MidiEvent::Mmc(MmcCommand::Play),
+    ] {
+        downstream.send(0, master.packet_for_event(&event, 0).unwrap());
+    }
+
+    let mut full_frames_at_slave = Vec::new();
+    let mut first_arrival_after_loss = vec![None; loss_windows.len()];
+    let mut piece = 0u64;
+    let mut now_us = 0;
+    while now_us <= duration_us {
+        // Master side: one quarter frame per period, then maintenance packets
+        let cycle_us = start_us + (piece / 8) * 8 * QF_PERIOD_US;
+        let qf = smpte_to_quarter_frames(&us_to_smpte(cycle_us))[(piece % 8) as usize];
+        let event = MidiEvent::MtcQuarter {
+            msg_type: qf.frame_type,
+            value: qf.value,
+        };
+        downstream.send(now_us, master.packet_for_event(&event, now_us).unwrap());
+        for packet in upstream.receive(now_us) {
+            master.handle_feedback(SlaveFeedback::from_packet(&packet).unwrap());
+        }
+        for packet in master.tick(now_us) {
+            downstream.send(now_us, packet);
+        }
+
+        // Slave side
+        for packet in downstream.receive(now_us) {
+            if is_full_frame(&packet) {
+                full_frames_at_slave.push(now_us);
+            }
+            for (i, &(_, end)) in loss_windows.iter().enumerate() {
+                if now_us >= end + LATENCY_US && first_arrival_after_loss[i].is_none() {
+                    first_arrival_after_loss[i] = Some(now_us);
+                }
+            }
+            slave
+                .feed_packet(&packet, now_us, &mut NoopHandler)
+                .unwrap();
+        }
+        if let Some(packet) = slave.feedback_to_send(now_us) {
+            upstream.send(now_us, packet);
+        }
+
+        piece += 1;
+        now_us += QF_PERIOD_US;
+    }
+
+    Run {
+        full_frames_at_slave,
+        first_arrival_after_loss: first_arrival_after_loss.into_iter().flatten().collect(),
+        master,
+        slave,
+    }
+}
+
+#[test]
+fn test_feedback_message_roundtrip() {
+    for feedback in [SlaveFeedback::PacketLoss, SlaveFeedback::Requested] {
+        let payload = feedback.to_payload();
+        let event = slave_netsync_flow(&payload).unwrap();
+        assert_eq!(SlaveFeedback::from_event(&event), Some(feedback));
+    }
+    assert_eq!(
+        SlaveFeedback::PacketLoss.to_sysex(),
+        [0xF0, 0x7D, 0x4E, 0x53, 0x01, 0x01, 0xF7]
+    );
+    assert_eq!(
+        SlaveFeedback::from_event(&MidiEvent::Other(vec![
+            0xF0, 0x7D, 0x4E, 0x53, 0x01, 0x09, 0xF7
+        ])),
+        None
+    );
+    assert_eq!(
+        SlaveFeedback::from_event(&MidiEvent::Mmc(MmcCommand::Play)),
+        None
+    );
+}
+
+#[test]
+fn test_no_feedback_without_loss() {
+    let run = run(Vec::new(), 2_000_000);
+    // Only the Full Frame following the initial Locate
+    assert_eq!(run.full_frames_at_slave.len(), 1);
+    assert_eq!(run.slave.metrics().resync_requests, 0);
+    assert_eq!(run.master.metrics().resync_requests, 0);
+}
+
+#[test]
+fn test_loss_burst_recovers_within_one_round_trip() {
+    let bursts = vec![(500_000, 600_000), (1_200_000, 1_250_000)];
+    let run = run(bursts, 2_000_000);
+
+    assert_eq!(run.first_arrival_after_loss.len(), 2);
+    let refreshes = &run.full_frames_at_slave[1..];
+    assert_eq!(refreshes.len(), 2);
+
+    for (detected_at, recovered_at) in run.first_arrival_after_loss.iter().zip(refreshes) {
+        // Request goes up, the master's next tick sends the Full Frame back down
+        let recovery_us = recovered_at - detected_at;
+        assert!(
+            recovery_us <= 2 * LATENCY_US + QF_PERIOD_US,
+            "recovered after {} us",
+            recovery_us
+        );
+    }
+
+    assert_eq!(run.slave.metrics().resync_requests, 2);
+    assert_eq!(run.master.metrics().resync_requests, 2);
+    assert_eq!(run.slave.metrics().sequence_gaps, 2);
+}
+
+#[test]
+fn test_feedback_is_coalesced_and_spaced() {
+    let mut slave = SlaveSession::new();
+    assert_eq!(slave.feedback_to_send(0), None);
+
+    slave.request_resync();
+    slave.request_resync();
+    let packet = slave.feedback_to_send(0).unwrap();
+    assert_eq!(
+        SlaveFeedback::from_packet(&packet),
+        Some(SlaveFeedback::Requested)
+    );
+    assert_eq!(slave.feedback_to_send(1_000), None);
+
+    // A new request within the hold-off waits for it to elapse
+    slave.request_resync();
+    assert_eq!(slave.feedback_to_send(50_000), None);
+    assert!(slave.feedback_to_send(100_000).is_some());
+    assert_eq!(slave.metrics().resync_requests, 2);
+}
+
+#[test]
+fn test_full_frame_cancels_pending_feedback() {
+    let mut master = MasterSession::new();
+    let mut slave = SlaveSession::new();
+    let play = master
+        .packet_for_event(&MidiEvent::Mmc(MmcCommand::Play), 0)
+        .unwrap();
+    let _lost = master
+        .packet_for_event(&MidiEvent::Mmc(MmcCommand::Stop), 0)
+        .unwrap();
+    let full = master
+        .packet_for_event(
+            &MidiEvent::MtcFull {
+                hour: 0,
+                minute: 0,
+                second: 1,
+                frame: 0,
+            },
+            0,
+        )
+        .unwrap();
+
+    slave.feed_packet(&play, 0, &mut NoopHandler).unwrap();
+    // The gap is detected on the Full Frame itself, which already resyncs
+    slave.feed_packet(&full, 0, &mut NoopHandler).unwrap();
+    assert_eq!(slave.metrics().sequence_gaps, 1);
+    assert_eq!(slave.feedback_to_send(0), None);
+}
@@ -0,0 +1,249 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::ffi::c_int;
+
+use rtp_midi_netsync::ffi::{
+    vlc_rtpmidi_build_midi_ffi, vlc_rtpmidi_create_clock_event, vlc_rtpmidi_create_continue_event,
+    vlc_rtpmidi_create_mmc_deferred_play_event, vlc_rtpmidi_create_mmc_eject_event,
+    vlc_rtpmidi_create_mmc_fast_forward_event, vlc_rtpmidi_create_mmc_locate_event,
+    vlc_rtpmidi_create_mmc_pause_event, vlc_rtpmidi_create_mmc_play_event,
+    vlc_rtpmidi_create_mmc_record_exit_event, vlc_rtpmidi_create_mmc_record_strobe_event,
+    vlc_rtpmidi_create_mmc_reset_event, vlc_rtpmidi_create_mmc_rewind_event,
+    vlc_rtpmidi_create_mmc_stop_event, vlc_rtpmidi_create_mtc_full_event,
+    vlc_rtpmidi_create_mtc_quarter_event, vlc_rtpmidi_create_song_position_event,
+    vlc_rtpmidi_create_start_event, vlc_rtpmidi_create_stop_rt_event,
+    vlc_rtpmidi_default_netsync_config, vlc_rtpmidi_master_netsync_flow_dev_ffi,
+    vlc_rtpmidi_master_netsync_flow_ffi, vlc_rtpmidi_master_session_free,
+    vlc_rtpmidi_master_session_metrics, vlc_rtpmidi_master_session_new,
+    vlc_rtpmidi_master_session_reset_metrics, vlc_rtpmidi_parse_midi_ffi,
+    vlc_rtpmidi_slave_netsync_flow_ffi, vlc_rtpmidi_slave_netsync_flow_raw_ffi,
+    vlc_rtpmidi_slave_session_free, vlc_rtpmidi_slave_session_metrics,
+    vlc_rtpmidi_slave_session_new, vlc_rtpmidi_slave_session_reset_metrics,
+    vlc_rtpmidi_validate_payload_ffi, VlcRtpmidiErrorCode, VlcRtpmidiEvent, VlcRtpmidiEventType,
+    VlcRtpmidiNetsyncMetrics,
+};
+
+/// Counts the allocations of the current thread, so parallel tests do not interfere
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn count_allocation() {
+    let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count_allocation();
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Returns the number of allocations `body` made
+fn allocations_during<R>(body: impl FnOnce() -> R) -> (R, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = body();
+    (result, ALLOCATIONS.with(Cell::get) - before)
+}
+
+fn every_event() -> Vec<VlcRtpmidiEvent> {
+    vec![
+        vlc_rtpmidi_create_mtc_quarter_event(3, 9),
+        vlc_rtpmidi_create_mtc_full_event(1, 2, 3, 4),
+        vlc_rtpmidi_create_mmc_stop_event(),
+        vlc_rtpmidi_create_mmc_play_event(),
+        vlc_rtpmidi_create_mmc_locate_event(1, 2, 3, 4),
+        vlc_rtpmidi_create_mmc_deferred_play_event(),
+        vlc_rtpmidi_create_mmc_fast_forward_event(),
+        vlc_rtpmidi_create_mmc_rewind_event(),
+        vlc_rtpmidi_create_mmc_record_strobe_event(),
+        vlc_rtpmidi_create_mmc_record_exit_event(),
+        vlc_rtpmidi_create_mmc_pause_event(),
+        vlc_rtpmidi_create_mmc_eject_event(),
+        vlc_rtpmidi_create_mmc_reset_event(),
+        vlc_rtpmidi_create_song_position_event(0x3FFF),
+        vlc_rtpmidi_create_clock_event(),
+        vlc_rtpmidi_create_start_event(),
+        vlc_rtpmidi_create_continue_event(),
+        vlc_rtpmidi_create_stop_rt_event(),
+    ]
+}
+
+fn empty_event() -> VlcRtpmidiEvent {
+    VlcRtpmidiEvent {
+        event_type: VlcRtpmidiEventType::MmcStop as u32,
+        data: [0; 8],
+        data_len: 0,
+    }
+}
+
+#[test]
+fn test_allocations_are_counted() {
+    let (_, allocations) = allocations_during(|| Box::new(0u8));
+    assert_eq!(allocations, 1);
+}
+
+#[test]
+fn test_master_flow_does_not_allocate() {
+    for event in every_event() {
+        let mut buffer = [0u8; 16];
+        let mut actual_size = 0;
+        let (code, allocations) = allocations_during(|| unsafe {
+            vlc_rtpmidi_master_netsync_flow_ffi(
+                &event,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &mut actual_size,
+            )
+        });
+        assert_eq!(code, VlcRtpmidiErrorCode::Success as c_int);
+        assert_eq!(allocations, 0, "{:?}", event.event_type);
+
+        let (code, allocations) = allocations_during(|| unsafe {
+            vlc_rtpmidi_master_netsync_flow_dev_ffi(
+                &event,
+                0x05,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &mut actual_size,
+            )
+        });
+        assert_eq!(code, VlcRtpmidiErrorCode::Success as c_int);
+        assert_eq!(allocations, 0, "{:?}", event.event_type);
+
+        let mut midi = [0u8; 16];
+        let mut written = 0;
+        let (code, allocations) = allocations_during(|| unsafe {
+            vlc_rtpmidi_build_midi_ffi(&event, midi.as_mut_ptr(), midi.len(), &mut written)
+        });
+        assert_eq!(code, VlcRtpmidiErrorCode::Success as c_int);
+        assert_eq!(allocations, 0, "{:?}", event.event_type);
+    }
+
+    // Rejections do not allocate either
+    let mut event = vlc_rtpmidi_create_mmc_play_event();
+    event.data_len = 7;
+    let mut buffer = [0u8; 16];
+    let mut actual_size = 0;
+    let (code, allocations) = allocations_during(|| unsafe {
+        vlc_rtpmidi_master_netsync_flow_ffi(&event, buffer.as_mut_ptr(), 1, &mut actual_size)
+    });
+    assert_eq!(code, VlcRtpmidiErrorCode::InvalidEventType as c_int);
+    assert_eq!(allocations, 0);
+    let event = vlc_rtpmidi_create_mmc_locate_event(1, 2, 3, 4);
+    let (code, allocations) = allocations_during(|| unsafe {
+        vlc_rtpmidi_master_netsync_flow_ffi(&event, buffer.as_mut_ptr(), 1, &mut actual_size)
+    });
+    assert_eq!(code, VlcRtpmidiErrorCode::BufferTooSmall as c_int);
+    assert_eq!(allocations, 0);
+}
+
+#[test]
+fn test_slave_flow_does_not_allocate() {
+    let mut payloads: Vec<Vec<u8>> = Vec::new();
+    for event in every_event() {
+        let mut buffer = [0u8; 16];
+        let mut actual_size = 0;
+        unsafe {
+            vlc_rtpmidi_master_netsync_flow_ffi(
+                &event,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &mut actual_size,
+            )
+        };
+        payloads.push(buffer[..actual_size].to_vec());
+    }
+    payloads.extend([
+        // Pass-through messages: SysEx, beat clock, Song Position Pointer, Note On
+        vec![0x05, 0xF0, 0x7D, 0x01, 0x02, 0xF7],
+        vec![0x01, 0xF8],
+        vec![0x03, 0xF2, 0x10, 0x00],
+        vec![0x03, 0x90, 0x40, 0x7F],
+        // No commands, then malformed
+        vec![0x00],
+        vec![0x05, 0xF1],
+        vec![0x02, 0xF1, 0x80],
+        vec![],
+    ]);
+
+    for payload in &payloads {
+        let mut event = empty_event();
+        let (_, allocations) = allocations_during(|| unsafe {
+            vlc_rtpmidi_slave_netsync_flow_ffi(payload.as_ptr(), payload.len(), &mut event)
+        });
+        assert_eq!(allocations, 0, "{:02X?}", payload);
+
+        let mut kind = 0;
+        let (_, allocations) = allocations_during(|| unsafe {
+            vlc_rtpmidi_validate_payload_ffi(payload.as_ptr(), payload.len(), &mut kind)
+        });
+        assert_eq!(allocations, 0, "{:02X?}", payload);
+
+        let mut midi = [0u8; 16];
+        let mut midi_len = 0;
+        let mut event_type = VlcRtpmidiEventType::Raw;
+        let (_, allocations) = allocations_during(|| unsafe {
+            vlc_rtpmidi_slave_netsync_flow_raw_ffi(
+                payload.as_ptr(),
+                payload.len(),
+                midi.as_mut_ptr(),
+                midi.len(),
+                &mut midi_len,
+                &mut event_type,
+            )
+        });
+        assert_eq!(allocations, 0, "{:02X?}", payload);
+
+        if let Some(midi) = payload.get(1..).filter(|midi| !midi.is_empty()) {
+            let mut consumed = 0;
+            let (_, allocations) = allocations_during(|| unsafe {
+                vlc_rtpmidi_parse_midi_ffi(midi.as_ptr(), midi.len(), &mut event, &mut consumed)
+            });
+            assert_eq!(allocations, 0, "{:02X?}", midi);
+        }
+    }
+}
+
+#[test]
+fn test_session_metrics_do_not_allocate() {
+    let config = vlc_rtpmidi_default_netsync_config();
+    let master = unsafe { vlc_rtpmidi_master_session_new(&config) };
+    let slave = unsafe { vlc_rtpmidi_slave_session_new(&config) };
+    let mut metrics = VlcRtpmidiNetsyncMetrics::default();
+
+    let (codes, allocations) = allocations_during(|| unsafe {
+        [
+            vlc_rtpmidi_master_session_metrics(master, &mut metrics),
+            vlc_rtpmidi_slave_session_metrics(slave, &mut metrics),
+            vlc_rtpmidi_master_session_reset_metrics(master),
+            vlc_rtpmidi_slave_session_reset_metrics(slave),
+        ]
+    });
+    assert_eq!(codes, [VlcRtpmidiErrorCode::Success as c_int; 4]);
+    assert_eq!(allocations, 0);
+
+    unsafe {
+        vlc_rtpmidi_master_session_free(master);
+        vlc_rtpmidi_slave_session_free(slave);
+    }
+}
@@ -0,0 +1,330 @@
+use std::ffi::{c_char, c_int, c_void, CStr};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Barrier, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use rtp_midi_netsync::ffi::{
+    vlc_rtpmidi_create_mmc_play_event, vlc_rtpmidi_default_netsync_config,
+    vlc_rtpmidi_get_error_message, vlc_rtpmidi_get_last_error_detail,
+    vlc_rtpmidi_master_netsync_flow_ffi, vlc_rtpmidi_master_session_free,
+    vlc_rtpmidi_master_session_new, vlc_rtpmidi_master_session_packet_for_event,
+    vlc_rtpmidi_set_log_callback, vlc_rtpmidi_slave_netsync_flow_ffi,
+    vlc_rtpmidi_slave_netsync_flow_multi_ffi, vlc_rtpmidi_slave_session_feed,
+    vlc_rtpmidi_slave_session_free, vlc_rtpmidi_slave_session_new, VlcRtpmidiErrorCode,
+    VlcRtpmidiEvent, VlcRtpmidiEventType, VlcRtpmidiLogLevel,
+};
+
+/// The callback is process-wide: tests installing one run one at a time
+static LOG_LOCK: Mutex<()> = Mutex::new(());
+
+/// Calls the Rust closure behind `user` with the decoded message
+unsafe extern "C" fn trampoline<F: Fn(c_int, &str) + Sync>(
+    level: c_int,
+    msg: *const c_char,
+    user: *mut c_void,
+) {
+    let callback = &*(user as *const F);
+    callback(level, &CStr::from_ptr(msg).to_string_lossy());
+}
+
+/// Installs `callback`, which must outlive its installation
+fn install<F: Fn(c_int, &str) + Sync>(callback: &F) {
+    unsafe {
+        vlc_rtpmidi_set_log_callback(Some(trampoline::<F>), callback as *const F as *mut c_void)
+    };
+}
+
+fn clear() {
+    unsafe { vlc_rtpmidi_set_log_callback(None, std::ptr::null_mut()) };
+}
+
+/// Runs `body` with a callback recording every message
+fn capture_log<R>(body: impl FnOnce() -> R) -> (R, Vec<(c_int, String)>) {
+    let _guard = LOG_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let messages = Mutex::new(Vec::new());
+    let record = |level: c_int, msg: &str| messages.lock().unwrap().push((level, msg.to_string()));
+    install(&record);
+    let result = body();
+    clear();
+    (result, messages.into_inner().unwrap())
+}
+
+fn slave_flow(payload: &[u8]) -> c_int {
+    let mut event = VlcRtpmidiEvent {
+        event_type: VlcRtpmidiEventType::MmcStop as u32,
+        data: [0; 8],
+        data_len: 0,
+    };
+    unsafe { vlc_rtpmidi_slave_netsync_flow_ffi(payload.as_ptr(), payload.len(), &mut event) }
+}
+
+#[test]
+fn test_rejected_payload_names_the_offending_byte() {
+    // LEN=5 with a single MIDI byte present
+    let (code, messages) = capture_log(|| slave_flow(&[0x05, 0xF1]));
+    assert_eq!(code, VlcRtpmidiErrorCode::TruncatedPayload as c_int);
+    assert_eq!(messages.len(), 1);
+    let (level, message) = &messages[0];
+    assert_eq!(*level, VlcRtpmidiLogLevel::Warning as c_int);
+    assert!(
+        message.starts_with("slave flow: payload rejected: [error] byte "),
+        "{}",
+        message
+    );
+
+    // Accepted payloads log nothing
+    let (code, messages) = capture_log(|| slave_flow(&[0x03, 0x90, 0x40, 0x7F]));
+    assert_eq!(code, VlcRtpmidiErrorCode::InvalidEventType as c_int);
+    assert!(messages.is_empty(), "{:?}", messages);
+    let (_, messages) = capture_log(|| slave_flow(&[0x02, 0xF1, 0x25]));
+    assert!(messages.is_empty(), "{:?}", messages);
+}
+
+#[test]
+fn test_rejected_events_and_configs_are_logged() {
+    let (code, messages) = capture_log(|| {
+        let mut event = vlc_rtpmidi_create_mmc_play_event();
+        event.data_len = 3;
+        let mut buffer = [0u8; 16];
+        let mut actual_size = 0;
+        unsafe {
+            vlc_rtpmidi_master_netsync_flow_ffi(
+                &event,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &mut actual_size,
+            )
+        }
+    });
+    assert_eq!(code, VlcRtpmidiErrorCode::InvalidEventType as c_int);
+    assert_eq!(
+        messages,
+        [(
+            VlcRtpmidiLogLevel::Warning as c_int,
+            "event rejected: data_len 3 is invalid for event type 3".to_string()
+        )]
+    );
+
+    let (_, messages) = capture_log(|| {
+        let mut config = vlc_rtpmidi_default_netsync_config();
+        config.frame_rate = 9;
+        assert!(unsafe { vlc_rtpmidi_slave_session_new(&config) }.is_null());
+        config = vlc_rtpmidi_default_netsync_config();
+        config.device_id = 0x80;
+        assert!(unsafe { vlc_rtpmidi_master_session_new(&config) }.is_null());
+    });
+    assert_eq!(
+        messages,
+        [
+            (
+                VlcRtpmidiLogLevel::Error as c_int,
+                "config rejected: frame_rate 9 is out of range".to_string()
+            ),
+            (
+                VlcRtpmidiLogLevel::Error as c_int,
+                "config rejected: device_id 0x80 is above 0x7F".to_string()
+            ),
+        ]
+    );
+
+    let (code, messages) = capture_log(|| {
+        let mut count = 0;
+        unsafe {
+            vlc_rtpmidi_slave_netsync_flow_multi_ffi(
+                [0x01].as_ptr(),
+                0,
+                std::ptr::null_mut(),
+                0,
+                &mut count,
+            )
+        }
+    });
+    assert_eq!(code, VlcRtpmidiErrorCode::InvalidSlaveEvent as c_int);
+    assert_eq!(messages[0].1, "slave flow: empty buffer rejected");
+}
+
+#[test]
+fn test_session_rejections_are_logged() {
+    let config = vlc_rtpmidi_default_netsync_config();
+    let master = unsafe { vlc_rtpmidi_master_session_new(&config) };
+    let slave = unsafe { vlc_rtpmidi_slave_session_new(&config) };
+    let mut packet = [0u8; 32];
+    let mut len = 0;
+    let play = vlc_rtpmidi_create_mmc_play_event();
+    unsafe {
+        vlc_rtpmidi_master_session_packet_for_event(
+            master,
+            &play,
+            0,
+            packet.as_mut_ptr(),
+            packet.len(),
+            &mut len,
+        )
+    };
+
+    let (codes, messages) = capture_log(|| unsafe {
+        [
+            vlc_rtpmidi_slave_session_feed(slave, packet.as_ptr(), len, 0),
+            vlc_rtpmidi_slave_session_feed(slave, packet.as_ptr(), len, 0),
+            vlc_rtpmidi_slave_session_feed(slave, packet.as_ptr(), 4, 0),
+        ]
+    });
+    assert_eq!(
+        codes,
+        [
+            VlcRtpmidiErrorCode::Success as c_int,
+            VlcRtpmidiErrorCode::Duplicate as c_int,
+            VlcRtpmidiErrorCode::TruncatedPayload as c_int,
+        ]
+    );
+    assert_eq!(messages.len(), 2);
+    assert_eq!(
+        messages[0],
+        (
+            VlcRtpmidiLogLevel::Debug as c_int,
+            "slave session: packet dropped: repeated sequence number".to_string()
+        )
+    );
+    assert_eq!(messages[1].0, VlcRtpmidiLogLevel::Warning as c_int);
+    assert!(
+        messages[1]
+            .1
+            .starts_with("slave session: RTP header rejected: "),
+        "{}",
+        messages[1].1
+    );
+
+    unsafe {
+        vlc_rtpmidi_master_session_free(master);
+        vlc_rtpmidi_slave_session_free(slave);
+    }
+}
+
+#[test]
+fn test_callback_is_not_invoked_after_being_cleared() {
+    let _guard = LOG_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let calls = AtomicUsize::new(0);
+    let cleared = AtomicBool::new(false);
+    let late_calls = AtomicUsize::new(0);
+    let count = |_: c_int, _: &str| {
+        calls.fetch_add(1, Ordering::SeqCst);
+        if cleared.load(Ordering::SeqCst) {
+            late_calls.fetch_add(1, Ordering::SeqCst);
+        }
+    };
+    let stop = AtomicBool::new(false);
+
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                while !stop.load(Ordering::SeqCst) {
+                    slave_flow(&[0x05, 0xF1]);
+                }
+            });
+        }
+
+        install(&count);
+        while calls.load(Ordering::SeqCst) < 100 {
+            thread::yield_now();
+        }
+        clear();
+        cleared.store(true, Ordering::SeqCst);
+
+        // Keep logging for a while with no callback installed
+        thread::sleep(Duration::from_millis(20));
+        stop.store(true, Ordering::SeqCst);
+    });
+
+    assert_eq!(late_calls.load(Ordering::SeqCst), 0);
+}
+
+fn last_error_detail() -> String {
+    unsafe { CStr::from_ptr(vlc_rtpmidi_get_last_error_detail()) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[test]
+fn test_last_error_detail_is_set_on_failure_and_cleared_on_success() {
+    // Without a callback, the detail comes from the MIDI parser
+    let _guard = LOG_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let truncated = [0x0D, 0xF0, 0x7F, 0x7F, 0x06, 0x44, 0x06, 0x01, 0x21];
+    assert_eq!(
+        slave_flow(&truncated),
+        VlcRtpmidiErrorCode::TruncatedPayload as c_int
+    );
+    assert_eq!(
+        last_error_detail(),
+        "slave flow: payload rejected: LEN=13: Requested 13 bytes but only 8 available in buffer \
+         (bytes 0..8 of 8: F0 7F 7F 06 44 06 01 21)"
+    );
+
+    // Reading the message of the code leaves the detail in place
+    vlc_rtpmidi_get_error_message(VlcRtpmidiErrorCode::TruncatedPayload as c_int);
+    assert!(last_error_detail().starts_with("slave flow: "));
+
+    assert_eq!(slave_flow(&[0x02, 0xF1, 0x25]), 0);
+    assert_eq!(last_error_detail(), "");
+
+    // Failures with no more precise reason fall back to the code's message
+    let code =
+        unsafe { vlc_rtpmidi_slave_netsync_flow_ffi([0x01].as_ptr(), 1, std::ptr::null_mut()) };
+    assert_eq!(code, VlcRtpmidiErrorCode::NullPointer as c_int);
+    assert_eq!(last_error_detail(), "Null pointer passed");
+
+    let mut config = vlc_rtpmidi_default_netsync_config();
+    config.frame_rate = 9;
+    assert!(unsafe { vlc_rtpmidi_slave_session_new(&config) }.is_null());
+    assert_eq!(
+        last_error_detail(),
+        "config rejected: frame_rate 9 is out of range"
+    );
+    let session = unsafe { vlc_rtpmidi_slave_session_new(&vlc_rtpmidi_default_netsync_config()) };
+    assert_eq!(last_error_detail(), "");
+    unsafe { vlc_rtpmidi_slave_session_free(session) };
+
+    let mut buffer = [0u8; 4];
+    let mut actual_size = 0;
+    let play = vlc_rtpmidi_create_mmc_play_event();
+    let code = unsafe {
+        vlc_rtpmidi_master_netsync_flow_ffi(
+            &play,
+            buffer.as_mut_ptr(),
+            buffer.len(),
+            &mut actual_size,
+        )
+    };
+    assert_eq!(code, VlcRtpmidiErrorCode::BufferTooSmall as c_int);
+    assert_eq!(
+        last_error_detail(),
+        "payload of 7 bytes does not fit in a buffer of 4"
+    );
+}
+
+#[test]
+fn test_last_error_detail_is_per_thread() {
+    let _guard = LOG_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let start = Barrier::new(2);
+    thread::scope(|scope| {
+        let truncated = scope.spawn(|| {
+            slave_flow(&[0x05, 0xF1]);
+            start.wait();
+            start.wait();
+            last_error_detail()
+        });
+        let null = scope.spawn(|| {
+            start.wait();
+            unsafe {
+                vlc_rtpmidi_slave_netsync_flow_ffi(std::ptr::null(), 1, std::ptr::null_mut())
+            };
+            start.wait();
+            last_error_detail()
+        });
+        assert!(truncated
+            .join()
+            .unwrap()
+            .starts_with("slave flow: payload rejected: LEN=5: "));
+        assert_eq!(null.join().unwrap(), "Null pointer passed");
+    });
+}
@@ -0,0 +1,3726 @@
+use std::ffi::CStr;
+use std::mem::size_of;
+
+use rtp_midi_netsync::error::{AppleMidiError, MtcError, NetsyncError, ParseError};
+use rtp_midi_netsync::ffi::{
+    vlc_rtpmidi_abi_version, vlc_rtpmidi_build_midi_ffi, vlc_rtpmidi_config_layout_check,
+    vlc_rtpmidi_create_clock_event, vlc_rtpmidi_create_continue_event,
+    vlc_rtpmidi_create_mmc_deferred_play_event, vlc_rtpmidi_create_mmc_eject_event,
+    vlc_rtpmidi_create_mmc_fast_forward_event, vlc_rtpmidi_create_mmc_locate_event,
+    vlc_rtpmidi_create_mmc_locate_event_sf, vlc_rtpmidi_create_mmc_pause_event,
+    vlc_rtpmidi_create_mmc_play_event, vlc_rtpmidi_create_mmc_record_exit_event,
+    vlc_rtpmidi_create_mmc_record_strobe_event, vlc_rtpmidi_create_mmc_reset_event,
+    vlc_rtpmidi_create_mmc_rewind_event, vlc_rtpmidi_create_mmc_stop_event,
+    vlc_rtpmidi_create_mmc_variable_play_event, vlc_rtpmidi_create_mtc_full_event,
+    vlc_rtpmidi_create_mtc_quarter_event, vlc_rtpmidi_create_mtc_quarter_sequence,
+    vlc_rtpmidi_create_song_position_event, vlc_rtpmidi_create_start_event,
+    vlc_rtpmidi_create_stop_rt_event, vlc_rtpmidi_default_netsync_config,
+    vlc_rtpmidi_describe_payload_ffi, vlc_rtpmidi_event_get_data, vlc_rtpmidi_event_get_type,
+    vlc_rtpmidi_event_layout_check, vlc_rtpmidi_event_set_mmc_command,
+    vlc_rtpmidi_event_set_mmc_locate, vlc_rtpmidi_event_set_mtc_full,
+    vlc_rtpmidi_event_set_mtc_quarter, vlc_rtpmidi_get_error_message,
+    vlc_rtpmidi_get_event_type_name, vlc_rtpmidi_get_last_error_detail,
+    vlc_rtpmidi_get_max_packet_size, vlc_rtpmidi_get_max_payload_size,
+    vlc_rtpmidi_get_payload_size_for_event, vlc_rtpmidi_master_netsync_flow_batch_ffi,
+    vlc_rtpmidi_master_netsync_flow_dev_ffi, vlc_rtpmidi_master_netsync_flow_ffi,
+    vlc_rtpmidi_master_netsync_flow_multi_ffi, vlc_rtpmidi_master_session_free,
+    vlc_rtpmidi_master_session_metrics, vlc_rtpmidi_master_session_new,
+    vlc_rtpmidi_master_session_packet_for_event, vlc_rtpmidi_master_session_reset_metrics,
+    vlc_rtpmidi_master_session_tick, vlc_rtpmidi_metrics_layout_check,
+    vlc_rtpmidi_mtc_assembler_feed, vlc_rtpmidi_mtc_assembler_frame_rate,
+    vlc_rtpmidi_mtc_assembler_free, vlc_rtpmidi_mtc_assembler_new, vlc_rtpmidi_mtc_assembler_reset,
+    vlc_rtpmidi_netsync_config_default, vlc_rtpmidi_netsync_config_validate,
+    vlc_rtpmidi_parse_midi_ffi, vlc_rtpmidi_quarter_frame_payloads_ffi,
+    vlc_rtpmidi_quarter_frames_to_smpte_ffi, vlc_rtpmidi_quarter_frames_to_smpte_rate_ffi,
+    vlc_rtpmidi_slave_netsync_flow_dev_ffi, vlc_rtpmidi_slave_netsync_flow_ffi,
+    vlc_rtpmidi_slave_netsync_flow_raw_ffi, vlc_rtpmidi_slave_session_feed,
+    vlc_rtpmidi_slave_session_free, vlc_rtpmidi_slave_session_metrics,
+    vlc_rtpmidi_slave_session_new, vlc_rtpmidi_slave_session_poll_event,
+    vlc_rtpmidi_slave_session_reset_metrics, vlc_rtpmidi_smpte_from_string_ffi,
+    vlc_rtpmidi_smpte_to_quarter_frames_ffi, vlc_rtpmidi_smpte_to_quarter_frames_rate_ffi,
+    vlc_rtpmidi_smpte_to_string_ffi, vlc_rtpmidi_smpte_to_us_ffi, vlc_rtpmidi_smpte_to_us_rate_ffi,
+    vlc_rtpmidi_stream_parser_feed, vlc_rtpmidi_stream_parser_free, vlc_rtpmidi_stream_parser_new,
+    vlc_rtpmidi_stream_parser_poll, vlc_rtpmidi_us_to_smpte_ffi, vlc_rtpmidi_us_to_smpte_rate_ffi,
+    vlc_rtpmidi_validate_event_ffi, vlc_rtpmidi_validate_payload_ffi, vlc_rtpmidi_version_major,
+    vlc_rtpmidi_version_minor, vlc_rtpmidi_version_patch, vlc_rtpmidi_version_string,
+    VlcRtpmidiErrorCode, VlcRtpmidiEvent, VlcRtpmidiEventType, VlcRtpmidiFrameRate,
+    VlcRtpmidiMasterSession, VlcRtpmidiMtcAssembler, VlcRtpmidiNetsyncConfig,
+    VlcRtpmidiNetsyncMetrics, VlcRtpmidiSlaveSession, VlcRtpmidiSmpteTime, VlcRtpmidiStreamParser,
+    VLC_RTPMIDI_ABI_VERSION, VLC_RTPMIDI_SMPTE_STRING_SIZE,
+};
+use rtp_midi_netsync::midi::{MidiEvent, MmcCommand, MmcSpeed, RealtimeMessage};
+use rtp_midi_netsync::mtc::{
+    smpte_to_quarter_frames, smpte_to_quarter_frames_rate, smpte_to_us, smpte_to_us_rate,
+    us_to_smpte, us_to_smpte_rate, FrameRate, MtcFullFrame,
+};
+use rtp_midi_netsync::netsync::{
+    describe_payload, master_netsync_flow, slave_netsync_flow, slave_netsync_flow_detailed,
+    slave_netsync_flow_stream, slave_netsync_flow_with_policy, MasterSession, NetsyncConfig,
+    ParsePolicy, MAX_TICK_PACKETS,
+};
+use rtp_midi_netsync::rtp::{RtpHeader, RTP_HEADER_LENGTH};
+use rtp_midi_netsync::Error;
+
+/// Calls the multi-event master flow, returning the code, payload and failed index
+fn master_multi(events: &[VlcRtpmidiEvent], buffer_size: usize) -> (i32, Vec<u8>, usize) {
+    let mut buffer = vec![0u8; buffer_size];
+    let mut actual_size = usize::MAX;
+    let mut failed_index = usize::MAX;
+    let result = unsafe {
+        vlc_rtpmidi_master_netsync_flow_multi_ffi(
+            events.as_ptr(),
+            events.len(),
+            buffer.as_mut_ptr(),
+            buffer.len(),
+            &mut actual_size,
+            &mut failed_index,
+        )
+    };
+    buffer.truncate(actual_size);
+    (result, buffer, failed_index)
+}
+
+// === Multi-Event Master Flow Tests ===
+
+#[test]
+fn test_master_multi_ffi_single_event_matches_master_flow() {
+    let (result, payload, _) = master_multi(&[vlc_rtpmidi_create_mtc_quarter_event(2, 7)], 16);
+    assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+    assert_eq!(
+        payload,
+        master_netsync_flow(&MidiEvent::MtcQuarter {
+            msg_type: 2,
+            value: 7
+        })
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_master_multi_ffi_uses_long_header() {
+    let events = [
+        vlc_rtpmidi_create_mmc_locate_event(0, 1, 2, 3),
+        vlc_rtpmidi_create_mmc_play_event(),
+    ];
+    let (result, payload, _) = master_multi(&events, 64);
+    assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+
+    // 13-byte Locate and 6-byte Play: 19 bytes need the two-octet header
+    assert_eq!(&payload[..2], &[0x80, 19]);
+    assert_eq!(payload.len(), 2 + 19);
+    assert_eq!(
+        slave_netsync_flow_stream(&payload),
+        Ok((
+            Some(MidiEvent::Mmc(MmcCommand::Locate {
+                hour: 0,
+                minute: 1,
+                second: 2,
+                frame: 3,
+                subframe: 0
+            })),
+            payload.len()
+        ))
+    );
+}
+
+#[test]
+fn test_master_multi_ffi_null_pointers() {
+    let events = [vlc_rtpmidi_create_mmc_play_event()];
+    let mut buffer = [0u8; 16];
+    let mut actual_size = 0;
+    let mut failed_index = 0;
+    let null_pointer = VlcRtpmidiErrorCode::NullPointer as i32;
+    unsafe {
+        assert_eq!(
+            vlc_rtpmidi_master_netsync_flow_multi_ffi(
+                std::ptr::null(),
+                1,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &mut actual_size,
+                &mut failed_index,
+            ),
+            null_pointer
+        );
+        assert_eq!(
+            vlc_rtpmidi_master_netsync_flow_multi_ffi(
+                events.as_ptr(),
+                1,
+                std::ptr::null_mut(),
+                buffer.len(),
+                &mut actual_size,
+                &mut failed_index,
+            ),
+            null_pointer
+        );
+        assert_eq!(
+            vlc_rtpmidi_master_netsync_flow_multi_ffi(
+                events.as_ptr(),
+                1,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                std::ptr::null_mut(),
+                &mut failed_index,
+            ),
+            null_pointer
+        );
+        assert_eq!(
+            vlc_rtpmidi_master_netsync_flow_multi_ffi(
+                events.as_ptr(),
+                1,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &mut actual_size,
+                std::ptr::null_mut(),
+            ),
+            null_pointer
+        );
+    }
+}
+
+#[test]
+fn test_master_multi_ffi_zero_count() {
+    let (result, payload, failed_index) = master_multi(&[], 16);
+    assert_eq!(result, VlcRtpmidiErrorCode::InvalidMasterEvent as i32);
+    assert!(payload.is_empty());
+    // No single event is at fault
+    assert_eq!(failed_index, 0);
+}
+
+#[test]
+fn test_master_multi_ffi_reports_invalid_event_index() {
+    let mut bad = vlc_rtpmidi_create_mmc_play_event();
+    bad.data_len = 3;
+    let events = [
+        vlc_rtpmidi_create_mmc_play_event(),
+        vlc_rtpmidi_create_mtc_quarter_event(0, 1),
+        bad,
+        vlc_rtpmidi_create_mmc_play_event(),
+    ];
+    let (result, payload, failed_index) = master_multi(&events, 64);
+    assert_eq!(result, VlcRtpmidiErrorCode::InvalidEventType as i32);
+    assert!(payload.is_empty());
+    assert_eq!(failed_index, 2);
+}
+
+#[test]
+fn test_master_multi_ffi_oversized() {
+    // 315 Locates of 13 bytes fill the 12-bit LEN exactly
+    let locate = vlc_rtpmidi_create_mmc_locate_event(0, 0, 0, 0);
+    let (result, payload, _) = master_multi(&vec![locate.clone(); 315], 4097);
+    assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+    assert_eq!(&payload[..2], &[0x8F, 0xFF]);
+
+    let (result, _, failed_index) = master_multi(&vec![locate.clone(); 316], 8192);
+    assert_eq!(result, VlcRtpmidiErrorCode::InvalidMasterEvent as i32);
+    assert_eq!(failed_index, 315);
+
+    // Valid events but a destination buffer one byte short
+    let (result, payload, failed_index) = master_multi(&vec![locate.clone(); 315], 4096);
+    assert_eq!(result, VlcRtpmidiErrorCode::BufferTooSmall as i32);
+    assert!(payload.is_empty());
+    assert_eq!(failed_index, 315);
+}
+
+/// Calls the batch master flow, returning the code, the buffer and the offsets
+fn master_batch(events: &[VlcRtpmidiEvent], buffer_size: usize) -> (i32, Vec<u8>, Vec<usize>) {
+    let mut buffer = vec![0xAAu8; buffer_size];
+    let mut offsets = vec![usize::MAX; events.len()];
+    let mut actual_total = usize::MAX;
+    let result = unsafe {
+        vlc_rtpmidi_master_netsync_flow_batch_ffi(
+            events.as_ptr(),
+            events.len(),
+            buffer.as_mut_ptr(),
+            buffer.len(),
+            offsets.as_mut_ptr(),
+            &mut actual_total,
+        )
+    };
+    buffer.truncate(actual_total);
+    (result, buffer, offsets)
+}
+
+// === Batch Master Flow Tests ===
+
+#[test]
+fn test_master_batch_ffi_packs_quarter_frame_cycle() {
+    let events: Vec<_> = (0..8)
+        .map(|piece| vlc_rtpmidi_create_mtc_quarter_event(piece, piece + 1))
+        .collect();
+    let (result, buffer, offsets) = master_batch(&events, 64);
+    assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+    assert_eq!(buffer.len(), 24);
+    assert_eq!(offsets, [0, 3, 6, 9, 12, 15, 18, 21]);
+    for (piece, &offset) in offsets.iter().enumerate() {
+        let expected = master_netsync_flow(&MidiEvent::MtcQuarter {
+            msg_type: piece as u8,
+            value: piece as u8 + 1,
+        })
+        .unwrap();
+        assert_eq!(&buffer[offset..offset + 3], expected.as_slice());
+    }
+}
+
+#[test]
+fn test_master_batch_ffi_mixed_sizes() {
+    let events = [
+        vlc_rtpmidi_create_mmc_locate_event(1, 2, 3, 4),
+        vlc_rtpmidi_create_mmc_play_event(),
+        vlc_rtpmidi_create_mtc_full_event(1, 2, 3, 4),
+    ];
+    let (result, buffer, offsets) = master_batch(&events, 64);
+    assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+    let ends = [offsets[1], offsets[2], buffer.len()];
+    for ((event, &start), &end) in events.iter().zip(&offsets).zip(&ends) {
+        let mut single = [0u8; 16];
+        let mut single_len = 0;
+        let code = unsafe {
+            vlc_rtpmidi_master_netsync_flow_ffi(
+                event,
+                single.as_mut_ptr(),
+                single.len(),
+                &mut single_len,
+            )
+        };
+        assert_eq!(code, VlcRtpmidiErrorCode::Success as i32);
+        assert_eq!(&buffer[start..end], &single[..single_len]);
+    }
+}
+
+#[test]
+fn test_master_batch_ffi_long_batch() {
+    // More events than the batch keeps converted between its two passes
+    let events: Vec<_> = (0..40)
+        .map(|index| vlc_rtpmidi_create_mtc_quarter_event(index % 8, index % 16))
+        .collect();
+    let (result, buffer, offsets) = master_batch(&events, 256);
+    assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+    assert_eq!(buffer.len(), 120);
+    for (index, &offset) in offsets.iter().enumerate() {
+        assert_eq!(offset, index * 3);
+        let expected = master_netsync_flow(&MidiEvent::MtcQuarter {
+            msg_type: index as u8 % 8,
+            value: index as u8 % 16,
+        })
+        .unwrap();
+        assert_eq!(&buffer[offset..offset + 3], expected.as_slice());
+    }
+}
+
+#[test]
+fn test_master_batch_ffi_is_atomic() {
+    let mut bad = vlc_rtpmidi_create_mmc_play_event();
+    bad.data_len = 3;
+    let events = [
+        vlc_rtpmidi_create_mmc_play_event(),
+        vlc_rtpmidi_create_mtc_quarter_event(0, 1),
+        bad,
+    ];
+    let mut buffer = [0xAAu8; 64];
+    let mut offsets = [usize::MAX; 3];
+    let mut actual_total = usize::MAX;
+    let result = unsafe {
+        vlc_rtpmidi_master_netsync_flow_batch_ffi(
+            events.as_ptr(),
+            events.len(),
+            buffer.as_mut_ptr(),
+            buffer.len(),
+            offsets.as_mut_ptr(),
+            &mut actual_total,
+        )
+    };
+    assert_eq!(result, VlcRtpmidiErrorCode::InvalidEventType as i32);
+    assert_eq!(actual_total, 0);
+    assert!(buffer.iter().all(|&b| b == 0xAA));
+    assert_eq!(offsets, [usize::MAX; 3]);
+
+    // Nine bytes are needed: the first two payloads fit, the third does not
+    let events = vec![vlc_rtpmidi_create_mtc_quarter_event(0, 1); 3];
+    let mut buffer = [0xAAu8; 8];
+    let result = unsafe {
+        vlc_rtpmidi_master_netsync_flow_batch_ffi(
+            events.as_ptr(),
+            events.len(),
+            buffer.as_mut_ptr(),
+            buffer.len(),
+            offsets.as_mut_ptr(),
+            &mut actual_total,
+        )
+    };
+    assert_eq!(result, VlcRtpmidiErrorCode::BufferTooSmall as i32);
+    assert_eq!(actual_total, 0);
+    assert!(buffer.iter().all(|&b| b == 0xAA));
+    assert_eq!(offsets, [usize::MAX; 3]);
+}
+
+#[test]
+fn test_master_batch_ffi_empty_and_null() {
+    let mut actual_total = usize::MAX;
+    let result = unsafe {
+        vlc_rtpmidi_master_netsync_flow_batch_ffi(
+            std::ptr::null(),
+            0,
+            std::ptr::null_mut(),
+            0,
+            std::ptr::null_mut(),
+            &mut actual_total,
+        )
+    };
+    assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+    assert_eq!(actual_total, 0);
+
+    let events = [vlc_rtpmidi_create_mmc_play_event()];
+    let mut buffer = [0u8; 16];
+    let mut offsets = [0usize; 1];
+    let null_pointer = VlcRtpmidiErrorCode::NullPointer as i32;
+    unsafe {
+        assert_eq!(
+            vlc_rtpmidi_master_netsync_flow_batch_ffi(
+                std::ptr::null(),
+                1,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                offsets.as_mut_ptr(),
+                &mut actual_total,
+            ),
+            null_pointer
+        );
+        assert_eq!(
+            vlc_rtpmidi_master_netsync_flow_batch_ffi(
+                events.as_ptr(),
+                1,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                std::ptr::null_mut(),
+                &mut actual_total,
+            ),
+            null_pointer
+        );
+        assert_eq!(
+            vlc_rtpmidi_master_netsync_flow_batch_ffi(
+                events.as_ptr(),
+                1,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                offsets.as_mut_ptr(),
+                std::ptr::null_mut(),
+            ),
+            null_pointer
+        );
+        // A missing buffer only passes the pointer checks when it is empty
+        assert_eq!(
+            vlc_rtpmidi_master_netsync_flow_batch_ffi(
+                events.as_ptr(),
+                1,
+                std::ptr::null_mut(),
+                0,
+                offsets.as_mut_ptr(),
+                &mut actual_total,
+            ),
+            VlcRtpmidiErrorCode::BufferTooSmall as i32
+        );
+    }
+}
+
+/// Calls the quarter-frame payload builder, returning the code, the buffer and the offsets
+fn quarter_frame_payloads(
+    tc: &VlcRtpmidiSmpteTime,
+    buffer_size: usize,
+) -> (i32, Vec<u8>, [usize; 8]) {
+    let mut buffer = vec![0xAAu8; buffer_size];
+    let mut offsets = [usize::MAX; 8];
+    let mut actual_total = usize::MAX;
+    let result = unsafe {
+        vlc_rtpmidi_quarter_frame_payloads_ffi(
+            tc,
+            buffer.as_mut_ptr(),
+            buffer.len(),
+            offsets.as_mut_ptr(),
+            &mut actual_total,
+        )
+    };
+    buffer.truncate(actual_total);
+    (result, buffer, offsets)
+}
+
+#[test]
+fn test_quarter_frame_payloads_ffi_round_trip() {
+    let tc = VlcRtpmidiSmpteTime {
+        hours: 23,
+        minutes: 59,
+        seconds: 58,
+        frames: 17,
+    };
+    let (result, buffer, offsets) = quarter_frame_payloads(&tc, 64);
+    assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+    assert_eq!(buffer.len(), 24);
+    assert_eq!(offsets, [0, 3, 6, 9, 12, 15, 18, 21]);
+
+    // Same bytes as splitting the timecode and batching the pieces
+    let mut pieces = vec![vlc_rtpmidi_create_mmc_play_event(); 8];
+    let code = unsafe { vlc_rtpmidi_smpte_to_quarter_frames_ffi(&tc, pieces.as_mut_ptr()) };
+    assert_eq!(code, VlcRtpmidiErrorCode::Success as i32);
+    let (code, batch, _) = master_batch(&pieces, 64);
+    assert_eq!(code, VlcRtpmidiErrorCode::Success as i32);
+    assert_eq!(buffer, batch);
+
+    // A slave parsing each payload reassembles the exact timecode
+    let mut received = vec![vlc_rtpmidi_create_mmc_play_event(); 8];
+    for (event, &offset) in received.iter_mut().zip(&offsets) {
+        let code =
+            unsafe { vlc_rtpmidi_slave_netsync_flow_ffi(buffer[offset..].as_ptr(), 3, event) };
+        assert_eq!(code, VlcRtpmidiErrorCode::Success as i32);
+    }
+    let mut out = VlcRtpmidiSmpteTime::default();
+    let code = unsafe { vlc_rtpmidi_quarter_frames_to_smpte_ffi(received.as_ptr(), &mut out) };
+    assert_eq!(code, VlcRtpmidiErrorCode::Success as i32);
+    assert_eq!(out, tc);
+}
+
+#[test]
+fn test_quarter_frame_payloads_ffi_errors() {
+    let tc = VlcRtpmidiSmpteTime {
+        hours: 1,
+        minutes: 2,
+        seconds: 3,
+        frames: 30,
+    };
+    let (result, buffer, offsets) = quarter_frame_payloads(&tc, 64);
+    assert_eq!(result, VlcRtpmidiErrorCode::InvalidTimecode as i32);
+    assert!(buffer.is_empty());
+    assert_eq!(offsets, [usize::MAX; 8]);
+
+    // One byte short of the eight payloads: nothing is written
+    let tc = VlcRtpmidiSmpteTime { frames: 4, ..tc };
+    let mut buffer = [0xAAu8; 23];
+    let mut offsets = [usize::MAX; 8];
+    let mut actual_total = usize::MAX;
+    let result = unsafe {
+        vlc_rtpmidi_quarter_frame_payloads_ffi(
+            &tc,
+            buffer.as_mut_ptr(),
+            buffer.len(),
+            offsets.as_mut_ptr(),
+            &mut actual_total,
+        )
+    };
+    assert_eq!(result, VlcRtpmidiErrorCode::BufferTooSmall as i32);
+    assert_eq!(actual_total, 0);
+    assert!(buffer.iter().all(|&b| b == 0xAA));
+    assert_eq!(offsets, [usize::MAX; 8]);
+
+    let result = unsafe {
+        vlc_rtpmidi_quarter_frame_payloads_ffi(
+            std::ptr::null(),
+            buffer.as_mut_ptr(),
+            buffer.len(),
+            offsets.as_mut_ptr(),
+            &mut actual_total,
+        )
+    };
+    assert_eq!(result, VlcRtpmidiErrorCode::NullPointer as i32);
+}
+
+#[test]
+fn test_master_multi_ffi_short_header() {
+    // Two short events fit the one-octet header
+    let events = [
+        vlc_rtpmidi_create_mtc_quarter_event(0, 1),
+        vlc_rtpmidi_create_mtc_quarter_event(1, 2),
+    ];
+    let (result, payload, _) = master_multi(&events, 16);
+    assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+    assert_eq!(payload, [0x04, 0xF1, 0x01, 0xF1, 0x12]);
+}
+
+// === Raw Slave Flow Tests ===
+
+/// Calls the raw slave flow, returning the code, MIDI bytes, length and type
+fn slave_raw(payload: &[u8], out_cap: usize) -> (i32, Vec<u8>, usize, VlcRtpmidiEventType) {
+    let mut out = vec![0u8; out_cap];
+    let mut out_len = usize::MAX;
+    let mut event_type = VlcRtpmidiEventType::MtcQuarter;
+    let result = unsafe {
+        vlc_rtpmidi_slave_netsync_flow_raw_ffi(
+            payload.as_ptr(),
+            payload.len(),
+            out.as_mut_ptr(),
+            out.len(),
+            &mut out_len,
+            &mut event_type,
+        )
+    };
+    out.truncate(out_len.min(out_cap));
+    (result, out, out_len, event_type)
+}
+
+#[test]
+fn test_slave_raw_ffi_passes_other_messages_through() {
+    let sysex = [0xF0, 0x7D, 0x01, 0x02, 0xF7];
+    let mut payload = vec![sysex.len() as u8];
+    payload.extend_from_slice(&sysex);
+    let (result, midi, len, event_type) = slave_raw(&payload, 16);
+    assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+    assert_eq!(midi, sysex);
+    assert_eq!(len, sysex.len());
+    assert_eq!(event_type, VlcRtpmidiEventType::Raw);
+
+    // Channel messages have no event structure either
+    let (result, midi, _, event_type) = slave_raw(&[0x03, 0x90, 0x40, 0x7F], 16);
+    assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+    assert_eq!(midi, [0x90, 0x40, 0x7F]);
+    assert_eq!(event_type, VlcRtpmidiEventType::Raw);
+
+    // Beat clock is reported with its own type
+    let (result, midi, _, event_type) = slave_raw(&[0x01, 0xF8], 16);
+    assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+    assert_eq!(midi, [0xF8]);
+    assert_eq!(event_type, VlcRtpmidiEventType::Clock);
+
+    // Supported events keep their type
+    let payload = master_netsync_flow(&MidiEvent::Mmc(MmcCommand::Play)).unwrap();
+    let (result, midi, _, event_type) = slave_raw(&payload, 16);
+    assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+    assert_eq!(midi, payload[1..]);
+    assert_eq!(event_type, VlcRtpmidiEventType::MmcPlay);
+}
+
+#[test]
+fn test_slave_raw_ffi_reports_required_size() {
+    let payload = [0x05, 0xF0, 0x7D, 0x01, 0x02, 0xF7];
+    let (result, _, len, event_type) = slave_raw(&payload, 4);
+    assert_eq!(result, VlcRtpmidiErrorCode::BufferTooSmall as i32);
+    assert_eq!(len, 5);
+    assert_eq!(event_type, VlcRtpmidiEventType::Raw);
+
+    // Size query without a buffer
+    let mut out_len = 0;
+    let mut event_type = VlcRtpmidiEventType::MtcQuarter;
+    let result = unsafe {
+        vlc_rtpmidi_slave_netsync_flow_raw_ffi(
+            payload.as_ptr(),
+            payload.len(),
+            std::ptr::null_mut(),
+            0,
+            &mut out_len,
+            &mut event_type,
+        )
+    };
+    assert_eq!(result, VlcRtpmidiErrorCode::BufferTooSmall as i32);
+    assert_eq!(out_len, 5);
+}
+
+#[test]
+fn test_slave_raw_ffi_errors() {
+    let (result, _, len, _) = slave_raw(&[0x00], 16);
+    assert_eq!(result, VlcRtpmidiErrorCode::NoEvent as i32);
+    assert_eq!(len, 0);
+    let (result, _, len, _) = slave_raw(&[0x03, 0xF0], 16);
+    assert_eq!(result, VlcRtpmidiErrorCode::TruncatedPayload as i32);
+    assert_eq!(len, 0);
+
+    let payload = [0x01, 0xF8];
+    let mut out = [0u8; 4];
+    let mut out_len = 0;
+    let mut event_type = VlcRtpmidiEventType::MtcQuarter;
+    unsafe {
+        assert_eq!(
+            vlc_rtpmidi_slave_netsync_flow_raw_ffi(
+                std::ptr::null(),
+                2,
+                out.as_mut_ptr(),
+                4,
+                &mut out_len,
+                &mut event_type
+            ),
+            VlcRtpmidiErrorCode::NullPointer as i32
+        );
+        assert_eq!(
+            vlc_rtpmidi_slave_netsync_flow_raw_ffi(
+                payload.as_ptr(),
+                2,
+                std::ptr::null_mut(),
+                4,
+                &mut out_len,
+                &mut event_type
+            ),
+            VlcRtpmidiErrorCode::NullPointer as i32
+        );
+        assert_eq!(
+            vlc_rtpmidi_slave_netsync_flow_raw_ffi(
+                payload.as_ptr(),
+                2,
+                out.as_mut_ptr(),
+                4,
+                std::ptr::null_mut(),
+                &mut event_type
+            ),
+            VlcRtpmidiErrorCode::NullPointer as i32
+        );
+        assert_eq!(
+            vlc_rtpmidi_slave_netsync_flow_raw_ffi(
+                payload.as_ptr(),
+                2,
+                out.as_mut_ptr(),
+                4,
+                &mut out_len,
+                std::ptr::null_mut()
+            ),
+            VlcRtpmidiErrorCode::NullPointer as i32
+        );
+    }
+}
+
+// === Payload Size Tests ===
+
+#[test]
+fn test_payload_size_for_event_matches_master_flow() {
+    let events = [
+        vlc_rtpmidi_create_mmc_play_event(),
+        vlc_rtpmidi_create_mmc_stop_event(),
+        vlc_rtpmidi_create_mmc_locate_event(1, 2, 3, 4),
+        vlc_rtpmidi_create_mtc_quarter_event(3, 9),
+        vlc_rtpmidi_create_mtc_full_event(1, 2, 3, 4),
+    ];
+    let mut longest = 0;
+    for event in &events {
+        let mut size = usize::MAX;
+        let result = unsafe { vlc_rtpmidi_get_payload_size_for_event(event, &mut size) };
+        assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+
+        // A buffer of exactly that size is enough
+        let mut buffer = vec![0u8; size];
+        let mut actual_size = 0;
+        let result = unsafe {
+            vlc_rtpmidi_master_netsync_flow_ffi(event, buffer.as_mut_ptr(), size, &mut actual_size)
+        };
+        assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+        assert_eq!(actual_size, size);
+        longest = longest.max(size);
+    }
+    assert_eq!(longest, vlc_rtpmidi_get_max_payload_size());
+}
+
+#[test]
+fn test_payload_size_for_event_errors() {
+    let mut bad = vlc_rtpmidi_create_mmc_play_event();
+    bad.data_len = 3;
+    let mut size = usize::MAX;
+    unsafe {
+        assert_eq!(
+            vlc_rtpmidi_get_payload_size_for_event(&bad, &mut size),
+            VlcRtpmidiErrorCode::InvalidEventType as i32
+        );
+        assert_eq!(size, 0);
+        assert_eq!(
+            vlc_rtpmidi_get_payload_size_for_event(std::ptr::null(), &mut size),
+            VlcRtpmidiErrorCode::NullPointer as i32
+        );
+        assert_eq!(
+            vlc_rtpmidi_get_payload_size_for_event(&bad, std::ptr::null_mut()),
+            VlcRtpmidiErrorCode::NullPointer as i32
+        );
+    }
+}
+
+// === Locate Subframe Tests ===
+
+#[test]
+fn test_create_mmc_locate_event_sf() {
+    let event = vlc_rtpmidi_create_mmc_locate_event_sf(1, 2, 3, 4, 42);
+    assert_eq!(event.event_type, VlcRtpmidiEventType::MmcLocate as u32);
+    assert_eq!(event.data_len, 5);
+    assert_eq!(event.data[..5], [1, 2, 3, 4, 42]);
+
+    // Clamped to the largest valid subframe
+    let event = vlc_rtpmidi_create_mmc_locate_event_sf(1, 2, 3, 4, 150);
+    assert_eq!(event.data[4], 99);
+
+    // The 4-argument creator keeps its layout
+    let event = vlc_rtpmidi_create_mmc_locate_event(1, 2, 3, 4);
+    assert_eq!(event.data_len, 4);
+    assert_eq!(event.data, [1, 2, 3, 4, 0, 0, 0, 0]);
+}
+
+#[test]
+fn test_locate_subframe_validation() {
+    let mut size = 0;
+    let event = vlc_rtpmidi_create_mmc_locate_event_sf(0, 0, 1, 0, 99);
+    let result = unsafe { vlc_rtpmidi_get_payload_size_for_event(&event, &mut size) };
+    assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+    assert_eq!(size, 14);
+
+    // Hand-built events with an out-of-range subframe are rejected
+    let mut event = vlc_rtpmidi_create_mmc_locate_event_sf(0, 0, 1, 0, 0);
+    event.data[4] = 100;
+    let result = unsafe { vlc_rtpmidi_get_payload_size_for_event(&event, &mut size) };
+    assert_eq!(result, VlcRtpmidiErrorCode::InvalidEventType as i32);
+    event.data_len = 6;
+    event.data[4] = 0;
+    let result = unsafe { vlc_rtpmidi_get_payload_size_for_event(&event, &mut size) };
+    assert_eq!(result, VlcRtpmidiErrorCode::InvalidEventType as i32);
+}
+
+// === Extended MMC Tests ===
+
+#[test]
+fn test_extended_mmc_creators_roundtrip() {
+    let creators: [(
+        extern "C" fn() -> VlcRtpmidiEvent,
+        VlcRtpmidiEventType,
+        MmcCommand,
+    ); 8] = [
+        (
+            vlc_rtpmidi_create_mmc_deferred_play_event,
+            VlcRtpmidiEventType::MmcDeferredPlay,
+            MmcCommand::DeferredPlay,
+        ),
+        (
+            vlc_rtpmidi_create_mmc_fast_forward_event,
+            VlcRtpmidiEventType::MmcFastForward,
+            MmcCommand::FastForward,
+        ),
+        (
+            vlc_rtpmidi_create_mmc_rewind_event,
+            VlcRtpmidiEventType::MmcRewind,
+            MmcCommand::Rewind,
+        ),
+        (
+            vlc_rtpmidi_create_mmc_record_strobe_event,
+            VlcRtpmidiEventType::MmcRecordStrobe,
+            MmcCommand::RecordStrobe,
+        ),
+        (
+            vlc_rtpmidi_create_mmc_record_exit_event,
+            VlcRtpmidiEventType::MmcRecordExit,
+            MmcCommand::RecordExit,
+        ),
+        (
+            vlc_rtpmidi_create_mmc_pause_event,
+            VlcRtpmidiEventType::MmcPause,
+            MmcCommand::Pause,
+        ),
+        (
+            vlc_rtpmidi_create_mmc_eject_event,
+            VlcRtpmidiEventType::MmcEject,
+            MmcCommand::Eject,
+        ),
+        (
+            vlc_rtpmidi_create_mmc_reset_event,
+            VlcRtpmidiEventType::MmcReset,
+            MmcCommand::Reset,
+        ),
+    ];
+
+    for (create, event_type, command) in creators {
+        let event = create();
+        assert_eq!(event.event_type, event_type as u32);
+        assert_eq!(event.data_len, 0);
+
+        let mut buffer = [0u8; 16];
+        let mut size = 0;
+        let result = unsafe {
+            vlc_rtpmidi_master_netsync_flow_ffi(
+                &event,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &mut size,
+            )
+        };
+        assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+        assert_eq!(
+            buffer[..size],
+            master_netsync_flow(&MidiEvent::Mmc(command)).unwrap()
+        );
+
+        let mut parsed = vlc_rtpmidi_create_mmc_play_event();
+        let result =
+            unsafe { vlc_rtpmidi_slave_netsync_flow_ffi(buffer.as_ptr(), size, &mut parsed) };
+        assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+        assert_same_event(&parsed, &event);
+
+        // Data is not allowed
+        let mut bad = event.clone();
+        bad.data_len = 1;
+        let result = unsafe {
+            vlc_rtpmidi_master_netsync_flow_ffi(&bad, buffer.as_mut_ptr(), buffer.len(), &mut size)
+        };
+        assert_eq!(result, VlcRtpmidiErrorCode::InvalidEventType as i32);
+    }
+}
+
+#[test]
+fn test_beat_clock_creators_roundtrip() {
+    let creators = [
+        (
+            vlc_rtpmidi_create_song_position_event(0x3FFF),
+            VlcRtpmidiEventType::SongPosition,
+            MidiEvent::SongPosition(0x3FFF),
+        ),
+        (
+            vlc_rtpmidi_create_song_position_event(200),
+            VlcRtpmidiEventType::SongPosition,
+            MidiEvent::SongPosition(200),
+        ),
+        (
+            vlc_rtpmidi_create_clock_event(),
+            VlcRtpmidiEventType::Clock,
+            MidiEvent::Realtime(RealtimeMessage::Clock),
+        ),
+        (
+            vlc_rtpmidi_create_start_event(),
+            VlcRtpmidiEventType::Start,
+            MidiEvent::Realtime(RealtimeMessage::Start),
+        ),
+        (
+            vlc_rtpmidi_create_continue_event(),
+            VlcRtpmidiEventType::Continue,
+            MidiEvent::Realtime(RealtimeMessage::Continue),
+        ),
+        (
+            vlc_rtpmidi_create_stop_rt_event(),
+            VlcRtpmidiEventType::RealtimeStop,
+            MidiEvent::Realtime(RealtimeMessage::Stop),
+        ),
+    ];
+
+    for (event, event_type, midi_event) in creators {
+        assert_eq!(event.event_type, event_type as u32);
+
+        let mut buffer = [0u8; 16];
+        let mut size = 0;
+        let result = unsafe {
+            vlc_rtpmidi_master_netsync_flow_ffi(
+                &event,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &mut size,
+            )
+        };
+        assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+        assert_eq!(buffer[..size], master_netsync_flow(&midi_event).unwrap());
+
+        let mut parsed = vlc_rtpmidi_create_mmc_play_event();
+        let result =
+            unsafe { vlc_rtpmidi_slave_netsync_flow_ffi(buffer.as_ptr(), size, &mut parsed) };
+        assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+        assert_same_event(&parsed, &event);
+
+        let mut kind = -1;
+        let result = unsafe { vlc_rtpmidi_validate_payload_ffi(buffer.as_ptr(), size, &mut kind) };
+        assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+        assert_eq!(kind, event_type as i32);
+    }
+
+    // Song position bytes are the little-endian beat count
+    let event = vlc_rtpmidi_create_song_position_event(0x1234);
+    assert_eq!(event.data_len, 2);
+    assert_eq!(event.data[..2], [0x34, 0x12]);
+}
+
+#[test]
+fn test_song_position_above_14_bits_is_rejected() {
+    let mut buffer = [0u8; 16];
+    let mut size = 0;
+    for beats in [0x4000, u16::MAX] {
+        let event = vlc_rtpmidi_create_song_position_event(beats);
+        let result = unsafe {
+            vlc_rtpmidi_master_netsync_flow_ffi(
+                &event,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &mut size,
+            )
+        };
+        assert_eq!(result, VlcRtpmidiErrorCode::InvalidEventType as i32);
+    }
+
+    // So is any other data length
+    let mut event = vlc_rtpmidi_create_song_position_event(1);
+    event.data_len = 3;
+    let result = unsafe {
+        vlc_rtpmidi_master_netsync_flow_ffi(&event, buffer.as_mut_ptr(), buffer.len(), &mut size)
+    };
+    assert_eq!(result, VlcRtpmidiErrorCode::InvalidEventType as i32);
+}
+
+#[test]
+fn test_variable_play_creator_roundtrip() {
+    // Play speed and half speed, as sh sm sl bytes
+    for (speed, bytes) in [(1.0, [0x01, 0x00, 0x00]), (0.5, [0x00, 0x40, 0x00])] {
+        let event = vlc_rtpmidi_create_mmc_variable_play_event(speed);
+        assert_eq!(
+            event.event_type,
+            VlcRtpmidiEventType::MmcVariablePlay as u32
+        );
+        assert_eq!(event.data_len, 3);
+        assert_eq!(event.data[..3], bytes);
+
+        let mut buffer = [0u8; 16];
+        let mut size = 0;
+        let result = unsafe {
+            vlc_rtpmidi_master_netsync_flow_ffi(
+                &event,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &mut size,
+            )
+        };
+        assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+        let [sh, sm, sl] = bytes;
+        assert_eq!(
+            buffer[..size],
+            [0x0A, 0xF0, 0x7F, 0x7F, 0x06, 0x45, 0x03, sh, sm, sl, 0xF7]
+        );
+
+        let mut parsed = vlc_rtpmidi_create_mmc_play_event();
+        let result =
+            unsafe { vlc_rtpmidi_slave_netsync_flow_ffi(buffer.as_ptr(), size, &mut parsed) };
+        assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+        assert_same_event(&parsed, &event);
+
+        let mut kind = -1;
+        let result = unsafe { vlc_rtpmidi_validate_payload_ffi(buffer.as_ptr(), size, &mut kind) };
+        assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+        assert_eq!(kind, VlcRtpmidiEventType::MmcVariablePlay as i32);
+    }
+
+    // Any other data length is rejected
+    let mut event = vlc_rtpmidi_create_mmc_variable_play_event(1.0);
+    event.data_len = 4;
+    let mut buffer = [0u8; 16];
+    let mut size = 0;
+    let result = unsafe {
+        vlc_rtpmidi_master_netsync_flow_ffi(&event, buffer.as_mut_ptr(), buffer.len(), &mut size)
+    };
+    assert_eq!(result, VlcRtpmidiErrorCode::InvalidEventType as i32);
+}
+
+#[test]
+fn test_slave_session_ffi_queues_extended_mmc() {
+    let handle = slave_session();
+    let mut master = MasterSession::new();
+    let packet = master
+        .packet_for_event(&MidiEvent::Mmc(MmcCommand::Pause), 0)
+        .unwrap();
+    assert_eq!(
+        slave_feed(handle, &packet, 0),
+        VlcRtpmidiErrorCode::Success as i32
+    );
+
+    let events = slave_poll_all(handle);
+    assert_eq!(events.len(), 1);
+    assert_same_event(&events[0], &vlc_rtpmidi_create_mmc_pause_event());
+
+    unsafe { vlc_rtpmidi_slave_session_free(handle) };
+}
+
+// === Timecode Conversion Tests ===
+
+#[test]
+fn test_us_to_smpte_ffi_matches_rust() {
+    // Every frame boundary of the first ten seconds, with the instants either side
+    // of it, then coarse steps up to past the 24-hour wrap
+    let frame_us = 1_000_000 / 30;
+    let fine = (0..300u64).flat_map(|n| {
+        let boundary = n * 1_000_000 / 30;
+        [
+            boundary.saturating_sub(1),
+            boundary,
+            boundary + 1,
+            boundary + frame_us / 2,
+        ]
+    });
+    let coarse = (0..1_000u64).map(|n| n * 90_000_017);
+    for us in fine.chain(coarse).chain([u64::MAX / 1_000_000]) {
+        let mut out = VlcRtpmidiSmpteTime::default();
+        let result = unsafe { vlc_rtpmidi_us_to_smpte_ffi(us, &mut out) };
+        assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+
+        let expected = us_to_smpte(us);
+        assert_eq!(
+            (out.hours, out.minutes, out.seconds, out.frames),
+            (
+                expected.hours,
+                expected.minutes,
+                expected.seconds,
+                expected.frames
+            ),
+            "{} us",
+            us
+        );
+    }
+}
+
+#[test]
+fn test_smpte_to_us_ffi_matches_rust() {
+    for hours in [0, 1, 12, 23] {
+        for minutes in (0..60).step_by(7) {
+            for seconds in (0..60).step_by(11) {
+                for frames in 0..30 {
+                    let input = VlcRtpmidiSmpteTime {
+                        hours,
+                        minutes,
+                        seconds,
+                        frames,
+                    };
+                    let mut out = 0;
+                    let result = unsafe { vlc_rtpmidi_smpte_to_us_ffi(&input, &mut out) };
+                    assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+                    assert_eq!(
+                        out,
+                        smpte_to_us(&MtcFullFrame {
+                            hours,
+                            minutes,
+                            seconds,
+                            frames
+                        })
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_smpte_to_us_ffi_validates_range() {
+    let valid = VlcRtpmidiSmpteTime {
+        hours: 23,
+        minutes: 59,
+        seconds: 59,
+        frames: 29,
+    };
+    for input in [
+        VlcRtpmidiSmpteTime { hours: 24, ..valid },
+        VlcRtpmidiSmpteTime {
+            minutes: 60,
+            ..valid
+        },
+        VlcRtpmidiSmpteTime {
+            seconds: 60,
+            ..valid
+        },
+        VlcRtpmidiSmpteTime {
+            frames: 30,
+            ..valid
+        },
+    ] {
+        let mut out = 42;
+        let result = unsafe { vlc_rtpmidi_smpte_to_us_ffi(&input, &mut out) };
+        assert_eq!(result, VlcRtpmidiErrorCode::InvalidTimecode as i32);
+        assert_eq!(out, 42);
+    }
+}
+
+#[test]
+fn test_timecode_ffi_null_pointers() {
+    let null_pointer = VlcRtpmidiErrorCode::NullPointer as i32;
+    let input = VlcRtpmidiSmpteTime::default();
+    let mut out = 0;
+    unsafe {
+        assert_eq!(
+            vlc_rtpmidi_us_to_smpte_ffi(0, std::ptr::null_mut()),
+            null_pointer
+        );
+        assert_eq!(
+            vlc_rtpmidi_smpte_to_us_ffi(std::ptr::null(), &mut out),
+            null_pointer
+        );
+        assert_eq!(
+            vlc_rtpmidi_smpte_to_us_ffi(&input, std::ptr::null_mut()),
+            null_pointer
+        );
+    }
+}
+
+// === Quarter-Frame Conversion Tests ===
+
+fn quarter_frames_ffi(input: &VlcRtpmidiSmpteTime) -> (i32, Vec<VlcRtpmidiEvent>) {
+    let mut events = vec![vlc_rtpmidi_create_mmc_play_event(); 8];
+    let result = unsafe { vlc_rtpmidi_smpte_to_quarter_frames_ffi(input, events.as_mut_ptr()) };
+    (result, events)
+}
+
+fn smpte_ffi(events: &[VlcRtpmidiEvent]) -> (i32, VlcRtpmidiSmpteTime) {
+    let mut out = VlcRtpmidiSmpteTime::default();
+    let result = unsafe { vlc_rtpmidi_quarter_frames_to_smpte_ffi(events.as_ptr(), &mut out) };
+    (result, out)
+}
+
+#[test]
+fn test_quarter_frames_ffi_matches_rust_and_roundtrips() {
+    for (hours, minutes, seconds, frames) in [(0, 0, 0, 0), (1, 23, 45, 10), (23, 59, 59, 29)] {
+        let input = VlcRtpmidiSmpteTime {
+            hours,
+            minutes,
+            seconds,
+            frames,
+        };
+        let (result, events) = quarter_frames_ffi(&input);
+        assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+
+        let expected = smpte_to_quarter_frames(&MtcFullFrame {
+            hours,
+            minutes,
+            seconds,
+            frames,
+        });
+        for (event, qf) in events.iter().zip(expected) {
+            assert_eq!(event.event_type, VlcRtpmidiEventType::MtcQuarter as u32);
+            assert_eq!(event.data_len, 2);
+            assert_eq!(&event.data[..2], &[qf.frame_type, qf.value]);
+        }
+
+        assert_eq!(
+            smpte_ffi(&events),
+            (VlcRtpmidiErrorCode::Success as i32, input)
+        );
+    }
+}
+
+#[test]
+fn test_smpte_to_quarter_frames_ffi_validates_range() {
+    let (result, events) = quarter_frames_ffi(&VlcRtpmidiSmpteTime {
+        hours: 0,
+        minutes: 0,
+        seconds: 0,
+        frames: 30,
+    });
+    assert_eq!(result, VlcRtpmidiErrorCode::InvalidTimecode as i32);
+    // Nothing written
+    assert!(events
+        .iter()
+        .all(|event| event.event_type == VlcRtpmidiEventType::MmcPlay as u32));
+}
+
+#[test]
+fn test_create_mtc_quarter_sequence_matches_rust() {
+    for (hours, minutes, seconds, frames) in [(0, 0, 0, 0), (1, 23, 45, 10), (23, 59, 59, 29)] {
+        let input = VlcRtpmidiSmpteTime {
+            hours,
+            minutes,
+            seconds,
+            frames,
+        };
+        let mut events = vec![vlc_rtpmidi_create_mmc_play_event(); 8];
+        let result =
+            unsafe { vlc_rtpmidi_create_mtc_quarter_sequence(&input, events.as_mut_ptr()) };
+        assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+
+        let expected = smpte_to_quarter_frames(&MtcFullFrame {
+            hours,
+            minutes,
+            seconds,
+            frames,
+        });
+        for (piece, (event, qf)) in events.iter().zip(expected).enumerate() {
+            // Transmission order: piece 0 first
+            assert_eq!(qf.frame_type, piece as u8);
+            assert_eq!(event.event_type, VlcRtpmidiEventType::MtcQuarter as u32);
+            assert_eq!(
+                &event.data[..event.data_len as usize],
+                &[qf.frame_type, qf.value]
+            );
+        }
+    }
+
+    let mut events = vec![vlc_rtpmidi_create_mmc_play_event(); 8];
+    let invalid = VlcRtpmidiSmpteTime {
+        hours: 24,
+        minutes: 0,
+        seconds: 0,
+        frames: 0,
+    };
+    unsafe {
+        assert_eq!(
+            vlc_rtpmidi_create_mtc_quarter_sequence(&invalid, events.as_mut_ptr()),
+            VlcRtpmidiErrorCode::InvalidTimecode as i32
+        );
+        assert_eq!(
+            vlc_rtpmidi_create_mtc_quarter_sequence(std::ptr::null(), events.as_mut_ptr()),
+            VlcRtpmidiErrorCode::NullPointer as i32
+        );
+    }
+    assert!(events
+        .iter()
+        .all(|event| event.event_type == VlcRtpmidiEventType::MmcPlay as u32));
+}
+
+#[test]
+fn test_quarter_frames_to_smpte_ffi_errors() {
+    let (_, valid) = quarter_frames_ffi(&VlcRtpmidiSmpteTime {
+        hours: 1,
+        minutes: 2,
+        seconds: 3,
+        frames: 4,
+    });
+
+    // Wrong event type and malformed quarter frame
+    let mut events = valid.clone();
+    events[3] = vlc_rtpmidi_create_mmc_play_event();
+    assert_eq!(
+        smpte_ffi(&events).0,
+        VlcRtpmidiErrorCode::InvalidEventType as i32
+    );
+    let mut events = valid.clone();
+    events[3].data_len = 1;
+    assert_eq!(
+        smpte_ffi(&events).0,
+        VlcRtpmidiErrorCode::InvalidEventType as i32
+    );
+
+    // Pieces swapped
+    let mut events = valid.clone();
+    events.swap(2, 3);
+    assert_eq!(
+        smpte_ffi(&events).0,
+        VlcRtpmidiErrorCode::QuarterFrameOrder as i32
+    );
+
+    // Frames 31, and rate bits other than 30fps
+    let mut events = valid.clone();
+    events[0] = vlc_rtpmidi_create_mtc_quarter_event(0, 0x0F);
+    events[1] = vlc_rtpmidi_create_mtc_quarter_event(1, 0x01);
+    assert_eq!(
+        smpte_ffi(&events).0,
+        VlcRtpmidiErrorCode::InvalidTimecode as i32
+    );
+    let mut events = valid.clone();
+    events[7] = vlc_rtpmidi_create_mtc_quarter_event(7, 0x02);
+    assert_eq!(
+        smpte_ffi(&events).0,
+        VlcRtpmidiErrorCode::InvalidTimecode as i32
+    );
+
+    // Output untouched on error
+    let mut out = VlcRtpmidiSmpteTime {
+        hours: 9,
+        ..VlcRtpmidiSmpteTime::default()
+    };
+    let result = unsafe { vlc_rtpmidi_quarter_frames_to_smpte_ffi(events.as_ptr(), &mut out) };
+    assert_eq!(result, VlcRtpmidiErrorCode::InvalidTimecode as i32);
+    assert_eq!(out.hours, 9);
+}
+
+#[test]
+fn test_quarter_frame_ffi_null_pointers() {
+    let null_pointer = VlcRtpmidiErrorCode::NullPointer as i32;
+    let input = VlcRtpmidiSmpteTime::default();
+    let mut events = vec![vlc_rtpmidi_create_mmc_play_event(); 8];
+    let mut out = VlcRtpmidiSmpteTime::default();
+    unsafe {
+        assert_eq!(
+            vlc_rtpmidi_smpte_to_quarter_frames_ffi(std::ptr::null(), events.as_mut_ptr()),
+            null_pointer
+        );
+        assert_eq!(
+            vlc_rtpmidi_smpte_to_quarter_frames_ffi(&input, std::ptr::null_mut()),
+            null_pointer
+        );
+        assert_eq!(
+            vlc_rtpmidi_quarter_frames_to_smpte_ffi(std::ptr::null(), &mut out),
+            null_pointer
+        );
+        assert_eq!(
+            vlc_rtpmidi_quarter_frames_to_smpte_ffi(events.as_ptr(), std::ptr::null_mut()),
+            null_pointer
+        );
+    }
+}
+
+// === Quarter-Frame Assembler Tests ===
+
+/// Feeds one event, returning the code, completion flag and timecode
+fn assembler_feed(
+    handle: *mut VlcRtpmidiMtcAssembler,
+    event: &VlcRtpmidiEvent,
+) -> (i32, i32, VlcRtpmidiSmpteTime) {
+    let mut out = VlcRtpmidiSmpteTime::default();
+    let mut complete = -1;
+    let result = unsafe { vlc_rtpmidi_mtc_assembler_feed(handle, event, &mut out, &mut complete) };
+    (result, complete, out)
+}
+
+#[test]
+fn test_mtc_assembler_ffi_completes_cycle() {
+    let input = VlcRtpmidiSmpteTime {
+        hours: 1,
+        minutes: 2,
+        seconds: 3,
+        frames: 4,
+    };
+    let (_, pieces) = quarter_frames_ffi(&input);
+    let handle = vlc_rtpmidi_mtc_assembler_new();
+    assert!(!handle.is_null());
+
+    for piece in &pieces[..7] {
+        let (result, complete, _) = assembler_feed(handle, piece);
+        assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+        assert_eq!(complete, 0);
+    }
+    assert_eq!(
+        assembler_feed(handle, &pieces[7]),
+        (VlcRtpmidiErrorCode::Success as i32, 1, input)
+    );
+
+    // A reset discards a partial cycle
+    for piece in &pieces[..4] {
+        assembler_feed(handle, piece);
+    }
+    assert_eq!(
+        unsafe { vlc_rtpmidi_mtc_assembler_reset(handle) },
+        VlcRtpmidiErrorCode::Success as i32
+    );
+    for piece in &pieces[4..] {
+        assert_eq!(assembler_feed(handle, piece).1, 0);
+    }
+
+    unsafe { vlc_rtpmidi_mtc_assembler_free(handle) };
+}
+
+#[test]
+fn test_mtc_assembler_ffi_rejects_invalid_events() {
+    let handle = vlc_rtpmidi_mtc_assembler_new();
+
+    assert_eq!(
+        assembler_feed(handle, &vlc_rtpmidi_create_mmc_play_event()).0,
+        VlcRtpmidiErrorCode::InvalidEventType as i32
+    );
+    assert_eq!(
+        assembler_feed(handle, &vlc_rtpmidi_create_mtc_quarter_event(8, 0)).0,
+        VlcRtpmidiErrorCode::InvalidEventType as i32
+    );
+    assert_eq!(
+        assembler_feed(handle, &vlc_rtpmidi_create_mtc_quarter_event(0, 16)).0,
+        VlcRtpmidiErrorCode::InvalidTimecode as i32
+    );
+
+    unsafe { vlc_rtpmidi_mtc_assembler_free(handle) };
+}
+
+#[test]
+fn test_mtc_assembler_ffi_null_pointers() {
+    let null_pointer = VlcRtpmidiErrorCode::NullPointer as i32;
+    let handle = vlc_rtpmidi_mtc_assembler_new();
+    let event = vlc_rtpmidi_create_mtc_quarter_event(0, 0);
+    let mut out = VlcRtpmidiSmpteTime::default();
+    let mut complete = 0;
+    unsafe {
+        assert_eq!(
+            vlc_rtpmidi_mtc_assembler_feed(std::ptr::null_mut(), &event, &mut out, &mut complete),
+            null_pointer
+        );
+        assert_eq!(
+            vlc_rtpmidi_mtc_assembler_feed(handle, std::ptr::null(), &mut out, &mut complete),
+            null_pointer
+        );
+        assert_eq!(
+            vlc_rtpmidi_mtc_assembler_feed(handle, &event, std::ptr::null_mut(), &mut complete),
+            null_pointer
+        );
+        assert_eq!(
+            vlc_rtpmidi_mtc_assembler_feed(handle, &event, &mut out, std::ptr::null_mut()),
+            null_pointer
+        );
+        assert_eq!(
+            vlc_rtpmidi_mtc_assembler_reset(std::ptr::null_mut()),
+            null_pointer
+        );
+        // Freeing null is a no-op
+        vlc_rtpmidi_mtc_assembler_free(std::ptr::null_mut());
+        vlc_rtpmidi_mtc_assembler_free(handle);
+    }
+}
+
+// === Master Session Tests ===
+
+fn master_session() -> *mut VlcRtpmidiMasterSession {
+    let config = VlcRtpmidiNetsyncConfig {
+        ssrc: 0x1234_5678,
+        ..vlc_rtpmidi_default_netsync_config()
+    };
+    unsafe { vlc_rtpmidi_master_session_new(&config) }
+}
+
+/// Builds the packet for one event, returning the code and the packet
+fn session_packet(
+    handle: *mut VlcRtpmidiMasterSession,
+    event: &VlcRtpmidiEvent,
+    now_us: u64,
+    buffer_size: usize,
+) -> (i32, Vec<u8>) {
+    let mut buffer = vec![0u8; buffer_size];
+    let mut actual_size = usize::MAX;
+    let result = unsafe {
+        vlc_rtpmidi_master_session_packet_for_event(
+            handle,
+            event,
+            now_us,
+            buffer.as_mut_ptr(),
+            buffer.len(),
+            &mut actual_size,
+        )
+    };
+    buffer.truncate(actual_size);
+    (result, buffer)
+}
+
+/// Runs one tick, returning the code and each packet
+fn session_tick(handle: *mut VlcRtpmidiMasterSession, now_us: u64) -> (i32, Vec<Vec<u8>>) {
+    let mut buffer = vec![0u8; 2 * vlc_rtpmidi_get_max_packet_size()];
+    let mut sizes = [0usize; 2];
+    let mut count = usize::MAX;
+    let result = unsafe {
+        vlc_rtpmidi_master_session_tick(
+            handle,
+            now_us,
+            buffer.as_mut_ptr(),
+            buffer.len(),
+            sizes.as_mut_ptr(),
+            sizes.len(),
+            &mut count,
+        )
+    };
+    let mut packets = Vec::new();
+    let mut offset = 0;
+    for &size in &sizes[..count] {
+        packets.push(buffer[offset..offset + size].to_vec());
+        offset += size;
+    }
+    (result, packets)
+}
+
+#[test]
+fn test_master_session_ffi_matches_rust_session() {
+    let handle = master_session();
+    assert!(!handle.is_null());
+    let mut session = MasterSession::with_config(NetsyncConfig {
+        ssrc: 0x1234_5678,
+        ..NetsyncConfig::default()
+    });
+
+    let events = [
+        (
+            vlc_rtpmidi_create_mmc_locate_event(0, 1, 0, 0),
+            MidiEvent::Mmc(MmcCommand::Locate {
+                hour: 0,
+                minute: 1,
+                second: 0,
+                frame: 0,
+                subframe: 0,
+            }),
+        ),
+        (
+            vlc_rtpmidi_create_mmc_play_event(),
+            MidiEvent::Mmc(MmcCommand::Play),
+        ),
+    ];
+    for (now_us, (c_event, event)) in (0..).step_by(1_000).zip(&events) {
+        let (result, packet) = session_packet(handle, c_event, now_us, 64);
+        assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+        assert_eq!(packet, session.packet_for_event(event, now_us).unwrap());
+    }
+
+    // The Locate makes a Full Frame due, followed by Play while rolling
+    let (result, packets) = session_tick(handle, 10_000);
+    assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+    assert_eq!(packets.len(), MAX_TICK_PACKETS);
+    assert_eq!(packets, session.tick(10_000));
+    let sequences: Vec<u16> = packets
+        .iter()
+        .map(|packet| RtpHeader::parse(packet).unwrap().0.sequence_number)
+        .collect();
+    assert_eq!(sequences, [2, 3]);
+
+    // Nothing due right after
+    assert_eq!(
+        session_tick(handle, 20_000),
+        (VlcRtpmidiErrorCode::Success as i32, Vec::new())
+    );
+
+    unsafe { vlc_rtpmidi_master_session_free(handle) };
+}
+
+#[test]
+fn test_master_session_ffi_buffer_too_small_keeps_sequence() {
+    let handle = master_session();
+    let play = vlc_rtpmidi_create_mmc_play_event();
+
+    // 12-byte RTP header, 1-byte payload header, 6-byte MMC Play
+    let (result, packet) = session_packet(handle, &play, 0, 18);
+    assert_eq!(result, VlcRtpmidiErrorCode::BufferTooSmall as i32);
+    assert!(packet.is_empty());
+    let (result, packet) = session_packet(handle, &play, 0, 19);
+    assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+    assert_eq!(RtpHeader::parse(&packet).unwrap().0.sequence_number, 0);
+
+    // Tick needs room for the worst case
+    let mut buffer = [0u8; 8];
+    let mut sizes = [0usize; 2];
+    let mut count = 0;
+    let result = unsafe {
+        vlc_rtpmidi_master_session_tick(
+            handle,
+            0,
+            buffer.as_mut_ptr(),
+            buffer.len(),
+            sizes.as_mut_ptr(),
+            sizes.len(),
+            &mut count,
+        )
+    };
+    assert_eq!(result, VlcRtpmidiErrorCode::BufferTooSmall as i32);
+
+    unsafe { vlc_rtpmidi_master_session_free(handle) };
+}
+
+#[test]
+fn test_master_session_ffi_null_pointers() {
+    let null_pointer = VlcRtpmidiErrorCode::NullPointer as i32;
+    let handle = master_session();
+    let play = vlc_rtpmidi_create_mmc_play_event();
+    let mut buffer = [0u8; 64];
+    let mut sizes = [0usize; 2];
+    let mut size = 0;
+    unsafe {
+        assert!(vlc_rtpmidi_master_session_new(std::ptr::null()).is_null());
+        for (handle, event, buffer, size) in [
+            (
+                std::ptr::null_mut(),
+                &play as *const _,
+                buffer.as_mut_ptr(),
+                &mut size as *mut _,
+            ),
+            (handle, std::ptr::null(), buffer.as_mut_ptr(), &mut size),
+            (handle, &play, std::ptr::null_mut(), &mut size),
+            (handle, &play, buffer.as_mut_ptr(), std::ptr::null_mut()),
+        ] {
+            assert_eq!(
+                vlc_rtpmidi_master_session_packet_for_event(handle, event, 0, buffer, 64, size),
+                null_pointer
+            );
+        }
+        for (handle, buffer, sizes, count) in [
+            (
+                std::ptr::null_mut(),
+                buffer.as_mut_ptr(),
+                sizes.as_mut_ptr(),
+                &mut size as *mut _,
+            ),
+            (handle, std::ptr::null_mut(), sizes.as_mut_ptr(), &mut size),
+            (handle, buffer.as_mut_ptr(), std::ptr::null_mut(), &mut size),
+            (
+                handle,
+                buffer.as_mut_ptr(),
+                sizes.as_mut_ptr(),
+                std::ptr::null_mut(),
+            ),
+        ] {
+            assert_eq!(
+                vlc_rtpmidi_master_session_tick(handle, 0, buffer, 64, sizes, 2, count),
+                null_pointer
+            );
+        }
+        vlc_rtpmidi_master_session_free(std::ptr::null_mut());
+        vlc_rtpmidi_master_session_free(handle);
+    }
+}
+
+// === Slave Session Tests ===
+
+fn slave_session() -> *mut VlcRtpmidiSlaveSession {
+    let config = vlc_rtpmidi_default_netsync_config();
+    unsafe { vlc_rtpmidi_slave_session_new(&config) }
+}
+
+fn slave_feed(handle: *mut VlcRtpmidiSlaveSession, packet: &[u8], arrival_us: u64) -> i32 {
+    unsafe { vlc_rtpmidi_slave_session_feed(handle, packet.as_ptr(), packet.len(), arrival_us) }
+}
+
+/// Drains the event queue
+fn slave_poll_all(handle: *mut VlcRtpmidiSlaveSession) -> Vec<VlcRtpmidiEvent> {
+    let mut events = Vec::new();
+    let mut event = vlc_rtpmidi_create_mmc_play_event();
+    while unsafe { vlc_rtpmidi_slave_session_poll_event(handle, &mut event) }
+        == VlcRtpmidiErrorCode::Success as i32
+    {
+        events.push(event.clone());
+    }
+    events
+}
+
+fn assert_same_event(actual: &VlcRtpmidiEvent, expected: &VlcRtpmidiEvent) {
+    assert_eq!(actual.event_type, expected.event_type);
+    assert_eq!(actual.data_len, expected.data_len);
+    assert_eq!(actual.data, expected.data);
+}
+
+#[test]
+fn test_slave_session_ffi_queues_events() {
+    let handle = slave_session();
+    assert!(!handle.is_null());
+    let mut master = MasterSession::new();
+
+    let locate = master
+        .packet_for_event(
+            &MidiEvent::Mmc(MmcCommand::Locate {
+                hour: 0,
+                minute: 1,
+                second: 2,
+                frame: 3,
+                subframe: 0,
+            }),
+            0,
+        )
+        .unwrap();
+    let play = master
+        .packet_for_event(&MidiEvent::Mmc(MmcCommand::Play), 0)
+        .unwrap();
+    assert_eq!(
+        slave_feed(handle, &locate, 0),
+        VlcRtpmidiErrorCode::Success as i32
+    );
+    assert_eq!(
+        slave_feed(handle, &play, 0),
+        VlcRtpmidiErrorCode::Success as i32
+    );
+
+    // A complete quarter-frame cycle comes out as one Full Frame
+    let position = MtcFullFrame {
+        hours: 0,
+        minutes: 1,
+        seconds: 4,
+        frames: 6,
+    };
+    for qf in smpte_to_quarter_frames(&position) {
+        let event = MidiEvent::MtcQuarter {
+            msg_type: qf.frame_type,
+            value: qf.value,
+        };
+        let packet = master.packet_for_event(&event, 0).unwrap();
+        assert_eq!(
+            slave_feed(handle, &packet, 0),
+            VlcRtpmidiErrorCode::Success as i32
+        );
+    }
+
+    let events = slave_poll_all(handle);
+    assert_eq!(events.len(), 3);
+    assert_same_event(&events[0], &vlc_rtpmidi_create_mmc_locate_event(0, 1, 2, 3));
+    assert_same_event(&events[1], &vlc_rtpmidi_create_mmc_play_event());
+    assert_same_event(&events[2], &vlc_rtpmidi_create_mtc_full_event(0, 1, 4, 6));
+
+    let mut event = vlc_rtpmidi_create_mmc_play_event();
+    assert_eq!(
+        unsafe { vlc_rtpmidi_slave_session_poll_event(handle, &mut event) },
+        VlcRtpmidiErrorCode::NoEvent as i32
+    );
+
+    unsafe { vlc_rtpmidi_slave_session_free(handle) };
+}
+
+#[test]
+fn test_slave_session_ffi_reports_dropped_packets() {
+    let handle = slave_session();
+    let mut master = MasterSession::new();
+    let mut other = MasterSession::with_config(NetsyncConfig {
+        ssrc: 0x0BAD_CAFE,
+        ..NetsyncConfig::default()
+    });
+    let play = MidiEvent::Mmc(MmcCommand::Play);
+
+    let first = master.packet_for_event(&play, 0).unwrap();
+    let second = master.packet_for_event(&play, 0).unwrap();
+    assert_eq!(
+        slave_feed(handle, &first, 0),
+        VlcRtpmidiErrorCode::Success as i32
+    );
+    assert_eq!(
+        slave_feed(handle, &second, 0),
+        VlcRtpmidiErrorCode::Success as i32
+    );
+    assert_eq!(
+        slave_feed(handle, &second, 0),
+        VlcRtpmidiErrorCode::Duplicate as i32
+    );
+    assert_eq!(
+        slave_feed(handle, &first, 0),
+        VlcRtpmidiErrorCode::OutOfOrder as i32
+    );
+    let foreign = other.packet_for_event(&play, 0).unwrap();
+    assert_eq!(
+        slave_feed(handle, &foreign, 0),
+        VlcRtpmidiErrorCode::ForeignSsrc as i32
+    );
+    assert_eq!(
+        slave_feed(handle, &[0x80, 0x61], 0),
+        VlcRtpmidiErrorCode::TruncatedPayload as i32
+    );
+
+    // Only the accepted packets queued events
+    assert_eq!(slave_poll_all(handle).len(), 2);
+
+    let mut metrics = VlcRtpmidiNetsyncMetrics::default();
+    assert_eq!(
+        unsafe { vlc_rtpmidi_slave_session_metrics(handle, &mut metrics) },
+        VlcRtpmidiErrorCode::Success as i32
+    );
+    assert_eq!(metrics.packets_parsed, 2);
+    assert_eq!(metrics.duplicates_dropped, 1);
+    assert_eq!(metrics.out_of_order_dropped, 1);
+    assert_eq!(metrics.foreign_ssrc_dropped, 1);
+    assert_eq!(metrics.rtp_header_errors, 1);
+    assert_eq!(metrics.has_last_drift, 0);
+
+    unsafe { vlc_rtpmidi_slave_session_free(handle) };
+}
+
+#[test]
+fn test_slave_session_ffi_reports_why_payloads_are_rejected() {
+    let mut config = vlc_rtpmidi_default_netsync_config();
+    config.strict_parsing = 1;
+    let handle = unsafe { vlc_rtpmidi_slave_session_new(&config) };
+    let mut master = MasterSession::new();
+    let play = MidiEvent::Mmc(MmcCommand::Play);
+
+    let cases: [(&[u8], VlcRtpmidiErrorCode); 5] = [
+        // LEN=9 with three bytes present
+        (
+            &[0x09, 0xF0, 0x7F, 0x7F],
+            VlcRtpmidiErrorCode::TruncatedPayload,
+        ),
+        // LEN=0 followed by a command, with no J flag announcing a journal
+        (&[0x00, 0xFA], VlcRtpmidiErrorCode::HeaderMismatch),
+        (&[0x01, 0x40], VlcRtpmidiErrorCode::InvalidMidi),
+        // MMC Play without its F7 terminator
+        (
+            &[0x05, 0xF0, 0x7F, 0x7F, 0x06, 0x02],
+            VlcRtpmidiErrorCode::MalformedSysex,
+        ),
+        // Full Frame at minute 75
+        (
+            &[
+                0x0A, 0xF0, 0x7F, 0x7F, 0x01, 0x01, 0x01, 0x4B, 0x00, 0x00, 0xF7,
+            ],
+            VlcRtpmidiErrorCode::ValueOutOfRange,
+        ),
+    ];
+    for (payload, expected) in cases {
+        let mut packet = master.packet_for_event(&play, 0).unwrap();
+        packet.truncate(RTP_HEADER_LENGTH);
+        packet.extend_from_slice(payload);
+        assert_eq!(
+            slave_feed(handle, &packet, 0),
+            expected as i32,
+            "{:02X?}",
+            payload
+        );
+    }
+
+    // Malformed bytes are located from the start of the MIDI list
+    let mut packet = master.packet_for_event(&play, 0).unwrap();
+    packet.truncate(RTP_HEADER_LENGTH);
+    packet.extend_from_slice(&[0x05, 0xF0, 0x7D, 0x01, 0x90, 0xF7]);
+    assert_eq!(
+        slave_feed(handle, &packet, 0),
+        VlcRtpmidiErrorCode::InvalidMidi as i32
+    );
+    assert_eq!(
+        c_str(vlc_rtpmidi_get_last_error_detail()),
+        "slave session: payload rejected: Invalid MIDI data at position 3: 0x90 \
+         (bytes 0..5 of 5: F0 7D 01 90 F7)"
+    );
+
+    unsafe { vlc_rtpmidi_slave_session_free(handle) };
+}
+
+#[test]
+fn test_slave_session_ffi_fails_on_fatal_errors() {
+    let config = vlc_rtpmidi_default_netsync_config();
+    let handle = unsafe { vlc_rtpmidi_slave_session_new(&config) };
+    let mut master = MasterSession::new();
+    let play = MidiEvent::Mmc(MmcCommand::Play);
+
+    let mut bad_version = master.packet_for_event(&play, 0).unwrap();
+    bad_version[0] = 0x40;
+    assert_eq!(
+        slave_feed(handle, &bad_version, 0),
+        VlcRtpmidiErrorCode::SessionFailed as i32
+    );
+    assert_eq!(
+        c_str(vlc_rtpmidi_get_last_error_detail()),
+        "slave session: failed: Parse error: Unsupported RTP version 1, expected 2"
+    );
+
+    // Valid packets are dropped from then on
+    let packet = master.packet_for_event(&play, 0).unwrap();
+    assert_eq!(
+        slave_feed(handle, &packet, 0),
+        VlcRtpmidiErrorCode::SessionFailed as i32
+    );
+
+    unsafe { vlc_rtpmidi_slave_session_free(handle) };
+}
+
+#[test]
+fn test_error_codes_map_every_rust_error() {
+    use VlcRtpmidiErrorCode as Code;
+
+    let table: Vec<(Error, Code)> = vec![
+        (ParseError::EmptyBuffer.into(), Code::InvalidSlaveEvent),
+        (
+            ParseError::BufferTooSmall {
+                requested: 5,
+                available: 1,
+            }
+            .into(),
+            Code::TruncatedPayload,
+        ),
+        (
+            ParseError::InsufficientHeaderData.into(),
+            Code::TruncatedPayload,
+        ),
+        (
+            ParseError::InvalidMidiData {
+                position: 3,
+                byte: 0x90,
+            }
+            .into(),
+            Code::InvalidMidi,
+        ),
+        (
+            ParseError::UnsupportedRtpVersion(1).into(),
+            Code::HeaderMismatch,
+        ),
+        (MtcError::IncompleteFrame.into(), Code::QuarterFrameOrder),
+        (MtcError::InvalidFrameType.into(), Code::QuarterFrameOrder),
+        (MtcError::TooManyFrames.into(), Code::QuarterFrameOrder),
+        (MtcError::InvalidFrames(30).into(), Code::InvalidTimecode),
+        (MtcError::InvalidSeconds(60).into(), Code::InvalidTimecode),
+        (MtcError::InvalidMinutes(60).into(), Code::InvalidTimecode),
+        (MtcError::InvalidHours(24).into(), Code::InvalidTimecode),
+        (
+            MtcError::UnsupportedFrameRate(1).into(),
+            Code::InvalidTimecode,
+        ),
+        (MtcError::MalformedTimecode.into(), Code::MalformedTimecode),
+        (
+            NetsyncError::InvalidMasterEvent.into(),
+            Code::InvalidMasterEvent,
+        ),
+        (
+            NetsyncError::InvalidSlaveEvent.into(),
+            Code::InvalidSlaveEvent,
+        ),
+        (NetsyncError::SessionFailed.into(), Code::SessionFailed),
+        (
+            NetsyncError::TooShort { got: 1, min: 2 }.into(),
+            Code::TruncatedPayload,
+        ),
+        (
+            NetsyncError::DeclaredLengthExceedsBuffer {
+                declared: 5,
+                available: 1,
+            }
+            .into(),
+            Code::TruncatedPayload,
+        ),
+        (
+            NetsyncError::TrailingData { extra: 1 }.into(),
+            Code::HeaderMismatch,
+        ),
+        (
+            NetsyncError::UnsupportedFlags { flags: 0x2 }.into(),
+            Code::HeaderMismatch,
+        ),
+        (
+            AppleMidiError::TooShort {
+                expected: 16,
+                available: 4,
+            }
+            .into(),
+            Code::TruncatedPayload,
+        ),
+        (
+            AppleMidiError::InvalidSignature.into(),
+            Code::HeaderMismatch,
+        ),
+        (
+            AppleMidiError::UnknownCommand(*b"XX").into(),
+            Code::InvalidEventType,
+        ),
+        (
+            AppleMidiError::UnsupportedVersion(1).into(),
+            Code::HeaderMismatch,
+        ),
+        (AppleMidiError::InvalidName.into(), Code::InvalidSlaveEvent),
+        // A flow failure reports its cause, a policy violation its finding
+        (
+            slave_netsync_flow_detailed(&[0x05, 0xF1]).unwrap_err(),
+            Code::TruncatedPayload,
+        ),
+        (
+            slave_netsync_flow_with_policy(
+                &[0x05, 0xF0, 0x7F, 0x7F, 0x06, 0x02],
+                ParsePolicy::Strict,
+            )
+            .unwrap_err()
+            .into(),
+            Code::MalformedSysex,
+        ),
+    ];
+    for (error, code) in table {
+        assert_eq!(Code::from(&error), code, "{:?}", error);
+    }
+}
+
+#[test]
+fn test_error_codes_map_parse_errors() {
+    let code = |error: ParseError| VlcRtpmidiErrorCode::from(&error);
+    assert_eq!(
+        code(ParseError::BufferTooSmall {
+            requested: 5,
+            available: 1
+        }),
+        VlcRtpmidiErrorCode::TruncatedPayload
+    );
+    assert_eq!(
+        code(ParseError::InsufficientHeaderData),
+        VlcRtpmidiErrorCode::TruncatedPayload
+    );
+    assert_eq!(
+        code(ParseError::UnsupportedRtpVersion(1)),
+        VlcRtpmidiErrorCode::HeaderMismatch
+    );
+    assert_eq!(
+        code(ParseError::EmptyBuffer),
+        VlcRtpmidiErrorCode::InvalidSlaveEvent
+    );
+    assert_eq!(
+        VlcRtpmidiErrorCode::from(&NetsyncError::InvalidMasterEvent),
+        VlcRtpmidiErrorCode::InvalidMasterEvent
+    );
+
+    // The plain slave flow reports the same codes
+    let mut event = vlc_rtpmidi_create_mmc_play_event();
+    let flow = |payload: &[u8], event: &mut VlcRtpmidiEvent| unsafe {
+        vlc_rtpmidi_slave_netsync_flow_ffi(payload.as_ptr(), payload.len(), event)
+    };
+    assert_eq!(
+        flow(&[0x05, 0xF1], &mut event),
+        VlcRtpmidiErrorCode::TruncatedPayload as i32
+    );
+    assert_eq!(
+        flow(&[0x00, 0xFA], &mut event),
+        VlcRtpmidiErrorCode::HeaderMismatch as i32
+    );
+    assert_eq!(
+        flow(&[], &mut event),
+        VlcRtpmidiErrorCode::InvalidSlaveEvent as i32
+    );
+}
+
+#[test]
+fn test_slave_session_ffi_queue_drops_oldest() {
+    let handle = slave_session();
+    let mut master = MasterSession::new();
+    for second in 0..40 {
+        let event = MidiEvent::MtcFull {
+            hour: 0,
+            minute: 0,
+            second,
+            frame: 0,
+        };
+        let packet = master.packet_for_event(&event, 0).unwrap();
+        assert_eq!(
+            slave_feed(handle, &packet, 0),
+            VlcRtpmidiErrorCode::Success as i32
+        );
+    }
+
+    let events = slave_poll_all(handle);
+    assert_eq!(events.len(), 32);
+    assert_same_event(&events[0], &vlc_rtpmidi_create_mtc_full_event(0, 0, 8, 0));
+
+    unsafe { vlc_rtpmidi_slave_session_free(handle) };
+}
+
+#[test]
+fn test_slave_session_ffi_null_pointers() {
+    let null_pointer = VlcRtpmidiErrorCode::NullPointer as i32;
+    let handle = slave_session();
+    let packet = [0u8; 16];
+    let mut event = vlc_rtpmidi_create_mmc_play_event();
+    let mut metrics = VlcRtpmidiNetsyncMetrics::default();
+    unsafe {
+        assert!(vlc_rtpmidi_slave_session_new(std::ptr::null()).is_null());
+        assert_eq!(
+            vlc_rtpmidi_slave_session_feed(std::ptr::null_mut(), packet.as_ptr(), 16, 0),
+            null_pointer
+        );
+        assert_eq!(
+            vlc_rtpmidi_slave_session_feed(handle, std::ptr::null(), 16, 0),
+            null_pointer
+        );
+        assert_eq!(
+            vlc_rtpmidi_slave_session_poll_event(std::ptr::null_mut(), &mut event),
+            null_pointer
+        );
+        assert_eq!(
+            vlc_rtpmidi_slave_session_poll_event(handle, std::ptr::null_mut()),
+            null_pointer
+        );
+        assert_eq!(
+            vlc_rtpmidi_slave_session_metrics(std::ptr::null(), &mut metrics),
+            null_pointer
+        );
+        assert_eq!(
+            vlc_rtpmidi_slave_session_metrics(handle, std::ptr::null_mut()),
+            null_pointer
+        );
+        vlc_rtpmidi_slave_session_free(std::ptr::null_mut());
+        vlc_rtpmidi_slave_session_free(handle);
+    }
+}
+
+#[test]
+fn test_session_ffi_metrics_reset() {
+    let config = vlc_rtpmidi_default_netsync_config();
+    let master = unsafe { vlc_rtpmidi_master_session_new(&config) };
+    let slave = slave_session();
+    let play = vlc_rtpmidi_create_mmc_play_event();
+    let mut packet = [0u8; 32];
+    let mut len = 0;
+    for _ in 0..3 {
+        unsafe {
+            vlc_rtpmidi_master_session_packet_for_event(
+                master,
+                &play,
+                0,
+                packet.as_mut_ptr(),
+                packet.len(),
+                &mut len,
+            )
+        };
+        slave_feed(slave, &packet[..len], 0);
+    }
+    slave_feed(slave, &packet[..4], 0);
+
+    let mut metrics = VlcRtpmidiNetsyncMetrics::default();
+    let read = |metrics: &mut VlcRtpmidiNetsyncMetrics| unsafe {
+        assert_eq!(
+            vlc_rtpmidi_master_session_metrics(master, metrics),
+            VlcRtpmidiErrorCode::Success as i32
+        );
+        let master_sent = metrics.packets_sent;
+        assert_eq!(
+            vlc_rtpmidi_slave_session_metrics(slave, metrics),
+            VlcRtpmidiErrorCode::Success as i32
+        );
+        master_sent
+    };
+    assert_eq!(read(&mut metrics), 3);
+    assert_eq!(metrics.packets_parsed, 3);
+    assert_eq!(metrics.rtp_header_errors, 1);
+
+    unsafe {
+        assert_eq!(
+            vlc_rtpmidi_master_session_reset_metrics(master),
+            VlcRtpmidiErrorCode::Success as i32
+        );
+        assert_eq!(
+            vlc_rtpmidi_slave_session_reset_metrics(slave),
+            VlcRtpmidiErrorCode::Success as i32
+        );
+    }
+    assert_eq!(read(&mut metrics), 0);
+    assert_eq!(metrics, VlcRtpmidiNetsyncMetrics::default());
+
+    let null_pointer = VlcRtpmidiErrorCode::NullPointer as i32;
+    unsafe {
+        assert_eq!(
+            vlc_rtpmidi_master_session_metrics(std::ptr::null(), &mut metrics),
+            null_pointer
+        );
+        assert_eq!(
+            vlc_rtpmidi_master_session_metrics(master, std::ptr::null_mut()),
+            null_pointer
+        );
+        assert_eq!(
+            vlc_rtpmidi_master_session_reset_metrics(std::ptr::null_mut()),
+            null_pointer
+        );
+        assert_eq!(
+            vlc_rtpmidi_slave_session_reset_metrics(std::ptr::null_mut()),
+            null_pointer
+        );
+        vlc_rtpmidi_master_session_free(master);
+        vlc_rtpmidi_slave_session_free(slave);
+    }
+}
+
+// === Frame Rate Tests ===
+
+const FRAME_RATES: [(VlcRtpmidiFrameRate, FrameRate); 4] = [
+    (VlcRtpmidiFrameRate::Fps24, FrameRate::Fps24),
+    (VlcRtpmidiFrameRate::Fps25, FrameRate::Fps25),
+    (VlcRtpmidiFrameRate::Fps2997Df, FrameRate::Fps2997Df),
+    (VlcRtpmidiFrameRate::Fps30Ndf, FrameRate::Fps30),
+];
+
+#[test]
+fn test_rate_conversions_ffi_match_rust() {
+    let success = VlcRtpmidiErrorCode::Success as i32;
+    for (c_rate, rate) in FRAME_RATES {
+        for us in [0, 41_667, 60_060_000, 3_723_456_789] {
+            let expected = us_to_smpte_rate(us, rate);
+            let mut tc = VlcRtpmidiSmpteTime::default();
+            assert_eq!(
+                unsafe { vlc_rtpmidi_us_to_smpte_rate_ffi(us, c_rate as u32, &mut tc) },
+                success
+            );
+            assert_eq!(
+                (tc.hours, tc.minutes, tc.seconds, tc.frames),
+                (
+                    expected.hours,
+                    expected.minutes,
+                    expected.seconds,
+                    expected.frames
+                )
+            );
+
+            let mut back = u64::MAX;
+            assert_eq!(
+                unsafe { vlc_rtpmidi_smpte_to_us_rate_ffi(&tc, c_rate as u32, &mut back) },
+                success
+            );
+            assert_eq!(back, smpte_to_us_rate(&expected, rate));
+
+            let mut pieces = vec![vlc_rtpmidi_create_mmc_play_event(); 8];
+            assert_eq!(
+                unsafe {
+                    vlc_rtpmidi_smpte_to_quarter_frames_rate_ffi(
+                        &tc,
+                        c_rate as u32,
+                        pieces.as_mut_ptr(),
+                    )
+                },
+                success
+            );
+            for (piece, qf) in pieces
+                .iter()
+                .zip(smpte_to_quarter_frames_rate(&expected, rate))
+            {
+                assert_same_event(
+                    piece,
+                    &vlc_rtpmidi_create_mtc_quarter_event(qf.frame_type, qf.value),
+                );
+            }
+
+            let mut decoded = VlcRtpmidiSmpteTime::default();
+            let mut decoded_rate = VlcRtpmidiFrameRate::Fps24;
+            assert_eq!(
+                unsafe {
+                    vlc_rtpmidi_quarter_frames_to_smpte_rate_ffi(
+                        pieces.as_ptr(),
+                        &mut decoded,
+                        &mut decoded_rate,
+                    )
+                },
+                success
+            );
+            assert_eq!(decoded, tc);
+            assert_eq!(decoded_rate, c_rate);
+        }
+    }
+}
+
+#[test]
+fn test_rate_conversions_ffi_check_frames_against_rate() {
+    let tc = VlcRtpmidiSmpteTime {
+        hours: 0,
+        minutes: 0,
+        seconds: 1,
+        frames: 24,
+    };
+    let mut us = 0;
+    let mut pieces = vec![vlc_rtpmidi_create_mmc_play_event(); 8];
+    unsafe {
+        assert_eq!(
+            vlc_rtpmidi_smpte_to_us_rate_ffi(&tc, VlcRtpmidiFrameRate::Fps24 as u32, &mut us),
+            VlcRtpmidiErrorCode::InvalidTimecode as i32
+        );
+        assert_eq!(
+            vlc_rtpmidi_smpte_to_quarter_frames_rate_ffi(
+                &tc,
+                VlcRtpmidiFrameRate::Fps24 as u32,
+                pieces.as_mut_ptr()
+            ),
+            VlcRtpmidiErrorCode::InvalidTimecode as i32
+        );
+        assert_eq!(
+            vlc_rtpmidi_smpte_to_us_rate_ffi(&tc, VlcRtpmidiFrameRate::Fps25 as u32, &mut us),
+            VlcRtpmidiErrorCode::Success as i32
+        );
+    }
+    assert_eq!(us, 1_960_000);
+}
+
+/// Formats `tc` at `rate` into a buffer of `cap` bytes, returning the code and text
+fn smpte_text(tc: &VlcRtpmidiSmpteTime, rate: VlcRtpmidiFrameRate, cap: usize) -> (i32, String) {
+    let mut out = vec![0x55u8; cap];
+    let result =
+        unsafe { vlc_rtpmidi_smpte_to_string_ffi(tc, rate as u32, out.as_mut_ptr().cast(), cap) };
+    let text = match out.iter().position(|&b| b == 0) {
+        Some(end) => String::from_utf8(out[..end].to_vec()).unwrap(),
+        None => String::new(),
+    };
+    (result, text)
+}
+
+/// Parses `text` at `rate`, returning the code and the timecode
+fn smpte_parse(text: &str, rate: VlcRtpmidiFrameRate) -> (i32, VlcRtpmidiSmpteTime) {
+    let text = std::ffi::CString::new(text).unwrap();
+    let mut tc = VlcRtpmidiSmpteTime {
+        hours: 99,
+        ..VlcRtpmidiSmpteTime::default()
+    };
+    let result = unsafe { vlc_rtpmidi_smpte_from_string_ffi(text.as_ptr(), rate as u32, &mut tc) };
+    (result, tc)
+}
+
+#[test]
+fn test_smpte_string_ffi_matches_rust() {
+    let tc = VlcRtpmidiSmpteTime {
+        hours: 1,
+        minutes: 2,
+        seconds: 3,
+        frames: 4,
+    };
+    let smpte = MtcFullFrame {
+        hours: 1,
+        minutes: 2,
+        seconds: 3,
+        frames: 4,
+    };
+    let size = VLC_RTPMIDI_SMPTE_STRING_SIZE;
+    assert_eq!(
+        smpte_text(&tc, VlcRtpmidiFrameRate::Fps30Ndf, size),
+        (0, smpte.to_string())
+    );
+    assert_eq!(
+        smpte_text(&tc, VlcRtpmidiFrameRate::Fps2997Df, size),
+        (0, format!("{:#}", smpte))
+    );
+    assert_eq!(
+        smpte_text(&tc, VlcRtpmidiFrameRate::Fps25, size),
+        (0, "01:02:03:04".to_string())
+    );
+
+    // Both separators parse at every rate
+    for text in ["01:02:03:04", "01:02:03;04"] {
+        for rate in [VlcRtpmidiFrameRate::Fps24, VlcRtpmidiFrameRate::Fps2997Df] {
+            let (result, parsed) = smpte_parse(text, rate);
+            assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+            assert_eq!(parsed, tc);
+        }
+    }
+}
+
+#[test]
+fn test_smpte_string_ffi_boundaries() {
+    let success = VlcRtpmidiErrorCode::Success as i32;
+    let invalid = VlcRtpmidiErrorCode::InvalidTimecode as i32;
+
+    // Last frame of each rate, and one past it
+    for (rate, last) in [
+        (VlcRtpmidiFrameRate::Fps24, "23:59:59:23"),
+        (VlcRtpmidiFrameRate::Fps25, "23:59:59:24"),
+        (VlcRtpmidiFrameRate::Fps2997Df, "23:59:59;29"),
+        (VlcRtpmidiFrameRate::Fps30Ndf, "23:59:59:29"),
+    ] {
+        let (result, tc) = smpte_parse(last, rate);
+        assert_eq!(result, success);
+        assert_eq!(
+            smpte_text(&tc, rate, VLC_RTPMIDI_SMPTE_STRING_SIZE),
+            (success, last.to_string())
+        );
+
+        let past = VlcRtpmidiSmpteTime {
+            frames: tc.frames + 1,
+            ..tc
+        };
+        assert_eq!(
+            smpte_text(&past, rate, VLC_RTPMIDI_SMPTE_STRING_SIZE),
+            (invalid, String::new())
+        );
+        let (result, untouched) = smpte_parse(&format!("23:59:59:{}", tc.frames + 1), rate);
+        assert_eq!(result, invalid);
+        assert_eq!(untouched.hours, 99);
+    }
+    assert_eq!(
+        smpte_parse("24:00:00:00", VlcRtpmidiFrameRate::Fps30Ndf).0,
+        invalid
+    );
+
+    // The terminator needs room too; nothing is written without it
+    let tc = VlcRtpmidiSmpteTime::default();
+    let (result, text) = smpte_text(
+        &tc,
+        VlcRtpmidiFrameRate::Fps30Ndf,
+        VLC_RTPMIDI_SMPTE_STRING_SIZE - 1,
+    );
+    assert_eq!(result, VlcRtpmidiErrorCode::BufferTooSmall as i32);
+    assert_eq!(text, "");
+}
+
+#[test]
+fn test_smpte_string_ffi_errors() {
+    let malformed = VlcRtpmidiErrorCode::MalformedTimecode as i32;
+    for text in ["", "01:02:03", "01:02:03:04:05", "1h02m03s", "01:02:03:04 "] {
+        let (result, tc) = smpte_parse(text, VlcRtpmidiFrameRate::Fps30Ndf);
+        assert_eq!(result, malformed, "{:?}", text);
+        assert_eq!(tc.hours, 99);
+    }
+
+    // Text that is not UTF-8
+    let mut tc = VlcRtpmidiSmpteTime::default();
+    let bytes = b"01:02:03:\xff\0";
+    let result = unsafe {
+        vlc_rtpmidi_smpte_from_string_ffi(
+            bytes.as_ptr().cast(),
+            VlcRtpmidiFrameRate::Fps30Ndf as u32,
+            &mut tc,
+        )
+    };
+    assert_eq!(result, malformed);
+
+    let tc = VlcRtpmidiSmpteTime::default();
+    let mut out = [0 as std::ffi::c_char; 16];
+    unsafe {
+        assert_eq!(
+            vlc_rtpmidi_smpte_to_string_ffi(&tc, 4, out.as_mut_ptr(), out.len()),
+            VlcRtpmidiErrorCode::InvalidFrameRate as i32
+        );
+        assert_eq!(
+            vlc_rtpmidi_smpte_to_string_ffi(std::ptr::null(), 3, out.as_mut_ptr(), out.len()),
+            VlcRtpmidiErrorCode::NullPointer as i32
+        );
+        assert_eq!(
+            vlc_rtpmidi_smpte_to_string_ffi(&tc, 3, std::ptr::null_mut(), 0),
+            VlcRtpmidiErrorCode::NullPointer as i32
+        );
+    }
+    assert_eq!(
+        smpte_parse("00:00:00:00", VlcRtpmidiFrameRate::Fps30Ndf).0,
+        VlcRtpmidiErrorCode::Success as i32
+    );
+    let mut tc = VlcRtpmidiSmpteTime::default();
+    unsafe {
+        assert_eq!(
+            vlc_rtpmidi_smpte_from_string_ffi(c"00:00:00:00".as_ptr(), 4, &mut tc),
+            VlcRtpmidiErrorCode::InvalidFrameRate as i32
+        );
+        assert_eq!(
+            vlc_rtpmidi_smpte_from_string_ffi(std::ptr::null(), 3, &mut tc),
+            VlcRtpmidiErrorCode::NullPointer as i32
+        );
+    }
+}
+
+#[test]
+fn test_rate_conversions_ffi_reject_invalid_rates() {
+    let invalid_rate = VlcRtpmidiErrorCode::InvalidFrameRate as i32;
+    let tc = VlcRtpmidiSmpteTime::default();
+    let mut out_tc = VlcRtpmidiSmpteTime {
+        hours: 9,
+        ..VlcRtpmidiSmpteTime::default()
+    };
+    let mut us = u64::MAX;
+    let mut pieces = vec![vlc_rtpmidi_create_mmc_play_event(); 8];
+    for rate in [4, 0x100, u32::MAX] {
+        unsafe {
+            assert_eq!(
+                vlc_rtpmidi_us_to_smpte_rate_ffi(0, rate, &mut out_tc),
+                invalid_rate
+            );
+            assert_eq!(
+                vlc_rtpmidi_smpte_to_us_rate_ffi(&tc, rate, &mut us),
+                invalid_rate
+            );
+            assert_eq!(
+                vlc_rtpmidi_smpte_to_quarter_frames_rate_ffi(&tc, rate, pieces.as_mut_ptr()),
+                invalid_rate
+            );
+        }
+
+        let config = VlcRtpmidiNetsyncConfig {
+            frame_rate: rate,
+            ..vlc_rtpmidi_default_netsync_config()
+        };
+        unsafe {
+            assert!(vlc_rtpmidi_master_session_new(&config).is_null());
+            assert!(vlc_rtpmidi_slave_session_new(&config).is_null());
+        }
+    }
+    // Outputs are untouched
+    assert_eq!(out_tc.hours, 9);
+    assert_eq!(us, u64::MAX);
+    assert_eq!(pieces[0].event_type, VlcRtpmidiEventType::MmcPlay as u32);
+    assert_eq!(
+        vlc_rtpmidi_default_netsync_config().frame_rate,
+        VlcRtpmidiFrameRate::Fps30Ndf as u32
+    );
+}
+
+#[test]
+fn test_sessions_use_configured_frame_rate() {
+    let config = VlcRtpmidiNetsyncConfig {
+        frame_rate: VlcRtpmidiFrameRate::Fps25 as u32,
+        ..vlc_rtpmidi_default_netsync_config()
+    };
+    let master = unsafe { vlc_rtpmidi_master_session_new(&config) };
+    let slave = unsafe { vlc_rtpmidi_slave_session_new(&config) };
+    assert!(!master.is_null() && !slave.is_null());
+
+    // The refreshed Full Frame is timecode at 25fps
+    let locate = vlc_rtpmidi_create_mmc_locate_event(0, 0, 1, 24);
+    assert_eq!(
+        session_packet(master, &locate, 0, 64).0,
+        VlcRtpmidiErrorCode::Success as i32
+    );
+    let (result, packets) = session_tick(master, 0);
+    assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+    let payload = RtpHeader::parse(&packets[0]).unwrap().1;
+    assert_eq!(
+        slave_netsync_flow(payload),
+        Ok(MidiEvent::MtcFull {
+            hour: 0,
+            minute: 0,
+            second: 1,
+            frame: 24,
+        })
+    );
+
+    // A received Full Frame and a 25fps quarter-frame cycle come back unchanged
+    assert_eq!(
+        slave_feed(slave, &packets[0], 0),
+        VlcRtpmidiErrorCode::Success as i32
+    );
+    let position = MtcFullFrame {
+        hours: 1,
+        minutes: 2,
+        seconds: 3,
+        frames: 24,
+    };
+    for qf in smpte_to_quarter_frames_rate(&position, FrameRate::Fps25) {
+        let event = vlc_rtpmidi_create_mtc_quarter_event(qf.frame_type, qf.value);
+        let (result, packet) = session_packet(master, &event, 0, 64);
+        assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+        assert_eq!(
+            slave_feed(slave, &packet, 0),
+            VlcRtpmidiErrorCode::Success as i32
+        );
+    }
+    let events = slave_poll_all(slave);
+    assert_eq!(events.len(), 2);
+    assert_same_event(&events[0], &vlc_rtpmidi_create_mtc_full_event(0, 0, 1, 24));
+    assert_same_event(&events[1], &vlc_rtpmidi_create_mtc_full_event(1, 2, 3, 24));
+
+    unsafe {
+        vlc_rtpmidi_master_session_free(master);
+        vlc_rtpmidi_slave_session_free(slave);
+    }
+}
+
+#[test]
+fn test_mtc_assembler_ffi_reports_frame_rate() {
+    let handle = vlc_rtpmidi_mtc_assembler_new();
+    let mut rate = VlcRtpmidiFrameRate::Fps24;
+    unsafe {
+        assert_eq!(
+            vlc_rtpmidi_mtc_assembler_frame_rate(handle, &mut rate),
+            VlcRtpmidiErrorCode::Success as i32
+        );
+        assert_eq!(rate, VlcRtpmidiFrameRate::Fps30Ndf);
+
+        let position = MtcFullFrame {
+            hours: 0,
+            minutes: 10,
+            seconds: 0,
+            frames: 0,
+        };
+        let mut tc = VlcRtpmidiSmpteTime::default();
+        let mut complete = 0;
+        for qf in smpte_to_quarter_frames_rate(&position, FrameRate::Fps2997Df) {
+            let event = vlc_rtpmidi_create_mtc_quarter_event(qf.frame_type, qf.value);
+            assert_eq!(
+                vlc_rtpmidi_mtc_assembler_feed(handle, &event, &mut tc, &mut complete),
+                VlcRtpmidiErrorCode::Success as i32
+            );
+        }
+        assert_eq!(complete, 1);
+        assert_eq!(tc.minutes, 10);
+        assert_eq!(
+            vlc_rtpmidi_mtc_assembler_frame_rate(handle, &mut rate),
+            VlcRtpmidiErrorCode::Success as i32
+        );
+        assert_eq!(rate, VlcRtpmidiFrameRate::Fps2997Df);
+        assert_eq!(
+            vlc_rtpmidi_mtc_assembler_frame_rate(std::ptr::null(), &mut rate),
+            VlcRtpmidiErrorCode::NullPointer as i32
+        );
+        vlc_rtpmidi_mtc_assembler_free(handle);
+    }
+}
+
+// === Device ID Tests ===
+
+#[test]
+fn test_device_id_locate_roundtrip_ffi() {
+    let locate = vlc_rtpmidi_create_mmc_locate_event(1, 2, 3, 4);
+    let mut buffer = [0u8; 32];
+    let mut actual_size = 0;
+    let mut event = vlc_rtpmidi_create_mmc_play_event();
+    let mut device_id = 0;
+    unsafe {
+        assert_eq!(
+            vlc_rtpmidi_master_netsync_flow_dev_ffi(
+                &locate,
+                0x05,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &mut actual_size,
+            ),
+            VlcRtpmidiErrorCode::Success as i32
+        );
+        // F0 7F <device> 06 44 ...
+        assert_eq!(buffer[3], 0x05);
+
+        assert_eq!(
+            vlc_rtpmidi_slave_netsync_flow_dev_ffi(
+                buffer.as_ptr(),
+                actual_size,
+                &mut event,
+                &mut device_id,
+            ),
+            VlcRtpmidiErrorCode::Success as i32
+        );
+    }
+    assert_same_event(&event, &locate);
+    assert_eq!(device_id, 0x05);
+
+    // Broadcast payloads and quarter frames report 0x7F
+    for source in [
+        vlc_rtpmidi_create_mmc_stop_event(),
+        vlc_rtpmidi_create_mtc_quarter_event(3, 0x2),
+    ] {
+        unsafe {
+            assert_eq!(
+                vlc_rtpmidi_master_netsync_flow_ffi(
+                    &source,
+                    buffer.as_mut_ptr(),
+                    buffer.len(),
+                    &mut actual_size,
+                ),
+                VlcRtpmidiErrorCode::Success as i32
+            );
+            assert_eq!(
+                vlc_rtpmidi_slave_netsync_flow_dev_ffi(
+                    buffer.as_ptr(),
+                    actual_size,
+                    &mut event,
+                    &mut device_id,
+                ),
+                VlcRtpmidiErrorCode::Success as i32
+            );
+        }
+        assert_same_event(&event, &source);
+        assert_eq!(device_id, 0x7F);
+    }
+}
+
+#[test]
+fn test_device_id_out_of_range_rejected_ffi() {
+    let play = vlc_rtpmidi_create_mmc_play_event();
+    let mut buffer = [0u8; 32];
+    let mut actual_size = usize::MAX;
+    unsafe {
+        assert_eq!(
+            vlc_rtpmidi_master_netsync_flow_dev_ffi(
+                &play,
+                0x80,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &mut actual_size,
+            ),
+            VlcRtpmidiErrorCode::InvalidDeviceId as i32
+        );
+        assert_eq!(actual_size, 0);
+
+        let config = VlcRtpmidiNetsyncConfig {
+            device_id: 0x80,
+            ..vlc_rtpmidi_default_netsync_config()
+        };
+        assert!(vlc_rtpmidi_master_session_new(&config).is_null());
+        assert!(vlc_rtpmidi_slave_session_new(&config).is_null());
+    }
+    assert_eq!(vlc_rtpmidi_default_netsync_config().device_id, 0x7F);
+}
+
+#[test]
+fn test_master_session_ffi_addresses_device() {
+    let config = VlcRtpmidiNetsyncConfig {
+        device_id: 0x05,
+        ..vlc_rtpmidi_default_netsync_config()
+    };
+    let master = unsafe { vlc_rtpmidi_master_session_new(&config) };
+    assert!(!master.is_null());
+    let slave = slave_session();
+
+    let locate = vlc_rtpmidi_create_mmc_locate_event(0, 1, 0, 0);
+    let (result, packet) = session_packet(master, &locate, 0, 64);
+    assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+    let payload = RtpHeader::parse(&packet).unwrap().1;
+    let mut event = vlc_rtpmidi_create_mmc_play_event();
+    let mut device_id = 0;
+    assert_eq!(
+        unsafe {
+            vlc_rtpmidi_slave_netsync_flow_dev_ffi(
+                payload.as_ptr(),
+                payload.len(),
+                &mut event,
+                &mut device_id,
+            )
+        },
+        VlcRtpmidiErrorCode::Success as i32
+    );
+    assert_same_event(&event, &locate);
+    assert_eq!(device_id, 0x05);
+
+    // The Full Frame sent on the following tick is addressed as well
+    assert_eq!(
+        slave_feed(slave, &packet, 0),
+        VlcRtpmidiErrorCode::Success as i32
+    );
+    let (result, packets) = session_tick(master, 1_000);
+    assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+    assert!(!packets.is_empty());
+    for packet in &packets {
+        let payload = RtpHeader::parse(packet).unwrap().1;
+        assert_eq!(payload[3], 0x05);
+        slave_feed(slave, packet, 1_000);
+    }
+    let events = slave_poll_all(slave);
+    assert_same_event(&events[0], &locate);
+    assert_eq!(events.len(), 1 + packets.len());
+
+    unsafe {
+        vlc_rtpmidi_master_session_free(master);
+        vlc_rtpmidi_slave_session_free(slave);
+    }
+}
+
+// === Version Tests ===
+
+#[test]
+fn test_version_matches_crate() {
+    let version = format!(
+        "{}.{}.{}",
+        vlc_rtpmidi_version_major(),
+        vlc_rtpmidi_version_minor(),
+        vlc_rtpmidi_version_patch()
+    );
+    assert_eq!(version, env!("CARGO_PKG_VERSION"));
+
+    let string = unsafe { CStr::from_ptr(vlc_rtpmidi_version_string()) };
+    assert_eq!(string.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+    assert_eq!(vlc_rtpmidi_abi_version(), VLC_RTPMIDI_ABI_VERSION);
+}
+
+#[test]
+fn test_netsync_config_default_and_validate() {
+    /// The configuration as a newer header might declare it
+    #[repr(C)]
+    struct ExtendedConfig {
+        config: VlcRtpmidiNetsyncConfig,
+        future_field: u64,
+    }
+
+    let size = size_of::<VlcRtpmidiNetsyncConfig>();
+    let mut config = VlcRtpmidiNetsyncConfig {
+        ssrc: 9,
+        ..vlc_rtpmidi_default_netsync_config()
+    };
+    unsafe {
+        assert_eq!(
+            vlc_rtpmidi_netsync_config_default(&mut config, size),
+            VlcRtpmidiErrorCode::Success as i32
+        );
+        assert_eq!(config, vlc_rtpmidi_default_netsync_config());
+        assert_eq!(config.struct_size as usize, size);
+        assert_eq!(
+            vlc_rtpmidi_netsync_config_validate(&config),
+            VlcRtpmidiErrorCode::Success as i32
+        );
+
+        let invalid = [
+            (
+                VlcRtpmidiNetsyncConfig {
+                    frame_rate: 9,
+                    ..config
+                },
+                VlcRtpmidiErrorCode::InvalidFrameRate,
+            ),
+            (
+                VlcRtpmidiNetsyncConfig {
+                    device_id: 0x80,
+                    ..config
+                },
+                VlcRtpmidiErrorCode::InvalidDeviceId,
+            ),
+            (
+                VlcRtpmidiNetsyncConfig {
+                    struct_size: 0,
+                    ..config
+                },
+                VlcRtpmidiErrorCode::LayoutMismatch,
+            ),
+        ];
+        for (config, expected) in invalid {
+            assert_eq!(
+                vlc_rtpmidi_netsync_config_validate(&config),
+                expected as i32
+            );
+            assert!(vlc_rtpmidi_master_session_new(&config).is_null());
+            assert!(vlc_rtpmidi_slave_session_new(&config).is_null());
+        }
+
+        // Fields unknown to the library are accepted while left at zero
+        let mut extended = ExtendedConfig {
+            config,
+            future_field: u64::MAX,
+        };
+        let extended_size = size_of::<ExtendedConfig>();
+        assert_eq!(
+            vlc_rtpmidi_netsync_config_default(&mut extended.config, extended_size),
+            VlcRtpmidiErrorCode::Success as i32
+        );
+        assert_eq!(extended.future_field, 0);
+        assert_eq!(extended.config.struct_size as usize, extended_size);
+        extended.config.ssrc = 0x1234;
+        assert_eq!(
+            vlc_rtpmidi_netsync_config_validate(&extended.config),
+            VlcRtpmidiErrorCode::Success as i32
+        );
+        let session = vlc_rtpmidi_slave_session_new(&extended.config);
+        assert!(!session.is_null());
+        vlc_rtpmidi_slave_session_free(session);
+
+        extended.future_field = 1;
+        assert_eq!(
+            vlc_rtpmidi_netsync_config_validate(&extended.config),
+            VlcRtpmidiErrorCode::LayoutMismatch as i32
+        );
+        assert!(vlc_rtpmidi_master_session_new(&extended.config).is_null());
+
+        assert_eq!(
+            vlc_rtpmidi_netsync_config_default(&mut config, size - 8),
+            VlcRtpmidiErrorCode::LayoutMismatch as i32
+        );
+        assert_eq!(
+            vlc_rtpmidi_netsync_config_default(std::ptr::null_mut(), size),
+            VlcRtpmidiErrorCode::NullPointer as i32
+        );
+        assert_eq!(
+            vlc_rtpmidi_netsync_config_validate(std::ptr::null()),
+            VlcRtpmidiErrorCode::NullPointer as i32
+        );
+    }
+}
+
+#[test]
+fn test_layout_check() {
+    use std::mem::{offset_of, size_of};
+
+    let success = VlcRtpmidiErrorCode::Success as i32;
+    let mismatch = VlcRtpmidiErrorCode::LayoutMismatch as i32;
+    let (size, data, data_len) = (
+        size_of::<VlcRtpmidiEvent>(),
+        offset_of!(VlcRtpmidiEvent, data),
+        offset_of!(VlcRtpmidiEvent, data_len),
+    );
+    assert_eq!(
+        vlc_rtpmidi_event_layout_check(size, data, data_len),
+        success
+    );
+    // A packed C build: no padding after the one-byte length
+    assert_eq!(vlc_rtpmidi_event_layout_check(13, data, data_len), mismatch);
+    assert_eq!(vlc_rtpmidi_event_layout_check(size, 1, data_len), mismatch);
+    assert_eq!(vlc_rtpmidi_event_layout_check(size, data, 8), mismatch);
+
+    let (size, interval, rate, device) = (
+        size_of::<VlcRtpmidiNetsyncConfig>(),
+        offset_of!(VlcRtpmidiNetsyncConfig, full_frame_interval_us),
+        offset_of!(VlcRtpmidiNetsyncConfig, frame_rate),
+        offset_of!(VlcRtpmidiNetsyncConfig, device_id),
+    );
+    assert_eq!(
+        vlc_rtpmidi_config_layout_check(size, interval, rate, device),
+        success
+    );
+    // A header without the device ID field
+    assert_eq!(
+        vlc_rtpmidi_config_layout_check(size - 8, interval, rate, device),
+        mismatch
+    );
+    assert_eq!(
+        vlc_rtpmidi_config_layout_check(size, 4, rate, device),
+        mismatch
+    );
+    assert_eq!(
+        vlc_rtpmidi_config_layout_check(size, interval, rate, device + 1),
+        mismatch
+    );
+
+    let (size, drift, has_drift) = (
+        size_of::<VlcRtpmidiNetsyncMetrics>(),
+        offset_of!(VlcRtpmidiNetsyncMetrics, last_drift_us),
+        offset_of!(VlcRtpmidiNetsyncMetrics, has_last_drift),
+    );
+    assert_eq!(
+        vlc_rtpmidi_metrics_layout_check(size, drift, has_drift),
+        success
+    );
+    // A header from before the last counter was appended
+    assert_eq!(
+        vlc_rtpmidi_metrics_layout_check(size - 8, drift - 8, has_drift - 8),
+        mismatch
+    );
+}
+
+// === Payload Validation Tests ===
+
+/// Payloads covering every event type, pass-through messages and malformed input
+fn payload_corpus() -> Vec<Vec<u8>> {
+    let mut good: Vec<Vec<u8>> = [
+        MidiEvent::MtcQuarter {
+            msg_type: 7,
+            value: 0x6,
+        },
+        MidiEvent::MtcFull {
+            hour: 23,
+            minute: 59,
+            second: 59,
+            frame: 29,
+        },
+        MidiEvent::Mmc(MmcCommand::Locate {
+            hour: 1,
+            minute: 2,
+            second: 3,
+            frame: 4,
+            subframe: 0,
+        }),
+        MidiEvent::Mmc(MmcCommand::Play),
+        MidiEvent::Mmc(MmcCommand::Stop),
+        MidiEvent::Mmc(MmcCommand::DeferredPlay),
+        MidiEvent::Mmc(MmcCommand::Reset),
+        MidiEvent::Mmc(MmcCommand::Shuttle {
+            speed: MmcSpeed::from_f32(-1.0),
+        }),
+        MidiEvent::Mmc(MmcCommand::VariablePlay {
+            speed: MmcSpeed::from_f32(0.5),
+        }),
+    ]
+    .iter()
+    .map(|event| master_netsync_flow(event).unwrap())
+    .collect();
+    good.extend([
+        vec![0x01, 0xF8],
+        vec![0x03, 0xF2, 0x10, 0x00],
+        vec![0x05, 0xF0, 0x7D, 0x01, 0x02, 0xF7],
+        vec![0x00],
+        vec![0x40, 0x00, 0x00, 0x01],
+    ]);
+
+    let mut corpus = vec![vec![], vec![0x05, 0xF0], vec![0x02, 0xF1, 0x80]];
+    for payload in &good {
+        // Every truncation and every single-byte corruption
+        for len in 0..payload.len() {
+            corpus.push(payload[..len].to_vec());
+        }
+        for i in 0..payload.len() {
+            for byte in [0x00, 0x7F, 0x80, 0xFF] {
+                let mut corrupted = payload.clone();
+                corrupted[i] = byte;
+                corpus.push(corrupted);
+            }
+        }
+        let mut trailing = payload.clone();
+        trailing.push(0x00);
+        corpus.push(trailing);
+    }
+    corpus.extend(good);
+    corpus
+}
+
+#[test]
+fn test_validate_payload_agrees_with_slave_flow() {
+    for payload in payload_corpus() {
+        let mut kind = i32::MAX;
+        let mut event = vlc_rtpmidi_create_mmc_play_event();
+        let (validated, parsed) = unsafe {
+            (
+                vlc_rtpmidi_validate_payload_ffi(payload.as_ptr(), payload.len(), &mut kind),
+                vlc_rtpmidi_slave_netsync_flow_ffi(payload.as_ptr(), payload.len(), &mut event),
+            )
+        };
+        assert_eq!(validated, parsed, "payload {:02X?}", payload);
+
+        let (_, _, _, raw_type) = slave_raw(&payload, 64);
+        match validated {
+            x if x == VlcRtpmidiErrorCode::Success as i32 => {
+                assert_eq!(kind, event.event_type as i32, "payload {:02X?}", payload);
+            }
+            x if x == VlcRtpmidiErrorCode::InvalidEventType as i32 => {
+                assert_eq!(kind, VlcRtpmidiEventType::Raw as i32);
+                assert_eq!(raw_type, VlcRtpmidiEventType::Raw);
+            }
+            _ => assert_eq!(kind, -1, "payload {:02X?}", payload),
+        }
+    }
+}
+
+#[test]
+fn test_validate_payload_null_pointers() {
+    let payload = [0x01, 0xF8];
+    let mut kind = 0;
+    unsafe {
+        assert_eq!(
+            vlc_rtpmidi_validate_payload_ffi(std::ptr::null(), 2, &mut kind),
+            VlcRtpmidiErrorCode::NullPointer as i32
+        );
+        assert_eq!(
+            vlc_rtpmidi_validate_payload_ffi(payload.as_ptr(), 2, std::ptr::null_mut()),
+            VlcRtpmidiErrorCode::NullPointer as i32
+        );
+    }
+}
+
+#[test]
+fn test_validate_event_agrees_with_master_flow() {
+    let mut buffer = [0u8; 32];
+    let mut size = 0;
+    for event_type in 0..=19u32 {
+        for data_len in 0..=9u8 {
+            let event = VlcRtpmidiEvent {
+                event_type,
+                data: [1, 2, 3, 4, 5, 0, 0, 0],
+                data_len,
+            };
+            let flow = unsafe {
+                vlc_rtpmidi_master_netsync_flow_ffi(
+                    &event,
+                    buffer.as_mut_ptr(),
+                    buffer.len(),
+                    &mut size,
+                )
+            };
+            let validated = unsafe { vlc_rtpmidi_validate_event_ffi(&event) };
+            // Events the flow rejects are rejected with the same code
+            if flow != VlcRtpmidiErrorCode::Success as i32 {
+                assert_eq!(validated, flow, "type {} len {}", event_type, data_len);
+            }
+            // Events passing validation are always sent
+            if validated == VlcRtpmidiErrorCode::Success as i32 {
+                assert_eq!(flow, validated, "type {} len {}", event_type, data_len);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_validate_event_checks_field_ranges() {
+    let success = VlcRtpmidiErrorCode::Success as i32;
+    let invalid_timecode = VlcRtpmidiErrorCode::InvalidTimecode as i32;
+    let validate = |event: VlcRtpmidiEvent| unsafe { vlc_rtpmidi_validate_event_ffi(&event) };
+
+    assert_eq!(
+        validate(vlc_rtpmidi_create_mtc_quarter_event(7, 15)),
+        success
+    );
+    assert_eq!(
+        validate(vlc_rtpmidi_create_mtc_full_event(23, 59, 59, 29)),
+        success
+    );
+    assert_eq!(
+        validate(vlc_rtpmidi_create_mmc_locate_event_sf(23, 59, 59, 29, 99)),
+        success
+    );
+    assert_eq!(
+        validate(vlc_rtpmidi_create_song_position_event(0x3FFF)),
+        success
+    );
+    assert_eq!(validate(vlc_rtpmidi_create_mmc_play_event()), success);
+
+    for event in [
+        vlc_rtpmidi_create_mtc_quarter_event(8, 0),
+        vlc_rtpmidi_create_mtc_quarter_event(0, 16),
+        vlc_rtpmidi_create_mtc_full_event(24, 0, 0, 0),
+        vlc_rtpmidi_create_mtc_full_event(0, 60, 0, 0),
+        vlc_rtpmidi_create_mtc_full_event(0, 0, 60, 0),
+        vlc_rtpmidi_create_mtc_full_event(0, 0, 0, 30),
+        vlc_rtpmidi_create_mmc_locate_event(255, 255, 255, 255),
+    ] {
+        assert_eq!(validate(event.clone()), invalid_timecode, "{:?}", event);
+
+        // The flow still sends it, masked
+        let mut buffer = [0u8; 16];
+        let mut size = 0;
+        let result = unsafe {
+            vlc_rtpmidi_master_netsync_flow_ffi(
+                &event,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &mut size,
+            )
+        };
+        assert_eq!(result, success);
+    }
+    validate(vlc_rtpmidi_create_mmc_locate_event(255, 255, 255, 255));
+    assert_eq!(
+        c_str(vlc_rtpmidi_get_last_error_detail()),
+        "timecode 255:255:255:255 is out of range at 30 fps"
+    );
+
+    // A hand-built Locate with a subframe past 99 is malformed, as for the flows
+    let mut locate = vlc_rtpmidi_create_mmc_locate_event_sf(0, 0, 0, 0, 0);
+    locate.data[4] = 100;
+    assert_eq!(
+        validate(locate),
+        VlcRtpmidiErrorCode::InvalidEventType as i32
+    );
+    assert_eq!(
+        validate(vlc_rtpmidi_create_song_position_event(0x4000)),
+        VlcRtpmidiErrorCode::InvalidEventType as i32
+    );
+    assert_eq!(
+        unsafe { vlc_rtpmidi_validate_event_ffi(std::ptr::null()) },
+        VlcRtpmidiErrorCode::NullPointer as i32
+    );
+}
+
+// === MIDI Codec Tests ===
+
+#[test]
+fn test_midi_codec_ffi_roundtrip() {
+    let events = [
+        vlc_rtpmidi_create_mtc_quarter_event(3, 0x9),
+        vlc_rtpmidi_create_mtc_full_event(1, 2, 3, 4),
+        vlc_rtpmidi_create_mmc_locate_event(5, 6, 7, 8),
+        vlc_rtpmidi_create_mmc_play_event(),
+        vlc_rtpmidi_create_mmc_eject_event(),
+    ];
+
+    // Back to back, as a serial port would deliver them
+    let mut stream = Vec::new();
+    for event in &events {
+        let mut midi = [0u8; 16];
+        let mut written = usize::MAX;
+        let result = unsafe {
+            vlc_rtpmidi_build_midi_ffi(event, midi.as_mut_ptr(), midi.len(), &mut written)
+        };
+        assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+        let payload = unsafe {
+            let mut payload = [0u8; 16];
+            let mut size = 0;
+            vlc_rtpmidi_master_netsync_flow_ffi(event, payload.as_mut_ptr(), 16, &mut size);
+            payload[1..size].to_vec()
+        };
+        assert_eq!(midi[..written], payload);
+        stream.extend_from_slice(&midi[..written]);
+    }
+
+    let mut offset = 0;
+    for expected in &events {
+        let mut event = vlc_rtpmidi_create_mmc_stop_event();
+        let mut consumed = 0;
+        let result = unsafe {
+            vlc_rtpmidi_parse_midi_ffi(
+                stream[offset..].as_ptr(),
+                stream.len() - offset,
+                &mut event,
+                &mut consumed,
+            )
+        };
+        assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+        assert_same_event(&event, expected);
+        offset += consumed;
+    }
+    assert_eq!(offset, stream.len());
+}
+
+#[test]
+fn test_midi_codec_ffi_errors() {
+    let mut event = vlc_rtpmidi_create_mmc_stop_event();
+    let mut consumed = usize::MAX;
+    unsafe {
+        // A Note On is well formed but has no event structure
+        let note_on = [0x90, 0x40, 0x7F];
+        assert_eq!(
+            vlc_rtpmidi_parse_midi_ffi(note_on.as_ptr(), note_on.len(), &mut event, &mut consumed),
+            VlcRtpmidiErrorCode::InvalidEventType as i32
+        );
+        assert_eq!(consumed, 3);
+        assert_eq!(event.event_type, VlcRtpmidiEventType::MmcStop as u32);
+
+        assert_eq!(
+            vlc_rtpmidi_parse_midi_ffi(note_on.as_ptr(), 0, &mut event, &mut consumed),
+            VlcRtpmidiErrorCode::InvalidMidi as i32
+        );
+        assert_eq!(consumed, 0);
+
+        let locate = vlc_rtpmidi_create_mmc_locate_event(0, 0, 1, 0);
+        let mut midi = [0u8; 4];
+        let mut written = 0;
+        assert_eq!(
+            vlc_rtpmidi_build_midi_ffi(&locate, midi.as_mut_ptr(), midi.len(), &mut written),
+            VlcRtpmidiErrorCode::BufferTooSmall as i32
+        );
+        assert_eq!(written, 13);
+        assert_eq!(
+            vlc_rtpmidi_build_midi_ffi(&locate, std::ptr::null_mut(), 0, &mut written),
+            VlcRtpmidiErrorCode::BufferTooSmall as i32
+        );
+
+        let mut invalid = vlc_rtpmidi_create_mmc_play_event();
+        invalid.data_len = 3;
+        assert_eq!(
+            vlc_rtpmidi_build_midi_ffi(&invalid, midi.as_mut_ptr(), midi.len(), &mut written),
+            VlcRtpmidiErrorCode::InvalidEventType as i32
+        );
+        assert_eq!(written, 0);
+    }
+}
+
+// === Event Accessor Tests ===
+
+/// Reads an event through the accessors, returning its type and data bytes
+fn event_fields(event: &VlcRtpmidiEvent) -> (VlcRtpmidiEventType, Vec<u8>) {
+    let mut event_type = VlcRtpmidiEventType::Raw;
+    let mut data = [0u8; 8];
+    let mut len = usize::MAX;
+    unsafe {
+        assert_eq!(
+            vlc_rtpmidi_event_get_type(event, &mut event_type),
+            VlcRtpmidiErrorCode::Success as i32
+        );
+        assert_eq!(
+            vlc_rtpmidi_event_get_data(event, data.as_mut_ptr(), data.len(), &mut len),
+            VlcRtpmidiErrorCode::Success as i32
+        );
+    }
+    (event_type, data[..len].to_vec())
+}
+
+#[test]
+fn test_event_setters_match_creators() {
+    let mut event = vlc_rtpmidi_create_mmc_stop_event();
+    let success = VlcRtpmidiErrorCode::Success as i32;
+    unsafe {
+        assert_eq!(
+            vlc_rtpmidi_event_set_mtc_quarter(&mut event, 7, 0x3),
+            success
+        );
+        assert_same_event(&event, &vlc_rtpmidi_create_mtc_quarter_event(7, 0x3));
+        assert_eq!(
+            event_fields(&event),
+            (VlcRtpmidiEventType::MtcQuarter, vec![7, 0x3])
+        );
+
+        assert_eq!(
+            vlc_rtpmidi_event_set_mtc_full(&mut event, 23, 59, 59, 29),
+            success
+        );
+        assert_same_event(&event, &vlc_rtpmidi_create_mtc_full_event(23, 59, 59, 29));
+
+        assert_eq!(
+            vlc_rtpmidi_event_set_mmc_locate(&mut event, 1, 2, 3, 4, 0),
+            success
+        );
+        assert_same_event(&event, &vlc_rtpmidi_create_mmc_locate_event(1, 2, 3, 4));
+        assert_eq!(
+            vlc_rtpmidi_event_set_mmc_locate(&mut event, 1, 2, 3, 4, 50),
+            success
+        );
+        assert_same_event(
+            &event,
+            &vlc_rtpmidi_create_mmc_locate_event_sf(1, 2, 3, 4, 50),
+        );
+        assert_eq!(
+            event_fields(&event),
+            (VlcRtpmidiEventType::MmcLocate, vec![1, 2, 3, 4, 50])
+        );
+
+        assert_eq!(
+            vlc_rtpmidi_event_set_mmc_command(&mut event, VlcRtpmidiEventType::MmcPause as u32),
+            success
+        );
+        assert_same_event(&event, &vlc_rtpmidi_create_mmc_pause_event());
+        assert_eq!(
+            event_fields(&event),
+            (VlcRtpmidiEventType::MmcPause, vec![])
+        );
+    }
+}
+
+#[test]
+fn test_event_setters_reject_out_of_range() {
+    let original = vlc_rtpmidi_create_mmc_play_event();
+    let mut event = original.clone();
+    let invalid_timecode = VlcRtpmidiErrorCode::InvalidTimecode as i32;
+    unsafe {
+        assert_eq!(
+            vlc_rtpmidi_event_set_mtc_quarter(&mut event, 8, 0),
+            invalid_timecode
+        );
+        assert_eq!(
+            vlc_rtpmidi_event_set_mtc_quarter(&mut event, 0, 16),
+            invalid_timecode
+        );
+        assert_eq!(
+            vlc_rtpmidi_event_set_mtc_full(&mut event, 24, 0, 0, 0),
+            invalid_timecode
+        );
+        assert_eq!(
+            vlc_rtpmidi_event_set_mtc_full(&mut event, 0, 0, 0, 30),
+            invalid_timecode
+        );
+        assert_eq!(
+            vlc_rtpmidi_event_set_mmc_locate(&mut event, 0, 60, 0, 0, 0),
+            invalid_timecode
+        );
+        assert_eq!(
+            vlc_rtpmidi_event_set_mmc_locate(&mut event, 0, 0, 0, 0, 100),
+            invalid_timecode
+        );
+        for event_type in [
+            VlcRtpmidiEventType::MtcFull as u32,
+            VlcRtpmidiEventType::MmcLocate as u32,
+            VlcRtpmidiEventType::Raw as u32,
+            14,
+        ] {
+            assert_eq!(
+                vlc_rtpmidi_event_set_mmc_command(&mut event, event_type),
+                VlcRtpmidiErrorCode::InvalidEventType as i32
+            );
+        }
+        // Failed setters leave the event untouched
+        assert_same_event(&event, &original);
+
+        assert_eq!(
+            vlc_rtpmidi_event_set_mtc_full(std::ptr::null_mut(), 0, 0, 0, 0),
+            VlcRtpmidiErrorCode::NullPointer as i32
+        );
+    }
+}
+
+#[test]
+fn test_event_getters_validate() {
+    let mut event = vlc_rtpmidi_create_mtc_full_event(1, 2, 3, 4);
+    let mut data = [0u8; 2];
+    let mut len = 0;
+    let mut event_type = VlcRtpmidiEventType::Raw;
+    unsafe {
+        assert_eq!(
+            vlc_rtpmidi_event_get_data(&event, data.as_mut_ptr(), data.len(), &mut len),
+            VlcRtpmidiErrorCode::BufferTooSmall as i32
+        );
+        assert_eq!(len, 4);
+        assert_eq!(
+            vlc_rtpmidi_event_get_data(&event, std::ptr::null_mut(), 0, &mut len),
+            VlcRtpmidiErrorCode::BufferTooSmall as i32
+        );
+
+        event.data_len = 9;
+        assert_eq!(
+            vlc_rtpmidi_event_get_data(&event, data.as_mut_ptr(), data.len(), &mut len),
+            VlcRtpmidiErrorCode::InvalidEventType as i32
+        );
+        assert_eq!(len, 0);
+
+        // An out-of-range type written from C
+        event.event_type = 99;
+        assert_eq!(
+            vlc_rtpmidi_event_get_type(&event, &mut event_type),
+            VlcRtpmidiErrorCode::InvalidEventType as i32
+        );
+        assert_eq!(event_type, VlcRtpmidiEventType::Raw);
+        assert_eq!(
+            vlc_rtpmidi_event_get_type(std::ptr::null(), &mut event_type),
+            VlcRtpmidiErrorCode::NullPointer as i32
+        );
+    }
+}
+
+#[test]
+fn test_event_type_try_from_raw() {
+    for raw in 0..=19 {
+        let event_type = VlcRtpmidiEventType::try_from(raw).unwrap();
+        assert_eq!(event_type as u32, raw);
+    }
+    for raw in [20, 99, u32::MAX] {
+        assert_eq!(
+            VlcRtpmidiEventType::try_from(raw),
+            Err(VlcRtpmidiErrorCode::InvalidEventType)
+        );
+
+        // Out-of-range values stored from C are rejected, not undefined behavior
+        let mut event = vlc_rtpmidi_create_mmc_play_event();
+        event.event_type = raw;
+        let mut buffer = [0u8; 16];
+        let mut actual_size = 0;
+        let result = unsafe {
+            vlc_rtpmidi_master_netsync_flow_ffi(
+                &event,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &mut actual_size,
+            )
+        };
+        assert_eq!(result, VlcRtpmidiErrorCode::InvalidEventType as i32);
+    }
+}
+
+// === Name String Tests ===
+
+/// Every error code, in numeric order
+const ALL_ERROR_CODES: [VlcRtpmidiErrorCode; 23] = [
+    VlcRtpmidiErrorCode::Success,
+    VlcRtpmidiErrorCode::InvalidMasterEvent,
+    VlcRtpmidiErrorCode::InvalidSlaveEvent,
+    VlcRtpmidiErrorCode::BufferTooSmall,
+    VlcRtpmidiErrorCode::NullPointer,
+    VlcRtpmidiErrorCode::InvalidEventType,
+    VlcRtpmidiErrorCode::NoEvent,
+    VlcRtpmidiErrorCode::InvalidTimecode,
+    VlcRtpmidiErrorCode::QuarterFrameOrder,
+    VlcRtpmidiErrorCode::Duplicate,
+    VlcRtpmidiErrorCode::OutOfOrder,
+    VlcRtpmidiErrorCode::ForeignSsrc,
+    VlcRtpmidiErrorCode::InvalidFrameRate,
+    VlcRtpmidiErrorCode::InvalidDeviceId,
+    VlcRtpmidiErrorCode::LayoutMismatch,
+    VlcRtpmidiErrorCode::InvalidMidi,
+    VlcRtpmidiErrorCode::WouldBlock,
+    VlcRtpmidiErrorCode::TruncatedPayload,
+    VlcRtpmidiErrorCode::HeaderMismatch,
+    VlcRtpmidiErrorCode::MalformedSysex,
+    VlcRtpmidiErrorCode::ValueOutOfRange,
+    VlcRtpmidiErrorCode::MalformedTimecode,
+    VlcRtpmidiErrorCode::SessionFailed,
+];
+
+fn c_str(ptr: *const std::ffi::c_char) -> &'static str {
+    unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()
+}
+
+#[test]
+fn test_every_error_code_has_a_message() {
+    for (raw, code) in ALL_ERROR_CODES.iter().enumerate() {
+        assert_eq!(*code as usize, raw);
+        let message = c_str(vlc_rtpmidi_get_error_message(*code as i32));
+        assert!(!message.is_empty());
+        assert_ne!(message, "Unknown error", "{:?}", code);
+    }
+    // A code past the list means a variant is missing from it
+    assert_eq!(
+        c_str(vlc_rtpmidi_get_error_message(ALL_ERROR_CODES.len() as i32)),
+        "Unknown error"
+    );
+    assert_eq!(c_str(vlc_rtpmidi_get_error_message(-1)), "Unknown error");
+}
+
+#[test]
+fn test_every_event_type_has_a_name() {
+    let mut names = Vec::new();
+    for raw in 0..=19 {
+        let event_type = VlcRtpmidiEventType::try_from(raw).unwrap();
+        let name = c_str(vlc_rtpmidi_get_event_type_name(event_type as i32));
+        assert_ne!(name, "UNKNOWN", "{:?}", event_type);
+        names.push(name);
+    }
+    assert_eq!(names[0], "MTC_QUARTER");
+    assert_eq!(names[4], "MMC_LOCATE");
+    assert_eq!(names[13], "MMC_RESET");
+    assert_eq!(names[14], "SONG_POSITION");
+    assert_eq!(names[18], "REALTIME_STOP");
+    assert_eq!(names[19], "MMC_VARIABLE_PLAY");
+    names.sort();
+    names.dedup();
+    assert_eq!(names.len(), 20);
+
+    for raw in [20, 99, -1, i32::MIN, i32::MAX] {
+        assert_eq!(c_str(vlc_rtpmidi_get_event_type_name(raw)), "UNKNOWN");
+    }
+}
+
+/// Describes `payload` into a buffer of `cap` bytes, returning the code, the text
+/// and the reported length
+fn describe(payload: &[u8], cap: usize) -> (i32, String, usize) {
+    let mut out = vec![0x55u8; cap];
+    let mut out_len = usize::MAX;
+    let result = unsafe {
+        vlc_rtpmidi_describe_payload_ffi(
+            payload.as_ptr(),
+            payload.len(),
+            out.as_mut_ptr().cast(),
+            cap,
+            &mut out_len,
+        )
+    };
+    let text = match out.iter().position(|&b| b == 0) {
+        Some(end) => String::from_utf8(out[..end].to_vec()).unwrap(),
+        None => String::new(),
+    };
+    (result, text, out_len)
+}
+
+#[test]
+fn test_describe_payload_ffi_matches_rust() {
+    let payload = master_netsync_flow(&MidiEvent::MtcFull {
+        hour: 1,
+        minute: 23,
+        second: 45,
+        frame: 10,
+    })
+    .unwrap();
+    let expected = describe_payload(&payload);
+
+    let (result, text, len) = describe(&payload, 256);
+    assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+    assert_eq!(text, expected);
+    assert_eq!(len, expected.len());
+    // Exactly enough room for the terminator
+    let (result, text, _) = describe(&payload, expected.len() + 1);
+    assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+    assert_eq!(text, expected);
+
+    let (result, text, len) = describe(&[], 64);
+    assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+    assert_eq!(text, "Empty payload");
+    assert_eq!(len, text.len());
+}
+
+#[test]
+fn test_describe_payload_ffi_truncates_with_ellipsis() {
+    let payload = [0x05, 0xF1];
+    let expected = describe_payload(&payload);
+    for cap in 1..=expected.len() {
+        let (result, text, len) = describe(&payload, cap);
+        assert_eq!(
+            result,
+            VlcRtpmidiErrorCode::BufferTooSmall as i32,
+            "{}",
+            cap
+        );
+        assert_eq!(len, expected.len());
+        assert_eq!(text.len(), cap - 1);
+        let kept = text.trim_end_matches('.');
+        assert!(expected.starts_with(kept), "{:?}", text);
+        assert!(cap < 4 || text.ends_with("..."), "{:?}", text);
+    }
+
+    let mut out_len = 0;
+    assert_eq!(
+        unsafe {
+            vlc_rtpmidi_describe_payload_ffi(
+                payload.as_ptr(),
+                payload.len(),
+                std::ptr::null_mut(),
+                0,
+                &mut out_len,
+            )
+        },
+        VlcRtpmidiErrorCode::BufferTooSmall as i32
+    );
+    assert_eq!(out_len, expected.len());
+}
+
+#[test]
+fn test_describe_payload_ffi_bounds_its_input() {
+    let garbage: Vec<u8> = (0..10_000u32).map(|i| (i * 37) as u8).collect();
+    let (result, text, len) = describe(&garbage, 64 * 1024);
+    assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+    assert_eq!(len, text.len());
+    assert!(text.ends_with("\n(5903 more bytes not shown)"), "{}", text);
+
+    let mut out = [0 as std::ffi::c_char; 8];
+    let mut out_len = 0;
+    unsafe {
+        assert_eq!(
+            vlc_rtpmidi_describe_payload_ffi(
+                std::ptr::null(),
+                1,
+                out.as_mut_ptr(),
+                out.len(),
+                &mut out_len
+            ),
+            VlcRtpmidiErrorCode::NullPointer as i32
+        );
+        assert_eq!(
+            vlc_rtpmidi_describe_payload_ffi(
+                garbage.as_ptr(),
+                1,
+                std::ptr::null_mut(),
+                8,
+                &mut out_len
+            ),
+            VlcRtpmidiErrorCode::NullPointer as i32
+        );
+        assert_eq!(
+            vlc_rtpmidi_describe_payload_ffi(
+                garbage.as_ptr(),
+                1,
+                out.as_mut_ptr(),
+                out.len(),
+                std::ptr::null_mut()
+            ),
+            VlcRtpmidiErrorCode::NullPointer as i32
+        );
+    }
+}
+
+// === Stream Parser Tests ===
+
+/// Feeds `chunks` in order, polling after each one; returns the events and the
+/// error codes
+fn stream_parse(
+    parser: *mut VlcRtpmidiStreamParser,
+    chunks: &[&[u8]],
+) -> (Vec<(VlcRtpmidiEventType, Vec<u8>)>, Vec<i32>) {
+    let mut events = Vec::new();
+    let mut codes = Vec::new();
+    for chunk in chunks {
+        let result = unsafe { vlc_rtpmidi_stream_parser_feed(parser, chunk.as_ptr(), chunk.len()) };
+        assert_eq!(result, VlcRtpmidiErrorCode::Success as i32);
+        loop {
+            let mut event = vlc_rtpmidi_create_mmc_stop_event();
+            match unsafe { vlc_rtpmidi_stream_parser_poll(parser, &mut event) } {
+                code if code == VlcRtpmidiErrorCode::WouldBlock as i32 => break,
+                code if code == VlcRtpmidiErrorCode::Success as i32 => {
+                    events.push(event_fields(&event))
+                }
+                code => codes.push(code),
+            }
+        }
+    }
+    (events, codes)
+}
+
+#[test]
+fn test_stream_parser_ffi_split_at_every_boundary() {
+    let expected = [
+        vlc_rtpmidi_create_mtc_quarter_event(1, 5),
+        vlc_rtpmidi_create_mmc_locate_event(1, 2, 3, 4),
+        vlc_rtpmidi_create_mmc_play_event(),
+    ];
+    let mut stream = Vec::new();
+    for event in &expected {
+        let mut buffer = [0u8; 16];
+        let mut len = 0;
+        unsafe {
+            vlc_rtpmidi_master_netsync_flow_ffi(event, buffer.as_mut_ptr(), buffer.len(), &mut len)
+        };
+        stream.extend_from_slice(&buffer[..len]);
+    }
+    // A note-on payload between the Locate and the Play
+    stream.splice(17..17, [0x03, 0x90, 0x3C, 0x7F]);
+
+    for split in 0..=stream.len() {
+        let parser = vlc_rtpmidi_stream_parser_new(32);
+        assert!(!parser.is_null());
+        let (head, tail) = stream.split_at(split);
+        let (events, codes) = stream_parse(parser, &[head, tail]);
+        assert_eq!(
+            events,
+            expected.iter().map(event_fields).collect::<Vec<_>>(),
+            "split at {}",
+            split
+        );
+        assert_eq!(codes, [VlcRtpmidiErrorCode::InvalidEventType as i32]);
+        unsafe { vlc_rtpmidi_stream_parser_free(parser) };
+    }
+}
+
+#[test]
+fn test_stream_parser_ffi_buffer_is_bounded() {
+    let parser = vlc_rtpmidi_stream_parser_new(0);
+    let garbage = [0x7Fu8; 64];
+    unsafe {
+        // Raised to the largest payload
+        assert_eq!(
+            vlc_rtpmidi_stream_parser_feed(parser, garbage.as_ptr(), 15),
+            VlcRtpmidiErrorCode::BufferTooSmall as i32
+        );
+        assert_eq!(
+            vlc_rtpmidi_stream_parser_feed(parser, garbage.as_ptr(), 14),
+            VlcRtpmidiErrorCode::Success as i32
+        );
+        assert_eq!(
+            vlc_rtpmidi_stream_parser_feed(parser, garbage.as_ptr(), 1),
+            VlcRtpmidiErrorCode::BufferTooSmall as i32
+        );
+        // Polling discards the garbage and makes room again
+        let mut event = vlc_rtpmidi_create_mmc_stop_event();
+        assert_eq!(
+            vlc_rtpmidi_stream_parser_poll(parser, &mut event),
+            VlcRtpmidiErrorCode::WouldBlock as i32
+        );
+        assert_eq!(
+            vlc_rtpmidi_stream_parser_feed(parser, [0x01, 0xFC].as_ptr(), 2),
+            VlcRtpmidiErrorCode::Success as i32
+        );
+        assert_eq!(
+            vlc_rtpmidi_stream_parser_poll(parser, &mut event),
+            VlcRtpmidiErrorCode::Success as i32
+        );
+        assert_eq!(
+            event_fields(&event),
+            event_fields(&vlc_rtpmidi_create_stop_rt_event())
+        );
+        vlc_rtpmidi_stream_parser_free(parser);
+    }
+}
+
+#[test]
+fn test_stream_parser_ffi_null_pointers() {
+    let parser = vlc_rtpmidi_stream_parser_new(64);
+    let mut event = vlc_rtpmidi_create_mmc_stop_event();
+    let null_pointer = VlcRtpmidiErrorCode::NullPointer as i32;
+    unsafe {
+        assert_eq!(
+            vlc_rtpmidi_stream_parser_feed(std::ptr::null_mut(), [0x01].as_ptr(), 1),
+            null_pointer
+        );
+        assert_eq!(
+            vlc_rtpmidi_stream_parser_feed(parser, std::ptr::null(), 1),
+            null_pointer
+        );
+        assert_eq!(
+            vlc_rtpmidi_stream_parser_feed(parser, std::ptr::null(), 0),
+            VlcRtpmidiErrorCode::Success as i32
+        );
+        assert_eq!(
+            vlc_rtpmidi_stream_parser_poll(std::ptr::null_mut(), &mut event),
+            null_pointer
+        );
+        assert_eq!(
+            vlc_rtpmidi_stream_parser_poll(parser, std::ptr::null_mut()),
+            null_pointer
+        );
+        vlc_rtpmidi_stream_parser_free(parser);
+        vlc_rtpmidi_stream_parser_free(std::ptr::null_mut());
+    }
+}
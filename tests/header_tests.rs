@@ -0,0 +1,105 @@
+use std::path::Path;
+
+/// Prefixes cbindgen gives enum variants, and the ones the C API has always used
+const VARIANT_PREFIXES: [(&str, &str); 3] = [
+    ("VLC_RTPMIDI_EVENT_TYPE_", "VLC_RTPMIDI_EVENT_"),
+    ("VLC_RTPMIDI_ERROR_CODE_", "VLC_RTPMIDI_ERROR_"),
+    ("VLC_RTPMIDI_FRAME_RATE_FPS", "VLC_RTPMIDI_FRAME_RATE_"),
+];
+
+/// Generates the C header from src/ffi.rs with the settings of cbindgen.toml
+fn generate_header() -> String {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let config = cbindgen::Config::from_file(root.join("cbindgen.toml")).unwrap();
+    let bindings = cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(root.join("src/ffi.rs"))
+        .generate()
+        .unwrap();
+
+    let mut out = Vec::new();
+    bindings.write(&mut out);
+    let mut header = String::from_utf8(out).unwrap();
+    for (generated, published) in VARIANT_PREFIXES {
+        header = header.replace(generated, published);
+    }
+    header
+}
+
+/// Fails when include/rtp_midi_netsync.h is out of date; run with UPDATE_HEADER=1
+/// to regenerate it
+#[test]
+fn test_header_matches_ffi() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("include/rtp_midi_netsync.h");
+    let generated = generate_header();
+    if std::env::var_os("UPDATE_HEADER").is_some() {
+        std::fs::write(&path, &generated).unwrap();
+        return;
+    }
+
+    let checked_in = std::fs::read_to_string(&path).unwrap();
+    assert!(
+        checked_in == generated,
+        "include/rtp_midi_netsync.h differs from src/ffi.rs, regenerate it with \
+         `UPDATE_HEADER=1 cargo test --test header_tests`"
+    );
+}
+
+#[test]
+fn test_header_enum_values() {
+    let header = generate_header();
+    for line in [
+        "VLC_RTPMIDI_EVENT_MTC_QUARTER = 0,",
+        "VLC_RTPMIDI_EVENT_MMC_RESET = 13,",
+        "VLC_RTPMIDI_ERROR_SUCCESS = 0,",
+        "VLC_RTPMIDI_ERROR_NO_EVENT = 6,",
+        "VLC_RTPMIDI_ERROR_LAYOUT_MISMATCH = 14,",
+        "VLC_RTPMIDI_FRAME_RATE_2997_DF = 2,",
+        "VLC_RTPMIDI_LOG_LEVEL_ERROR = 3,",
+        "#define VLC_RTPMIDI_MAX_TICK_PACKETS 2",
+    ] {
+        assert!(header.contains(line), "missing `{}`", line);
+    }
+}
+
+/// Every exported name must carry the library prefix, so the C symbols cannot
+/// collide with other libraries linked into the same process
+#[test]
+fn test_header_names_are_prefixed() {
+    let header = generate_header();
+    let mut checked = 0;
+    for line in header.lines() {
+        let name = if let Some(rest) = line.strip_prefix("#define ") {
+            rest.split_whitespace().next().unwrap()
+        } else if let Some(rest) = line.strip_prefix("} ") {
+            rest.trim_end_matches(';')
+        } else if line.starts_with(|c: char| c.is_ascii_alphabetic()) && line.contains('(') {
+            let before = &line[..line.find('(').unwrap()];
+            before.rsplit([' ', '*']).next().unwrap()
+        } else {
+            continue;
+        };
+        if name == "RTP_MIDI_NETSYNC_H" || name.is_empty() || name.trim_start().starts_with("//") {
+            continue;
+        }
+        if let Some(pos) = line.find("(*") {
+            let callback = &line[pos + 2..line[pos..].find(')').unwrap() + pos];
+            assert!(
+                callback.starts_with("VlcRtpmidi"),
+                "unprefixed `{}`",
+                callback
+            );
+            checked += 1;
+            continue;
+        }
+        assert!(
+            name.starts_with("vlc_rtpmidi_")
+                || name.starts_with("VlcRtpmidi")
+                || name.starts_with("VLC_RTPMIDI_"),
+            "unprefixed `{}`",
+            name
+        );
+        checked += 1;
+    }
+    assert!(checked > 50, "only {} names found", checked);
+}
@@ -0,0 +1,53 @@
+use rtp_midi_netsync::util::{hexdump, hexdump_lines, HEXDUMP_LINE_LENGTH};
+
+const FULL_FRAME: [u8; 10] = [0xF0, 0x7F, 0x7F, 0x01, 0x01, 0x01, 0x17, 0x2D, 0x0A, 0xF7];
+
+// === Single line ===
+
+#[test]
+fn test_hexdump_snapshots() {
+    let cases: [(&[u8], usize, &str); 7] = [
+        (&[], 16, ""),
+        (&[], 0, ""),
+        (&[0x00], 16, "00"),
+        (&FULL_FRAME, 16, "F0 7F 7F 01 01 01 17 2D 0A F7"),
+        (&FULL_FRAME, 10, "F0 7F 7F 01 01 01 17 2D 0A F7"),
+        (&FULL_FRAME, 5, "F0 7F 7F 01 01 … (+5 bytes)"),
+        (&FULL_FRAME, 0, "… (+10 bytes)"),
+    ];
+    for (bytes, max_len, expected) in cases {
+        assert_eq!(hexdump(bytes, max_len).to_string(), expected);
+    }
+}
+
+#[test]
+fn test_hexdump_respects_formatter_target() {
+    use std::fmt::Write;
+
+    let mut out = String::from("payload: ");
+    write!(out, "{}", hexdump(&[0x90, 0x3C, 0x7F], usize::MAX)).unwrap();
+    assert_eq!(out, "payload: 90 3C 7F");
+}
+
+// === Multi-line ===
+
+#[test]
+fn test_hexdump_lines_snapshots() {
+    assert_eq!(hexdump_lines(&[]).to_string(), "");
+    assert_eq!(
+        hexdump_lines(&FULL_FRAME).to_string(),
+        "0000  F0 7F 7F 01 01 01 17 2D 0A F7"
+    );
+
+    let bytes: Vec<u8> = (0..=0x21).collect();
+    assert_eq!(
+        hexdump_lines(&bytes).to_string(),
+        "0000  00 01 02 03 04 05 06 07 08 09 0A 0B 0C 0D 0E 0F\n\
+         0010  10 11 12 13 14 15 16 17 18 19 1A 1B 1C 1D 1E 1F\n\
+         0020  20 21"
+    );
+    // A full last line has no trailing newline or empty line
+    let lines = hexdump_lines(&bytes[..2 * HEXDUMP_LINE_LENGTH]).to_string();
+    assert_eq!(lines.lines().count(), 2);
+    assert!(lines.ends_with("1E 1F"));
+}
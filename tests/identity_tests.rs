@@ -0,0 +1,162 @@
+use rtp_midi_netsync::midi::{DeviceIdentity, ManufacturerId, MidiEvent};
+use rtp_midi_netsync::netsync::{
+    master_netsync_flow, slave_netsync_flow, Endpoint, MasterSession, NetsyncConfig,
+    NetsyncHandler, PacketStatus, SlaveSession,
+};
+use rtp_midi_netsync::rtp::RtpHeader;
+
+#[derive(Default)]
+struct Recorder {
+    requests: Vec<u8>,
+    replies: Vec<DeviceIdentity>,
+}
+
+impl NetsyncHandler for Recorder {
+    fn on_identity_request(&mut self, device_id: u8) {
+        self.requests.push(device_id);
+    }
+
+    fn on_identity_reply(&mut self, identity: DeviceIdentity) {
+        self.replies.push(identity);
+    }
+}
+
+fn identity(device_id: u8, manufacturer: ManufacturerId) -> DeviceIdentity {
+    DeviceIdentity {
+        device_id,
+        manufacturer,
+        family: 0x0102,
+        model: 0x0304,
+        version: [0, 1, 2, 3],
+    }
+}
+
+fn slave(ssrc: u32, identity: Option<DeviceIdentity>) -> SlaveSession {
+    let mut slave = SlaveSession::with_config(NetsyncConfig {
+        ssrc,
+        ..NetsyncConfig::default()
+    });
+    slave.set_identity(identity);
+    slave
+}
+
+#[test]
+fn test_master_discovers_slaves() {
+    let mut master = MasterSession::new();
+    let mut slaves = [
+        slave(1, Some(identity(0x01, ManufacturerId::Short(0x41)))),
+        slave(
+            2,
+            Some(identity(0x02, ManufacturerId::Extended([0x20, 0x33]))),
+        ),
+        slave(3, None),
+    ];
+
+    let request = master.discover(0);
+    let (_, payload) = RtpHeader::parse(&request).unwrap();
+    assert_eq!(
+        slave_netsync_flow(payload),
+        Ok(MidiEvent::IdentityRequest { device_id: 0x7F })
+    );
+
+    for slave in &mut slaves {
+        let mut recorder = Recorder::default();
+        assert_eq!(
+            slave.feed_packet(&request, 0, &mut recorder),
+            Ok(PacketStatus::Accepted)
+        );
+        assert_eq!(recorder.requests, [0x7F]);
+        if let Some(packet) = slave.identity_reply_to_send(1_000) {
+            master.handle_identity_reply(DeviceIdentity::from_packet(&packet).unwrap());
+        }
+        // Answered once per request
+        assert_eq!(slave.identity_reply_to_send(2_000), None);
+    }
+    assert_eq!(
+        master.discovered(),
+        [
+            identity(0x01, ManufacturerId::Short(0x41)),
+            identity(0x02, ManufacturerId::Extended([0x20, 0x33])),
+        ]
+    );
+
+    // Duplicate replies are recorded once, and a new discovery starts over
+    master.handle_identity_reply(identity(0x01, ManufacturerId::Short(0x41)));
+    assert_eq!(master.discovered().len(), 2);
+    master.discover(3_000);
+    assert!(master.discovered().is_empty());
+}
+
+#[test]
+fn test_slave_answers_its_own_device_id() {
+    let mut slave = slave(1, Some(identity(0x05, ManufacturerId::Short(0x41))));
+    let mut master = MasterSession::new();
+    for (device_id, answered) in [(0x04, false), (0x05, true), (0x7F, true)] {
+        let packet = master
+            .packet_for_event(&MidiEvent::IdentityRequest { device_id }, 0)
+            .unwrap();
+        slave
+            .feed_packet(&packet, 0, &mut Recorder::default())
+            .unwrap();
+        assert_eq!(
+            slave.identity_reply_to_send(0).is_some(),
+            answered,
+            "{:02X}",
+            device_id
+        );
+    }
+}
+
+#[test]
+fn test_reply_payloads() {
+    // 15 bytes fit the one-octet header, 17 need the two-octet form
+    let short = identity(0x01, ManufacturerId::Short(0x41));
+    let payload = short.to_payload();
+    assert_eq!(payload[0], 0x0F);
+    assert_eq!(
+        slave_netsync_flow(&payload),
+        Ok(MidiEvent::IdentityReply(short))
+    );
+
+    let extended = identity(0x02, ManufacturerId::Extended([0x20, 0x33]));
+    let payload = extended.to_payload();
+    assert_eq!(payload[..2], [0x80, 0x11]);
+    assert_eq!(DeviceIdentity::from_payload(&payload), Some(extended));
+
+    // Replies are not sent by the master flow
+    assert!(master_netsync_flow(&MidiEvent::IdentityReply(short)).is_err());
+    assert_eq!(
+        DeviceIdentity::from_payload(
+            &master_netsync_flow(&MidiEvent::IdentityRequest { device_id: 0x7F }).unwrap()
+        ),
+        None
+    );
+}
+
+#[test]
+fn test_endpoints_discover_each_other() {
+    let mut a = Endpoint::with_config(NetsyncConfig {
+        ssrc: 1,
+        ..NetsyncConfig::default()
+    });
+    let mut b = Endpoint::with_config(NetsyncConfig {
+        ssrc: 2,
+        ..NetsyncConfig::default()
+    });
+    let identity = identity(0x02, ManufacturerId::Extended([0x20, 0x33]));
+    b.slave_mut().set_identity(Some(identity));
+
+    let request = a.master_mut().discover(0);
+    let mut recorder = Recorder::default();
+    assert_eq!(
+        b.receive_packet(&request, 0, &mut recorder),
+        Ok(PacketStatus::Accepted)
+    );
+    let replies = b.tick(1_000);
+    assert_eq!(replies.len(), 1);
+    assert_eq!(
+        a.receive_packet(&replies[0], 2_000, &mut recorder),
+        Ok(PacketStatus::Feedback)
+    );
+    assert_eq!(a.master().discovered(), [identity]);
+}
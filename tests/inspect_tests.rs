@@ -0,0 +1,217 @@
+#![cfg(feature = "inspect")]
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::UdpSocket;
+use std::process::{Command, Output, Stdio};
+
+use rtp_midi_netsync::netsync::net::blocking::Sender;
+use rtp_midi_netsync::netsync::{
+    describe_payload, payload_len, slave_netsync_flow_with_policy, NetsyncConfig, ParsePolicy,
+};
+use rtp_midi_netsync::testing::vectors::VECTORS;
+
+fn inspect() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_netsync-inspect"))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02X}", byte)).collect()
+}
+
+fn run(args: &[String]) -> Output {
+    inspect().args(args).output().unwrap()
+}
+
+/// Whether the text output shows `payload`'s description, indented under its entry
+fn describes(stdout: &str, payload: &[u8]) -> bool {
+    describe_payload(payload)
+        .lines()
+        .all(|line| stdout.contains(&format!("  {}\n", line)))
+}
+
+#[test]
+fn test_vectors_are_described() {
+    let args: Vec<String> = VECTORS.iter().map(|vector| hex(vector.payload)).collect();
+    let output = run(&args);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    for (index, vector) in VECTORS.iter().enumerate() {
+        assert!(
+            stdout.contains(&format!("#{}: ", index)),
+            "{}: missing entry",
+            vector.name
+        );
+        assert!(
+            describes(&stdout, vector.payload),
+            "{}: missing description",
+            vector.name
+        );
+    }
+}
+
+#[test]
+fn test_json_matches_policy() {
+    for policy in [ParsePolicy::Strict, ParsePolicy::Lenient] {
+        let mut args = vec![format!("--{:?}", policy).to_lowercase(), "--json".into()];
+        args.extend(VECTORS.iter().map(|vector| hex(vector.payload)));
+        let output = run(&args);
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let lines: Vec<serde_json::Value> = stdout
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), VECTORS.len());
+
+        let mut all_accepted = true;
+        for (line, vector) in lines.iter().zip(VECTORS) {
+            let accepted = slave_netsync_flow_with_policy(vector.payload, policy).is_ok();
+            all_accepted &= accepted;
+            assert_eq!(line["accepted"], accepted, "{}", vector.name);
+            assert_eq!(
+                line["description"],
+                describe_payload(vector.payload),
+                "{}",
+                vector.name
+            );
+            assert_eq!(line["error"].is_null(), accepted, "{}", vector.name);
+        }
+        assert_eq!(output.status.success(), all_accepted, "{:?}", policy);
+    }
+}
+
+#[test]
+fn test_strict_rejects_what_lenient_accepts() {
+    // Non-broadcast MMC device ID
+    let vector = VECTORS
+        .iter()
+        .find(|vector| vector.name == "rtpmidi_mmc_play_device_16")
+        .unwrap();
+    let lenient = run(&["--lenient".into(), hex(vector.payload)]);
+    assert!(lenient.status.success());
+    assert!(String::from_utf8(lenient.stdout)
+        .unwrap()
+        .contains("accepted: "));
+    let strict = run(&["--strict".into(), hex(vector.payload)]);
+    assert_eq!(strict.status.code(), Some(1));
+    assert!(String::from_utf8(strict.stdout)
+        .unwrap()
+        .contains("rejected: Strict policy"));
+}
+
+#[test]
+fn test_file_of_concatenated_payloads() {
+    // Payloads with a journal cannot be split by their LEN header
+    let vectors: Vec<_> = VECTORS
+        .iter()
+        .filter(|vector| payload_len(vector.payload) == Some(vector.payload.len()))
+        .collect();
+    let bytes: Vec<u8> = vectors
+        .iter()
+        .flat_map(|vector| vector.payload.iter().copied())
+        .collect();
+    let path = std::env::temp_dir().join(format!("netsync-inspect-{}.bin", std::process::id()));
+    std::fs::write(&path, &bytes).unwrap();
+
+    let output = run(&["--json".into(), "--file".into(), path.display().to_string()]);
+    std::fs::remove_file(&path).unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let payloads: Vec<String> = stdout
+        .lines()
+        .map(|line| {
+            let line: serde_json::Value = serde_json::from_str(line).unwrap();
+            line["payload"].as_str().unwrap().to_uppercase()
+        })
+        .collect();
+    let expected: Vec<String> = vectors.iter().map(|vector| hex(vector.payload)).collect();
+    assert_eq!(payloads, expected);
+}
+
+#[test]
+fn test_truncated_file_is_an_input_error() {
+    let path = std::env::temp_dir().join(format!(
+        "netsync-inspect-truncated-{}.bin",
+        std::process::id()
+    ));
+    std::fs::write(&path, [0x01, 0xF8, 0x03, 0xF2]).unwrap();
+    let output = run(&["--file".into(), path.display().to_string()]);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("truncated payload at byte 2"));
+}
+
+#[test]
+fn test_hex_lines_from_stdin() {
+    let mut child = inspect()
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    writeln!(stdin, "# comment").unwrap();
+    for vector in VECTORS.iter().take(3) {
+        let spaced: Vec<String> = vector
+            .payload
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        writeln!(stdin, "{}", spaced.join(" ")).unwrap();
+        writeln!(stdin).unwrap();
+    }
+    drop(stdin);
+    let output = child.wait_with_output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    for vector in VECTORS.iter().take(3) {
+        assert!(describes(&stdout, vector.payload));
+    }
+    assert!(!stdout.contains("#3: "));
+}
+
+#[test]
+fn test_usage_errors() {
+    assert_eq!(run(&["--verbose".into()]).status.code(), Some(2));
+    assert_eq!(run(&["0F0".into()]).status.code(), Some(2));
+    assert_eq!(
+        run(&["--listen".into(), "not-a-port".into()]).status.code(),
+        Some(2)
+    );
+}
+
+#[test]
+fn test_listen_decodes_live_traffic() {
+    let port = UdpSocket::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let mut child = inspect()
+        .args(["--json", "--listen", &port.to_string(), "--count", "2"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // Wait until the socket is bound
+    let mut stderr = BufReader::new(child.stderr.take().unwrap());
+    let mut line = String::new();
+    stderr.read_line(&mut line).unwrap();
+    assert!(line.starts_with("listening on "), "{}", line);
+
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    socket.connect(("127.0.0.1", port)).unwrap();
+    let sender = Sender::new(socket, NetsyncConfig::default()).unwrap();
+    sender.play().unwrap();
+    sender.stop().unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    let events: Vec<serde_json::Value> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0]["event"], "Play");
+    assert_eq!(events[1]["event"], "Stop");
+    assert_eq!(events[1]["metrics"]["sequence_gaps"], 0);
+}
@@ -0,0 +1,205 @@
+use std::ops::Range;
+
+use rtp_midi_netsync::error::NetsyncError;
+use rtp_midi_netsync::midi::{MidiEvent, MmcCommand};
+use rtp_midi_netsync::mtc::{smpte_to_quarter_frames, smpte_to_us, us_to_smpte, MtcFullFrame};
+use rtp_midi_netsync::netsync::{
+    FullFrameRefreshPolicy, MasterSession, NetsyncHandler, SlaveFeedback, SlaveSession,
+};
+use rtp_midi_netsync::testing::{LossyChannel, LossyConfig};
+
+/// Quarter frames per second at 30fps
+const QUARTER_FRAMES_PER_SECOND: u64 = 120;
+
+const START: MtcFullFrame = MtcFullFrame {
+    hours: 1,
+    minutes: 0,
+    seconds: 0,
+    frames: 0,
+};
+
+/// Time given to the slave to lock before its error is measured
+const SETTLE_US: u64 = 1_000_000;
+
+struct IgnoreEvents;
+
+impl NetsyncHandler for IgnoreEvents {}
+
+/// Result of a simulated session
+struct Run {
+    /// Largest distance between the slave's estimate and the master position
+    max_error_us: u64,
+    slave: SlaveSession,
+    channel: LossyChannel,
+}
+
+/// Runs a master rolling from [`START`] for `duration_us`, sending quarter frames
+/// through a channel applying `config` to a slave. Packets sent during `blackout`
+/// are all lost. Feedback from the slave reaches the master without loss.
+fn simulate(config: LossyConfig, duration_us: u64, blackout: Range<u64>) -> Run {
+    let start_us = smpte_to_us(&START);
+    let mut master = MasterSession::with_policy(FullFrameRefreshPolicy {
+        full_frame_interval_us: Some(500_000),
+        quarter_frame_cycles: None,
+    });
+    let mut slave = SlaveSession::new();
+    let mut channel = LossyChannel::new(config);
+    let mut max_error_us = 0;
+
+    let transmit = |channel: &mut LossyChannel, packet: &[u8], now_us: u64| {
+        if !blackout.contains(&now_us) {
+            channel.send(packet);
+        }
+    };
+    let locate = MidiEvent::Mmc(MmcCommand::Locate {
+        hour: START.hours,
+        minute: START.minutes,
+        second: START.seconds,
+        frame: START.frames,
+        subframe: 0,
+    });
+    for event in [locate, MidiEvent::Mmc(MmcCommand::Play)] {
+        let packet = master.packet_for_event(&event, 0).unwrap();
+        transmit(&mut channel, &packet, 0);
+    }
+
+    for piece in 0.. {
+        let now_us = piece * 1_000_000 / QUARTER_FRAMES_PER_SECOND;
+        if now_us > duration_us {
+            break;
+        }
+        let cycle_us = piece / 8 * 8 * 1_000_000 / QUARTER_FRAMES_PER_SECOND;
+        let qf = smpte_to_quarter_frames(&us_to_smpte(start_us + cycle_us))[(piece % 8) as usize];
+        let event = MidiEvent::MtcQuarter {
+            msg_type: qf.frame_type,
+            value: qf.value,
+        };
+        let packet = master.packet_for_event(&event, now_us).unwrap();
+        transmit(&mut channel, &packet, now_us);
+        for packet in master.tick(now_us) {
+            transmit(&mut channel, &packet, now_us);
+        }
+
+        while let Some(packet) = channel.recv() {
+            match slave.feed_packet(&packet, now_us, &mut IgnoreEvents) {
+                // Losses can leave a quarter-frame cycle that does not form a timecode
+                Ok(_) | Err(NetsyncError::InvalidSlaveEvent) => {}
+                Err(err) => panic!("at {} us: {:?}", now_us, err),
+            }
+        }
+        if let Some(feedback) = slave
+            .feedback_to_send(now_us)
+            .and_then(|packet| SlaveFeedback::from_packet(&packet))
+        {
+            master.handle_feedback(feedback);
+        }
+
+        if now_us >= SETTLE_US {
+            let estimate = slave.tracker().position_at(now_us).expect("slave locked");
+            max_error_us = max_error_us.max(estimate.abs_diff(start_us + now_us));
+        }
+    }
+
+    Run {
+        max_error_us,
+        slave,
+        channel,
+    }
+}
+
+#[test]
+fn test_channel_is_deterministic() {
+    let config = LossyConfig {
+        loss: 0.2,
+        duplication: 0.2,
+        max_reorder: 3,
+        seed: 42,
+    };
+    let mut first = LossyChannel::new(config);
+    let mut second = LossyChannel::new(config);
+    for byte in 0..=255u8 {
+        first.send(&[byte]);
+        second.send(&[byte]);
+        assert_eq!(first.recv(), second.recv());
+    }
+    assert_eq!(first.flush(), second.flush());
+    assert_eq!(first.dropped(), second.dropped());
+    assert!(first.dropped() > 0 && first.duplicated() > 0);
+}
+
+#[test]
+fn test_channel_reorder_distance() {
+    const MAX_REORDER: usize = 4;
+    let mut channel = LossyChannel::new(LossyConfig {
+        max_reorder: MAX_REORDER,
+        seed: 7,
+        ..LossyConfig::default()
+    });
+    let mut delivered = Vec::new();
+    for index in 0..1000u16 {
+        channel.send(&index.to_be_bytes());
+        delivered.extend(channel.recv());
+    }
+    delivered.extend(channel.flush());
+
+    assert_eq!(delivered.len(), 1000);
+    let mut reordered = 0;
+    for (position, packet) in delivered.iter().enumerate() {
+        let index = u16::from_be_bytes([packet[0], packet[1]]) as usize;
+        assert!(
+            index.abs_diff(position) <= MAX_REORDER,
+            "{} at {}",
+            index,
+            position
+        );
+        reordered += usize::from(index != position);
+    }
+    assert!(reordered > 0);
+}
+
+#[test]
+fn test_burst_loss_recovers() {
+    // Every packet for half a second, including a periodic Full Frame
+    let run = simulate(LossyConfig::default(), 4_000_000, 2_000_000..2_500_000);
+
+    assert!(run.max_error_us < 33_334, "error {} us", run.max_error_us);
+    assert_eq!(run.slave.metrics().sequence_gaps, 1);
+    assert!(run.slave.metrics().packets_lost >= 60);
+}
+
+#[test]
+fn test_sustained_loss_stays_locked() {
+    let run = simulate(
+        LossyConfig {
+            loss: 0.1,
+            duplication: 0.05,
+            seed: 10,
+            ..LossyConfig::default()
+        },
+        10_000_000,
+        0..0,
+    );
+
+    assert!(run.max_error_us < 33_334, "error {} us", run.max_error_us);
+    assert!(run.channel.dropped() > 0);
+    assert!(run.slave.metrics().duplicates_dropped > 0);
+}
+
+#[test]
+fn test_heavy_reordering_stays_locked() {
+    const MAX_REORDER: usize = 6;
+    let run = simulate(
+        LossyConfig {
+            max_reorder: MAX_REORDER,
+            seed: 3,
+            ..LossyConfig::default()
+        },
+        10_000_000,
+        0..0,
+    );
+
+    // Packets reach the slave up to MAX_REORDER quarter-frame periods late
+    let bound_us = 33_334 + MAX_REORDER as u64 * 1_000_000 / QUARTER_FRAMES_PER_SECOND;
+    assert!(run.max_error_us < bound_us, "error {} us", run.max_error_us);
+    assert!(run.slave.metrics().out_of_order_dropped > 0);
+}
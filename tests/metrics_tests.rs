@@ -0,0 +1,430 @@
+use rtp_midi_netsync::error::{NetsyncError, ParseError};
+use rtp_midi_netsync::midi::{MidiEvent, MmcCommand};
+use rtp_midi_netsync::mtc::{smpte_to_quarter_frames, MtcFullFrame, VlcTickT};
+use rtp_midi_netsync::netsync::{
+    master_netsync_flow, FullFrameRefreshPolicy, MasterSession, Metrics, NetsyncConfig,
+    NetsyncHandler, PacketStatus, SlaveSession,
+};
+use rtp_midi_netsync::rtp::RtpHeader;
+use rtp_midi_netsync::Error;
+
+const SSRC: u32 = 0x1234_5678;
+
+struct NoopHandler;
+
+impl NetsyncHandler for NoopHandler {}
+
+fn packet(sequence: u16, ssrc: u32, payload: &[u8]) -> Vec<u8> {
+    let mut packet = RtpHeader::new(sequence, 0, ssrc).serialize().to_vec();
+    packet.extend_from_slice(payload);
+    packet
+}
+
+fn event_packet(sequence: u16, event: &MidiEvent) -> Vec<u8> {
+    packet(sequence, SSRC, &master_netsync_flow(event).unwrap())
+}
+
+fn quarter_frames(tc: &MtcFullFrame) -> Vec<MidiEvent> {
+    smpte_to_quarter_frames(tc)
+        .iter()
+        .map(|qf| MidiEvent::MtcQuarter {
+            msg_type: qf.frame_type,
+            value: qf.value,
+        })
+        .collect()
+}
+
+fn feed(session: &mut SlaveSession, packet: &[u8]) -> Result<PacketStatus, NetsyncError> {
+    session.feed_packet(packet, 0, &mut NoopHandler)
+}
+
+// === Master Session Tests ===
+
+#[test]
+fn test_master_counts_packets_sent() {
+    let mut session = MasterSession::with_policy(FullFrameRefreshPolicy {
+        full_frame_interval_us: None,
+        quarter_frame_cycles: None,
+    });
+    session
+        .packet_for_event(&MidiEvent::Mmc(MmcCommand::Play), 0)
+        .unwrap();
+    session
+        .packet_for_event(&MidiEvent::Mmc(MmcCommand::Stop), 10)
+        .unwrap();
+    assert!(session
+        .packet_for_event(&MidiEvent::Other(vec![0x90, 0x3C, 0x7F]), 20)
+        .is_err());
+
+    assert_eq!(session.metrics().packets_sent, 2);
+}
+
+#[test]
+fn test_master_counts_cycles_and_refreshes() {
+    let mut session = MasterSession::with_policy(FullFrameRefreshPolicy {
+        full_frame_interval_us: None,
+        quarter_frame_cycles: Some(2),
+    });
+    session
+        .packet_for_event(&MidiEvent::Mmc(MmcCommand::Play), 0)
+        .unwrap();
+
+    let mut now_us = 0;
+    for frame in [0, 2, 4, 6] {
+        for event in quarter_frames(&MtcFullFrame {
+            hours: 0,
+            minutes: 0,
+            seconds: 1,
+            frames: frame,
+        }) {
+            session.packet_for_event(&event, now_us).unwrap();
+            session.tick(now_us);
+            now_us += 8_333;
+        }
+    }
+
+    let metrics = session.metrics();
+    assert_eq!(metrics.quarter_frame_cycles, 4);
+    assert_eq!(metrics.full_frames_refreshed, 2);
+    // Play, 32 quarter frames, and a Full Frame plus Play per refresh
+    assert_eq!(metrics.packets_sent, 1 + 32 + 4);
+}
+
+#[test]
+fn test_master_packets_are_numbered() {
+    let config = NetsyncConfig {
+        ssrc: SSRC,
+        ..NetsyncConfig::default()
+    };
+    let mut session = MasterSession::with_config(config);
+
+    for expected in 0..3u16 {
+        let packet = session
+            .packet_for_event(&MidiEvent::Mmc(MmcCommand::Play), 0)
+            .unwrap();
+        let (header, _) = RtpHeader::parse(&packet).unwrap();
+        assert_eq!(header.sequence_number, expected);
+        assert_eq!(header.ssrc, SSRC);
+    }
+}
+
+// === Slave Session Tests ===
+
+#[test]
+fn test_slave_counts_packets_parsed() {
+    let mut session = SlaveSession::new();
+    for sequence in 0..5 {
+        let status = feed(
+            &mut session,
+            &event_packet(sequence, &MidiEvent::Mmc(MmcCommand::Play)),
+        );
+        assert_eq!(status, Ok(PacketStatus::Accepted));
+    }
+    assert_eq!(session.metrics().packets_parsed, 5);
+}
+
+#[test]
+fn test_slave_counts_rtp_header_errors() {
+    let mut session = SlaveSession::new();
+    assert_eq!(
+        feed(&mut session, &[0x80, 0x61, 0x00]),
+        Err(NetsyncError::InvalidSlaveEvent)
+    );
+    let mut bad_version = event_packet(0, &MidiEvent::Mmc(MmcCommand::Play));
+    bad_version[0] = 0x40;
+    // Fatal: the session fails instead of counting it
+    assert_eq!(
+        feed(&mut session, &bad_version),
+        Err(NetsyncError::SessionFailed)
+    );
+
+    assert_eq!(session.metrics().rtp_header_errors, 1);
+    assert_eq!(session.metrics().packets_parsed, 0);
+}
+
+#[test]
+fn test_slave_fails_only_on_fatal_errors() {
+    let mut session = SlaveSession::new();
+    let play = MidiEvent::Mmc(MmcCommand::Play);
+
+    // Recoverable: counted, and the next packet is accepted
+    assert_eq!(
+        feed(&mut session, &packet(0, SSRC, &[0x05, 0xF1, 0x23])),
+        Err(NetsyncError::DeclaredLengthExceedsBuffer {
+            declared: 5,
+            available: 2
+        })
+    );
+    assert_eq!(session.metrics().length_errors, 1);
+    assert_eq!(session.failure(), None);
+    assert_eq!(
+        feed(&mut session, &event_packet(1, &play)),
+        Ok(PacketStatus::Accepted)
+    );
+
+    // Fatal: the session keeps the cause and drops everything until cleared
+    let mut bad_version = event_packet(2, &play);
+    bad_version[0] = 0x40;
+    assert_eq!(
+        feed(&mut session, &bad_version),
+        Err(NetsyncError::SessionFailed)
+    );
+    assert_eq!(
+        session.failure(),
+        Some(&Error::Parse(ParseError::UnsupportedRtpVersion(1)))
+    );
+    assert!(!session.failure().unwrap().is_recoverable());
+    assert_eq!(
+        feed(&mut session, &event_packet(3, &play)),
+        Err(NetsyncError::SessionFailed)
+    );
+    assert_eq!(session.metrics().rtp_header_errors, 0);
+    assert_eq!(session.metrics().packets_parsed, 1);
+
+    session.clear_failure();
+    assert_eq!(
+        feed(&mut session, &event_packet(4, &play)),
+        Ok(PacketStatus::Accepted)
+    );
+}
+
+#[test]
+fn test_slave_counts_payload_errors_by_kind() {
+    let mut session = SlaveSession::new();
+
+    assert!(feed(&mut session, &packet(0, SSRC, &[0x02])).is_err());
+    assert!(feed(&mut session, &packet(1, SSRC, &[0x05, 0xF1, 0x23])).is_err());
+    assert!(feed(&mut session, &packet(2, SSRC, &[0x00, 0xF1, 0x23])).is_err());
+
+    let metrics = session.metrics();
+    assert_eq!(metrics.too_short_errors, 1);
+    assert_eq!(metrics.length_errors, 1);
+    assert_eq!(metrics.midi_errors, 1);
+    assert_eq!(metrics.packets_parsed, 0);
+}
+
+#[test]
+fn test_slave_counts_timecode_errors() {
+    let mut session = SlaveSession::new();
+
+    // A complete cycle encoding frame 31 at 30fps is not a valid timecode
+    let values = [0x0F, 0x01, 0, 0, 0, 0, 0, 0x06];
+    let results: Vec<_> = (0..8u8)
+        .map(|piece| {
+            let event = MidiEvent::MtcQuarter {
+                msg_type: piece,
+                value: values[piece as usize],
+            };
+            feed(&mut session, &event_packet(piece as u16, &event))
+        })
+        .collect();
+
+    assert!(results[..7].iter().all(|result| result.is_ok()));
+    assert_eq!(results[7], Err(NetsyncError::InvalidSlaveEvent));
+    assert_eq!(session.metrics().timecode_errors, 1);
+    assert_eq!(session.metrics().quarter_frame_cycles, 0);
+}
+
+#[test]
+fn test_slave_counts_duplicates() {
+    let mut session = SlaveSession::new();
+    let play = event_packet(7, &MidiEvent::Mmc(MmcCommand::Play));
+
+    assert_eq!(feed(&mut session, &play), Ok(PacketStatus::Accepted));
+    assert_eq!(feed(&mut session, &play), Ok(PacketStatus::Duplicate));
+    assert_eq!(feed(&mut session, &play), Ok(PacketStatus::Duplicate));
+
+    assert_eq!(session.metrics().duplicates_dropped, 2);
+    assert_eq!(session.metrics().packets_parsed, 1);
+}
+
+#[test]
+fn test_slave_counts_out_of_order() {
+    let mut session = SlaveSession::new();
+    let play = MidiEvent::Mmc(MmcCommand::Play);
+
+    assert_eq!(
+        feed(&mut session, &event_packet(10, &play)),
+        Ok(PacketStatus::Accepted)
+    );
+    assert_eq!(
+        feed(&mut session, &event_packet(9, &play)),
+        Ok(PacketStatus::OutOfOrder)
+    );
+    assert_eq!(session.metrics().out_of_order_dropped, 1);
+}
+
+#[test]
+fn test_slave_counts_foreign_ssrc() {
+    let mut session = SlaveSession::new();
+    let payload = master_netsync_flow(&MidiEvent::Mmc(MmcCommand::Play)).unwrap();
+
+    assert_eq!(
+        feed(&mut session, &packet(0, SSRC, &payload)),
+        Ok(PacketStatus::Accepted)
+    );
+    assert_eq!(
+        feed(&mut session, &packet(1, SSRC + 1, &payload)),
+        Ok(PacketStatus::ForeignSsrc)
+    );
+    assert_eq!(
+        feed(&mut session, &packet(1, SSRC, &payload)),
+        Ok(PacketStatus::Accepted)
+    );
+    assert_eq!(session.metrics().foreign_ssrc_dropped, 1);
+}
+
+#[test]
+fn test_slave_counts_sequence_gaps_across_wraparound() {
+    let mut session = SlaveSession::new();
+    let play = MidiEvent::Mmc(MmcCommand::Play);
+
+    for sequence in [0xFFFE, 0x0001, 0x0002, 0x0006] {
+        assert_eq!(
+            feed(&mut session, &event_packet(sequence, &play)),
+            Ok(PacketStatus::Accepted)
+        );
+    }
+
+    let metrics = session.metrics();
+    assert_eq!(metrics.sequence_gaps, 2);
+    assert_eq!(metrics.packets_lost, 2 + 3);
+}
+
+#[test]
+fn test_slave_counts_quarter_frame_cycles() {
+    let mut session = SlaveSession::new();
+    let mut sequence = 0;
+    for frame in [0, 2, 4] {
+        for event in quarter_frames(&MtcFullFrame {
+            hours: 1,
+            minutes: 0,
+            seconds: 0,
+            frames: frame,
+        }) {
+            feed(&mut session, &event_packet(sequence, &event)).unwrap();
+            sequence += 1;
+        }
+    }
+    assert_eq!(session.metrics().quarter_frame_cycles, 3);
+}
+
+#[test]
+fn test_slave_reports_drift() {
+    let mut session = SlaveSession::new();
+    let mut handler = NoopHandler;
+    let full_frame = |frame: u8| MidiEvent::MtcFull {
+        hour: 0,
+        minute: 0,
+        second: 10,
+        frame,
+    };
+
+    session
+        .feed_packet(&event_packet(0, &full_frame(0)), 0, &mut handler)
+        .unwrap();
+    // Stopped: no drift is measured
+    assert_eq!(session.metrics().last_drift_us, None);
+
+    session
+        .feed_packet(
+            &event_packet(1, &MidiEvent::Mmc(MmcCommand::Play)),
+            0,
+            &mut handler,
+        )
+        .unwrap();
+    // One second later the master reports 1s + 3 frames (100 ms ahead)
+    let arrival_us: VlcTickT = 1_000_000;
+    session
+        .feed_packet(
+            &event_packet(
+                2,
+                &MidiEvent::MtcFull {
+                    hour: 0,
+                    minute: 0,
+                    second: 11,
+                    frame: 3,
+                },
+            ),
+            arrival_us,
+            &mut handler,
+        )
+        .unwrap();
+    assert_eq!(session.metrics().last_drift_us, Some(100_000));
+}
+
+#[test]
+fn test_reset_metrics() {
+    let mut slave = SlaveSession::new();
+    let play = event_packet(0, &MidiEvent::Mmc(MmcCommand::Play));
+    feed(&mut slave, &play).unwrap();
+    feed(&mut slave, &play).unwrap();
+    assert_ne!(*slave.metrics(), Metrics::default());
+
+    slave.reset_metrics();
+    assert_eq!(*slave.metrics(), Metrics::default());
+    // Sequence state survives a metrics reset
+    assert_eq!(feed(&mut slave, &play), Ok(PacketStatus::Duplicate));
+
+    let mut master = MasterSession::new();
+    master
+        .packet_for_event(&MidiEvent::Mmc(MmcCommand::Play), 0)
+        .unwrap();
+    master.reset_metrics();
+    assert_eq!(*master.metrics(), Metrics::default());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_metrics_serialize() {
+    fn assert_serialize<T: serde::Serialize>(_: &T) {}
+    assert_serialize(SlaveSession::new().metrics());
+}
+
+#[test]
+fn test_slave_accepts_packets_without_commands() {
+    let mut session = SlaveSession::new();
+    let play = event_packet(0, &MidiEvent::Mmc(MmcCommand::Play));
+    assert_eq!(feed(&mut session, &play), Ok(PacketStatus::Accepted));
+    assert_eq!(
+        feed(&mut session, &packet(1, SSRC, &[0x00])),
+        Ok(PacketStatus::NoCommands)
+    );
+    assert_eq!(
+        feed(&mut session, &packet(2, SSRC, &[0x40, 0x01, 0x00, 0x2A])),
+        Ok(PacketStatus::NoCommands)
+    );
+    assert_eq!(
+        feed(&mut session, &packet(3, SSRC, &[0x00, 0xFA])),
+        Err(NetsyncError::TrailingData { extra: 1 })
+    );
+    // Keepalives take part in sequence tracking: no gap is reported
+    let stop = event_packet(4, &MidiEvent::Mmc(MmcCommand::Stop));
+    assert_eq!(feed(&mut session, &stop), Ok(PacketStatus::Accepted));
+
+    let metrics = session.metrics();
+    assert_eq!(metrics.empty_payloads, 2);
+    assert_eq!(metrics.packets_parsed, 2);
+    assert_eq!(metrics.midi_errors, 1);
+    assert_eq!(metrics.sequence_gaps, 0);
+}
+
+#[test]
+fn test_master_keepalive() {
+    let mut master = MasterSession::new();
+    let mut slave = SlaveSession::new();
+    let play = master
+        .packet_for_event(&MidiEvent::Mmc(MmcCommand::Play), 0)
+        .unwrap();
+    let keepalive = master.keepalive(1_000_000);
+    let stop = master
+        .packet_for_event(&MidiEvent::Mmc(MmcCommand::Stop), 2_000_000)
+        .unwrap();
+
+    assert_eq!(&keepalive[12..], [0x00]);
+    assert_eq!(feed(&mut slave, &play), Ok(PacketStatus::Accepted));
+    assert_eq!(feed(&mut slave, &keepalive), Ok(PacketStatus::NoCommands));
+    assert_eq!(feed(&mut slave, &stop), Ok(PacketStatus::Accepted));
+    assert_eq!(master.metrics().packets_sent, 3);
+    assert_eq!(slave.metrics().sequence_gaps, 0);
+}
@@ -0,0 +1,57 @@
+use rtp_midi_netsync::util::midi_len::expected_len;
+
+/// Data bytes after each status byte, as listed in the MIDI 1.0 specification
+fn spec_len(status: u8) -> Option<usize> {
+    let high = status >> 4;
+    match high {
+        // Data bytes are not status bytes
+        0x0..=0x7 => None,
+        // Note Off, Note On, Polyphonic Key Pressure, Control Change
+        0x8..=0xB => Some(2),
+        // Program Change, Channel Pressure
+        0xC | 0xD => Some(1),
+        // Pitch Bend Change
+        0xE => Some(2),
+        _ => match status {
+            // System Exclusive and End of Exclusive, delimited by F7
+            0xF0 | 0xF7 => None,
+            // MTC Quarter Frame, Song Select
+            0xF1 | 0xF3 => Some(1),
+            // Song Position Pointer
+            0xF2 => Some(2),
+            // Undefined System Common
+            0xF4 | 0xF5 => None,
+            // Tune Request
+            0xF6 => Some(0),
+            // System Real-Time, including the undefined F9 and FD
+            _ => Some(0),
+        },
+    }
+}
+
+#[test]
+fn test_every_status_byte_matches_the_spec() {
+    for status in 0..=u8::MAX {
+        assert_eq!(
+            expected_len(status),
+            spec_len(status),
+            "status 0x{:02X}",
+            status
+        );
+    }
+}
+
+#[test]
+fn test_channel_messages_ignore_the_channel() {
+    for channel in 0..16 {
+        assert_eq!(expected_len(0x90 | channel), Some(2));
+        assert_eq!(expected_len(0xC0 | channel), Some(1));
+        assert_eq!(expected_len(0xE0 | channel), Some(2));
+    }
+}
+
+#[test]
+fn test_usable_in_const_context() {
+    const QUARTER_FRAME: Option<usize> = expected_len(0xF1);
+    assert_eq!(QUARTER_FRAME, Some(1));
+}
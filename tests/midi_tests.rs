@@ -1,5 +1,10 @@
 use rtp_midi_netsync::error::ParseError;
-use rtp_midi_netsync::midi::{build_midi_list, parse_midi_list, MidiEvent, MmcCommand};
+use rtp_midi_netsync::midi::{
+    build_midi_list, parse_midi_list, parse_midi_list_strict, parse_midi_message, peek_midi_kind,
+    DeviceIdentity, ManufacturerId, MessageKind, MidiEvent, MidiEventRef, MmcCommand, MmcSpeed,
+    RealtimeMessage, IDENTITY_REPLY_MAX_LENGTH, IDENTITY_REQUEST_LENGTH, MMC_MASKED_WRITE_LENGTH,
+    MMC_SPEED_LENGTH, MMC_TRACK_RECORD_READY,
+};
 
 #[cfg(test)]
 mod tests {
@@ -122,13 +127,16 @@ mod tests {
 
     #[test]
     fn test_parse_other_sysex() {
-        // Non-standard SysEx should be treated as Other
+        // Manufacturer SysEx keeps its ID apart from its data
         let buf = [0xF0, 0x43, 0x12, 0x34, 0xF7];
         let result = parse_midi_list(&buf, 5).unwrap();
 
         match result {
-            MidiEvent::Other(bytes) => assert_eq!(bytes, vec![0xF0, 0x43, 0x12, 0x34, 0xF7]),
-            _ => panic!("Expected Other event"),
+            MidiEvent::ManufacturerSysex { id, data } => {
+                assert_eq!(id, ManufacturerId::Short(0x43));
+                assert_eq!(data, vec![0x12, 0x34]);
+            }
+            _ => panic!("Expected ManufacturerSysex event"),
         }
     }
 
@@ -366,15 +374,101 @@ mod tests {
         }
 
         // Test MMC with wrong length
-        let buf = [0xF0, 0x7F, 0x7F, 0x06, 0x01]; // Missing F7
-        let result = parse_midi_list(&buf, 5).unwrap();
+        let buf = [0xF0, 0x7F, 0x7F, 0x06]; // Missing command and F7
+        let result = parse_midi_list(&buf, 4).unwrap();
 
         match result {
-            MidiEvent::Other(bytes) => assert_eq!(bytes.len(), 5),
+            MidiEvent::Other(bytes) => assert_eq!(bytes.len(), 4),
             _ => panic!("Expected Other event for partial MMC"),
         }
     }
 
+    #[test]
+    fn test_padded_full_frame() {
+        // Two vendor bytes between the frame byte and F7
+        let buf = [
+            0xF0, 0x7F, 0x7F, 0x01, 0x01, 0x01, 0x02, 0x03, 0x04, 0x11, 0x22, 0xF7,
+        ];
+        let event = MidiEvent::MtcFull {
+            hour: 1,
+            minute: 2,
+            second: 3,
+            frame: 4,
+        };
+        assert_eq!(parse_midi_list(&buf, buf.len()).unwrap(), event);
+        assert_eq!(
+            peek_midi_kind(&buf, buf.len()).unwrap(),
+            MessageKind::MtcFull
+        );
+        assert_eq!(parse_midi_message(&buf).unwrap().1, buf.len());
+
+        // The padding is not part of the event
+        assert_eq!(build_midi_list(&event), [&buf[..9], &[0xF7]].concat());
+
+        assert_eq!(
+            parse_midi_list_strict(&buf, buf.len()),
+            Err(ParseError::InvalidMidiData {
+                position: 9,
+                byte: 0x11
+            })
+        );
+    }
+
+    #[test]
+    fn test_unterminated_mmc_frames() {
+        // Frames missing only the F7 terminator are accepted leniently
+        let frames = [
+            (vec![0xF0, 0x7F, 0x7F, 0x06, 0x01], MmcCommand::Stop),
+            (vec![0xF0, 0x7F, 0x7F, 0x06, 0x02], MmcCommand::Play),
+            (
+                vec![
+                    0xF0, 0x7F, 0x7F, 0x06, 0x44, 0x06, 0x01, 0x01, 0x02, 0x03, 0x04, 0x00,
+                ],
+                MmcCommand::Locate {
+                    hour: 1,
+                    minute: 2,
+                    second: 3,
+                    frame: 4,
+                    subframe: 0,
+                },
+            ),
+        ];
+        for (buf, command) in frames {
+            let event = MidiEvent::Mmc(command);
+            assert_eq!(parse_midi_list(&buf, buf.len()).unwrap(), event);
+            assert_eq!(peek_midi_kind(&buf, buf.len()).unwrap(), event.kind());
+            assert_eq!(
+                parse_midi_list_strict(&buf, buf.len()),
+                Err(ParseError::BufferTooSmall {
+                    requested: buf.len() + 1,
+                    available: buf.len(),
+                })
+            );
+        }
+
+        // Frames also missing data bytes are rejected in both modes
+        let truncated: [&[u8]; 4] = [
+            &[0xF0, 0x7F, 0x7F, 0x06],
+            &[
+                0xF0, 0x7F, 0x7F, 0x06, 0x44, 0x06, 0x01, 0x01, 0x02, 0x03, 0x04,
+            ],
+            &[0xF0, 0x7F, 0x7F, 0x06, 0x44, 0x06, 0x01],
+            &[0xF0, 0x7F, 0x7F, 0x06, 0x01, 0x00],
+        ];
+        for buf in truncated {
+            assert_eq!(
+                parse_midi_list(buf, buf.len()).unwrap(),
+                MidiEvent::Other(buf.to_vec())
+            );
+        }
+        for buf in truncated {
+            assert!(!matches!(
+                parse_midi_list_strict(buf, buf.len()),
+                Ok(MidiEvent::Mmc(_))
+            ));
+        }
+    }
+
     #[test]
     fn test_mtc_quarter_edge_cases() {
         // Test maximum valid values for MTC quarter-frame
@@ -396,13 +490,13 @@ mod tests {
 
     #[test]
     fn test_sysex_wrong_universal_realtime_id() {
-        // SysEx with wrong Universal Real-Time ID should fall through to Other
+        // SysEx with wrong Universal Real-Time ID should fall through to a pass-through
         let buf = [0xF0, 0x43, 0x7F, 0x01, 0x01, 0x01, 0x23, 0x45, 0x67, 0xF7]; // Wrong first ID
         let result = parse_midi_list(&buf, 10).unwrap();
 
         match result {
-            MidiEvent::Other(bytes) => assert_eq!(bytes.len(), 10),
-            _ => panic!("Expected Other event for wrong Universal Real-Time ID"),
+            MidiEvent::ManufacturerSysex { data, .. } => assert_eq!(data.len(), 7),
+            _ => panic!("Expected ManufacturerSysex event for wrong Universal Real-Time ID"),
         }
     }
 
@@ -514,3 +608,602 @@ fn test_roundtrip_mmc_locate() {
 
     assert_eq!(original.to_vec(), rebuilt);
 }
+
+// === Extended MMC Command Tests ===
+
+/// Commands without data beyond Stop and Play, with their command bytes
+const EXTENDED_MMC_COMMANDS: [(MmcCommand, u8); 8] = [
+    (MmcCommand::DeferredPlay, 0x03),
+    (MmcCommand::FastForward, 0x04),
+    (MmcCommand::Rewind, 0x05),
+    (MmcCommand::RecordStrobe, 0x06),
+    (MmcCommand::RecordExit, 0x07),
+    (MmcCommand::Pause, 0x09),
+    (MmcCommand::Eject, 0x0A),
+    (MmcCommand::Reset, 0x0D),
+];
+
+#[test]
+fn test_build_and_parse_extended_mmc_commands() {
+    for (command, byte) in EXTENDED_MMC_COMMANDS {
+        let bytes = [0xF0, 0x7F, 0x7F, 0x06, byte, 0xF7];
+        let event = MidiEvent::Mmc(command);
+        assert_eq!(build_midi_list(&event), bytes);
+        assert_eq!(parse_midi_list(&bytes, bytes.len()).unwrap(), event);
+        assert_eq!(event.kind(), MessageKind::OtherMmc);
+        assert!(event.kind().is_transport());
+    }
+}
+
+#[test]
+fn test_parse_unknown_mmc_commands_as_other() {
+    // Record Pause, Chase and MMC Command Error Reset are not recognised
+    for byte in [0x08, 0x0B, 0x0C] {
+        let bytes = [0xF0, 0x7F, 0x7F, 0x06, byte, 0xF7];
+        assert_eq!(
+            parse_midi_list(&bytes, bytes.len()).unwrap(),
+            MidiEvent::Other(bytes.to_vec())
+        );
+    }
+}
+
+// === MMC Shuttle Unit Tests ===
+
+/// Speeds and their `sh sm sl` bytes: `sh` is `0 g sss iii`, and `iii sm sl` holds
+/// the magnitude with `14 - sss` fraction bits
+const SHUTTLE_SPEEDS: [(f32, [u8; 3]); 6] = [
+    (0.0, [0x00, 0x00, 0x00]),  // Stop
+    (1.0, [0x01, 0x00, 0x00]),  // Play speed forward
+    (-1.0, [0x41, 0x00, 0x00]), // Play speed in reverse
+    (0.5, [0x00, 0x40, 0x00]),  // Half speed
+    (-2.5, [0x42, 0x40, 0x00]), // 2.5 in reverse
+    (10.0, [0x0D, 0x00, 0x00]), // Shift 1 for a four-bit integer part
+];
+
+#[test]
+fn test_shuttle_speed_round_trip() {
+    for (speed, bytes) in SHUTTLE_SPEEDS {
+        assert_eq!(MmcSpeed::from_f32(speed).to_bytes(), bytes, "{}", speed);
+        assert_eq!(
+            MmcSpeed::from_bytes(bytes).to_f32(),
+            speed,
+            "{:02X?}",
+            bytes
+        );
+        assert_eq!(MmcSpeed::from_bytes(bytes).is_reverse(), speed < 0.0);
+
+        let [sh, sm, sl] = bytes;
+        let message = [0xF0, 0x7F, 0x7F, 0x06, 0x47, 0x03, sh, sm, sl, 0xF7];
+        let event = MidiEvent::Mmc(MmcCommand::Shuttle {
+            speed: MmcSpeed::from_f32(speed),
+        });
+        assert_eq!(build_midi_list(&event), message);
+        assert_eq!(parse_midi_list(&message, message.len()).unwrap(), event);
+        assert_eq!(event.kind(), MessageKind::Shuttle);
+        assert_eq!(event.kind().wire_len(), Some(MMC_SPEED_LENGTH));
+        assert!(event.kind().is_transport());
+    }
+}
+
+#[test]
+fn test_shuttle_speed_limits() {
+    assert_eq!(MmcSpeed::from_f32(f32::NAN), MmcSpeed::STOP);
+    assert_eq!(MmcSpeed::from_f32(-0.0), MmcSpeed::STOP);
+
+    // Saturates at shift 7 with every magnitude bit set
+    let fastest = MmcSpeed::from_f32(f32::NEG_INFINITY);
+    assert_eq!(fastest.to_bytes(), [0x7F, 0x7F, 0x7F]);
+    assert_eq!(fastest.to_f32(), -(1024.0 - 1.0 / 128.0));
+    assert_eq!(MmcSpeed::from_f32(5000.0).to_f32(), 1024.0 - 1.0 / 128.0);
+
+    // Rounds to the nearest of 14 fraction bits
+    let third = MmcSpeed::from_f32(1.0 / 3.0);
+    assert_eq!(third.to_bytes(), [0x00, 0x2A, 0x55]);
+    assert!((third.to_f32() - 1.0 / 3.0).abs() < 1.0 / 32768.0);
+
+    // Parsed bytes are kept, even a stop in reverse or a larger shift than needed
+    for bytes in [[0x40, 0x00, 0x00], [0x08, 0x40, 0x00]] {
+        let [sh, sm, sl] = bytes;
+        let message = [0xF0, 0x7F, 0x7F, 0x06, 0x47, 0x03, sh, sm, sl, 0xF7];
+        let event = parse_midi_list(&message, message.len()).unwrap();
+        assert_eq!(build_midi_list(&event), message);
+    }
+    assert_eq!(MmcSpeed::from_bytes([0x08, 0x40, 0x00]).to_f32(), 1.0);
+    assert_eq!(
+        MmcSpeed::from_bytes([0x81, 0x80, 0xFF]).to_bytes(),
+        [0x01, 0x00, 0x7F]
+    );
+}
+
+// === MMC Variable Play Unit Tests ===
+
+#[test]
+fn test_variable_play_round_trip() {
+    // Nominal and half speed
+    for (speed, bytes) in [(1.0, [0x01, 0x00, 0x00]), (0.5, [0x00, 0x40, 0x00])] {
+        let [sh, sm, sl] = bytes;
+        let message = [0xF0, 0x7F, 0x7F, 0x06, 0x45, 0x03, sh, sm, sl, 0xF7];
+        let event = MidiEvent::Mmc(MmcCommand::VariablePlay {
+            speed: MmcSpeed::from_f32(speed),
+        });
+        assert_eq!(build_midi_list(&event), message);
+        assert_eq!(parse_midi_list(&message, message.len()).unwrap(), event);
+        assert_eq!(event.kind(), MessageKind::VariablePlay);
+        assert_eq!(event.kind().wire_len(), Some(MMC_SPEED_LENGTH));
+        assert!(event.kind().is_transport());
+    }
+
+    // Same speed bytes as Shuttle, after a different command byte
+    for (speed, bytes) in SHUTTLE_SPEEDS {
+        let speed = MmcSpeed::from_f32(speed);
+        let shuttle = build_midi_list(&MidiEvent::Mmc(MmcCommand::Shuttle { speed }));
+        let variable_play = build_midi_list(&MidiEvent::Mmc(MmcCommand::VariablePlay { speed }));
+        assert_eq!(shuttle[6..9], bytes);
+        assert_eq!(variable_play[6..9], bytes);
+        assert_eq!((shuttle[4], variable_play[4]), (0x47, 0x45));
+    }
+}
+
+// === MMC Search Unit Tests ===
+
+#[test]
+fn test_search_round_trip() {
+    // Search forward at 10x, shift 1, and back at play speed
+    for (speed, bytes) in [(10.0, [0x0D, 0x00, 0x00]), (-1.0, [0x41, 0x00, 0x00])] {
+        let [sh, sm, sl] = bytes;
+        let message = [0xF0, 0x7F, 0x7F, 0x06, 0x46, 0x03, sh, sm, sl, 0xF7];
+        let event = MidiEvent::Mmc(MmcCommand::Search {
+            speed: MmcSpeed::from_f32(speed),
+        });
+        assert_eq!(build_midi_list(&event), message);
+        assert_eq!(parse_midi_list(&message, message.len()).unwrap(), event);
+        assert_eq!(event.kind(), MessageKind::Search);
+        assert_eq!(event.kind().wire_len(), Some(MMC_SPEED_LENGTH));
+        assert!(event.kind().is_transport());
+        assert!(event.kind().is_sync());
+    }
+}
+
+// === MMC Masked Write Unit Tests ===
+
+#[test]
+fn test_masked_write_round_trip() {
+    let command = MmcCommand::MaskedWrite {
+        field: MMC_TRACK_RECORD_READY,
+        start: 1,
+        mask: 0x01,
+        data: 0x01,
+    };
+    let bytes = [
+        0xF0, 0x7F, 0x7F, 0x06, 0x41, 0x04, 0x4F, 0x01, 0x01, 0x01, 0xF7,
+    ];
+    let event = MidiEvent::Mmc(command);
+    assert_eq!(build_midi_list(&event), bytes);
+    assert_eq!(parse_midi_list(&bytes, bytes.len()).unwrap(), event);
+    assert_eq!(parse_midi_list_strict(&bytes, bytes.len()).unwrap(), event);
+    assert_eq!(event.kind(), MessageKind::MaskedWrite);
+    assert_eq!(event.kind().wire_len(), Some(MMC_MASKED_WRITE_LENGTH));
+    assert!(!event.kind().is_transport());
+    assert!(event.kind().is_sync());
+}
+
+#[test]
+fn test_arm_track_bits() {
+    // Track 1 follows the Video, reserved, Time Code, Aux A and Aux B bits
+    assert_eq!(MmcCommand::arm_track_bits(1, true), Some((0, 0x20, 0x20)));
+    assert_eq!(MmcCommand::arm_track_bits(2, true), Some((0, 0x40, 0x40)));
+    assert_eq!(MmcCommand::arm_track_bits(3, true), Some((1, 0x01, 0x01)));
+    assert_eq!(MmcCommand::arm_track_bits(9, true), Some((1, 0x40, 0x40)));
+    assert_eq!(MmcCommand::arm_track_bits(10, false), Some((2, 0x01, 0x00)));
+    assert_eq!(
+        MmcCommand::arm_track_bits(891, true),
+        Some((0x7F, 0x40, 0x40))
+    );
+    assert_eq!(MmcCommand::arm_track_bits(892, true), None);
+    assert_eq!(MmcCommand::arm_track_bits(0, true), None);
+
+    assert_eq!(
+        MmcCommand::arm_track(3, true),
+        Some(MmcCommand::MaskedWrite {
+            field: MMC_TRACK_RECORD_READY,
+            start: 1,
+            mask: 0x01,
+            data: 0x01,
+        })
+    );
+}
+
+#[test]
+fn test_parse_malformed_masked_write() {
+    // The count byte must be 04 and end the message at the terminator
+    let longer = [
+        0xF0, 0x7F, 0x7F, 0x06, 0x41, 0x05, 0x4F, 0x01, 0x01, 0x01, 0x00, 0xF7,
+    ];
+    assert_eq!(
+        parse_midi_list(&longer, longer.len()).unwrap(),
+        MidiEvent::Other(longer.to_vec())
+    );
+    assert_eq!(
+        parse_midi_list_strict(&longer, longer.len()),
+        Err(ParseError::InvalidMidiData {
+            position: 5,
+            byte: 0x05
+        })
+    );
+    let cut_short = [0xF0, 0x7F, 0x7F, 0x06, 0x41, 0x04, 0x4F, 0x01, 0x01, 0xF7];
+    assert_eq!(
+        parse_midi_list(&cut_short, cut_short.len()).unwrap(),
+        MidiEvent::Other(cut_short.to_vec())
+    );
+
+    // Bytes with the high bit set are not 7-bit clean
+    let unclean = [
+        0xF0, 0x7F, 0x7F, 0x06, 0x41, 0x04, 0x4F, 0x01, 0x81, 0x01, 0xF7,
+    ];
+    assert_eq!(
+        parse_midi_list(&unclean, unclean.len()).unwrap(),
+        MidiEvent::Other(unclean.to_vec())
+    );
+    assert_eq!(
+        parse_midi_list_strict(&unclean, unclean.len()),
+        Err(ParseError::InvalidMidiData {
+            position: 8,
+            byte: 0x81
+        })
+    );
+}
+
+#[test]
+fn test_parse_malformed_speed_commands() {
+    for command in [0x45, 0x46, 0x47] {
+        // Wrong data byte count: passed through, rejected by strict parsing
+        let bytes = [
+            0xF0, 0x7F, 0x7F, 0x06, command, 0x02, 0x01, 0x00, 0x00, 0xF7,
+        ];
+        assert_eq!(
+            parse_midi_list(&bytes, bytes.len()).unwrap(),
+            MidiEvent::Other(bytes.to_vec())
+        );
+        assert_eq!(
+            parse_midi_list_strict(&bytes, bytes.len()),
+            Err(ParseError::InvalidMidiData {
+                position: 5,
+                byte: 0x02
+            })
+        );
+
+        // Cut short before the speed
+        let bytes = [0xF0, 0x7F, 0x7F, 0x06, command, 0x03, 0x01, 0xF7];
+        assert_eq!(
+            parse_midi_list(&bytes, bytes.len()).unwrap(),
+            MidiEvent::Other(bytes.to_vec())
+        );
+    }
+}
+
+#[test]
+fn test_parse_midi_message_stream() {
+    let events = [
+        MidiEvent::MtcQuarter {
+            msg_type: 1,
+            value: 0x2,
+        },
+        MidiEvent::Realtime(RealtimeMessage::Clock),
+        MidiEvent::MtcFull {
+            hour: 1,
+            minute: 2,
+            second: 3,
+            frame: 4,
+        },
+        MidiEvent::SongPosition(300),
+        MidiEvent::Mmc(MmcCommand::Play),
+    ];
+    let stream: Vec<u8> = events.iter().flat_map(build_midi_list).collect();
+
+    let mut rest = &stream[..];
+    for event in &events {
+        let (parsed, consumed) = parse_midi_message(rest).unwrap();
+        assert_eq!(&parsed.to_owned_event(), event);
+        assert_eq!(consumed, build_midi_list(event).len());
+        rest = &rest[consumed..];
+    }
+    assert_eq!(parse_midi_message(rest), Err(ParseError::EmptyBuffer));
+
+    // Unrecognised SysEx ends at its terminator, other messages take the rest
+    let (parsed, consumed) = parse_midi_message(&[0xF0, 0x7D, 0x01, 0xF7, 0xF8]).unwrap();
+    assert_eq!(parsed, MidiEventRef::Other(&[0xF0, 0x7D, 0x01, 0xF7]));
+    assert_eq!(consumed, 4);
+    let (_, consumed) = parse_midi_message(&[0x90, 0x40, 0x7F, 0xF8]).unwrap();
+    assert_eq!(consumed, 4);
+}
+
+#[test]
+fn test_parse_midi_list_strict_reports_invalid_bytes() {
+    // Messages the strict parser accepts are parsed as by parse_midi_list
+    let events = [
+        MidiEvent::MtcFull {
+            hour: 1,
+            minute: 2,
+            second: 3,
+            frame: 4,
+        },
+        MidiEvent::Mmc(MmcCommand::Locate {
+            hour: 1,
+            minute: 2,
+            second: 3,
+            frame: 4,
+            subframe: 0,
+        }),
+        MidiEvent::Other(vec![0xF0, 0x7D, 0x01, 0xF7]),
+        MidiEvent::Other(vec![0x90, 0x40, 0x7F]),
+    ];
+    for event in &events {
+        let list = build_midi_list(event);
+        assert_eq!(
+            parse_midi_list_strict(&list, list.len()).as_ref(),
+            Ok(event)
+        );
+    }
+
+    // Positions count from the start of the MIDI list
+    let cases: [(&[u8], ParseError); 4] = [
+        // A note-on inside a SysEx
+        (
+            &[0xF0, 0x7D, 0x90, 0x40, 0xF7],
+            ParseError::InvalidMidiData {
+                position: 2,
+                byte: 0x90,
+            },
+        ),
+        // Locate announcing 7 bytes of information field
+        (
+            &[
+                0xF0, 0x7F, 0x7F, 0x06, 0x44, 0x07, 0x01, 0x01, 0x02, 0x03, 0x04, 0x00, 0xF7,
+            ],
+            ParseError::InvalidMidiData {
+                position: 5,
+                byte: 0x07,
+            },
+        ),
+        // Full Frame terminated after its seconds
+        (
+            &[0xF0, 0x7F, 0x7F, 0x01, 0x01, 0x01, 0x02, 0x03, 0xF7],
+            ParseError::InvalidMidiData {
+                position: 8,
+                byte: 0xF7,
+            },
+        ),
+        // Full Frame cut off without a terminator
+        (
+            &[0xF0, 0x7F, 0x7F, 0x01, 0x01, 0x01],
+            ParseError::BufferTooSmall {
+                requested: 10,
+                available: 6,
+            },
+        ),
+    ];
+    for (list, error) in cases {
+        assert!(matches!(
+            parse_midi_list(list, list.len()),
+            Ok(MidiEvent::Other(_))
+        ));
+        assert_eq!(parse_midi_list_strict(list, list.len()), Err(error));
+    }
+}
+
+#[test]
+fn test_manufacturer_sysex_round_trip() {
+    let cases: [(&[u8], ManufacturerId, &[u8]); 4] = [
+        // Roland, one-byte ID
+        (
+            &[0xF0, 0x41, 0x10, 0x42, 0x12, 0xF7],
+            ManufacturerId::Short(0x41),
+            &[0x10, 0x42, 0x12],
+        ),
+        (&[0xF0, 0x7C, 0xF7], ManufacturerId::Short(0x7C), &[]),
+        // Three-byte ID 00 20 33, its first byte equal to the one-byte range's
+        (
+            &[0xF0, 0x00, 0x20, 0x33, 0x41, 0x00, 0xF7],
+            ManufacturerId::Extended([0x20, 0x33]),
+            &[0x41, 0x00],
+        ),
+        (
+            &[0xF0, 0x00, 0x00, 0x01, 0xF7],
+            ManufacturerId::Extended([0x00, 0x01]),
+            &[],
+        ),
+    ];
+    for (bytes, id, data) in cases {
+        let event = MidiEvent::ManufacturerSysex {
+            id,
+            data: data.to_vec(),
+        };
+        assert_eq!(parse_midi_list(bytes, bytes.len()).unwrap(), event);
+        assert_eq!(parse_midi_list_strict(bytes, bytes.len()).unwrap(), event);
+        assert_eq!(build_midi_list(&event), bytes);
+        assert_eq!(
+            parse_midi_message(bytes).unwrap(),
+            (MidiEventRef::ManufacturerSysex { id, data }, bytes.len())
+        );
+        assert_eq!(
+            peek_midi_kind(bytes, bytes.len()),
+            Ok(MessageKind::ManufacturerSysex)
+        );
+        assert!(!event.kind().is_sync());
+        assert_eq!(event.kind().wire_len(), None);
+    }
+
+    let long = MidiEvent::ManufacturerSysex {
+        id: ManufacturerId::Short(0x43),
+        data: (0..100).collect(),
+    };
+    let bytes = build_midi_list(&long);
+    assert_eq!(bytes.len(), 103);
+    assert_eq!(parse_midi_list(&bytes, bytes.len()).unwrap(), long);
+}
+
+#[test]
+fn test_manufacturer_id() {
+    assert_eq!(
+        ManufacturerId::parse(&[0x41, 0x10]),
+        Some(ManufacturerId::Short(0x41))
+    );
+    assert_eq!(
+        ManufacturerId::parse(&[0x00, 0x20, 0x33]),
+        Some(ManufacturerId::Extended([0x20, 0x33]))
+    );
+    // Reserved, truncated or not 7-bit
+    for bytes in [
+        &[0x7D][..],
+        &[0x7E],
+        &[0x7F],
+        &[0x00, 0x20],
+        &[0x00, 0x20, 0xF7],
+        &[0x80],
+        &[],
+    ] {
+        assert_eq!(ManufacturerId::parse(bytes), None, "{:02X?}", bytes);
+    }
+
+    let extended = ManufacturerId::Extended([0x20, 0x33]);
+    assert_eq!(extended.bytes().collect::<Vec<_>>(), [0x00, 0x20, 0x33]);
+    assert_eq!(extended.to_string(), "00 20 33");
+    assert_eq!(
+        ManufacturerId::Short(0x41).bytes().collect::<Vec<_>>(),
+        [0x41]
+    );
+    assert_eq!(ManufacturerId::Short(0x41).to_string(), "41");
+}
+
+#[test]
+fn test_malformed_manufacturer_sysex() {
+    let cases: [(&[u8], ParseError); 3] = [
+        // No terminator
+        (
+            &[0xF0, 0x41, 0x10, 0x42],
+            ParseError::BufferTooSmall {
+                requested: 5,
+                available: 4,
+            },
+        ),
+        // Terminated within the three-byte ID
+        (
+            &[0xF0, 0x00, 0x20, 0xF7],
+            ParseError::InvalidMidiData {
+                position: 3,
+                byte: 0xF7,
+            },
+        ),
+        // A note-on inside the data
+        (
+            &[0xF0, 0x41, 0x90, 0x40, 0xF7],
+            ParseError::InvalidMidiData {
+                position: 2,
+                byte: 0x90,
+            },
+        ),
+    ];
+    for (list, error) in cases {
+        assert_eq!(
+            parse_midi_list(list, list.len()),
+            Ok(MidiEvent::Other(list.to_vec()))
+        );
+        assert_eq!(parse_midi_list_strict(list, list.len()), Err(error));
+    }
+
+    // Non-commercial and universal non-real-time IDs are not manufacturers
+    for list in [&[0xF0, 0x7D, 0x01, 0xF7], &[0xF0, 0x7E, 0x01, 0xF7]] {
+        assert_eq!(
+            parse_midi_list(list, list.len()),
+            Ok(MidiEvent::Other(list.to_vec()))
+        );
+    }
+}
+
+#[test]
+fn test_identity_request_and_reply() {
+    let request = [0xF0, 0x7E, 0x7F, 0x06, 0x01, 0xF7];
+    let event = MidiEvent::IdentityRequest { device_id: 0x7F };
+    assert_eq!(parse_midi_list(&request, request.len()), Ok(event.clone()));
+    assert_eq!(build_midi_list(&event), request);
+    assert_eq!(
+        MessageKind::IdentityRequest.wire_len(),
+        Some(IDENTITY_REQUEST_LENGTH)
+    );
+
+    let replies: [(&[u8], DeviceIdentity); 2] = [
+        (
+            &[
+                0xF0, 0x7E, 0x10, 0x06, 0x02, 0x41, 0x02, 0x01, 0x7F, 0x7F, 0x01, 0x00, 0x02, 0x00,
+                0xF7,
+            ],
+            DeviceIdentity {
+                device_id: 0x10,
+                manufacturer: ManufacturerId::Short(0x41),
+                family: 0x0082,
+                model: 0x3FFF,
+                version: [0x01, 0x00, 0x02, 0x00],
+            },
+        ),
+        (
+            &[
+                0xF0, 0x7E, 0x00, 0x06, 0x02, 0x00, 0x20, 0x33, 0x05, 0x00, 0x01, 0x00, 0x00, 0x00,
+                0x00, 0x01, 0xF7,
+            ],
+            DeviceIdentity {
+                device_id: 0x00,
+                manufacturer: ManufacturerId::Extended([0x20, 0x33]),
+                family: 0x0005,
+                model: 0x0001,
+                version: [0x00, 0x00, 0x00, 0x01],
+            },
+        ),
+    ];
+    for (bytes, identity) in replies {
+        assert_eq!(identity.reply_len(), bytes.len());
+        let event = MidiEvent::IdentityReply(identity);
+        assert_eq!(parse_midi_list(bytes, bytes.len()), Ok(event.clone()));
+        assert_eq!(build_midi_list(&event), bytes);
+        assert!(!event.kind().is_sync());
+    }
+    assert_eq!(
+        DeviceIdentity {
+            manufacturer: ManufacturerId::Extended([0x20, 0x33]),
+            ..replies[0].1
+        }
+        .reply_len(),
+        IDENTITY_REPLY_MAX_LENGTH
+    );
+}
+
+#[test]
+fn test_malformed_identity_messages() {
+    let cases: [&[u8]; 5] = [
+        // Request with a trailing data byte
+        &[0xF0, 0x7E, 0x7F, 0x06, 0x01, 0x00, 0xF7],
+        // Reply missing its last version byte
+        &[
+            0xF0, 0x7E, 0x10, 0x06, 0x02, 0x41, 0x02, 0x01, 0x7F, 0x7F, 0x01, 0x00, 0x02, 0xF7,
+        ],
+        // Reply with a reserved manufacturer ID
+        &[
+            0xF0, 0x7E, 0x10, 0x06, 0x02, 0x7D, 0x02, 0x01, 0x7F, 0x7F, 0x01, 0x00, 0x02, 0x00,
+            0xF7,
+        ],
+        // Reply with a byte with the high bit set
+        &[
+            0xF0, 0x7E, 0x10, 0x06, 0x02, 0x41, 0x82, 0x01, 0x7F, 0x7F, 0x01, 0x00, 0x02, 0x00,
+            0xF7,
+        ],
+        // Unterminated request
+        &[0xF0, 0x7E, 0x7F, 0x06, 0x01],
+    ];
+    for list in cases {
+        assert_eq!(
+            parse_midi_list(list, list.len()),
+            Ok(MidiEvent::Other(list.to_vec())),
+            "{:02X?}",
+            list
+        );
+    }
+}
@@ -2,8 +2,11 @@
 mod mtc_tests {
     use rtp_midi_netsync::error::MtcError;
     use rtp_midi_netsync::mtc::{
-        quarter_frames_to_smpte, smpte_to_quarter_frames, smpte_to_us, us_to_smpte, MtcFullFrame,
-        MtcQuarterFrame,
+        convert_rate, convert_rate_us, quarter_frame_at, quarter_frames_to_smpte,
+        quarter_frames_to_smpte_rate, quarter_frames_to_smpte_slice, smpte_to_quarter_frames,
+        smpte_to_quarter_frames_rate, smpte_to_us, smpte_to_us_pulled, smpte_to_us_rate,
+        us_to_smpte, us_to_smpte_pulled, us_to_smpte_rate, DriftEstimator, FrameRate, MtcFullFrame,
+        MtcQuarterFrame, Pull, QuarterFrameAssembler, Rounding,
     };
 
     // === Helper Functions ===
@@ -177,11 +180,7 @@ mod mtc_tests {
             let result_us = smpte_to_us(&smpte);
 
             // Allow small rounding differences due to floating point arithmetic
-            let diff = if original_us > result_us {
-                original_us - result_us
-            } else {
-                result_us - original_us
-            };
+            let diff = original_us.abs_diff(result_us);
 
             assert!(
                 diff <= 33_333, // Within one frame duration
@@ -242,10 +241,7 @@ mod mtc_tests {
         assert_eq!(frames[4], create_test_quarter_frame(4, 34 & 0x0F)); // minutes low = 2
         assert_eq!(frames[5], create_test_quarter_frame(5, (34 >> 4) & 0x03)); // minutes high = 2
         assert_eq!(frames[6], create_test_quarter_frame(6, 12 & 0x0F)); // hours low = 12 (0x0C)
-        assert_eq!(
-            frames[7],
-            create_test_quarter_frame(7, ((12 >> 4) & 0x01) | (0x03 << 1))
-        ); // hours high + frame rate
+        assert_eq!(frames[7], create_test_quarter_frame(7, 0x03 << 1)); // hours high = 0 + frame rate
     }
 
     #[test]
@@ -355,7 +351,7 @@ mod mtc_tests {
         ];
 
         let result = quarter_frames_to_smpte(&frames);
-        assert_eq!(result, Err(MtcError::InvalidValue));
+        assert_eq!(result, Err(MtcError::InvalidFrames(16)));
     }
 
     #[test]
@@ -373,7 +369,7 @@ mod mtc_tests {
         ];
 
         let result = quarter_frames_to_smpte(&frames);
-        assert_eq!(result, Err(MtcError::InvalidValue));
+        assert_eq!(result, Err(MtcError::InvalidFrames(30)));
     }
 
     #[test]
@@ -391,7 +387,7 @@ mod mtc_tests {
         ];
 
         let result = quarter_frames_to_smpte(&frames);
-        assert_eq!(result, Err(MtcError::InvalidValue));
+        assert_eq!(result, Err(MtcError::InvalidSeconds(60)));
     }
 
     #[test]
@@ -409,7 +405,7 @@ mod mtc_tests {
         ];
 
         let result = quarter_frames_to_smpte(&frames);
-        assert_eq!(result, Err(MtcError::InvalidValue));
+        assert_eq!(result, Err(MtcError::InvalidMinutes(60)));
     }
 
     #[test]
@@ -427,7 +423,7 @@ mod mtc_tests {
         ];
 
         let result = quarter_frames_to_smpte(&frames);
-        assert_eq!(result, Err(MtcError::InvalidValue));
+        assert_eq!(result, Err(MtcError::InvalidHours(24)));
     }
 
     #[test]
@@ -445,7 +441,7 @@ mod mtc_tests {
         ];
 
         let result = quarter_frames_to_smpte(&frames);
-        assert_eq!(result, Err(MtcError::InvalidValue));
+        assert_eq!(result, Err(MtcError::UnsupportedFrameRate(2)));
     }
 
     // === Edge Case Tests ===
@@ -539,4 +535,722 @@ mod mtc_tests {
         assert_eq!(result.seconds, 0);
         assert_eq!(result.frames, 0);
     }
+
+    // === Quarter Frame Assembler Tests ===
+
+    #[test]
+    fn test_assembler_complete_cycle() {
+        let smpte = create_test_smpte(1, 2, 3, 4);
+        let mut assembler = QuarterFrameAssembler::new();
+
+        let pieces = smpte_to_quarter_frames(&smpte);
+        for piece in &pieces[..7] {
+            assert_eq!(assembler.feed(*piece), Ok(None));
+        }
+        assert_eq!(assembler.feed(pieces[7]), Ok(Some(smpte)));
+        assert_eq!(assembler.pending(), 0);
+    }
+
+    #[test]
+    fn test_assembler_waits_for_piece_zero() {
+        let smpte = create_test_smpte(10, 20, 30, 5);
+        let pieces = smpte_to_quarter_frames(&smpte);
+        let mut assembler = QuarterFrameAssembler::new();
+
+        // Joining mid-cycle: pieces 4-7 must not produce a timecode
+        for piece in &pieces[4..] {
+            assert_eq!(assembler.feed(*piece), Ok(None));
+        }
+        for piece in &pieces[..7] {
+            assert_eq!(assembler.feed(*piece), Ok(None));
+        }
+        assert_eq!(assembler.feed(pieces[7]), Ok(Some(smpte)));
+    }
+
+    #[test]
+    fn test_assembler_ignores_duplicate_piece() {
+        let smpte = create_test_smpte(0, 0, 59, 29);
+        let pieces = smpte_to_quarter_frames(&smpte);
+        let mut assembler = QuarterFrameAssembler::new();
+
+        for piece in &pieces[..4] {
+            assert_eq!(assembler.feed(*piece), Ok(None));
+        }
+        assert_eq!(assembler.feed(pieces[3]), Ok(None));
+        assert_eq!(assembler.pending(), 4);
+        for piece in &pieces[4..7] {
+            assert_eq!(assembler.feed(*piece), Ok(None));
+        }
+        assert_eq!(assembler.feed(pieces[7]), Ok(Some(smpte)));
+    }
+
+    #[test]
+    fn test_assembler_gap_discards_cycle() {
+        let pieces = smpte_to_quarter_frames(&create_test_smpte(0, 1, 0, 0));
+        let mut assembler = QuarterFrameAssembler::new();
+
+        for piece in &pieces[..3] {
+            assembler.feed(*piece).unwrap();
+        }
+        // Piece 3 lost
+        for piece in &pieces[4..] {
+            assert_eq!(assembler.feed(*piece), Ok(None));
+        }
+        assert_eq!(assembler.pending(), 0);
+    }
+
+    #[test]
+    fn test_assembler_invalid_pieces() {
+        let mut assembler = QuarterFrameAssembler::new();
+        assert_eq!(
+            assembler.feed(create_test_quarter_frame(8, 0)),
+            Err(MtcError::InvalidFrameType)
+        );
+        assert_eq!(
+            assembler.feed(create_test_quarter_frame(0, 16)),
+            Err(MtcError::InvalidFrames(16))
+        );
+
+        // A cycle encoding frame 31 completes but is rejected
+        let mut pieces = smpte_to_quarter_frames(&create_test_smpte(0, 0, 0, 0));
+        pieces[0].value = 0x0F;
+        pieces[1].value = 0x01;
+        for piece in &pieces[..7] {
+            assembler.feed(*piece).unwrap();
+        }
+        assert_eq!(assembler.feed(pieces[7]), Err(MtcError::InvalidFrames(31)));
+    }
+
+    // === Frame Rate Tests ===
+
+    #[test]
+    fn test_frame_rate_mtc_codes() {
+        for code in 0..4 {
+            let rate = FrameRate::from_mtc_code(code).unwrap();
+            assert_eq!(rate.mtc_code(), code);
+        }
+        assert_eq!(FrameRate::from_mtc_code(4), None);
+        assert_eq!(FrameRate::default(), FrameRate::Fps30);
+    }
+
+    #[test]
+    fn test_frame_rate_quarter_frame_periods() {
+        assert_eq!(FrameRate::Fps24.quarter_frame_period_us(), 10_416);
+        assert_eq!(FrameRate::Fps25.quarter_frame_period_us(), 10_000);
+        assert_eq!(FrameRate::Fps2997Df.quarter_frame_period_us(), 8_341);
+        assert_eq!(FrameRate::Fps30.quarter_frame_period_us(), 8_333);
+
+        // A full cycle spans exactly two frames
+        assert_eq!(FrameRate::Fps25.quarter_frames_to_us(8), 80_000);
+        assert_eq!(FrameRate::Fps30.quarter_frames_to_us(120), 1_000_000);
+        assert_eq!(FrameRate::Fps2997Df.quarter_frames_to_us(120), 1_001_000);
+    }
+
+    #[test]
+    fn test_rate_conversions_match_30fps_defaults() {
+        for us in [0, 33_333, 1_000_000, 3_723_456_789] {
+            assert_eq!(us_to_smpte_rate(us, FrameRate::Fps30), us_to_smpte(us));
+        }
+        let smpte = create_test_smpte(1, 2, 3, 29);
+        assert_eq!(
+            smpte_to_us_rate(&smpte, FrameRate::Fps30),
+            smpte_to_us(&smpte)
+        );
+        assert_eq!(
+            smpte_to_quarter_frames_rate(&smpte, FrameRate::Fps30),
+            smpte_to_quarter_frames(&smpte)
+        );
+    }
+
+    #[test]
+    fn test_rate_conversions_24_and_25fps() {
+        assert_eq!(
+            us_to_smpte_rate(1_040_000, FrameRate::Fps25),
+            create_test_smpte(0, 0, 1, 1)
+        );
+        assert_eq!(
+            us_to_smpte_rate(41_667, FrameRate::Fps24),
+            create_test_smpte(0, 0, 0, 1)
+        );
+        assert_eq!(
+            smpte_to_us_rate(&create_test_smpte(0, 0, 1, 24), FrameRate::Fps25),
+            1_960_000
+        );
+        assert_eq!(
+            smpte_to_us_rate(&create_test_smpte(1, 0, 0, 12), FrameRate::Fps24),
+            3_600_500_000
+        );
+    }
+
+    #[test]
+    fn test_drop_frame_labels_skip_minute_starts() {
+        let rate = FrameRate::Fps2997Df;
+        let cases = [
+            (create_test_smpte(0, 0, 59, 29), 1799),
+            (create_test_smpte(0, 1, 0, 2), 1800),
+            (create_test_smpte(0, 9, 59, 29), 17_981),
+            (create_test_smpte(0, 10, 0, 0), 17_982),
+            (create_test_smpte(0, 10, 0, 1), 17_983),
+        ];
+        for (smpte, framenum) in cases {
+            let us = framenum * 1001 * 1_000_000 / 30_000;
+            assert_eq!(smpte_to_us_rate(&smpte, rate), us);
+            assert_eq!(us_to_smpte_rate(us, rate), smpte);
+        }
+    }
+
+    #[test]
+    fn test_quarter_frames_rate_roundtrip() {
+        let smpte = create_test_smpte(17, 45, 30, 24);
+        for rate in [FrameRate::Fps25, FrameRate::Fps2997Df, FrameRate::Fps30] {
+            let pieces = smpte_to_quarter_frames_rate(&smpte, rate);
+            assert_eq!((pieces[7].value >> 1) & 0x03, rate.mtc_code());
+            assert_eq!(quarter_frames_to_smpte_rate(&pieces), Ok((smpte, rate)));
+        }
+
+        // Frame 24 does not exist at 24fps, and only 30fps passes the plain decoder
+        let pieces = smpte_to_quarter_frames_rate(&smpte, FrameRate::Fps24);
+        assert_eq!(
+            quarter_frames_to_smpte_rate(&pieces),
+            Err(MtcError::InvalidFrames(24))
+        );
+        let pieces = smpte_to_quarter_frames_rate(&smpte, FrameRate::Fps25);
+        assert_eq!(
+            quarter_frames_to_smpte(&pieces),
+            Err(MtcError::UnsupportedFrameRate(FrameRate::Fps25.mtc_code()))
+        );
+    }
+
+    #[test]
+    fn test_assembler_reports_signalled_rate() {
+        let mut assembler = QuarterFrameAssembler::new();
+        assert_eq!(assembler.frame_rate(), FrameRate::Fps30);
+
+        let smpte = create_test_smpte(0, 1, 0, 20);
+        let pieces = smpte_to_quarter_frames_rate(&smpte, FrameRate::Fps25);
+        let mut completed = None;
+        for piece in pieces {
+            completed = assembler.feed(piece).unwrap();
+        }
+        assert_eq!(completed, Some(smpte));
+        assert_eq!(assembler.frame_rate(), FrameRate::Fps25);
+    }
+
+    // === Text Form Tests ===
+
+    #[test]
+    fn test_smpte_display() {
+        let smpte = create_test_smpte(1, 2, 3, 4);
+        assert_eq!(smpte.to_string(), "01:02:03:04");
+        assert_eq!(format!("{:#}", smpte), "01:02:03;04");
+        assert_eq!(MtcFullFrame::default().to_string(), "00:00:00:00");
+        assert_eq!(create_test_smpte(23, 59, 59, 29).to_string(), "23:59:59:29");
+    }
+
+    #[test]
+    fn test_smpte_from_str_accepts_both_separators() {
+        let smpte = create_test_smpte(1, 2, 3, 4);
+        assert_eq!("01:02:03:04".parse(), Ok(smpte));
+        assert_eq!("01:02:03;04".parse(), Ok(smpte));
+        assert_eq!("1:2:3:4".parse(), Ok(smpte));
+        assert_eq!("23:59:59:29".parse(), Ok(create_test_smpte(23, 59, 59, 29)));
+
+        // Formatting and parsing round-trip
+        for text in ["00:00:00:00", "12:34:56:07", "23:59:59;29"] {
+            let parsed: MtcFullFrame = text.parse().unwrap();
+            let formatted = if text.contains(';') {
+                format!("{:#}", parsed)
+            } else {
+                parsed.to_string()
+            };
+            assert_eq!(formatted, text);
+        }
+    }
+
+    #[test]
+    fn test_smpte_from_str_rejects_bad_text() {
+        for text in [
+            "",
+            "01:02:03",
+            "01:02:03:04:05",
+            "01;02:03:04",
+            "01:02:03:",
+            "001:02:03:04",
+            "+1:02:03:04",
+            "01:02:03:4x",
+            " 01:02:03:04",
+            "01-02-03-04",
+        ] {
+            assert_eq!(
+                text.parse::<MtcFullFrame>(),
+                Err(MtcError::MalformedTimecode),
+                "{:?}",
+                text
+            );
+        }
+        for (text, error) in [
+            ("24:00:00:00", MtcError::InvalidHours(24)),
+            ("00:60:00:00", MtcError::InvalidMinutes(60)),
+            ("00:00:60:00", MtcError::InvalidSeconds(60)),
+            ("00:00:00:30", MtcError::InvalidFrames(30)),
+            // The first field out of range is reported
+            ("99:99:99;99", MtcError::InvalidHours(99)),
+        ] {
+            assert_eq!(text.parse::<MtcFullFrame>(), Err(error), "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn test_quarter_frames_to_smpte_slice() {
+        let smpte = create_test_smpte(1, 23, 45, 12);
+        let frames = smpte_to_quarter_frames(&smpte);
+        assert_eq!(quarter_frames_to_smpte_slice(&frames), Ok(smpte));
+
+        // Repeated pieces are redundant retransmissions
+        let repeated: Vec<MtcQuarterFrame> = frames.iter().flat_map(|&f| [f, f]).collect();
+        assert_eq!(quarter_frames_to_smpte_slice(&repeated), Ok(smpte));
+
+        assert_eq!(
+            quarter_frames_to_smpte_slice(&[]),
+            Err(MtcError::IncompleteFrame)
+        );
+        assert_eq!(
+            quarter_frames_to_smpte_slice(&repeated[..13]),
+            Err(MtcError::IncompleteFrame)
+        );
+
+        // A second cycle is not a repeat of the last piece
+        let two_cycles = [frames, frames].concat();
+        assert_eq!(
+            quarter_frames_to_smpte_slice(&two_cycles),
+            Err(MtcError::TooManyFrames)
+        );
+
+        // Out-of-sequence pieces are reported even before the cycle is complete
+        assert_eq!(
+            quarter_frames_to_smpte_slice(&[frames[0], frames[2]]),
+            Err(MtcError::InvalidFrameType)
+        );
+        // A repeat with another value is not a retransmission
+        let changed = [frames[0], create_test_quarter_frame(0, frames[0].value + 1)];
+        assert_eq!(
+            quarter_frames_to_smpte_slice(&changed),
+            Err(MtcError::InvalidFrameType)
+        );
+    }
+
+    // === Frame Rate Conversion Tests ===
+
+    const RATES: [FrameRate; 4] = [
+        FrameRate::Fps24,
+        FrameRate::Fps25,
+        FrameRate::Fps2997Df,
+        FrameRate::Fps30,
+    ];
+    const ROUNDINGS: [Rounding; 3] = [Rounding::Nearest, Rounding::Down, Rounding::Up];
+
+    #[test]
+    fn test_convert_rate_known_equivalences() {
+        let hour = create_test_smpte(1, 0, 0, 0);
+        for from in RATES {
+            for to in RATES {
+                // Drop-frame hours are 3.6ms short of wall-clock hours
+                assert_eq!(convert_rate(&hour, from, to, Rounding::Nearest), hour);
+                if from != FrameRate::Fps2997Df && to != FrameRate::Fps2997Df {
+                    for rounding in ROUNDINGS {
+                        assert_eq!(convert_rate(&hour, from, to, rounding), hour);
+                    }
+                }
+            }
+        }
+
+        // Same-rate conversions keep the timecode
+        for rate in RATES {
+            let tc = create_test_smpte(12, 34, 56, 7);
+            for rounding in ROUNDINGS {
+                assert_eq!(convert_rate(&tc, rate, rate, rounding), tc);
+            }
+        }
+
+        // Frame 15 at 30fps falls halfway between frames 12 and 13 at 25fps
+        let half = create_test_smpte(0, 0, 10, 15);
+        let convert = |rounding| convert_rate(&half, FrameRate::Fps30, FrameRate::Fps25, rounding);
+        assert_eq!(convert(Rounding::Nearest), create_test_smpte(0, 0, 10, 13));
+        assert_eq!(convert(Rounding::Down), create_test_smpte(0, 0, 10, 12));
+        assert_eq!(convert(Rounding::Up), create_test_smpte(0, 0, 10, 13));
+
+        // Drop-frame labels follow the wall clock, ten minutes being 0.6ms short of it
+        let ten_minutes = create_test_smpte(0, 10, 0, 0);
+        let convert = |rounding| {
+            convert_rate(
+                &ten_minutes,
+                FrameRate::Fps2997Df,
+                FrameRate::Fps30,
+                rounding,
+            )
+        };
+        assert_eq!(convert(Rounding::Nearest), ten_minutes);
+        assert_eq!(convert(Rounding::Down), create_test_smpte(0, 9, 59, 29));
+    }
+
+    #[test]
+    fn test_convert_rate_us_matches_plain_conversions() {
+        for rate in RATES {
+            for us in [0, 1, 20_000, 41_667, 3_600_000_000, 86_399_999_999] {
+                assert_eq!(
+                    convert_rate_us(us, rate, Rounding::Nearest),
+                    us_to_smpte_rate(us, rate)
+                );
+            }
+        }
+
+        // Times exact in microseconds convert the same way both routes
+        for to in RATES {
+            for rounding in ROUNDINGS {
+                let tc = create_test_smpte(3, 21, 9, 17);
+                let us = smpte_to_us_rate(&tc, FrameRate::Fps25);
+                assert_eq!(
+                    convert_rate(&tc, FrameRate::Fps25, to, rounding),
+                    convert_rate_us(us, to, rounding)
+                );
+            }
+        }
+
+        // Frame 1 at 30fps starts at 33333.3us, which truncating would round down
+        let tc = create_test_smpte(0, 0, 0, 1);
+        let truncated = smpte_to_us(&tc);
+        assert_eq!(
+            convert_rate_us(truncated, FrameRate::Fps30, Rounding::Down),
+            create_test_smpte(0, 0, 0, 0)
+        );
+        assert_eq!(
+            convert_rate(&tc, FrameRate::Fps30, FrameRate::Fps30, Rounding::Down),
+            tc
+        );
+    }
+
+    #[test]
+    fn test_convert_rate_error_bounds() {
+        for from in RATES {
+            for to in RATES {
+                let frame = to.frame_duration_us() as i64 + 1;
+                let day = smpte_to_us_rate(&create_test_smpte(24, 0, 0, 0), to) as i64;
+
+                for hours in 0..24 {
+                    for minutes in [0, 1, 9, 10, 29, 59] {
+                        for seconds in [0, 1, 30, 59] {
+                            for frames in 0..from.nominal_fps() as u8 {
+                                let tc = create_test_smpte(hours, minutes, seconds, frames);
+                                let source = smpte_to_us_rate(&tc, from) as i64;
+
+                                for rounding in ROUNDINGS {
+                                    let converted = convert_rate(&tc, from, to, rounding);
+                                    let mut target = smpte_to_us_rate(&converted, to) as i64;
+                                    // Rounding up past the end of the day wraps to midnight
+                                    if target < source - day / 2 {
+                                        target += day;
+                                    }
+                                    // One microsecond lost truncating either time
+                                    let error = target - source;
+                                    let within = match rounding {
+                                        Rounding::Nearest => 2 * (error.abs() - 1) <= frame,
+                                        Rounding::Down => error <= 1 && -error < frame,
+                                        Rounding::Up => error >= -1 && error < frame,
+                                    };
+                                    assert!(
+                                        within,
+                                        "{} at {:?} to {:?} rounding {:?} gives {}, {}us off",
+                                        tc, from, to, rounding, converted, error
+                                    );
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // === Pull Tests ===
+
+    #[test]
+    fn test_pulled_conversions() {
+        let hour_us = 3_600_000_000;
+        let hour = create_test_smpte(1, 0, 0, 0);
+
+        // An hour of clock time is 3.6 seconds less or more of pulled timecode
+        assert_eq!(
+            us_to_smpte_pulled(hour_us, FrameRate::Fps30, Pull::Down01),
+            create_test_smpte(0, 59, 56, 12)
+        );
+        assert_eq!(
+            us_to_smpte_pulled(hour_us, FrameRate::Fps30, Pull::Up01),
+            create_test_smpte(1, 0, 3, 18)
+        );
+        // 23.976fps film pulled down from 24fps
+        assert_eq!(
+            us_to_smpte_pulled(hour_us, FrameRate::Fps24, Pull::Down01),
+            create_test_smpte(0, 59, 56, 10)
+        );
+        assert_eq!(
+            smpte_to_us_pulled(&hour, FrameRate::Fps30, Pull::Down01),
+            3_603_600_000
+        );
+        assert_eq!(
+            smpte_to_us_pulled(&hour, FrameRate::Fps30, Pull::Up01),
+            3_596_403_596
+        );
+
+        for rate in RATES {
+            for us in [0, 41_667, hour_us, 86_399_999_999] {
+                assert_eq!(
+                    us_to_smpte_pulled(us, rate, Pull::None),
+                    us_to_smpte_rate(us, rate)
+                );
+            }
+            for pull in [Pull::None, Pull::Up01, Pull::Down01] {
+                let tc = create_test_smpte(7, 8, 9, 10);
+                let us = smpte_to_us_pulled(&tc, rate, pull);
+                assert_eq!(us_to_smpte_pulled(us, rate, pull), tc);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pull_ratios() {
+        assert_eq!(Pull::default(), Pull::None);
+        assert_eq!(Pull::Down01.apply(1_001_000), 1_000_000);
+        assert_eq!(Pull::Down01.remove(1_000_000), 1_001_000);
+        assert_eq!(Pull::Up01.apply(1_000_000), 1_001_000);
+        assert_eq!(Pull::Up01.remove(1_001_000), 1_000_000);
+        assert_eq!(Pull::None.apply(12_345), 12_345);
+        // Rounded down
+        assert_eq!(Pull::Down01.apply(1_000), 999);
+    }
+
+    // === Drop-Frame Validity Tests ===
+
+    #[test]
+    fn test_drop_frame_validity() {
+        let df = FrameRate::Fps2997Df;
+        assert!(!create_test_smpte(0, 5, 0, 0).is_valid_for(df));
+        assert!(!create_test_smpte(0, 5, 0, 1).is_valid_for(df));
+        assert!(create_test_smpte(0, 5, 0, 2).is_valid_for(df));
+        assert!(create_test_smpte(0, 10, 0, 0).is_valid_for(df));
+        assert!(create_test_smpte(0, 5, 1, 0).is_valid_for(df));
+        assert!(create_test_smpte(0, 5, 0, 1).is_valid_for(FrameRate::Fps30));
+
+        // Fields out of range
+        assert!(create_test_smpte(23, 59, 59, 29).is_valid_for(FrameRate::Fps30));
+        assert!(!create_test_smpte(23, 59, 59, 29).is_valid_for(FrameRate::Fps25));
+        assert!(!create_test_smpte(24, 0, 0, 0).is_valid_for(FrameRate::Fps30));
+        assert!(!create_test_smpte(0, 60, 0, 0).is_valid_for(FrameRate::Fps30));
+
+        let dropped = create_test_smpte(0, 5, 0, 1);
+        assert_eq!(dropped.next_valid(df), create_test_smpte(0, 5, 0, 2));
+        assert_eq!(dropped.prev_valid(df), create_test_smpte(0, 4, 59, 29));
+        let valid = create_test_smpte(0, 10, 0, 0);
+        assert_eq!(valid.next_valid(df), valid);
+        assert_eq!(valid.prev_valid(df), valid);
+        assert_eq!(dropped.next_valid(FrameRate::Fps30), dropped);
+
+        // Skipped labels are read as the next valid one
+        assert_eq!(
+            smpte_to_us_rate(&create_test_smpte(0, 1, 0, 0), df),
+            smpte_to_us_rate(&create_test_smpte(0, 1, 0, 2), df)
+        );
+    }
+
+    #[test]
+    fn test_drop_frame_ten_minute_cycle() {
+        let df = FrameRate::Fps2997Df;
+        let start = create_test_smpte(1, 20, 0, 0);
+        let end = create_test_smpte(1, 30, 0, 0);
+
+        let mut tc = start;
+        let mut count = 0;
+        while tc != end {
+            let next = tc.add_frames(1, df);
+            assert!(next.is_valid_for(df), "{:#} follows {:#}", next, tc);
+            assert_eq!(next.add_frames(-1, df), tc);
+            tc = next;
+            count += 1;
+        }
+        assert_eq!(count, 17_982);
+        assert_eq!(start.add_frames(17_982, df), end);
+
+        // Every label of the cycle, valid or not
+        let mut valid = 0;
+        for minutes in 20..30 {
+            for seconds in 0..60 {
+                for frames in 0..30 {
+                    valid += create_test_smpte(1, minutes, seconds, frames).is_valid_for(df) as u32;
+                }
+            }
+        }
+        assert_eq!(valid, 17_982);
+    }
+
+    #[test]
+    fn test_add_frames_wraps_and_skips() {
+        let df = FrameRate::Fps2997Df;
+        assert_eq!(
+            create_test_smpte(0, 0, 59, 29).add_frames(1, df),
+            create_test_smpte(0, 1, 0, 2)
+        );
+        assert_eq!(
+            create_test_smpte(0, 1, 0, 2).add_frames(-1, df),
+            create_test_smpte(0, 0, 59, 29)
+        );
+        assert_eq!(
+            create_test_smpte(0, 1, 0, 0).add_frames(0, df),
+            create_test_smpte(0, 1, 0, 2)
+        );
+
+        // Midnight in both directions
+        for rate in RATES {
+            let last = create_test_smpte(0, 0, 0, 0).add_frames(-1, rate);
+            assert_eq!(
+                last,
+                create_test_smpte(23, 59, 59, rate.nominal_fps() as u8 - 1)
+            );
+            assert_eq!(last.add_frames(1, rate), create_test_smpte(0, 0, 0, 0));
+        }
+        assert_eq!(
+            create_test_smpte(12, 0, 0, 0).add_frames(25 * 3600 * 13, FrameRate::Fps25),
+            create_test_smpte(1, 0, 0, 0)
+        );
+    }
+
+    // === Quarter Frame Scheduling Tests ===
+
+    #[test]
+    fn test_quarter_frame_at_rebuilds_cycles() {
+        for rate in RATES {
+            // Cycles at the start, in the middle and at the end of the day
+            let day_quarter_frames = 4 * smpte_to_us_rate(&create_test_smpte(24, 0, 0, 0), rate)
+                / rate.frame_duration_us();
+            for start in [0, 8 * 12_345, day_quarter_frames / 8 * 8 - 8] {
+                let expected = us_to_smpte_rate(rate.quarter_frames_to_us(start), rate);
+                let mut assembler = QuarterFrameAssembler::new();
+                let mut assembled = None;
+
+                for piece in 0..8 {
+                    let due_us = rate.quarter_frames_to_us(start + piece);
+                    let halfway_us = due_us + rate.quarter_frame_period_us() / 2;
+                    for us in [due_us, halfway_us] {
+                        let (index, frame) = quarter_frame_at(us, rate);
+                        assert_eq!(u64::from(index), piece);
+                        assert_eq!(u64::from(frame.frame_type), piece);
+                    }
+                    // The previous piece until this one is due
+                    if start + piece > 0 {
+                        assert_eq!(
+                            u64::from(quarter_frame_at(due_us - 1, rate).0),
+                            (piece + 7) % 8
+                        );
+                    }
+                    assembled = assembler.feed(quarter_frame_at(due_us, rate).1).unwrap();
+                }
+
+                assert_eq!(assembled, Some(expected), "{:?} at {}", rate, start);
+                assert_eq!(assembler.frame_rate(), rate);
+            }
+        }
+    }
+
+    #[test]
+    fn test_quarter_frame_at_values() {
+        // 01:02:03:04 at 30fps, the second quarter frame of its cycle
+        let us = smpte_to_us(&create_test_smpte(1, 2, 3, 4)) + 10_000;
+        assert_eq!(
+            quarter_frame_at(us, FrameRate::Fps30),
+            (1, create_test_quarter_frame(1, 0))
+        );
+        // Piece 7 of the cycle starting at frame 4 carries the hours and rate
+        let us = smpte_to_us(&create_test_smpte(17, 2, 3, 5)) + 30_000;
+        assert_eq!(
+            quarter_frame_at(us, FrameRate::Fps30),
+            (7, create_test_quarter_frame(7, 0x01 | 3 << 1))
+        );
+        // At 25fps cycles start on even frames counted from midnight, which are
+        // odd labels every other second
+        let us = smpte_to_us_rate(&create_test_smpte(0, 0, 1, 1), FrameRate::Fps25);
+        assert_eq!(quarter_frame_at(us, FrameRate::Fps25).0, 0);
+    }
+
+    // === Drift Estimator Tests ===
+
+    /// Feeds a quarter-frame cycle every 66.7 ms for two minutes from a master
+    /// running `ppm` fast, starting at 01:00:00:00, with up to 250 us of jitter.
+    fn drift_stream(estimator: &mut DriftEstimator, ppm: f64, start_us: u64) {
+        let mut seed = 0x2545_F491_u32;
+        for cycle in 0..1_800u64 {
+            let master_us = 3_600_000_000 + cycle * 200_000 / 3;
+            let local_us = (master_us - 3_600_000_000) as f64 / (1.0 + ppm * 1e-6);
+            seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            let jitter_us = u64::from(seed >> 23) % 500;
+            estimator.observe(start_us + local_us as u64 + jitter_us, master_us);
+        }
+    }
+
+    #[test]
+    fn test_drift_estimator_converges() {
+        for ppm in [50.0, -200.0] {
+            let mut estimator = DriftEstimator::new(512);
+            drift_stream(&mut estimator, ppm, 10_000_000);
+            let rate = estimator.rate_ppm().unwrap();
+            assert!(
+                (rate - ppm).abs() < 5.0,
+                "{} ppm estimated as {}",
+                ppm,
+                rate
+            );
+
+            // The master was at 01:00:00:00 at local 10 s, 250 us of jitter later
+            let (local_us, master_us) = (10_000_000 + 120_000_000, 3_720_000_000);
+            let drift_us = (120_000_000.0 * ppm * 1e-6) as i64;
+            let expected = master_us - local_us + drift_us - 250;
+            let offset = estimator.offset_us().unwrap();
+            assert!(
+                (offset - expected).abs() < 500,
+                "offset {} for {}",
+                offset,
+                expected
+            );
+            assert_eq!(estimator.samples(), 1_800);
+        }
+    }
+
+    #[test]
+    fn test_drift_estimator_resets_on_locate() {
+        let mut estimator = DriftEstimator::new(64);
+        assert_eq!(estimator.rate_ppm(), None);
+        assert_eq!(estimator.offset_us(), None);
+
+        assert!(!estimator.observe(1_000_000, 5_000_000));
+        assert_eq!(estimator.rate_ppm(), None);
+        assert_eq!(estimator.offset_us(), Some(4_000_000));
+        assert!(!estimator.observe(2_000_000, 6_000_100));
+        assert!((estimator.rate_ppm().unwrap() - 100.0).abs() < 1e-6);
+        assert_eq!(estimator.samples(), 2);
+
+        // Located ten seconds ahead
+        assert!(estimator.observe(3_000_000, 17_000_000));
+        assert_eq!(estimator.samples(), 1);
+        assert_eq!(estimator.rate_ppm(), None);
+        assert_eq!(estimator.offset_us(), Some(14_000_000));
+        assert!(!estimator.observe(4_000_000, 17_999_800));
+        assert!((estimator.rate_ppm().unwrap() + 200.0).abs() < 1e-6);
+
+        // Local time going backwards
+        assert!(estimator.observe(3_500_000, 17_500_000));
+        assert_eq!(estimator.samples(), 1);
+
+        // A tighter threshold
+        let mut estimator = DriftEstimator::with_discontinuity(64, 1_000);
+        estimator.observe(0, 0);
+        assert!(!estimator.observe(1_000_000, 1_000_900));
+        assert!(estimator.observe(2_000_000, 2_003_000));
+    }
 }
@@ -0,0 +1,138 @@
+#![cfg(feature = "tokio")]
+
+use std::future::poll_fn;
+use std::pin::Pin;
+use std::time::Duration;
+
+use futures_core::Stream;
+use tokio::net::UdpSocket;
+use tokio::time::timeout;
+
+use rtp_midi_netsync::midi::MidiEvent;
+use rtp_midi_netsync::mtc::{smpte_to_us, MtcFullFrame};
+use rtp_midi_netsync::netsync::net::{
+    NetsyncEvent, NetsyncReceiver, NetsyncSender, KEEPALIVE_INTERVAL,
+};
+use rtp_midi_netsync::netsync::NetsyncConfig;
+
+const START: MtcFullFrame = MtcFullFrame {
+    hours: 1,
+    minutes: 0,
+    seconds: 0,
+    frames: 0,
+};
+
+/// A sender connected to a receiver over loopback
+async fn link() -> (NetsyncSender, NetsyncReceiver) {
+    let receiver = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let sender = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    sender
+        .connect(receiver.local_addr().unwrap())
+        .await
+        .unwrap();
+    let config = NetsyncConfig::default();
+    (
+        NetsyncSender::new(sender, config),
+        NetsyncReceiver::new(receiver, config),
+    )
+}
+
+async fn next(receiver: &mut NetsyncReceiver) -> NetsyncEvent {
+    let item = timeout(
+        Duration::from_secs(1),
+        poll_fn(|cx| Pin::new(&mut *receiver).poll_next(cx)),
+    )
+    .await
+    .expect("no event within a second");
+    item.expect("stream ended").unwrap()
+}
+
+#[tokio::test]
+async fn test_transport_sequence() {
+    let (mut sender, mut receiver) = link().await;
+
+    sender.seek(START).await.unwrap();
+    assert_eq!(next(&mut receiver).await, NetsyncEvent::Locate(START));
+    sender.play().await.unwrap();
+    assert_eq!(next(&mut receiver).await, NetsyncEvent::Play);
+
+    // The first tick refreshes the Full Frame, followed by Play while rolling
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    sender.tick().await.unwrap();
+    let NetsyncEvent::TimeUpdate(position_us) = next(&mut receiver).await else {
+        panic!("expected a time update");
+    };
+    let start_us = smpte_to_us(&START);
+    assert!(
+        (start_us + 40_000..start_us + 500_000).contains(&position_us),
+        "{} us",
+        position_us
+    );
+    assert_eq!(next(&mut receiver).await, NetsyncEvent::Play);
+    assert!(receiver.session().tracker().is_playing());
+
+    sender.stop().await.unwrap();
+    assert_eq!(next(&mut receiver).await, NetsyncEvent::Stop);
+    assert!(!receiver.session().tracker().is_playing());
+    let held_us = receiver.position().unwrap();
+    assert!(held_us >= position_us && held_us < start_us + 500_000);
+
+    assert_eq!(receiver.session().metrics().packets_parsed, 5);
+    assert_eq!(receiver.session().metrics().sequence_gaps, 0);
+}
+
+#[tokio::test]
+async fn test_lost_packet_triggers_resync() {
+    let (mut sender, mut receiver) = link().await;
+    sender.seek(START).await.unwrap();
+    next(&mut receiver).await;
+    sender.tick().await.unwrap();
+    next(&mut receiver).await;
+    assert_eq!(sender.session().metrics().full_frames_refreshed, 1);
+
+    // A packet lost on the way opens a sequence gap
+    sender.session_mut().keepalive(0);
+    sender
+        .send_event(&MidiEvent::SongPosition(16))
+        .await
+        .unwrap();
+    assert_eq!(next(&mut receiver).await, NetsyncEvent::SongPosition(16));
+    assert_eq!(receiver.session().metrics().sequence_gaps, 1);
+
+    // The resync request reaches the sender, which answers with a Full Frame
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    sender.tick().await.unwrap();
+    assert_eq!(sender.session().metrics().resync_requests, 1);
+    assert_eq!(sender.session().metrics().full_frames_refreshed, 2);
+    assert_eq!(
+        next(&mut receiver).await,
+        NetsyncEvent::TimeUpdate(smpte_to_us(&START))
+    );
+}
+
+#[tokio::test]
+async fn test_idle_sender_sends_keepalive() {
+    let (mut sender, mut receiver) = link().await;
+    sender.tick().await.unwrap();
+    assert_eq!(sender.session().metrics().packets_sent, 0);
+
+    tokio::time::sleep(KEEPALIVE_INTERVAL).await;
+    sender.tick().await.unwrap();
+    assert_eq!(sender.session().metrics().packets_sent, 1);
+
+    // Keepalives carry no event: the stream yields nothing but counts it
+    sender.play().await.unwrap();
+    assert_eq!(next(&mut receiver).await, NetsyncEvent::Play);
+    assert_eq!(receiver.session().metrics().empty_payloads, 1);
+    assert_eq!(receiver.session().metrics().sequence_gaps, 0);
+}
+
+#[tokio::test]
+async fn test_unsupported_event_is_invalid_input() {
+    let (mut sender, _receiver) = link().await;
+    let err = sender
+        .send_event(&MidiEvent::Other(vec![0x90, 0x40, 0x7F]))
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
@@ -1,9 +1,23 @@
-use rtp_midi_netsync::error::NetsyncError;
-use rtp_midi_netsync::midi::{MidiEvent, MmcCommand};
+use rtp_midi_netsync::error::{FlowError, NetsyncError, ParseError};
+use rtp_midi_netsync::midi::{
+    MessageKind, MidiEvent, MidiEventRef, MmcCommand, MmcSpeed, RealtimeMessage,
+};
 use rtp_midi_netsync::midi::{
     MMC_LOCATE_LENGTH, MMC_START_STOP_LENGTH, MTC_FULL_FRAME_LENGTH, MTC_QUARTER_FRAME_LENGTH,
 };
-use rtp_midi_netsync::netsync::{master_netsync_flow, slave_netsync_flow};
+use rtp_midi_netsync::mtc::{
+    smpte_to_quarter_frames_rate, smpte_to_us, FrameRate, MtcFullFrame, QuarterFrameAssembler,
+};
+use rtp_midi_netsync::netsync::{
+    cue_and_play, is_timing_event, master_netsync_flow, master_netsync_flow_multi,
+    master_netsync_flow_slice, master_netsync_flow_to_device, master_netsync_flow_with,
+    master_payload_len, master_time_update, parse_all_payloads, payload_len, peek_event_kind,
+    slave_netsync_flow, slave_netsync_flow_buf, slave_netsync_flow_detailed,
+    slave_netsync_flow_opt, slave_netsync_flow_ref, slave_netsync_flow_stream,
+    slave_netsync_flow_with_device, slave_time_update, sync_burst, sync_burst_multi, BuildOptions,
+    CueOptions, TimeUpdateKind, MAX_PAYLOAD_LENGTH, MAX_QUARTER_FRAME_PADDING,
+};
+use rtp_midi_netsync::Error;
 
 #[test]
 fn test_master_flow_mmc_play() {
@@ -42,6 +56,69 @@ fn test_master_flow_mmc_locate() {
     assert_eq!(payload[0] & 0x0F, MMC_LOCATE_LENGTH as u8);
 }
 
+#[test]
+fn test_master_flow_mmc_shuttle() {
+    let event = MidiEvent::Mmc(MmcCommand::Shuttle {
+        speed: MmcSpeed::from_f32(-1.0),
+    });
+    let payload = master_netsync_flow(&event).unwrap();
+    assert_eq!(
+        payload,
+        [0x0A, 0xF0, 0x7F, 0x7F, 0x06, 0x47, 0x03, 0x41, 0x00, 0x00, 0xF7]
+    );
+    assert_eq!(slave_netsync_flow(&payload).unwrap(), event);
+    assert!(is_timing_event(&payload));
+}
+
+#[test]
+fn test_master_flow_mmc_variable_play() {
+    let event = MidiEvent::Mmc(MmcCommand::VariablePlay {
+        speed: MmcSpeed::from_f32(0.5),
+    });
+    let payload = master_netsync_flow(&event).unwrap();
+    assert_eq!(
+        payload,
+        [0x0A, 0xF0, 0x7F, 0x7F, 0x06, 0x45, 0x03, 0x00, 0x40, 0x00, 0xF7]
+    );
+    assert_eq!(slave_netsync_flow(&payload).unwrap(), event);
+    assert_eq!(peek_event_kind(&payload), Ok(MessageKind::VariablePlay));
+    assert!(is_timing_event(&payload));
+}
+
+#[test]
+fn test_master_flow_mmc_masked_write() {
+    let event = MidiEvent::Mmc(MmcCommand::arm_track(1, true).unwrap());
+    let payload = master_netsync_flow(&event).unwrap();
+    assert_eq!(
+        payload,
+        [0x0B, 0xF0, 0x7F, 0x7F, 0x06, 0x41, 0x04, 0x4F, 0x00, 0x20, 0x20, 0xF7]
+    );
+    assert_eq!(slave_netsync_flow(&payload).unwrap(), event);
+
+    // Every byte must be 7-bit clean
+    for (field, start, mask, data) in [
+        (0xCF, 0, 1, 1),
+        (0x4F, 0x80, 1, 1),
+        (0x4F, 0, 0x80, 0),
+        (0x4F, 0, 1, 0xFF),
+    ] {
+        let unclean = MidiEvent::Mmc(MmcCommand::MaskedWrite {
+            field,
+            start,
+            mask,
+            data,
+        });
+        assert_eq!(
+            master_netsync_flow(&unclean),
+            Err(NetsyncError::InvalidMasterEvent)
+        );
+        assert_eq!(
+            master_netsync_flow_multi(&[event.clone(), unclean]),
+            Err(NetsyncError::InvalidMasterEvent)
+        );
+    }
+}
+
 #[test]
 fn test_master_flow_mtc_quarter() {
     let event = MidiEvent::MtcQuarter {
@@ -78,12 +155,535 @@ fn test_master_flow_invalid_event() {
     assert_eq!(result.unwrap_err(), NetsyncError::InvalidMasterEvent);
 }
 
+#[test]
+fn test_master_payload_len_matches_flow() {
+    let events = [
+        MidiEvent::Mmc(MmcCommand::Play),
+        MidiEvent::Mmc(MmcCommand::Stop),
+        MidiEvent::Mmc(MmcCommand::Locate {
+            hour: 1,
+            minute: 2,
+            second: 3,
+            frame: 4,
+            subframe: 0,
+        }),
+        MidiEvent::MtcQuarter {
+            msg_type: 3,
+            value: 9,
+        },
+        MidiEvent::MtcFull {
+            hour: 1,
+            minute: 2,
+            second: 3,
+            frame: 4,
+        },
+        MidiEvent::Realtime(RealtimeMessage::Clock),
+        MidiEvent::SongPosition(96),
+    ];
+    let mut longest = 0;
+    for event in &events {
+        let len = master_payload_len(event).unwrap();
+        assert_eq!(len, master_netsync_flow(event).unwrap().len());
+        longest = longest.max(len);
+    }
+    assert_eq!(longest, MAX_PAYLOAD_LENGTH);
+    assert_eq!(
+        master_payload_len(&MidiEvent::Other(vec![0x90, 0x60, 0x7F])),
+        Err(NetsyncError::InvalidMasterEvent)
+    );
+}
+
+#[test]
+fn test_master_flow_multi() {
+    let play = MidiEvent::Mmc(MmcCommand::Play);
+    assert_eq!(
+        master_netsync_flow_multi(std::slice::from_ref(&play)),
+        master_netsync_flow(&play)
+    );
+
+    // Quarter frames fit the one-octet header
+    let quarter = |msg_type| MidiEvent::MtcQuarter { msg_type, value: 0 };
+    assert_eq!(
+        master_netsync_flow_multi(&[quarter(0), quarter(1)]).unwrap(),
+        [0x04, 0xF1, 0x00, 0xF1, 0x10]
+    );
+
+    // Play, Stop and two quarter frames need the two-octet header
+    let payload = master_netsync_flow_multi(&[
+        play.clone(),
+        MidiEvent::Mmc(MmcCommand::Stop),
+        quarter(2),
+        quarter(3),
+    ])
+    .unwrap();
+    assert_eq!(&payload[..2], &[0x80, 16]);
+    assert_eq!(payload.len(), 18);
+
+    assert_eq!(
+        master_netsync_flow_multi(&[]),
+        Err(NetsyncError::InvalidMasterEvent)
+    );
+    assert_eq!(
+        master_netsync_flow_multi(&[play, MidiEvent::Other(vec![0x90, 0x3C, 0x7F])]),
+        Err(NetsyncError::InvalidMasterEvent)
+    );
+    assert_eq!(
+        master_netsync_flow_multi(&vec![MidiEvent::Realtime(RealtimeMessage::Clock); 4096]),
+        Err(NetsyncError::InvalidMasterEvent)
+    );
+}
+
+#[test]
+fn test_sync_burst() {
+    let start = MtcFullFrame {
+        hours: 1,
+        minutes: 2,
+        seconds: 3,
+        frames: 4,
+    };
+    let full_frame = MidiEvent::MtcFull {
+        hour: 1,
+        minute: 2,
+        second: 3,
+        frame: 4,
+    };
+    let play = MidiEvent::Mmc(MmcCommand::Play);
+
+    // The position first, immediately followed by Play
+    let burst = sync_burst(&start).unwrap();
+    assert_eq!(
+        burst,
+        [
+            master_netsync_flow(&full_frame).unwrap(),
+            master_netsync_flow(&play).unwrap()
+        ]
+    );
+    assert_eq!(slave_netsync_flow(&burst[0]), Ok(full_frame.clone()));
+    assert_eq!(slave_netsync_flow(&burst[1]), Ok(play.clone()));
+
+    // The single payload carries the same MIDI list, behind the two-octet header
+    let single = sync_burst_multi(&start).unwrap();
+    assert_eq!(
+        single,
+        master_netsync_flow_multi(&[full_frame, play]).unwrap()
+    );
+    assert_eq!(&single[..2], &[0x80, 16]);
+    assert_eq!(single[2..], [&burst[0][1..], &burst[1][1..]].concat());
+
+    for start in [
+        MtcFullFrame { hours: 24, ..start },
+        MtcFullFrame {
+            frames: 30,
+            ..start
+        },
+    ] {
+        assert_eq!(sync_burst(&start), Err(NetsyncError::InvalidMasterEvent));
+        assert_eq!(
+            sync_burst_multi(&start),
+            Err(NetsyncError::InvalidMasterEvent)
+        );
+    }
+}
+
+#[test]
+fn test_cue_and_play() {
+    let target = MtcFullFrame {
+        hours: 1,
+        minutes: 2,
+        seconds: 3,
+        frames: 4,
+    };
+    let locate = vec![
+        0x0D, 0xF0, 0x7F, 0x7F, 0x06, 0x44, 0x06, 0x01, 0x01, 0x02, 0x03, 0x04, 0x00, 0xF7,
+    ];
+    let deferred_play = vec![0x06, 0xF0, 0x7F, 0x7F, 0x06, 0x03, 0xF7];
+
+    // Back to back by default
+    assert_eq!(
+        cue_and_play(&target, CueOptions::default()).unwrap(),
+        [(0, locate.clone()), (0, deferred_play.clone())]
+    );
+    let options = CueOptions { settle_us: 250_000 };
+    let sequence = cue_and_play(&target, options).unwrap();
+    assert_eq!(sequence, [(0, locate), (250_000, deferred_play)]);
+    assert_eq!(
+        slave_netsync_flow(&sequence[1].1),
+        Ok(MidiEvent::Mmc(MmcCommand::DeferredPlay))
+    );
+
+    assert_eq!(
+        cue_and_play(
+            &MtcFullFrame {
+                seconds: 60,
+                ..target
+            },
+            options
+        ),
+        Err(NetsyncError::InvalidMasterEvent)
+    );
+}
+
+#[test]
+fn test_master_flow_padded_quarter_frame() {
+    let quarter = MidiEvent::MtcQuarter {
+        msg_type: 2,
+        value: 3,
+    };
+    let padded = |quarter_frame_padding| BuildOptions {
+        quarter_frame_padding,
+    };
+
+    // Padded as MIDI-OX sends them, and decoded back to the plain quarter frame
+    let payload = master_netsync_flow_with(&quarter, &padded(2)).unwrap();
+    assert_eq!(payload, [0x04, 0xF1, 0x23, 0x00, 0x00]);
+    assert_eq!(slave_netsync_flow(&payload), Ok(quarter.clone()));
+
+    assert_eq!(
+        master_netsync_flow_with(&quarter, &BuildOptions::default()),
+        master_netsync_flow(&quarter)
+    );
+    let payload = master_netsync_flow_with(&quarter, &padded(MAX_QUARTER_FRAME_PADDING)).unwrap();
+    assert_eq!(payload.len(), MAX_PAYLOAD_LENGTH);
+    assert_eq!(slave_netsync_flow(&payload), Ok(quarter.clone()));
+    assert_eq!(
+        master_netsync_flow_with(&quarter, &padded(MAX_QUARTER_FRAME_PADDING + 1)),
+        Err(NetsyncError::InvalidMasterEvent)
+    );
+
+    // Other messages are never padded
+    for event in [
+        MidiEvent::Mmc(MmcCommand::Play),
+        MidiEvent::Realtime(RealtimeMessage::Clock),
+    ] {
+        assert_eq!(
+            master_netsync_flow_with(&event, &padded(MAX_QUARTER_FRAME_PADDING + 1)),
+            master_netsync_flow(&event)
+        );
+    }
+}
+
+#[test]
+fn test_master_time_update() {
+    let quarter = |byte| vec![0x02, 0xF1, byte];
+    // (position, Full Frame timecode, quarter frame due, cycle data bytes)
+    let cases: [(u64, [u8; 4], u8, [u8; 8]); 3] = [
+        (
+            0,
+            [0, 0, 0, 0],
+            0x00,
+            [0x00, 0x10, 0x20, 0x30, 0x40, 0x50, 0x60, 0x76],
+        ),
+        // 00:00:01:01 once rounded, during piece 3 of the cycle of 00:00:01:00
+        (
+            1_025_000,
+            [0, 0, 1, 1],
+            0x30,
+            [0x01, 0x10, 0x21, 0x30, 0x40, 0x50, 0x60, 0x76],
+        ),
+        // 01:01:01:15, during piece 4
+        (
+            3_661_500_000,
+            [1, 1, 1, 15],
+            0x41,
+            [0x0F, 0x10, 0x21, 0x30, 0x41, 0x50, 0x61, 0x76],
+        ),
+    ];
+    for (us, [hour, minute, second, frame], due, cycle) in cases {
+        assert_eq!(
+            master_time_update(us, TimeUpdateKind::FullFrame).unwrap(),
+            [vec![
+                0x0A, 0xF0, 0x7F, 0x7F, 0x01, 0x01, hour, minute, second, frame, 0xF7
+            ]]
+        );
+        assert_eq!(
+            master_time_update(us, TimeUpdateKind::QuarterFrame).unwrap(),
+            [quarter(due)]
+        );
+        assert_eq!(
+            master_time_update(us, TimeUpdateKind::QuarterFrameBurst).unwrap(),
+            cycle.map(quarter)
+        );
+    }
+
+    // Positions wrap past 24 hours
+    assert_eq!(
+        master_time_update(86_400_000_000 + 1_025_000, TimeUpdateKind::FullFrame),
+        master_time_update(1_025_000, TimeUpdateKind::FullFrame)
+    );
+}
+
+#[test]
+fn test_slave_time_update() {
+    let quarter = |byte| [0x02, 0xF1, byte];
+    let mut state = QuarterFrameAssembler::new();
+
+    // A Full Frame carries the time by itself
+    let full = master_time_update(3_661_500_000, TimeUpdateKind::FullFrame).unwrap();
+    assert_eq!(
+        slave_time_update(&full[0], &mut state),
+        Ok(Some(3_661_500_000))
+    );
+
+    // A quarter-frame cycle carries it once complete: 00:00:01:01
+    let cycle = [0x01, 0x10, 0x21, 0x30, 0x40, 0x50, 0x60, 0x76];
+    for byte in &cycle[..7] {
+        assert_eq!(slave_time_update(&quarter(*byte), &mut state), Ok(None));
+    }
+    assert_eq!(
+        slave_time_update(&quarter(cycle[7]), &mut state),
+        Ok(Some(smpte_to_us(&MtcFullFrame {
+            hours: 0,
+            minutes: 0,
+            seconds: 1,
+            frames: 1
+        })))
+    );
+
+    // A cycle at 25fps is converted at 25fps, and so are the Full Frames after it
+    let tc = MtcFullFrame {
+        hours: 0,
+        minutes: 0,
+        seconds: 2,
+        frames: 5,
+    };
+    for (n, piece) in smpte_to_quarter_frames_rate(&tc, FrameRate::Fps25)
+        .iter()
+        .enumerate()
+    {
+        let time = slave_time_update(&quarter(piece.frame_type << 4 | piece.value), &mut state);
+        assert_eq!(time, Ok((n == 7).then_some(2_200_000)));
+    }
+    let full = master_netsync_flow(&MidiEvent::MtcFull {
+        hour: 0,
+        minute: 0,
+        second: 2,
+        frame: 5,
+    })
+    .unwrap();
+    assert_eq!(slave_time_update(&full, &mut state), Ok(Some(2_200_000)));
+    state = QuarterFrameAssembler::new();
+
+    // Transport commands and other messages carry no time
+    for event in [
+        MidiEvent::Mmc(MmcCommand::Play),
+        MidiEvent::Mmc(MmcCommand::Stop),
+        MidiEvent::Mmc(MmcCommand::DeferredPlay),
+        MidiEvent::Realtime(RealtimeMessage::Start),
+    ] {
+        let payload = master_netsync_flow(&event).unwrap();
+        assert_eq!(slave_time_update(&payload, &mut state), Ok(None));
+    }
+    assert_eq!(slave_time_update(&[0x00], &mut state), Ok(None));
+
+    // Full Frames and Locate discard a partial cycle
+    let locate = master_netsync_flow(&MidiEvent::Mmc(MmcCommand::Locate {
+        hour: 0,
+        minute: 0,
+        second: 1,
+        frame: 1,
+        subframe: 0,
+    }))
+    .unwrap();
+    for interruption in [&full, &locate] {
+        for byte in &cycle[..4] {
+            slave_time_update(&quarter(*byte), &mut state).unwrap();
+        }
+        slave_time_update(interruption, &mut state).unwrap();
+        assert_eq!(state.pending(), 0);
+    }
+
+    // Invalid payloads and timecodes are rejected
+    assert_eq!(
+        slave_time_update(&[0x02], &mut state),
+        Err(NetsyncError::TooShort { got: 1, min: 2 })
+    );
+    let frame_30 = [0x0E, 0x11, 0x20, 0x30, 0x40, 0x50, 0x60, 0x76];
+    for byte in &frame_30[..7] {
+        slave_time_update(&quarter(*byte), &mut state).unwrap();
+    }
+    assert_eq!(
+        slave_time_update(&quarter(frame_30[7]), &mut state),
+        Err(NetsyncError::InvalidSlaveEvent)
+    );
+}
+
 #[test]
 fn test_slave_flow_insufficient_data() {
     let short_buf = &[0x02];
     let result = slave_netsync_flow(short_buf);
     assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), NetsyncError::InvalidSlaveEvent);
+    assert_eq!(
+        result.unwrap_err(),
+        NetsyncError::TooShort { got: 1, min: 2 }
+    );
+}
+
+#[test]
+fn test_slave_flow_detailed_chains_to_parse_error() {
+    use std::error::Error as _;
+
+    // LEN=5 with a single MIDI byte present
+    let error = slave_netsync_flow_detailed(&[0x05, 0xF1]).unwrap_err();
+    let cause = ParseError::BufferTooSmall {
+        requested: 5,
+        available: 1,
+    };
+    let kind = NetsyncError::DeclaredLengthExceedsBuffer {
+        declared: 5,
+        available: 1,
+    };
+    assert_eq!(
+        error,
+        Error::NetsyncError(FlowError {
+            kind,
+            cause: Some(cause.clone()),
+        })
+    );
+    assert_eq!(
+        error.to_string(),
+        "Netsync flow error: Declared length 5 exceeds the 1 bytes available"
+    );
+
+    let flow = error.source().unwrap();
+    assert_eq!(flow.downcast_ref::<FlowError>().unwrap().kind, kind);
+    let parse = flow.source().unwrap();
+    assert_eq!(parse.downcast_ref::<ParseError>(), Some(&cause));
+    assert!(parse.source().is_none());
+
+    // Short buffers and empty MIDI lists keep their cause too
+    for (payload, cause) in [
+        (
+            &[0x05][..],
+            ParseError::BufferTooSmall {
+                requested: 5,
+                available: 0,
+            },
+        ),
+        (&[0x00, 0x01][..], ParseError::EmptyBuffer),
+        (&[][..], ParseError::EmptyBuffer),
+    ] {
+        match slave_netsync_flow_detailed(payload) {
+            Err(Error::NetsyncError(flow)) => assert_eq!(flow.cause, Some(cause)),
+            other => panic!("{:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_slave_flow_detailed_agrees_with_slave_flow() {
+    let payloads: [&[u8]; 6] = [
+        &[0x02, 0xF1, 0x25],
+        &[0x01, 0xF8],
+        &[0x00],
+        &[0x00, 0x01],
+        &[0x03, 0xF1],
+        &[0x02, 0x90, 0x40],
+    ];
+    for payload in payloads {
+        match (
+            slave_netsync_flow(payload),
+            slave_netsync_flow_detailed(payload),
+        ) {
+            (Ok(event), Ok(detailed)) => assert_eq!(event, detailed),
+            (Err(kind), Err(Error::NetsyncError(flow))) => assert_eq!(flow.kind, kind),
+            other => panic!("{:?}: {:?}", payload, other),
+        }
+    }
+
+    // Master errors convert without a cause
+    let error = Error::from(master_netsync_flow(&MidiEvent::Other(vec![0x90])).unwrap_err());
+    assert!(std::error::Error::source(&error)
+        .and_then(std::error::Error::source)
+        .is_none());
+}
+
+#[test]
+fn test_slave_flow_opt_accepts_payloads_without_commands() {
+    // Keepalive, journal-only with short and long headers
+    assert_eq!(slave_netsync_flow_opt(&[0x00]), Ok(None));
+    assert_eq!(slave_netsync_flow_opt(&[0x40, 0x01, 0x00, 0x2A]), Ok(None));
+    assert_eq!(
+        slave_netsync_flow_opt(&[0xC0, 0x00, 0x81, 0x00, 0x2A, 0x00]),
+        Ok(None)
+    );
+    // The legacy flow still rejects them
+    assert_eq!(
+        slave_netsync_flow(&[0x00]),
+        Err(NetsyncError::TooShort { got: 1, min: 2 })
+    );
+
+    let payload = master_netsync_flow(&MidiEvent::Mmc(MmcCommand::Play)).unwrap();
+    assert_eq!(
+        slave_netsync_flow_opt(&payload),
+        Ok(Some(MidiEvent::Mmc(MmcCommand::Play)))
+    );
+}
+
+#[test]
+fn test_slave_flow_opt_validates_payloads_without_commands() {
+    for (buf, error) in [
+        (&[][..], NetsyncError::TooShort { got: 0, min: 2 }),
+        // Truncated two-octet header
+        (&[0x80], NetsyncError::TooShort { got: 1, min: 2 }),
+        // Stray bytes without a journal
+        (&[0x00, 0xFA], NetsyncError::TrailingData { extra: 1 }),
+        // J flag without a complete journal header
+        (&[0x40], NetsyncError::TooShort { got: 1, min: 4 }),
+        (
+            &[0x40, 0x01, 0x00],
+            NetsyncError::TooShort { got: 3, min: 4 },
+        ),
+    ] {
+        assert_eq!(slave_netsync_flow_opt(buf), Err(error), "{:02X?}", buf);
+    }
+}
+
+#[test]
+fn test_slave_flow_unsupported_flags() {
+    // Z flag (delta time), P flag (phantom status) and both, each with a Note On
+    for (header, flags) in [(0x23, 0x2), (0x13, 0x1), (0x33, 0x3), (0x73, 0x7)] {
+        assert_eq!(
+            slave_netsync_flow(&[header, 0x90, 0x3C, 0x7F]),
+            Err(NetsyncError::UnsupportedFlags { flags }),
+            "{:02X}",
+            header
+        );
+    }
+    // The J flag alone is still accepted, the journal is ignored
+    assert_eq!(
+        slave_netsync_flow(&[0x41, 0xFA, 0x00, 0x00, 0x2A]),
+        Ok(MidiEvent::Realtime(RealtimeMessage::Start))
+    );
+}
+
+#[test]
+fn test_slave_flow_trailing_data() {
+    // Bytes after LEN without a J flag, for both header forms
+    assert_eq!(
+        slave_netsync_flow(&[0x01, 0xFA, 0x00, 0x01]),
+        Err(NetsyncError::TrailingData { extra: 2 })
+    );
+    assert_eq!(
+        slave_netsync_flow(&[0x80, 0x01, 0xFA, 0x00]),
+        Err(NetsyncError::TrailingData { extra: 1 })
+    );
+    // LEN of zero followed by a command
+    assert_eq!(
+        slave_netsync_flow(&[0x00, 0xFA]),
+        Err(NetsyncError::TrailingData { extra: 1 })
+    );
+    // Reported after a non-empty MIDI list too
+    assert_eq!(
+        slave_netsync_flow(&[0x02, 0xF8, 0xF8, 0x00])
+            .unwrap_err()
+            .to_string(),
+        "1 trailing bytes after the MIDI list of LEN bytes"
+    );
+    // Exactly LEN bytes is fine
+    assert_eq!(
+        slave_netsync_flow(&[0x01, 0xFA]),
+        Ok(MidiEvent::Realtime(RealtimeMessage::Start))
+    );
 }
 
 #[test]
@@ -91,7 +691,10 @@ fn test_slave_flow_empty_buffer() {
     let empty_buf = &[];
     let result = slave_netsync_flow(empty_buf);
     assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), NetsyncError::InvalidSlaveEvent);
+    assert_eq!(
+        result.unwrap_err(),
+        NetsyncError::TooShort { got: 0, min: 2 }
+    );
 }
 
 #[test]
@@ -141,3 +744,731 @@ fn test_roundtrip_mmc_locate() {
 
     assert_eq!(expected_event, reconstructed_event);
 }
+
+// === Event Kind Peeking Tests ===
+
+/// Asserts that peeking agrees with a full parse of the same payload
+fn assert_peek_agrees(payload: &[u8]) {
+    let peeked = peek_event_kind(payload);
+    let parsed = slave_netsync_flow(payload).map(|event| event.kind());
+    assert_eq!(peeked, parsed, "payload {:02X?}", payload);
+}
+
+#[test]
+fn test_peek_supported_events() {
+    let events = [
+        (
+            MidiEvent::MtcQuarter {
+                msg_type: 7,
+                value: 6,
+            },
+            MessageKind::MtcQuarter,
+        ),
+        (
+            MidiEvent::MtcFull {
+                hour: 1,
+                minute: 2,
+                second: 3,
+                frame: 4,
+            },
+            MessageKind::MtcFull,
+        ),
+        (MidiEvent::Mmc(MmcCommand::Play), MessageKind::Play),
+        (MidiEvent::Mmc(MmcCommand::Stop), MessageKind::Stop),
+        (
+            MidiEvent::Mmc(MmcCommand::Locate {
+                hour: 0,
+                minute: 1,
+                second: 0,
+                frame: 0,
+                subframe: 0,
+            }),
+            MessageKind::Locate,
+        ),
+        (
+            MidiEvent::Mmc(MmcCommand::Shuttle {
+                speed: MmcSpeed::from_f32(0.5),
+            }),
+            MessageKind::Shuttle,
+        ),
+    ];
+
+    for (event, kind) in events {
+        let payload = master_netsync_flow(&event).unwrap();
+        assert_eq!(peek_event_kind(&payload), Ok(kind));
+        assert_peek_agrees(&payload);
+    }
+}
+
+#[test]
+fn test_slave_flow_unterminated_mmc() {
+    // Declared lengths covering all but the F7 terminator
+    let payloads: [(&[u8], MmcCommand); 3] = [
+        (&[0x05, 0xF0, 0x7F, 0x7F, 0x06, 0x01], MmcCommand::Stop),
+        (&[0x05, 0xF0, 0x7F, 0x7F, 0x06, 0x02], MmcCommand::Play),
+        (
+            &[
+                0x0C, 0xF0, 0x7F, 0x7F, 0x06, 0x44, 0x06, 0x01, 0x00, 0x01, 0x02, 0x03, 0x00,
+            ],
+            MmcCommand::Locate {
+                hour: 0,
+                minute: 1,
+                second: 2,
+                frame: 3,
+                subframe: 0,
+            },
+        ),
+    ];
+    for (payload, command) in payloads {
+        assert_eq!(slave_netsync_flow(payload), Ok(MidiEvent::Mmc(command)));
+        assert!(is_timing_event(payload));
+        assert_peek_agrees(payload);
+    }
+
+    // Declared lengths also missing data bytes
+    let truncated: &[u8] = &[
+        0x0B, 0xF0, 0x7F, 0x7F, 0x06, 0x44, 0x06, 0x01, 0x00, 0x01, 0x02, 0x03,
+    ];
+    assert_eq!(
+        slave_netsync_flow(truncated),
+        Ok(MidiEvent::Other(truncated[1..].to_vec()))
+    );
+
+    // A LEN that stops short of the data leaves it trailing
+    let short: &[u8] = &[0x04, 0xF0, 0x7F, 0x7F, 0x06, 0x01];
+    assert_eq!(
+        slave_netsync_flow(short),
+        Err(NetsyncError::TrailingData { extra: 1 })
+    );
+    assert!(!is_timing_event(short));
+    assert_peek_agrees(short);
+}
+
+#[test]
+fn test_peek_other_messages() {
+    let payloads: [&[u8]; 4] = [
+        &[0x03, 0x90, 0x3C, 0x7F],                   // Note On
+        &[0x01, 0xF1],                               // Truncated quarter frame
+        &[0x06, 0xF0, 0x7F, 0x7F, 0x06, 0x0B, 0xF7], // Unknown MMC command (Chase)
+        &[0x04, 0xF0, 0x7F, 0x7F, 0x06],             // Truncated MMC
+    ];
+
+    for payload in payloads {
+        assert_eq!(peek_event_kind(payload), Ok(MessageKind::Other));
+        assert_peek_agrees(payload);
+    }
+
+    let manufacturer: &[u8] = &[0x06, 0xF0, 0x43, 0x10, 0x4C, 0x00, 0xF7];
+    assert_eq!(
+        peek_event_kind(manufacturer),
+        Ok(MessageKind::ManufacturerSysex)
+    );
+    assert_peek_agrees(manufacturer);
+}
+
+#[test]
+fn test_peek_malformed_payloads() {
+    let payloads: [(&[u8], NetsyncError); 5] = [
+        (&[], NetsyncError::TooShort { got: 0, min: 2 }),
+        (&[0x02], NetsyncError::TooShort { got: 1, min: 2 }),
+        (
+            &[0x02, 0xF1],
+            NetsyncError::DeclaredLengthExceedsBuffer {
+                declared: 2,
+                available: 1,
+            },
+        ),
+        // LEN of zero
+        (&[0x00, 0xF1, 0x23], NetsyncError::TrailingData { extra: 2 }),
+        // LEN exceeds the data
+        (
+            &[0x0A, 0xF0, 0x7F, 0x7F],
+            NetsyncError::DeclaredLengthExceedsBuffer {
+                declared: 10,
+                available: 3,
+            },
+        ),
+    ];
+
+    for (payload, error) in payloads {
+        assert_eq!(peek_event_kind(payload), Err(error));
+        assert_peek_agrees(payload);
+    }
+}
+
+#[test]
+fn test_peek_agrees_on_every_short_payload() {
+    // Exhaustively cover three-byte payloads built from interesting bytes
+    let bytes = [
+        0x00, 0x01, 0x02, 0x03, 0x06, 0x0F, 0x7F, 0x90, 0xF0, 0xF1, 0xF7,
+    ];
+    for &a in &bytes {
+        for &b in &bytes {
+            for &c in &bytes {
+                assert_peek_agrees(&[a, b, c]);
+                assert_peek_agrees(&[a, b, c, 0x7F, 0x06, 0x01, 0xF7]);
+            }
+        }
+    }
+}
+
+// === Timing Event Filter Tests ===
+
+#[test]
+fn test_is_timing_event_for_sync_events() {
+    let events = [
+        MidiEvent::MtcQuarter {
+            msg_type: 2,
+            value: 9,
+        },
+        MidiEvent::MtcFull {
+            hour: 1,
+            minute: 2,
+            second: 3,
+            frame: 4,
+        },
+        MidiEvent::Mmc(MmcCommand::Play),
+        MidiEvent::Mmc(MmcCommand::Stop),
+        MidiEvent::Mmc(MmcCommand::Locate {
+            hour: 0,
+            minute: 1,
+            second: 0,
+            frame: 0,
+            subframe: 0,
+        }),
+    ];
+
+    for event in events {
+        let payload = master_netsync_flow(&event).unwrap();
+        assert!(is_timing_event(&payload), "{:?}", event);
+    }
+}
+
+#[test]
+fn test_is_timing_event_rejects_other_traffic() {
+    let payloads: [&[u8]; 8] = [
+        &[],
+        &[0x02],
+        &[0x00, 0xF1, 0x23],
+        &[0x03, 0x90, 0x3C, 0x7F],
+        &[0x01, 0xF1, 0x23],
+        &[0x06, 0xF0, 0x7F, 0x7F, 0x06, 0x0B, 0xF7],
+        &[0x06, 0xF0, 0x43, 0x10, 0x4C, 0x00, 0xF7],
+        &[0x0A, 0xF0, 0x7F, 0x7F],
+    ];
+
+    for payload in payloads {
+        assert!(!is_timing_event(payload), "{:02X?}", payload);
+    }
+}
+
+#[test]
+fn test_is_timing_event_padded_quarter_frame() {
+    assert!(is_timing_event(&[0x04, 0xF1, 0x23, 0x00, 0x00]));
+}
+
+#[test]
+fn test_is_timing_event_long_header() {
+    // B flag set: LEN spans the low nibble of the first octet and the second octet
+    assert!(is_timing_event(&[0x80, 0x02, 0xF1, 0x23]));
+    assert!(is_timing_event(&[
+        0x80, 0x06, 0xF0, 0x7F, 0x7F, 0x06, 0x02, 0xF7
+    ]));
+    assert!(!is_timing_event(&[0x80, 0x03, 0x90, 0x3C, 0x7F]));
+    // Truncated long header and LEN beyond the data
+    assert!(!is_timing_event(&[0x80]));
+    assert!(!is_timing_event(&[0x81, 0x00, 0xF1, 0x23]));
+}
+
+#[test]
+fn test_is_timing_event_agrees_with_slave_flow() {
+    let bytes = [0x00, 0x02, 0x03, 0x06, 0x7F, 0x90, 0xF0, 0xF1, 0xF7];
+    // The slave flow only understands the one-octet header (B flag clear)
+    for &a in bytes.iter().filter(|&&a| a & 0x80 == 0) {
+        for &b in &bytes {
+            for &c in &bytes {
+                for payload in [vec![a, b, c], vec![a, b, c, 0x7F, 0x06, 0x01, 0xF7]] {
+                    let expected = matches!(
+                        slave_netsync_flow(&payload),
+                        Ok(event) if event.kind().is_sync()
+                    );
+                    assert_eq!(is_timing_event(&payload), expected, "{:02X?}", payload);
+                }
+            }
+        }
+    }
+}
+
+// === Scratch Buffer Flow Tests ===
+
+#[test]
+fn test_slave_flow_buf_matches_slave_flow() {
+    let payloads = [
+        master_netsync_flow(&MidiEvent::Mmc(MmcCommand::Play)).unwrap(),
+        master_netsync_flow(&MidiEvent::MtcQuarter {
+            msg_type: 5,
+            value: 9,
+        })
+        .unwrap(),
+        vec![0x03, 0x90, 0x3C, 0x7F],
+        vec![0x02, 0xC0, 0x05],
+    ];
+    let mut scratch = Vec::new();
+    for payload in &payloads {
+        let expected = slave_netsync_flow(payload).unwrap();
+        let event = slave_netsync_flow_buf(payload, &mut scratch).unwrap();
+        assert_eq!(event.to_owned_event(), expected);
+    }
+}
+
+#[test]
+fn test_slave_flow_buf_reuses_scratch() {
+    let mut scratch = Vec::new();
+    let note_on = [0x03, 0x90, 0x3C, 0x7F];
+    assert_eq!(
+        slave_netsync_flow_buf(&note_on, &mut scratch),
+        Ok(MidiEventRef::Other(&[0x90, 0x3C, 0x7F]))
+    );
+    let capacity = scratch.capacity();
+    let pointer = scratch.as_ptr();
+
+    for payload in [[0x03, 0x80, 0x3C, 0x00], [0x03, 0xB0, 0x07, 0x64]] {
+        let event = slave_netsync_flow_buf(&payload, &mut scratch).unwrap();
+        assert_eq!(event, MidiEventRef::Other(&payload[1..]));
+    }
+    assert_eq!(scratch.capacity(), capacity);
+    assert_eq!(scratch.as_ptr(), pointer);
+}
+
+#[test]
+fn test_slave_flow_buf_rejects_what_slave_flow_rejects() {
+    let mut scratch = vec![0xAA];
+    for payload in [&[][..], &[0x01], &[0x05, 0xF1, 0x23], &[0x13, 0x90, 0x3C]] {
+        let error = slave_netsync_flow(payload).unwrap_err();
+        assert_eq!(slave_netsync_flow_buf(payload, &mut scratch), Err(error));
+    }
+}
+
+#[test]
+fn test_slave_flow_ref_borrows_from_payload() {
+    let note_on = [0x03, 0x90, 0x3C, 0x7F];
+    assert_eq!(
+        slave_netsync_flow_ref(&note_on),
+        Ok(MidiEventRef::Other(&note_on[1..]))
+    );
+    let play = master_netsync_flow(&MidiEvent::Mmc(MmcCommand::Play)).unwrap();
+    assert_eq!(
+        slave_netsync_flow_ref(&play),
+        Ok(MidiEventRef::Mmc(MmcCommand::Play))
+    );
+    for (payload, error) in [
+        (&[][..], NetsyncError::TooShort { got: 0, min: 2 }),
+        (&[0x01], NetsyncError::TooShort { got: 1, min: 2 }),
+        (
+            &[0x05, 0xF1, 0x23],
+            NetsyncError::DeclaredLengthExceedsBuffer {
+                declared: 5,
+                available: 2,
+            },
+        ),
+    ] {
+        assert_eq!(slave_netsync_flow_ref(payload), Err(error));
+    }
+}
+
+#[test]
+fn test_master_flow_slice_matches_master_flow() {
+    let events = [
+        MidiEvent::MtcQuarter {
+            msg_type: 7,
+            value: 1,
+        },
+        MidiEvent::MtcFull {
+            hour: 1,
+            minute: 2,
+            second: 3,
+            frame: 4,
+        },
+        MidiEvent::Mmc(MmcCommand::Locate {
+            hour: 1,
+            minute: 2,
+            second: 3,
+            frame: 4,
+            subframe: 0,
+        }),
+        MidiEvent::Mmc(MmcCommand::Eject),
+        MidiEvent::Realtime(RealtimeMessage::Clock),
+        MidiEvent::SongPosition(300),
+    ];
+    for event in &events {
+        let mut out = [0xAA; MAX_PAYLOAD_LENGTH + 1];
+        let len = master_netsync_flow_slice(event, &mut out).unwrap();
+        assert_eq!(&out[..len], master_netsync_flow(event).unwrap());
+        assert_eq!(out[len], 0xAA);
+    }
+    assert_eq!(
+        master_netsync_flow_slice(&MidiEvent::Other(vec![0xF8]), &mut [0; 4]),
+        Err(NetsyncError::InvalidMasterEvent)
+    );
+}
+
+#[test]
+fn test_wire_len_matches_max_payload_length() {
+    let kinds = [
+        MessageKind::MtcQuarter,
+        MessageKind::MtcFull,
+        MessageKind::Play,
+        MessageKind::Stop,
+        MessageKind::Locate,
+        MessageKind::Shuttle,
+        MessageKind::VariablePlay,
+        MessageKind::Search,
+        MessageKind::MaskedWrite,
+        MessageKind::OtherMmc,
+        MessageKind::Realtime,
+        MessageKind::SongPosition,
+        MessageKind::Other,
+    ];
+    let longest = kinds.iter().filter_map(|kind| kind.wire_len()).max();
+    assert_eq!(longest, Some(MAX_PAYLOAD_LENGTH - 1));
+    assert_eq!(MessageKind::Other.wire_len(), None);
+
+    for event in [
+        MidiEvent::MtcQuarter {
+            msg_type: 7,
+            value: 1,
+        },
+        MidiEvent::Mmc(MmcCommand::Stop),
+        MidiEvent::Mmc(MmcCommand::Rewind),
+        MidiEvent::Mmc(MmcCommand::Shuttle {
+            speed: MmcSpeed::from_f32(-1.0),
+        }),
+        MidiEvent::Realtime(RealtimeMessage::Start),
+        MidiEvent::SongPosition(0x3FFF),
+    ] {
+        assert_eq!(
+            event.kind().wire_len(),
+            Some(master_netsync_flow(&event).unwrap().len() - 1),
+            "{:?}",
+            event
+        );
+    }
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn test_master_flow_fixed() {
+    use rtp_midi_netsync::netsync::{master_netsync_flow_fixed, Payload};
+
+    assert_eq!(Payload::CAPACITY, MAX_PAYLOAD_LENGTH);
+    let events = [
+        MidiEvent::MtcFull {
+            hour: 1,
+            minute: 2,
+            second: 3,
+            frame: 4,
+        },
+        MidiEvent::Mmc(MmcCommand::Locate {
+            hour: 1,
+            minute: 2,
+            second: 3,
+            frame: 4,
+            subframe: 0,
+        }),
+        MidiEvent::Mmc(MmcCommand::Play),
+        MidiEvent::Realtime(RealtimeMessage::Clock),
+    ];
+    for event in &events {
+        let payload = master_netsync_flow_fixed(event).unwrap();
+        assert_eq!(&*payload, master_netsync_flow(event).unwrap());
+        assert_eq!(slave_netsync_flow(&payload), Ok(event.clone()));
+    }
+    // Pass-through messages are rejected, not truncated to the capacity
+    for len in [3, MAX_PAYLOAD_LENGTH + 10] {
+        let mut sysex = vec![0x01; len];
+        sysex[0] = 0xF0;
+        sysex[len - 1] = 0xF7;
+        assert_eq!(
+            master_netsync_flow_fixed(&MidiEvent::Other(sysex)),
+            Err(NetsyncError::InvalidMasterEvent)
+        );
+    }
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn test_quarter_frame_payloads_round_trip() {
+    use rtp_midi_netsync::mtc::{MtcQuarterFrame, QuarterFrameAssembler};
+    use rtp_midi_netsync::netsync::quarter_frame_payloads;
+
+    let timecodes = [
+        MtcFullFrame::default(),
+        MtcFullFrame {
+            hours: 1,
+            minutes: 2,
+            seconds: 3,
+            frames: 4,
+        },
+        MtcFullFrame {
+            hours: 23,
+            minutes: 59,
+            seconds: 59,
+            frames: 29,
+        },
+    ];
+    for tc in timecodes {
+        let payloads = quarter_frame_payloads(&tc).unwrap();
+        let mut assembler = QuarterFrameAssembler::new();
+        let mut assembled = None;
+        for (piece, payload) in payloads.iter().enumerate() {
+            let MidiEvent::MtcQuarter { msg_type, value } = slave_netsync_flow(payload).unwrap()
+            else {
+                panic!("payload {piece} is not a quarter frame");
+            };
+            // Transmission order is piece order
+            assert_eq!(msg_type, piece as u8);
+            assert_eq!(
+                &**payload,
+                master_netsync_flow(&MidiEvent::MtcQuarter { msg_type, value }).unwrap()
+            );
+            assembled = assembler
+                .feed(MtcQuarterFrame {
+                    frame_type: msg_type,
+                    value,
+                })
+                .unwrap();
+        }
+        assert_eq!(assembled, Some(tc));
+    }
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn test_quarter_frame_payloads_rejects_invalid_timecode() {
+    use rtp_midi_netsync::netsync::quarter_frame_payloads;
+
+    let valid = MtcFullFrame {
+        hours: 1,
+        minutes: 2,
+        seconds: 3,
+        frames: 4,
+    };
+    // Each field one past its range, which the quarter frames would truncate
+    for tc in [
+        MtcFullFrame { hours: 24, ..valid },
+        MtcFullFrame {
+            minutes: 60,
+            ..valid
+        },
+        MtcFullFrame {
+            seconds: 60,
+            ..valid
+        },
+        MtcFullFrame {
+            frames: 30,
+            ..valid
+        },
+    ] {
+        assert_eq!(
+            quarter_frame_payloads(&tc),
+            Err(NetsyncError::InvalidMasterEvent)
+        );
+    }
+}
+
+// === Stream Parsing Tests ===
+
+fn concat(events: &[MidiEvent]) -> Vec<u8> {
+    events
+        .iter()
+        .flat_map(|event| master_netsync_flow(event).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_stream_two_payloads() {
+    let events = [
+        MidiEvent::Mmc(MmcCommand::Play),
+        MidiEvent::MtcQuarter {
+            msg_type: 1,
+            value: 2,
+        },
+    ];
+    let datagram = concat(&events);
+
+    let (first, consumed) = slave_netsync_flow_stream(&datagram).unwrap();
+    assert_eq!(first, Some(events[0].clone()));
+    assert_eq!(consumed, 1 + MMC_START_STOP_LENGTH);
+    let (second, consumed_second) = slave_netsync_flow_stream(&datagram[consumed..]).unwrap();
+    assert_eq!(second, Some(events[1].clone()));
+    assert_eq!(consumed + consumed_second, datagram.len());
+
+    assert_eq!(parse_all_payloads(&datagram), Ok(events.to_vec()));
+}
+
+#[test]
+fn test_payload_len() {
+    let datagram = concat(&[MidiEvent::Mmc(MmcCommand::Play)]);
+    assert_eq!(payload_len(&datagram), Some(datagram.len()));
+    // Long header: B flag, LEN=0x102
+    assert_eq!(payload_len(&[0x81, 0x02]), Some(2 + 0x102));
+    assert_eq!(payload_len(&[0x81]), None);
+    assert_eq!(payload_len(&[]), None);
+}
+
+#[test]
+fn test_stream_three_payloads_with_sysex() {
+    // The Full Frame is followed by more SysEx terminators, which must not be
+    // mistaken for its own
+    let events = [
+        MidiEvent::MtcFull {
+            hour: 1,
+            minute: 2,
+            second: 3,
+            frame: 4,
+        },
+        MidiEvent::Mmc(MmcCommand::Locate {
+            hour: 5,
+            minute: 6,
+            second: 7,
+            frame: 8,
+            subframe: 0,
+        }),
+        MidiEvent::Mmc(MmcCommand::Stop),
+    ];
+    assert_eq!(parse_all_payloads(&concat(&events)), Ok(events.to_vec()));
+}
+
+#[test]
+fn test_stream_long_header() {
+    let mut datagram = vec![0x80, 0x03, 0x90, 0x3C, 0x7F];
+    datagram.extend(master_netsync_flow(&MidiEvent::Mmc(MmcCommand::Play)).unwrap());
+
+    let (event, consumed) = slave_netsync_flow_stream(&datagram).unwrap();
+    assert_eq!(event, Some(MidiEvent::Other(vec![0x90, 0x3C, 0x7F])));
+    assert_eq!(consumed, 5);
+    assert_eq!(parse_all_payloads(&datagram).unwrap().len(), 2);
+}
+
+#[test]
+fn test_stream_truncated_final_payload() {
+    let mut datagram = concat(&[
+        MidiEvent::Mmc(MmcCommand::Play),
+        MidiEvent::Mmc(MmcCommand::Stop),
+    ]);
+    let full = master_netsync_flow(&MidiEvent::MtcFull {
+        hour: 0,
+        minute: 0,
+        second: 1,
+        frame: 0,
+    })
+    .unwrap();
+    datagram.extend_from_slice(&full[..full.len() - 3]);
+
+    assert_eq!(
+        parse_all_payloads(&datagram),
+        Err(NetsyncError::DeclaredLengthExceedsBuffer {
+            declared: 10,
+            available: 7,
+        })
+    );
+    // Long header cut after its first octet
+    assert_eq!(
+        slave_netsync_flow_stream(&[0x80]),
+        Err(NetsyncError::TooShort { got: 1, min: 2 })
+    );
+    assert_eq!(parse_all_payloads(&[]), Ok(vec![]));
+}
+
+#[test]
+fn test_stream_unsupported_flags() {
+    // J (journal), Z (delta time) and P (phantom status), in both header forms
+    for (header, flags) in [(0x41, 0x4), (0x21, 0x2), (0x11, 0x1), (0xC0, 0xC)] {
+        let mut datagram = vec![header];
+        if header & 0x80 != 0 {
+            datagram.push(0x01);
+        }
+        datagram.extend([0xFA, 0x00, 0x00, 0x2A]);
+        assert_eq!(
+            slave_netsync_flow_stream(&datagram),
+            Err(NetsyncError::UnsupportedFlags { flags }),
+            "{:02X}",
+            header
+        );
+        assert_eq!(
+            parse_all_payloads(&datagram),
+            Err(NetsyncError::UnsupportedFlags { flags })
+        );
+    }
+}
+
+#[test]
+fn test_stream_payloads_without_commands() {
+    // A keepalive with LEN=0 consumes only its header, in both header forms
+    assert_eq!(slave_netsync_flow_stream(&[0x00, 0xF8]), Ok((None, 1)));
+    assert_eq!(slave_netsync_flow_stream(&[0x80, 0x00]), Ok((None, 2)));
+
+    let play = MidiEvent::Mmc(MmcCommand::Play);
+    let stop = MidiEvent::Mmc(MmcCommand::Stop);
+    let mut datagram = master_netsync_flow(&play).unwrap();
+    datagram.push(0x00);
+    datagram.extend(master_netsync_flow(&stop).unwrap());
+    datagram.extend([0x80, 0x00]);
+    assert_eq!(parse_all_payloads(&datagram), Ok(vec![play, stop]));
+}
+
+#[test]
+fn test_device_id_roundtrip() {
+    let events = [
+        MidiEvent::MtcFull {
+            hour: 1,
+            minute: 2,
+            second: 3,
+            frame: 4,
+        },
+        MidiEvent::Mmc(MmcCommand::Locate {
+            hour: 1,
+            minute: 2,
+            second: 3,
+            frame: 4,
+            subframe: 0,
+        }),
+        MidiEvent::Mmc(MmcCommand::Stop),
+    ];
+    for event in &events {
+        let payload = master_netsync_flow_to_device(event, 0x05).unwrap();
+        assert_eq!(payload[3], 0x05);
+        assert_eq!(
+            slave_netsync_flow_with_device(&payload),
+            Ok((event.clone(), 0x05))
+        );
+
+        let broadcast = master_netsync_flow_to_device(event, 0x7F).unwrap();
+        assert_eq!(broadcast, master_netsync_flow(event).unwrap());
+        assert_eq!(
+            slave_netsync_flow_with_device(&broadcast),
+            Ok((event.clone(), 0x7F))
+        );
+    }
+
+    // Quarter frames carry no device ID
+    let quarter = MidiEvent::MtcQuarter {
+        msg_type: 2,
+        value: 0x5,
+    };
+    let payload = master_netsync_flow_to_device(&quarter, 0x05).unwrap();
+    assert_eq!(payload, master_netsync_flow(&quarter).unwrap());
+    assert_eq!(
+        slave_netsync_flow_with_device(&payload),
+        Ok((quarter, 0x7F))
+    );
+
+    assert_eq!(
+        master_netsync_flow_to_device(&MidiEvent::Mmc(MmcCommand::Play), 0x80),
+        Err(NetsyncError::InvalidMasterEvent)
+    );
+    assert_eq!(
+        slave_netsync_flow_with_device(&[0x00]),
+        Err(NetsyncError::TooShort { got: 1, min: 2 })
+    );
+}
@@ -0,0 +1,171 @@
+//! Checks the APIs available without `std` and `alloc`, and that the crate builds
+//! with `--no-default-features`.
+//!
+//! Integration tests link `std`, so the first tests call the allocation-free
+//! paths directly while the last one builds the library itself in both
+//! `no_std` configurations.
+
+use std::process::Command;
+
+use rtp_midi_netsync::error::NetsyncError;
+use rtp_midi_netsync::midi::{
+    parse_midi_message, MidiEvent, MidiEventRef, MmcCommand, RealtimeMessage,
+};
+use rtp_midi_netsync::mtc::{
+    quarter_frames_to_smpte, smpte_to_quarter_frames, smpte_to_us_rate, us_to_smpte_rate,
+    FrameRate, MtcFullFrame,
+};
+use rtp_midi_netsync::netsync::{
+    master_netsync_flow, master_netsync_flow_ref, peek_event_kind, slave_netsync_flow_ref,
+    MAX_PAYLOAD_LENGTH,
+};
+use rtp_midi_netsync::util::{sevenbit, vlq};
+
+fn events() -> [MidiEventRef<'static>; 6] {
+    [
+        MidiEventRef::MtcQuarter {
+            msg_type: 3,
+            value: 0x0A,
+        },
+        MidiEventRef::MtcFull {
+            hour: 1,
+            minute: 2,
+            second: 3,
+            frame: 4,
+        },
+        MidiEventRef::Mmc(MmcCommand::Play),
+        MidiEventRef::Mmc(MmcCommand::Locate {
+            hour: 10,
+            minute: 20,
+            second: 30,
+            frame: 15,
+            subframe: 0,
+        }),
+        MidiEventRef::Realtime(RealtimeMessage::Clock),
+        MidiEventRef::SongPosition(0x1234),
+    ]
+}
+
+#[test]
+fn test_ref_flows_roundtrip() {
+    for event in events() {
+        let mut buf = [0; MAX_PAYLOAD_LENGTH];
+        let len = master_netsync_flow_ref(&event, &mut buf).unwrap();
+        assert_eq!(slave_netsync_flow_ref(&buf[..len]), Ok(event.clone()));
+        assert_eq!(peek_event_kind(&buf[..len]), Ok(event.kind()));
+
+        // Same bytes as the allocating flow
+        let owned: MidiEvent = event.to_owned_event();
+        assert_eq!(
+            &buf[..len],
+            master_netsync_flow(&owned).unwrap(),
+            "{:?}",
+            event
+        );
+    }
+}
+
+#[test]
+fn test_ref_flow_rejects_other() {
+    assert_eq!(
+        master_netsync_flow_ref(&MidiEventRef::Other(&[0x90, 0x3C, 0x7F]), &mut [0; 8]),
+        Err(NetsyncError::InvalidMasterEvent)
+    );
+}
+
+#[test]
+fn test_parse_midi_message_borrows() {
+    let bytes = [0xF0, 0x7E, 0x7F, 0x06, 0x03, 0xF7, 0xF8];
+    let (event, len) = parse_midi_message(&bytes).unwrap();
+    assert_eq!(len, 6);
+    assert!(matches!(event, MidiEventRef::Other(msg) if msg.as_ptr() == bytes.as_ptr()));
+}
+
+#[test]
+fn test_timecode_without_floats() {
+    for rate in [
+        FrameRate::Fps24,
+        FrameRate::Fps25,
+        FrameRate::Fps2997Df,
+        FrameRate::Fps30,
+    ] {
+        let smpte = MtcFullFrame {
+            hours: 1,
+            minutes: 9,
+            seconds: 59,
+            frames: 20,
+        };
+        assert_eq!(
+            us_to_smpte_rate(smpte_to_us_rate(&smpte, rate), rate),
+            smpte
+        );
+    }
+
+    let smpte = MtcFullFrame {
+        hours: 23,
+        minutes: 59,
+        seconds: 59,
+        frames: 29,
+    };
+    assert_eq!(
+        quarter_frames_to_smpte(&smpte_to_quarter_frames(&smpte)),
+        Ok(smpte)
+    );
+}
+
+#[test]
+fn test_util_into_variants() {
+    let mut buf = [0; vlq::MAX_LENGTH];
+    let len = vlq::encode(0x4000, &mut buf);
+    assert_eq!(vlq::decode(&buf[..len]), Ok((0x4000, 3)));
+
+    let data = [0xFF, 0x00, 0x80, 0x7F, 0x01, 0xFE, 0x81, 0x42];
+    let mut packed = [0; 10];
+    assert_eq!(sevenbit::pack_into(&data, &mut packed), packed.len());
+    let mut unpacked = [0; 8];
+    assert_eq!(
+        sevenbit::unpack_into(&packed, &mut unpacked),
+        Ok(data.len())
+    );
+    assert_eq!(unpacked, data);
+}
+
+/// Builds the library as an rlib, since the staticlib needs `std` to link.
+fn build_without_default_features(features: &[&str]) {
+    let mut command = Command::new(env!("CARGO"));
+    command
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .env(
+            "CARGO_TARGET_DIR",
+            format!("{}/no_std", env!("CARGO_TARGET_TMPDIR")),
+        )
+        .args([
+            "rustc",
+            "--quiet",
+            "--offline",
+            "--lib",
+            "--crate-type",
+            "rlib",
+        ])
+        .arg("--no-default-features");
+    if !features.is_empty() {
+        command.arg("--features").arg(features.join(","));
+    }
+    command.args(["--", "-D", "warnings"]);
+
+    let output = command.output().expect("cargo runs");
+    assert!(
+        output.status.success(),
+        "build with features {:?} failed:\n{}",
+        features,
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_builds_without_std() {
+    build_without_default_features(&[]);
+    build_without_default_features(&["alloc"]);
+    build_without_default_features(&["heapless"]);
+    build_without_default_features(&["alloc", "tracing"]);
+}
@@ -0,0 +1,442 @@
+use rtp_midi_netsync::error::ParseError;
+use rtp_midi_netsync::midi::{MidiEvent, MmcCommand, RealtimeMessage};
+use rtp_midi_netsync::mtc::FrameRate;
+use rtp_midi_netsync::netsync::{
+    master_netsync_flow, slave_netsync_flow_with_policy, FindingKind, NetsyncConfig,
+    NetsyncHandler, ParsePolicy, SlaveSession,
+};
+
+/// Asserts that Strict rejects `payload` with `kind` while Lenient returns `lenient`
+fn assert_divergence(payload: &[u8], kind: FindingKind, lenient: MidiEvent) {
+    let violation = slave_netsync_flow_with_policy(payload, ParsePolicy::Strict).unwrap_err();
+    assert_eq!(violation.policy, ParsePolicy::Strict);
+    assert_eq!(violation.finding.kind, kind);
+    assert_eq!(
+        slave_netsync_flow_with_policy(payload, ParsePolicy::Lenient),
+        Ok(lenient)
+    );
+}
+
+/// Asserts that both policies reject `payload` with `kind`
+fn assert_rejected_by_both(payload: &[u8], kind: FindingKind) {
+    for policy in [ParsePolicy::Strict, ParsePolicy::Lenient] {
+        let violation = slave_netsync_flow_with_policy(payload, policy).unwrap_err();
+        assert_eq!(violation.finding.kind, kind, "{:?}", policy);
+    }
+}
+
+const PLAY: MidiEvent = MidiEvent::Mmc(MmcCommand::Play);
+
+// === Agreement ===
+
+#[test]
+fn test_policies_agree_on_master_payloads() {
+    let events = [
+        MidiEvent::MtcQuarter {
+            msg_type: 7,
+            value: 0x6,
+        },
+        MidiEvent::MtcFull {
+            hour: 1,
+            minute: 2,
+            second: 3,
+            frame: 4,
+        },
+        PLAY,
+        MidiEvent::Mmc(MmcCommand::Locate {
+            hour: 1,
+            minute: 2,
+            second: 3,
+            frame: 4,
+            subframe: 0,
+        }),
+        MidiEvent::Realtime(RealtimeMessage::Start),
+        MidiEvent::SongPosition(100),
+    ];
+    for event in events {
+        let payload = master_netsync_flow(&event).unwrap();
+        for policy in [ParsePolicy::Strict, ParsePolicy::Lenient] {
+            assert_eq!(
+                slave_netsync_flow_with_policy(&payload, policy),
+                Ok(event.clone())
+            );
+        }
+    }
+
+    // Neither is the two-octet header, which the master flow itself sends
+    for policy in [ParsePolicy::Strict, ParsePolicy::Lenient] {
+        assert_eq!(
+            slave_netsync_flow_with_policy(
+                &[0x80, 0x06, 0xF0, 0x7F, 0x7F, 0x06, 0x02, 0xF7],
+                policy
+            ),
+            Ok(PLAY)
+        );
+    }
+
+    // Pass-through traffic is not a deviation
+    for policy in [ParsePolicy::Strict, ParsePolicy::Lenient] {
+        assert_eq!(
+            slave_netsync_flow_with_policy(&[0x03, 0x90, 0x3C, 0x7F], policy),
+            Ok(MidiEvent::Other(vec![0x90, 0x3C, 0x7F]))
+        );
+    }
+}
+
+// === Divergences ===
+
+#[test]
+fn test_journal_flag() {
+    // LEN=6 followed by a journal the slave does not read
+    assert_divergence(
+        &[0x46, 0xF0, 0x7F, 0x7F, 0x06, 0x02, 0xF7, 0x00],
+        FindingKind::JournalPresent,
+        PLAY,
+    );
+}
+
+#[test]
+fn test_bytes_after_len() {
+    assert_divergence(
+        &[0x06, 0xF0, 0x7F, 0x7F, 0x06, 0x02, 0xF7, 0x00, 0x00],
+        FindingKind::TrailingBytes {
+            declared: 6,
+            available: 8,
+        },
+        PLAY,
+    );
+}
+
+#[test]
+fn test_bytes_after_message() {
+    assert_divergence(
+        &[0x04, 0xF1, 0x23, 0x00, 0x00],
+        FindingKind::ExtraBytes { count: 2 },
+        MidiEvent::MtcQuarter {
+            msg_type: 2,
+            value: 3,
+        },
+    );
+}
+
+#[test]
+fn test_non_broadcast_device_id() {
+    assert_divergence(
+        &[0x06, 0xF0, 0x7F, 0x10, 0x06, 0x02, 0xF7],
+        FindingKind::NonBroadcastDeviceId { device_id: 0x10 },
+        PLAY,
+    );
+}
+
+#[test]
+fn test_missing_sysex_end() {
+    assert_divergence(
+        &[0x05, 0xF0, 0x7F, 0x7F, 0x06, 0x01],
+        FindingKind::MissingSysexEnd,
+        MidiEvent::Mmc(MmcCommand::Stop),
+    );
+}
+
+#[test]
+fn test_rate_bits_in_hour() {
+    assert_divergence(
+        &[
+            0x0A, 0xF0, 0x7F, 0x7F, 0x01, 0x01, 0x61, 0x02, 0x03, 0x04, 0xF7,
+        ],
+        FindingKind::RateBitsInHour {
+            rate: FrameRate::Fps30,
+        },
+        MidiEvent::MtcFull {
+            hour: 1,
+            minute: 2,
+            second: 3,
+            frame: 4,
+        },
+    );
+}
+
+#[test]
+fn test_value_out_of_range() {
+    assert_divergence(
+        &[
+            0x0A, 0xF0, 0x7F, 0x7F, 0x01, 0x01, 0x01, 0x02, 0x03, 30, 0xF7,
+        ],
+        FindingKind::ValueOutOfRange {
+            field: "frames",
+            value: 30,
+            max: 29,
+        },
+        MidiEvent::MtcFull {
+            hour: 1,
+            minute: 2,
+            second: 3,
+            frame: 30,
+        },
+    );
+}
+
+#[test]
+fn test_truncated_message() {
+    assert_divergence(
+        &[0x02, 0xF2, 0x10],
+        FindingKind::TruncatedMessage {
+            message: "Song Position Pointer",
+            expected: 3,
+            available: 2,
+        },
+        MidiEvent::Other(vec![0xF2, 0x10]),
+    );
+}
+
+#[test]
+fn test_truncated_manufacturer_sysex() {
+    // Three-byte ID terminated after its second byte
+    assert_divergence(
+        &[0x04, 0xF0, 0x00, 0x20, 0xF7],
+        FindingKind::TruncatedMessage {
+            message: "Manufacturer SysEx",
+            expected: 5,
+            available: 4,
+        },
+        MidiEvent::Other(vec![0xF0, 0x00, 0x20, 0xF7]),
+    );
+    assert_divergence(
+        &[0x04, 0xF0, 0x41, 0x10, 0x42],
+        FindingKind::MissingSysexEnd,
+        MidiEvent::Other(vec![0xF0, 0x41, 0x10, 0x42]),
+    );
+}
+
+#[test]
+fn test_lenient_passes_unknown_universal_sysex_through_unmodified() {
+    // Universal real-time, device 0x10, sub-ID 0x03 (notation): not a sync message
+    let payload = [0x05, 0xF0, 0x7F, 0x10, 0x03, 0x01];
+    assert_eq!(
+        slave_netsync_flow_with_policy(&payload, ParsePolicy::Lenient),
+        Ok(MidiEvent::Other(payload[1..].to_vec()))
+    );
+}
+
+// === Rejected by Both ===
+
+#[test]
+fn test_unreadable_payloads_rejected_by_both() {
+    assert_rejected_by_both(&[], FindingKind::EmptyPayload);
+    assert_rejected_by_both(&[0x80], FindingKind::TruncatedLongHeader);
+    assert_rejected_by_both(&[0x00, 0xF8], FindingKind::EmptyMidiList);
+    assert_rejected_by_both(
+        &[0x05, 0xF1, 0x23],
+        FindingKind::TruncatedMidiList {
+            declared: 5,
+            available: 2,
+        },
+    );
+    assert_rejected_by_both(&[0x23, 0x00, 0xF8], FindingKind::DeltaTimePresent);
+    assert_rejected_by_both(&[0x12, 0x3C, 0x7F], FindingKind::PhantomStatus);
+    assert_rejected_by_both(
+        &[0x02, 0x3C, 0x7F],
+        FindingKind::MissingStatusByte { byte: 0x3C },
+    );
+    assert_rejected_by_both(
+        &[0x02, 0xF1, 0x93],
+        FindingKind::DataByteHighBit { byte: 0x93 },
+    );
+}
+
+#[test]
+fn test_policies_never_panic() {
+    let bytes = [
+        0x00, 0x03, 0x06, 0x7F, 0x80, 0xC4, 0xF0, 0xF1, 0xF2, 0xF7, 0xFF,
+    ];
+    for &a in &bytes {
+        for &b in &bytes {
+            for &c in &bytes {
+                for &d in &bytes {
+                    for policy in [ParsePolicy::Strict, ParsePolicy::Lenient] {
+                        let _ = slave_netsync_flow_with_policy(&[a, b, c, d], policy);
+                        let _ = slave_netsync_flow_with_policy(&[a, 0x7F, b, c, d], policy);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// === Session ===
+
+#[derive(Default)]
+struct PlayCounter {
+    plays: usize,
+}
+
+impl NetsyncHandler for PlayCounter {
+    fn on_play(&mut self) {
+        self.plays += 1;
+    }
+}
+
+#[test]
+fn test_session_honours_configured_policy() {
+    let quirky_play = [0x06, 0xF0, 0x7F, 0x10, 0x06, 0x02, 0xF7];
+
+    let mut lenient = SlaveSession::new();
+    let mut handler = PlayCounter::default();
+    lenient.dispatch(&quirky_play, &mut handler).unwrap();
+    assert_eq!(handler.plays, 1);
+
+    let mut strict = SlaveSession::with_config(NetsyncConfig {
+        policy: ParsePolicy::Strict,
+        ..NetsyncConfig::default()
+    });
+    assert!(strict.dispatch(&quirky_play, &mut handler).is_err());
+    assert_eq!(handler.plays, 1);
+}
+
+#[test]
+fn test_invalid_locate_length() {
+    let payload = [
+        0x0D, 0xF0, 0x7F, 0x7F, 0x06, 0x44, 0x07, 0x01, 0x01, 0x02, 0x03, 0x04, 0x00, 0xF7,
+    ];
+    assert_divergence(
+        &payload,
+        FindingKind::InvalidLocateLength { byte: 0x07 },
+        MidiEvent::Other(payload[1..].to_vec()),
+    );
+
+    // The cause counts from the start of the MIDI list, the finding from the payload
+    let violation = slave_netsync_flow_with_policy(&payload, ParsePolicy::Strict).unwrap_err();
+    assert_eq!(violation.finding.offset, 6);
+    assert_eq!(
+        violation.cause,
+        Some(ParseError::InvalidMidiData {
+            position: 5,
+            byte: 0x07
+        })
+    );
+    let source = std::error::Error::source(&violation).unwrap();
+    assert_eq!(source.to_string(), "Invalid MIDI data at position 5: 0x07");
+}
+
+#[test]
+fn test_invalid_masked_write_length() {
+    let payload = [
+        0x0B, 0xF0, 0x7F, 0x7F, 0x06, 0x41, 0x05, 0x4F, 0x00, 0x01, 0x01, 0xF7,
+    ];
+    assert_divergence(
+        &payload,
+        FindingKind::InvalidMmcLength {
+            command: "Masked Write",
+            byte: 0x05,
+            expected: 0x04,
+        },
+        MidiEvent::Other(payload[1..].to_vec()),
+    );
+}
+
+#[test]
+fn test_invalid_variable_play_length() {
+    let payload = [
+        0x0B, 0xF0, 0x7F, 0x7F, 0x06, 0x45, 0x76, 0x34, 0x68, 0x61, 0x19, 0xF7,
+    ];
+    assert_divergence(
+        &payload,
+        FindingKind::InvalidMmcLength {
+            command: "Variable Play",
+            byte: 0x76,
+            expected: 0x03,
+        },
+        MidiEvent::Other(payload[1..].to_vec()),
+    );
+}
+
+#[test]
+fn test_invalid_search_length() {
+    let payload = [0x09, 0xF0, 0x7F, 0x7F, 0x06, 0x46, 0x01, 0x40, 0x00, 0xF7];
+    assert_divergence(
+        &payload,
+        FindingKind::InvalidMmcLength {
+            command: "Search",
+            byte: 0x01,
+            expected: 0x03,
+        },
+        MidiEvent::Other(payload[1..].to_vec()),
+    );
+}
+
+#[test]
+fn test_invalid_shuttle_length() {
+    let payload = [
+        0x0A, 0xF0, 0x7F, 0x7F, 0x06, 0x47, 0x02, 0x01, 0x00, 0x00, 0xF7,
+    ];
+    assert_divergence(
+        &payload,
+        FindingKind::InvalidMmcLength {
+            command: "Shuttle",
+            byte: 0x02,
+            expected: 0x03,
+        },
+        MidiEvent::Other(payload[1..].to_vec()),
+    );
+    let violation = slave_netsync_flow_with_policy(&payload, ParsePolicy::Strict).unwrap_err();
+    assert_eq!(violation.finding.offset, 6);
+    assert_eq!(
+        violation.cause,
+        Some(ParseError::InvalidMidiData {
+            position: 5,
+            byte: 0x02
+        })
+    );
+}
+
+#[test]
+fn test_padded_full_frame() {
+    let payload = [
+        0x0C, 0xF0, 0x7F, 0x7F, 0x01, 0x01, 0x01, 0x02, 0x03, 0x04, 0x11, 0x22, 0xF7,
+    ];
+    assert_divergence(
+        &payload,
+        FindingKind::PaddedFullFrame { count: 2 },
+        MidiEvent::MtcFull {
+            hour: 1,
+            minute: 2,
+            second: 3,
+            frame: 4,
+        },
+    );
+
+    let violation = slave_netsync_flow_with_policy(&payload, ParsePolicy::Strict).unwrap_err();
+    assert_eq!(violation.finding.offset, 10);
+    assert_eq!(
+        violation.cause,
+        Some(ParseError::InvalidMidiData {
+            position: 9,
+            byte: 0x11
+        })
+    );
+}
+
+#[test]
+fn test_report_covers_every_mmc_length_byte() {
+    // Every MMC command with every plausible length byte: a policy rejecting a
+    // payload always names the deviation rather than falling back on the parser
+    for command in 0x01..=0x7F {
+        for size in [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x7F] {
+            for data in 0..=8 {
+                let mut framed = vec![7 + data, 0xF0, 0x7F, 0x7F, 0x06, command, size];
+                framed.extend(1..=data);
+                framed.push(0xF7);
+                for policy in [ParsePolicy::Strict, ParsePolicy::Lenient] {
+                    if let Err(violation) = slave_netsync_flow_with_policy(&framed, policy) {
+                        assert_ne!(
+                            violation.finding.kind,
+                            FindingKind::UnreadableMessage,
+                            "{:02X?} {:?}",
+                            framed,
+                            policy
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
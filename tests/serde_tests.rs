@@ -0,0 +1,194 @@
+#![cfg(feature = "serde")]
+
+use rtp_midi_netsync::header::PayloadHeader;
+use rtp_midi_netsync::mtc::{FrameRate, Pull};
+use rtp_midi_netsync::netsync::{
+    verify_payload, BuildOptions, CueOptions, FullFrameRefreshPolicy, NetsyncConfig, ParsePolicy,
+    TrackerConfig,
+};
+use rtp_midi_netsync::rtp::RtpHeader;
+use serde_json::json;
+
+fn custom_config() -> NetsyncConfig {
+    NetsyncConfig {
+        ssrc: 0x1234_5678,
+        refresh: FullFrameRefreshPolicy {
+            full_frame_interval_us: None,
+            quarter_frame_cycles: Some(4),
+        },
+        tracker: TrackerConfig {
+            frame_rate: FrameRate::Fps2997Df,
+            stale_after_quarter_frames: None,
+            freewheel_frames: 5,
+            pull: Pull::None,
+        },
+        policy: ParsePolicy::Strict,
+        build: BuildOptions {
+            quarter_frame_padding: 2,
+        },
+    }
+}
+
+#[test]
+fn test_config_toml_round_trip() {
+    for config in [NetsyncConfig::default(), custom_config()] {
+        let text = toml::to_string(&config).unwrap();
+        assert_eq!(toml::from_str::<NetsyncConfig>(&text).unwrap(), config);
+    }
+}
+
+#[test]
+fn test_config_json_round_trip() {
+    for config in [NetsyncConfig::default(), custom_config()] {
+        let text = serde_json::to_string(&config).unwrap();
+        assert_eq!(
+            serde_json::from_str::<NetsyncConfig>(&text).unwrap(),
+            config
+        );
+    }
+}
+
+#[test]
+fn test_config_file_schema() {
+    let config: NetsyncConfig = toml::from_str(
+        r#"
+        ssrc = 305419896
+        policy = "strict"
+
+        [refresh]
+        quarter_frame_cycles = 4
+
+        [tracker]
+        frame_rate = "29.97df"
+        freewheel_frames = 5
+
+        [build]
+        quarter_frame_padding = 2
+        "#,
+    )
+    .unwrap();
+    assert_eq!(config, custom_config());
+
+    // Left-out fields take their defaults
+    assert_eq!(
+        toml::from_str::<NetsyncConfig>("").unwrap(),
+        NetsyncConfig::default()
+    );
+    let config: NetsyncConfig = toml::from_str("policy = \"lenient\"").unwrap();
+    assert_eq!(config.policy, ParsePolicy::Lenient);
+    assert_eq!(config.tracker, TrackerConfig::default());
+}
+
+#[test]
+fn test_config_rejects_unknown_fields() {
+    assert!(toml::from_str::<NetsyncConfig>("ssrc = 1\nsrc = 2").is_err());
+    assert!(toml::from_str::<NetsyncConfig>("[refresh]\ninterval = 5").is_err());
+    assert!(toml::from_str::<NetsyncConfig>(
+        "[tracker]\nframe_rate = \"30\"\nfreewheel_frames = 0\nfreewheel = 1"
+    )
+    .is_err());
+    // Required within the tracker section
+    assert!(toml::from_str::<NetsyncConfig>("[tracker]\nfreewheel_frames = 0").is_err());
+    assert!(toml::from_str::<NetsyncConfig>(
+        "[tracker]\nframe_rate = \"60\"\nfreewheel_frames = 0"
+    )
+    .is_err());
+    assert!(toml::from_str::<NetsyncConfig>("policy = \"Strict\"").is_err());
+}
+
+#[test]
+fn test_frame_rate_names() {
+    let rates = [
+        (FrameRate::Fps24, "24"),
+        (FrameRate::Fps25, "25"),
+        (FrameRate::Fps2997Df, "29.97df"),
+        (FrameRate::Fps30, "30"),
+    ];
+    for (rate, name) in rates {
+        assert_eq!(serde_json::to_value(rate).unwrap(), json!(name));
+        assert_eq!(
+            serde_json::from_value::<FrameRate>(json!(name)).unwrap(),
+            rate
+        );
+    }
+}
+
+#[test]
+fn test_pull_names() {
+    let pulls = [
+        (Pull::None, "none"),
+        (Pull::Up01, "up01"),
+        (Pull::Down01, "down01"),
+    ];
+    for (pull, name) in pulls {
+        assert_eq!(serde_json::to_value(pull).unwrap(), json!(name));
+        assert_eq!(serde_json::from_value::<Pull>(json!(name)).unwrap(), pull);
+    }
+
+    let config: NetsyncConfig =
+        toml::from_str("[tracker]\nframe_rate = \"24\"\nfreewheel_frames = 0\npull = \"down01\"")
+            .unwrap();
+    assert_eq!(config.tracker.pull, Pull::Down01);
+}
+
+#[test]
+fn test_cue_options() {
+    let options = CueOptions { settle_us: 250_000 };
+    assert_eq!(
+        serde_json::to_value(options).unwrap(),
+        json!({ "settle_us": 250_000 })
+    );
+    assert_eq!(
+        toml::from_str::<CueOptions>("settle_us = 250000").unwrap(),
+        options
+    );
+    assert!(toml::from_str::<CueOptions>("settle = 250000").is_err());
+}
+
+#[test]
+fn test_headers_serialize() {
+    let header = RtpHeader::new(7, 1000, 0xAABB_CCDD);
+    let value = serde_json::to_value(header).unwrap();
+    assert_eq!(
+        value,
+        json!({
+            "marker": true,
+            "payload_type": 97,
+            "sequence_number": 7,
+            "timestamp": 1000,
+            "ssrc": 0xAABB_CCDDu32,
+        })
+    );
+    assert_eq!(serde_json::from_value::<RtpHeader>(value).unwrap(), header);
+
+    let header = PayloadHeader { flags: 0, len: 6 };
+    let value = serde_json::to_value(&header).unwrap();
+    assert_eq!(value, json!({ "flags": 0, "len": 6 }));
+    assert_eq!(
+        serde_json::from_value::<PayloadHeader>(value).unwrap(),
+        header
+    );
+}
+
+#[test]
+fn test_report_serialize() {
+    // LEN announces 6 bytes, only 2 follow, and the SysEx has no end
+    let report = verify_payload(&[0x06, 0xF0, 0x7F]);
+    let value = serde_json::to_value(&report).unwrap();
+    assert_eq!(
+        value,
+        json!({
+            "findings": [{
+                "offset": 0,
+                "severity": "error",
+                "kind": "truncated_midi_list",
+                "declared": 6,
+                "available": 2,
+            }, {
+                "offset": 3,
+                "severity": "error",
+                "kind": "missing_sysex_end",
+            }],
+        })
+    );
+}
@@ -0,0 +1,731 @@
+use rtp_midi_netsync::error::NetsyncError;
+use rtp_midi_netsync::midi::{MidiEvent, MmcCommand};
+use rtp_midi_netsync::mtc::{
+    smpte_to_quarter_frames, smpte_to_quarter_frames_rate, smpte_to_us, us_to_smpte, FrameRate,
+    MtcFullFrame, VlcTickT,
+};
+use rtp_midi_netsync::netsync::{
+    cue_and_play, master_netsync_flow, slave_netsync_flow, sync_burst, BuildOptions, CueOptions,
+    FullFrameRefreshPolicy, MasterSession, NetsyncConfig, NetsyncHandler, PositionTracker,
+    SlaveSession, TimeUpdateKind, TrackerConfig, MAX_QUARTER_FRAME_PADDING,
+};
+use rtp_midi_netsync::rtp::RtpHeader;
+
+/// Every callback received by the handler, in order
+#[derive(Debug, PartialEq)]
+enum Call {
+    Play,
+    Stop,
+    Locate(MtcFullFrame),
+    TimeUpdate(VlcTickT),
+    Other(Vec<u8>),
+}
+
+#[derive(Default)]
+struct RecordingHandler {
+    calls: Vec<Call>,
+}
+
+impl NetsyncHandler for RecordingHandler {
+    fn on_play(&mut self) {
+        self.calls.push(Call::Play);
+    }
+
+    fn on_stop(&mut self) {
+        self.calls.push(Call::Stop);
+    }
+
+    fn on_locate(&mut self, position: MtcFullFrame) {
+        self.calls.push(Call::Locate(position));
+    }
+
+    fn on_time_update(&mut self, us: VlcTickT) {
+        self.calls.push(Call::TimeUpdate(us));
+    }
+
+    fn on_other(&mut self, bytes: &[u8]) {
+        self.calls.push(Call::Other(bytes.to_vec()));
+    }
+}
+
+fn smpte(hours: u8, minutes: u8, seconds: u8, frames: u8) -> MtcFullFrame {
+    MtcFullFrame {
+        hours,
+        minutes,
+        seconds,
+        frames,
+    }
+}
+
+fn quarter_frame_payloads(tc: &MtcFullFrame) -> Vec<Vec<u8>> {
+    smpte_to_quarter_frames(tc)
+        .iter()
+        .map(|qf| {
+            master_netsync_flow(&MidiEvent::MtcQuarter {
+                msg_type: qf.frame_type,
+                value: qf.value,
+            })
+            .unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn test_dispatch_realistic_sequence() {
+    let start = smpte(0, 1, 0, 0);
+    let mut packets = vec![
+        master_netsync_flow(&MidiEvent::Mmc(MmcCommand::Locate {
+            hour: 0,
+            minute: 1,
+            second: 0,
+            frame: 0,
+            subframe: 0,
+        }))
+        .unwrap(),
+        master_netsync_flow(&MidiEvent::MtcFull {
+            hour: 0,
+            minute: 1,
+            second: 0,
+            frame: 0,
+        })
+        .unwrap(),
+        master_netsync_flow(&MidiEvent::Mmc(MmcCommand::Play)).unwrap(),
+    ];
+    packets.extend(quarter_frame_payloads(&start));
+    packets.extend(quarter_frame_payloads(&smpte(0, 1, 0, 2)));
+    packets.push(vec![0x03, 0x90, 0x3C, 0x7F]); // Note On pass-through
+    packets.push(master_netsync_flow(&MidiEvent::Mmc(MmcCommand::Stop)).unwrap());
+
+    let mut session = SlaveSession::new();
+    let mut handler = RecordingHandler::default();
+    for packet in &packets {
+        session.dispatch(packet, &mut handler).unwrap();
+    }
+
+    assert_eq!(
+        handler.calls,
+        vec![
+            Call::Locate(start),
+            Call::TimeUpdate(60_000_000),
+            Call::Play,
+            Call::TimeUpdate(60_000_000),
+            Call::TimeUpdate(smpte_to_us(&smpte(0, 1, 0, 2))),
+            Call::Other(vec![0x90, 0x3C, 0x7F]),
+            Call::Stop,
+        ]
+    );
+}
+
+#[test]
+fn test_dispatch_partial_cycle_reports_nothing() {
+    let mut session = SlaveSession::new();
+    let mut handler = RecordingHandler::default();
+
+    for packet in quarter_frame_payloads(&smpte(1, 0, 0, 0)).iter().take(7) {
+        session.dispatch(packet, &mut handler).unwrap();
+    }
+    assert!(handler.calls.is_empty());
+}
+
+#[test]
+fn test_dispatch_full_frame_discards_partial_cycle() {
+    let mut session = SlaveSession::new();
+    let mut handler = RecordingHandler::default();
+    let pieces = quarter_frame_payloads(&smpte(2, 0, 0, 0));
+
+    for packet in &pieces[..4] {
+        session.dispatch(packet, &mut handler).unwrap();
+    }
+    let full = master_netsync_flow(&MidiEvent::MtcFull {
+        hour: 3,
+        minute: 0,
+        second: 0,
+        frame: 0,
+    })
+    .unwrap();
+    session.dispatch(&full, &mut handler).unwrap();
+
+    // The rest of the interrupted cycle must not complete a timecode
+    for packet in &pieces[4..] {
+        session.dispatch(packet, &mut handler).unwrap();
+    }
+    assert_eq!(
+        handler.calls,
+        vec![Call::TimeUpdate(smpte_to_us(&smpte(3, 0, 0, 0)))]
+    );
+}
+
+#[test]
+fn test_dispatch_invalid_payload() {
+    let mut session = SlaveSession::new();
+    let mut handler = RecordingHandler::default();
+
+    assert_eq!(
+        session.dispatch(&[0x02], &mut handler),
+        Err(NetsyncError::TooShort { got: 1, min: 2 })
+    );
+    assert_eq!(
+        session.dispatch(&[0x23, 0x90, 0x3C, 0x7F], &mut handler),
+        Err(NetsyncError::UnsupportedFlags { flags: 0x2 })
+    );
+    assert_eq!(
+        session.dispatch(&[0x02, 0x90], &mut handler),
+        Err(NetsyncError::DeclaredLengthExceedsBuffer {
+            declared: 2,
+            available: 1
+        })
+    );
+    assert_eq!(
+        session.dispatch(&[0x02, 0x3C, 0x7F], &mut handler),
+        Err(NetsyncError::InvalidSlaveEvent)
+    );
+    assert!(handler.calls.is_empty());
+}
+
+#[test]
+fn test_dispatch_default_handler_methods() {
+    struct PlayOnly(u32);
+    impl NetsyncHandler for PlayOnly {
+        fn on_play(&mut self) {
+            self.0 += 1;
+        }
+    }
+
+    let mut session = SlaveSession::new();
+    let mut handler = PlayOnly(0);
+    let play = master_netsync_flow(&MidiEvent::Mmc(MmcCommand::Play)).unwrap();
+    let stop = master_netsync_flow(&MidiEvent::Mmc(MmcCommand::Stop)).unwrap();
+
+    session.dispatch(&play, &mut handler).unwrap();
+    session.dispatch(&stop, &mut handler).unwrap();
+    assert_eq!(handler.0, 1);
+}
+
+// === Master Session Full Frame Refresh Tests ===
+
+/// Quarter frame period at 30fps: 1/120 s
+const QF_PERIOD_US: f64 = 1_000_000.0 / 120.0;
+
+/// Strips the RTP header from a packet produced by a `MasterSession`
+#[test]
+fn test_dispatch_follows_frame_rate() {
+    let mut session = SlaveSession::with_config(NetsyncConfig {
+        tracker: TrackerConfig {
+            frame_rate: FrameRate::Fps25,
+            ..TrackerConfig::default()
+        },
+        ..NetsyncConfig::default()
+    });
+    let mut handler = RecordingHandler::default();
+
+    // Full Frames are read at the configured rate
+    let full = master_netsync_flow(&MidiEvent::MtcFull {
+        hour: 0,
+        minute: 0,
+        second: 1,
+        frame: 24,
+    })
+    .unwrap();
+    session.dispatch(&full, &mut handler).unwrap();
+
+    // Quarter frames at the rate they signal
+    for qf in smpte_to_quarter_frames_rate(&smpte(0, 1, 0, 2), FrameRate::Fps2997Df) {
+        let payload = master_netsync_flow(&MidiEvent::MtcQuarter {
+            msg_type: qf.frame_type,
+            value: qf.value,
+        })
+        .unwrap();
+        session.dispatch(&payload, &mut handler).unwrap();
+    }
+
+    assert_eq!(
+        handler.calls,
+        vec![Call::TimeUpdate(1_960_000), Call::TimeUpdate(60_060_000)]
+    );
+}
+
+#[test]
+fn test_master_timestamps_wrap_for_late_times() {
+    let mut master = MasterSession::new();
+    for (now_us, timestamp) in [
+        (1_000_000, 10_000),
+        // 100 µs per tick, modulo 2^32
+        (429_496_729_600, 0),
+        (u64::MAX, 0xF5C2_8F5C),
+    ] {
+        let packet = master
+            .packet_for_event(&MidiEvent::Mmc(MmcCommand::Play), now_us)
+            .unwrap();
+        assert_eq!(RtpHeader::parse(&packet).unwrap().0.timestamp, timestamp);
+    }
+}
+
+fn payload_of(packet: &[u8]) -> Vec<u8> {
+    RtpHeader::parse(packet).unwrap().1.to_vec()
+}
+
+fn is_full_frame(payload: &[u8]) -> bool {
+    matches!(slave_netsync_flow(payload), Ok(MidiEvent::MtcFull { .. }))
+}
+
+/// Runs a master rolling from `start` for `duration_us`, sending quarter frames and
+/// ticking every quarter-frame period. Returns every payload, without its RTP
+/// header, with its send time.
+fn run_master(
+    session: &mut MasterSession,
+    start: &MtcFullFrame,
+    duration_us: u64,
+) -> Vec<(u64, Vec<u8>)> {
+    let start_us = smpte_to_us(start);
+    let mut sent = vec![
+        (
+            0,
+            session
+                .packet_for_event(
+                    &MidiEvent::Mmc(MmcCommand::Locate {
+                        hour: start.hours,
+                        minute: start.minutes,
+                        second: start.seconds,
+                        frame: start.frames,
+                        subframe: 0,
+                    }),
+                    0,
+                )
+                .map(|packet| payload_of(&packet))
+                .unwrap(),
+        ),
+        (
+            0,
+            session
+                .packet_for_event(&MidiEvent::Mmc(MmcCommand::Play), 0)
+                .map(|packet| payload_of(&packet))
+                .unwrap(),
+        ),
+    ];
+
+    let mut piece = 0u64;
+    loop {
+        let now_us = (piece as f64 * QF_PERIOD_US).round() as u64;
+        if now_us > duration_us {
+            break;
+        }
+        let cycle_tc = us_to_smpte(start_us + ((piece / 8) as f64 * 8.0 * QF_PERIOD_US) as u64);
+        let qf = smpte_to_quarter_frames(&cycle_tc)[(piece % 8) as usize];
+        let event = MidiEvent::MtcQuarter {
+            msg_type: qf.frame_type,
+            value: qf.value,
+        };
+        let packet = session.packet_for_event(&event, now_us).unwrap();
+        sent.push((now_us, payload_of(&packet)));
+        for packet in session.tick(now_us) {
+            sent.push((now_us, payload_of(&packet)));
+        }
+        piece += 1;
+    }
+    sent
+}
+
+#[test]
+fn test_master_full_frame_after_locate() {
+    let mut session = MasterSession::with_policy(FullFrameRefreshPolicy {
+        full_frame_interval_us: None,
+        quarter_frame_cycles: None,
+    });
+    assert!(session.tick(0).is_empty());
+
+    session
+        .packet_for_event(
+            &MidiEvent::Mmc(MmcCommand::Locate {
+                hour: 0,
+                minute: 5,
+                second: 0,
+                frame: 0,
+                subframe: 0,
+            }),
+            0,
+        )
+        .unwrap();
+    let packets = session.tick(10);
+    assert_eq!(packets.len(), 1);
+    assert_eq!(
+        slave_netsync_flow(&payload_of(&packets[0])).unwrap(),
+        MidiEvent::MtcFull {
+            hour: 0,
+            minute: 5,
+            second: 0,
+            frame: 0
+        }
+    );
+
+    // Nothing else is due without a periodic trigger
+    assert!(session.tick(10_000_000).is_empty());
+}
+
+#[test]
+fn test_master_play_sends_sync_burst() {
+    let start = smpte(0, 5, 0, 0);
+    let mut master = MasterSession::new();
+    let packets = master.play(&start, 1_000).unwrap();
+    let payloads: Vec<_> = packets.iter().map(|packet| payload_of(packet)).collect();
+    assert_eq!(payloads, sync_burst(&start).unwrap());
+    let sequences: Vec<_> = packets
+        .iter()
+        .map(|packet| RtpHeader::parse(packet).unwrap().0.sequence_number)
+        .collect();
+    assert_eq!(sequences, [0, 1]);
+
+    // A slave jumps to the start position then starts rolling
+    let mut slave = SlaveSession::new();
+    let mut handler = RecordingHandler::default();
+    for payload in &payloads {
+        slave.dispatch(payload, &mut handler).unwrap();
+    }
+    assert_eq!(
+        handler.calls,
+        [Call::TimeUpdate(smpte_to_us(&start)), Call::Play]
+    );
+
+    // The master rolls from the start position: its next refresh is one second
+    // further, followed by Play
+    assert!(master.tick(500_000).is_empty());
+    let refresh: Vec<_> = master
+        .tick(1_001_000)
+        .iter()
+        .map(|packet| slave_netsync_flow(&payload_of(packet)).unwrap())
+        .collect();
+    assert_eq!(
+        refresh,
+        [
+            MidiEvent::MtcFull {
+                hour: 0,
+                minute: 5,
+                second: 1,
+                frame: 0
+            },
+            MidiEvent::Mmc(MmcCommand::Play)
+        ]
+    );
+}
+
+#[test]
+fn test_master_cue_play() {
+    let mut master = MasterSession::new();
+    master
+        .packet_for_event(&MidiEvent::Mmc(MmcCommand::Play), 0)
+        .unwrap();
+    let options = CueOptions { settle_us: 300_000 };
+    let target = smpte(0, 2, 0, 0);
+    let sent = master.cue_play(smpte_to_us(&target), options, 1_000_000);
+
+    // Locate now, Deferred Play once the slaves have settled
+    let expected = cue_and_play(&target, options).unwrap();
+    assert_eq!(sent.len(), 2);
+    for ((send_us, packet), (offset_us, payload)) in sent.iter().zip(&expected) {
+        assert_eq!(*send_us, 1_000_000 + offset_us);
+        assert_eq!(&payload_of(packet), payload);
+    }
+
+    // A refresh while settling repeats the target and holds back Play
+    let refresh = master.tick(1_100_000);
+    assert_eq!(refresh.len(), 1);
+    assert_eq!(
+        slave_netsync_flow(&payload_of(&refresh[0])),
+        Ok(MidiEvent::MtcFull {
+            hour: 0,
+            minute: 2,
+            second: 0,
+            frame: 0
+        })
+    );
+
+    // A slave receiving everything starts rolling from the target on time
+    let mut slave = PositionTracker::new();
+    let received = [
+        (1_000_000, &sent[0].1),
+        (1_100_000, &refresh[0]),
+        (1_300_000, &sent[1].1),
+    ];
+    for (arrival_us, packet) in received {
+        let event = slave_netsync_flow(&payload_of(packet)).unwrap();
+        slave.apply_event(&event, arrival_us).unwrap();
+    }
+    assert!(slave.is_playing());
+    assert_eq!(
+        slave.position_at(1_310_000),
+        Some(smpte_to_us(&target) + 10_000)
+    );
+}
+
+#[test]
+fn test_master_pads_quarter_frames() {
+    let mut master = MasterSession::with_config(NetsyncConfig {
+        build: BuildOptions {
+            quarter_frame_padding: 2,
+        },
+        ..NetsyncConfig::default()
+    });
+    let tc = smpte(0, 1, 2, 3);
+    let mut slave = SlaveSession::new();
+    let mut handler = RecordingHandler::default();
+    for (now_us, payload) in quarter_frame_payloads(&tc).iter().enumerate() {
+        let event = slave_netsync_flow(payload).unwrap();
+        let packet = master.packet_for_event(&event, now_us as u64).unwrap();
+        let padded = payload_of(&packet);
+        assert_eq!(padded[0], 0x04);
+        assert_eq!(padded[1..3], payload[1..]);
+        assert_eq!(padded[3..], [0x00, 0x00]);
+        slave.dispatch(&padded, &mut handler).unwrap();
+    }
+    assert_eq!(handler.calls, [Call::TimeUpdate(smpte_to_us(&tc))]);
+
+    // The refresh carries no quarter frame, so nothing is padded
+    let refresh = master.tick(10);
+    assert_eq!(payload_of(&refresh[0])[0], 0x0A);
+
+    let mut master = MasterSession::with_config(NetsyncConfig {
+        build: BuildOptions {
+            quarter_frame_padding: MAX_QUARTER_FRAME_PADDING + 1,
+        },
+        ..NetsyncConfig::default()
+    });
+    let quarter = MidiEvent::MtcQuarter {
+        msg_type: 0,
+        value: 0,
+    };
+    assert_eq!(
+        master.packet_for_event(&quarter, 0),
+        Err(NetsyncError::InvalidMasterEvent)
+    );
+    assert_eq!(master.metrics().packets_sent, 0);
+}
+
+#[test]
+fn test_master_time_update_cycles() {
+    let mut master = MasterSession::new();
+    let mut slave = SlaveSession::new();
+    let mut handler = RecordingHandler::default();
+    let mut send =
+        |master: &mut MasterSession, handler: &mut RecordingHandler, position_us, kind| {
+            let packets = master.time_update(position_us, kind, position_us).unwrap();
+            for packet in &packets {
+                slave.dispatch(&payload_of(packet), handler).unwrap();
+            }
+            packets.len()
+        };
+
+    // One piece per call, every piece of a cycle carrying the timecode its piece
+    // 0 was sent at, then a new cycle from the position reached
+    for cycle in 0..2u64 {
+        for piece in 0..8 {
+            let position_us = 10_000_000 + (cycle * 8 + piece) * 1_000_000 / 120;
+            assert_eq!(
+                send(
+                    &mut master,
+                    &mut handler,
+                    position_us,
+                    TimeUpdateKind::QuarterFrame
+                ),
+                1
+            );
+        }
+    }
+    assert_eq!(
+        handler.calls,
+        [
+            Call::TimeUpdate(smpte_to_us(&smpte(0, 0, 10, 0))),
+            Call::TimeUpdate(smpte_to_us(&smpte(0, 0, 10, 2)))
+        ]
+    );
+
+    // A Full Frame interrupts the cycle in progress: the next piece is piece 0
+    handler.calls.clear();
+    send(
+        &mut master,
+        &mut handler,
+        20_000_000,
+        TimeUpdateKind::QuarterFrame,
+    );
+    assert_eq!(
+        send(
+            &mut master,
+            &mut handler,
+            30_000_000,
+            TimeUpdateKind::FullFrame
+        ),
+        1
+    );
+    assert_eq!(
+        handler.calls,
+        [Call::TimeUpdate(smpte_to_us(&smpte(0, 0, 30, 0)))]
+    );
+    let next = master
+        .time_update(40_000_000, TimeUpdateKind::QuarterFrame, 40_000_000)
+        .unwrap();
+    assert_eq!(
+        slave_netsync_flow(&payload_of(&next[0])),
+        Ok(MidiEvent::MtcQuarter {
+            msg_type: 0,
+            value: 0
+        })
+    );
+
+    // So does a burst, which carries a whole cycle
+    let burst = master
+        .time_update(50_000_000, TimeUpdateKind::QuarterFrameBurst, 50_000_000)
+        .unwrap();
+    assert_eq!(burst.len(), 8);
+    let next = master
+        .time_update(60_000_000, TimeUpdateKind::QuarterFrame, 60_000_000)
+        .unwrap();
+    assert_eq!(
+        slave_netsync_flow(&payload_of(&next[0])),
+        Ok(MidiEvent::MtcQuarter {
+            msg_type: 0,
+            value: 0
+        })
+    );
+}
+
+#[test]
+fn test_master_play_rejects_invalid_start() {
+    let mut master = MasterSession::new();
+    assert_eq!(
+        master.play(&smpte(24, 0, 0, 0), 0),
+        Err(NetsyncError::InvalidMasterEvent)
+    );
+    // Nothing was recorded or sent
+    assert!(master.tick(10_000_000).is_empty());
+    assert_eq!(master.metrics().packets_sent, 0);
+    let packets = master.play(&smpte(0, 0, 1, 0), 0).unwrap();
+    assert_eq!(RtpHeader::parse(&packets[0]).unwrap().0.sequence_number, 0);
+}
+
+#[test]
+fn test_master_full_frame_interval() {
+    let mut session = MasterSession::with_policy(FullFrameRefreshPolicy {
+        full_frame_interval_us: Some(250_000),
+        quarter_frame_cycles: None,
+    });
+    let sent = run_master(&mut session, &smpte(0, 10, 0, 0), 1_000_000);
+
+    let full_frame_times: Vec<u64> = sent
+        .iter()
+        .filter(|(_, payload)| is_full_frame(payload))
+        .map(|(time, _)| *time)
+        .collect();
+    assert!(full_frame_times.len() >= 4);
+    for pair in full_frame_times.windows(2) {
+        let gap = pair[1] - pair[0];
+        assert!((250_000..250_000 + 8_334).contains(&gap), "gap {}", gap);
+    }
+
+    // Every refresh while rolling is followed by Play
+    for (i, (_, payload)) in sent.iter().enumerate() {
+        if is_full_frame(payload) {
+            assert_eq!(
+                slave_netsync_flow(&sent[i + 1].1).unwrap(),
+                MidiEvent::Mmc(MmcCommand::Play)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_master_full_frame_after_quarter_frame_cycles() {
+    let mut session = MasterSession::with_policy(FullFrameRefreshPolicy {
+        full_frame_interval_us: None,
+        quarter_frame_cycles: Some(4),
+    });
+    let sent = run_master(&mut session, &smpte(0, 0, 30, 0), 1_000_000);
+
+    // Initial Full Frame after the Locate on piece 0, then one each time a fourth
+    // cycle completes on piece 7: 31 quarter frames later, then every 32
+    let mut quarter_frames_between = Vec::new();
+    let mut count = 0;
+    for (_, payload) in sent.iter().skip(2) {
+        if is_full_frame(payload) {
+            quarter_frames_between.push(count);
+            count = 0;
+        } else if matches!(
+            slave_netsync_flow(payload),
+            Ok(MidiEvent::MtcQuarter { .. })
+        ) {
+            count += 1;
+        }
+    }
+    assert_eq!(quarter_frames_between[..2], [1, 31]);
+    assert!(quarter_frames_between[2..].iter().all(|&n| n == 32));
+}
+
+#[test]
+fn test_master_full_frame_after_quarter_frame_discontinuity() {
+    let mut session = MasterSession::with_policy(FullFrameRefreshPolicy {
+        full_frame_interval_us: None,
+        quarter_frame_cycles: None,
+    });
+    session
+        .packet_for_event(&MidiEvent::Mmc(MmcCommand::Play), 0)
+        .unwrap();
+
+    let mut send_cycle = |tc: &MtcFullFrame, now_us: u64| {
+        for qf in smpte_to_quarter_frames(tc) {
+            let event = MidiEvent::MtcQuarter {
+                msg_type: qf.frame_type,
+                value: qf.value,
+            };
+            session.packet_for_event(&event, now_us).unwrap();
+        }
+        session.tick(now_us)
+    };
+
+    // The first cycle establishes the position, the second follows on from it
+    assert_eq!(send_cycle(&smpte(0, 0, 1, 0), 0).len(), 0);
+    assert_eq!(send_cycle(&smpte(0, 0, 1, 2), 66_667).len(), 0);
+    // A jump of ten seconds between cycles is a discontinuity
+    let packets = send_cycle(&smpte(0, 0, 11, 4), 133_333);
+    assert_eq!(packets.len(), 2);
+    assert!(is_full_frame(&payload_of(&packets[0])));
+}
+
+#[test]
+fn test_late_joining_slave_locks_within_interval() {
+    const INTERVAL_US: u64 = 500_000;
+    const JOIN_US: u64 = 1_234_567;
+
+    let start = smpte(0, 10, 0, 0);
+    let start_us = smpte_to_us(&start);
+    let mut session = MasterSession::with_policy(FullFrameRefreshPolicy {
+        full_frame_interval_us: Some(INTERVAL_US),
+        quarter_frame_cycles: None,
+    });
+    let sent = run_master(&mut session, &start, 3_000_000);
+
+    // The slave only sees what was sent after it joined: it missed Locate and Play
+    let mut tracker = PositionTracker::new();
+    let mut locked_at = None;
+    for (time, payload) in sent.iter().filter(|(time, _)| *time >= JOIN_US) {
+        let event = slave_netsync_flow(payload).unwrap();
+        tracker.apply_event(&event, *time).unwrap();
+
+        let true_us = start_us + time;
+        let locked = tracker.is_playing()
+            && tracker
+                .position_at(*time)
+                .is_some_and(|us| us.abs_diff(true_us) < 33_333);
+        if locked && locked_at.is_none() {
+            locked_at = Some(*time);
+        }
+    }
+
+    let locked_at = locked_at.expect("slave never locked");
+    assert!(
+        locked_at - JOIN_US <= INTERVAL_US,
+        "locked after {} us",
+        locked_at - JOIN_US
+    );
+
+    // Once locked, the slave keeps following the master
+    let end_us = sent.last().unwrap().0;
+    let estimate = tracker.position_at(end_us).unwrap();
+    assert!(estimate.abs_diff(start_us + end_us) < 33_333);
+}
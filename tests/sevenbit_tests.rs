@@ -0,0 +1,115 @@
+use rtp_midi_netsync::error::ParseError;
+use rtp_midi_netsync::util::sevenbit::{
+    pack, pack_into, packed_len, unpack, unpack_into, unpacked_len,
+};
+
+/// Deterministic pseudo-random bytes (xorshift32), so failures reproduce
+fn test_bytes(len: usize, seed: u32) -> Vec<u8> {
+    let mut state = seed.max(1);
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        })
+        .collect()
+}
+
+// === Packing ===
+
+#[test]
+fn test_fixed_vector() {
+    // High bits of data bytes 0, 2 and 5 collected in bits 0, 2 and 5 of the
+    // first byte, then a second group holding the eighth data byte
+    let data = [0x80, 0x01, 0xFF, 0x7F, 0x00, 0x81, 0x40, 0xC3];
+    let packed = [0x25, 0x00, 0x01, 0x7F, 0x7F, 0x00, 0x01, 0x40, 0x01, 0x43];
+
+    let mut out = vec![0xF0];
+    pack(&data, &mut out);
+    assert_eq!(out[0], 0xF0);
+    assert_eq!(&out[1..], &packed);
+
+    let mut unpacked = Vec::new();
+    assert_eq!(unpack(&packed, &mut unpacked), Ok(()));
+    assert_eq!(unpacked, data);
+}
+
+#[test]
+fn test_roundtrip_lengths_and_values() {
+    for len in 0..=64 {
+        for seed in [1, 0xDEAD_BEEF, 0x1234_5678] {
+            let data = test_bytes(len, seed + len as u32);
+            let mut packed = Vec::new();
+            pack(&data, &mut packed);
+            assert_eq!(packed.len(), packed_len(len));
+            assert!(packed.iter().all(|&byte| byte < 0x80), "{:02X?}", packed);
+            assert_eq!(unpacked_len(packed.len()), len);
+
+            let mut unpacked = Vec::new();
+            assert_eq!(unpack(&packed, &mut unpacked), Ok(()));
+            assert_eq!(unpacked, data);
+        }
+    }
+    // Every byte value in every group position
+    let data: Vec<u8> = (0..=255).collect();
+    let mut packed = Vec::new();
+    pack(&data, &mut packed);
+    let mut unpacked = Vec::new();
+    unpack(&packed, &mut unpacked).unwrap();
+    assert_eq!(unpacked, data);
+}
+
+#[test]
+fn test_into_variants_match_vec_variants() {
+    let data = test_bytes(20, 7);
+    let mut packed = [0xAA; 32];
+    let len = pack_into(&data, &mut packed);
+    assert_eq!(len, packed_len(data.len()));
+    assert_eq!(packed[len], 0xAA);
+
+    let mut expected = Vec::new();
+    pack(&data, &mut expected);
+    assert_eq!(&packed[..len], &expected[..]);
+
+    let mut unpacked = [0; 20];
+    assert_eq!(unpack_into(&packed[..len], &mut unpacked), Ok(20));
+    assert_eq!(&unpacked[..], &data[..]);
+}
+
+// === Unpacking errors ===
+
+#[test]
+fn test_unpack_errors() {
+    let mut out = vec![0x11];
+
+    // A data byte with its high bit set
+    assert_eq!(
+        unpack(&[0x00, 0x01, 0x82], &mut out),
+        Err(ParseError::InvalidMidiData {
+            position: 2,
+            byte: 0x82,
+        })
+    );
+    // High bit announced for a third data byte the final group lacks
+    assert_eq!(
+        unpack(&[0x04, 0x01, 0x02], &mut out),
+        Err(ParseError::InvalidMidiData {
+            position: 0,
+            byte: 0x04,
+        })
+    );
+    // Second group holds only its high-bit byte
+    let mut packed = Vec::new();
+    pack(&[0; 7], &mut packed);
+    packed.push(0x00);
+    assert_eq!(
+        unpack(&packed, &mut out),
+        Err(ParseError::BufferTooSmall {
+            requested: 10,
+            available: 9,
+        })
+    );
+    // The vector is left as it was
+    assert_eq!(out, [0x11]);
+}
@@ -0,0 +1,143 @@
+use rtp_midi_netsync::error::ParseError;
+use rtp_midi_netsync::midi::{MidiEvent, MmcCommand};
+use rtp_midi_netsync::netsync::{master_netsync_flow, StreamParser, MAX_PAYLOAD_LENGTH};
+
+fn events() -> Vec<MidiEvent> {
+    vec![
+        MidiEvent::Mmc(MmcCommand::Locate {
+            hour: 1,
+            minute: 2,
+            second: 3,
+            frame: 4,
+            subframe: 0,
+        }),
+        MidiEvent::MtcQuarter {
+            msg_type: 0,
+            value: 4,
+        },
+        MidiEvent::MtcFull {
+            hour: 1,
+            minute: 2,
+            second: 3,
+            frame: 4,
+        },
+        MidiEvent::Mmc(MmcCommand::Play),
+        MidiEvent::Other(vec![0x90, 0x3C, 0x7F]),
+    ]
+}
+
+/// The payloads of `events()`, back to back
+fn stream() -> Vec<u8> {
+    let mut stream: Vec<u8> = events()
+        .iter()
+        .filter(|event| !matches!(event, MidiEvent::Other(_)))
+        .flat_map(|event| master_netsync_flow(event).unwrap())
+        .collect();
+    stream.extend_from_slice(&[0x03, 0x90, 0x3C, 0x7F]);
+    stream
+}
+
+/// Feeds `chunks` in order, polling every event after each one
+fn feed_all<'a>(
+    parser: &mut StreamParser,
+    chunks: impl IntoIterator<Item = &'a [u8]>,
+) -> Vec<MidiEvent> {
+    let mut received = Vec::new();
+    for chunk in chunks {
+        parser.feed(chunk).unwrap();
+        while let Some(event) = parser.poll() {
+            received.push(event);
+        }
+    }
+    received
+}
+
+#[test]
+fn test_stream_split_at_every_boundary() {
+    let stream = stream();
+    for split in 0..=stream.len() {
+        let mut parser = StreamParser::new(64);
+        let (head, tail) = stream.split_at(split);
+        assert_eq!(
+            feed_all(&mut parser, [head, tail]),
+            events(),
+            "split at {}",
+            split
+        );
+        assert_eq!(parser.buffered(), 0);
+        assert_eq!(parser.discarded(), 0);
+    }
+}
+
+#[test]
+fn test_stream_one_byte_at_a_time() {
+    let stream = stream();
+    let mut parser = StreamParser::new(MAX_PAYLOAD_LENGTH);
+    assert_eq!(feed_all(&mut parser, stream.chunks(1)), events());
+    assert_eq!(parser.discarded(), 0);
+}
+
+#[test]
+fn test_stream_resynchronizes_after_garbage() {
+    // A LEN=0 header, stray data bytes, then headers with the Z, J and P flags
+    let garbage = [0x00, 0x35, 0x7F, 0x22, 0xF0, 0x41, 0x10];
+    let mut bytes = garbage.to_vec();
+    bytes.extend(stream());
+    for split in 0..=bytes.len() {
+        let mut parser = StreamParser::new(64);
+        let (head, tail) = bytes.split_at(split);
+        assert_eq!(
+            feed_all(&mut parser, [head, tail]),
+            events(),
+            "split at {}",
+            split
+        );
+        assert_eq!(parser.discarded(), garbage.len() as u64);
+    }
+}
+
+#[test]
+fn test_stream_joined_mid_payload() {
+    // The receiver starts listening four bytes into the Locate payload
+    let stream = stream();
+    let mut parser = StreamParser::new(64);
+    assert_eq!(feed_all(&mut parser, [&stream[4..]]), events()[1..]);
+    assert_eq!(parser.discarded(), 10);
+}
+
+#[test]
+fn test_stream_buffer_is_bounded() {
+    let mut parser = StreamParser::new(0);
+    assert_eq!(parser.capacity(), MAX_PAYLOAD_LENGTH);
+
+    let locate = master_netsync_flow(&events()[0]).unwrap();
+    parser.feed(&locate[..10]).unwrap();
+    assert_eq!(
+        parser.feed(&locate),
+        Err(ParseError::BufferTooSmall {
+            requested: 14,
+            available: 4
+        })
+    );
+    // Nothing was appended: the rest of the payload still completes it
+    assert_eq!(parser.buffered(), 10);
+    parser.feed(&locate[10..]).unwrap();
+    assert_eq!(parser.poll(), Some(events()[0].clone()));
+
+    // Consumed bytes make room again
+    for _ in 0..4 {
+        parser.feed(&locate).unwrap();
+        assert_eq!(parser.poll(), Some(events()[0].clone()));
+    }
+    assert_eq!(parser.poll(), None);
+}
+
+#[test]
+fn test_stream_skips_headers_beyond_capacity() {
+    // A two-octet header announcing 4095 bytes can never complete in 64
+    let mut bytes = vec![0x8F, 0xFF, 0xF8];
+    bytes.extend(stream());
+    let mut parser = StreamParser::new(64);
+    assert_eq!(feed_all(&mut parser, [bytes.as_slice()]), events());
+    assert_eq!(parser.discarded(), 3);
+}
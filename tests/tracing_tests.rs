@@ -0,0 +1,286 @@
+#![cfg(feature = "tracing")]
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+use rtp_midi_netsync::midi::{MidiEvent, MmcCommand};
+use rtp_midi_netsync::mtc::{smpte_to_quarter_frames, MtcFullFrame};
+use rtp_midi_netsync::netsync::{
+    master_netsync_flow, slave_netsync_flow, MasterSession, NetsyncHandler, SlaveSession,
+};
+
+/// An event with the names of the spans it was recorded in, innermost last
+#[derive(Debug, Clone)]
+struct Captured {
+    spans: Vec<String>,
+    fields: HashMap<String, String>,
+}
+
+impl Captured {
+    fn message(&self) -> &str {
+        self.fields.get("message").map_or("", String::as_str)
+    }
+
+    fn field(&self, name: &str) -> Option<&str> {
+        self.fields.get(name).map(String::as_str)
+    }
+}
+
+#[derive(Default)]
+struct FieldMap(HashMap<String, String>);
+
+impl Visit for FieldMap {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+#[derive(Default)]
+struct State {
+    /// Name and fields of each span, indexed by id - 1
+    spans: Vec<(String, HashMap<String, String>)>,
+    stack: Vec<usize>,
+    events: Vec<Captured>,
+}
+
+/// Records every event and span at every level
+#[derive(Clone, Default)]
+struct Capture(Arc<Mutex<State>>);
+
+impl Capture {
+    fn events(&self) -> Vec<Captured> {
+        self.0.lock().unwrap().events.clone()
+    }
+
+    /// Fields of the first span named `name`
+    fn span_fields(&self, name: &str) -> HashMap<String, String> {
+        let state = self.0.lock().unwrap();
+        state
+            .spans
+            .iter()
+            .find(|(span, _)| span == name)
+            .map(|(_, fields)| fields.clone())
+            .unwrap_or_else(|| panic!("no span {}", name))
+    }
+}
+
+impl Subscriber for Capture {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = FieldMap::default();
+        span.record(&mut fields);
+        let mut state = self.0.lock().unwrap();
+        state
+            .spans
+            .push((span.metadata().name().to_string(), fields.0));
+        Id::from_u64(state.spans.len() as u64)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut fields = FieldMap::default();
+        values.record(&mut fields);
+        let mut state = self.0.lock().unwrap();
+        state.spans[span.into_u64() as usize - 1].1.extend(fields.0);
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = FieldMap::default();
+        event.record(&mut fields);
+        let mut state = self.0.lock().unwrap();
+        let spans = state
+            .stack
+            .iter()
+            .map(|&index| state.spans[index].0.clone())
+            .collect();
+        state.events.push(Captured {
+            spans,
+            fields: fields.0,
+        });
+    }
+
+    fn enter(&self, span: &Id) {
+        let mut state = self.0.lock().unwrap();
+        state.stack.push(span.into_u64() as usize - 1);
+    }
+
+    fn exit(&self, _: &Id) {
+        self.0.lock().unwrap().stack.pop();
+    }
+}
+
+/// Runs `f` with a capturing subscriber and returns it
+fn capture(f: impl FnOnce()) -> Capture {
+    let capture = Capture::default();
+    tracing::subscriber::with_default(capture.clone(), f);
+    capture
+}
+
+struct IgnoreEvents;
+
+impl NetsyncHandler for IgnoreEvents {}
+
+const TIMECODE: MtcFullFrame = MtcFullFrame {
+    hours: 1,
+    minutes: 2,
+    seconds: 3,
+    frames: 4,
+};
+
+#[test]
+fn test_flows_emit_kind_and_length() {
+    let event = MidiEvent::Mmc(MmcCommand::Play);
+    let mut payload = Vec::new();
+    let capture = capture(|| {
+        payload = master_netsync_flow(&event).unwrap();
+        slave_netsync_flow(&payload).unwrap();
+        slave_netsync_flow(&[0x01]).unwrap_err();
+    });
+    let events = capture.events();
+
+    let built = events
+        .iter()
+        .find(|e| e.message() == "payload built")
+        .unwrap();
+    assert_eq!(built.spans, ["master_netsync_flow"]);
+    assert_eq!(built.field("len"), Some(payload.len().to_string().as_str()));
+    assert_eq!(capture.span_fields("master_netsync_flow")["kind"], "Play");
+
+    let decoded = events
+        .iter()
+        .find(|e| e.message() == "payload decoded")
+        .unwrap();
+    assert_eq!(decoded.spans, ["slave_netsync_flow"]);
+    assert_eq!(decoded.field("kind"), Some("Play"));
+
+    let rejected = events
+        .iter()
+        .find(|e| e.message() == "payload rejected")
+        .unwrap();
+    assert!(rejected.field("error").unwrap().starts_with("TooShort"));
+}
+
+#[test]
+fn test_master_flow_reports_unsupported_event() {
+    let capture = capture(|| {
+        master_netsync_flow(&MidiEvent::Other(vec![0x90, 0x40, 0x7F])).unwrap_err();
+    });
+    let events = capture.events();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].message(), "event not sent");
+    assert_eq!(events[0].field("error"), Some("InvalidMasterEvent"));
+}
+
+#[test]
+fn test_session_reports_sequence_and_timecode() {
+    let mut master = MasterSession::new();
+    let mut packets: Vec<Vec<u8>> = smpte_to_quarter_frames(&TIMECODE)
+        .iter()
+        .enumerate()
+        .map(|(piece, qf)| {
+            let event = MidiEvent::MtcQuarter {
+                msg_type: qf.frame_type,
+                value: qf.value,
+            };
+            master.packet_for_event(&event, piece as u64).unwrap()
+        })
+        .collect();
+    // Lose the packet after the cycle
+    master
+        .packet_for_event(&MidiEvent::Mmc(MmcCommand::Stop), 8)
+        .unwrap();
+    packets.push(
+        master
+            .packet_for_event(&MidiEvent::Mmc(MmcCommand::Stop), 9)
+            .unwrap(),
+    );
+    packets.push(packets[8].clone());
+
+    let mut slave = SlaveSession::new();
+    let capture = capture(|| {
+        for packet in &packets {
+            slave.feed_packet(packet, 0, &mut IgnoreEvents).unwrap();
+        }
+    });
+    let events = capture.events();
+
+    // Both the session and its position tracker assemble quarter frames
+    for piece in 0..8 {
+        assert!(events.iter().any(|e| e.message() == "quarter frame"
+            && e.field("frame_type") == Some(piece.to_string().as_str())));
+    }
+    let timecode = events
+        .iter()
+        .find(|e| e.message() == "timecode from quarter frames")
+        .unwrap();
+    assert_eq!(timecode.spans, ["feed_packet"]);
+    assert_eq!(
+        timecode.field("timecode"),
+        Some(format!("{:?}", TIMECODE).as_str())
+    );
+    assert!(events
+        .iter()
+        .any(|e| e.message() == "quarter-frame cycle assembled"));
+
+    let gap = events
+        .iter()
+        .find(|e| e.message() == "sequence gap")
+        .unwrap();
+    assert_eq!(gap.field("lost"), Some("1"));
+    assert!(events.iter().any(|e| e.message() == "duplicate dropped"));
+    assert_eq!(
+        events
+            .iter()
+            .filter(|e| e.message() == "packet accepted")
+            .count(),
+        9
+    );
+    assert!(capture.span_fields("feed_packet").contains_key("sequence"));
+}
+
+#[test]
+fn test_tick_reports_full_frame_refresh() {
+    let mut master = MasterSession::new();
+    let locate = MidiEvent::Mmc(MmcCommand::Locate {
+        hour: TIMECODE.hours,
+        minute: TIMECODE.minutes,
+        second: TIMECODE.seconds,
+        frame: TIMECODE.frames,
+        subframe: 0,
+    });
+    master.packet_for_event(&locate, 0).unwrap();
+
+    let mut sent = Vec::new();
+    let capture = capture(|| sent = master.tick(0));
+    assert_eq!(sent.len(), 1);
+
+    let events = capture.events();
+    let refresh = events
+        .iter()
+        .find(|e| e.message() == "Full Frame refresh")
+        .unwrap();
+    assert_eq!(refresh.spans, ["tick"]);
+    assert_eq!(
+        refresh.field("timecode"),
+        Some(format!("{:?}", TIMECODE).as_str())
+    );
+    assert_eq!(refresh.field("playing"), Some("false"));
+    assert_eq!(refresh.field("sequence"), Some("1"));
+}
+
+#[test]
+fn test_nothing_recorded_when_nothing_due() {
+    let mut master = MasterSession::new();
+    let capture = capture(|| assert!(master.tick(0).is_empty()));
+    assert!(capture.events().is_empty());
+}
@@ -0,0 +1,597 @@
+use rtp_midi_netsync::applemidi::ClockSync;
+use rtp_midi_netsync::midi::{MidiEvent, MmcCommand, MmcSpeed};
+use rtp_midi_netsync::mtc::{
+    smpte_to_quarter_frames, smpte_to_us, us_to_smpte, us_to_smpte_pulled, FrameRate, MtcFullFrame,
+    Pull,
+};
+use rtp_midi_netsync::netsync::{slave_netsync_flow, PositionTracker, SyncState, TrackerConfig};
+
+/// One quarter frame at 30fps: 1/120 s
+const QF_PERIOD_US: f64 = 1_000_000.0 / 120.0;
+
+fn smpte(hours: u8, minutes: u8, seconds: u8, frames: u8) -> MtcFullFrame {
+    MtcFullFrame {
+        hours,
+        minutes,
+        seconds,
+        frames,
+    }
+}
+
+fn locate(tc: &MtcFullFrame) -> MidiEvent {
+    MidiEvent::Mmc(MmcCommand::Locate {
+        hour: tc.hours,
+        minute: tc.minutes,
+        second: tc.seconds,
+        frame: tc.frames,
+        subframe: 0,
+    })
+}
+
+fn quarter_frame_events(tc: &MtcFullFrame) -> Vec<MidiEvent> {
+    smpte_to_quarter_frames(tc)
+        .iter()
+        .map(|qf| MidiEvent::MtcQuarter {
+            msg_type: qf.frame_type,
+            value: qf.value,
+        })
+        .collect()
+}
+
+#[test]
+fn test_no_position_before_first_update() {
+    let mut tracker = PositionTracker::new();
+    assert_eq!(tracker.position_at(0), None);
+
+    // Transport commands alone carry no position
+    tracker
+        .apply_event(&MidiEvent::Mmc(MmcCommand::Play), 1_000)
+        .unwrap();
+    assert!(tracker.is_playing());
+    assert_eq!(tracker.position_at(2_000), None);
+}
+
+#[test]
+fn test_play_quarter_frames_stop_locate_lifecycle() {
+    let mut tracker = PositionTracker::new();
+
+    // Locate parks the transport at 00:01:00:00
+    tracker.apply_event(&locate(&smpte(0, 1, 0, 0)), 0).unwrap();
+    assert_eq!(tracker.position_at(500_000), Some(60_000_000));
+
+    // Play starts extrapolating from the parked position
+    tracker
+        .apply_event(&MidiEvent::Mmc(MmcCommand::Play), 1_000)
+        .unwrap();
+    assert_eq!(tracker.position_at(1_000), Some(60_000_000));
+    assert_eq!(tracker.position_at(11_000), Some(60_010_000));
+
+    // A quarter-frame cycle re-anchors the estimate once piece 7 arrives
+    let tc = smpte(0, 1, 0, 2);
+    let events = quarter_frame_events(&tc);
+    for (i, event) in events.iter().enumerate() {
+        tracker
+            .apply_event(event, 50_000 + i as u64 * 8_333)
+            .unwrap();
+    }
+    let arrival = 50_000 + 7 * 8_333;
+    let anchored = smpte_to_us(&tc) + 58_333;
+    assert_eq!(tracker.position_at(arrival), Some(anchored));
+    assert_eq!(tracker.position_at(arrival + 1_000), Some(anchored + 1_000));
+
+    // Stop freezes the estimate
+    tracker
+        .apply_event(&MidiEvent::Mmc(MmcCommand::Stop), arrival + 100_000)
+        .unwrap();
+    assert!(!tracker.is_playing());
+    assert_eq!(
+        tracker.position_at(arrival + 100_000),
+        Some(anchored + 100_000)
+    );
+    assert_eq!(tracker.position_at(10_000_000), Some(anchored + 100_000));
+
+    // Locate jumps while stopped
+    tracker
+        .apply_event(&locate(&smpte(0, 0, 10, 0)), 11_000_000)
+        .unwrap();
+    assert_eq!(tracker.position_at(12_000_000), Some(10_000_000));
+}
+
+#[test]
+fn test_full_frame_jumps_while_playing() {
+    let mut tracker = PositionTracker::new();
+    tracker.apply_event(&locate(&smpte(0, 0, 0, 0)), 0).unwrap();
+    tracker
+        .apply_event(&MidiEvent::Mmc(MmcCommand::Play), 0)
+        .unwrap();
+
+    let full = MidiEvent::MtcFull {
+        hour: 1,
+        minute: 0,
+        second: 0,
+        frame: 0,
+    };
+    tracker.apply_event(&full, 500_000).unwrap();
+    assert_eq!(tracker.position_at(500_000), Some(3_600_000_000));
+    assert_eq!(tracker.position_at(600_000), Some(3_600_100_000));
+}
+
+#[test]
+fn test_remote_event_uses_clock_offset() {
+    // The master's clock runs 5 s behind the slave's
+    let mut master_clock = ClockSync::new(1);
+    let mut slave_clock = ClockSync::new(2);
+    let ck0 = master_clock.start(1_000_000);
+    let ck1 = slave_clock.handle(&ck0, 6_001_000).unwrap();
+    let ck2 = master_clock.handle(&ck1, 1_002_000).unwrap();
+    slave_clock.handle(&ck2, 6_002_000);
+    let offset_us = slave_clock.offset_us().unwrap();
+    assert_eq!(offset_us, -5_000_000);
+
+    let mut tracker = PositionTracker::new();
+    tracker
+        .apply_remote_event(&MidiEvent::Mmc(MmcCommand::Play), 500_000, offset_us)
+        .unwrap();
+    let full = MidiEvent::MtcFull {
+        hour: 0,
+        minute: 1,
+        second: 0,
+        frame: 0,
+    };
+    tracker
+        .apply_remote_event(&full, 1_000_000, offset_us)
+        .unwrap();
+
+    // Anchored at the send time on the local clock, whenever the packets arrived
+    assert_eq!(tracker.position_at(6_000_000), Some(60_000_000));
+    assert_eq!(tracker.position_at(6_030_000), Some(60_030_000));
+}
+
+#[test]
+fn test_extrapolation_accuracy_over_one_second() {
+    // Master starts rolling from 00:10:00:00 at local time 1s and sends
+    // quarter frames every 1/120 s, as a real MTC generator would
+    let start_us = smpte_to_us(&smpte(0, 10, 0, 0));
+    let local_start_us = 1_000_000u64;
+
+    let mut tracker = PositionTracker::new();
+    tracker
+        .apply_event(&locate(&smpte(0, 10, 0, 0)), local_start_us - 100_000)
+        .unwrap();
+    tracker
+        .apply_event(&MidiEvent::Mmc(MmcCommand::Play), local_start_us)
+        .unwrap();
+
+    let mut max_error = 0u64;
+    for piece in 0..120u64 {
+        let elapsed_us = (piece as f64 * QF_PERIOD_US).round() as u64;
+        let local_us = local_start_us + elapsed_us;
+
+        // Each cycle of 8 pieces encodes the timecode at which its piece 0 was sent
+        let cycle_tc = us_to_smpte(start_us + ((piece / 8) as f64 * 8.0 * QF_PERIOD_US) as u64);
+        let qf = smpte_to_quarter_frames(&cycle_tc)[(piece % 8) as usize];
+        tracker
+            .apply_event(
+                &MidiEvent::MtcQuarter {
+                    msg_type: qf.frame_type,
+                    value: qf.value,
+                },
+                local_us,
+            )
+            .unwrap();
+
+        // Sample halfway to the next quarter frame
+        let sample_us = local_us + 4_000;
+        let true_us = start_us + elapsed_us + 4_000;
+        let estimate = tracker.position_at(sample_us).unwrap();
+        max_error = max_error.max(estimate.abs_diff(true_us));
+    }
+
+    // Well below one frame (33.3 ms) once quarter-frame latency is compensated
+    assert!(max_error <= 1_000, "max error {} us", max_error);
+}
+
+// === Stale Sync Detection Tests ===
+
+fn playing_tracker(config: TrackerConfig) -> PositionTracker {
+    let mut tracker = PositionTracker::with_config(config);
+    tracker.apply_event(&locate(&smpte(0, 1, 0, 0)), 0).unwrap();
+    tracker
+        .apply_event(&MidiEvent::Mmc(MmcCommand::Play), 0)
+        .unwrap();
+    tracker
+}
+
+#[test]
+fn test_sync_state_unlocked_without_position() {
+    let tracker = PositionTracker::new();
+    assert_eq!(tracker.sync_state_at(0), SyncState::Unlocked);
+}
+
+#[test]
+fn test_sync_state_stale_after_missed_quarter_frames() {
+    let tracker = playing_tracker(TrackerConfig::default());
+
+    // Default: 24 quarter frames at 30fps
+    let timeout_us = FrameRate::Fps30.quarter_frames_to_us(24);
+    assert_eq!(tracker.sync_state_at(timeout_us), SyncState::Locked);
+    assert_eq!(tracker.sync_state_at(timeout_us + 1), SyncState::Lost);
+
+    // The position is still extrapolated while stale
+    assert_eq!(
+        tracker.position_at(timeout_us + 1),
+        Some(60_000_000 + timeout_us + 1)
+    );
+}
+
+#[test]
+fn test_sync_state_recovers_when_updates_resume() {
+    let mut tracker = playing_tracker(TrackerConfig::default());
+    assert_eq!(tracker.sync_state_at(500_000), SyncState::Lost);
+
+    // A single quarter frame proves the master is alive again
+    let event = quarter_frame_events(&smpte(0, 1, 0, 14))[0].clone();
+    tracker.apply_event(&event, 500_000).unwrap();
+    assert_eq!(tracker.sync_state_at(500_000), SyncState::Locked);
+    assert_eq!(tracker.sync_state_at(600_000), SyncState::Locked);
+
+    // A Full Frame does too
+    assert_eq!(tracker.sync_state_at(800_000), SyncState::Lost);
+    let full = MidiEvent::MtcFull {
+        hour: 0,
+        minute: 1,
+        second: 0,
+        frame: 24,
+    };
+    tracker.apply_event(&full, 800_000).unwrap();
+    assert_eq!(tracker.sync_state_at(800_000), SyncState::Locked);
+}
+
+#[test]
+fn test_sync_state_stopped_transport_never_stale() {
+    let mut tracker = playing_tracker(TrackerConfig::default());
+    tracker
+        .apply_event(&MidiEvent::Mmc(MmcCommand::Stop), 10_000)
+        .unwrap();
+    assert_eq!(tracker.sync_state_at(60_000_000), SyncState::Locked);
+}
+
+#[test]
+fn test_sync_state_timeout_follows_frame_rate() {
+    let config = TrackerConfig {
+        frame_rate: FrameRate::Fps24,
+        stale_after_quarter_frames: Some(24),
+        freewheel_frames: 0,
+        pull: Pull::None,
+    };
+    let tracker_24 = playing_tracker(config);
+    let tracker_30 = playing_tracker(TrackerConfig::default());
+
+    // 24 quarter frames last 200 ms at 30fps but 250 ms at 24fps
+    assert_eq!(tracker_30.sync_state_at(220_000), SyncState::Lost);
+    assert_eq!(tracker_24.sync_state_at(220_000), SyncState::Locked);
+    assert_eq!(tracker_24.sync_state_at(250_001), SyncState::Lost);
+}
+
+#[test]
+fn test_sync_state_detection_disabled() {
+    let tracker = playing_tracker(TrackerConfig {
+        frame_rate: FrameRate::Fps30,
+        stale_after_quarter_frames: None,
+        freewheel_frames: 0,
+        pull: Pull::None,
+    });
+    assert_eq!(tracker.sync_state_at(3_600_000_000), SyncState::Locked);
+}
+
+// === Freewheel Tests ===
+
+/// Stops updates at 1s after 1s of regular quarter frames, then returns the
+/// tracker and the arrival time of the last quarter frame.
+fn tracker_after_dropout_start(freewheel_frames: u32) -> (PositionTracker, u64) {
+    let mut tracker = playing_tracker(TrackerConfig {
+        frame_rate: FrameRate::Fps30,
+        stale_after_quarter_frames: Some(8),
+        freewheel_frames,
+        pull: Pull::None,
+    });
+    let mut last_us = 0;
+    for piece in 0..120u64 {
+        last_us = FrameRate::Fps30.quarter_frames_to_us(piece);
+        let tc = us_to_smpte(60_000_000 + FrameRate::Fps30.quarter_frames_to_us(piece / 8 * 8));
+        let event = quarter_frame_events(&tc)[(piece % 8) as usize].clone();
+        tracker.apply_event(&event, last_us).unwrap();
+    }
+    (tracker, last_us)
+}
+
+#[test]
+fn test_freewheel_dropout_shorter_than_window() {
+    let (mut tracker, last_us) = tracker_after_dropout_start(30);
+    let timeout_us = FrameRate::Fps30.quarter_frames_to_us(8);
+    let window_us = FrameRate::Fps30.quarter_frames_to_us(4 * 30);
+
+    // Half a second of silence: past the timeout, inside the freewheel window
+    let resume_us = last_us + timeout_us + window_us / 2;
+    assert_eq!(
+        tracker.sync_state_at(last_us + timeout_us),
+        SyncState::Locked
+    );
+    assert_eq!(
+        tracker.sync_state_at(last_us + timeout_us + 1),
+        SyncState::Freewheeling
+    );
+    assert_eq!(tracker.sync_state_at(resume_us), SyncState::Freewheeling);
+
+    // Freewheeling keeps extrapolating
+    let before = tracker.position_at(last_us).unwrap();
+    assert_eq!(
+        tracker.position_at(resume_us),
+        Some(before + resume_us - last_us)
+    );
+
+    tracker
+        .apply_event(
+            &MidiEvent::MtcFull {
+                hour: 0,
+                minute: 1,
+                second: 1,
+                frame: 0,
+            },
+            resume_us,
+        )
+        .unwrap();
+    assert_eq!(tracker.sync_state_at(resume_us), SyncState::Locked);
+}
+
+#[test]
+fn test_freewheel_dropout_equal_to_window() {
+    let (tracker, last_us) = tracker_after_dropout_start(30);
+    let end_us = last_us
+        + FrameRate::Fps30.quarter_frames_to_us(8)
+        + FrameRate::Fps30.quarter_frames_to_us(4 * 30);
+
+    assert_eq!(tracker.sync_state_at(end_us), SyncState::Freewheeling);
+    assert_eq!(tracker.sync_state_at(end_us + 1), SyncState::Lost);
+}
+
+#[test]
+fn test_freewheel_dropout_longer_than_window() {
+    let (mut tracker, last_us) = tracker_after_dropout_start(30);
+    let outage_end_us = last_us + 5_000_000;
+    assert_eq!(tracker.sync_state_at(outage_end_us), SyncState::Lost);
+
+    // Recovery still happens automatically once updates resume
+    let tc = us_to_smpte(tracker.position_at(outage_end_us).unwrap());
+    for (i, event) in quarter_frame_events(&tc).iter().enumerate() {
+        tracker
+            .apply_event(
+                event,
+                outage_end_us + FrameRate::Fps30.quarter_frames_to_us(i as u64),
+            )
+            .unwrap();
+        assert_eq!(
+            tracker.sync_state_at(outage_end_us + FrameRate::Fps30.quarter_frames_to_us(i as u64)),
+            SyncState::Locked
+        );
+    }
+}
+
+#[test]
+fn test_freewheel_window_scales_with_frame_rate() {
+    let config = |frame_rate| TrackerConfig {
+        frame_rate,
+        stale_after_quarter_frames: Some(8),
+        freewheel_frames: 25,
+        pull: Pull::None,
+    };
+    let tracker_25 = playing_tracker(config(FrameRate::Fps25));
+    let tracker_30 = playing_tracker(config(FrameRate::Fps30));
+
+    // 8 quarter frames + 25 frames: 1.08 s at 25fps, ~0.9 s at 30fps
+    assert_eq!(tracker_25.sync_state_at(1_000_000), SyncState::Freewheeling);
+    assert_eq!(tracker_30.sync_state_at(1_000_000), SyncState::Lost);
+    assert_eq!(tracker_25.sync_state_at(1_080_000), SyncState::Freewheeling);
+    assert_eq!(tracker_25.sync_state_at(1_080_001), SyncState::Lost);
+}
+
+#[test]
+fn test_extended_transport_commands() {
+    let mut tracker = PositionTracker::new();
+    tracker.apply_event(&locate(&smpte(0, 1, 0, 0)), 0).unwrap();
+
+    // Deferred Play rolls from the located position like Play
+    tracker
+        .apply_event(&MidiEvent::Mmc(MmcCommand::DeferredPlay), 1_000)
+        .unwrap();
+    assert!(tracker.is_playing());
+    assert_eq!(tracker.position_at(11_000), Some(60_010_000));
+
+    // Recording leaves the transport running
+    tracker
+        .apply_event(&MidiEvent::Mmc(MmcCommand::RecordStrobe), 11_000)
+        .unwrap();
+    assert!(tracker.is_playing());
+
+    // Pause freezes the estimate like Stop
+    tracker
+        .apply_event(&MidiEvent::Mmc(MmcCommand::Pause), 21_000)
+        .unwrap();
+    assert!(!tracker.is_playing());
+    assert_eq!(tracker.position_at(1_000_000), Some(60_020_000));
+}
+
+#[test]
+fn test_locate_deferred_play_cue() {
+    let mut tracker = PositionTracker::new();
+    tracker
+        .apply_event(&locate(&smpte(0, 0, 10, 0)), 0)
+        .unwrap();
+    tracker
+        .apply_event(&MidiEvent::Mmc(MmcCommand::Play), 0)
+        .unwrap();
+    assert_eq!(tracker.cued_position(), None);
+
+    // Cued while rolling: Deferred Play starts from the target when it arrives,
+    // whatever the estimate advanced to while the slave settled
+    tracker
+        .apply_event(&locate(&smpte(0, 1, 0, 0)), 1_000_000)
+        .unwrap();
+    assert_eq!(tracker.cued_position(), Some(60_000_000));
+    let full_frame = MidiEvent::MtcFull {
+        hour: 0,
+        minute: 1,
+        second: 0,
+        frame: 0,
+    };
+    tracker.apply_event(&full_frame, 1_200_000).unwrap();
+    assert_eq!(tracker.cued_position(), Some(60_000_000));
+    tracker
+        .apply_event(&MidiEvent::Mmc(MmcCommand::DeferredPlay), 1_500_000)
+        .unwrap();
+    assert!(tracker.is_playing());
+    assert_eq!(tracker.cued_position(), None);
+    assert_eq!(tracker.position_at(1_510_000), Some(60_010_000));
+
+    // Any other position or transport change supersedes the cue
+    let superseding = [
+        MidiEvent::MtcFull {
+            hour: 0,
+            minute: 2,
+            second: 0,
+            frame: 0,
+        },
+        MidiEvent::Mmc(MmcCommand::Play),
+        MidiEvent::Mmc(MmcCommand::Stop),
+    ];
+    for event in &superseding {
+        tracker
+            .apply_event(&locate(&smpte(0, 1, 0, 0)), 2_000_000)
+            .unwrap();
+        tracker.apply_event(event, 2_000_000).unwrap();
+        assert_eq!(tracker.cued_position(), None, "{event:?}");
+    }
+
+    // Without a cue, Deferred Play rolls from the current estimate like Play
+    tracker.apply_event(&full_frame, 3_000_000).unwrap();
+    tracker
+        .apply_event(&MidiEvent::Mmc(MmcCommand::DeferredPlay), 3_500_000)
+        .unwrap();
+    assert_eq!(tracker.position_at(3_510_000), Some(60_010_000));
+}
+
+#[test]
+fn test_variable_play_extrapolates_only_at_play_speed() {
+    let mut tracker = PositionTracker::new();
+    tracker.apply_event(&locate(&smpte(0, 1, 0, 0)), 0).unwrap();
+
+    let variable_play = |speed| {
+        MidiEvent::Mmc(MmcCommand::VariablePlay {
+            speed: MmcSpeed::from_f32(speed),
+        })
+    };
+    tracker.apply_event(&variable_play(1.0), 1_000).unwrap();
+    assert!(tracker.is_playing());
+    assert_eq!(tracker.position_at(11_000), Some(60_010_000));
+
+    // Half speed cannot be extrapolated, so the estimate waits for time updates
+    tracker.apply_event(&variable_play(0.5), 11_000).unwrap();
+    assert!(!tracker.is_playing());
+    assert_eq!(tracker.position_at(1_000_000), Some(60_010_000));
+}
+
+#[test]
+fn test_locate_search_play_pre_roll() {
+    // Locate to 01:00:00:00, Search forward at 10x while the deck
+    // pre-rolls, then Play once it is parked, as a deck sends them
+    let payloads: [&[u8]; 3] = [
+        &[
+            0x0D, 0xF0, 0x7F, 0x7F, 0x06, 0x44, 0x06, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0xF7,
+        ],
+        &[
+            0x0A, 0xF0, 0x7F, 0x7F, 0x06, 0x46, 0x03, 0x0D, 0x00, 0x00, 0xF7,
+        ],
+        &[0x06, 0xF0, 0x7F, 0x7F, 0x06, 0x02, 0xF7],
+    ];
+    let events: Vec<MidiEvent> = payloads
+        .iter()
+        .map(|payload| slave_netsync_flow(payload).unwrap())
+        .collect();
+    assert_eq!(
+        events[1],
+        MidiEvent::Mmc(MmcCommand::Search {
+            speed: MmcSpeed::from_f32(10.0),
+        })
+    );
+
+    let mut tracker = PositionTracker::new();
+    tracker.apply_event(&events[0], 0).unwrap();
+    let located = tracker.position_at(0);
+    assert_eq!(located, Some(3_600_000_000));
+
+    // Moving but not playing: not taken for a stop, but not extrapolated either
+    tracker.apply_event(&events[1], 100_000).unwrap();
+    assert!(!tracker.is_playing());
+    assert_eq!(tracker.position_at(2_000_000), located);
+
+    tracker.apply_event(&events[2], 2_000_000).unwrap();
+    assert!(tracker.is_playing());
+    assert_eq!(
+        tracker.position_at(2_010_000),
+        located.map(|position| position + 10_000)
+    );
+}
+
+// === Pull Tests ===
+
+/// Plays from 00:00:00:00 at local time 0 and returns the position estimated
+/// every minute of local time for an hour.
+fn positions_over_an_hour(pull: Pull) -> Vec<(u64, u64)> {
+    let mut tracker = PositionTracker::with_config(TrackerConfig {
+        pull,
+        ..TrackerConfig::default()
+    });
+    tracker
+        .apply_event(
+            &MidiEvent::Mmc(MmcCommand::Locate {
+                hour: 0,
+                minute: 0,
+                second: 0,
+                frame: 0,
+                subframe: 0,
+            }),
+            0,
+        )
+        .unwrap();
+    tracker
+        .apply_event(&MidiEvent::Mmc(MmcCommand::Play), 0)
+        .unwrap();
+    (1..=60)
+        .map(|minute| {
+            let now_us = minute * 60_000_000;
+            (now_us, tracker.position_at(now_us).unwrap())
+        })
+        .collect()
+}
+
+#[test]
+fn test_pull_down_hour_simulation() {
+    let frame_us = FrameRate::Fps30.frame_duration_us();
+
+    // A master whose timecode runs at 29.97 against the local clock
+    for (now_us, position_us) in positions_over_an_hour(Pull::Down01) {
+        let master_us = (now_us as u128 * 1000 / 1001) as u64;
+        assert!(master_us.abs_diff(position_us) < frame_us);
+        assert_eq!(
+            us_to_smpte(position_us),
+            us_to_smpte_pulled(now_us, FrameRate::Fps30, Pull::Down01)
+        );
+    }
+
+    // Without the pull the estimate runs 3.6 s ahead after an hour
+    let (now_us, position_us) = positions_over_an_hour(Pull::None)[59];
+    let master_us = (now_us as u128 * 1000 / 1001) as u64;
+    let drift_us = position_us - master_us;
+    assert_eq!(drift_us, 3_596_404);
+    assert!(drift_us > 100 * frame_us);
+}
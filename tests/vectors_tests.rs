@@ -0,0 +1,83 @@
+use std::collections::HashSet;
+
+use rtp_midi_netsync::midi::{MidiEvent, MidiEventRef, MmcCommand};
+use rtp_midi_netsync::mtc::{FrameRate, MtcFullFrame, MtcQuarterFrame, QuarterFrameAssembler};
+use rtp_midi_netsync::netsync::{slave_netsync_flow, slave_netsync_flow_ref};
+use rtp_midi_netsync::testing::vectors::{MTC_GENERATOR_CYCLE, VECTORS};
+
+/// Timecode of a Full Frame or Locate event, without the rate bits of its hours byte
+fn timecode(event: &MidiEvent) -> Option<MtcFullFrame> {
+    match *event {
+        MidiEvent::MtcFull {
+            hour,
+            minute,
+            second,
+            frame,
+        }
+        | MidiEvent::Mmc(MmcCommand::Locate {
+            hour,
+            minute,
+            second,
+            frame,
+            ..
+        }) => Some(MtcFullFrame {
+            hours: hour & 0x1F,
+            minutes: minute,
+            seconds: second,
+            frames: frame,
+        }),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_vectors_decode() {
+    for vector in VECTORS {
+        let event = slave_netsync_flow(vector.payload)
+            .unwrap_or_else(|err| panic!("{}: {:?}", vector.name, err));
+        assert_eq!(event, vector.event.to_owned_event(), "{}", vector.name);
+        assert_eq!(
+            slave_netsync_flow_ref(vector.payload),
+            Ok(vector.event.clone()),
+            "{}",
+            vector.name
+        );
+        assert_eq!(timecode(&event), vector.timecode, "{}", vector.name);
+        if let MidiEvent::MtcFull { hour, .. } = event {
+            assert!(
+                FrameRate::from_mtc_code(hour >> 5).is_some(),
+                "{}",
+                vector.name
+            );
+        }
+    }
+}
+
+#[test]
+fn test_vector_names_unique() {
+    let mut names = HashSet::new();
+    for vector in VECTORS {
+        assert!(names.insert(vector.name), "duplicate {}", vector.name);
+    }
+}
+
+#[test]
+fn test_mtc_generator_cycle() {
+    let mut assembler = QuarterFrameAssembler::new();
+    let mut assembled = None;
+    for (piece, payload) in MTC_GENERATOR_CYCLE.payloads.iter().enumerate() {
+        let Ok(MidiEventRef::MtcQuarter { msg_type, value }) = slave_netsync_flow_ref(payload)
+        else {
+            panic!("piece {}: {:02X?}", piece, payload);
+        };
+        assert_eq!(msg_type as usize, piece);
+        assembled = assembler
+            .feed(MtcQuarterFrame {
+                frame_type: msg_type,
+                value,
+            })
+            .unwrap();
+    }
+    assert_eq!(assembled, Some(MTC_GENERATOR_CYCLE.timecode));
+    assert_eq!(assembler.frame_rate(), MTC_GENERATOR_CYCLE.rate);
+}
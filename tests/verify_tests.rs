@@ -0,0 +1,266 @@
+use rtp_midi_netsync::midi::{MidiEvent, MmcCommand, RealtimeMessage};
+use rtp_midi_netsync::mtc::FrameRate;
+use rtp_midi_netsync::netsync::{
+    master_netsync_flow, slave_netsync_flow, verify_payload, FindingKind, Severity,
+};
+
+/// Broken payloads seen from third-party senders, with the finding each must produce
+fn corpus() -> Vec<(&'static str, Vec<u8>, FindingKind, usize)> {
+    vec![
+        ("empty", vec![], FindingKind::EmptyPayload, 0),
+        (
+            "b flag without second octet",
+            vec![0x80],
+            FindingKind::TruncatedLongHeader,
+            0,
+        ),
+        (
+            "two-octet header",
+            vec![0x80, 0x02, 0xF1, 0x23],
+            FindingKind::LongHeader { len: 2 },
+            0,
+        ),
+        ("len zero", vec![0x00, 0xF8], FindingKind::EmptyMidiList, 0),
+        (
+            "len beyond payload",
+            vec![0x0A, 0xF0, 0x7F, 0x7F, 0x01, 0x01, 0x01],
+            FindingKind::TruncatedMidiList {
+                declared: 10,
+                available: 6,
+            },
+            0,
+        ),
+        (
+            "len shorter than midi list",
+            vec![
+                0x0A, 0xF0, 0x7F, 0x7F, 0x01, 0x01, 0x01, 0x02, 0x03, 0x04, 0xF7, 0x00, 0x00, 0x00,
+            ],
+            FindingKind::TrailingBytes {
+                declared: 10,
+                available: 13,
+            },
+            11,
+        ),
+        (
+            "running status",
+            vec![0x02, 0x3C, 0x7F],
+            FindingKind::MissingStatusByte { byte: 0x3C },
+            1,
+        ),
+        (
+            "device id not broadcast",
+            vec![0x06, 0xF0, 0x7F, 0x10, 0x06, 0x02, 0xF7],
+            FindingKind::NonBroadcastDeviceId { device_id: 0x10 },
+            3,
+        ),
+        (
+            "frame out of range",
+            vec![
+                0x0A, 0xF0, 0x7F, 0x7F, 0x01, 0x01, 0x01, 0x02, 0x03, 30, 0xF7,
+            ],
+            FindingKind::ValueOutOfRange {
+                field: "frames",
+                value: 30,
+                max: 29,
+            },
+            9,
+        ),
+        (
+            "frame out of range at 25fps",
+            vec![
+                0x0A, 0xF0, 0x7F, 0x7F, 0x01, 0x01, 0x21, 0x02, 0x03, 25, 0xF7,
+            ],
+            FindingKind::ValueOutOfRange {
+                field: "frames",
+                value: 25,
+                max: 24,
+            },
+            9,
+        ),
+        (
+            "rate bits in hour",
+            vec![
+                0x0A, 0xF0, 0x7F, 0x7F, 0x01, 0x01, 0x61, 0x02, 0x03, 0x04, 0xF7,
+            ],
+            FindingKind::RateBitsInHour {
+                rate: FrameRate::Fps30,
+            },
+            6,
+        ),
+        (
+            "minutes out of range in locate",
+            vec![
+                0x0D, 0xF0, 0x7F, 0x7F, 0x06, 0x44, 0x06, 0x01, 0x01, 60, 0x03, 0x04, 0x00, 0xF7,
+            ],
+            FindingKind::ValueOutOfRange {
+                field: "minutes",
+                value: 60,
+                max: 59,
+            },
+            9,
+        ),
+        (
+            "sysex without end",
+            vec![0x05, 0xF0, 0x7F, 0x7F, 0x06, 0x02],
+            FindingKind::MissingSysexEnd,
+            6,
+        ),
+        (
+            "data byte with high bit",
+            vec![0x02, 0xF1, 0x93],
+            FindingKind::DataByteHighBit { byte: 0x93 },
+            2,
+        ),
+        (
+            "truncated song position",
+            vec![0x02, 0xF2, 0x10],
+            FindingKind::TruncatedMessage {
+                message: "Song Position Pointer",
+                expected: 3,
+                available: 2,
+            },
+            1,
+        ),
+        (
+            "truncated full frame",
+            vec![0x08, 0xF0, 0x7F, 0x7F, 0x01, 0x01, 0x01, 0x02, 0xF7],
+            FindingKind::TruncatedMessage {
+                message: "Full Frame",
+                expected: 10,
+                available: 8,
+            },
+            1,
+        ),
+        (
+            "padded full frame",
+            vec![
+                0x0C, 0xF0, 0x7F, 0x7F, 0x01, 0x01, 0x01, 0x02, 0x03, 0x04, 0x11, 0x22, 0xF7,
+            ],
+            FindingKind::PaddedFullFrame { count: 2 },
+            10,
+        ),
+        (
+            "journal flag",
+            vec![0x41, 0xF8],
+            FindingKind::JournalPresent,
+            0,
+        ),
+        (
+            "delta time flag",
+            vec![0x22, 0x00, 0xF8],
+            FindingKind::DeltaTimePresent,
+            0,
+        ),
+        (
+            "padded quarter frame",
+            vec![0x04, 0xF1, 0x23, 0x00, 0x00],
+            FindingKind::ExtraBytes { count: 2 },
+            3,
+        ),
+        (
+            "quarter frame at 25fps",
+            vec![0x02, 0xF1, 0x72],
+            FindingKind::UnusualFrameRate {
+                rate: FrameRate::Fps25,
+            },
+            2,
+        ),
+        (
+            "note on",
+            vec![0x03, 0x90, 0x3C, 0x7F],
+            FindingKind::Unrecognized,
+            1,
+        ),
+    ]
+}
+
+#[test]
+fn test_master_payloads_are_clean() {
+    let events = [
+        MidiEvent::MtcQuarter {
+            msg_type: 7,
+            value: 0x06,
+        },
+        MidiEvent::MtcFull {
+            hour: 23,
+            minute: 59,
+            second: 59,
+            frame: 29,
+        },
+        MidiEvent::Mmc(MmcCommand::Play),
+        MidiEvent::Mmc(MmcCommand::Stop),
+        MidiEvent::Mmc(MmcCommand::Locate {
+            hour: 1,
+            minute: 2,
+            second: 3,
+            frame: 4,
+            subframe: 0,
+        }),
+        MidiEvent::Realtime(RealtimeMessage::Clock),
+        MidiEvent::SongPosition(0x3FFF),
+    ];
+    for event in events {
+        let payload = master_netsync_flow(&event).unwrap();
+        let report = verify_payload(&payload);
+        assert!(report.is_clean(), "{:?}: {}", event, report);
+    }
+}
+
+#[test]
+fn test_corpus_findings() {
+    for (name, payload, kind, offset) in corpus() {
+        let report = verify_payload(&payload);
+        let finding = report
+            .findings
+            .iter()
+            .find(|finding| finding.kind == kind)
+            .unwrap_or_else(|| panic!("{}: expected {:?}, got\n{}", name, kind, report));
+        assert_eq!(finding.offset, offset, "{}", name);
+        assert_eq!(finding.severity, kind.severity(), "{}", name);
+    }
+}
+
+#[test]
+fn test_slave_rejections_are_errors() {
+    for (name, payload, _, _) in corpus() {
+        if slave_netsync_flow(&payload).is_err() {
+            assert!(verify_payload(&payload).has_errors(), "{}", name);
+        }
+    }
+}
+
+#[test]
+fn test_report_display() {
+    let report = verify_payload(&[
+        0x0A, 0xF0, 0x7F, 0x10, 0x01, 0x01, 0x01, 0x02, 0x03, 30, 0xF7,
+    ]);
+    assert_eq!(report.worst(), Some(Severity::Error));
+    assert_eq!(
+        report.to_string(),
+        "[warning] byte 3: device ID 0x10 instead of broadcast, passed through\n\
+         [error] byte 9: frames value 30 exceeds maximum 29"
+    );
+    assert_eq!(verify_payload(&[0x01, 0xF8]).to_string(), "no findings");
+}
+
+#[test]
+fn test_never_panics_on_arbitrary_input() {
+    let bytes = [
+        0x00, 0x01, 0x0F, 0x7F, 0x80, 0x8F, 0xF0, 0xF1, 0xF2, 0xF7, 0xFF,
+    ];
+    for &a in &bytes {
+        for &b in &bytes {
+            for &c in &bytes {
+                for &d in &bytes {
+                    let payload = [a, b, c, d];
+                    for end in 0..=payload.len() {
+                        let report = verify_payload(&payload[..end]);
+                        if slave_netsync_flow(&payload[..end]).is_err() {
+                            assert!(report.has_errors(), "{:02X?}", &payload[..end]);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
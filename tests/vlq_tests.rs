@@ -0,0 +1,118 @@
+use rtp_midi_netsync::error::ParseError;
+use rtp_midi_netsync::util::vlq::{
+    decode, decode_strict, encode, encoded_len, MAX_LENGTH, MAX_VALUE,
+};
+
+/// Encodes `value` and checks that both decoders return it with the same length
+fn assert_roundtrip(value: u32) {
+    let mut buf = [0xAA; MAX_LENGTH + 1];
+    let len = encode(value, &mut buf);
+    assert_eq!(len, encoded_len(value), "0x{:X}", value);
+    assert_eq!(buf[len], 0xAA, "0x{:X} wrote past its length", value);
+    assert_eq!(decode(&buf), Ok((value, len)), "0x{:X}", value);
+    assert_eq!(
+        decode_strict(&buf[..len]),
+        Ok((value, len)),
+        "0x{:X}",
+        value
+    );
+}
+
+// === Encoding ===
+
+#[test]
+fn test_boundary_values() {
+    let cases: [(u32, &[u8]); 8] = [
+        (0x00, &[0x00]),
+        (0x40, &[0x40]),
+        (0x7F, &[0x7F]),
+        (0x80, &[0x81, 0x00]),
+        (0x2000, &[0xC0, 0x00]),
+        (0x3FFF, &[0xFF, 0x7F]),
+        (0x4000, &[0x81, 0x80, 0x00]),
+        (MAX_VALUE, &[0xFF, 0xFF, 0xFF, 0x7F]),
+    ];
+    for (value, bytes) in cases {
+        let mut buf = [0; MAX_LENGTH];
+        let len = encode(value, &mut buf);
+        assert_eq!(&buf[..len], bytes, "0x{:X}", value);
+        assert_eq!(decode(bytes), Ok((value, bytes.len())));
+        assert_eq!(decode_strict(bytes), Ok((value, bytes.len())));
+    }
+}
+
+#[test]
+fn test_roundtrip_u28_range() {
+    // Every value up to three bytes, then a sweep of the full range with a prime step
+    for value in 0..=0x4000 {
+        assert_roundtrip(value);
+    }
+    for value in (0..=MAX_VALUE).step_by(0x1FF) {
+        assert_roundtrip(value);
+    }
+    for boundary in [0x80, 0x4000, 0x20_0000] {
+        for value in boundary - 2..boundary + 2 {
+            assert_roundtrip(value);
+        }
+    }
+    assert_roundtrip(MAX_VALUE);
+}
+
+#[test]
+#[should_panic(expected = "exceeds 28 bits")]
+fn test_encode_rejects_values_above_28_bits() {
+    encode(MAX_VALUE + 1, &mut [0; 8]);
+}
+
+// === Decoding ===
+
+#[test]
+fn test_decode_ignores_following_bytes() {
+    assert_eq!(decode(&[0x81, 0x00, 0x90, 0x3C]), Ok((0x80, 2)));
+    assert_eq!(decode(&[0x00, 0xFF]), Ok((0, 1)));
+}
+
+#[test]
+fn test_decode_errors() {
+    assert_eq!(decode(&[]), Err(ParseError::EmptyBuffer));
+    // Continuation bit set on the last byte
+    assert_eq!(
+        decode(&[0x81, 0x80]),
+        Err(ParseError::BufferTooSmall {
+            requested: 3,
+            available: 2,
+        })
+    );
+    // A fifth byte would be needed
+    assert_eq!(
+        decode(&[0xFF, 0xFF, 0xFF, 0xFF, 0x7F]),
+        Err(ParseError::InvalidMidiData {
+            position: 3,
+            byte: 0xFF,
+        })
+    );
+}
+
+#[test]
+fn test_strict_rejects_leading_zeros() {
+    for bytes in [
+        &[0x80, 0x00][..],
+        &[0x80, 0x81, 0x00],
+        &[0x80, 0x80, 0x80, 0x7F],
+    ] {
+        let (value, len) = decode(bytes).unwrap();
+        assert_eq!(len, bytes.len());
+        assert_eq!(
+            decode_strict(bytes),
+            Err(ParseError::InvalidMidiData {
+                position: 0,
+                byte: 0x80,
+            }),
+            "{:02X?} = 0x{:X}",
+            bytes,
+            value
+        );
+    }
+    // Zero groups after the first byte are canonical
+    assert_eq!(decode_strict(&[0x81, 0x80, 0x00]), Ok((0x4000, 3)));
+}
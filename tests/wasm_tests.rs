@@ -0,0 +1,96 @@
+//! Runs in Node with `wasm-bindgen-test-runner` as the cargo runner:
+//!
+//! ```bash
+//! CARGO_TARGET_WASM32_UNKNOWN_UNKNOWN_RUNNER=wasm-bindgen-test-runner \
+//!     cargo test --target wasm32-unknown-unknown --features wasm --test wasm_tests
+//! ```
+#![cfg(all(feature = "wasm", target_arch = "wasm32"))]
+
+use js_sys::{Reflect, Uint8Array};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+use rtp_midi_netsync::wasm::{decode_payload, describe_payload, smpte_to_us, us_to_smpte};
+
+fn property(object: &JsValue, name: &str) -> JsValue {
+    Reflect::get(object, &name.into()).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn test_decode_locate() {
+    let payload = [
+        0x0D, 0xF0, 0x7F, 0x7F, 0x06, 0x44, 0x06, 0x01, 0x61, 0x3B, 0x3B, 0x1D, 0x00, 0xF7,
+    ];
+    let event = decode_payload(&payload).unwrap();
+    assert_eq!(property(&event, "type"), "Mmc");
+
+    let command = property(&event, "command");
+    assert_eq!(property(&command, "type"), "Locate");
+    assert_eq!(property(&command, "hour"), 0x61);
+    assert_eq!(property(&command, "minute"), 59);
+    assert_eq!(property(&command, "second"), 59);
+    assert_eq!(property(&command, "frame"), 29);
+    assert_eq!(property(&command, "subframe"), 0);
+}
+
+#[wasm_bindgen_test]
+fn test_decode_other_bytes() {
+    let event = decode_payload(&[0x03, 0x90, 0x40, 0x7F]).unwrap();
+    assert_eq!(property(&event, "type"), "Other");
+    let bytes = Uint8Array::new(&property(&event, "bytes"));
+    assert_eq!(bytes.to_vec(), [0x90, 0x40, 0x7F]);
+}
+
+#[wasm_bindgen_test]
+fn test_decode_manufacturer_sysex() {
+    let event = decode_payload(&[0x06, 0xF0, 0x00, 0x20, 0x33, 0x01, 0xF7]).unwrap();
+    assert_eq!(property(&event, "type"), "ManufacturerSysex");
+    let id = Uint8Array::new(&property(&event, "id"));
+    assert_eq!(id.to_vec(), [0x00, 0x20, 0x33]);
+    let data = Uint8Array::new(&property(&event, "data"));
+    assert_eq!(data.to_vec(), [0x01]);
+}
+
+#[wasm_bindgen_test]
+fn test_decode_identity_reply() {
+    let event = decode_payload(&[
+        0x0F, 0xF0, 0x7E, 0x10, 0x06, 0x02, 0x41, 0x02, 0x01, 0x03, 0x00, 0x01, 0x02, 0x03, 0x04,
+        0xF7,
+    ])
+    .unwrap();
+    assert_eq!(property(&event, "type"), "IdentityReply");
+    let identity = property(&event, "identity");
+    assert_eq!(property(&identity, "device_id"), 0x10);
+    assert_eq!(
+        Uint8Array::new(&property(&identity, "manufacturer")).to_vec(),
+        [0x41]
+    );
+    assert_eq!(property(&identity, "family"), 0x82);
+    assert_eq!(property(&identity, "model"), 0x03);
+    assert_eq!(
+        Uint8Array::new(&property(&identity, "version")).to_vec(),
+        [1, 2, 3, 4]
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_decode_rejects_truncated_payload() {
+    assert!(decode_payload(&[0x05, 0xF0]).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_describe_payload() {
+    assert_eq!(
+        describe_payload(&[0x06, 0xF0, 0x7F, 0x7F, 0x06, 0x02, 0xF7]),
+        "Header 0x06: LEN=6\nMMC Play"
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_timecode_conversions() {
+    assert_eq!(smpte_to_us("00:00:01:00", None).unwrap(), 1_000_000);
+    assert_eq!(us_to_smpte(1_000_000, Some(1)).unwrap(), "00:00:01:00");
+    assert_eq!(us_to_smpte(60_060_000, Some(2)).unwrap(), "00:01:00;02");
+    assert!(smpte_to_us("1:2:3", None).is_err());
+    assert!(us_to_smpte(0, Some(4)).is_err());
+}